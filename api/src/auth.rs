@@ -4,20 +4,51 @@ use argon2::{
 };
 use axum::{
     extract::{Request, State},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
     Json,
 };
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{decode, decode_header, encode, Header, Validation};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{Executor, PgPool, Postgres};
+use utoipa::ToSchema;
+
+use crate::models::RefreshRequest;
+use crate::routes::settings;
+use crate::services::auth_events::{self, AuthEvent, RequestOrigin};
+use crate::services::two_factor::{self, SecondFactor};
+use crate::services::{sessions, signing_keys};
+
+/// Issuer and audience written into every token and required when validating it, so tokens
+/// signed with the same secret for something else are not accepted here
+const JWT_ISSUER: &str = "people-scheduler-api";
+const JWT_AUDIENCE: &str = "people-scheduler";
+
+/// Used when a role has no `jwt_lifetime_hours_{role}` setting
+const DEFAULT_TOKEN_LIFETIME_HOURS: i64 = 24;
+
+/// Seconds of clock difference tolerated on `exp`/`iat` (JWT_LEEWAY_SECONDS, default 60)
+fn get_jwt_leeway() -> u64 {
+    std::env::var("JWT_LEEWAY_SECONDS")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(60)
+}
 
-// JWT secret - in production, use environment variable
-fn get_jwt_secret() -> String {
-    std::env::var("JWT_SECRET")
-        .unwrap_or_else(|_| "people-scheduler-secret-key-change-in-production".to_string())
+/// How long a login lasts for this role, from the `jwt_lifetime_hours_{role}` settings
+pub async fn token_lifetime<'e, E>(executor: E, role: &str) -> Result<Duration, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let hours = settings::get_i64(
+        executor,
+        &format!("jwt_lifetime_hours_{}", role),
+        DEFAULT_TOKEN_LIFETIME_HOURS,
+    )
+    .await?;
+    Ok(Duration::hours(hours.max(1)))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,24 +59,58 @@ pub struct Claims {
     pub person_id: Option<String>, // linked person for servidores
     pub exp: i64,                  // expiration time
     pub iat: i64,                  // issued at
+    pub iss: String,
+    pub aud: String,
+    // jobs a coordinator manages, read from user_job_scopes at login
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub job_ids: Vec<String>,
+    // the login session (user_sessions) the token belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Claims {
+    pub fn is_admin(&self) -> bool {
+        self.role == "admin"
+    }
+
+    pub fn is_coordinator(&self) -> bool {
+        self.role == "coordinator"
+    }
+
+    /// A parent whose login covers the children in `guardian_children`
+    pub fn is_guardian(&self) -> bool {
+        self.role == "guardian"
+    }
+
+    /// Admins reach every job and coordinators the jobs in their scope; servidores and
+    /// guardians none
+    pub fn in_scope(&self, job_id: &str) -> bool {
+        self.is_admin() || (self.is_coordinator() && self.job_ids.iter().any(|j| j == job_id))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct LoginRequest {
     pub username: String,
     pub password: String,
+    /// For accounts with two-factor on: a code from the authenticator app or a recovery code
+    #[serde(default)]
+    pub totp_code: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct LoginResponse {
     pub token: String,
     pub username: String,
     pub role: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub person_id: Option<String>,
+    /// Trade at `POST /login/refresh` for a new token when this one expires
+    pub refresh_token: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ChangePasswordRequest {
     pub current_password: String,
     pub new_password: String,
@@ -80,9 +145,14 @@ pub fn verify_password(password: &str, password_hash: &str) -> bool {
 }
 
 // Generate a JWT token
-pub fn generate_token(user: &User) -> Result<String, jsonwebtoken::errors::Error> {
+pub fn generate_token(
+    user: &User,
+    job_ids: Vec<String>,
+    lifetime: Duration,
+    sid: Option<&str>,
+) -> Result<String, jsonwebtoken::errors::Error> {
     let now = Utc::now();
-    let exp = now + Duration::hours(24);
+    let exp = now + lifetime;
 
     let claims = Claims {
         sub: user.id.to_string(),
@@ -91,30 +161,59 @@ pub fn generate_token(user: &User) -> Result<String, jsonwebtoken::errors::Error
         person_id: user.person_id.clone(),
         exp: exp.timestamp(),
         iat: now.timestamp(),
+        iss: JWT_ISSUER.to_string(),
+        aud: JWT_AUDIENCE.to_string(),
+        job_ids,
+        sid: sid.map(str::to_string),
     };
 
-    encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(get_jwt_secret().as_bytes()),
-    )
+    sign_claims(&claims)
+}
+
+/// Sign with the current key, named in the header so it can be told apart after a rotation
+fn sign_claims(claims: &Claims) -> Result<String, jsonwebtoken::errors::Error> {
+    let (kid, key) = signing_keys::keys().signing_key();
+    let header = Header {
+        kid: Some(kid.to_string()),
+        ..Header::default()
+    };
+    encode(&header, claims, &key)
 }
 
 // Validate a JWT token
 pub fn validate_token(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
-    let token_data = decode::<Claims>(
-        token,
-        &DecodingKey::from_secret(get_jwt_secret().as_bytes()),
-        &Validation::default(),
-    )?;
-    Ok(token_data.claims)
+    let mut validation = Validation::default();
+    validation.leeway = get_jwt_leeway();
+    validation.set_issuer(&[JWT_ISSUER]);
+    validation.set_audience(&[JWT_AUDIENCE]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+
+    // Any active key verifies; a token from a key that was dropped has none left to try
+    let kid = decode_header(token)?.kid;
+    let mut result = Err(jsonwebtoken::errors::ErrorKind::InvalidSignature.into());
+    for key in signing_keys::keys().verifying_keys(kid.as_deref()) {
+        result = decode::<Claims>(token, &key, &validation);
+        if result.is_ok() {
+            break;
+        }
+    }
+    Ok(result?.claims)
 }
 
 // Login endpoint
+#[utoipa::path(
+    post,
+    path = "/login",
+    tag = "auth",
+    responses((status = 200, body = LoginResponse))
+)]
 pub async fn login(
     State(pool): State<PgPool>,
+    headers: HeaderMap,
     Json(request): Json<LoginRequest>,
 ) -> Result<Json<LoginResponse>, (StatusCode, String)> {
+    let origin = RequestOrigin::from_headers(&headers);
+
     // Find user by username
     let user = sqlx::query_as::<_, User>(
         "SELECT id, username, password_hash, role, person_id FROM users WHERE username = $1",
@@ -124,32 +223,163 @@ pub async fn login(
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
+    // Fall back to an old username that is still within its grace period
     let user = match user {
-        Some(u) => u,
-        None => return Err((StatusCode::UNAUTHORIZED, "Invalid credentials".to_string())),
+        Some(u) => Some(u),
+        None => sqlx::query_as::<_, User>(
+            r#"
+            SELECT u.id, u.username, u.password_hash, u.role, u.person_id
+            FROM username_aliases a
+            JOIN users u ON a.user_id = u.id
+            WHERE a.username = $1 AND a.expires_at > NOW()
+            "#,
+        )
+        .bind(&request.username)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
     };
 
-    // Verify password
-    if !verify_password(&request.password, &user.password_hash) {
-        return Err((StatusCode::UNAUTHORIZED, "Invalid credentials".to_string()));
+    // Why the login is refused, if it is
+    let refused = match &user {
+        None => Some("Unknown username"),
+        Some(u) if !verify_password(&request.password, &u.password_hash) => Some("Wrong password"),
+        Some(_) => None,
+    };
+    // With the right password, an account with two-factor on also needs its code
+    let second_factor = match (&user, refused) {
+        (Some(u), None) => two_factor::check_login(&pool, u.id, request.totp_code.as_deref())
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?,
+        _ => SecondFactor::NotEnrolled,
+    };
+    if second_factor == SecondFactor::Missing {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            two_factor::CODE_REQUIRED.to_string(),
+        ));
+    }
+    let refused = refused.or(second_factor.refusal());
+
+    auth_events::record(
+        &pool,
+        &AuthEvent {
+            event: if refused.is_some() {
+                "login_failed"
+            } else {
+                "login"
+            },
+            user_id: user.as_ref().map(|u| u.id),
+            username: Some(&request.username),
+            detail: refused.or(second_factor.detail()),
+        },
+        &origin,
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    match (user, refused, second_factor.refusal()) {
+        (Some(user), None, _) => Ok(Json(issue_login(&pool, user, &origin).await?)),
+        // The password was right, so saying the code was wrong gives nothing away
+        (_, _, Some(reason)) => Err((StatusCode::UNAUTHORIZED, reason.to_string())),
+        _ => Err((StatusCode::UNAUTHORIZED, "Invalid credentials".to_string())),
     }
+}
+
+/// The token and details a successful sign-in answers with, by password or OIDC. Every
+/// sign-in starts a session of its own.
+pub async fn issue_login(
+    pool: &PgPool,
+    user: User,
+    origin: &RequestOrigin,
+) -> Result<LoginResponse, (StatusCode, String)> {
+    let lifetime_days = settings::get_i64(
+        pool,
+        "session_lifetime_days",
+        sessions::DEFAULT_LIFETIME_DAYS,
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let lifetime_days = settings::clamp_numeric("session_lifetime_days", lifetime_days);
+    let (sid, refresh_token) = sessions::create(pool, user.id, origin, lifetime_days)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    session_response(pool, user, &sid, refresh_token).await
+}
+
+/// A new access token for the session
+async fn session_response(
+    pool: &PgPool,
+    user: User,
+    sid: &str,
+    refresh_token: String,
+) -> Result<LoginResponse, (StatusCode, String)> {
+    // A coordinator's scope travels in the token; changes apply from the next login or refresh
+    let job_ids: Vec<String> = if user.role == "coordinator" {
+        sqlx::query_scalar("SELECT job_id FROM user_job_scopes WHERE user_id = $1 ORDER BY job_id")
+            .bind(user.id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    } else {
+        Vec::new()
+    };
 
     // Generate token
-    let token =
-        generate_token(&user).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let lifetime = token_lifetime(pool, &user.role)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let token = generate_token(&user, job_ids, lifetime, Some(sid))
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    Ok(Json(LoginResponse {
+    Ok(LoginResponse {
         token,
         username: user.username,
         role: user.role,
         person_id: user.person_id,
-    }))
+        refresh_token,
+    })
+}
+
+/// A new token for a session that is still on. The refresh token is replaced too, so each
+/// one works once.
+#[utoipa::path(
+    post,
+    path = "/login/refresh",
+    tag = "auth",
+    responses((status = 200, body = LoginResponse))
+)]
+pub async fn refresh(
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    Json(request): Json<RefreshRequest>,
+) -> Result<Json<LoginResponse>, (StatusCode, String)> {
+    let origin = RequestOrigin::from_headers(&headers);
+    let (user, sid, refresh_token) = sessions::refresh(&pool, &request.refresh_token, &origin)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((
+            StatusCode::UNAUTHORIZED,
+            "The session has ended; sign in again".to_string(),
+        ))?;
+
+    Ok(Json(
+        session_response(&pool, user, &sid, refresh_token).await?,
+    ))
 }
 
 // Change password endpoint
+#[utoipa::path(
+    post,
+    path = "/auth/change-password",
+    tag = "auth",
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn change_password(
     State(pool): State<PgPool>,
     claims: Claims,
+    headers: HeaderMap,
     Json(request): Json<ChangePasswordRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
     // Get current user
@@ -166,8 +396,23 @@ pub async fn change_password(
         None => return Err((StatusCode::NOT_FOUND, "User not found".to_string())),
     };
 
+    let origin = RequestOrigin::from_headers(&headers);
+    let audit = |event, detail| AuthEvent {
+        event,
+        user_id: Some(user.id),
+        username: Some(&user.username),
+        detail,
+    };
+
     // Verify current password
     if !verify_password(&request.current_password, &user.password_hash) {
+        auth_events::record(
+            &pool,
+            &audit("password_change_failed", Some("Wrong current password")),
+            &origin,
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
         return Err((
             StatusCode::UNAUTHORIZED,
             "Current password is incorrect".to_string(),
@@ -195,24 +440,201 @@ pub async fn change_password(
     .execute(&pool)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    // Whoever knew the old password is signed out everywhere but here
+    sessions::revoke_all(&pool, user.id, claims.sid.as_deref())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    auth_events::record(&pool, &audit("password_changed", None), &origin)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
 
     Ok(Json(
         serde_json::json!({ "message": "Password changed successfully" }),
     ))
 }
 
+/// `GET /auth/me`: who the token belongs to
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CurrentUser {
+    pub id: String,
+    pub username: String,
+    pub role: String,
+    pub job_ids: Vec<String>,
+}
+
 // Get current user info
-pub async fn me(claims: Claims) -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "id": claims.sub,
-        "username": claims.username,
-        "role": claims.role
-    }))
+#[utoipa::path(
+    get,
+    path = "/auth/me",
+    tag = "auth",
+    responses((status = 200, body = CurrentUser))
+)]
+pub async fn me(claims: Claims) -> Json<CurrentUser> {
+    Json(CurrentUser {
+        id: claims.sub,
+        username: claims.username,
+        role: claims.role,
+        job_ids: claims.job_ids,
+    })
+}
+
+// ============ Coordinator scope ============
+
+fn out_of_scope(claims: &Claims) -> (StatusCode, String) {
+    let message = if claims.is_coordinator() {
+        "This is outside the jobs you coordinate"
+    } else {
+        "Only admins and coordinators can do this"
+    };
+    (StatusCode::FORBIDDEN, message.to_string())
+}
+
+/// 403 for a coordinator acting on a job outside their scope, and for servidores and guardians
+pub fn require_job_scope(claims: &Claims, job_id: &str) -> Result<(), (StatusCode, String)> {
+    if claims.in_scope(job_id) {
+        Ok(())
+    } else {
+        Err(out_of_scope(claims))
+    }
+}
+
+/// Adding or removing a person's jobs: a coordinator may only touch jobs in their scope,
+/// the person's other jobs have to stay as they are
+pub fn require_job_changes_in_scope(
+    claims: &Claims,
+    before: &[String],
+    after: &[String],
+) -> Result<(), (StatusCode, String)> {
+    let changed = before
+        .iter()
+        .filter(|j| !after.contains(j))
+        .chain(after.iter().filter(|j| !before.contains(j)));
+    for job_id in changed {
+        require_job_scope(claims, job_id)?;
+    }
+    Ok(())
+}
+
+/// 403 for a coordinator acting on a person who holds none of their jobs, and for servidores
+/// and guardians
+pub async fn require_person_scope<'e, E>(
+    executor: E,
+    claims: &Claims,
+    person_id: &str,
+) -> Result<(), (StatusCode, String)>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    if claims.is_admin() {
+        return Ok(());
+    }
+    if !claims.is_coordinator() {
+        return Err(out_of_scope(claims));
+    }
+    let in_scope: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM person_jobs WHERE person_id = $1 AND job_id = ANY($2))",
+    )
+    .bind(person_id)
+    .bind(&claims.job_ids)
+    .fetch_one(executor)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if in_scope {
+        Ok(())
+    } else {
+        Err(out_of_scope(claims))
+    }
+}
+
+/// A person's own data: they can reach it themselves, coordinators only for people in their
+/// jobs, admins always
+pub async fn require_self_or_person_scope<'e, E>(
+    executor: E,
+    claims: &Claims,
+    person_id: &str,
+) -> Result<(), (StatusCode, String)>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    if claims.person_id.as_deref() == Some(person_id) {
+        return Ok(());
+    }
+    if !claims.is_admin() && !claims.is_coordinator() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "You can only access your own data".to_string(),
+        ));
+    }
+    require_person_scope(executor, claims, person_id).await
+}
+
+/// The person a self-service request (`/my-...`) acts for: the user's own, or with
+/// `?person_id=` one of a guardian's children
+pub async fn acting_person<'e, E>(
+    executor: E,
+    claims: &Claims,
+    on_behalf_of: Option<&str>,
+) -> Result<String, (StatusCode, String)>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let Some(person_id) = on_behalf_of.filter(|p| claims.person_id.as_deref() != Some(*p)) else {
+        return claims.person_id.clone().ok_or((
+            StatusCode::FORBIDDEN,
+            "No tiene un servidor vinculado a su cuenta".to_string(),
+        ));
+    };
+    if !claims.is_guardian() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "You can only access your own data".to_string(),
+        ));
+    }
+    let linked: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM guardian_children WHERE user_id = $1 AND person_id = $2)",
+    )
+    .bind(uuid::Uuid::parse_str(&claims.sub).ok())
+    .bind(person_id)
+    .fetch_one(executor)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if linked {
+        Ok(person_id.to_string())
+    } else {
+        Err((
+            StatusCode::FORBIDDEN,
+            "Esta persona no está vinculada a su cuenta".to_string(),
+        ))
+    }
+}
+
+/// 403 for anyone but an admin; `action` finishes "Only admins can ..."
+pub fn require_admin(claims: &Claims, action: &str) -> Result<(), (StatusCode, String)> {
+    if claims.is_admin() {
+        Ok(())
+    } else {
+        Err((StatusCode::FORBIDDEN, format!("Only admins can {}", action)))
+    }
+}
+
+/// 403 for servidores and guardians; `action` finishes "Only admins and coordinators can ..."
+pub fn require_admin_or_coordinator(
+    claims: &Claims,
+    action: &str,
+) -> Result<(), (StatusCode, String)> {
+    if claims.is_admin() || claims.is_coordinator() {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::FORBIDDEN,
+            format!("Only admins and coordinators can {}", action),
+        ))
+    }
 }
 
 // Auth middleware - extracts and validates JWT from Authorization header
 pub async fn auth_middleware(
-    State(_pool): State<PgPool>,
+    State(pool): State<PgPool>,
     mut request: Request,
     next: Next,
 ) -> Response {
@@ -241,6 +663,18 @@ pub async fn auth_middleware(
         }
     };
 
+    // A token outlives its session only until it expires, unless the session is checked here.
+    // Tokens from before sessions existed have no sid and run out on their own.
+    if let Some(sid) = &claims.sid {
+        match sessions::is_active(&pool, sid).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return (StatusCode::UNAUTHORIZED, "The session has ended").into_response();
+            }
+            Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+        }
+    }
+
     // Add claims to request extensions
     request.extensions_mut().insert(claims);
 
@@ -288,3 +722,114 @@ pub async fn init_admin_user(pool: &PgPool) -> Result<(), sqlx::Error> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(role: &str, job_ids: &[&str]) -> Claims {
+        Claims {
+            sub: "u1".to_string(),
+            username: "user".to_string(),
+            role: role.to_string(),
+            person_id: None,
+            exp: 0,
+            iat: 0,
+            iss: JWT_ISSUER.to_string(),
+            aud: JWT_AUDIENCE.to_string(),
+            job_ids: job_ids.iter().map(|j| j.to_string()).collect(),
+            sid: None,
+        }
+    }
+
+    fn ids(job_ids: &[&str]) -> Vec<String> {
+        job_ids.iter().map(|j| j.to_string()).collect()
+    }
+
+    fn user(role: &str) -> User {
+        User {
+            id: uuid::Uuid::new_v4(),
+            username: "user".to_string(),
+            password_hash: String::new(),
+            role: role.to_string(),
+            person_id: None,
+        }
+    }
+
+    fn sign(claims: &Claims) -> String {
+        sign_claims(claims).unwrap()
+    }
+
+    #[test]
+    fn tokens_carry_their_lifetime_issuer_and_audience() {
+        let token = generate_token(
+            &user("servidor"),
+            Vec::new(),
+            Duration::days(30),
+            Some("s1"),
+        )
+        .unwrap();
+        let claims = validate_token(&token).unwrap();
+        assert_eq!(claims.sid.as_deref(), Some("s1"));
+        assert_eq!(claims.iss, JWT_ISSUER);
+        assert_eq!(claims.aud, JWT_AUDIENCE);
+        assert_eq!(claims.exp - claims.iat, Duration::days(30).num_seconds());
+    }
+
+    #[test]
+    fn tokens_for_another_audience_or_long_expired_are_refused() {
+        let now = Utc::now().timestamp();
+        let mut other = claims("admin", &[]);
+        other.exp = now + 3600;
+        other.aud = "someone-else".to_string();
+        assert!(validate_token(&sign(&other)).is_err());
+
+        let mut expired = claims("admin", &[]);
+        expired.exp = now - get_jwt_leeway() as i64 - 60;
+        assert!(validate_token(&sign(&expired)).is_err());
+
+        // Within the leeway a slightly late clock is still accepted
+        expired.exp = now - 5;
+        assert!(validate_token(&sign(&expired)).is_ok());
+    }
+
+    #[test]
+    fn coordinators_only_reach_their_jobs() {
+        let coordinator = claims("coordinator", &["lectores"]);
+        assert!(coordinator.in_scope("lectores"));
+        assert!(!coordinator.in_scope("monaguillos"));
+        assert!(claims("admin", &[]).in_scope("monaguillos"));
+        // Anyone who is not staff is outside every scope
+        assert!(!claims("servidor", &["lectores"]).in_scope("lectores"));
+        assert!(!claims("guardian", &[]).in_scope("monaguillos"));
+    }
+
+    #[test]
+    fn job_changes_outside_the_scope_are_refused() {
+        let coordinator = claims("coordinator", &["lectores"]);
+        let before = ids(&["lectores", "monaguillos"]);
+        // Dropping their own job while keeping the other one is fine
+        assert!(
+            require_job_changes_in_scope(&coordinator, &before, &ids(&["monaguillos"])).is_ok()
+        );
+        assert!(require_job_changes_in_scope(&coordinator, &before, &ids(&["lectores"])).is_err());
+        assert!(require_job_changes_in_scope(&coordinator, &[], &ids(&["monaguillos"])).is_err());
+    }
+
+    #[test]
+    fn servidores_cannot_generate_or_edit_schedules() {
+        for caller in [claims("servidor", &[]), claims("guardian", &[])] {
+            let (status, _) = require_job_scope(&caller, "monaguillos").unwrap_err();
+            assert_eq!(status, StatusCode::FORBIDDEN);
+            let (status, _) = require_admin(&caller, "generate schedules").unwrap_err();
+            assert_eq!(status, StatusCode::FORBIDDEN);
+            let (status, _) =
+                require_admin_or_coordinator(&caller, "edit assignments").unwrap_err();
+            assert_eq!(status, StatusCode::FORBIDDEN);
+        }
+
+        let coordinator = claims("coordinator", &["lectores"]);
+        assert!(require_admin(&coordinator, "generate schedules").is_err());
+        assert!(require_admin_or_coordinator(&coordinator, "edit assignments").is_ok());
+    }
+}
@@ -0,0 +1,307 @@
+//! WHERE / ORDER BY / SET clauses built from request parameters. Column names only come from
+//! `&'static str` literals or a whitelist, values are always bound, so nothing a client sends
+//! ends up spliced into the SQL.
+
+use sqlx::{Encode, Postgres, QueryBuilder, Type};
+
+/// A SELECT with optional conditions, e.g.
+/// `Filter::new("SELECT * FROM schedules").eq("year", query.year).order_by(...)`
+pub struct Filter<'args> {
+    builder: QueryBuilder<'args, Postgres>,
+    has_where: bool,
+}
+
+impl<'args> Filter<'args> {
+    pub fn new(select: &str) -> Self {
+        Self {
+            builder: QueryBuilder::new(select),
+            has_where: false,
+        }
+    }
+
+    fn condition(&mut self) -> &mut QueryBuilder<'args, Postgres> {
+        self.builder
+            .push(if self.has_where { " AND " } else { " WHERE " });
+        self.has_where = true;
+        &mut self.builder
+    }
+
+    /// A condition without parameters, always applied
+    pub fn raw(&mut self, condition: &'static str) -> &mut Self {
+        self.condition().push(condition);
+        self
+    }
+
+    /// `column = value`, skipped when the parameter was not given
+    pub fn eq<T>(&mut self, column: &'static str, value: Option<T>) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Type<Postgres>,
+    {
+        self.compare(column, " = ", value)
+    }
+
+    /// `column >= value`, skipped when the parameter was not given
+    pub fn gte<T>(&mut self, column: &'static str, value: Option<T>) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Type<Postgres>,
+    {
+        self.compare(column, " >= ", value)
+    }
+
+    /// `column <= value`, skipped when the parameter was not given
+    pub fn lte<T>(&mut self, column: &'static str, value: Option<T>) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Type<Postgres>,
+    {
+        self.compare(column, " <= ", value)
+    }
+
+    fn compare<T>(&mut self, column: &'static str, op: &str, value: Option<T>) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Type<Postgres>,
+    {
+        if let Some(value) = value {
+            self.condition().push(column).push(op).push_bind(value);
+        }
+        self
+    }
+
+    /// `column IN (select value)`, e.g. people holding a job:
+    /// `in_select("id", "SELECT person_id FROM person_jobs WHERE job_id = ", query.job_id)`;
+    /// skipped when the parameter was not given
+    pub fn in_select<T>(
+        &mut self,
+        column: &'static str,
+        select: &'static str,
+        value: Option<T>,
+    ) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Type<Postgres>,
+    {
+        if let Some(value) = value {
+            self.condition()
+                .push(column)
+                .push(" IN (")
+                .push(select)
+                .push_bind(value)
+                .push(")");
+        }
+        self
+    }
+
+    /// Case-insensitive substring match on any of `columns`; blank terms are ignored
+    pub fn search(&mut self, columns: &[&'static str], term: Option<&str>) -> &mut Self {
+        let Some(term) = term.map(str::trim).filter(|t| !t.is_empty()) else {
+            return self;
+        };
+        if columns.is_empty() {
+            return self;
+        }
+        let pattern = format!("%{}%", escape_like(term));
+        let builder = self.condition();
+        builder.push("(");
+        for (i, column) in columns.iter().enumerate() {
+            if i > 0 {
+                builder.push(" OR ");
+            }
+            builder
+                .push(*column)
+                .push(" ILIKE ")
+                .push_bind(pattern.clone());
+        }
+        builder.push(")");
+        self
+    }
+
+    /// ORDER BY from a `sort` parameter (see `parse_sort`), or `default` when it is missing
+    pub fn order_by(
+        &mut self,
+        sort: Option<&str>,
+        allowed: &[(&str, &'static str)],
+        default: &'static str,
+    ) -> Result<&mut Self, String> {
+        let keys = parse_sort(sort.unwrap_or(""), allowed)?;
+        self.builder.push(" ORDER BY ");
+        if keys.is_empty() {
+            self.builder.push(default);
+        }
+        for (i, (column, descending)) in keys.into_iter().enumerate() {
+            if i > 0 {
+                self.builder.push(", ");
+            }
+            self.builder
+                .push(column)
+                .push(if descending { " DESC" } else { " ASC" });
+        }
+        Ok(self)
+    }
+
+    pub fn sql(&self) -> &str {
+        self.builder.sql()
+    }
+
+    pub fn into_builder(self) -> QueryBuilder<'args, Postgres> {
+        self.builder
+    }
+}
+
+/// `UPDATE table SET ...` with only the fields a request provided
+pub struct Update<'args> {
+    builder: QueryBuilder<'args, Postgres>,
+    empty: bool,
+}
+
+impl<'args> Update<'args> {
+    pub fn new(table: &'static str) -> Self {
+        let mut builder = QueryBuilder::new("UPDATE ");
+        builder.push(table).push(" SET ");
+        Self {
+            builder,
+            empty: true,
+        }
+    }
+
+    fn assignment(&mut self) -> &mut QueryBuilder<'args, Postgres> {
+        if !self.empty {
+            self.builder.push(", ");
+        }
+        self.empty = false;
+        &mut self.builder
+    }
+
+    /// `column = value`, skipped when the field was left out of the request
+    pub fn set<T>(&mut self, column: &'static str, value: Option<T>) -> &mut Self
+    where
+        T: 'args + Encode<'args, Postgres> + Type<Postgres>,
+    {
+        if let Some(value) = value {
+            self.assignment().push(column).push(" = ").push_bind(value);
+        }
+        self
+    }
+
+    /// An assignment without parameters, e.g. `updated_at = NOW()`
+    pub fn set_raw(&mut self, assignment: &'static str) -> &mut Self {
+        self.assignment().push(assignment);
+        self
+    }
+
+    /// Nothing to update: callers skip the query
+    pub fn is_empty(&self) -> bool {
+        self.empty
+    }
+
+    /// Close with `WHERE id_column = id RETURNING *`
+    pub fn finish<T>(mut self, id_column: &'static str, id: T) -> QueryBuilder<'args, Postgres>
+    where
+        T: 'args + Encode<'args, Postgres> + Type<Postgres>,
+    {
+        self.builder
+            .push(" WHERE ")
+            .push(id_column)
+            .push(" = ")
+            .push_bind(id)
+            .push(" RETURNING *");
+        self.builder
+    }
+}
+
+/// Parse `sort=last_name,-created_at` (a leading `-` sorts descending) into whitelisted
+/// columns. `allowed` maps the names clients use to SQL columns.
+pub fn parse_sort(
+    sort: &str,
+    allowed: &[(&str, &'static str)],
+) -> Result<Vec<(&'static str, bool)>, String> {
+    sort.split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(|key| {
+            let (name, descending) = match key.strip_prefix('-') {
+                Some(name) => (name, true),
+                None => (key, false),
+            };
+            allowed
+                .iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, column)| (*column, descending))
+                .ok_or_else(|| {
+                    let fields: Vec<&str> = allowed.iter().map(|(field, _)| *field).collect();
+                    format!("Cannot sort by {}. Allowed: {}", name, fields.join(", "))
+                })
+        })
+        .collect()
+}
+
+/// Escape LIKE wildcards so a search term matches literally
+fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SORTS: &[(&str, &str)] = &[("name", "p.last_name"), ("created", "p.created_at")];
+
+    #[test]
+    fn conditions_bind_their_values() {
+        let mut filter = Filter::new("SELECT * FROM people p");
+        filter
+            .eq("p.active", Some(true))
+            .eq("p.id", None::<String>)
+            .gte("p.created_at", Some("2026-01-01".to_string()))
+            .search(&["p.first_name", "p.last_name"], Some(" ana "))
+            .in_select(
+                "p.id",
+                "SELECT person_id FROM person_jobs WHERE job_id = ",
+                Some("lectores"),
+            );
+        assert_eq!(
+            filter.sql(),
+            "SELECT * FROM people p WHERE p.active = $1 AND p.created_at >= $2 \
+             AND (p.first_name ILIKE $3 OR p.last_name ILIKE $4) \
+             AND p.id IN (SELECT person_id FROM person_jobs WHERE job_id = $5)"
+        );
+    }
+
+    #[test]
+    fn sorting_only_accepts_whitelisted_fields() {
+        assert_eq!(
+            parse_sort("-created, name", SORTS).unwrap(),
+            vec![("p.created_at", true), ("p.last_name", false)]
+        );
+        assert!(parse_sort("name; DROP TABLE people", SORTS).is_err());
+        assert!(parse_sort("p.last_name", SORTS).is_err());
+
+        let mut filter = Filter::new("SELECT * FROM people p");
+        filter
+            .order_by(None, SORTS, "p.last_name, p.first_name")
+            .unwrap();
+        assert_eq!(
+            filter.sql(),
+            "SELECT * FROM people p ORDER BY p.last_name, p.first_name"
+        );
+    }
+
+    #[test]
+    fn updates_only_set_given_fields() {
+        let mut update = Update::new("people");
+        assert!(update.is_empty());
+        update
+            .set("first_name", Some("Ana"))
+            .set("email", None::<&str>)
+            .set_raw("updated_at = NOW()");
+        assert!(!update.is_empty());
+        assert_eq!(
+            update.finish("id", "p1").sql(),
+            "UPDATE people SET first_name = $1, updated_at = NOW() WHERE id = $2 RETURNING *"
+        );
+    }
+
+    #[test]
+    fn search_terms_match_literally() {
+        assert_eq!(escape_like("50%_a\\b"), "50\\%\\_a\\\\b");
+    }
+}
@@ -1,3 +1,6 @@
+pub mod filters;
+pub mod tx;
+
 use sqlx::{postgres::PgPoolOptions, PgPool};
 use std::env;
 
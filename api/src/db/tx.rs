@@ -0,0 +1,84 @@
+use axum::{
+    extract::{FromRef, FromRequestParts, Request},
+    http::{request::Parts, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+type Slot = Arc<Mutex<Option<Transaction<'static, Postgres>>>>;
+
+/// Per-request transaction slot, filled the first time a handler extracts `Tx`
+#[derive(Clone, Default)]
+struct TxSlot(Slot);
+
+/// Commits the request's transaction when the handler succeeds (2xx/3xx) and rolls it back
+/// otherwise, so a handler that fails halfway leaves nothing behind
+pub async fn tx_middleware(mut request: Request, next: Next) -> Response {
+    let slot = TxSlot::default();
+    request.extensions_mut().insert(slot.clone());
+
+    let response = next.run(request).await;
+
+    let Some(tx) = slot.0.lock().await.take() else {
+        return response;
+    };
+    let status = response.status();
+    if status.is_success() || status.is_redirection() {
+        if let Err(e) = tx.commit().await {
+            tracing::error!("Could not commit request transaction: {}", e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
+        }
+    } else if let Err(e) = tx.rollback().await {
+        tracing::warn!("Could not roll back request transaction: {}", e);
+    }
+
+    response
+}
+
+/// A database transaction tied to the request. Derefs to the connection, so queries run with
+/// `.execute(&mut *tx)`; the commit or rollback happens in `tx_middleware`.
+pub struct Tx(OwnedMutexGuard<Option<Transaction<'static, Postgres>>>);
+
+impl<S> FromRequestParts<S> for Tx
+where
+    PgPool: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let slot = parts.extensions.get::<TxSlot>().cloned().ok_or((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Transaction layer is not installed".to_string(),
+        ))?;
+
+        let mut guard = slot.0.lock_owned().await;
+        if guard.is_none() {
+            let tx = PgPool::from_ref(state)
+                .begin()
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            *guard = Some(tx);
+        }
+
+        Ok(Tx(guard))
+    }
+}
+
+impl Deref for Tx {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        self.0.as_ref().expect("transaction already finished")
+    }
+}
+
+impl DerefMut for Tx {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        self.0.as_mut().expect("transaction already finished")
+    }
+}
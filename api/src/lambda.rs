@@ -4,7 +4,7 @@
 //! Deploy with: cargo lambda deploy
 
 use lambda_http::{run, Error};
-use people_scheduler_api::{create_app, db, init_database};
+use people_scheduler_api::{create_app, db, init_database, services::signing_keys};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
@@ -17,6 +17,9 @@ async fn main() -> Result<(), Error> {
         ))
         .init();
 
+    // A bad JWT key configuration stops startup instead of the first login
+    signing_keys::keys();
+
     // Create database pool
     let pool = db::create_pool()
         .await
@@ -2,6 +2,7 @@ pub mod auth;
 pub mod db;
 pub mod models;
 pub mod routes;
+pub mod services;
 
 use axum::Router;
 use sqlx::PgPool;
@@ -109,14 +110,613 @@ pub async fn init_database(pool: &PgPool) -> Result<(), Box<dyn std::error::Erro
         Err(e) => tracing::warn!("Migration 009d: {}", e),
     }
 
-    match sqlx::query("ALTER TABLE people ADD COLUMN IF NOT EXISTS photo_consent BOOLEAN NOT NULL DEFAULT FALSE")
-        .execute(pool)
-        .await
+    match sqlx::query(
+        "ALTER TABLE people ADD COLUMN IF NOT EXISTS photo_consent BOOLEAN NOT NULL DEFAULT FALSE",
+    )
+    .execute(pool)
+    .await
     {
         Ok(_) => tracing::info!("Migration 009e: photo_consent column added"),
         Err(e) => tracing::warn!("Migration 009e: {}", e),
     }
 
+    // Migration 010: Add min_rest_days column for per-person rest gap
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/010_min_rest_days.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 010: min_rest_days column added"),
+        Err(e) => tracing::warn!("Migration 010: {}", e),
+    }
+
+    // Migration 011: Key-value app settings (junior program defaults)
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/011_app_settings.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 011: app_settings table created"),
+        Err(e) => tracing::warn!("Migration 011: {}", e),
+    }
+
+    // Migration 012: Pairing preferences (prefer-with / avoid-with)
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/012_pairing_preferences.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 012: pairing_preferences table created"),
+        Err(e) => tracing::warn!("Migration 012: {}", e),
+    }
+
+    // Migration 013: Per-job novice limit per service date
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/013_job_novice_limit.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 013: novice limit columns added"),
+        Err(e) => tracing::warn!("Migration 013: {}", e),
+    }
+
+    // Migration 014: Team templates rotated as a unit
+    match sqlx::raw_sql(include_str!("../../migrations-postgres/014_teams.sql"))
+        .execute(pool)
+        .await
+    {
+        Ok(_) => tracing::info!("Migration 014: teams tables created"),
+        Err(e) => tracing::warn!("Migration 014: {}", e),
+    }
+
+    // Migration 015: Scheduling notes, separate from pastoral notes
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/015_scheduling_notes.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 015: scheduling_notes column added"),
+        Err(e) => tracing::warn!("Migration 015: {}", e),
+    }
+
+    // Migration 016: Per-person per-job blackout months
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/016_job_blackout_months.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 016: person_job_blackouts table created"),
+        Err(e) => tracing::warn!("Migration 016: {}", e),
+    }
+
+    // Migration 017: Per-date staffing overrides
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/017_staffing_overrides.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 017: staffing_overrides table created"),
+        Err(e) => tracing::warn!("Migration 017: {}", e),
+    }
+
+    // Migration 018: Username aliases (login grace period) and in-app notifications
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/018_username_aliases.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 018: username_aliases and notifications tables created"),
+        Err(e) => tracing::warn!("Migration 018: {}", e),
+    }
+
+    // Migration 019: Per-job first communion requirement
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/019_requires_first_communion.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 019: requires_first_communion column added"),
+        Err(e) => tracing::warn!("Migration 019: {}", e),
+    }
+
+    // Migration 020: Mentor rule for novices
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/020_requires_mentor.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 020: requires_mentor column added"),
+        Err(e) => tracing::warn!("Migration 020: {}", e),
+    }
+
+    // Migration 021: Per-job fairness weight setting
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/021_fairness_job_weight.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 021: fairness_job_weight setting added"),
+        Err(e) => tracing::warn!("Migration 021: {}", e),
+    }
+
+    // Migration 022: Assignment change log
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/022_assignment_events.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 022: assignment_events table created"),
+        Err(e) => tracing::warn!("Migration 022: {}", e),
+    }
+
+    // Migration 023: Persisted position rotation state
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/023_position_rotation.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 023: position_rotation table created"),
+        Err(e) => tracing::warn!("Migration 023: {}", e),
+    }
+
+    // Migration 024: Schedules for arbitrary date ranges
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/024_schedule_ranges.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 024: schedule date ranges added"),
+        Err(e) => tracing::warn!("Migration 024: {}", e),
+    }
+
+    // Migration 025: Per-job exclusions
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/025_person_job_exclusions.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 025: person_job_exclusions table created"),
+        Err(e) => tracing::warn!("Migration 025: {}", e),
+    }
+
+    // Migration 026: Assignment visibility lead time
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/026_assignment_visibility.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 026: assignment_visibility_weeks setting added"),
+        Err(e) => tracing::warn!("Migration 026: {}", e),
+    }
+
+    // Migration 027: Sibling pairing scope
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/027_sibling_pairing_scope.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 027: sibling_groups.pairing_scope column added"),
+        Err(e) => tracing::warn!("Migration 027: {}", e),
+    }
+
+    // Migration 028: Fairness carry-over settings
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/028_fairness_carry_over.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 028: fairness carry-over settings added"),
+        Err(e) => tracing::warn!("Migration 028: {}", e),
+    }
+
+    // Migration 029: Max distinct jobs per person per month
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/029_max_jobs_per_month.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 029: max_jobs_per_person_per_month setting added"),
+        Err(e) => tracing::warn!("Migration 029: {}", e),
+    }
+
+    // Migration 030: Co-assignment variety penalty
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/030_co_assignment_penalty.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 030: co_assignment_penalty setting added"),
+        Err(e) => tracing::warn!("Migration 030: {}", e),
+    }
+
+    // Migration 031: Notification dead-letter queue
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/031_notification_failures.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 031: notification_failures table created"),
+        Err(e) => tracing::warn!("Migration 031: {}", e),
+    }
+
+    // Migration 032: Correct the visibility setting description
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/032_visibility_description.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 032: assignment_visibility_weeks description updated"),
+        Err(e) => tracing::warn!("Migration 032: {}", e),
+    }
+
+    // Migration 033: Recurring unavailability patterns
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/033_recurring_unavailability.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 033: unavailability recurrence added"),
+        Err(e) => tracing::warn!("Migration 033: {}", e),
+    }
+
+    // Migration 034: Schedule naming pattern
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/034_schedule_name_pattern.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 034: schedule_name_pattern setting added"),
+        Err(e) => tracing::warn!("Migration 034: {}", e),
+    }
+
+    // Migration 035: Schedule notes
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/035_schedule_notes.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 035: schedules.notes added"),
+        Err(e) => tracing::warn!("Migration 035: {}", e),
+    }
+
+    // Migration 036: Coordinator job scopes
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/036_coordinator_scopes.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 036: user_job_scopes table created"),
+        Err(e) => tracing::warn!("Migration 036: {}", e),
+    }
+
+    // Migration 037: Per-job schedule publishing
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/037_schedule_job_status.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 037: schedule_job_status table created"),
+        Err(e) => tracing::warn!("Migration 037: {}", e),
+    }
+
+    // Migration 038: Schedule status change log
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/038_schedule_status_events.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 038: schedule_status_events table created"),
+        Err(e) => tracing::warn!("Migration 038: {}", e),
+    }
+
+    // Migration 039: Schedule snapshots taken on publish
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/039_schedule_snapshots.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 039: schedule_snapshots tables created"),
+        Err(e) => tracing::warn!("Migration 039: {}", e),
+    }
+
+    // Migration 040: Login lifetime per role
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/040_jwt_lifetimes.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 040: JWT lifetime settings added"),
+        Err(e) => tracing::warn!("Migration 040: {}", e),
+    }
+
+    // Migration 041: Suggested (copied) assignments
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/041_assignment_suggestions.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 041: assignments.suggested column added"),
+        Err(e) => tracing::warn!("Migration 041: {}", e),
+    }
+
+    // Migration 042: Google Sheets exports
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/042_sheet_exports.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 042: sheet_exports table created"),
+        Err(e) => tracing::warn!("Migration 042: {}", e),
+    }
+
+    // Migration 043: Assignment notes
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/043_assignment_notes.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 043: assignments.notes column added"),
+        Err(e) => tracing::warn!("Migration 043: {}", e),
+    }
+
+    // Migration 044: One schedule per month
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/044_schedule_month_unique.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 044: one active schedule per month"),
+        Err(e) => tracing::warn!("Migration 044: {}", e),
+    }
+
+    // Migration 045: Cancelled service dates
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/045_service_date_cancellation.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 045: service date cancellation columns added"),
+        Err(e) => tracing::warn!("Migration 045: {}", e),
+    }
+
+    // Migration 046: Reasons for assignments made despite warnings
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/046_assignment_override_reason.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 046: assignment override reason columns added"),
+        Err(e) => tracing::warn!("Migration 046: {}", e),
+    }
+
+    // Migration 047: Edit log for undo/redo
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/047_assignment_edits.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 047: assignment_edits table created"),
+        Err(e) => tracing::warn!("Migration 047: {}", e),
+    }
+
+    // Migration 048: Who created and last changed each assignment
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/048_assignment_actors.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 048: assignment created_by/updated_by added"),
+        Err(e) => tracing::warn!("Migration 048: {}", e),
+    }
+
+    // Migration 049: Mass time for the signage display
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/049_mass_time_setting.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 049: mass_time setting added"),
+        Err(e) => tracing::warn!("Migration 049: {}", e),
+    }
+
+    // Migration 050: Location for calendar exports
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/050_service_location_setting.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 050: service_location setting added"),
+        Err(e) => tracing::warn!("Migration 050: {}", e),
+    }
+
+    // Migration 051: Calendar subscription feeds
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/051_calendar_feeds.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 051: calendar_feeds table created"),
+        Err(e) => tracing::warn!("Migration 051: {}", e),
+    }
+
+    // Migration 052: Pre-service reminders
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/052_service_reminders.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => {
+            tracing::info!("Migration 052: service_reminders and notification_outbox tables added")
+        }
+        Err(e) => tracing::warn!("Migration 052: {}", e),
+    }
+
+    // Migration 053: WhatsApp templates
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/053_whatsapp_channel.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 053: whatsapp_templates table created"),
+        Err(e) => tracing::warn!("Migration 053: {}", e),
+    }
+
+    // Migration 054: Web Push subscriptions
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/054_push_subscriptions.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 054: push_subscriptions and push_outbox created"),
+        Err(e) => tracing::warn!("Migration 054: {}", e),
+    }
+
+    // Migration 055: Per-person notification preferences
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/055_notification_preferences.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 055: notification_preferences tables created"),
+        Err(e) => tracing::warn!("Migration 055: {}", e),
+    }
+
+    // Migration 056: Notices when a published assignment changes
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/056_change_notices.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 056: change_channel setting added"),
+        Err(e) => tracing::warn!("Migration 056: {}", e),
+    }
+
+    // Migration 057: Background job queue
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/057_background_jobs.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 057: background_jobs table created"),
+        Err(e) => tracing::warn!("Migration 057: {}", e),
+    }
+
+    // Migration 058: OIDC sign-in
+    match sqlx::raw_sql(include_str!("../../migrations-postgres/058_oidc_login.sql"))
+        .execute(pool)
+        .await
+    {
+        Ok(_) => tracing::info!("Migration 058: OIDC identity and key tables created"),
+        Err(e) => tracing::warn!("Migration 058: {}", e),
+    }
+
+    // Migration 059: Authentication audit trail
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/059_auth_events.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 059: auth_events table created"),
+        Err(e) => tracing::warn!("Migration 059: {}", e),
+    }
+
+    // Migration 060: Login sessions with refresh tokens
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/060_user_sessions.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 060: user_sessions table created"),
+        Err(e) => tracing::warn!("Migration 060: {}", e),
+    }
+
+    // Migration 061: TOTP second factor
+    match sqlx::raw_sql(include_str!("../../migrations-postgres/061_two_factor.sql"))
+        .execute(pool)
+        .await
+    {
+        Ok(_) => tracing::info!("Migration 061: two-factor columns created"),
+        Err(e) => tracing::warn!("Migration 061: {}", e),
+    }
+
+    // Migration 062: Guardian accounts
+    match sqlx::raw_sql(include_str!("../../migrations-postgres/062_guardians.sql"))
+        .execute(pool)
+        .await
+    {
+        Ok(_) => tracing::info!("Migration 062: guardian_children table created"),
+        Err(e) => tracing::warn!("Migration 062: {}", e),
+    }
+
+    // Migration 063: API keys for read-only integrations
+    match sqlx::raw_sql(include_str!("../../migrations-postgres/063_api_keys.sql"))
+        .execute(pool)
+        .await
+    {
+        Ok(_) => tracing::info!("Migration 063: api_keys table created"),
+        Err(e) => tracing::warn!("Migration 063: {}", e),
+    }
+
+    // Migration 064: Invitation-based onboarding
+    match sqlx::raw_sql(include_str!(
+        "../../migrations-postgres/064_invitations.sql"
+    ))
+    .execute(pool)
+    .await
+    {
+        Ok(_) => tracing::info!("Migration 064: invitations table created"),
+        Err(e) => tracing::warn!("Migration 064: {}", e),
+    }
+
     // Initialize admin user if not exists
     auth::init_admin_user(pool).await?;
 
@@ -4,7 +4,10 @@
 //! Or: cargo watch -x 'run --bin api'
 
 use dotenvy::dotenv;
-use people_scheduler_api::{create_app, db, init_database};
+use people_scheduler_api::{
+    create_app, db, init_database,
+    services::{background_jobs, signing_keys},
+};
 use std::net::SocketAddr;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -21,6 +24,9 @@ async fn main() {
         ))
         .init();
 
+    // A bad JWT key configuration stops startup instead of the first login
+    signing_keys::keys();
+
     // Create database pool
     let pool = db::create_pool()
         .await
@@ -34,6 +40,9 @@ async fn main() {
         .expect("Failed to initialize database");
     tracing::info!("Database initialized");
 
+    // Queued jobs run here; on Lambda a scheduler drains them instead
+    background_jobs::spawn_worker(pool.clone());
+
     // Create app
     let app = create_app(pool);
 
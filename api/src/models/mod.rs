@@ -1,5 +1,6 @@
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::ToSchema;
 
 // Helper to deserialize empty strings as None for Option<NaiveDate>
 fn deserialize_optional_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
@@ -29,9 +30,24 @@ where
     }
 }
 
+// Helper to tell an explicit null (Some(None)) apart from a missing field (None)
+fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
 // ============ Jobs ============
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+/// Job ids of the Monaguillos Jr. program and the senior Monaguillos it feeds into
+pub const JUNIOR_JOB_ID: &str = "monaguillos_jr";
+pub const SENIOR_JOB_ID: &str = "monaguillos";
+/// Job id of the Lectores ministry
+pub const LECTORES_JOB_ID: &str = "lectores";
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct Job {
     pub id: String,
     pub name: String,
@@ -41,9 +57,28 @@ pub struct Job {
     pub active: bool,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
+    // Added via migration 013 - at most N novices (< novice_threshold past assignments) per date
+    pub max_novices_per_date: Option<i32>,
+    pub novice_threshold: i32,
+    // Added via migration 019 - only people with first_communion = true can serve
+    pub requires_first_communion: bool,
+    // Added via migration 020 - a novice is never scheduled without an experienced person
+    pub requires_mentor: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateJobRequirements {
+    pub requires_first_communion: Option<bool>,
+    pub requires_mentor: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateJobNoviceLimit {
+    pub max_novices_per_date: Option<i32>,
+    pub novice_threshold: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct JobPosition {
     pub id: String,
     pub job_id: String,
@@ -54,7 +89,7 @@ pub struct JobPosition {
 
 // ============ People ============
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct Person {
     pub id: String,
     pub first_name: String,
@@ -79,27 +114,61 @@ pub struct Person {
     pub parent_name: Option<String>,
     pub address: Option<String>,
     pub photo_consent: bool,
+    // Added via migration 010 - minimum days between two assignments
+    pub min_rest_days: Option<i32>,
+    // Added via migration 015 - notes for whoever builds the schedule
+    pub scheduling_notes: Option<String>,
+    // Added via migration 031 - a notification to their email or phone bounced
+    pub contact_bounced: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PersonWithJobs {
     #[serde(flatten)]
     pub person: Person,
     pub job_ids: Vec<String>,
+    /// Jobs the person is qualified for but must not be scheduled in
+    pub excluded_job_ids: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub username: Option<String>,
+    /// Only with `GET /people?include=stats`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<PersonStats>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// `GET /people`: one page of the people matching the filters
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PeoplePage {
+    pub people: Vec<PersonWithJobs>,
+    /// All the people matching the filters, across pages
+    pub total: i64,
+    pub page: i64,
+    /// None when everyone matching was returned at once
+    pub limit: Option<i64>,
+}
+
+/// Activity summary shown in the people table
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct PersonStats {
+    pub assignments_this_year: i64,
+    pub last_served_date: Option<NaiveDate>,
+    pub upcoming_assignments: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PersonWithCredentials {
     #[serde(flatten)]
     pub person: Person,
     pub job_ids: Vec<String>,
-    pub username: String,
-    pub generated_password: String, // Only returned once when creating or resetting
+    // Without an invitation: the login, with its password returned only once
+    pub username: Option<String>,
+    pub generated_password: Option<String>,
+    // With `invite`: no login until the servidor accepts the invitation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invitation: Option<IssuedInvitation>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreatePerson {
     pub first_name: String,
     pub last_name: String,
@@ -123,9 +192,15 @@ pub struct CreatePerson {
     #[serde(default, deserialize_with = "deserialize_optional_string")]
     pub address: Option<String>,
     pub photo_consent: Option<bool>,
+    pub min_rest_days: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_optional_string")]
+    pub scheduling_notes: Option<String>,
+    // Send an invitation link instead of creating a login with a generated password
+    #[serde(default)]
+    pub invite: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdatePerson {
     #[serde(default, deserialize_with = "deserialize_optional_string")]
     pub first_name: Option<String>,
@@ -154,16 +229,19 @@ pub struct UpdatePerson {
     #[serde(default, deserialize_with = "deserialize_optional_string")]
     pub address: Option<String>,
     pub photo_consent: Option<bool>,
+    pub min_rest_days: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_optional_string")]
+    pub scheduling_notes: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UploadPhotoRequest {
     pub photo_data: String,
 }
 
 // ============ Person Jobs ============
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct PersonJob {
     pub id: String,
     pub person_id: String,
@@ -174,32 +252,199 @@ pub struct PersonJob {
 
 // ============ Sibling Groups ============
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct SiblingGroup {
     pub id: String,
     pub name: String,
     pub pairing_rule: String,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
+    // Added via migration 027 - SAME_JOB or SAME_DATE
+    pub pairing_scope: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Pairing scopes: the rule applies within one job's selection, or across every job that date
+pub const PAIRING_SCOPE_SAME_JOB: &str = "SAME_JOB";
+pub const PAIRING_SCOPE_SAME_DATE: &str = "SAME_DATE";
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SiblingGroupWithMembers {
     #[serde(flatten)]
     pub group: SiblingGroup,
     pub member_ids: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateSiblingGroup {
     pub name: String,
     pub pairing_rule: String,
+    /// Defaults to SAME_JOB
+    pub pairing_scope: Option<String>,
+    pub member_ids: Vec<String>,
+}
+
+/// Sibling groups sharing at least one member, usually one family imported more than once
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SiblingGroupDuplicate {
+    pub groups: Vec<SiblingGroupWithMembers>,
+    pub shared_member_ids: Vec<String>, // People in more than one of the groups
+    pub conflicting_rules: bool,        // The groups disagree on pairing rule or scope
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeSiblingGroups {
+    /// The first group is kept; the others are folded into it and deleted
+    pub group_ids: Vec<String>,
+    pub name: Option<String>,
+    /// Required when the groups disagree, otherwise their common value is kept
+    pub pairing_rule: Option<String>,
+    pub pairing_scope: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SiblingGroupMergeResult {
+    #[serde(flatten)]
+    pub group: SiblingGroupWithMembers,
+    /// Upcoming assignments that break the merged group's SEPARATE rule
+    pub conflicts: Vec<ScheduleConflict>,
+}
+
+// ============ Staffing Overrides ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct StaffingOverride {
+    pub id: String,
+    pub service_date: NaiveDate,
+    pub job_id: String,
+    pub people_required: i32,
+    pub reason: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetStaffingOverride {
+    pub service_date: NaiveDate,
+    pub job_id: String,
+    pub people_required: i32,
+    pub reason: Option<String>,
+}
+
+// ============ Job Blackout Months ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct PersonJobBlackout {
+    pub id: String,
+    pub person_id: String,
+    pub job_id: String,
+    pub months: Vec<i32>,
+    pub reason: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportVCardRequest {
+    /// Contents of a .vcf file (one or more cards)
+    pub data: String,
+    /// "skip" (default) leaves existing people untouched; "update" fills in their phone and email
+    pub on_duplicate: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VCardImportSkipped {
+    pub name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VCardImportResult {
+    pub created: Vec<Person>,
+    pub updated: Vec<Person>,
+    pub skipped: Vec<VCardImportSkipped>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePersonJobBlackout {
+    pub job_id: String,
+    pub months: Vec<i32>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct PersonJobExclusion {
+    pub id: String,
+    pub person_id: String,
+    pub job_id: String,
+    pub reason: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePersonJobExclusion {
+    pub job_id: String,
+    pub reason: Option<String>,
+}
+
+// ============ Teams ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct Team {
+    pub id: String,
+    pub name: String,
+    pub job_id: String,
+    pub active: bool,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TeamWithMembers {
+    #[serde(flatten)]
+    pub team: Team,
+    pub member_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateTeam {
+    pub name: String,
+    pub job_id: String,
+    pub active: Option<bool>,
     pub member_ids: Vec<String>,
 }
 
+// ============ Pairing Preferences ============
+
+/// Pairing preference values: PREFER (place together) or AVOID (keep apart)
+pub const PAIRING_PREFER: &str = "PREFER";
+pub const PAIRING_AVOID: &str = "AVOID";
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct PairingPreference {
+    pub id: String,
+    pub person_a_id: String,
+    pub person_b_id: String,
+    pub preference: String,
+    pub notes: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePairingPreference {
+    pub person_a_id: String,
+    pub person_b_id: String,
+    pub preference: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePairingPreference {
+    pub preference: Option<String>,
+    pub notes: Option<String>,
+}
+
 // ============ Unavailability ============
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct Unavailability {
     pub id: String,
     pub person_id: String,
@@ -207,28 +452,36 @@ pub struct Unavailability {
     pub end_date: NaiveDate,
     pub reason: Option<String>,
     pub recurring: Option<bool>,
+    /// RRULE-like pattern repeated inside start_date..end_date (see services::recurrence)
+    pub recurrence: Option<String>,
     pub created_at: Option<DateTime<Utc>>,
+    /// Upcoming dates a recurring record covers, filled in by the list endpoints
+    #[sqlx(skip)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub occurrences: Vec<NaiveDate>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UnavailabilityWithPerson {
     #[serde(flatten)]
     pub unavailability: Unavailability,
     pub person_name: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateUnavailability {
     pub person_id: String,
     pub start_date: NaiveDate,
     pub end_date: NaiveDate,
     pub reason: Option<String>,
     pub recurring: Option<bool>,
+    /// e.g. "FREQ=WEEKLY;BYDAY=SA" or "FREQ=MONTHLY;BYDAY=1SU"; makes the record recurring
+    pub recurrence: Option<String>,
 }
 
 // ============ Schedules ============
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct Schedule {
     pub id: String,
     pub name: String,
@@ -238,18 +491,33 @@ pub struct Schedule {
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
     pub published_at: Option<DateTime<Utc>>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    /// Shared by the schedules generated together from one date range
+    pub series_id: Option<String>,
+    pub notes: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct ServiceDate {
     pub id: String,
     pub schedule_id: String,
     pub service_date: NaiveDate,
     pub notes: Option<String>,
     pub created_at: Option<DateTime<Utc>>,
+    /// Set when the date was called off; it keeps no assignments
+    #[sqlx(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cancelled_at: Option<DateTime<Utc>>,
+    #[sqlx(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cancelled_by: Option<String>,
+    #[sqlx(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cancel_reason: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct Assignment {
     pub id: String,
     pub service_date_id: String,
@@ -258,32 +526,278 @@ pub struct Assignment {
     pub position: Option<i32>,
     pub position_name: Option<String>,
     pub manual_override: Option<bool>,
+    /// Copied from another month and not looked at yet
+    #[sqlx(default)]
+    #[serde(default)]
+    pub suggested: bool,
+    /// Instructions for whoever serves the slot, e.g. "arrive 20 min early for rehearsal"
+    #[sqlx(default)]
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Why the person was assigned despite a warning (unavailable, sibling conflict)
+    #[sqlx(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub override_reason: Option<String>,
+    /// Username that created the slot (None = the generator) and that last changed it
+    #[sqlx(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<String>,
+    #[sqlx(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<String>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AssignmentWithDetails {
     #[serde(flatten)]
     pub assignment: Assignment,
     pub person_name: String,
     pub job_name: String,
+    pub job_color: Option<String>,
+    // Where the slot sits, so an assignment makes sense without its parent schedule
+    pub service_date: NaiveDate,
+    pub schedule_id: String,
+    pub schedule_year: i32,
+    pub schedule_month: i32,
+    // Coordinator-only notes about the assigned person (editor payload)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub person_notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduling_notes: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct GenerateScheduleRequest {
     pub year: i32,
     pub month: i32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GenerateScheduleRangeRequest {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// "monthly" (default): one linked schedule per month; "single": one long schedule
+    pub mode: Option<String>,
+    pub name: Option<String>,
+}
+
+/// Generate every month from start to end (inclusive), one schedule each
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GenerateScheduleBatchRequest {
+    pub start_year: i32,
+    pub start_month: i32,
+    pub end_year: i32,
+    pub end_month: i32,
+}
+
+/// Copy a schedule's assignments onto another month
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DuplicateScheduleRequest {
+    pub year: i32,
+    pub month: i32,
+    /// Defaults to the name pattern for the new month
+    pub name: Option<String>,
+}
+
+/// Fields left out are not changed; an empty `notes` clears them
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateScheduleRequest {
+    pub name: Option<String>,
+    pub notes: Option<String>,
+    /// DRAFT, PUBLISHED or ARCHIVED
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UnpublishScheduleRequest {
+    pub reason: String,
+    /// Tell everyone assigned in the schedule that its dates may change
+    pub notify: Option<bool>,
+}
+
+/// A schedule status change: who made it and why
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct ScheduleStatusEvent {
+    pub id: String,
+    pub from_status: String,
+    pub to_status: String,
+    pub reason: Option<String>,
+    pub changed_by: String,
+    /// People sent a retraction notice
+    pub notified: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A published version of a schedule
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct ScheduleSnapshot {
+    pub version: i32,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub assignments: i64,
+}
+
+/// One slot as it was in a snapshot (or is now, for diffs against the live schedule)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct SnapshotAssignment {
+    pub service_date: NaiveDate,
+    pub job_id: String,
+    pub job_name: String,
+    pub position: Option<i32>,
+    pub position_name: Option<String>,
+    pub person_id: Option<String>,
+    pub person_name: Option<String>,
+}
+
+/// A slot that has someone else in it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AssignmentChange {
+    pub service_date: NaiveDate,
+    pub job_id: String,
+    pub job_name: String,
+    pub position: Option<i32>,
+    pub position_name: Option<String>,
+    pub from_person_id: Option<String>,
+    pub from_person_name: Option<String>,
+    pub to_person_id: Option<String>,
+    pub to_person_name: Option<String>,
+}
+
+/// What changed between two versions of a schedule. Slots that were filled and are now empty
+/// are `removed`, slots filled since are `added`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ScheduleDiff {
+    pub from_version: i32,
+    /// None when compared against the schedule as it is now
+    pub to_version: Option<i32>,
+    pub added: Vec<SnapshotAssignment>,
+    pub removed: Vec<SnapshotAssignment>,
+    pub changed: Vec<AssignmentChange>,
+}
+
+/// Publishing state of one job within a schedule. While the schedule is a draft each job is
+/// published on its own; afterwards the schedule's status applies to all of them.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct ScheduleJobStatus {
+    pub job_id: String,
+    pub job_name: String,
+    pub job_color: Option<String>,
+    /// DRAFT, PUBLISHED or ARCHIVED
+    pub status: String,
+    pub published_at: Option<DateTime<Utc>>,
+    pub assignments: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegenerateScheduleRequest {
+    /// "future" (default): keep dates before today as served; "all": recompute every date
+    pub mode: Option<String>,
+}
+
+/// Outcome of one month of a batch generation
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BatchMonthSummary {
+    pub year: i32,
+    pub month: i32,
+    /// "generated", or "skipped" when the month already had a schedule
+    pub status: String,
+    pub schedule_id: Option<String>,
+    pub service_dates: usize,
+    pub total_slots: usize,
+    pub filled_slots: usize,
+    pub conflicts: Vec<ScheduleConflict>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateAssignmentRequest {
     pub person_id: String,
+    // Who the client saw in the slot (null = empty); required, omitting it gets a 428
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub expected_person_id: Option<Option<String>>,
+    // Required when the person is unavailable or a kept-apart sibling already serves
+    pub override_reason: Option<String>,
+}
+
+/// One edit in a bulk request, e.g. `{"op": "swap", "assignment_id_1": ..., "assignment_id_2": ...}`
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BulkAssignmentOperation {
+    Set {
+        assignment_id: String,
+        person_id: String,
+        // Same optimistic check as a single update; required
+        #[serde(default, deserialize_with = "deserialize_some")]
+        expected_person_id: Option<Option<String>>,
+        override_reason: Option<String>,
+    },
+    Clear {
+        assignment_id: String,
+    },
+    Swap {
+        assignment_id_1: String,
+        assignment_id_2: String,
+    },
+}
+
+/// Applied in order, all or nothing
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkAssignmentsRequest {
+    pub operations: Vec<BulkAssignmentOperation>,
+}
+
+/// `notes: null` (or blank) removes the note
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateAssignmentNotesRequest {
+    pub notes: Option<String>,
+}
+
+/// `notes: null` (or blank) removes the note
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateServiceDateNotesRequest {
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CancelServiceDateRequest {
+    pub reason: Option<String>,
+}
+
+// ============ Position Rotation ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RotationStatus {
+    pub person_id: String,
+    pub person_name: String,
+    pub cycle: i32,
+    pub done_positions: Vec<i32>,    // Done in the current cycle
+    pub pending_positions: Vec<i32>, // Still owed in the current cycle
+}
+
+// ============ Assignment Events ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct AssignmentEvent {
+    pub id: String,
+    pub assignment_id: String,
+    pub job_id: String,
+    pub job_name: String,
+    pub position: Option<i32>,
+    pub position_name: Option<String>,
+    pub action: String, // generated, assigned, substituted, cleared, swapped, moved, copied, undone, redone
+    pub person_id: Option<String>,
+    pub person_name: Option<String>,
+    pub previous_person_id: Option<String>,
+    pub previous_person_name: Option<String>,
+    pub changed_by: Option<String>, // None = generator
+    pub reason: Option<String>,     // Override reason given when assigning despite a warning
+    pub created_at: DateTime<Utc>,
 }
 
 // ============ Assignment History ============
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct AssignmentHistory {
     pub id: String,
     pub person_id: String,
@@ -297,13 +811,13 @@ pub struct AssignmentHistory {
 
 // ============ Reports ============
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct JobAssignmentCount {
     pub job_name: String,
     pub count: i64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct FairnessScore {
     pub person_id: String,
     pub person_name: String,
@@ -312,7 +826,7 @@ pub struct FairnessScore {
     pub assignments_by_job: Vec<JobAssignmentCount>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PersonHistoryEntry {
     pub service_date: NaiveDate,
     pub job_id: String,
@@ -321,16 +835,634 @@ pub struct PersonHistoryEntry {
     pub position_name: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct JuniorRosterEntry {
+    pub person_id: String,
+    pub person_name: String,
+    pub birth_date: Option<NaiveDate>,
+    pub age: Option<i32>,
+    pub first_communion: bool,
+    pub junior_services: i64,
+    pub senior_services: i64,
+    pub last_junior_service: Option<NaiveDate>,
+    pub qualified_as_senior: bool,
+    pub ready_for_promotion: bool,
+    pub missing_requirements: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BirthdayEntry {
+    pub person_id: String,
+    pub person_name: String,
+    pub birth_date: NaiveDate,
+    pub birthday: NaiveDate, // Birthday in the requested year
+    pub turning_age: i32,
+    pub photo_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecruitingNeed {
+    pub job_id: String,
+    pub job_name: String,
+    pub year: i32,
+    pub month: u32,
+    pub required_slots: i64, // Sum of people_required over the month's Sundays (with overrides)
+    pub qualified_people: i64, // Active, qualified and not excluded
+    pub available_people: i64, // Of those, not blacked out for this job in this month
+    pub sustainable_slots: f64, // What the pool can supply per month without breaking the rotation rules
+    pub gap: i64,               // Slots the pool cannot cover (0 if covered)
+    pub recruits_needed: i64,   // New people needed to close the gap
+}
+
+/// A servidor's place in the year-end recognition ranking
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecognitionEntry {
+    pub rank: i64, // Ties share a rank
+    pub person_id: String,
+    pub person_name: String,
+    pub services_in_year: i64,
+    pub lifetime_services: i64,       // Up to the end of the year
+    pub milestones_reached: Vec<i64>, // Lifetime milestones crossed during the year
+    pub next_milestone: Option<i64>,
+}
+
+// ============ Notifications ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct Notification {
+    pub id: String,
+    pub person_id: String,
+    pub kind: String,
+    pub message: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+/// A tab of a shared Google Sheet that published assignments are written into
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct SheetExport {
+    pub id: String,
+    pub name: String,
+    pub spreadsheet_id: String,
+    pub sheet_name: String,
+    /// None = every job
+    pub job_id: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub last_pushed_at: Option<DateTime<Utc>>,
+    /// Why the last write failed; cleared by the next one that succeeds
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateSheetExportRequest {
+    pub name: String,
+    /// The spreadsheet's id or its address
+    pub spreadsheet: String,
+    pub sheet_name: String,
+    pub job_id: Option<String>,
+}
+
+/// A person's calendar subscription link; rotating replaces the token, deleting revokes it
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct CalendarFeed {
+    pub person_id: String,
+    /// Also when it was last rotated
+    pub created_at: DateTime<Utc>,
+    pub last_fetched_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedCalendarFeed {
+    #[serde(flatten)]
+    pub feed: CalendarFeed,
+    /// For the link "<api host>/calendar/<token>.ics"; only its digest is kept, so it is
+    /// shown only this once
+    pub token: String,
+}
+
+/// The approved WhatsApp template a kind of notification is sent in
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct WhatsappTemplate {
+    pub kind: String,
+    pub name: String,
+    pub language: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetWhatsappTemplateRequest {
+    pub name: String,
+    pub language: Option<String>,
+}
+
+/// How a servidor wants to be told things; None = each notification's default
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct NotificationPreferences {
+    pub person_id: String,
+    /// email, sms, whatsapp, push or in_app
+    pub channel: Option<String>,
+    /// Days before a service date to be reminded (0 = no reminders)
+    pub reminder_days_before: Option<i32>,
+    /// One message a day with everything instead of each notification right away
+    pub digest: bool,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateNotificationPreferences {
+    pub channel: Option<String>,
+    pub reminder_days_before: Option<i32>,
+    #[serde(default)]
+    pub digest: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DigestRun {
+    /// People who were sent a digest
+    pub sent: usize,
+}
+
+/// A browser's push subscription, as `PushSubscription.toJSON()` gives it
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PushSubscriptionRequest {
+    pub endpoint: String,
+    pub keys: PushSubscriptionKeys,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PushSubscriptionKeys {
+    pub p256dh: String,
+    pub auth: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DeletePushSubscriptionRequest {
+    pub endpoint: String,
+}
+
+/// The VAPID public key browsers subscribe with; None when push is not set up
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PushPublicKey {
+    pub public_key: Option<String>,
+}
+
+/// A servidor's reminder of a service date they are assigned to
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ServiceReminder {
+    pub person_id: String,
+    pub person_name: String,
+    pub service_date_id: String,
+    pub service_date: NaiveDate,
+    /// "Lectores (Salmo)", one per published assignment that day
+    pub jobs: Vec<String>,
+    pub message: String,
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+/// Notification that could not be delivered, kept until a retry succeeds
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct NotificationFailure {
+    pub id: String,
+    pub person_id: String,
+    pub person_name: String,
+    pub channel: String,
+    pub destination: Option<String>,
+    pub kind: String,
+    pub message: String,
+    pub error: String,
+    pub permanent: bool,
+    pub attempts: i32,
+    pub created_at: Option<DateTime<Utc>>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+// ============ Background jobs ============
+
+/// Work queued for the background worker. `payload` and `result` are the kind's JSON: the
+/// request it was queued with and what the run returned.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BackgroundJob {
+    pub id: String,
+    /// generate_batch, send_reminders, send_digests or notify_published
+    pub kind: String,
+    pub payload: serde_json::Value,
+    /// queued, running, done or failed
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_after: DateTime<Utc>,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateBackgroundJobRequest {
+    pub kind: String,
+    #[serde(default)]
+    pub payload: Option<serde_json::Value>,
+}
+
+// ============ Auth events ============
+
+/// A sign-in or password change, from the `auth_events` audit trail
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, ToSchema)]
+pub struct AuthEventEntry {
+    pub id: String,
+    pub event: String,
+    pub user_id: Option<uuid::Uuid>,
+    pub username: Option<String>,
+    pub detail: Option<String>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ============ OIDC sign-in ============
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct OidcLoginRequest {
+    /// The ID token the provider gave the browser
+    pub id_token: String,
+    /// For accounts with two-factor on
+    #[serde(default)]
+    pub totp_code: Option<String>,
+}
+
+/// What the login page needs to offer "Sign in with Google"; `enabled` is false without
+/// OIDC_CLIENT_ID
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OidcSettings {
+    pub enabled: bool,
+    pub client_id: Option<String>,
+    pub issuer: Option<String>,
+    pub hosted_domain: Option<String>,
+}
+
+/// Sign-in email of a user; null clears it
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetUserEmailRequest {
+    pub email: Option<String>,
+}
+
+// ============ Sessions ============
+
+/// A device a user is signed in on
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, ToSchema)]
+pub struct UserSession {
+    pub id: String,
+    /// User agent at login
+    pub device: Option<String>,
+    pub ip: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// The session this request was made with
+    pub current: bool,
+}
+
+/// How many sessions a sign-out everywhere ended
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RevokedSessions {
+    pub revoked: u64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+// ============ Two-factor ============
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TwoFactorStatus {
+    pub enabled: bool,
+    pub enabled_at: Option<DateTime<Utc>>,
+    pub recovery_codes_left: i64,
+}
+
+/// A new secret for the authenticator app, to scan as a QR code or type in
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TwoFactorSetup {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+/// A code from the authenticator app; where noted, a recovery code works too
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TwoFactorCodeRequest {
+    pub code: String,
+}
+
+/// Shown once; only their hashes are kept
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecoveryCodes {
+    pub recovery_codes: Vec<String>,
+}
+
+// ============ Settings ============
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct AppSetting {
+    pub key: String,
+    pub value: String,
+    pub description: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateSetting {
+    pub value: String,
+}
+
+// ============ Parish bootstrap ============
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BootstrapRequest {
+    pub parish_name: String,
+    /// Standard ministries to enable: "monaguillos", "lectores", "monaguillos_jr"
+    pub ministries: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BootstrapResponse {
+    pub parish_name: String,
+    pub jobs: Vec<Job>,
+    // What this call created; empty on a repeated call
+    pub jobs_created: Vec<String>,
+    pub positions_created: u64,
+    pub settings_created: Vec<String>,
+}
+
+// ============ Coordinator scopes ============
+
+/// Jobs a user coordinates; an empty list turns a coordinator back into a servidor
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetJobScopeRequest {
+    pub job_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct CoordinatorScope {
+    pub user_id: uuid::Uuid,
+    pub username: String,
+    pub person_id: Option<String>,
+    pub job_ids: Vec<String>,
+}
+
+// ============ API keys ============
+
+/// A key for a read-only integration; the secret itself is never returned after creation
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct ApiKey {
+    pub id: String,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetApiKeyScopesRequest {
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    /// "psk_<id>.<secret>", for the X-Api-Key header; shown only this once
+    pub key: String,
+}
+
+// ============ Invitations ============
+
+/// A servidor's invitation to set their own password; the link's token is never stored
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct Invitation {
+    pub person_id: String,
+    pub created_by: String,
+    /// When the current link was sent
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub accepted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IssuedInvitation {
+    #[serde(flatten)]
+    pub invitation: Invitation,
+    /// Goes in the link the servidor opens; shown only this once
+    pub token: String,
+}
+
+/// What the invitation page shows before the servidor picks a password
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct InvitationDetails {
+    pub first_name: String,
+    pub last_name: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AcceptInvitationRequest {
+    pub password: String,
+}
+
+// ============ Guardians ============
+
+/// A parent's login, answering for several servidores
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct GuardianAccount {
+    pub user_id: uuid::Uuid,
+    pub username: String,
+    pub person_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateGuardianRequest {
+    pub username: String,
+    pub person_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetGuardianChildrenRequest {
+    pub person_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GuardianWithCredentials {
+    #[serde(flatten)]
+    pub guardian: GuardianAccount,
+    pub password: String,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct GuardianChild {
+    pub person_id: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub photo_url: Option<String>,
+}
+
+// ============ Desktop sync ============
+
+/// People deleted in the desktop app since the last sync
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SyncDeletedPeopleRequest {
+    pub person_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SyncDeletedPeopleResponse {
+    pub deleted_people: Vec<String>,
+    /// Usernames of the accounts removed with their person
+    pub deleted_users: Vec<String>,
+    /// Ids the cloud did not have (already deleted or never synced)
+    pub not_found: Vec<String>,
+}
+
+/// Every person the desktop app still has, to compare against cloud accounts
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReconcileUsersRequest {
+    pub person_ids: Vec<String>,
+}
+
+/// A servidor account whose person is gone: "no_person" when the cloud has no person for it,
+/// "deleted_locally" when the person is missing from the desktop app
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct OrphanUser {
+    pub id: uuid::Uuid,
+    pub username: String,
+    pub person_id: Option<String>,
+    pub person_name: Option<String>,
+    pub reason: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
 // ============ Schedule with full details ============
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ScheduleWithDates {
     #[serde(flatten)]
     pub schedule: Schedule,
     pub service_dates: Vec<ServiceDateWithAssignments>,
+    // Filled by the generator when it had to break a soft rule, and on load for rules that
+    // manual edits can break (max jobs per person per month)
+    #[serde(default)]
+    pub conflicts: Vec<ScheduleConflict>,
+}
+
+/// Someone who could take an open slot, best first
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReplacementCandidate {
+    pub person_id: String,
+    pub person_name: String,
+    pub fairness_score: i64,
+}
+
+/// A slot the absent person holds and who could take it over
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AbsenceImpact {
+    pub assignment_id: String,
+    pub service_date: NaiveDate,
+    pub job_id: String,
+    pub job_name: String,
+    pub position: Option<i32>,
+    pub position_name: Option<String>,
+    pub replacements: Vec<ReplacementCandidate>,
+}
+
+/// Result of simulating a person's absence from a draft schedule (nothing is saved)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AbsenceSimulation {
+    pub schedule_id: String,
+    pub person_id: String,
+    pub person_name: String,
+    pub affected: Vec<AbsenceImpact>,
+    /// Slots that would stay empty because nobody else is eligible
+    pub uncovered: usize,
+}
+
+/// Someone qualified for an empty slot, with the rules that keep them out of it
+/// (`blocked_by` is empty when they can take it as is)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SlotCandidate {
+    pub person_id: String,
+    pub person_name: String,
+    pub fairness_score: i64,
+    pub blocked_by: Vec<String>,
+}
+
+/// An empty slot of a schedule and the best people to fill it.
+/// `assignment_id` is None for positions that were never created.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EmptySlotSuggestions {
+    pub assignment_id: Option<String>,
+    pub service_date: NaiveDate,
+    pub job_id: String,
+    pub job_name: String,
+    pub position_name: Option<String>,
+    pub candidates: Vec<SlotCandidate>,
+}
+
+/// Another filled slot in the same schedule whose person could trade places with the selected one
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SwapSuggestion {
+    pub assignment_id: String,
+    pub person_id: String,
+    pub person_name: String,
+    pub service_date: NaiveDate,
+    pub job_id: String,
+    pub job_name: String,
+    pub position_name: Option<String>,
+    /// How much the two people's combined fairness score drops with the swap (0 = no change)
+    pub fairness_gain: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ScheduleConflict {
+    pub service_date: NaiveDate,
+    pub job_id: String,
+    pub conflict_type: String,
+    pub message: String,
+    pub affected_person_ids: Vec<String>,
+}
+
+/// A person holding slots on more than one service date that falls on the same calendar day,
+/// usually from two schedules that both cover a special date
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DoubleBooking {
+    pub person_id: String,
+    pub person_name: String,
+    pub service_date: NaiveDate,
+    pub slots: Vec<DoubleBookingSlot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct DoubleBookingSlot {
+    pub assignment_id: String,
+    pub schedule_id: String,
+    pub schedule_name: String,
+    pub job_id: String,
+    pub job_name: String,
+    pub position: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ServiceDateWithAssignments {
     #[serde(flatten)]
     pub service_date: ServiceDate,
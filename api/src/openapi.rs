@@ -0,0 +1,11 @@
+//! Prints the OpenAPI description of the API, which the SDKs are generated from
+//!
+//! Run with: cargo run --bin openapi > openapi.json
+//! Or from the repository root: npm run sdk
+
+fn main() {
+    let spec = people_scheduler_api::routes::openapi()
+        .to_pretty_json()
+        .expect("Failed to serialize the OpenAPI description");
+    println!("{}", spec);
+}
@@ -0,0 +1,474 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::PgPool;
+
+use crate::auth::Claims;
+use crate::db::tx::Tx;
+use crate::models::{
+    BootstrapRequest, BootstrapResponse, CoordinatorScope, Job, SetJobScopeRequest, JUNIOR_JOB_ID,
+    SENIOR_JOB_ID,
+};
+
+/// A standard ministry a new parish can enable: job row plus its named positions
+struct MinistryTemplate {
+    id: &'static str,
+    name: &'static str,
+    description: &'static str,
+    people_required: i32,
+    color: &'static str,
+    requires_mentor: bool,
+    positions: &'static [&'static str],
+}
+
+const MINISTRY_TEMPLATES: &[MinistryTemplate] = &[
+    MinistryTemplate {
+        id: "monaguillos",
+        name: "Monaguillos",
+        description: "Altar servers",
+        people_required: 4,
+        color: "#3B82F6",
+        requires_mentor: true,
+        positions: &[
+            "Monaguillo 1",
+            "Monaguillo 2",
+            "Monaguillo 3",
+            "Monaguillo 4",
+        ],
+    },
+    MinistryTemplate {
+        id: "lectores",
+        name: "Lectores",
+        description: "Scripture readers",
+        people_required: 4,
+        color: "#10B981",
+        requires_mentor: false,
+        positions: &["Monitor", "Primera Lectura", "Salmo", "Segunda Lectura"],
+    },
+    MinistryTemplate {
+        id: "monaguillos_jr",
+        name: "Monaguillos Jr.",
+        description: "Monaguillos Junior",
+        people_required: 2,
+        color: "#8B5CF6",
+        requires_mentor: true,
+        positions: &["Monaguillo Jr. 1", "Monaguillo Jr. 2"],
+    },
+];
+
+/// Scheduler settings with their defaults (same values the migrations seed)
+const DEFAULT_SETTINGS: &[(&str, &str, &str)] = &[
+    (
+        "juniors_max_per_senior",
+        "1",
+        "Maximum Monaguillos Jr. per Mass for each senior Monaguillo assigned that date",
+    ),
+    (
+        "junior_promotion_min_age",
+        "10",
+        "Minimum age for a Monaguillo Jr. to be promoted",
+    ),
+    (
+        "junior_promotion_min_services",
+        "8",
+        "Minimum Monaguillos Jr. services before promotion",
+    ),
+    (
+        "username_alias_grace_days",
+        "30",
+        "Days an old username keeps working for login after it is regenerated",
+    ),
+    (
+        "fairness_job_weight",
+        "50",
+        "Percent of the fairness score taken from assignments in the same job (the rest from all assignments this year)",
+    ),
+    (
+        "fairness_window_months",
+        "12",
+        "Months of assignment history the generator counts for fairness (0 = calendar year only)",
+    ),
+    (
+        "fairness_decay_percent",
+        "10",
+        "How much less an assignment counts for each month it is older (0 = no decay)",
+    ),
+    (
+        "co_assignment_penalty",
+        "1",
+        "Fairness points added per time a candidate served with an already picked person in the same job over the last 5 weeks (0 = off)",
+    ),
+    (
+        "max_jobs_per_person_per_month",
+        "0",
+        "Maximum different jobs a person is scheduled in within one month (0 = no limit)",
+    ),
+    (
+        "assignment_visibility_weeks",
+        "0",
+        "Weeks ahead a servidor can see assignments in the app (0 = no limit)",
+    ),
+    (
+        "schedule_name_pattern",
+        "{mes} {año}",
+        "Name given to generated monthly schedules and export titles: {mes} is the month name, {mm} the month number and {año} the year",
+    ),
+    (
+        "mass_time",
+        "",
+        "Time of the Mass the schedules cover, HH:MM (shown on the narthex display; blank = not shown)",
+    ),
+    (
+        "service_location",
+        "",
+        "Where services take place, shown as the location of calendar events (blank = not shown)",
+    ),
+    (
+        "reminder_days_before",
+        "2",
+        "Days before a service date its servidores are reminded (0 = no reminders)",
+    ),
+    (
+        "reminder_channel",
+        "email",
+        "Where reminders are sent: email, sms, whatsapp, push or in_app",
+    ),
+    (
+        "whatsapp_country_code",
+        "502",
+        "Country code added to phone numbers saved without one, for WhatsApp",
+    ),
+    (
+        "publish_channel",
+        "",
+        "Where servidores are told a schedule with their dates was published: email, sms, whatsapp, push or in_app (blank = not told)",
+    ),
+    (
+        "change_channel",
+        "in_app",
+        "Where servidores are told a published assignment of theirs changed: email, sms, whatsapp, push or in_app (blank = push only)",
+    ),
+    (
+        "jwt_lifetime_hours_admin",
+        "24",
+        "Hours an admin login stays valid",
+    ),
+    (
+        "jwt_lifetime_hours_coordinator",
+        "24",
+        "Hours a coordinator login stays valid",
+    ),
+    (
+        "jwt_lifetime_hours_servidor",
+        "24",
+        "Hours a servidor login stays valid",
+    ),
+    (
+        "session_lifetime_days",
+        "30",
+        "Days a login can keep refreshing its token without signing in again",
+    ),
+    (
+        "invitation_lifetime_days",
+        "7",
+        "Days an invitation link can be used to set a password",
+    ),
+];
+
+/// Set up a new parish: enable the chosen standard ministries with their positions and seed the
+/// scheduler settings. Safe to call again: existing jobs, positions and settings are left as they are.
+#[utoipa::path(
+    post,
+    path = "/admin/bootstrap",
+    tag = "admin",
+    responses((status = 200, body = BootstrapResponse))
+)]
+pub async fn bootstrap(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(input): Json<BootstrapRequest>,
+) -> Result<Json<BootstrapResponse>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can bootstrap the parish".to_string(),
+        ));
+    }
+
+    let parish_name = input.parish_name.trim().to_string();
+    if parish_name.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Parish name is required".to_string(),
+        ));
+    }
+
+    let mut templates = Vec::new();
+    for ministry in &input.ministries {
+        let template = MINISTRY_TEMPLATES
+            .iter()
+            .find(|t| t.id == ministry.trim())
+            .ok_or_else(|| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "Unknown ministry: {}. Allowed: {}",
+                        ministry,
+                        MINISTRY_TEMPLATES
+                            .iter()
+                            .map(|t| t.id)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                )
+            })?;
+        templates.push(template);
+    }
+    if templates.iter().any(|t| t.id == JUNIOR_JOB_ID)
+        && !templates.iter().any(|t| t.id == SENIOR_JOB_ID)
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Monaguillos Jr. needs Monaguillos enabled (juniors are capped per senior)".to_string(),
+        ));
+    }
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut jobs_created = Vec::new();
+    let mut positions_created = 0;
+    for template in &templates {
+        let created = sqlx::query(
+            r#"
+            INSERT INTO jobs (id, name, description, people_required, color, active, requires_mentor)
+            VALUES ($1, $2, $3, $4, $5, true, $6)
+            ON CONFLICT (id) DO NOTHING
+            "#,
+        )
+        .bind(template.id)
+        .bind(template.name)
+        .bind(template.description)
+        .bind(template.people_required)
+        .bind(template.color)
+        .bind(template.requires_mentor)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        if created.rows_affected() > 0 {
+            jobs_created.push(template.id.to_string());
+        } else {
+            // Enabling a ministry that was switched off turns it back on, nothing else changes
+            sqlx::query("UPDATE jobs SET active = true, updated_at = NOW() WHERE id = $1 AND active = false")
+                .bind(template.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        }
+
+        for (i, name) in template.positions.iter().enumerate() {
+            let number = i as i32 + 1;
+            let created = sqlx::query(
+                r#"
+                INSERT INTO job_positions (id, job_id, position_number, name)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (job_id, position_number) DO NOTHING
+                "#,
+            )
+            .bind(format!("{}-{}", template.id, number))
+            .bind(template.id)
+            .bind(number)
+            .bind(name)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            positions_created += created.rows_affected();
+        }
+    }
+
+    let mut settings_created = Vec::new();
+    for (key, value, description) in DEFAULT_SETTINGS {
+        let created = sqlx::query(
+            r#"
+            INSERT INTO app_settings (key, value, description)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (key) DO NOTHING
+            "#,
+        )
+        .bind(key)
+        .bind(value)
+        .bind(description)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        if created.rows_affected() > 0 {
+            settings_created.push(key.to_string());
+        }
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO app_settings (key, value, description)
+        VALUES ('parish_name', $1, 'Parish name shown in exports and notifications')
+        ON CONFLICT (key) DO UPDATE SET value = EXCLUDED.value, updated_at = NOW()
+        "#,
+    )
+    .bind(&parish_name)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let jobs = sqlx::query_as::<_, Job>("SELECT * FROM jobs ORDER BY name")
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    tracing::info!(
+        "Bootstrapped parish {}: {} job(s), {} position(s), {} setting(s) created",
+        parish_name,
+        jobs_created.len(),
+        positions_created,
+        settings_created.len()
+    );
+
+    Ok(Json(BootstrapResponse {
+        parish_name,
+        jobs,
+        jobs_created,
+        positions_created,
+        settings_created,
+    }))
+}
+
+// ============ Coordinator scopes ============
+
+const COORDINATOR_SELECT: &str = r#"
+    SELECT u.id as user_id, u.username, u.person_id,
+           COALESCE(array_agg(s.job_id ORDER BY s.job_id)
+                    FILTER (WHERE s.job_id IS NOT NULL), '{}') as job_ids
+    FROM users u
+    LEFT JOIN user_job_scopes s ON s.user_id = u.id
+"#;
+
+#[utoipa::path(
+    get,
+    path = "/admin/coordinators",
+    tag = "admin",
+    responses((status = 200, body = Vec<CoordinatorScope>))
+)]
+pub async fn get_coordinators(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<Vec<CoordinatorScope>>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can see coordinators".to_string(),
+        ));
+    }
+
+    let coordinators = sqlx::query_as::<_, CoordinatorScope>(&format!(
+        "{} WHERE u.role = 'coordinator' GROUP BY u.id ORDER BY u.username",
+        COORDINATOR_SELECT
+    ))
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(coordinators))
+}
+
+/// Make a servidor account the coordinator of some jobs, or hand a coordinator's jobs back
+/// with an empty list. Takes effect the next time they log in.
+#[utoipa::path(
+    put,
+    path = "/admin/users/{id}/job-scope",
+    tag = "admin",
+    responses((status = 200, body = CoordinatorScope))
+)]
+pub async fn set_job_scope(
+    claims: Claims,
+    mut tx: Tx,
+    Path(user_id): Path<uuid::Uuid>,
+    Json(input): Json<SetJobScopeRequest>,
+) -> Result<Json<CoordinatorScope>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can assign coordinators".to_string(),
+        ));
+    }
+
+    let role: String = sqlx::query_scalar("SELECT role FROM users WHERE id = $1 FOR UPDATE")
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "User not found".to_string()))?;
+    if role == "admin" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Admins already manage every job".to_string(),
+        ));
+    }
+
+    let mut job_ids = input.job_ids.clone();
+    job_ids.sort();
+    job_ids.dedup();
+    let known: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE id = ANY($1)")
+        .bind(&job_ids)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if known != job_ids.len() as i64 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Unknown job in job_ids".to_string(),
+        ));
+    }
+
+    sqlx::query("DELETE FROM user_job_scopes WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    sqlx::query("INSERT INTO user_job_scopes (user_id, job_id) SELECT $1, UNNEST($2::varchar[])")
+        .bind(user_id)
+        .bind(&job_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let role = if job_ids.is_empty() {
+        "servidor"
+    } else {
+        "coordinator"
+    };
+    sqlx::query("UPDATE users SET role = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1")
+        .bind(user_id)
+        .bind(role)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let scope = sqlx::query_as::<_, CoordinatorScope>(&format!(
+        "{} WHERE u.id = $1 GROUP BY u.id",
+        COORDINATOR_SELECT
+    ))
+    .bind(user_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(scope))
+}
@@ -0,0 +1,230 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+
+use crate::auth::{self, Claims};
+use crate::models::{ApiKey, CreateApiKeyRequest, CreatedApiKey, SetApiKeyScopesRequest};
+use crate::routes::settings;
+use crate::services::api_keys;
+use crate::services::sheets::{self, SheetRow};
+use crate::services::signage::{self, NextServices};
+use crate::services::visibility;
+
+// ============ Key management (admins) ============
+
+#[utoipa::path(
+    get,
+    path = "/admin/api-keys",
+    tag = "api-keys",
+    responses((status = 200, body = Vec<ApiKey>))
+)]
+pub async fn get_all(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<Vec<ApiKey>>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage API keys")?;
+
+    let keys = api_keys::list(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(keys))
+}
+
+/// The answer carries the key itself; only its hash is kept
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys",
+    tag = "api-keys",
+    responses((status = 201, body = CreatedApiKey))
+)]
+pub async fn create(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(input): Json<CreateApiKeyRequest>,
+) -> Result<(StatusCode, Json<CreatedApiKey>), (StatusCode, String)> {
+    auth::require_admin(&claims, "manage API keys")?;
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Name is required".to_string()));
+    }
+    let scopes =
+        api_keys::normalize_scopes(&input.scopes).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let (api_key, key) = api_keys::create(&pool, name, &scopes, &claims.username)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok((StatusCode::CREATED, Json(CreatedApiKey { api_key, key })))
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/api-keys/{id}",
+    tag = "api-keys",
+    responses((status = 200, body = ApiKey))
+)]
+pub async fn set_scopes(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+    Json(input): Json<SetApiKeyScopesRequest>,
+) -> Result<Json<ApiKey>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage API keys")?;
+    let scopes =
+        api_keys::normalize_scopes(&input.scopes).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let api_key = api_keys::set_scopes(&pool, &id, &scopes)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((StatusCode::NOT_FOUND, "API key not found".to_string()))?;
+
+    Ok(Json(api_key))
+}
+
+/// Revoke a key; integrations using it get 401 from then on
+#[utoipa::path(
+    delete,
+    path = "/admin/api-keys/{id}",
+    tag = "api-keys",
+    responses((status = 204))
+)]
+pub async fn revoke(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage API keys")?;
+
+    let revoked = api_keys::revoke(&pool, &id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if !revoked {
+        return Err((StatusCode::NOT_FOUND, "API key not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============ Integrations (authenticated by key) ============
+
+/// 401 without a working key, 403 when the key lacks the scope
+async fn require_scope(
+    pool: &PgPool,
+    headers: &HeaderMap,
+    scope: &str,
+) -> Result<(), (StatusCode, String)> {
+    let key = headers
+        .get(api_keys::HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let scopes = api_keys::authenticate(pool, key)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((StatusCode::UNAUTHORIZED, "Invalid API key".to_string()))?;
+    if !scopes.iter().any(|s| s == scope) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            format!("This API key does not have the {} scope", scope),
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AssignmentsQuery {
+    /// First service date included; defaults to the start of the current month
+    pub from: Option<NaiveDate>,
+    pub job_id: Option<String>,
+}
+
+/// Published assignments, the same rows as the spreadsheet feeds. Scope `assignments:read`.
+#[utoipa::path(
+    get,
+    path = "/integrations/assignments",
+    params(AssignmentsQuery),
+    security(("api_key" = [])),
+    tag = "api-keys",
+    responses((status = 200, body = Vec<SheetRow>))
+)]
+pub async fn get_assignments(
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    Query(query): Query<AssignmentsQuery>,
+) -> Result<Json<Vec<SheetRow>>, (StatusCode, String)> {
+    require_scope(&pool, &headers, "assignments:read").await?;
+
+    let from = query.from.unwrap_or_else(|| {
+        let today = Utc::now().date_naive();
+        today.with_day(1).unwrap_or(today)
+    });
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let rows = sheets::published_rows(&mut conn, from, query.job_id.as_deref())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(rows))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NextServiceQuery {
+    /// How many upcoming service dates to return (1-10, default 1)
+    pub count: Option<i64>,
+}
+
+/// Who serves at the next service date(s), for the sacristy display; dates past the
+/// servidores' visibility window stay out. Scope `next_service:read`.
+#[utoipa::path(
+    get,
+    path = "/integrations/next-service",
+    params(NextServiceQuery),
+    security(("api_key" = [])),
+    tag = "api-keys",
+    responses((status = 200, body = NextServices))
+)]
+pub async fn get_next_service(
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    Query(query): Query<NextServiceQuery>,
+) -> Result<Json<NextServices>, (StatusCode, String)> {
+    require_scope(&pool, &headers, "next_service:read").await?;
+
+    let count = query.count.unwrap_or(1);
+    if !(1..=10).contains(&count) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "count must be between 1 and 10".to_string(),
+        ));
+    }
+    let until = visibility::horizon(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let mass_time = settings::get_string(&pool, "mass_time")
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let (dates, slots) = signage::load_upcoming(&mut conn, Utc::now().date_naive(), until, count)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(signage::build_next(
+        mass_time.as_deref(),
+        dates,
+        &slots,
+    )))
+}
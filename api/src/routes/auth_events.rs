@@ -0,0 +1,68 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+
+use crate::auth::Claims;
+use crate::db::filters::Filter;
+use crate::models::AuthEventEntry;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuthEventsQuery {
+    /// e.g. login_failed
+    pub event: Option<String>,
+    /// As typed at the login, or the account's
+    pub username: Option<String>,
+    pub ip: Option<String>,
+    /// Only events at or after this time
+    pub since: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+}
+
+/// Recent sign-ins, failed attempts, password changes and resets, newest first
+#[utoipa::path(
+    get,
+    path = "/admin/auth-events",
+    params(AuthEventsQuery),
+    tag = "auth-events",
+    responses((status = 200, body = Vec<AuthEventEntry>))
+)]
+pub async fn get_all(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<AuthEventsQuery>,
+) -> Result<Json<Vec<AuthEventEntry>>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can see authentication activity".to_string(),
+        ));
+    }
+
+    let mut filter = Filter::new(
+        "SELECT id, event, user_id, username, detail, ip, user_agent, created_at FROM auth_events",
+    );
+    filter
+        .eq("event", query.event.as_ref())
+        .eq("username", query.username.as_ref())
+        .eq("ip", query.ip.as_ref())
+        .gte("created_at", query.since);
+
+    let mut builder = filter.into_builder();
+    builder
+        .push(" ORDER BY created_at DESC LIMIT ")
+        .push_bind(query.limit.unwrap_or(100).clamp(1, 1000));
+    let events = builder
+        .build_query_as::<AuthEventEntry>()
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(events))
+}
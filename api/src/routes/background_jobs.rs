@@ -0,0 +1,145 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+
+use crate::auth::{self, Claims};
+use crate::models::{BackgroundJob, CreateBackgroundJobRequest};
+use crate::services::{background_jobs, notifications, push, sheets};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct JobsQuery {
+    /// queued, running, done or failed
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Recent jobs, newest first
+#[utoipa::path(
+    get,
+    path = "/admin/background-jobs",
+    params(JobsQuery),
+    tag = "background-jobs",
+    responses((status = 200, body = Vec<BackgroundJob>))
+)]
+pub async fn list(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<JobsQuery>,
+) -> Result<Json<Vec<BackgroundJob>>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage background jobs")?;
+
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    let jobs = background_jobs::list(&pool, query.status.as_deref(), limit)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(jobs))
+}
+
+/// Queue a job; the answer is 202 with the job, whose status can then be polled
+#[utoipa::path(
+    post,
+    path = "/admin/background-jobs",
+    tag = "background-jobs",
+    responses((status = 202, body = BackgroundJob))
+)]
+pub async fn create(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(input): Json<CreateBackgroundJobRequest>,
+) -> Result<(StatusCode, Json<BackgroundJob>), (StatusCode, String)> {
+    auth::require_admin(&claims, "manage background jobs")?;
+
+    let payload = input.payload.unwrap_or_else(|| serde_json::json!({}));
+    background_jobs::validate(&input.kind, &payload).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let job = background_jobs::enqueue(&pool, &input.kind, &payload, Some(&claims.username))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// One job's status and result. Whoever queued it can follow it, not only admins.
+#[utoipa::path(
+    get,
+    path = "/background-jobs/{id}",
+    tag = "background-jobs",
+    responses((status = 200, body = BackgroundJob))
+)]
+pub async fn get_one(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<BackgroundJob>, (StatusCode, String)> {
+    let job = background_jobs::get(&pool, &id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((StatusCode::NOT_FOUND, "Job not found".to_string()))?;
+    if !claims.is_admin() && job.created_by.as_deref() != Some(claims.username.as_str()) {
+        return Err((StatusCode::NOT_FOUND, "Job not found".to_string()));
+    }
+
+    Ok(Json(job))
+}
+
+/// Queue a failed job again
+#[utoipa::path(
+    post,
+    path = "/admin/background-jobs/{id}/retry",
+    tag = "background-jobs",
+    responses((status = 200, body = BackgroundJob))
+)]
+pub async fn retry(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<BackgroundJob>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage background jobs")?;
+
+    let job = background_jobs::retry(&pool, &id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((
+            StatusCode::CONFLICT,
+            "Only failed jobs can be retried".to_string(),
+        ))?;
+
+    Ok(Json(job))
+}
+
+/// Work through the due jobs now, then deliver the queued notifications and pushes and update
+/// the Google Sheets exports. The local server's worker does this on its own; on Lambda a scheduler calls it
+/// every few minutes.
+#[utoipa::path(
+    post,
+    path = "/admin/background-jobs/run",
+    tag = "background-jobs",
+    responses((status = 200, body = Vec<BackgroundJob>))
+)]
+pub async fn run(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<Vec<BackgroundJob>>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage background jobs")?;
+
+    let ran = background_jobs::run_pending(&pool, background_jobs::RUN_LIMIT)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    notifications::send_queued(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    push::send_pending(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    sheets::sync(&pool, None, false)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(ran))
+}
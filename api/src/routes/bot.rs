@@ -0,0 +1,191 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+use subtle::ConstantTimeEq;
+use utoipa::ToSchema;
+
+use crate::routes::schedules::published_assignments;
+use crate::routes::{settings, unavailability};
+use crate::services::chat_bot::{self, BotCommand};
+use crate::services::{signage, visibility};
+
+/// A message relayed from the WhatsApp/Telegram bot
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BotMessage {
+    /// The sender's phone number in any format, e.g. "whatsapp:+50255551234"
+    pub from: String,
+    pub text: String,
+}
+
+/// The text the relay sends back to the chat
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BotReply {
+    pub reply: String,
+}
+
+#[derive(FromRow)]
+struct SenderRow {
+    id: String,
+    first_name: String,
+    last_name: String,
+}
+
+/// The relay's token is the CHAT_BOT_TOKEN environment variable; without it the bot endpoints
+/// do not exist
+fn check_token(token: &str) -> Result<(), (StatusCode, String)> {
+    let expected = std::env::var("CHAT_BOT_TOKEN").unwrap_or_default();
+    // Constant-time, so the answer's timing does not give the token away a byte at a time
+    if expected.is_empty() || !bool::from(token.as_bytes().ct_eq(expected.as_bytes())) {
+        return Err((StatusCode::NOT_FOUND, "Not found".to_string()));
+    }
+    Ok(())
+}
+
+fn reply(text: impl Into<String>) -> Json<BotReply> {
+    Json(BotReply { reply: text.into() })
+}
+
+/// Webhook for the parish chat bot. The relay authenticates with the token in the path. The sender's
+/// phone is matched to active people, so one family number can speak for several servidores.
+/// Answers always come back as 200 with a reply so the relay just forwards them.
+#[utoipa::path(
+    post,
+    path = "/bot/{token}",
+    tag = "bot",
+    responses((status = 200, body = BotReply))
+)]
+pub async fn webhook(
+    State(pool): State<PgPool>,
+    Path(token): Path<String>,
+    Json(message): Json<BotMessage>,
+) -> Result<Json<BotReply>, (StatusCode, String)> {
+    check_token(&token)?;
+
+    let Some(phone) = chat_bot::phone_key(&message.from) else {
+        return Ok(reply("No pudimos leer su número de teléfono."));
+    };
+    let senders = sqlx::query_as::<_, SenderRow>(
+        r#"
+        SELECT id, first_name, last_name FROM people
+        WHERE active = true AND RIGHT(regexp_replace(phone, '\D', '', 'g'), 8) = $1
+        ORDER BY first_name, last_name
+        "#,
+    )
+    .bind(&phone)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if senders.is_empty() {
+        return Ok(reply(
+            "Su número no está registrado. Pida a su coordinador que lo agregue a su ficha.",
+        ));
+    }
+
+    // The bot answers as a servidor would see things in the app
+    let horizon = visibility::horizon(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let today = Utc::now().date_naive();
+
+    match chat_bot::parse(&message.text) {
+        BotCommand::WhoServes => {
+            let mass_time = settings::get_string(&pool, "mass_time")
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            let mut conn = pool
+                .acquire()
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            let (dates, slots) = signage::load_upcoming(&mut conn, today, horizon, 1)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            let next = signage::build_next(mass_time.as_deref(), dates, &slots);
+            Ok(reply(chat_bot::format_next_service(next.services.first())))
+        }
+        BotCommand::MyDates => {
+            let mut messages = Vec::new();
+            for sender in &senders {
+                let upcoming: Vec<_> = published_assignments(&pool, &sender.id, horizon)
+                    .await?
+                    .into_iter()
+                    .filter(|a| a.service_date >= today)
+                    .collect();
+                messages.push(chat_bot::format_dates(&sender.first_name, &upcoming));
+            }
+            Ok(reply(messages.join("\n\n")))
+        }
+        BotCommand::CannotServe { day, month, name } => {
+            let Some(date) = chat_bot::resolve_date(today, day, month) else {
+                return Ok(reply(
+                    "No reconocimos esa fecha. Escriba por ejemplo \"no puedo el 14\".",
+                ));
+            };
+            let matching: Vec<&SenderRow> = senders
+                .iter()
+                .filter(|s| {
+                    name.as_ref().is_none_or(|name| {
+                        let full =
+                            chat_bot::normalize(&format!("{} {}", s.first_name, s.last_name));
+                        full.starts_with(name.as_str())
+                    })
+                })
+                .collect();
+            let sender = match matching.as_slice() {
+                [sender] => *sender,
+                [] => {
+                    return Ok(reply(format!(
+                        "No encontramos a \"{}\" con este número.",
+                        name.unwrap_or_default()
+                    )))
+                }
+                _ => {
+                    let names: Vec<&str> = matching.iter().map(|s| s.first_name.as_str()).collect();
+                    return Ok(reply(format!(
+                        "Este número es de varios servidores ({}). Escriba por ejemplo \"no puedo el {} {}\".",
+                        names.join(", "),
+                        day,
+                        names[0]
+                    )));
+                }
+            };
+
+            let mut tx = pool
+                .begin()
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            unavailability::set_availability(
+                &mut tx,
+                &sender.id,
+                date,
+                false,
+                Some("Avisó por chat"),
+            )
+            .await?;
+            tx.commit()
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+            let mut text = format!(
+                "Anotado: {} no puede el {}.",
+                sender.first_name,
+                chat_bot::format_date(date)
+            );
+            let already_serving = published_assignments(&pool, &sender.id, None)
+                .await?
+                .iter()
+                .any(|a| a.service_date == date);
+            if already_serving {
+                text.push_str(
+                    " Ya tenía un servicio asignado ese día; avise también a su coordinador.",
+                );
+            }
+            Ok(reply(text))
+        }
+        BotCommand::Help => Ok(reply(chat_bot::HELP)),
+    }
+}
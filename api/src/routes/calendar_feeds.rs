@@ -0,0 +1,163 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use sqlx::PgPool;
+
+use crate::auth::{self, Claims};
+use crate::models::{CalendarFeed, CreatedCalendarFeed};
+use crate::routes::schedules::person_calendar;
+use crate::services::{tokens, visibility};
+
+// ============ Calendar subscriptions ============
+//
+// Phone calendars subscribe to `https://.../calendar/<token>.ics` and re-fetch it on their own,
+// so a servidor's calendar follows the published assignments without another download. The
+// token is the only credential (calendar apps cannot send a JWT); rotating it breaks old links.
+// Only the token's digest is stored, so the link can be shown once, when it is created.
+
+const FEED_COLUMNS: &str = "person_id, created_at, last_fetched_at";
+
+#[utoipa::path(
+    get,
+    path = "/people/{id}/calendar-feed",
+    tag = "calendar-feeds",
+    responses((status = 200, body = Option<CalendarFeed>))
+)]
+pub async fn get_feed(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+) -> Result<Json<Option<CalendarFeed>>, (StatusCode, String)> {
+    auth::require_self_or_person_scope(&pool, &claims, &person_id).await?;
+
+    let feed = sqlx::query_as::<_, CalendarFeed>(&format!(
+        "SELECT {} FROM calendar_feeds WHERE person_id = $1",
+        FEED_COLUMNS
+    ))
+    .bind(&person_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(feed))
+}
+
+/// Create the person's feed, or give it a new token when it exists (revoking the old link).
+/// The answer is the only time the token is shown.
+#[utoipa::path(
+    post,
+    path = "/people/{id}/calendar-feed",
+    tag = "calendar-feeds",
+    responses((status = 200, body = CreatedCalendarFeed))
+)]
+pub async fn rotate_feed(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+) -> Result<Json<CreatedCalendarFeed>, (StatusCode, String)> {
+    auth::require_self_or_person_scope(&pool, &claims, &person_id).await?;
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM people WHERE id = $1)")
+        .bind(&person_id)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, "Person not found".to_string()));
+    }
+
+    let token = tokens::generate_token();
+    let feed = sqlx::query_as::<_, CalendarFeed>(&format!(
+        r#"
+        INSERT INTO calendar_feeds (person_id, token_hash)
+        VALUES ($1, $2)
+        ON CONFLICT (person_id) DO UPDATE
+            SET token_hash = EXCLUDED.token_hash, created_at = NOW(), last_fetched_at = NULL
+        RETURNING {}
+        "#,
+        FEED_COLUMNS
+    ))
+    .bind(&person_id)
+    .bind(tokens::digest(&token))
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(CreatedCalendarFeed { feed, token }))
+}
+
+/// Revoke the person's feed; subscribed calendars stop updating
+#[utoipa::path(
+    delete,
+    path = "/people/{id}/calendar-feed",
+    tag = "calendar-feeds",
+    responses((status = 204))
+)]
+pub async fn delete_feed(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    auth::require_self_or_person_scope(&pool, &claims, &person_id).await?;
+
+    let result = sqlx::query("DELETE FROM calendar_feeds WHERE person_id = $1")
+        .bind(&person_id)
+        .execute(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "Feed not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Public: the token is the only credential, so an unknown token is a plain 404. The path
+/// segment carries the `.ics` extension some calendar apps insist on.
+#[utoipa::path(
+    get,
+    path = "/calendar/{token}",
+    tag = "calendar-feeds",
+    responses((status = 200, content_type = "text/calendar", body = String))
+)]
+pub async fn get_feed_ics(
+    State(pool): State<PgPool>,
+    Path(token): Path<String>,
+) -> Result<Response, (StatusCode, String)> {
+    let token = token.strip_suffix(".ics").unwrap_or(&token);
+
+    let person_id: Option<String> = sqlx::query_scalar(
+        r#"
+        UPDATE calendar_feeds f SET last_fetched_at = NOW()
+        FROM people p
+        WHERE f.token_hash = $1 AND p.id = f.person_id AND p.active = true
+        RETURNING f.person_id
+        "#,
+    )
+    .bind(tokens::digest(token))
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let Some(person_id) = person_id else {
+        return Err((StatusCode::NOT_FOUND, "Feed not found".to_string()));
+    };
+
+    // The feed shows what the servidor would see in the app
+    let horizon = visibility::horizon(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let calendar = person_calendar(&pool, &person_id, horizon).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (header::CACHE_CONTROL, "private, max-age=3600"),
+        ],
+        calendar,
+    )
+        .into_response())
+}
@@ -0,0 +1,241 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::auth::{self, hash_password, Claims};
+use crate::db::tx::Tx;
+use crate::models::{
+    CreateGuardianRequest, GuardianAccount, GuardianChild, GuardianWithCredentials,
+    SetGuardianChildrenRequest,
+};
+use crate::routes::people::{generate_random_password, username_exists};
+
+const GUARDIAN_SELECT: &str = r#"
+    SELECT u.id as user_id, u.username,
+           COALESCE(array_agg(g.person_id ORDER BY g.person_id)
+                    FILTER (WHERE g.person_id IS NOT NULL), '{}') as person_ids
+    FROM users u
+    LEFT JOIN guardian_children g ON g.user_id = u.id
+"#;
+
+/// Sorted, without repeats, and every one a known person
+async fn checked_person_ids(
+    conn: &mut PgConnection,
+    person_ids: &[String],
+) -> Result<Vec<String>, (StatusCode, String)> {
+    let mut person_ids = person_ids.to_vec();
+    person_ids.sort();
+    person_ids.dedup();
+    if person_ids.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "A guardian needs at least one child".to_string(),
+        ));
+    }
+
+    let known: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM people WHERE id = ANY($1)")
+        .bind(&person_ids)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if known != person_ids.len() as i64 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Unknown person in person_ids".to_string(),
+        ));
+    }
+
+    Ok(person_ids)
+}
+
+async fn replace_children(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    person_ids: &[String],
+) -> Result<GuardianAccount, (StatusCode, String)> {
+    sqlx::query("DELETE FROM guardian_children WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    sqlx::query(
+        "INSERT INTO guardian_children (user_id, person_id) SELECT $1, UNNEST($2::varchar[])",
+    )
+    .bind(user_id)
+    .bind(person_ids)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    sqlx::query_as::<_, GuardianAccount>(&format!(
+        "{} WHERE u.id = $1 GROUP BY u.id",
+        GUARDIAN_SELECT
+    ))
+    .bind(user_id)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/guardians",
+    tag = "guardians",
+    responses((status = 200, body = Vec<GuardianAccount>))
+)]
+pub async fn get_all(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<Vec<GuardianAccount>>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage guardian accounts")?;
+
+    let guardians = sqlx::query_as::<_, GuardianAccount>(&format!(
+        "{} WHERE u.role = 'guardian' GROUP BY u.id ORDER BY u.username",
+        GUARDIAN_SELECT
+    ))
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(guardians))
+}
+
+/// One login for a parent of several servidores; the generated password is shown only once
+#[utoipa::path(
+    post,
+    path = "/admin/guardians",
+    tag = "guardians",
+    responses((status = 201, body = GuardianWithCredentials))
+)]
+pub async fn create(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
+    Json(input): Json<CreateGuardianRequest>,
+) -> Result<(StatusCode, Json<GuardianWithCredentials>), (StatusCode, String)> {
+    auth::require_admin(&claims, "manage guardian accounts")?;
+
+    let username = input.username.trim().to_lowercase();
+    if username.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Username is required".to_string()));
+    }
+    if username_exists(&pool, &username).await? {
+        return Err((StatusCode::CONFLICT, "Username already exists".to_string()));
+    }
+    let person_ids = checked_person_ids(&mut tx, &input.person_ids).await?;
+
+    let password = generate_random_password();
+    let password_hash =
+        hash_password(&password).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let user_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO users (username, password_hash, role) VALUES ($1, $2, 'guardian') RETURNING id",
+    )
+    .bind(&username)
+    .bind(&password_hash)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let guardian = replace_children(&mut tx, user_id, &person_ids).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(GuardianWithCredentials { guardian, password }),
+    ))
+}
+
+/// Replace the children a guardian answers for
+#[utoipa::path(
+    put,
+    path = "/admin/guardians/{id}/children",
+    tag = "guardians",
+    responses((status = 200, body = GuardianAccount))
+)]
+pub async fn set_children(
+    claims: Claims,
+    mut tx: Tx,
+    Path(user_id): Path<Uuid>,
+    Json(input): Json<SetGuardianChildrenRequest>,
+) -> Result<Json<GuardianAccount>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage guardian accounts")?;
+
+    let role: String = sqlx::query_scalar("SELECT role FROM users WHERE id = $1 FOR UPDATE")
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Guardian not found".to_string()))?;
+    if role != "guardian" {
+        return Err((StatusCode::NOT_FOUND, "Guardian not found".to_string()));
+    }
+
+    let person_ids = checked_person_ids(&mut tx, &input.person_ids).await?;
+    let guardian = replace_children(&mut tx, user_id, &person_ids).await?;
+
+    Ok(Json(guardian))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/guardians/{id}",
+    tag = "guardians",
+    responses((status = 204))
+)]
+pub async fn delete(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(user_id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage guardian accounts")?;
+
+    let result = sqlx::query("DELETE FROM users WHERE id = $1 AND role = 'guardian'")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "Guardian not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The children of the signed-in guardian, for picking whose absences to manage
+#[utoipa::path(
+    get,
+    path = "/my-children",
+    tag = "guardians",
+    responses((status = 200, body = Vec<GuardianChild>))
+)]
+pub async fn get_my_children(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<Vec<GuardianChild>>, (StatusCode, String)> {
+    if !claims.is_guardian() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only guardian accounts have children".to_string(),
+        ));
+    }
+
+    let children = sqlx::query_as::<_, GuardianChild>(
+        r#"
+        SELECT p.id as person_id, p.first_name, p.last_name, p.photo_url
+        FROM guardian_children g
+        JOIN people p ON p.id = g.person_id
+        WHERE g.user_id = $1
+        ORDER BY p.first_name, p.last_name
+        "#,
+    )
+    .bind(Uuid::parse_str(&claims.sub).ok())
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(children))
+}
@@ -0,0 +1,303 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use sqlx::PgPool;
+
+use crate::auth::{self, hash_password, Claims, LoginResponse, User};
+use crate::models::{AcceptInvitationRequest, Invitation, InvitationDetails, IssuedInvitation};
+use crate::routes::people::generate_username;
+use crate::routes::settings;
+use crate::services::auth_events::{self, AuthEvent, RequestOrigin};
+use crate::services::invitations;
+
+// ============ Sending invitations (admins and coordinators) ============
+
+async fn require_invite_rights(
+    pool: &PgPool,
+    claims: &Claims,
+    person_id: &str,
+) -> Result<(), (StatusCode, String)> {
+    if !claims.is_admin() && !claims.is_coordinator() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins and coordinators can invite servidores".to_string(),
+        ));
+    }
+    auth::require_person_scope(pool, claims, person_id).await
+}
+
+/// A new link for the person, who must not have a login yet
+async fn issue(
+    pool: &PgPool,
+    claims: &Claims,
+    person_id: &str,
+) -> Result<IssuedInvitation, (StatusCode, String)> {
+    let has_account: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE person_id = $1)")
+            .bind(person_id)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if has_account {
+        return Err((
+            StatusCode::CONFLICT,
+            "User account already exists for this person".to_string(),
+        ));
+    }
+
+    let lifetime_days = settings::get_i64(
+        pool,
+        "invitation_lifetime_days",
+        invitations::DEFAULT_LIFETIME_DAYS,
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let (invitation, token) = invitations::issue(pool, person_id, &claims.username, lifetime_days)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(IssuedInvitation { invitation, token })
+}
+
+/// The person's invitation: when it was sent, until when it works, whether it was used
+#[utoipa::path(
+    get,
+    path = "/people/{id}/invitation",
+    tag = "invitations",
+    responses((status = 200, body = Invitation))
+)]
+pub async fn get_for_person(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+) -> Result<Json<Invitation>, (StatusCode, String)> {
+    require_invite_rights(&pool, &claims, &person_id).await?;
+
+    let invitation = invitations::for_person(&pool, &person_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((StatusCode::NOT_FOUND, "Invitation not found".to_string()))?;
+
+    Ok(Json(invitation))
+}
+
+/// Invite a person who has no login; 409 while an earlier invitation still works (resend it
+/// instead)
+#[utoipa::path(
+    post,
+    path = "/people/{id}/invitation",
+    tag = "invitations",
+    responses((status = 201, body = IssuedInvitation))
+)]
+pub async fn create(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+) -> Result<(StatusCode, Json<IssuedInvitation>), (StatusCode, String)> {
+    require_invite_rights(&pool, &claims, &person_id).await?;
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM people WHERE id = $1)")
+        .bind(&person_id)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, "Person not found".to_string()));
+    }
+    let open: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS(SELECT 1 FROM invitations
+                      WHERE person_id = $1 AND accepted_at IS NULL AND expires_at > NOW())
+        "#,
+    )
+    .bind(&person_id)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if open {
+        return Err((
+            StatusCode::CONFLICT,
+            "An invitation is already pending; resend it for a new link".to_string(),
+        ));
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(issue(&pool, &claims, &person_id).await?),
+    ))
+}
+
+/// A new link with a new expiry, for a lost or expired one; the previous link stops working
+#[utoipa::path(
+    post,
+    path = "/people/{id}/invitation/resend",
+    tag = "invitations",
+    responses((status = 200, body = IssuedInvitation))
+)]
+pub async fn resend(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+) -> Result<Json<IssuedInvitation>, (StatusCode, String)> {
+    require_invite_rights(&pool, &claims, &person_id).await?;
+
+    invitations::for_person(&pool, &person_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((StatusCode::NOT_FOUND, "Invitation not found".to_string()))?;
+
+    Ok(Json(issue(&pool, &claims, &person_id).await?))
+}
+
+/// Withdraw a pending invitation; its link stops working
+#[utoipa::path(
+    delete,
+    path = "/people/{id}/invitation",
+    tag = "invitations",
+    responses((status = 204))
+)]
+pub async fn cancel(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    require_invite_rights(&pool, &claims, &person_id).await?;
+
+    let result =
+        sqlx::query("DELETE FROM invitations WHERE person_id = $1 AND accepted_at IS NULL")
+            .bind(&person_id)
+            .execute(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "Invitation not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============ Accepting (public, the token is the credential) ============
+
+const NOT_FOUND: &str = "This invitation link is not valid or has expired";
+
+/// Who the link is for, so the page can greet them before they pick a password
+#[utoipa::path(
+    get,
+    path = "/invitations/{token}",
+    tag = "invitations",
+    responses((status = 200, body = InvitationDetails))
+)]
+pub async fn get_by_token(
+    State(pool): State<PgPool>,
+    Path(token): Path<String>,
+) -> Result<Json<InvitationDetails>, (StatusCode, String)> {
+    let (_, person_id) = invitations::find_open(&pool, &token)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((StatusCode::NOT_FOUND, NOT_FOUND.to_string()))?;
+
+    let details = sqlx::query_as::<_, InvitationDetails>(
+        r#"
+        SELECT p.first_name, p.last_name, i.expires_at
+        FROM invitations i JOIN people p ON p.id = i.person_id
+        WHERE i.person_id = $1
+        "#,
+    )
+    .bind(&person_id)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(details))
+}
+
+/// Set the password, create the login and sign in with it. The link works once.
+#[utoipa::path(
+    post,
+    path = "/invitations/{token}/accept",
+    tag = "invitations",
+    responses((status = 200, body = LoginResponse))
+)]
+pub async fn accept(
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    Path(token): Path<String>,
+    Json(input): Json<AcceptInvitationRequest>,
+) -> Result<Json<LoginResponse>, (StatusCode, String)> {
+    if input.password.len() < 6 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Password must be at least 6 characters".to_string(),
+        ));
+    }
+    let (invitation_id, person_id) = invitations::find_open(&pool, &token)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((StatusCode::NOT_FOUND, NOT_FOUND.to_string()))?;
+
+    let (first_name, last_name): (String, String) =
+        sqlx::query_as("SELECT first_name, last_name FROM people WHERE id = $1")
+            .bind(&person_id)
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let username = generate_username(&pool, &first_name, &last_name).await?;
+    let password_hash = hash_password(&input.password)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    // Only the first of two requests racing with the same link gets through
+    let used = sqlx::query(
+        "UPDATE invitations SET accepted_at = NOW() WHERE id = $1 AND accepted_at IS NULL",
+    )
+    .bind(&invitation_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if used.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, NOT_FOUND.to_string()));
+    }
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        INSERT INTO users (username, password_hash, role, person_id)
+        SELECT $1, $2, 'servidor', $3
+        WHERE NOT EXISTS (SELECT 1 FROM users WHERE person_id = $3)
+        RETURNING id, username, password_hash, role, person_id
+        "#,
+    )
+    .bind(&username)
+    .bind(&password_hash)
+    .bind(&person_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or((
+        StatusCode::CONFLICT,
+        "User account already exists for this person".to_string(),
+    ))?;
+    tx.commit()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let origin = RequestOrigin::from_headers(&headers);
+    auth_events::record(
+        &pool,
+        &AuthEvent {
+            event: "invitation_accepted",
+            user_id: Some(user.id),
+            username: Some(&user.username),
+            detail: None,
+        },
+        &origin,
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(auth::issue_login(&pool, user, &origin).await?))
+}
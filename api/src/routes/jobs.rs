@@ -5,8 +5,18 @@ use axum::{
 };
 use sqlx::PgPool;
 
-use crate::models::{Job, JobPosition};
+use crate::auth::{self, Claims};
+use crate::models::{
+    Job, JobPosition, RotationStatus, UpdateJobNoviceLimit, UpdateJobRequirements,
+};
+use crate::services::rotation;
 
+#[utoipa::path(
+    get,
+    path = "/jobs",
+    tag = "jobs",
+    responses((status = 200, body = Vec<Job>))
+)]
 pub async fn get_all(State(pool): State<PgPool>) -> Result<Json<Vec<Job>>, (StatusCode, String)> {
     let jobs = sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE active = true ORDER BY name")
         .fetch_all(&pool)
@@ -16,6 +26,12 @@ pub async fn get_all(State(pool): State<PgPool>) -> Result<Json<Vec<Job>>, (Stat
     Ok(Json(jobs))
 }
 
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}/positions",
+    tag = "jobs",
+    responses((status = 200, body = Vec<JobPosition>))
+)]
 pub async fn get_positions(
     State(pool): State<PgPool>,
     Path(job_id): Path<String>,
@@ -30,3 +46,141 @@ pub async fn get_positions(
 
     Ok(Json(positions))
 }
+
+/// Admins, or a coordinator of the job
+#[utoipa::path(
+    put,
+    path = "/jobs/{id}/novice-limit",
+    tag = "jobs",
+    responses((status = 200, body = Job))
+)]
+pub async fn update_novice_limit(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(job_id): Path<String>,
+    Json(input): Json<UpdateJobNoviceLimit>,
+) -> Result<Json<Job>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "change a job's novice limit")?;
+    auth::require_job_scope(&claims, &job_id)?;
+    if input.max_novices_per_date.is_some_and(|n| n < 0)
+        || input.novice_threshold.is_some_and(|k| k < 1)
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "max_novices_per_date must be >= 0 and novice_threshold >= 1".to_string(),
+        ));
+    }
+
+    // max_novices_per_date is always replaced (null removes the limit)
+    let job = sqlx::query_as::<_, Job>(
+        r#"
+        UPDATE jobs
+        SET max_novices_per_date = $1, novice_threshold = COALESCE($2, novice_threshold)
+        WHERE id = $3
+        RETURNING *
+        "#,
+    )
+    .bind(input.max_novices_per_date)
+    .bind(input.novice_threshold)
+    .bind(&job_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or((StatusCode::NOT_FOUND, "Job not found".to_string()))?;
+
+    Ok(Json(job))
+}
+
+/// Admins, or a coordinator of the job
+#[utoipa::path(
+    put,
+    path = "/jobs/{id}/requirements",
+    tag = "jobs",
+    responses((status = 200, body = Job))
+)]
+pub async fn update_requirements(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(job_id): Path<String>,
+    Json(input): Json<UpdateJobRequirements>,
+) -> Result<Json<Job>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "change a job's requirements")?;
+    auth::require_job_scope(&claims, &job_id)?;
+    let job = sqlx::query_as::<_, Job>(
+        r#"
+        UPDATE jobs
+        SET requires_first_communion = COALESCE($1, requires_first_communion),
+            requires_mentor = COALESCE($2, requires_mentor)
+        WHERE id = $3
+        RETURNING *
+        "#,
+    )
+    .bind(input.requires_first_communion)
+    .bind(input.requires_mentor)
+    .bind(&job_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or((StatusCode::NOT_FOUND, "Job not found".to_string()))?;
+
+    Ok(Json(job))
+}
+
+/// Where each qualified person stands in the position rotation of a job.
+/// People who still owe position 1 come first, closest to finishing their cycle first.
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}/rotation",
+    tag = "jobs",
+    responses((status = 200, body = Vec<RotationStatus>))
+)]
+pub async fn get_rotation(
+    State(pool): State<PgPool>,
+    Path(job_id): Path<String>,
+) -> Result<Json<Vec<RotationStatus>>, (StatusCode, String)> {
+    let job = sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE id = $1")
+        .bind(&job_id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Job not found".to_string()))?;
+
+    let people: Vec<(String, String)> = sqlx::query_as(
+        r#"
+        SELECT p.id, p.first_name || ' ' || p.last_name
+        FROM people p
+        JOIN person_jobs pj ON p.id = pj.person_id
+        WHERE pj.job_id = $1 AND p.active = true
+        ORDER BY p.last_name, p.first_name
+        "#,
+    )
+    .bind(&job_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut result = Vec::new();
+    for (person_id, person_name) in people {
+        let (cycle, done_positions) = rotation::load(&mut conn, &person_id, &job_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        let pending_positions = rotation::pending_positions(&done_positions, job.people_required);
+
+        result.push(RotationStatus {
+            person_id,
+            person_name,
+            cycle,
+            done_positions,
+            pending_positions,
+        });
+    }
+
+    result.sort_by_key(|r| (!r.pending_positions.contains(&1), r.pending_positions.len()));
+
+    Ok(Json(result))
+}
@@ -1,19 +1,43 @@
+pub mod admin;
+pub mod api_keys;
+pub mod auth_events;
+pub mod background_jobs;
+pub mod bot;
+pub mod calendar_feeds;
+pub mod guardians;
+pub mod invitations;
 pub mod jobs;
+pub mod notifications;
+pub mod oidc;
+pub mod pairing_preferences;
 pub mod people;
+pub mod push;
+pub mod reminders;
 pub mod reports;
 pub mod schedules;
+pub mod service_dates;
+pub mod sessions;
+pub mod settings;
+pub mod sheets;
 pub mod sibling_groups;
+pub mod staffing;
+pub mod sync;
+pub mod teams;
+pub mod two_factor;
 pub mod unavailability;
 
-use axum::{
-    middleware,
-    routing::{delete, get, post, put},
-    Router,
-};
+use axum::{middleware, routing::get, Json, Router};
 use sqlx::PgPool;
 use tower_http::cors::{Any, CorsLayer};
+use utoipa::openapi::path::{Operation, PathItem};
+use utoipa::openapi::security::{
+    ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme,
+};
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
 
 use crate::auth;
+use crate::db::tx;
 
 pub fn create_router(pool: PgPool) -> Router {
     let cors = CorsLayer::new()
@@ -21,104 +45,343 @@ pub fn create_router(pool: PgPool) -> Router {
         .allow_methods(Any)
         .allow_headers(Any);
 
-    // API routes that require authentication
-    let api_routes = Router::new()
-        // Auth routes (protected)
-        .route("/auth/me", get(auth::me))
-        .route("/auth/change-password", post(auth::change_password))
-        // People routes
-        .route("/people", get(people::get_all).post(people::create))
-        .route(
-            "/people/{id}",
-            get(people::get_by_id)
-                .put(people::update)
-                .delete(people::delete),
-        )
-        .route("/people/{id}/reset-password", post(people::reset_password))
-        .route(
-            "/people/{id}/create-user",
-            post(people::create_user_account),
-        )
-        .route(
-            "/people/{id}/photo",
-            post(people::upload_photo).delete(people::delete_photo),
-        )
+    let api_routes = api_routes()
+        // Handlers that extract `Tx` commit on success and roll back on error
+        .route_layer(middleware::from_fn(tx::tx_middleware))
+        .route_layer(middleware::from_fn_with_state(
+            pool.clone(),
+            auth::auth_middleware,
+        ));
+    let (router, spec) = public_routes()
+        // Protected API routes
+        .nest("/api", api_routes)
+        .split_for_parts();
+    let spec = Json(describe(spec));
+
+    router
+        // What the SDKs are generated from; see `npm run sdk`
         .route(
-            "/my-photo",
-            post(people::upload_my_photo).delete(people::delete_my_photo),
+            "/openapi.json",
+            get(move || std::future::ready(spec.clone())),
         )
+        .with_state(pool)
+        .layer(cors)
+}
+
+/// The OpenAPI description of every route, as served at `/openapi.json`
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    describe(public_routes().nest("/api", api_routes()).into_openapi())
+}
+
+// Each handler's method and path are in its `#[utoipa::path]`, next to the handler
+
+/// API routes that require authentication
+fn api_routes() -> OpenApiRouter<PgPool> {
+    OpenApiRouter::new()
+        // Auth routes (protected)
+        .routes(routes!(auth::me))
+        .routes(routes!(auth::change_password))
+        .routes(routes!(sessions::logout))
+        .routes(routes!(sessions::get_mine, sessions::revoke_all_mine))
+        .routes(routes!(sessions::revoke_mine))
+        .routes(routes!(two_factor::get_status))
+        .routes(routes!(two_factor::setup))
+        .routes(routes!(two_factor::enable))
+        .routes(routes!(two_factor::disable))
+        .routes(routes!(two_factor::regenerate_recovery_codes))
+        // People routes
+        .routes(routes!(people::get_all, people::create))
+        .routes(routes!(people::export_vcard, people::import_vcard))
+        .routes(routes!(people::export_directory))
+        .routes(routes!(people::get_by_id, people::update, people::delete))
+        .routes(routes!(schedules::get_person_assignments))
+        .routes(routes!(schedules::export_person_calendar))
+        .routes(routes!(
+            calendar_feeds::get_feed,
+            calendar_feeds::rotate_feed,
+            calendar_feeds::delete_feed
+        ))
+        .routes(routes!(people::reset_password))
+        .routes(routes!(people::regenerate_username))
+        .routes(routes!(people::create_user_account))
+        .routes(routes!(
+            invitations::get_for_person,
+            invitations::create,
+            invitations::cancel
+        ))
+        .routes(routes!(invitations::resend))
+        .routes(routes!(people::upload_photo, people::delete_photo))
+        .routes(routes!(people::upload_my_photo, people::delete_my_photo))
         // Jobs routes
-        .route("/jobs", get(jobs::get_all))
-        .route("/jobs/{id}/positions", get(jobs::get_positions))
+        .routes(routes!(jobs::get_all))
+        .routes(routes!(jobs::get_positions))
+        .routes(routes!(jobs::update_novice_limit))
+        .routes(routes!(jobs::update_requirements))
+        .routes(routes!(jobs::get_rotation))
         // Schedules routes
-        .route(
-            "/schedules",
-            get(schedules::get_all).post(schedules::generate),
-        )
-        .route(
-            "/schedules/{id}",
-            get(schedules::get_by_id).delete(schedules::delete),
-        )
-        .route("/schedules/{id}/publish", post(schedules::publish))
-        .route("/schedules/{id}/export", get(schedules::export_excel))
-        .route("/assignments/{id}", put(schedules::update_assignment))
-        .route("/assignments/{id}/clear", put(schedules::clear_assignment))
-        .route("/assignments/{id}/move", put(schedules::move_assignment))
-        .route("/assignments/swap", post(schedules::swap_assignments))
-        .route(
-            "/schedules/{id}/completeness",
-            get(schedules::get_schedule_completeness),
-        )
-        .route(
-            "/my-assignments/{person_id}",
-            get(schedules::get_my_assignments),
-        )
+        .routes(routes!(schedules::get_all, schedules::generate))
+        .routes(routes!(schedules::generate_range))
+        .routes(routes!(schedules::generate_batch))
+        .routes(routes!(schedules::get_double_bookings))
+        .routes(routes!(schedules::get_next_service))
+        .routes(routes!(schedules::regenerate))
+        .routes(routes!(
+            schedules::get_by_id,
+            schedules::update,
+            schedules::delete
+        ))
+        .routes(routes!(schedules::publish))
+        .routes(routes!(schedules::unpublish))
+        .routes(routes!(schedules::get_status_history))
+        .routes(routes!(schedules::archive))
+        .routes(routes!(schedules::duplicate))
+        .routes(routes!(schedules::get_snapshots))
+        .routes(routes!(schedules::get_diff))
+        .routes(routes!(schedules::get_audit))
+        .routes(routes!(schedules::get_job_statuses))
+        .routes(routes!(schedules::publish_job))
+        .routes(routes!(schedules::unpublish_job))
+        .routes(routes!(schedules::export_schedule))
+        .routes(routes!(schedules::export_summary))
+        .routes(routes!(schedules::export_signage))
+        .routes(routes!(schedules::bulk_update_assignments))
+        .routes(routes!(schedules::undo))
+        .routes(routes!(schedules::redo))
+        .routes(routes!(service_dates::get_history))
+        .routes(routes!(service_dates::update_notes))
+        .routes(routes!(service_dates::cancel))
+        .routes(routes!(schedules::update_assignment))
+        .routes(routes!(schedules::clear_assignment))
+        .routes(routes!(schedules::move_assignment))
+        .routes(routes!(schedules::update_assignment_notes))
+        .routes(routes!(schedules::swap_assignments))
+        .routes(routes!(schedules::get_swap_suggestions))
+        .routes(routes!(schedules::get_schedule_completeness))
+        .routes(routes!(schedules::get_completeness_suggestions))
+        .routes(routes!(schedules::simulate_absence))
+        .routes(routes!(schedules::get_my_assignments))
+        .routes(routes!(schedules::get_my_family_assignments))
+        .routes(routes!(schedules::get_my_calendar))
+        .routes(routes!(guardians::get_my_children))
+        // Web Push subscriptions of the logged-in user's browsers
+        .routes(routes!(push::get_public_key))
+        .routes(routes!(push::subscribe, push::unsubscribe))
+        // My notifications (servidor self-service)
+        .routes(routes!(notifications::get_my_notifications))
+        .routes(routes!(notifications::mark_read))
+        .routes(routes!(
+            notifications::get_my_preferences,
+            notifications::update_my_preferences
+        ))
         // Unavailability routes (admin)
-        .route(
-            "/unavailability",
-            get(unavailability::get_all).post(unavailability::create),
-        )
-        .route("/unavailability/{id}", delete(unavailability::delete))
+        .routes(routes!(unavailability::get_all, unavailability::create))
+        .routes(routes!(unavailability::delete))
+        .routes(routes!(
+            unavailability::get_person_blackouts,
+            unavailability::set_person_blackout
+        ))
+        .routes(routes!(unavailability::delete_blackout))
+        .routes(routes!(people::get_exclusions, people::set_exclusion))
+        .routes(routes!(people::delete_exclusion))
         // My unavailability routes (servidor self-service)
-        .route(
-            "/my-unavailability",
-            get(unavailability::get_my_unavailability)
-                .post(unavailability::create_my_unavailability),
-        )
-        .route(
-            "/my-unavailability/{id}",
-            delete(unavailability::delete_my_unavailability),
-        )
+        .routes(routes!(
+            unavailability::get_my_unavailability,
+            unavailability::create_my_unavailability
+        ))
+        .routes(routes!(unavailability::delete_my_unavailability))
+        .routes(routes!(unavailability::set_my_availability))
         // Sibling groups routes
-        .route(
-            "/sibling-groups",
-            get(sibling_groups::get_all).post(sibling_groups::create),
-        )
-        .route(
-            "/sibling-groups/{id}",
-            put(sibling_groups::update).delete(sibling_groups::delete),
-        )
+        .routes(routes!(sibling_groups::get_all, sibling_groups::create))
+        .routes(routes!(sibling_groups::get_duplicates))
+        .routes(routes!(sibling_groups::merge))
+        .routes(routes!(sibling_groups::update, sibling_groups::delete))
+        // Staffing overrides routes
+        .routes(routes!(staffing::get_all, staffing::set))
+        .routes(routes!(staffing::delete))
+        // Teams routes
+        .routes(routes!(teams::get_all, teams::create))
+        .routes(routes!(teams::update, teams::delete))
+        // Pairing preferences routes
+        .routes(routes!(
+            pairing_preferences::get_all,
+            pairing_preferences::create
+        ))
+        .routes(routes!(
+            pairing_preferences::update,
+            pairing_preferences::delete
+        ))
         // Reports routes
-        .route("/reports/fairness", get(reports::get_fairness_scores))
-        .route(
-            "/reports/person/{id}/history",
-            get(reports::get_person_history),
-        )
-        .route_layer(middleware::from_fn_with_state(
-            pool.clone(),
-            auth::auth_middleware,
-        ));
+        .routes(routes!(reports::get_fairness_scores))
+        .routes(routes!(reports::export_fairness_workbook))
+        .routes(routes!(reports::get_person_history))
+        .routes(routes!(reports::get_junior_roster))
+        .routes(routes!(reports::get_birthdays))
+        .routes(routes!(reports::get_recruiting_needs))
+        .routes(routes!(reports::get_recognition))
+        .routes(routes!(reports::get_recognition_certificates))
+        // Settings routes
+        .routes(routes!(settings::get_all))
+        .routes(routes!(settings::update))
+        // Admin routes
+        .routes(routes!(admin::bootstrap))
+        .routes(routes!(admin::get_coordinators))
+        .routes(routes!(admin::set_job_scope))
+        .routes(routes!(guardians::get_all, guardians::create))
+        .routes(routes!(guardians::delete))
+        .routes(routes!(guardians::set_children))
+        .routes(routes!(oidc::set_user_email))
+        .routes(routes!(auth_events::get_all))
+        .routes(routes!(
+            sessions::get_for_user,
+            sessions::revoke_all_for_user
+        ))
+        .routes(routes!(sessions::revoke_for_user))
+        .routes(routes!(two_factor::reset_for_user))
+        .routes(routes!(notifications::get_failures))
+        .routes(routes!(notifications::retry_failure))
+        .routes(routes!(notifications::get_whatsapp_templates))
+        .routes(routes!(
+            notifications::set_whatsapp_template,
+            notifications::delete_whatsapp_template
+        ))
+        .routes(routes!(notifications::send_digests))
+        .routes(routes!(background_jobs::list, background_jobs::create))
+        .routes(routes!(background_jobs::run))
+        .routes(routes!(background_jobs::get_one))
+        .routes(routes!(background_jobs::retry))
+        .routes(routes!(reminders::preview))
+        .routes(routes!(reminders::send))
+        .routes(routes!(sync::apply_deleted_people))
+        .routes(routes!(sync::reconcile_users))
+        .routes(routes!(sheets::get_exports, sheets::create_export))
+        .routes(routes!(sheets::push_export))
+        .routes(routes!(sheets::delete_export))
+        .routes(routes!(api_keys::get_all, api_keys::create))
+        .routes(routes!(api_keys::set_scopes, api_keys::revoke))
+}
 
-    Router::new()
-        // Public routes - no auth
-        .route("/health", get(health_check))
-        .route("/login", post(auth::login))
-        // Protected API routes
-        .nest("/api", api_routes)
-        .with_state(pool)
-        .layer(cors)
+/// Public routes - no auth
+fn public_routes() -> OpenApiRouter<PgPool> {
+    OpenApiRouter::with_openapi(ApiDoc::openapi())
+        .routes(routes!(health_check))
+        .routes(routes!(auth::login))
+        // A refresh token gets a new access token while its session is on
+        .routes(routes!(auth::refresh))
+        // Single sign-on with the diocese's Google accounts, when OIDC_CLIENT_ID is set
+        .routes(routes!(oidc::get_settings, oidc::login))
+        // Invitation links: the token is the credential until the servidor picks a password
+        .routes(routes!(invitations::get_by_token))
+        .routes(routes!(invitations::accept))
+        // Read-only integrations authenticate with an API key in the X-Api-Key header
+        .routes(routes!(api_keys::get_assignments))
+        .routes(routes!(api_keys::get_next_service))
+        // Calendar subscriptions authenticate with the token in the link; the segment is
+        // "<token>.ics"
+        .routes(routes!(calendar_feeds::get_feed_ics))
+        // The chat bot relay authenticates with the token in the path
+        .routes(routes!(bot::webhook))
+}
+
+#[derive(OpenApi)]
+#[openapi(info(
+    title = "People Scheduler API",
+    description = "Volunteer scheduling for the parish ministries"
+))]
+struct ApiDoc;
+
+/// The route table only knows each handler on its own: add the security schemes, mark what
+/// sits behind the login, and make operation ids unique across modules for generated clients
+fn describe(mut openapi: utoipa::openapi::OpenApi) -> utoipa::openapi::OpenApi {
+    // Taken from Cargo.toml, which has none
+    openapi.info.license = None;
+    let components = openapi.components.get_or_insert_with(Default::default);
+    components.add_security_scheme(
+        "bearer",
+        SecurityScheme::Http(
+            HttpBuilder::new()
+                .scheme(HttpAuthScheme::Bearer)
+                .bearer_format("JWT")
+                .build(),
+        ),
+    );
+    components.add_security_scheme(
+        "api_key",
+        SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Api-Key"))),
+    );
+
+    for (path, item) in openapi.paths.paths.iter_mut() {
+        let protected = path.starts_with("/api/");
+        for operation in operations(item) {
+            if protected {
+                operation.security = Some(vec![SecurityRequirement::new(
+                    "bearer",
+                    Vec::<String>::new(),
+                )]);
+            }
+            // `update` alone is in half the modules: prefix the tag ("people_update")
+            let tag = operation
+                .tags
+                .as_ref()
+                .and_then(|tags| tags.first())
+                .map(|tag| tag.replace('-', "_"));
+            if let (Some(tag), Some(id)) = (tag, operation.operation_id.as_mut()) {
+                *id = format!("{}_{}", tag, id);
+            }
+        }
+    }
+    openapi
+}
+
+fn operations(item: &mut PathItem) -> impl Iterator<Item = &mut Operation> {
+    [
+        &mut item.get,
+        &mut item.post,
+        &mut item.put,
+        &mut item.patch,
+        &mut item.delete,
+    ]
+    .into_iter()
+    .filter_map(Option::as_mut)
 }
 
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses((status = 200, content_type = "text/plain", body = String))
+)]
 async fn health_check() -> &'static str {
     "OK"
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_routes_need_a_token_and_operation_ids_are_unique() {
+        let mut spec = openapi();
+        let bearer = vec![SecurityRequirement::new("bearer", Vec::<String>::new())];
+        let mut ids = std::collections::HashSet::new();
+        for (path, item) in spec.paths.paths.iter_mut() {
+            for operation in operations(item) {
+                let id = operation.operation_id.clone().unwrap();
+                assert!(ids.insert(id.clone()), "operation id {} is taken", id);
+                assert_eq!(
+                    operation.security.as_ref() == Some(&bearer),
+                    path.starts_with("/api/"),
+                    "{}",
+                    path
+                );
+            }
+        }
+
+        let people = &spec.paths.paths["/api/people/{id}"];
+        assert_eq!(
+            people.put.as_ref().unwrap().operation_id.as_deref(),
+            Some("people_update")
+        );
+        assert!(people.get.is_some() && people.delete.is_some());
+        assert!(spec.paths.paths.contains_key("/login"));
+    }
+}
@@ -0,0 +1,464 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+
+use crate::auth::Claims;
+use crate::models::{
+    DigestRun, Notification, NotificationFailure, NotificationPreferences,
+    SetWhatsappTemplateRequest, UpdateNotificationPreferences, WhatsappTemplate,
+};
+use crate::routes::settings::NOTIFICATION_CHANNELS;
+use crate::services::notifications;
+
+#[utoipa::path(
+    get,
+    path = "/my-notifications",
+    tag = "notifications",
+    responses((status = 200, body = Vec<Notification>))
+)]
+pub async fn get_my_notifications(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<Vec<Notification>>, (StatusCode, String)> {
+    let person_id = claims.person_id.ok_or((
+        StatusCode::FORBIDDEN,
+        "No tiene un servidor vinculado a su cuenta".to_string(),
+    ))?;
+
+    let notifications = sqlx::query_as::<_, Notification>(
+        "SELECT * FROM notifications WHERE person_id = $1 ORDER BY created_at DESC LIMIT 50",
+    )
+    .bind(&person_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(notifications))
+}
+
+#[utoipa::path(
+    put,
+    path = "/my-notifications/{id}/read",
+    tag = "notifications",
+    responses((status = 204))
+)]
+pub async fn mark_read(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let person_id = claims.person_id.ok_or((
+        StatusCode::FORBIDDEN,
+        "No tiene un servidor vinculado a su cuenta".to_string(),
+    ))?;
+
+    let result = sqlx::query(
+        "UPDATE notifications SET read_at = COALESCE(read_at, NOW()) WHERE id = $1 AND person_id = $2",
+    )
+    .bind(&id)
+    .bind(&person_id)
+    .execute(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "Notification not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============ Preferences (servidor self-service) ============
+
+fn my_person_id(claims: &Claims) -> Result<String, (StatusCode, String)> {
+    claims.person_id.clone().ok_or((
+        StatusCode::FORBIDDEN,
+        "No tiene un servidor vinculado a su cuenta".to_string(),
+    ))
+}
+
+/// The servidor's notification preferences; all defaults until they save some
+#[utoipa::path(
+    get,
+    path = "/my-notification-preferences",
+    tag = "notifications",
+    responses((status = 200, body = NotificationPreferences))
+)]
+pub async fn get_my_preferences(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<NotificationPreferences>, (StatusCode, String)> {
+    let person_id = my_person_id(&claims)?;
+
+    let preferences = sqlx::query_as::<_, NotificationPreferences>(
+        "SELECT * FROM notification_preferences WHERE person_id = $1",
+    )
+    .bind(&person_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .unwrap_or(NotificationPreferences {
+        person_id,
+        channel: None,
+        reminder_days_before: None,
+        digest: false,
+        updated_at: None,
+    });
+
+    Ok(Json(preferences))
+}
+
+/// Replace the servidor's preferences. A blank or missing channel and lead time go back to the
+/// parish defaults.
+#[utoipa::path(
+    put,
+    path = "/my-notification-preferences",
+    tag = "notifications",
+    responses((status = 200, body = NotificationPreferences))
+)]
+pub async fn update_my_preferences(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(input): Json<UpdateNotificationPreferences>,
+) -> Result<Json<NotificationPreferences>, (StatusCode, String)> {
+    let person_id = my_person_id(&claims)?;
+
+    let channel = input
+        .channel
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty());
+    if let Some(channel) = channel {
+        if !NOTIFICATION_CHANNELS.contains(&channel) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "channel must be one of {}",
+                    NOTIFICATION_CHANNELS.join(", ")
+                ),
+            ));
+        }
+        if let Some(missing) = notifications::missing_sender(channel) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("{} cannot be sent from this server: {}", channel, missing),
+            ));
+        }
+    }
+    if let Some(days) = input.reminder_days_before {
+        if !(0..=60).contains(&days) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "reminder_days_before must be a whole number from 0 to 60".to_string(),
+            ));
+        }
+    }
+
+    let preferences = sqlx::query_as::<_, NotificationPreferences>(
+        r#"
+        INSERT INTO notification_preferences (person_id, channel, reminder_days_before, digest)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (person_id) DO UPDATE
+        SET channel = EXCLUDED.channel,
+            reminder_days_before = EXCLUDED.reminder_days_before,
+            digest = EXCLUDED.digest,
+            updated_at = NOW()
+        RETURNING *
+        "#,
+    )
+    .bind(&person_id)
+    .bind(channel)
+    .bind(input.reminder_days_before)
+    .bind(input.digest)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(preferences))
+}
+
+/// Send the daily digests of people who chose one. Meant to be called once a day by a
+/// scheduler, like the reminders.
+#[utoipa::path(
+    post,
+    path = "/admin/notifications/send-digests",
+    tag = "notifications",
+    responses((status = 200, body = DigestRun))
+)]
+pub async fn send_digests(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<DigestRun>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can send digests".to_string(),
+        ));
+    }
+
+    let sent = notifications::send_digests(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(DigestRun { sent }))
+}
+
+// ============ Failed deliveries (admin) ============
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FailuresQuery {
+    pub include_resolved: Option<bool>,
+}
+
+const FAILURE_SELECT: &str = r#"
+    SELECT f.id, f.person_id, p.first_name || ' ' || p.last_name as person_name,
+           f.channel, f.destination, f.kind, f.message, f.error, f.permanent, f.attempts,
+           f.created_at, f.last_attempt_at, f.resolved_at
+    FROM notification_failures f
+    JOIN people p ON f.person_id = p.id
+"#;
+
+#[utoipa::path(
+    get,
+    path = "/admin/notification-failures",
+    params(FailuresQuery),
+    tag = "notifications",
+    responses((status = 200, body = Vec<NotificationFailure>))
+)]
+pub async fn get_failures(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<FailuresQuery>,
+) -> Result<Json<Vec<NotificationFailure>>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can view failed notifications".to_string(),
+        ));
+    }
+
+    let failures = sqlx::query_as::<_, NotificationFailure>(&format!(
+        "{} WHERE f.resolved_at IS NULL OR $1 ORDER BY f.created_at DESC LIMIT 200",
+        FAILURE_SELECT
+    ))
+    .bind(query.include_resolved.unwrap_or(false))
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(failures))
+}
+
+/// Try a failed notification again. On success it is marked resolved; otherwise the attempt
+/// count and error are updated and the failure stays in the queue.
+#[utoipa::path(
+    post,
+    path = "/admin/notification-failures/{id}/retry",
+    tag = "notifications",
+    responses((status = 200, body = NotificationFailure))
+)]
+pub async fn retry_failure(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<NotificationFailure>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can retry notifications".to_string(),
+        ));
+    }
+
+    let failure = get_failure(&pool, &id).await?;
+    if failure.resolved_at.is_some() {
+        return Err((
+            StatusCode::CONFLICT,
+            "Notification was already delivered".to_string(),
+        ));
+    }
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let outcome = notifications::deliver(
+        &mut conn,
+        &failure.channel,
+        &failure.person_id,
+        &failure.kind,
+        &failure.message,
+    )
+    .await;
+
+    match &outcome {
+        Ok(()) => {
+            sqlx::query(
+                r#"
+                UPDATE notification_failures
+                SET attempts = attempts + 1, last_attempt_at = NOW(), resolved_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(&id)
+            .execute(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        }
+        Err(failed) => {
+            sqlx::query(
+                r#"
+                UPDATE notification_failures
+                SET attempts = attempts + 1, last_attempt_at = NOW(), error = $1, permanent = $2
+                WHERE id = $3
+                "#,
+            )
+            .bind(&failed.error)
+            .bind(failed.permanent)
+            .bind(&id)
+            .execute(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+            if failed.permanent {
+                sqlx::query("UPDATE people SET contact_bounced = true WHERE id = $1")
+                    .bind(&failure.person_id)
+                    .execute(&pool)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            }
+        }
+    }
+
+    Ok(Json(get_failure(&pool, &id).await?))
+}
+
+async fn get_failure(pool: &PgPool, id: &str) -> Result<NotificationFailure, (StatusCode, String)> {
+    sqlx::query_as::<_, NotificationFailure>(&format!("{} WHERE f.id = $1", FAILURE_SELECT))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            "Notification failure not found".to_string(),
+        ))
+}
+
+// ============ WhatsApp templates (admin) ============
+
+/// Which approved template each kind of notification is sent in. Kinds without one go out as
+/// plain text, which WhatsApp only delivers within 24 hours of the person writing in.
+#[utoipa::path(
+    get,
+    path = "/admin/whatsapp-templates",
+    tag = "notifications",
+    responses((status = 200, body = Vec<WhatsappTemplate>))
+)]
+pub async fn get_whatsapp_templates(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<Vec<WhatsappTemplate>>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can view WhatsApp templates".to_string(),
+        ));
+    }
+
+    let templates =
+        sqlx::query_as::<_, WhatsappTemplate>("SELECT * FROM whatsapp_templates ORDER BY kind")
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(templates))
+}
+
+/// Send a kind of notification (service_reminder, schedule_published, ...) in the named
+/// template. The template takes the message text as its one parameter.
+#[utoipa::path(
+    put,
+    path = "/admin/whatsapp-templates/{kind}",
+    tag = "notifications",
+    responses((status = 200, body = WhatsappTemplate))
+)]
+pub async fn set_whatsapp_template(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(kind): Path<String>,
+    Json(input): Json<SetWhatsappTemplateRequest>,
+) -> Result<Json<WhatsappTemplate>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can change WhatsApp templates".to_string(),
+        ));
+    }
+
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Template name is required".to_string(),
+        ));
+    }
+    let language = input
+        .language
+        .as_deref()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .unwrap_or("es");
+
+    let template = sqlx::query_as::<_, WhatsappTemplate>(
+        r#"
+        INSERT INTO whatsapp_templates (kind, name, language) VALUES ($1, $2, $3)
+        ON CONFLICT (kind) DO UPDATE
+        SET name = EXCLUDED.name, language = EXCLUDED.language, updated_at = NOW()
+        RETURNING *
+        "#,
+    )
+    .bind(&kind)
+    .bind(name)
+    .bind(language)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(template))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/whatsapp-templates/{kind}",
+    tag = "notifications",
+    responses((status = 204))
+)]
+pub async fn delete_whatsapp_template(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(kind): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can change WhatsApp templates".to_string(),
+        ));
+    }
+
+    let result = sqlx::query("DELETE FROM whatsapp_templates WHERE kind = $1")
+        .bind(&kind)
+        .execute(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "Template not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,176 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::{self, Claims, LoginResponse, User};
+use crate::models::{OidcLoginRequest, OidcSettings, SetUserEmailRequest};
+use crate::services::auth_events::{self, AuthEvent, RequestOrigin};
+use crate::services::oidc;
+use crate::services::two_factor::{self, SecondFactor};
+
+/// Whether the login page should offer the provider, and with which client id
+#[utoipa::path(
+    get,
+    path = "/login/oidc",
+    tag = "oidc",
+    responses((status = 200, body = OidcSettings))
+)]
+pub async fn get_settings() -> Json<OidcSettings> {
+    let config = oidc::config();
+    Json(OidcSettings {
+        enabled: config.is_some(),
+        client_id: config.as_ref().map(|c| c.client_id.clone()),
+        issuer: config.as_ref().map(|c| c.issuer.clone()),
+        hosted_domain: config.and_then(|c| c.hosted_domain),
+    })
+}
+
+/// Sign in with the provider's ID token instead of a password. Only existing users can: the
+/// account is matched by email (see `oidc::find_user`), never created.
+#[utoipa::path(
+    post,
+    path = "/login/oidc",
+    tag = "oidc",
+    responses((status = 200, body = LoginResponse))
+)]
+pub async fn login(
+    State(pool): State<PgPool>,
+    headers: HeaderMap,
+    Json(request): Json<OidcLoginRequest>,
+) -> Result<Json<LoginResponse>, (StatusCode, String)> {
+    let config = oidc::config().ok_or((
+        StatusCode::NOT_FOUND,
+        "Single sign-on is not configured".to_string(),
+    ))?;
+
+    let origin = RequestOrigin::from_headers(&headers);
+    let user = match identify(&pool, &config, &request.id_token).await {
+        Ok(user) => user,
+        Err((status, e)) => {
+            if status != StatusCode::INTERNAL_SERVER_ERROR {
+                let event = AuthEvent {
+                    event: "oidc_login_failed",
+                    user_id: None,
+                    username: None,
+                    detail: Some(&e),
+                };
+                auth_events::record(&pool, &event, &origin)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            }
+            return Err((status, e));
+        }
+    };
+
+    // The provider vouches for the account, not for the second factor enrolled here
+    let second_factor = two_factor::check_login(&pool, user.id, request.totp_code.as_deref())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if second_factor == SecondFactor::Missing {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            two_factor::CODE_REQUIRED.to_string(),
+        ));
+    }
+    let refused = second_factor.refusal();
+
+    let event = AuthEvent {
+        event: if refused.is_some() {
+            "oidc_login_failed"
+        } else {
+            "oidc_login"
+        },
+        user_id: Some(user.id),
+        username: Some(&user.username),
+        detail: refused.or(second_factor.detail()),
+    };
+    auth_events::record(&pool, &event, &origin)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if let Some(reason) = refused {
+        return Err((StatusCode::UNAUTHORIZED, reason.to_string()));
+    }
+
+    Ok(Json(auth::issue_login(&pool, user, &origin).await?))
+}
+
+/// The user the ID token signs in as
+async fn identify(
+    pool: &PgPool,
+    config: &oidc::OidcConfig,
+    id_token: &str,
+) -> Result<User, (StatusCode, String)> {
+    let keys = oidc::current_keys(pool, config, id_token)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let claims =
+        oidc::verify(id_token, &keys, config).map_err(|e| (StatusCode::UNAUTHORIZED, e))?;
+    let email = oidc::verified_email(&claims, config.hosted_domain.as_deref())
+        .map_err(|e| (StatusCode::UNAUTHORIZED, e))?;
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    oidc::find_user(&mut conn, &claims, &email)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((
+            StatusCode::FORBIDDEN,
+            format!("No user is linked to {}", email),
+        ))
+}
+
+/// Set the email a user without a linked person (an admin, say) signs in with
+#[utoipa::path(
+    put,
+    path = "/admin/users/{id}/email",
+    tag = "oidc",
+    responses((status = 204))
+)]
+pub async fn set_user_email(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+    Json(input): Json<SetUserEmailRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can set sign-in emails".to_string(),
+        ));
+    }
+    let user_id =
+        Uuid::parse_str(&id).map_err(|_| (StatusCode::NOT_FOUND, "User not found".to_string()))?;
+
+    let email = input
+        .email
+        .map(|e| e.trim().to_lowercase())
+        .filter(|e| !e.is_empty());
+    if email.as_deref().is_some_and(|e| !e.contains('@')) {
+        return Err((StatusCode::BAD_REQUEST, "Invalid email".to_string()));
+    }
+
+    let result =
+        sqlx::query("UPDATE users SET email = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1")
+            .bind(user_id)
+            .bind(&email)
+            .execute(&pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db) if db.is_unique_violation() => (
+                    StatusCode::CONFLICT,
+                    "Another user already signs in with that email".to_string(),
+                ),
+                e => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+            })?;
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "User not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
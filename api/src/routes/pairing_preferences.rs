@@ -0,0 +1,230 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::{self, Claims};
+use crate::models::{
+    CreatePairingPreference, PairingPreference, UpdatePairingPreference, PAIRING_AVOID,
+    PAIRING_PREFER,
+};
+
+fn validate_preference(preference: &str) -> Result<String, (StatusCode, String)> {
+    let preference = preference.trim().to_uppercase();
+    if preference != PAIRING_PREFER && preference != PAIRING_AVOID {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Invalid preference: {}. Allowed: PREFER, AVOID", preference),
+        ));
+    }
+    Ok(preference)
+}
+
+/// Both people must exist, otherwise the insert would fail on the foreign key with a 500
+async fn ensure_people_exist(pool: &PgPool, ids: &[&String]) -> Result<(), (StatusCode, String)> {
+    let found: Vec<String> = sqlx::query_scalar("SELECT id FROM people WHERE id = ANY($1)")
+        .bind(ids.iter().map(|id| id.to_string()).collect::<Vec<_>>())
+        .fetch_all(pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let missing: Vec<&str> = ids
+        .iter()
+        .filter(|id| !found.contains(id))
+        .map(|id| id.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Unknown person: {}", missing.join(", ")),
+        ));
+    }
+    Ok(())
+}
+
+// "Avoid" pairs record conflicts between people: only admins and coordinators see or edit them,
+// coordinators only when both people hold one of their jobs
+async fn require_pair_scope(
+    pool: &PgPool,
+    claims: &Claims,
+    person_a_id: &str,
+    person_b_id: &str,
+) -> Result<(), (StatusCode, String)> {
+    auth::require_admin_or_coordinator(claims, "manage pairing preferences")?;
+    auth::require_person_scope(pool, claims, person_a_id).await?;
+    auth::require_person_scope(pool, claims, person_b_id).await
+}
+
+async fn require_existing_pair_scope(
+    pool: &PgPool,
+    claims: &Claims,
+    id: &str,
+) -> Result<(), (StatusCode, String)> {
+    let (person_a_id, person_b_id): (String, String) =
+        sqlx::query_as("SELECT person_a_id, person_b_id FROM pairing_preferences WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((
+                StatusCode::NOT_FOUND,
+                "Pairing preference not found".to_string(),
+            ))?;
+    require_pair_scope(pool, claims, &person_a_id, &person_b_id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/pairing-preferences",
+    tag = "pairing-preferences",
+    responses((status = 200, body = Vec<PairingPreference>))
+)]
+pub async fn get_all(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<Vec<PairingPreference>>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "see pairing preferences")?;
+    let scope = claims.is_coordinator().then_some(&claims.job_ids);
+
+    let preferences = sqlx::query_as::<_, PairingPreference>(
+        r#"
+        SELECT * FROM pairing_preferences pp
+        WHERE $1::text[] IS NULL
+           OR (EXISTS (SELECT 1 FROM person_jobs WHERE person_id = pp.person_a_id AND job_id = ANY($1))
+               AND EXISTS (SELECT 1 FROM person_jobs WHERE person_id = pp.person_b_id AND job_id = ANY($1)))
+        ORDER BY created_at
+        "#,
+    )
+    .bind(scope)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(preferences))
+}
+
+#[utoipa::path(
+    post,
+    path = "/pairing-preferences",
+    tag = "pairing-preferences",
+    responses((status = 200, body = PairingPreference))
+)]
+pub async fn create(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(input): Json<CreatePairingPreference>,
+) -> Result<Json<PairingPreference>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "manage pairing preferences")?;
+    if input.person_a_id == input.person_b_id {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "A pairing preference needs two different people".to_string(),
+        ));
+    }
+    let preference = validate_preference(&input.preference)?;
+    ensure_people_exist(&pool, &[&input.person_a_id, &input.person_b_id]).await?;
+    require_pair_scope(&pool, &claims, &input.person_a_id, &input.person_b_id).await?;
+
+    // Store each pair once, in a stable order
+    let (person_a_id, person_b_id) = if input.person_a_id < input.person_b_id {
+        (input.person_a_id, input.person_b_id)
+    } else {
+        (input.person_b_id, input.person_a_id)
+    };
+
+    let id = Uuid::new_v4().to_string();
+    let created = sqlx::query_as::<_, PairingPreference>(
+        r#"
+        INSERT INTO pairing_preferences (id, person_a_id, person_b_id, preference, notes)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (person_a_id, person_b_id) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(&id)
+    .bind(&person_a_id)
+    .bind(&person_b_id)
+    .bind(&preference)
+    .bind(&input.notes)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or((
+        StatusCode::CONFLICT,
+        "A pairing preference already exists for these people".to_string(),
+    ))?;
+
+    Ok(Json(created))
+}
+
+#[utoipa::path(
+    put,
+    path = "/pairing-preferences/{id}",
+    tag = "pairing-preferences",
+    responses((status = 200, body = PairingPreference))
+)]
+pub async fn update(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+    Json(input): Json<UpdatePairingPreference>,
+) -> Result<Json<PairingPreference>, (StatusCode, String)> {
+    require_existing_pair_scope(&pool, &claims, &id).await?;
+    let preference = match &input.preference {
+        Some(p) => Some(validate_preference(p)?),
+        None => None,
+    };
+
+    let updated = sqlx::query_as::<_, PairingPreference>(
+        r#"
+        UPDATE pairing_preferences
+        SET preference = COALESCE($1, preference), notes = COALESCE($2, notes)
+        WHERE id = $3
+        RETURNING *
+        "#,
+    )
+    .bind(&preference)
+    .bind(&input.notes)
+    .bind(&id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or((
+        StatusCode::NOT_FOUND,
+        "Pairing preference not found".to_string(),
+    ))?;
+
+    Ok(Json(updated))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/pairing-preferences/{id}",
+    tag = "pairing-preferences",
+    responses((status = 204))
+)]
+pub async fn delete(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    require_existing_pair_scope(&pool, &claims, &id).await?;
+
+    let result = sqlx::query("DELETE FROM pairing_preferences WHERE id = $1")
+        .bind(&id)
+        .execute(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err((
+            StatusCode::NOT_FOUND,
+            "Pairing preference not found".to_string(),
+        ));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
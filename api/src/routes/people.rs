@@ -1,16 +1,33 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
     Json,
 };
-use sqlx::PgPool;
+use serde::Deserialize;
+use sqlx::{Executor, FromRow, PgConnection, PgPool, Postgres};
+use std::collections::HashMap;
+use utoipa::IntoParams;
 use uuid::Uuid;
 
-use crate::auth::{hash_password, Claims};
-use crate::models::{CreatePerson, Person, PersonWithCredentials, PersonWithJobs, UpdatePerson, UploadPhotoRequest};
+use crate::auth::{self, hash_password, Claims};
+use crate::db::filters::{Filter, Update};
+use crate::db::tx::Tx;
+use crate::models::{
+    CreatePerson, CreatePersonJobExclusion, ImportVCardRequest, IssuedInvitation, PeoplePage,
+    Person, PersonJobExclusion, PersonStats, PersonWithCredentials, PersonWithJobs, UpdatePerson,
+    UploadPhotoRequest, VCardImportResult, VCardImportSkipped, JUNIOR_JOB_ID, LECTORES_JOB_ID,
+    SENIOR_JOB_ID,
+};
+use crate::routes::settings;
+use crate::services::auth_events::{self, AuthEvent, RequestOrigin};
+use crate::services::invitations;
+use crate::services::notifications;
+use crate::services::sessions;
+use crate::services::{csv, vcard, xlsx};
 
 // Generate a random password (8 characters, alphanumeric)
-fn generate_random_password() -> String {
+pub(crate) fn generate_random_password() -> String {
     use rand::Rng;
     const CHARSET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZabcdefghjkmnpqrstuvwxyz23456789";
     let mut rng = rand::thread_rng();
@@ -25,7 +42,7 @@ fn generate_random_password() -> String {
 // Generate username from first name and last name
 // Format: first letter of first name + last name (lowercase, no spaces/accents)
 // If taken, try first two letters + last name, then add numbers
-async fn generate_username(
+pub(crate) async fn generate_username(
     pool: &PgPool,
     first_name: &str,
     last_name: &str,
@@ -41,30 +58,9 @@ async fn generate_username(
         ));
     }
 
-    // Try: first letter + last name
-    let base_username = format!(
-        "{}{}",
-        first_normalized.chars().next().unwrap(),
-        last_normalized
-    );
-
-    if !username_exists(pool, &base_username).await? {
-        return Ok(base_username);
-    }
-
-    // Try: first two letters + last name
-    if first_normalized.len() >= 2 {
-        let username_two = format!("{}{}", &first_normalized[..2], last_normalized);
-        if !username_exists(pool, &username_two).await? {
-            return Ok(username_two);
-        }
-    }
-
-    // Add numbers until we find one that works
-    for i in 1..=99 {
-        let username_numbered = format!("{}{}", base_username, i);
-        if !username_exists(pool, &username_numbered).await? {
-            return Ok(username_numbered);
+    for candidate in username_candidates(&first_normalized, &last_normalized) {
+        if !username_exists(pool, &candidate).await? {
+            return Ok(candidate);
         }
     }
 
@@ -74,14 +70,38 @@ async fn generate_username(
     ))
 }
 
-// Check if username exists in users table
-async fn username_exists(pool: &PgPool, username: &str) -> Result<bool, (StatusCode, String)> {
-    let exists =
-        sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM users WHERE username = $1)")
-            .bind(username)
-            .fetch_one(pool)
-            .await
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+// Usernames a person with this (normalized) name may get, in order of preference:
+// first letter + last name, first two letters + last name, then numbered variants
+fn username_candidates(first_normalized: &str, last_normalized: &str) -> Vec<String> {
+    let Some(initial) = first_normalized.chars().next() else {
+        return Vec::new();
+    };
+    let base_username = format!("{}{}", initial, last_normalized);
+
+    let mut candidates = vec![base_username.clone()];
+    if first_normalized.chars().count() >= 2 {
+        let first_two: String = first_normalized.chars().take(2).collect();
+        candidates.push(format!("{}{}", first_two, last_normalized));
+    }
+    candidates.extend((1..=99).map(|i| format!("{}{}", base_username, i)));
+    candidates
+}
+
+// Check if username exists in users table or is still held as a login alias
+pub(crate) async fn username_exists(
+    pool: &PgPool,
+    username: &str,
+) -> Result<bool, (StatusCode, String)> {
+    let exists = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS(SELECT 1 FROM users WHERE username = $1)
+            OR EXISTS(SELECT 1 FROM username_aliases WHERE username = $1 AND expires_at > NOW())
+        "#,
+    )
+    .bind(username)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     Ok(exists)
 }
@@ -103,98 +123,414 @@ fn normalize_name(name: &str) -> String {
 }
 
 // Get username for a person (from linked user)
-async fn get_username_for_person(
-    pool: &PgPool,
+async fn get_username_for_person<'e, E>(
+    executor: E,
     person_id: &str,
-) -> Result<Option<String>, (StatusCode, String)> {
+) -> Result<Option<String>, (StatusCode, String)>
+where
+    E: Executor<'e, Database = Postgres>,
+{
     let username =
         sqlx::query_scalar::<_, String>("SELECT username FROM users WHERE person_id = $1")
             .bind(person_id)
-            .fetch_optional(pool)
+            .fetch_optional(executor)
             .await
             .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     Ok(username)
 }
 
+// Jobs the person is qualified for
+async fn person_job_ids(
+    pool: &PgPool,
+    person_id: &str,
+) -> Result<Vec<String>, (StatusCode, String)> {
+    sqlx::query_scalar("SELECT job_id FROM person_jobs WHERE person_id = $1 ORDER BY job_id")
+        .bind(person_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+// Jobs the person must not be scheduled in
+async fn get_excluded_job_ids<'e, E>(
+    executor: E,
+    person_id: &str,
+) -> Result<Vec<String>, (StatusCode, String)>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    sqlx::query_scalar(
+        "SELECT job_id FROM person_job_exclusions WHERE person_id = $1 ORDER BY job_id",
+    )
+    .bind(person_id)
+    .fetch_all(executor)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+// Apply a legacy exclude_* flag to the exclusion rows of the jobs it covers
+async fn set_legacy_exclusion(
+    conn: &mut PgConnection,
+    person_id: &str,
+    job_ids: &[&str],
+    excluded: bool,
+) -> Result<(), (StatusCode, String)> {
+    for job_id in job_ids {
+        let result = if excluded {
+            sqlx::query(
+                r#"
+                INSERT INTO person_job_exclusions (id, person_id, job_id)
+                SELECT $1, $2, id FROM jobs WHERE id = $3
+                ON CONFLICT (person_id, job_id) DO NOTHING
+                "#,
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(person_id)
+            .bind(job_id)
+            .execute(&mut *conn)
+            .await
+        } else {
+            sqlx::query("DELETE FROM person_job_exclusions WHERE person_id = $1 AND job_id = $2")
+                .bind(person_id)
+                .bind(job_id)
+                .execute(&mut *conn)
+                .await
+        };
+        result.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+// Keep the legacy exclude_* flags readable by older clients after an exclusion change
+async fn refresh_legacy_flags(
+    conn: &mut PgConnection,
+    person_id: &str,
+) -> Result<(), (StatusCode, String)> {
+    sqlx::query(
+        r#"
+        UPDATE people SET
+            exclude_monaguillos = EXISTS (
+                SELECT 1 FROM person_job_exclusions WHERE person_id = $1 AND job_id = $2
+            ),
+            exclude_lectores = EXISTS (
+                SELECT 1 FROM person_job_exclusions WHERE person_id = $1 AND job_id = $3
+            )
+        WHERE id = $1
+        "#,
+    )
+    .bind(person_id)
+    .bind(SENIOR_JOB_ID)
+    .bind(LECTORES_JOB_ID)
+    .execute(conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(())
+}
+
+// Pastoral and scheduling notes are for coordinators only
+fn hide_notes_unless_admin(person: &mut Person, claims: &Claims) {
+    if !claims.is_admin() {
+        person.notes = None;
+        person.scheduling_notes = None;
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PeopleQuery {
+    /// Comma-separated extras; currently only "stats"
+    pub include: Option<String>,
+    /// Matches the name, email or phone
+    pub q: Option<String>,
+    /// Only people qualified for this job
+    pub job_id: Option<String>,
+    pub active: Option<bool>,
+    /// 1-based; only used with `limit`
+    pub page: Option<i64>,
+    /// Page size (at most 500); without it everyone matching is returned
+    pub limit: Option<i64>,
+}
+
+const MAX_PAGE_SIZE: i64 = 500;
+
+// The people the list filters select; shared by the page and its total
+fn people_filter<'q>(select: &str, query: &'q PeopleQuery) -> Filter<'q> {
+    let mut filter = Filter::new(select);
+    filter
+        .search(
+            &[
+                "first_name",
+                "last_name",
+                "first_name || ' ' || last_name",
+                "email",
+                "phone",
+            ],
+            query.q.as_deref(),
+        )
+        .in_select(
+            "id",
+            "SELECT person_id FROM person_jobs WHERE job_id = ",
+            query.job_id.as_ref(),
+        )
+        .eq("active", query.active);
+    filter
+}
+
+// person id -> values, for the people of one page in a single query
+async fn group_by_person(
+    pool: &PgPool,
+    sql: &str,
+    person_ids: &[String],
+) -> Result<HashMap<String, Vec<String>>, (StatusCode, String)> {
+    let rows: Vec<(String, String)> = sqlx::query_as(sql)
+        .bind(person_ids)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut grouped: HashMap<String, Vec<String>> = HashMap::new();
+    for (person_id, value) in rows {
+        grouped.entry(person_id).or_default().push(value);
+    }
+    Ok(grouped)
+}
+
+#[derive(FromRow)]
+struct PersonStatsRow {
+    person_id: String,
+    #[sqlx(flatten)]
+    stats: PersonStats,
+}
+
+/// Assignment counts for every person in one aggregate pass over assignment_history
+async fn get_all_stats(
+    pool: &PgPool,
+) -> Result<HashMap<String, PersonStats>, (StatusCode, String)> {
+    let rows = sqlx::query_as::<_, PersonStatsRow>(
+        r#"
+        SELECT
+            p.id as person_id,
+            COUNT(ah.id) FILTER (WHERE ah.year = EXTRACT(YEAR FROM CURRENT_DATE)::int) as assignments_this_year,
+            MAX(ah.service_date) FILTER (WHERE ah.service_date <= CURRENT_DATE) as last_served_date,
+            COUNT(ah.id) FILTER (WHERE ah.service_date > CURRENT_DATE) as upcoming_assignments
+        FROM people p
+        LEFT JOIN assignment_history ah ON ah.person_id = p.id
+        GROUP BY p.id
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(rows.into_iter().map(|r| (r.person_id, r.stats)).collect())
+}
+
+#[utoipa::path(
+    get,
+    path = "/people",
+    params(PeopleQuery),
+    tag = "people",
+    responses((status = 200, body = PeoplePage))
+)]
 pub async fn get_all(
     State(pool): State<PgPool>,
-) -> Result<Json<Vec<PersonWithJobs>>, (StatusCode, String)> {
-    let people = sqlx::query_as::<_, Person>(
+    claims: Claims,
+    Query(query): Query<PeopleQuery>,
+) -> Result<Json<PeoplePage>, (StatusCode, String)> {
+    let include_stats = query
+        .include
+        .as_deref()
+        .unwrap_or("")
+        .split(',')
+        .any(|part| part.trim() == "stats");
+    let limit = query.limit.map(|limit| limit.clamp(1, MAX_PAGE_SIZE));
+    let page = if limit.is_some() {
+        query.page.unwrap_or(1).max(1)
+    } else {
+        1
+    };
+
+    let total: i64 = people_filter("SELECT COUNT(*) FROM people", &query)
+        .into_builder()
+        .build_query_scalar()
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut builder = people_filter(
         r#"SELECT id, first_name, last_name, email, phone, preferred_frequency,
                   max_consecutive_weeks, preference_level, active, notes,
                   created_at, updated_at, exclude_monaguillos, exclude_lectores, photo_url,
-                  birth_date, first_communion, parent_name, address, photo_consent
-           FROM people ORDER BY last_name, first_name"#
+                  birth_date, first_communion, parent_name, address, photo_consent,
+                  min_rest_days, scheduling_notes, contact_bounced
+           FROM people"#,
+        &query,
     )
+    .into_builder();
+    builder.push(" ORDER BY last_name, first_name, id");
+    if let Some(limit) = limit {
+        builder
+            .push(" LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind((page - 1) * limit);
+    }
+    let people = builder
+        .build_query_as::<Person>()
         .fetch_all(&pool)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    let mut result = Vec::new();
-    for person in people {
-        let job_ids: Vec<String> =
-            sqlx::query_scalar("SELECT job_id FROM person_jobs WHERE person_id = $1")
-                .bind(&person.id)
-                .fetch_all(&pool)
-                .await
-                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let person_ids: Vec<String> = people.iter().map(|p| p.id.clone()).collect();
+    let mut job_ids = group_by_person(
+        &pool,
+        "SELECT person_id, job_id FROM person_jobs WHERE person_id = ANY($1)",
+        &person_ids,
+    )
+    .await?;
+    let mut excluded_job_ids = group_by_person(
+        &pool,
+        "SELECT person_id, job_id FROM person_job_exclusions WHERE person_id = ANY($1) ORDER BY job_id",
+        &person_ids,
+    )
+    .await?;
+    let mut usernames = group_by_person(
+        &pool,
+        "SELECT person_id, username FROM users WHERE person_id = ANY($1)",
+        &person_ids,
+    )
+    .await?;
+    let mut stats = if include_stats {
+        get_all_stats(&pool).await?
+    } else {
+        HashMap::new()
+    };
 
-        let username = get_username_for_person(&pool, &person.id).await?;
+    let mut result = Vec::new();
+    for mut person in people {
+        hide_notes_unless_admin(&mut person, &claims);
+
+        let person_stats = if include_stats {
+            Some(stats.remove(&person.id).unwrap_or(PersonStats {
+                assignments_this_year: 0,
+                last_served_date: None,
+                upcoming_assignments: 0,
+            }))
+        } else {
+            None
+        };
 
         result.push(PersonWithJobs {
+            job_ids: job_ids.remove(&person.id).unwrap_or_default(),
+            excluded_job_ids: excluded_job_ids.remove(&person.id).unwrap_or_default(),
+            username: usernames
+                .remove(&person.id)
+                .and_then(|names| names.into_iter().next()),
+            stats: person_stats,
             person,
-            job_ids,
-            username,
         });
     }
 
-    Ok(Json(result))
+    Ok(Json(PeoplePage {
+        people: result,
+        total,
+        page,
+        limit,
+    }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/people/{id}",
+    tag = "people",
+    responses((status = 200, body = PersonWithJobs))
+)]
 pub async fn get_by_id(
     State(pool): State<PgPool>,
+    claims: Claims,
     Path(id): Path<String>,
 ) -> Result<Json<PersonWithJobs>, (StatusCode, String)> {
-    let person = sqlx::query_as::<_, Person>(
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(load_person(&mut conn, &claims, &id).await?))
+}
+
+// Person with jobs, exclusions and username, read on the given connection so a handler
+// can return what it just wrote before the transaction commits
+async fn load_person(
+    conn: &mut PgConnection,
+    claims: &Claims,
+    id: &str,
+) -> Result<PersonWithJobs, (StatusCode, String)> {
+    let mut person = sqlx::query_as::<_, Person>(
         r#"SELECT id, first_name, last_name, email, phone, preferred_frequency,
                   max_consecutive_weeks, preference_level, active, notes,
                   created_at, updated_at, exclude_monaguillos, exclude_lectores, photo_url,
-                  birth_date, first_communion, parent_name, address, photo_consent
-           FROM people WHERE id = $1"#
+                  birth_date, first_communion, parent_name, address, photo_consent,
+                  min_rest_days, scheduling_notes, contact_bounced
+           FROM people WHERE id = $1"#,
     )
-        .bind(&id)
-        .fetch_optional(&pool)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((StatusCode::NOT_FOUND, "Person not found".to_string()))?;
+    .bind(id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or((StatusCode::NOT_FOUND, "Person not found".to_string()))?;
+    hide_notes_unless_admin(&mut person, claims);
 
     let job_ids: Vec<String> =
         sqlx::query_scalar("SELECT job_id FROM person_jobs WHERE person_id = $1")
-            .bind(&id)
-            .fetch_all(&pool)
+            .bind(id)
+            .fetch_all(&mut *conn)
             .await
             .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    let username = get_username_for_person(&pool, &id).await?;
+    let excluded_job_ids = get_excluded_job_ids(&mut *conn, id).await?;
+    let username = get_username_for_person(&mut *conn, id).await?;
 
-    Ok(Json(PersonWithJobs {
+    Ok(PersonWithJobs {
         person,
         job_ids,
+        excluded_job_ids,
         username,
-    }))
+        stats: None,
+    })
 }
 
+#[utoipa::path(
+    post,
+    path = "/people",
+    tag = "people",
+    responses((status = 200, body = PersonWithCredentials))
+)]
 pub async fn create(
     State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
     Json(input): Json<CreatePerson>,
 ) -> Result<Json<PersonWithCredentials>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "add people")?;
+
+    // A coordinator adds people to their own jobs only
+    if claims.is_coordinator() && input.job_ids.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Pick at least one of the jobs you coordinate".to_string(),
+        ));
+    }
+    auth::require_job_changes_in_scope(&claims, &[], &input.job_ids)?;
+
     let id = Uuid::new_v4().to_string();
 
     let person = sqlx::query_as::<_, Person>(
         r#"
-        INSERT INTO people (id, first_name, last_name, email, phone, preferred_frequency, max_consecutive_weeks, preference_level, notes, birth_date, first_communion, parent_name, address, photo_consent)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+        INSERT INTO people (id, first_name, last_name, email, phone, preferred_frequency, max_consecutive_weeks, preference_level, notes, birth_date, first_communion, parent_name, address, photo_consent, min_rest_days, scheduling_notes)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
         RETURNING *
         "#
     )
@@ -204,15 +540,17 @@ pub async fn create(
     .bind(&input.email)
     .bind(&input.phone)
     .bind(&input.preferred_frequency)
-    .bind(&input.max_consecutive_weeks)
-    .bind(&input.preference_level)
+    .bind(input.max_consecutive_weeks)
+    .bind(input.preference_level)
     .bind(&input.notes)
-    .bind(&input.birth_date)
+    .bind(input.birth_date)
     .bind(input.first_communion.unwrap_or(false))
     .bind(&input.parent_name)
     .bind(&input.address)
     .bind(input.photo_consent.unwrap_or(false))
-    .fetch_one(&pool)
+    .bind(input.min_rest_days)
+    .bind(&input.scheduling_notes)
+    .fetch_one(&mut *tx)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
@@ -223,11 +561,34 @@ pub async fn create(
             .bind(&pj_id)
             .bind(&id)
             .bind(job_id)
-            .execute(&pool)
+            .execute(&mut *tx)
             .await
             .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     }
 
+    // Invited servidores pick their own password; the login is created when they accept
+    if input.invite {
+        let lifetime_days = settings::get_i64(
+            &mut *tx,
+            "invitation_lifetime_days",
+            invitations::DEFAULT_LIFETIME_DAYS,
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        let (invitation, token) =
+            invitations::issue(&mut *tx, &id, &claims.username, lifetime_days)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+        return Ok(Json(PersonWithCredentials {
+            person,
+            job_ids: input.job_ids,
+            username: None,
+            generated_password: None,
+            invitation: Some(IssuedInvitation { invitation, token }),
+        }));
+    }
+
     // Generate username and password for servidor login
     let username = generate_username(&pool, &input.first_name, &input.last_name).await?;
     let generated_password = generate_random_password();
@@ -241,162 +602,97 @@ pub async fn create(
     .bind(&username)
     .bind(&password_hash)
     .bind(&id)
-    .execute(&pool)
+    .execute(&mut *tx)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     Ok(Json(PersonWithCredentials {
         person,
         job_ids: input.job_ids,
-        username,
-        generated_password,
+        username: Some(username),
+        generated_password: Some(generated_password),
+        invitation: None,
     }))
 }
 
+#[utoipa::path(
+    put,
+    path = "/people/{id}",
+    tag = "people",
+    responses((status = 200, body = PersonWithJobs))
+)]
 pub async fn update(
     State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
     Path(id): Path<String>,
     Json(input): Json<UpdatePerson>,
 ) -> Result<Json<PersonWithJobs>, (StatusCode, String)> {
-    // Build dynamic update query
-    let mut updates = Vec::new();
-    let mut param_count = 1;
-
-    if input.first_name.is_some() {
-        updates.push(format!("first_name = ${}", param_count));
-        param_count += 1;
-    }
-    if input.last_name.is_some() {
-        updates.push(format!("last_name = ${}", param_count));
-        param_count += 1;
-    }
-    if input.email.is_some() {
-        updates.push(format!("email = ${}", param_count));
-        param_count += 1;
-    }
-    if input.phone.is_some() {
-        updates.push(format!("phone = ${}", param_count));
-        param_count += 1;
-    }
-    if input.preferred_frequency.is_some() {
-        updates.push(format!("preferred_frequency = ${}", param_count));
-        param_count += 1;
-    }
-    if input.max_consecutive_weeks.is_some() {
-        updates.push(format!("max_consecutive_weeks = ${}", param_count));
-        param_count += 1;
-    }
-    if input.preference_level.is_some() {
-        updates.push(format!("preference_level = ${}", param_count));
-        param_count += 1;
-    }
-    if input.active.is_some() {
-        updates.push(format!("active = ${}", param_count));
-        param_count += 1;
-    }
-    if input.notes.is_some() {
-        updates.push(format!("notes = ${}", param_count));
-        param_count += 1;
-    }
-    if input.exclude_monaguillos.is_some() {
-        updates.push(format!("exclude_monaguillos = ${}", param_count));
-        param_count += 1;
-    }
-    if input.exclude_lectores.is_some() {
-        updates.push(format!("exclude_lectores = ${}", param_count));
-        param_count += 1;
-    }
-    if input.birth_date.is_some() {
-        updates.push(format!("birth_date = ${}", param_count));
-        param_count += 1;
-    }
-    if input.first_communion.is_some() {
-        updates.push(format!("first_communion = ${}", param_count));
-        param_count += 1;
-    }
-    if input.parent_name.is_some() {
-        updates.push(format!("parent_name = ${}", param_count));
-        param_count += 1;
-    }
-    if input.address.is_some() {
-        updates.push(format!("address = ${}", param_count));
-        param_count += 1;
-    }
-    if input.photo_consent.is_some() {
-        updates.push(format!("photo_consent = ${}", param_count));
-        param_count += 1;
-    }
-
-    if !updates.is_empty() {
-        let query = format!(
-            "UPDATE people SET {} WHERE id = ${} RETURNING *",
-            updates.join(", "),
-            param_count
-        );
-
-        let mut q = sqlx::query_as::<_, Person>(&query);
-
-        if let Some(ref v) = input.first_name {
-            q = q.bind(v);
-        }
-        if let Some(ref v) = input.last_name {
-            q = q.bind(v);
-        }
-        if let Some(ref v) = input.email {
-            q = q.bind(v);
-        }
-        if let Some(ref v) = input.phone {
-            q = q.bind(v);
+    auth::require_admin_or_coordinator(&claims, "edit people")?;
+    auth::require_person_scope(&pool, &claims, &id).await?;
+    if claims.is_coordinator() {
+        if input.exclude_monaguillos.is_some() {
+            auth::require_job_scope(&claims, SENIOR_JOB_ID)?;
+            auth::require_job_scope(&claims, JUNIOR_JOB_ID)?;
         }
-        if let Some(ref v) = input.preferred_frequency {
-            q = q.bind(v);
+        if input.exclude_lectores.is_some() {
+            auth::require_job_scope(&claims, LECTORES_JOB_ID)?;
         }
-        if let Some(ref v) = input.max_consecutive_weeks {
-            q = q.bind(v);
+        if let Some(job_ids) = &input.job_ids {
+            let current = person_job_ids(&pool, &id).await?;
+            auth::require_job_changes_in_scope(&claims, &current, job_ids)?;
         }
-        if let Some(ref v) = input.preference_level {
-            q = q.bind(v);
-        }
-        if let Some(ref v) = input.active {
-            q = q.bind(v);
-        }
-        if let Some(ref v) = input.notes {
-            q = q.bind(v);
-        }
-        if let Some(ref v) = input.exclude_monaguillos {
-            q = q.bind(v);
-        }
-        if let Some(ref v) = input.exclude_lectores {
-            q = q.bind(v);
-        }
-        if let Some(ref v) = input.birth_date {
-            q = q.bind(v);
-        }
-        if let Some(ref v) = input.first_communion {
-            q = q.bind(v);
-        }
-        if let Some(ref v) = input.parent_name {
-            q = q.bind(v);
-        }
-        if let Some(ref v) = input.address {
-            q = q.bind(v);
-        }
-        if let Some(ref v) = input.photo_consent {
-            q = q.bind(v);
-        }
-        q = q.bind(&id);
+    }
 
-        q.fetch_one(&pool)
+    let mut update = Update::new("people");
+    update
+        .set("first_name", input.first_name.as_ref())
+        .set("last_name", input.last_name.as_ref())
+        .set("email", input.email.as_ref())
+        .set("phone", input.phone.as_ref());
+    // New contact details: give them a fresh chance after a bounce
+    if input.email.is_some() || input.phone.is_some() {
+        update.set_raw("contact_bounced = false");
+    }
+    update
+        .set("preferred_frequency", input.preferred_frequency.as_ref())
+        .set("max_consecutive_weeks", input.max_consecutive_weeks)
+        .set("preference_level", input.preference_level)
+        .set("active", input.active)
+        .set("notes", input.notes.as_ref())
+        .set("exclude_monaguillos", input.exclude_monaguillos)
+        .set("exclude_lectores", input.exclude_lectores)
+        .set("birth_date", input.birth_date)
+        .set("first_communion", input.first_communion)
+        .set("parent_name", input.parent_name.as_ref())
+        .set("address", input.address.as_ref())
+        .set("photo_consent", input.photo_consent)
+        .set("min_rest_days", input.min_rest_days)
+        .set("scheduling_notes", input.scheduling_notes.as_ref());
+
+    if !update.is_empty() {
+        update
+            .finish("id", &id)
+            .build_query_as::<Person>()
+            .fetch_one(&mut *tx)
             .await
             .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     }
 
+    // The legacy flags are shorthands for rows in person_job_exclusions
+    if let Some(excluded) = input.exclude_monaguillos {
+        set_legacy_exclusion(&mut tx, &id, &[SENIOR_JOB_ID, JUNIOR_JOB_ID], excluded).await?;
+    }
+    if let Some(excluded) = input.exclude_lectores {
+        set_legacy_exclusion(&mut tx, &id, &[LECTORES_JOB_ID], excluded).await?;
+    }
+
     // Update job_ids if provided
     if let Some(job_ids) = &input.job_ids {
         // Delete existing
         sqlx::query("DELETE FROM person_jobs WHERE person_id = $1")
             .bind(&id)
-            .execute(&pool)
+            .execute(&mut *tx)
             .await
             .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
@@ -407,30 +703,47 @@ pub async fn update(
                 .bind(&pj_id)
                 .bind(&id)
                 .bind(job_id)
-                .execute(&pool)
+                .execute(&mut *tx)
                 .await
                 .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
         }
     }
 
     // Return updated person
-    get_by_id(State(pool), Path(id)).await
+    Ok(Json(load_person(&mut tx, &claims, &id).await?))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/people/{id}",
+    tag = "people",
+    responses((status = 204))
+)]
 pub async fn delete(
     State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
     Path(id): Path<String>,
 ) -> Result<StatusCode, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "delete people")?;
+
+    // A coordinator can only remove someone who serves in nothing but their jobs
+    if claims.is_coordinator() {
+        auth::require_person_scope(&pool, &claims, &id).await?;
+        let current = person_job_ids(&pool, &id).await?;
+        auth::require_job_changes_in_scope(&claims, &current, &[])?;
+    }
+
     // Delete linked user first (cascade should handle this but be explicit)
     sqlx::query("DELETE FROM users WHERE person_id = $1")
         .bind(&id)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     let result = sqlx::query("DELETE FROM people WHERE id = $1")
         .bind(&id)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
@@ -442,23 +755,34 @@ pub async fn delete(
 }
 
 // Create user account for an existing person (servidor) who doesn't have one
+#[utoipa::path(
+    post,
+    path = "/people/{id}/create-user",
+    tag = "people",
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn create_user_account(
     State(pool): State<PgPool>,
+    claims: Claims,
     Path(person_id): Path<String>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "create accounts for people")?;
+    auth::require_person_scope(&pool, &claims, &person_id).await?;
+
     // Check person exists
     let person = sqlx::query_as::<_, Person>(
         r#"SELECT id, first_name, last_name, email, phone, preferred_frequency,
                   max_consecutive_weeks, preference_level, active, notes,
                   created_at, updated_at, exclude_monaguillos, exclude_lectores, photo_url,
-                  birth_date, first_communion, parent_name, address, photo_consent
-           FROM people WHERE id = $1"#
+                  birth_date, first_communion, parent_name, address, photo_consent,
+                  min_rest_days, scheduling_notes, contact_bounced
+           FROM people WHERE id = $1"#,
     )
-        .bind(&person_id)
-        .fetch_optional(&pool)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .ok_or((StatusCode::NOT_FOUND, "Person not found".to_string()))?;
+    .bind(&person_id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or((StatusCode::NOT_FOUND, "Person not found".to_string()))?;
 
     // Check if user account already exists
     let existing_user =
@@ -499,10 +823,21 @@ pub async fn create_user_account(
 }
 
 // Reset password for a servidor - returns the new password once
+#[utoipa::path(
+    post,
+    path = "/people/{id}/reset-password",
+    tag = "people",
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn reset_password(
     State(pool): State<PgPool>,
+    claims: Claims,
+    headers: HeaderMap,
     Path(person_id): Path<String>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "reset passwords")?;
+    auth::require_person_scope(&pool, &claims, &person_id).await?;
+
     // Check person exists
     let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM people WHERE id = $1)")
         .bind(&person_id)
@@ -520,21 +855,41 @@ pub async fn reset_password(
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     // Update user's password
-    let result = sqlx::query(
-        "UPDATE users SET password_hash = $1, updated_at = CURRENT_TIMESTAMP WHERE person_id = $2",
+    let user: Option<(Uuid, String)> = sqlx::query_as(
+        r#"
+        UPDATE users SET password_hash = $1, updated_at = CURRENT_TIMESTAMP WHERE person_id = $2
+        RETURNING id, username
+        "#,
     )
     .bind(&password_hash)
     .bind(&person_id)
-    .execute(&pool)
+    .fetch_optional(&pool)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    if result.rows_affected() == 0 {
+    let Some((user_id, username)) = user else {
         return Err((
             StatusCode::NOT_FOUND,
             "User not found for this person".to_string(),
         ));
-    }
+    };
+    // The old password may be how someone else got in
+    sessions::revoke_all(&pool, user_id, None)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let detail = format!("By {}", claims.username);
+    auth_events::record(
+        &pool,
+        &AuthEvent {
+            event: "password_reset",
+            user_id: Some(user_id),
+            username: Some(&username),
+            detail: Some(&detail),
+        },
+        &RequestOrigin::from_headers(&headers),
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
 
     Ok(Json(serde_json::json!({
         "message": "Password reset successfully",
@@ -542,6 +897,126 @@ pub async fn reset_password(
     })))
 }
 
+// Regenerate the username after a name correction
+// The old username keeps working for login during the grace period
+#[utoipa::path(
+    post,
+    path = "/people/{id}/regenerate-username",
+    tag = "people",
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn regenerate_username(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can change usernames".to_string(),
+        ));
+    }
+
+    let (first_name, last_name): (String, String) =
+        sqlx::query_as("SELECT first_name, last_name FROM people WHERE id = $1")
+            .bind(&person_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((StatusCode::NOT_FOUND, "Person not found".to_string()))?;
+
+    let (user_id, old_username): (Uuid, String) =
+        sqlx::query_as("SELECT id, username FROM users WHERE person_id = $1")
+            .bind(&person_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((
+                StatusCode::NOT_FOUND,
+                "User not found for this person".to_string(),
+            ))?;
+
+    // Nothing to do if the current username already matches the (corrected) name
+    if username_candidates(&normalize_name(&first_name), &normalize_name(&last_name))
+        .contains(&old_username)
+    {
+        return Ok(Json(serde_json::json!({
+            "message": "Username already matches the person's name",
+            "username": old_username,
+        })));
+    }
+
+    let new_username = generate_username(&pool, &first_name, &last_name).await?;
+
+    let grace_days = settings::get_i64(&pool, "username_alias_grace_days", 30)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    sqlx::query("UPDATE users SET username = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2")
+        .bind(&new_username)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let alias_expires_at = chrono::Utc::now() + chrono::Duration::days(grace_days);
+    sqlx::query(
+        r#"
+        INSERT INTO username_aliases (username, user_id, expires_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (username) DO UPDATE SET user_id = EXCLUDED.user_id, expires_at = EXCLUDED.expires_at
+        "#,
+    )
+    .bind(&old_username)
+    .bind(user_id)
+    .bind(alias_expires_at)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let message = format!(
+        "Su usuario cambió de '{}' a '{}'. El usuario anterior funcionará hasta el {}.",
+        old_username,
+        new_username,
+        alias_expires_at.format("%d/%m/%Y")
+    );
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if let Err(e) = notifications::send(
+        &mut conn,
+        "in_app",
+        &person_id,
+        "username_changed",
+        &message,
+    )
+    .await
+    {
+        tracing::warn!(
+            "Could not notify {} about username change: {}",
+            person_id,
+            e
+        );
+    }
+
+    Ok(Json(serde_json::json!({
+        "message": "Username regenerated successfully",
+        "username": new_username,
+        "old_username": old_username,
+        "old_username_valid_until": alias_expires_at,
+    })))
+}
+
 // Validate photo data URI
 fn validate_photo_data(photo_data: &str) -> Result<(), (StatusCode, String)> {
     // Check format: data:image/TYPE;base64,DATA
@@ -564,7 +1039,10 @@ fn validate_photo_data(photo_data: &str) -> Result<(), (StatusCode, String)> {
     if !allowed_types.contains(&mime_type) {
         return Err((
             StatusCode::BAD_REQUEST,
-            format!("Invalid image type: {}. Allowed: jpeg, png, webp", mime_type),
+            format!(
+                "Invalid image type: {}. Allowed: jpeg, png, webp",
+                mime_type
+            ),
         ));
     }
 
@@ -582,11 +1060,21 @@ fn validate_photo_data(photo_data: &str) -> Result<(), (StatusCode, String)> {
 }
 
 // Admin: Upload photo for any person
+#[utoipa::path(
+    post,
+    path = "/people/{id}/photo",
+    tag = "people",
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn upload_photo(
     State(pool): State<PgPool>,
+    claims: Claims,
     Path(person_id): Path<String>,
     Json(input): Json<UploadPhotoRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "change people's photos")?;
+    auth::require_person_scope(&pool, &claims, &person_id).await?;
+
     // Validate photo data
     validate_photo_data(&input.photo_data)?;
 
@@ -609,14 +1097,26 @@ pub async fn upload_photo(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    Ok(Json(serde_json::json!({ "message": "Photo uploaded successfully" })))
+    Ok(Json(
+        serde_json::json!({ "message": "Photo uploaded successfully" }),
+    ))
 }
 
 // Admin: Delete photo for any person
+#[utoipa::path(
+    delete,
+    path = "/people/{id}/photo",
+    tag = "people",
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn delete_photo(
     State(pool): State<PgPool>,
+    claims: Claims,
     Path(person_id): Path<String>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "change people's photos")?;
+    auth::require_person_scope(&pool, &claims, &person_id).await?;
+
     // Check person exists
     let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM people WHERE id = $1)")
         .bind(&person_id)
@@ -635,10 +1135,18 @@ pub async fn delete_photo(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    Ok(Json(serde_json::json!({ "message": "Photo deleted successfully" })))
+    Ok(Json(
+        serde_json::json!({ "message": "Photo deleted successfully" }),
+    ))
 }
 
 // Servidor: Upload own photo
+#[utoipa::path(
+    post,
+    path = "/my-photo",
+    tag = "people",
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn upload_my_photo(
     State(pool): State<PgPool>,
     claims: Claims,
@@ -661,10 +1169,18 @@ pub async fn upload_my_photo(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    Ok(Json(serde_json::json!({ "message": "Photo uploaded successfully" })))
+    Ok(Json(
+        serde_json::json!({ "message": "Photo uploaded successfully" }),
+    ))
 }
 
 // Servidor: Delete own photo
+#[utoipa::path(
+    delete,
+    path = "/my-photo",
+    tag = "people",
+    responses((status = 200, body = serde_json::Value))
+)]
 pub async fn delete_my_photo(
     State(pool): State<PgPool>,
     claims: Claims,
@@ -682,5 +1198,528 @@ pub async fn delete_my_photo(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    Ok(Json(serde_json::json!({ "message": "Photo deleted successfully" })))
+    Ok(Json(
+        serde_json::json!({ "message": "Photo deleted successfully" }),
+    ))
+}
+
+// ============ Per-job exclusions (admin) ============
+
+#[utoipa::path(
+    get,
+    path = "/people/{id}/exclusions",
+    tag = "people",
+    responses((status = 200, body = Vec<PersonJobExclusion>))
+)]
+pub async fn get_exclusions(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+) -> Result<Json<Vec<PersonJobExclusion>>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "see job exclusions")?;
+
+    let exclusions = sqlx::query_as::<_, PersonJobExclusion>(
+        "SELECT * FROM person_job_exclusions WHERE person_id = $1 ORDER BY job_id",
+    )
+    .bind(&person_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(exclusions))
+}
+
+/// Exclude a person from one job, or update the reason if already excluded
+#[utoipa::path(
+    put,
+    path = "/people/{id}/exclusions",
+    tag = "people",
+    responses((status = 200, body = PersonJobExclusion))
+)]
+pub async fn set_exclusion(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
+    Path(person_id): Path<String>,
+    Json(input): Json<CreatePersonJobExclusion>,
+) -> Result<Json<PersonJobExclusion>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "exclude people from jobs")?;
+    auth::require_job_scope(&claims, &input.job_id)?;
+    auth::require_person_scope(&pool, &claims, &person_id).await?;
+
+    let id = Uuid::new_v4().to_string();
+    let exclusion = sqlx::query_as::<_, PersonJobExclusion>(
+        r#"
+        INSERT INTO person_job_exclusions (id, person_id, job_id, reason)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (person_id, job_id) DO UPDATE SET reason = EXCLUDED.reason
+        RETURNING *
+        "#,
+    )
+    .bind(&id)
+    .bind(&person_id)
+    .bind(&input.job_id)
+    .bind(&input.reason)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    refresh_legacy_flags(&mut tx, &person_id).await?;
+
+    Ok(Json(exclusion))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/exclusions/{id}",
+    tag = "people",
+    responses((status = 204))
+)]
+pub async fn delete_exclusion(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "exclude people from jobs")?;
+
+    if claims.is_coordinator() {
+        let job_id: String =
+            sqlx::query_scalar("SELECT job_id FROM person_job_exclusions WHERE id = $1")
+                .bind(&id)
+                .fetch_optional(&pool)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                .ok_or((StatusCode::NOT_FOUND, "Exclusion not found".to_string()))?;
+        auth::require_job_scope(&claims, &job_id)?;
+    }
+
+    let person_id: String =
+        sqlx::query_scalar("DELETE FROM person_job_exclusions WHERE id = $1 RETURNING person_id")
+            .bind(&id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((StatusCode::NOT_FOUND, "Exclusion not found".to_string()))?;
+
+    refresh_legacy_flags(&mut tx, &person_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============ vCard import / export ============
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VCardExportQuery {
+    pub include_inactive: Option<bool>,
+}
+
+/// Download people as a vCard bundle (names, phone, email) for phone directories
+#[utoipa::path(
+    get,
+    path = "/people/vcard",
+    params(VCardExportQuery),
+    tag = "people",
+    responses((status = 200, content_type = "text/vcard", body = String))
+)]
+pub async fn export_vcard(
+    State(pool): State<PgPool>,
+    Query(query): Query<VCardExportQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let people = sqlx::query_as::<_, DirectoryRow>(
+        r#"
+        SELECT id, first_name, last_name, email, phone
+        FROM people
+        WHERE active = true OR $1
+        ORDER BY last_name, first_name
+        "#,
+    )
+    .bind(query.include_inactive.unwrap_or(false))
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let contacts: Vec<vcard::Contact> = people
+        .into_iter()
+        .map(|p| vcard::Contact {
+            first_name: p.first_name,
+            last_name: p.last_name,
+            phone: p.phone,
+            email: p.email,
+        })
+        .collect();
+
+    Ok(vcard::attachment(
+        "servidores.vcf",
+        vcard::to_vcards(&contacts),
+    ))
+}
+
+// ============ Directory export ============
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DirectoryExportQuery {
+    /// "csv" (default) or "xlsx"
+    pub format: Option<String>,
+    pub include_inactive: Option<bool>,
+    /// Leave out people whose family has not given photo/data consent, for lists that are
+    /// shared beyond the coordinators
+    pub consented_only: Option<bool>,
+}
+
+const DIRECTORY_HEADER: [&str; 8] = [
+    "Nombre",
+    "Apellido",
+    "Teléfono",
+    "Correo",
+    "Dirección",
+    "Ministerios",
+    "Activo",
+    "Padre/madre o encargado",
+];
+
+#[derive(Debug, Clone, FromRow)]
+struct DirectoryExportRow {
+    first_name: String,
+    last_name: String,
+    phone: Option<String>,
+    email: Option<String>,
+    address: Option<String>,
+    job_names: Vec<String>,
+    active: bool,
+    parent_name: Option<String>,
+}
+
+fn directory_grid(people: &[DirectoryExportRow]) -> Vec<Vec<String>> {
+    people
+        .iter()
+        .map(|p| {
+            vec![
+                p.first_name.clone(),
+                p.last_name.clone(),
+                p.phone.clone().unwrap_or_default(),
+                p.email.clone().unwrap_or_default(),
+                p.address.clone().unwrap_or_default(),
+                p.job_names.join(", "),
+                if p.active { "Sí" } else { "No" }.to_string(),
+                p.parent_name.clone().unwrap_or_default(),
+            ]
+        })
+        .collect()
+}
+
+/// Download the people roster as CSV or Excel. Coordinators get the people in their jobs.
+#[utoipa::path(
+    get,
+    path = "/people/directory",
+    params(DirectoryExportQuery),
+    tag = "people",
+    responses((status = 200, content((String = "text/csv"), ("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"))))
+)]
+pub async fn export_directory(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<DirectoryExportQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    if !claims.is_admin() && !claims.is_coordinator() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins and coordinators can export the directory".to_string(),
+        ));
+    }
+    let workbook = match query.format.as_deref() {
+        None | Some("csv") => false,
+        Some("xlsx") => true,
+        Some(other) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Unknown format '{}', use csv or xlsx", other),
+            ))
+        }
+    };
+
+    let people = sqlx::query_as::<_, DirectoryExportRow>(
+        r#"
+        SELECT p.first_name, p.last_name, p.phone, p.email, p.address, p.active, p.parent_name,
+               COALESCE(
+                   ARRAY_AGG(j.name ORDER BY j.name) FILTER (WHERE j.id IS NOT NULL),
+                   '{}'
+               ) as job_names
+        FROM people p
+        LEFT JOIN person_jobs pj ON pj.person_id = p.id
+        LEFT JOIN jobs j ON j.id = pj.job_id
+        WHERE (p.active = true OR $1)
+          AND (p.photo_consent = true OR NOT $2)
+          AND (NOT $3 OR EXISTS (
+              SELECT 1 FROM person_jobs sj WHERE sj.person_id = p.id AND sj.job_id = ANY($4)
+          ))
+        GROUP BY p.id
+        ORDER BY p.last_name, p.first_name
+        "#,
+    )
+    .bind(query.include_inactive.unwrap_or(false))
+    .bind(query.consented_only.unwrap_or(false))
+    .bind(claims.is_coordinator())
+    .bind(&claims.job_ids)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let grid = directory_grid(&people);
+    if workbook {
+        let mut rows = vec![DIRECTORY_HEADER.map(xlsx::Cell::from).to_vec()];
+        rows.extend(
+            grid.into_iter()
+                .map(|line| line.into_iter().map(xlsx::Cell::from).collect()),
+        );
+        let sheet = xlsx::Sheet {
+            name: "Servidores".to_string(),
+            rows,
+            widths: vec![18.0, 18.0, 14.0, 26.0, 30.0, 30.0, 8.0, 26.0],
+        };
+        let body = xlsx::workbook(&[sheet]).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        return Ok(xlsx::attachment("servidores.xlsx", body));
+    }
+    Ok(csv::attachment(
+        "servidores.csv",
+        csv::to_csv(&DIRECTORY_HEADER, &grid),
+    ))
+}
+
+#[derive(FromRow)]
+struct DirectoryRow {
+    id: String,
+    first_name: String,
+    last_name: String,
+    email: Option<String>,
+    phone: Option<String>,
+}
+
+fn phone_digits(phone: &str) -> String {
+    phone.chars().filter(|c| c.is_ascii_digit()).collect()
+}
+
+/// Why a contact matches an existing person: same email, same phone number or same name
+fn duplicate_reason(contact: &vcard::Contact, existing: &DirectoryRow) -> Option<&'static str> {
+    let same_email = match (&contact.email, &existing.email) {
+        (Some(a), Some(b)) => a.trim().eq_ignore_ascii_case(b.trim()),
+        _ => false,
+    };
+    if same_email {
+        return Some("Same email as an existing person");
+    }
+
+    let same_phone = match (&contact.phone, &existing.phone) {
+        (Some(a), Some(b)) => {
+            let (a, b) = (phone_digits(a), phone_digits(b));
+            // Compare the last 8 digits so a country code prefix does not matter
+            a.len() >= 7
+                && b.len() >= 7
+                && a[a.len().saturating_sub(8)..] == b[b.len().saturating_sub(8)..]
+        }
+        _ => false,
+    };
+    if same_phone {
+        return Some("Same phone as an existing person");
+    }
+
+    let contact_name = normalize_name(&format!("{}{}", contact.first_name, contact.last_name));
+    let existing_name = normalize_name(&format!("{}{}", existing.first_name, existing.last_name));
+    if contact_name == existing_name {
+        return Some("Same name as an existing person");
+    }
+
+    None
+}
+
+/// Create people from a vCard bundle. Contacts matching an existing person (email, phone or name)
+/// are skipped, or have their phone and email filled in with `on_duplicate: "update"`.
+/// Imported people get no login; use create-user afterwards for those who need one.
+#[utoipa::path(
+    post,
+    path = "/people/vcard",
+    tag = "people",
+    responses((status = 200, body = VCardImportResult))
+)]
+pub async fn import_vcard(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(input): Json<ImportVCardRequest>,
+) -> Result<Json<VCardImportResult>, (StatusCode, String)> {
+    // Imported contacts have no jobs yet, so they would land outside any coordinator's scope
+    auth::require_admin(&claims, "import contacts")?;
+
+    let update_duplicates = match input.on_duplicate.as_deref().unwrap_or("skip") {
+        "skip" => false,
+        "update" => true,
+        other => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Invalid on_duplicate: {}. Allowed: skip, update", other),
+            ))
+        }
+    };
+
+    let contacts = vcard::parse(&input.data);
+    if contacts.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "No contacts with a name found in the vCard data".to_string(),
+        ));
+    }
+
+    let mut existing = sqlx::query_as::<_, DirectoryRow>(
+        "SELECT id, first_name, last_name, email, phone FROM people",
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut result = VCardImportResult {
+        created: Vec::new(),
+        updated: Vec::new(),
+        skipped: Vec::new(),
+    };
+    let mut updated_ids: Vec<String> = Vec::new();
+
+    for contact in contacts {
+        let name = format!("{} {}", contact.first_name, contact.last_name)
+            .trim()
+            .to_string();
+
+        let duplicate = existing
+            .iter()
+            .find_map(|p| duplicate_reason(&contact, p).map(|reason| (p.id.clone(), reason)));
+
+        if let Some((person_id, reason)) = duplicate {
+            if !update_duplicates || updated_ids.contains(&person_id) {
+                result.skipped.push(VCardImportSkipped {
+                    name,
+                    reason: reason.to_string(),
+                });
+                continue;
+            }
+
+            let person = sqlx::query_as::<_, Person>(
+                r#"
+                UPDATE people
+                SET phone = COALESCE($1, phone), email = COALESCE($2, email), updated_at = NOW()
+                WHERE id = $3
+                RETURNING *
+                "#,
+            )
+            .bind(&contact.phone)
+            .bind(&contact.email)
+            .bind(&person_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+            updated_ids.push(person_id);
+            result.updated.push(person);
+            continue;
+        }
+
+        let id = Uuid::new_v4().to_string();
+        let person = sqlx::query_as::<_, Person>(
+            r#"
+            INSERT INTO people (id, first_name, last_name, email, phone)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(&id)
+        .bind(&contact.first_name)
+        .bind(&contact.last_name)
+        .bind(&contact.email)
+        .bind(&contact.phone)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        // Later cards in the same bundle are checked against this one too
+        existing.push(DirectoryRow {
+            id,
+            first_name: contact.first_name,
+            last_name: contact.last_name,
+            email: contact.email,
+            phone: contact.phone,
+        });
+        result.created.push(person);
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    tracing::info!(
+        "vCard import: {} created, {} updated, {} skipped",
+        result.created.len(),
+        result.updated.len(),
+        result.skipped.len()
+    );
+
+    Ok(Json(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directory_lists_jobs_and_active_flag() {
+        let person = DirectoryExportRow {
+            first_name: "Ana".to_string(),
+            last_name: "López".to_string(),
+            phone: Some("5555-1234".to_string()),
+            email: None,
+            address: None,
+            job_names: vec!["Lectores".to_string(), "Monaguillos".to_string()],
+            active: false,
+            parent_name: Some("María López".to_string()),
+        };
+        assert_eq!(
+            directory_grid(&[person]),
+            vec![vec![
+                "Ana",
+                "López",
+                "5555-1234",
+                "",
+                "",
+                "Lectores, Monaguillos",
+                "No",
+                "María López"
+            ]]
+        );
+    }
+
+    #[test]
+    fn username_candidates_follow_preference_order() {
+        let candidates = username_candidates("juan", "perez");
+        assert_eq!(candidates[0], "jperez");
+        assert_eq!(candidates[1], "juperez");
+        assert_eq!(candidates[2], "jperez1");
+        assert_eq!(candidates.last().unwrap(), "jperez99");
+    }
+
+    #[test]
+    fn username_candidates_handle_multibyte_first_names() {
+        let first = normalize_name("Çelia");
+        let candidates = username_candidates(&first, "gomez");
+        assert_eq!(candidates[0], "Çgomez");
+        assert_eq!(candidates[1], "Çegomez");
+    }
+
+    #[test]
+    fn username_candidates_skip_two_letter_form_for_single_letter_names() {
+        let candidates = username_candidates("j", "perez");
+        assert_eq!(candidates[1], "jperez1");
+    }
 }
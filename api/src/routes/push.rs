@@ -0,0 +1,88 @@
+use axum::{extract::State, http::StatusCode, Json};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::Claims;
+use crate::models::{DeletePushSubscriptionRequest, PushPublicKey, PushSubscriptionRequest};
+use crate::services::web_push::Vapid;
+
+// Web Push: browsers subscribe with the VAPID public key and the API queues pushes in
+// push_outbox; the background worker encrypts and sends them (services::push::send_pending).
+
+/// No key, so no push offered, unless the server has the key pair to send with
+#[utoipa::path(
+    get,
+    path = "/push/public-key",
+    tag = "push",
+    responses((status = 200, body = PushPublicKey))
+)]
+pub async fn get_public_key() -> Json<PushPublicKey> {
+    let public_key = Vapid::from_env().map(|vapid| vapid.public_key().to_string());
+    Json(PushPublicKey { public_key })
+}
+
+fn user_id(claims: &Claims) -> Result<Uuid, (StatusCode, String)> {
+    Uuid::parse_str(&claims.sub)
+        .map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid token".to_string()))
+}
+
+/// Turn on notifications for this browser. Subscribing again (or from another user on the same
+/// browser) moves the subscription to the current user.
+#[utoipa::path(
+    post,
+    path = "/my-push-subscriptions",
+    tag = "push",
+    responses((status = 204))
+)]
+pub async fn subscribe(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(input): Json<PushSubscriptionRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    if !input.endpoint.starts_with("https://") {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Push endpoint must be an https URL".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO push_subscriptions (id, user_id, endpoint, p256dh, auth)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (endpoint) DO UPDATE
+        SET user_id = EXCLUDED.user_id, p256dh = EXCLUDED.p256dh, auth = EXCLUDED.auth
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(user_id(&claims)?)
+    .bind(&input.endpoint)
+    .bind(&input.keys.p256dh)
+    .bind(&input.keys.auth)
+    .execute(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/my-push-subscriptions",
+    tag = "push",
+    responses((status = 204))
+)]
+pub async fn unsubscribe(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(input): Json<DeletePushSubscriptionRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    sqlx::query("DELETE FROM push_subscriptions WHERE endpoint = $1 AND user_id = $2")
+        .bind(&input.endpoint)
+        .bind(user_id(&claims)?)
+        .execute(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
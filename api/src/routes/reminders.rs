@@ -0,0 +1,95 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+
+use crate::auth::Claims;
+use crate::models::ServiceReminder;
+use crate::routes::settings;
+use crate::services::reminders::{self, ReminderRun};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReminderQuery {
+    /// Service date to remind; without it, the reminders due today (each person's lead time)
+    pub date: Option<NaiveDate>,
+}
+
+/// The given date, or else whoever's reminder is due today
+pub async fn reminder_run(
+    pool: &PgPool,
+    query: &ReminderQuery,
+) -> Result<ReminderRun, (StatusCode, String)> {
+    if let Some(date) = query.date {
+        return Ok(ReminderRun::Date(date));
+    }
+    let default_days = settings::get_i64(pool, "reminder_days_before", 2)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    Ok(ReminderRun::Due {
+        today: Utc::now().date_naive(),
+        default_days,
+    })
+}
+
+/// Who would be reminded, with the message each gets and whether it already went out
+#[utoipa::path(
+    get,
+    path = "/admin/reminders",
+    params(ReminderQuery),
+    tag = "reminders",
+    responses((status = 200, body = Vec<ServiceReminder>))
+)]
+pub async fn preview(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<ReminderQuery>,
+) -> Result<Json<Vec<ServiceReminder>>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can preview reminders".to_string(),
+        ));
+    }
+
+    let run = reminder_run(&pool, &query).await?;
+    let reminders = reminders::reminders_for(&pool, run)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(reminders))
+}
+
+/// Send the due reminders that have not gone out yet. Meant to be called once a day by a
+/// scheduler as well as by hand; a second call the same day sends nothing new.
+#[utoipa::path(
+    post,
+    path = "/admin/reminders/send",
+    params(ReminderQuery),
+    tag = "reminders",
+    responses((status = 200, body = Vec<ServiceReminder>))
+)]
+pub async fn send(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<ReminderQuery>,
+) -> Result<Json<Vec<ServiceReminder>>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can send reminders".to_string(),
+        ));
+    }
+
+    let run = reminder_run(&pool, &query).await?;
+    let sent = reminders::send_reminders(&pool, run)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(sent))
+}
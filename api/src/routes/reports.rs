@@ -1,15 +1,36 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
+    response::{IntoResponse, Response},
     Json,
 };
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate, Utc};
 use serde::Deserialize;
 use sqlx::{FromRow, PgPool};
+use std::collections::HashMap;
+use utoipa::IntoParams;
 
-use crate::models::{FairnessScore, JobAssignmentCount, PersonHistoryEntry};
+use crate::auth::{self, Claims};
+use crate::models::{
+    BirthdayEntry, FairnessScore, Job, JobAssignmentCount, JuniorRosterEntry, PersonHistoryEntry,
+    RecognitionEntry, RecruitingNeed, JUNIOR_JOB_ID, SENIOR_JOB_ID,
+};
+use crate::routes::{settings, staffing};
+use crate::services::age::{age_on, birthday_in_year};
+use crate::services::csv;
+use crate::services::generator::{get_sundays_of_month, has_consecutive_month_restriction};
+use crate::services::pdf;
+use crate::services::xlsx;
+
+/// Reports show people's history, ages and birthdays: they are for admins and coordinators, and
+/// a coordinator's only cover their jobs. Returns that scope (None for admins) for the queries.
+fn report_scope(claims: &Claims) -> Result<Option<&Vec<String>>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(claims, "see reports")?;
+    Ok(claims.is_coordinator().then_some(&claims.job_ids))
+}
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct FairnessQuery {
     year: i32,
 }
@@ -28,10 +49,20 @@ struct JobCountRow {
     count: i64,
 }
 
+#[utoipa::path(
+    get,
+    path = "/reports/fairness",
+    params(FairnessQuery),
+    tag = "reports",
+    responses((status = 200, body = Vec<FairnessScore>))
+)]
 pub async fn get_fairness_scores(
     State(pool): State<PgPool>,
+    claims: Claims,
     Query(query): Query<FairnessQuery>,
 ) -> Result<Json<Vec<FairnessScore>>, (StatusCode, String)> {
+    let scope = report_scope(&claims)?;
+
     // Get all active people with their assignment counts
     let rows = sqlx::query_as::<_, FairnessRow>(
         r#"
@@ -43,11 +74,15 @@ pub async fn get_fairness_scores(
         FROM people p
         LEFT JOIN assignment_history ah ON p.id = ah.person_id AND ah.year = $1
         WHERE p.active = true
+          AND ($2::text[] IS NULL OR EXISTS (
+              SELECT 1 FROM person_jobs sp WHERE sp.person_id = p.id AND sp.job_id = ANY($2)
+          ))
         GROUP BY p.id, p.first_name, p.last_name
         ORDER BY assignments_this_year DESC, p.last_name, p.first_name
         "#,
     )
     .bind(query.year)
+    .bind(scope)
     .fetch_all(&pool)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
@@ -93,6 +128,145 @@ pub async fn get_fairness_scores(
     Ok(Json(result))
 }
 
+// ============ Fairness workbook ============
+
+/// How often an active person served in one job during the year. People who hold the job
+/// but never served it have a zero row, which is what the review with the pastor is about.
+#[derive(Debug, Clone, FromRow)]
+struct FairnessJobRow {
+    job_name: String,
+    person_id: String,
+    person_name: String,
+    count: i64,
+    last_served: Option<NaiveDate>,
+}
+
+fn format_day(date: Option<NaiveDate>) -> String {
+    date.map(|d| d.format("%d/%m/%Y").to_string())
+        .unwrap_or_default()
+}
+
+/// A summary sheet (one column per job) followed by one sheet per job, busiest people first.
+/// `rows` come ordered by job and then by count.
+fn fairness_sheets(year: i32, rows: &[FairnessJobRow]) -> Vec<xlsx::Sheet> {
+    let mut jobs: Vec<&str> = Vec::new();
+    for row in rows {
+        if jobs.last() != Some(&row.job_name.as_str()) {
+            jobs.push(&row.job_name);
+        }
+    }
+
+    // person_id -> (name, count per job, last served anywhere)
+    let mut people: HashMap<&str, (&str, Vec<i64>, Option<NaiveDate>)> = HashMap::new();
+    for row in rows {
+        let job = jobs.iter().position(|j| *j == row.job_name).unwrap_or(0);
+        let entry = people
+            .entry(&row.person_id)
+            .or_insert_with(|| (&row.person_name, vec![0; jobs.len()], None));
+        entry.1[job] = row.count;
+        entry.2 = entry.2.max(row.last_served);
+    }
+    let total = |id: &str| people.get(id).map_or(0, |p| p.1.iter().sum::<i64>());
+
+    let mut summary: Vec<_> = people.values().collect();
+    summary.sort_by(|a, b| {
+        b.1.iter()
+            .sum::<i64>()
+            .cmp(&a.1.iter().sum::<i64>())
+            .then_with(|| a.0.cmp(b.0))
+    });
+    let mut header: Vec<xlsx::Cell> = vec!["Servidor".into(), format!("Total {}", year).into()];
+    header.extend(jobs.iter().map(|job| xlsx::Cell::from(*job)));
+    header.push("Último servicio".into());
+    let mut summary_rows = vec![header];
+    for (name, counts, last) in summary {
+        let mut line: Vec<xlsx::Cell> = vec![(*name).into(), counts.iter().sum::<i64>().into()];
+        line.extend(counts.iter().map(|count| xlsx::Cell::from(*count)));
+        line.push(format_day(*last).into());
+        summary_rows.push(line);
+    }
+    let mut widths = vec![30.0, 12.0];
+    widths.extend(jobs.iter().map(|_| 14.0));
+    widths.push(16.0);
+    let mut sheets = vec![xlsx::Sheet {
+        name: "Resumen".to_string(),
+        rows: summary_rows,
+        widths,
+    }];
+
+    for job in &jobs {
+        let mut sheet_rows = vec![vec![
+            "Servidor".into(),
+            format!("Servicios {}", year).into(),
+            "Último servicio".into(),
+            "Total en todos los ministerios".into(),
+        ]];
+        for row in rows.iter().filter(|row| row.job_name == *job) {
+            sheet_rows.push(vec![
+                row.person_name.as_str().into(),
+                row.count.into(),
+                format_day(row.last_served).into(),
+                total(&row.person_id).into(),
+            ]);
+        }
+        sheets.push(xlsx::Sheet {
+            name: job.to_string(),
+            rows: sheet_rows,
+            widths: vec![30.0, 14.0, 16.0, 16.0],
+        });
+    }
+    sheets
+}
+
+/// The fairness report as an Excel workbook for reviewing balance with the pastor: a summary
+/// sheet, then one sheet per job with each person's count and last date served in it
+#[utoipa::path(
+    get,
+    path = "/reports/fairness/export",
+    params(FairnessQuery),
+    tag = "reports",
+    responses((status = 200, content_type = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"))
+)]
+pub async fn export_fairness_workbook(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<FairnessQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let scope = report_scope(&claims)?;
+
+    let rows = sqlx::query_as::<_, FairnessJobRow>(
+        r#"
+        SELECT j.name as job_name, p.id as person_id,
+               p.first_name || ' ' || p.last_name as person_name,
+               COUNT(ah.id) as count, MAX(ah.service_date) as last_served
+        FROM people p
+        JOIN jobs j ON EXISTS (
+                SELECT 1 FROM person_jobs pj WHERE pj.person_id = p.id AND pj.job_id = j.id
+            ) OR EXISTS (
+                SELECT 1 FROM assignment_history h
+                WHERE h.person_id = p.id AND h.job_id = j.id AND h.year = $1
+            )
+        LEFT JOIN assignment_history ah
+            ON ah.person_id = p.id AND ah.job_id = j.id AND ah.year = $1
+        WHERE p.active = true AND ($2::text[] IS NULL OR j.id = ANY($2))
+        GROUP BY j.id, j.name, p.id, p.first_name, p.last_name
+        ORDER BY j.name, j.id, count DESC, p.last_name, p.first_name
+        "#,
+    )
+    .bind(query.year)
+    .bind(scope)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let body = xlsx::workbook(&fairness_sheets(query.year, &rows))
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    Ok(xlsx::attachment(
+        &format!("equidad-{}.xlsx", query.year),
+        body,
+    ))
+}
+
 #[derive(FromRow)]
 struct HistoryRow {
     service_date: NaiveDate,
@@ -102,10 +276,20 @@ struct HistoryRow {
     position_name: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/reports/person/{id}/history",
+    tag = "reports",
+    responses((status = 200, body = Vec<PersonHistoryEntry>))
+)]
 pub async fn get_person_history(
     State(pool): State<PgPool>,
+    claims: Claims,
     Path(person_id): Path<String>,
 ) -> Result<Json<Vec<PersonHistoryEntry>>, (StatusCode, String)> {
+    report_scope(&claims)?;
+    auth::require_person_scope(&pool, &claims, &person_id).await?;
+
     let rows = sqlx::query_as::<_, HistoryRow>(
         r#"
         SELECT
@@ -139,3 +323,641 @@ pub async fn get_person_history(
 
     Ok(Json(result))
 }
+
+// ============ Monaguillos Jr. Program ============
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct JuniorRosterQuery {
+    #[serde(default)]
+    ready_only: bool,
+}
+
+#[derive(FromRow)]
+struct JuniorRow {
+    person_id: String,
+    person_name: String,
+    birth_date: Option<NaiveDate>,
+    first_communion: bool,
+    junior_services: i64,
+    senior_services: i64,
+    last_junior_service: Option<NaiveDate>,
+    qualified_as_senior: bool,
+}
+
+#[utoipa::path(
+    get,
+    path = "/reports/juniors",
+    params(JuniorRosterQuery),
+    tag = "reports",
+    responses((status = 200, body = Vec<JuniorRosterEntry>))
+)]
+pub async fn get_junior_roster(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<JuniorRosterQuery>,
+) -> Result<Json<Vec<JuniorRosterEntry>>, (StatusCode, String)> {
+    report_scope(&claims)?;
+    auth::require_job_scope(&claims, JUNIOR_JOB_ID)?;
+
+    let min_age = settings::get_i64(&pool, "junior_promotion_min_age", 10)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let min_services = settings::get_i64(&pool, "junior_promotion_min_services", 8)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let rows = sqlx::query_as::<_, JuniorRow>(
+        r#"
+        SELECT
+            p.id as person_id,
+            p.first_name || ' ' || p.last_name as person_name,
+            p.birth_date,
+            p.first_communion,
+            COUNT(ah.id) FILTER (WHERE ah.job_id = $1) as junior_services,
+            COUNT(ah.id) FILTER (WHERE ah.job_id = $2) as senior_services,
+            MAX(ah.service_date) FILTER (WHERE ah.job_id = $1) as last_junior_service,
+            EXISTS (
+                SELECT 1 FROM person_jobs sj WHERE sj.person_id = p.id AND sj.job_id = $2
+            ) as qualified_as_senior
+        FROM people p
+        JOIN person_jobs pj ON p.id = pj.person_id AND pj.job_id = $1
+        LEFT JOIN assignment_history ah ON p.id = ah.person_id
+        WHERE p.active = true
+        GROUP BY p.id, p.first_name, p.last_name, p.birth_date, p.first_communion
+        ORDER BY p.birth_date NULLS LAST, p.last_name, p.first_name
+        "#,
+    )
+    .bind(JUNIOR_JOB_ID)
+    .bind(SENIOR_JOB_ID)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let today = Utc::now().date_naive();
+
+    let result: Vec<JuniorRosterEntry> = rows
+        .into_iter()
+        .map(|row| {
+            let age = row.birth_date.map(|b| age_on(b, today));
+
+            let mut missing_requirements = Vec::new();
+            match age {
+                Some(a) if (a as i64) < min_age => {
+                    missing_requirements.push(format!("Must be at least {} years old", min_age))
+                }
+                None => missing_requirements.push("Birth date not recorded".to_string()),
+                _ => {}
+            }
+            if row.junior_services < min_services {
+                missing_requirements.push(format!(
+                    "Needs {} more junior services",
+                    min_services - row.junior_services
+                ));
+            }
+            if !row.first_communion {
+                missing_requirements.push("First communion not recorded".to_string());
+            }
+
+            JuniorRosterEntry {
+                person_id: row.person_id,
+                person_name: row.person_name,
+                birth_date: row.birth_date,
+                age,
+                first_communion: row.first_communion,
+                junior_services: row.junior_services,
+                senior_services: row.senior_services,
+                last_junior_service: row.last_junior_service,
+                qualified_as_senior: row.qualified_as_senior,
+                ready_for_promotion: missing_requirements.is_empty(),
+                missing_requirements,
+            }
+        })
+        .filter(|entry| !query.ready_only || entry.ready_for_promotion)
+        .collect();
+
+    Ok(Json(result))
+}
+
+// ============ Birthdays ============
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BirthdaysQuery {
+    month: u32,
+    year: Option<i32>,
+}
+
+#[derive(FromRow)]
+struct BirthdayRow {
+    person_id: String,
+    person_name: String,
+    birth_date: NaiveDate,
+    photo_url: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/reports/birthdays",
+    params(BirthdaysQuery),
+    tag = "reports",
+    responses((status = 200, body = Vec<BirthdayEntry>))
+)]
+pub async fn get_birthdays(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<BirthdaysQuery>,
+) -> Result<Json<Vec<BirthdayEntry>>, (StatusCode, String)> {
+    let scope = report_scope(&claims)?;
+    if !(1..=12).contains(&query.month) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "month must be between 1 and 12".to_string(),
+        ));
+    }
+    let year = query.year.unwrap_or_else(|| Utc::now().year());
+
+    let rows = sqlx::query_as::<_, BirthdayRow>(
+        r#"
+        SELECT
+            p.id as person_id,
+            p.first_name || ' ' || p.last_name as person_name,
+            p.birth_date,
+            p.photo_url
+        FROM people p
+        WHERE p.active = true
+          AND p.birth_date IS NOT NULL
+          AND EXTRACT(MONTH FROM p.birth_date) = $1
+          AND ($2::text[] IS NULL OR EXISTS (
+              SELECT 1 FROM person_jobs sp WHERE sp.person_id = p.id AND sp.job_id = ANY($2)
+          ))
+        ORDER BY EXTRACT(DAY FROM p.birth_date), p.last_name, p.first_name
+        "#,
+    )
+    .bind(query.month as i32)
+    .bind(scope)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let result: Vec<BirthdayEntry> = rows
+        .into_iter()
+        .map(|row| {
+            let birthday = birthday_in_year(row.birth_date, year);
+            BirthdayEntry {
+                person_id: row.person_id,
+                person_name: row.person_name,
+                birth_date: row.birth_date,
+                birthday,
+                turning_age: age_on(row.birth_date, birthday),
+                photo_url: row.photo_url,
+            }
+        })
+        .collect();
+
+    Ok(Json(result))
+}
+
+// ============ Recruiting needs ============
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RecruitingNeedsQuery {
+    year: Option<i32>,
+    month: Option<u32>,
+    format: Option<String>,
+}
+
+#[derive(FromRow)]
+struct PoolMemberRow {
+    preferred_frequency: Option<String>,
+    active_jobs: i64,
+    blacked_out: bool,
+}
+
+/// Per job, compare the slots a month needs with what the current pool can sustainably cover.
+/// Everyone serves a job at most once a month; people who prefer to serve monthly are split
+/// across the jobs they do, and jobs with the consecutive-month rule only get each person
+/// every other month (unless the month has 5 Sundays).
+#[utoipa::path(
+    get,
+    path = "/reports/recruiting-needs",
+    params(RecruitingNeedsQuery),
+    tag = "reports",
+    responses((status = 200, content((Vec<RecruitingNeed> = "application/json"), (String = "text/csv"))))
+)]
+pub async fn get_recruiting_needs(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<RecruitingNeedsQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    report_scope(&claims)?;
+
+    // Default to next month, the one usually being planned
+    let today = Utc::now().date_naive();
+    let (default_year, default_month) = if today.month() == 12 {
+        (today.year() + 1, 1)
+    } else {
+        (today.year(), today.month() + 1)
+    };
+    let year = query.year.unwrap_or(default_year);
+    let month = query.month.unwrap_or(default_month);
+    if !(1..=12).contains(&month) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "month must be between 1 and 12".to_string(),
+        ));
+    }
+
+    let mut jobs = sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE active = true ORDER BY name")
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    jobs.retain(|job| claims.in_scope(&job.id));
+
+    let sundays = get_sundays_of_month(year, month);
+
+    let mut needs = Vec::new();
+    for job in &jobs {
+        let mut required_slots = 0i64;
+        for sunday in &sundays {
+            let required = staffing::people_required(&pool, *sunday, &job.id, job.people_required)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            required_slots += required.max(0) as i64;
+        }
+
+        let members = sqlx::query_as::<_, PoolMemberRow>(
+            r#"
+            SELECT
+                p.preferred_frequency,
+                (SELECT COUNT(*) FROM person_jobs opj
+                 JOIN jobs oj ON opj.job_id = oj.id
+                 WHERE opj.person_id = p.id AND oj.active = true) as active_jobs,
+                EXISTS (
+                    SELECT 1 FROM person_job_blackouts b
+                    WHERE b.person_id = p.id AND b.job_id = $1 AND $3 = ANY(b.months)
+                ) as blacked_out
+            FROM people p
+            JOIN person_jobs pj ON p.id = pj.person_id
+            WHERE pj.job_id = $1
+              AND p.active = true
+              AND NOT EXISTS (
+                  SELECT 1 FROM person_job_exclusions x
+                  WHERE x.person_id = p.id AND x.job_id = $1
+              )
+              AND (NOT $2 OR p.first_communion = true)
+            "#,
+        )
+        .bind(&job.id)
+        .bind(job.requires_first_communion)
+        .bind(month as i32)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        // Share of a month a single person can give this job under the rotation rules
+        let rotation_share = if has_consecutive_month_restriction(&job.name) && sundays.len() <= 4 {
+            0.5
+        } else {
+            1.0
+        };
+
+        let available: Vec<&PoolMemberRow> = members.iter().filter(|m| !m.blacked_out).collect();
+        let sustainable_slots: f64 = available
+            .iter()
+            .map(|m| {
+                let frequency_share = match m.preferred_frequency.as_deref() {
+                    Some("monthly") => 1.0 / m.active_jobs.max(1) as f64,
+                    _ => 1.0,
+                };
+                rotation_share * frequency_share
+            })
+            .sum();
+
+        let gap = (required_slots as f64 - sustainable_slots).ceil().max(0.0) as i64;
+        // A new recruit is assumed to serve only this job
+        let recruits_needed = (gap as f64 / rotation_share).ceil() as i64;
+
+        needs.push(RecruitingNeed {
+            job_id: job.id.clone(),
+            job_name: job.name.clone(),
+            year,
+            month,
+            required_slots,
+            qualified_people: members.len() as i64,
+            available_people: available.len() as i64,
+            sustainable_slots: (sustainable_slots * 10.0).round() / 10.0,
+            gap,
+            recruits_needed,
+        });
+    }
+
+    if query.format.as_deref() == Some("csv") {
+        let rows: Vec<Vec<String>> = needs
+            .iter()
+            .map(|n| {
+                vec![
+                    n.job_name.clone(),
+                    format!("{}-{:02}", n.year, n.month),
+                    n.required_slots.to_string(),
+                    n.qualified_people.to_string(),
+                    n.available_people.to_string(),
+                    n.sustainable_slots.to_string(),
+                    n.gap.to_string(),
+                    n.recruits_needed.to_string(),
+                ]
+            })
+            .collect();
+        let body = csv::to_csv(
+            &[
+                "job",
+                "month",
+                "required_slots",
+                "qualified_people",
+                "available_people",
+                "sustainable_slots",
+                "gap",
+                "recruits_needed",
+            ],
+            &rows,
+        );
+        return Ok(csv::attachment(
+            &format!("recruiting-needs-{}-{:02}.csv", year, month),
+            body,
+        ));
+    }
+
+    Ok(Json(needs).into_response())
+}
+
+// ============ Year-end recognition ============
+
+/// Lifetime service counts celebrated at the annual appreciation Mass
+const RECOGNITION_MILESTONES: [i64; 3] = [25, 50, 100];
+
+#[derive(FromRow)]
+struct RecognitionRow {
+    person_id: String,
+    person_name: String,
+    services_in_year: i64,
+    lifetime_services: i64,
+}
+
+/// Milestones passed when the lifetime count went from `before` to `after`
+fn milestones_crossed(before: i64, after: i64) -> Vec<i64> {
+    RECOGNITION_MILESTONES
+        .iter()
+        .copied()
+        .filter(|m| before < *m && *m <= after)
+        .collect()
+}
+
+fn next_milestone(lifetime: i64) -> Option<i64> {
+    RECOGNITION_MILESTONES
+        .iter()
+        .copied()
+        .find(|m| *m > lifetime)
+}
+
+/// Ranks for counts sorted from highest to lowest; equal counts share a rank (1, 2, 2, 4)
+fn competition_ranks(counts: &[i64]) -> Vec<i64> {
+    let mut ranks = Vec::with_capacity(counts.len());
+    for (i, count) in counts.iter().enumerate() {
+        let rank = match ranks.last() {
+            Some(&previous) if counts[i - 1] == *count => previous,
+            _ => i as i64 + 1,
+        };
+        ranks.push(rank);
+    }
+    ranks
+}
+
+/// Servidores who served during the year, most services first. Only services on dates that
+/// have already passed count, so planned assignments later in the year are left out.
+async fn recognition_entries(
+    pool: &PgPool,
+    year: i32,
+    scope: Option<&Vec<String>>,
+) -> Result<Vec<RecognitionEntry>, (StatusCode, String)> {
+    let (Some(year_start), Some(year_end)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        return Err((StatusCode::BAD_REQUEST, "Invalid year".to_string()));
+    };
+    let until = year_end.min(Utc::now().date_naive());
+
+    let rows = sqlx::query_as::<_, RecognitionRow>(
+        r#"
+        SELECT
+            p.id as person_id,
+            p.first_name || ' ' || p.last_name as person_name,
+            COUNT(ah.id) FILTER (WHERE ah.service_date >= $1) as services_in_year,
+            COUNT(ah.id) as lifetime_services
+        FROM people p
+        JOIN assignment_history ah ON p.id = ah.person_id
+        WHERE ah.service_date <= $2
+          AND ($3::text[] IS NULL OR EXISTS (
+              SELECT 1 FROM person_jobs sp WHERE sp.person_id = p.id AND sp.job_id = ANY($3)
+          ))
+        GROUP BY p.id, p.first_name, p.last_name
+        HAVING COUNT(ah.id) FILTER (WHERE ah.service_date >= $1) > 0
+        ORDER BY services_in_year DESC, p.last_name, p.first_name
+        "#,
+    )
+    .bind(year_start)
+    .bind(until)
+    .bind(scope)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let counts: Vec<i64> = rows.iter().map(|row| row.services_in_year).collect();
+    let ranks = competition_ranks(&counts);
+
+    Ok(rows
+        .into_iter()
+        .zip(ranks)
+        .map(|(row, rank)| RecognitionEntry {
+            rank,
+            milestones_reached: milestones_crossed(
+                row.lifetime_services - row.services_in_year,
+                row.lifetime_services,
+            ),
+            next_milestone: next_milestone(row.lifetime_services),
+            person_id: row.person_id,
+            person_name: row.person_name,
+            services_in_year: row.services_in_year,
+            lifetime_services: row.lifetime_services,
+        })
+        .collect())
+}
+
+#[utoipa::path(
+    get,
+    path = "/reports/recognition/{year}",
+    tag = "reports",
+    responses((status = 200, body = Vec<RecognitionEntry>))
+)]
+pub async fn get_recognition(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(year): Path<i32>,
+) -> Result<Json<Vec<RecognitionEntry>>, (StatusCode, String)> {
+    let scope = report_scope(&claims)?;
+    Ok(Json(recognition_entries(&pool, year, scope).await?))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CertificatesQuery {
+    person_id: Option<String>,
+}
+
+fn certificate_page(entry: &RecognitionEntry, year: i32) -> pdf::Page {
+    let services = if entry.services_in_year == 1 {
+        "servicio".to_string()
+    } else {
+        format!("{} servicios", entry.services_in_year)
+    };
+    let mut lines = vec![
+        pdf::CenteredLine {
+            text: "Certificado de Reconocimiento".to_string(),
+            size: 32.0,
+            y: 450.0,
+            bold: true,
+        },
+        pdf::CenteredLine {
+            text: "Con gratitud a".to_string(),
+            size: 16.0,
+            y: 390.0,
+            bold: false,
+        },
+        pdf::CenteredLine {
+            text: entry.person_name.clone(),
+            size: 30.0,
+            y: 335.0,
+            bold: true,
+        },
+        pdf::CenteredLine {
+            text: format!("por su {} durante el año {}", services, year),
+            size: 16.0,
+            y: 280.0,
+            bold: false,
+        },
+    ];
+    if let Some(milestone) = entry.milestones_reached.last() {
+        lines.push(pdf::CenteredLine {
+            text: format!("¡{} servicios en total!", milestone),
+            size: 18.0,
+            y: 235.0,
+            bold: true,
+        });
+    }
+    pdf::Page { lines }
+}
+
+/// Recognition certificates as a PDF, one page per servidor (or just the one asked for)
+#[utoipa::path(
+    get,
+    path = "/reports/recognition/{year}/certificates",
+    params(CertificatesQuery),
+    tag = "reports",
+    responses((status = 200, content_type = "application/pdf"))
+)]
+pub async fn get_recognition_certificates(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(year): Path<i32>,
+    Query(query): Query<CertificatesQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let scope = report_scope(&claims)?;
+    let mut entries = recognition_entries(&pool, year, scope).await?;
+    if let Some(person_id) = &query.person_id {
+        entries.retain(|entry| &entry.person_id == person_id);
+    }
+    if entries.is_empty() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("No services recorded in {}", year),
+        ));
+    }
+
+    let pages: Vec<pdf::Page> = entries
+        .iter()
+        .map(|entry| certificate_page(entry, year))
+        .collect();
+    let filename = match &query.person_id {
+        Some(person_id) => format!("reconocimiento-{}-{}.pdf", year, person_id),
+        None => format!("reconocimiento-{}.pdf", year),
+    };
+
+    let title = format!("Reconocimiento {}", year);
+    let body = pdf::document(&title, &pages).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Could not write the certificates: {}", e),
+        )
+    })?;
+    Ok(pdf::attachment(&filename, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fairness_row(job: &str, person: &str, count: i64, last_day: Option<u32>) -> FairnessJobRow {
+        FairnessJobRow {
+            job_name: job.to_string(),
+            person_id: person.to_lowercase(),
+            person_name: person.to_string(),
+            count,
+            last_served: last_day.map(|d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap()),
+        }
+    }
+
+    #[test]
+    fn fairness_workbook_has_a_summary_and_a_sheet_per_job() {
+        let sheets = fairness_sheets(
+            2026,
+            &[
+                fairness_row("Lectores", "Ana", 3, Some(15)),
+                fairness_row("Lectores", "Luis", 0, None),
+                fairness_row("Monaguillos", "Luis", 2, Some(8)),
+            ],
+        );
+        let names: Vec<&str> = sheets.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Resumen", "Lectores", "Monaguillos"]);
+
+        assert_eq!(
+            sheets[0].rows[1],
+            vec![
+                "Ana".into(),
+                3i64.into(),
+                3i64.into(),
+                0i64.into(),
+                "15/03/2026".into()
+            ]
+        );
+        assert_eq!(
+            sheets[1].rows[2],
+            vec!["Luis".into(), 0i64.into(), "".into(), 2i64.into()]
+        );
+    }
+
+    #[test]
+    fn milestones_count_only_when_crossed_this_year() {
+        assert_eq!(milestones_crossed(20, 30), vec![25]);
+        assert_eq!(milestones_crossed(25, 49), Vec::<i64>::new());
+        assert_eq!(milestones_crossed(24, 120), vec![25, 50, 100]);
+        assert_eq!(next_milestone(25), Some(50));
+        assert_eq!(next_milestone(100), None);
+    }
+
+    #[test]
+    fn ties_share_a_rank() {
+        assert_eq!(competition_ranks(&[12, 9, 9, 4]), vec![1, 2, 2, 4]);
+        assert_eq!(competition_ranks(&[]), Vec::<i64>::new());
+    }
+}
@@ -0,0 +1,1620 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{Datelike, NaiveDate};
+use sqlx::{FromRow, PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::auth::{self, Claims};
+use crate::db::tx::Tx;
+use crate::models::{
+    Assignment, AssignmentWithDetails, BulkAssignmentOperation, BulkAssignmentsRequest, Schedule,
+    ScheduleWithDates, ServiceDate, ServiceDateWithAssignments, SwapSuggestion,
+    UpdateAssignmentNotesRequest, UpdateAssignmentRequest, PAIRING_SCOPE_SAME_DATE,
+};
+use crate::routes::settings;
+use crate::services::{assignment_events, edit_log, generator, rotation};
+
+use super::queries::max_jobs_conflicts;
+use super::{
+    archived_error, fetch_assignment_details, normalize_note, AssignmentRow,
+    ASSIGNMENT_DETAILS_SELECT,
+};
+
+// ============ Update Assignment ============
+
+#[utoipa::path(
+    put,
+    path = "/assignments/{id}",
+    tag = "schedules",
+    responses((status = 200, body = AssignmentWithDetails))
+)]
+pub async fn update_assignment(
+    State(pool): State<PgPool>,
+    mut tx: Tx,
+    claims: Claims,
+    Path(id): Path<String>,
+    Json(input): Json<UpdateAssignmentRequest>,
+) -> Result<Json<AssignmentWithDetails>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "edit assignments")?;
+
+    // The client must say who it saw in the slot so concurrent edits are not overwritten
+    let expected = input.expected_person_id.as_ref().ok_or((
+        StatusCode::PRECONDITION_REQUIRED,
+        "expected_person_id is required (null for an empty slot)".to_string(),
+    ))?;
+
+    let job_id: String = sqlx::query_scalar("SELECT job_id FROM assignments WHERE id = $1")
+        .bind(&id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Assignment not found".to_string()))?;
+    auth::require_job_scope(&claims, &job_id)?;
+    ensure_first_communion(&pool, &input.person_id, &job_id).await?;
+
+    // Get current assignment
+    let current = lock_assignments(&mut tx, &[&id])
+        .await?
+        .pop()
+        .ok_or((StatusCode::NOT_FOUND, "Assignment not found".to_string()))?;
+
+    assign_locked(
+        &mut tx,
+        &claims,
+        &current,
+        expected,
+        &input.person_id,
+        input.override_reason.as_deref(),
+    )
+    .await?;
+    edit_log::close(&mut tx, "update", &claims.username)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    // Return updated assignment with details
+    let assignment = fetch_assignment_details(&mut tx, &id, claims.is_admin()).await?;
+
+    Ok(Json(assignment))
+}
+
+/// Put `person_id` in an assignment already locked on `conn`, keeping rotation and history
+/// in step. `expected` is who the client saw in the slot; `override_reason` is required when
+/// the assignment goes against a warning.
+async fn assign_locked(
+    conn: &mut PgConnection,
+    claims: &Claims,
+    current: &Assignment,
+    expected: &Option<String>,
+    person_id: &String,
+    override_reason: Option<&str>,
+) -> Result<(), (StatusCode, String)> {
+    // Someone else changed the slot since the client loaded it
+    if &current.person_id != expected {
+        return Err((
+            StatusCode::CONFLICT,
+            "Assignment was changed by someone else, reload and try again".to_string(),
+        ));
+    }
+
+    // Get service date for history update
+    let sd = sqlx::query_as::<_, ServiceDate>("SELECT * FROM service_dates WHERE id = $1")
+        .bind(&current.service_date_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let warnings = assignment_warnings(conn, person_id, current, sd.service_date).await?;
+    let override_reason = require_override_reason(&warnings, override_reason)?;
+
+    // Update assignment
+    sqlx::query(
+        r#"
+        UPDATE assignments
+        SET person_id = $1, manual_override = true, suggested = false, override_reason = $2,
+            updated_by = $3
+        WHERE id = $4
+        "#,
+    )
+    .bind(person_id)
+    .bind(&override_reason)
+    .bind(&claims.username)
+    .bind(&current.id)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let action = if current.person_id.is_some() {
+        "substituted"
+    } else {
+        "assigned"
+    };
+    assignment_events::record_with_reason(
+        &mut *conn,
+        current,
+        action,
+        Some(person_id),
+        Some(&claims.username),
+        override_reason.as_deref(),
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    // Keep position rotation in step with the manual change
+    if let Some(pos) = current.position {
+        if let Some(old_person_id) = &current.person_id {
+            rotation::unmark_done(&mut *conn, old_person_id, &current.job_id, pos)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        }
+        rotation::mark_done(&mut *conn, person_id, &current.job_id, sd.service_date, pos)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+
+    // Update assignment history - remove old entry if there was a person
+    if let Some(old_person_id) = &current.person_id {
+        sqlx::query(
+            r#"
+            DELETE FROM assignment_history
+            WHERE person_id = $1 AND job_id = $2 AND service_date = $3
+            "#,
+        )
+        .bind(old_person_id)
+        .bind(&current.job_id)
+        .bind(sd.service_date)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+
+    // Add new history entry
+    let history_id = Uuid::new_v4().to_string();
+    let year = sd.service_date.year();
+    let week_number = sd.service_date.iso_week().week() as i32;
+
+    sqlx::query(
+        r#"
+        INSERT INTO assignment_history (id, person_id, job_id, service_date, year, week_number, position)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#
+    )
+    .bind(&history_id)
+    .bind(person_id)
+    .bind(&current.job_id)
+    .bind(sd.service_date)
+    .bind(year)
+    .bind(week_number)
+    .bind(current.position)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(())
+}
+
+// ============ Clear Assignment (remove person from slot) ============
+
+#[utoipa::path(
+    put,
+    path = "/assignments/{id}/clear",
+    tag = "schedules",
+    responses((status = 200, body = AssignmentWithDetails))
+)]
+pub async fn clear_assignment(
+    mut tx: Tx,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<AssignmentWithDetails>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "edit assignments")?;
+
+    // Get current assignment
+    let current = lock_assignments(&mut tx, &[&id])
+        .await?
+        .pop()
+        .ok_or((StatusCode::NOT_FOUND, "Assignment not found".to_string()))?;
+    auth::require_job_scope(&claims, &current.job_id)?;
+
+    clear_locked(&mut tx, &claims, &current).await?;
+    edit_log::close(&mut tx, "clear", &claims.username)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    // Return updated assignment with details
+    let assignment = fetch_assignment_details(&mut tx, &id, claims.is_admin()).await?;
+
+    Ok(Json(assignment))
+}
+
+/// Empty an assignment already locked on `conn`, giving back its rotation position and history
+async fn clear_locked(
+    conn: &mut PgConnection,
+    claims: &Claims,
+    current: &Assignment,
+) -> Result<(), (StatusCode, String)> {
+    // Get service date for history update
+    let sd = sqlx::query_as::<_, ServiceDate>("SELECT * FROM service_dates WHERE id = $1")
+        .bind(&current.service_date_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    // Clear the person_id (set to NULL)
+    sqlx::query("UPDATE assignments SET person_id = NULL, manual_override = true, suggested = false, override_reason = NULL, updated_by = $2 WHERE id = $1")
+        .bind(&current.id)
+        .bind(&claims.username)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    assignment_events::record(&mut *conn, current, "cleared", None, Some(&claims.username))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    if let (Some(old_person_id), Some(pos)) = (&current.person_id, current.position) {
+        rotation::unmark_done(&mut *conn, old_person_id, &current.job_id, pos)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+
+    // Remove from assignment history if there was a person
+    if let Some(old_person_id) = &current.person_id {
+        sqlx::query(
+            r#"
+            DELETE FROM assignment_history
+            WHERE person_id = $1 AND job_id = $2 AND service_date = $3
+            "#,
+        )
+        .bind(old_person_id)
+        .bind(&current.job_id)
+        .bind(sd.service_date)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+// ============ Assignment Notes ============
+
+/// Set or remove the note on a slot. The note belongs to the slot, so it stays when the
+/// person serving it changes.
+#[utoipa::path(
+    put,
+    path = "/assignments/{id}/notes",
+    tag = "schedules",
+    responses((status = 200, body = AssignmentWithDetails))
+)]
+pub async fn update_assignment_notes(
+    mut tx: Tx,
+    claims: Claims,
+    Path(id): Path<String>,
+    Json(input): Json<UpdateAssignmentNotesRequest>,
+) -> Result<Json<AssignmentWithDetails>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "edit assignments")?;
+
+    let notes = normalize_note(input.notes.as_deref())?;
+
+    let current = lock_assignments(&mut tx, &[&id])
+        .await?
+        .pop()
+        .ok_or((StatusCode::NOT_FOUND, "Assignment not found".to_string()))?;
+    auth::require_job_scope(&claims, &current.job_id)?;
+
+    sqlx::query("UPDATE assignments SET notes = $1, updated_by = $3 WHERE id = $2")
+        .bind(&notes)
+        .bind(&id)
+        .bind(&claims.username)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let assignment = fetch_assignment_details(&mut tx, &id, claims.is_admin()).await?;
+
+    Ok(Json(assignment))
+}
+
+// ============ Helper: Lock assignment rows ============
+
+/// Lock assignment rows for the rest of the transaction.
+/// Rows are locked in id order so two requests touching the same slots cannot deadlock, and
+/// NOWAIT makes whoever comes second get a 409 instead of silently overwriting the first.
+async fn lock_assignments(
+    conn: &mut PgConnection,
+    ids: &[&String],
+) -> Result<Vec<Assignment>, (StatusCode, String)> {
+    let mut ids: Vec<&String> = ids.to_vec();
+    ids.sort();
+    ids.dedup();
+
+    let locked = sqlx::query_as::<_, Assignment>(
+        "SELECT * FROM assignments WHERE id = ANY($1) ORDER BY id FOR UPDATE NOWAIT",
+    )
+    .bind(ids)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(lock_error)?;
+
+    let service_date_ids: Vec<&String> = locked.iter().map(|a| &a.service_date_id).collect();
+    ensure_not_archived(conn, &service_date_ids).await?;
+    Ok(locked)
+}
+
+/// Archived schedules are kept as they were: their assignments cannot be edited
+async fn ensure_not_archived(
+    conn: &mut PgConnection,
+    service_date_ids: &[&String],
+) -> Result<(), (StatusCode, String)> {
+    let archived: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM service_dates sd
+            JOIN schedules s ON s.id = sd.schedule_id
+            WHERE sd.id = ANY($1) AND s.status = 'ARCHIVED'
+        )
+        "#,
+    )
+    .bind(service_date_ids)
+    .fetch_one(conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if archived {
+        return Err(archived_error());
+    }
+    Ok(())
+}
+
+/// A NOWAIT lock held by another request means someone else is editing the slot
+fn lock_error(e: sqlx::Error) -> (StatusCode, String) {
+    match &e {
+        sqlx::Error::Database(db) if db.code().as_deref() == Some("55P03") => (
+            StatusCode::CONFLICT,
+            "Assignment is being changed by someone else, reload and try again".to_string(),
+        ),
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+// ============ Helper: Override warnings ============
+
+/// What a coordinator should know before putting `person_id` in the slot: unavailable that
+/// day, a sibling to be kept apart already serving (same job, or same date for SAME_DATE
+/// groups), or already booked that day in another schedule. Soft rules the generator avoids
+/// but a person can knowingly break.
+async fn assignment_warnings(
+    conn: &mut PgConnection,
+    person_id: &str,
+    slot: &Assignment,
+    service_date: NaiveDate,
+) -> Result<Vec<String>, (StatusCode, String)> {
+    let mut warnings = Vec::new();
+
+    let unavailable: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM unavailability u
+            WHERE u.person_id = $1 AND unavailability_matches(u, $2)
+        )
+        "#,
+    )
+    .bind(person_id)
+    .bind(service_date)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if unavailable {
+        warnings.push("the person is unavailable on this date".to_string());
+    }
+
+    let siblings: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT DISTINCT p.first_name || ' ' || p.last_name
+        FROM sibling_group_members me
+        JOIN sibling_groups g ON g.id = me.sibling_group_id AND g.pairing_rule = 'SEPARATE'
+        JOIN sibling_group_members sib
+          ON sib.sibling_group_id = g.id AND sib.person_id <> me.person_id
+        JOIN assignments a
+          ON a.person_id = sib.person_id AND a.service_date_id = $2 AND a.id <> $3
+         AND (g.pairing_scope = $5 OR a.job_id = $4)
+        JOIN people p ON p.id = sib.person_id
+        WHERE me.person_id = $1
+        "#,
+    )
+    .bind(person_id)
+    .bind(&slot.service_date_id)
+    .bind(&slot.id)
+    .bind(&slot.job_id)
+    .bind(PAIRING_SCOPE_SAME_DATE)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    for sibling in siblings {
+        warnings.push(format!(
+            "{} should be kept apart and is already serving",
+            sibling
+        ));
+    }
+
+    // Another service date on the same day, e.g. a special date both months' schedules cover
+    let same_day: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT DISTINCT s.name || ' (' || j.name || ')'
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN schedules s ON sd.schedule_id = s.id
+        JOIN jobs j ON a.job_id = j.id
+        WHERE a.person_id = $1 AND sd.service_date = $2 AND sd.id <> $3
+        "#,
+    )
+    .bind(person_id)
+    .bind(service_date)
+    .bind(&slot.service_date_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    for booking in same_day {
+        warnings.push(format!("the person already serves that day in {}", booking));
+    }
+
+    Ok(warnings)
+}
+
+/// Assignments that go against a warning need a reason, kept for later coordinators;
+/// without warnings there is nothing to explain and any reason given is dropped
+fn require_override_reason(
+    warnings: &[String],
+    reason: Option<&str>,
+) -> Result<Option<String>, (StatusCode, String)> {
+    if warnings.is_empty() {
+        return Ok(None);
+    }
+    match reason.map(str::trim).filter(|r| !r.is_empty()) {
+        Some(reason) => Ok(Some(reason.to_string())),
+        None => Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("override_reason is required: {}", warnings.join("; ")),
+        )),
+    }
+}
+
+// ============ Helper: First communion requirement ============
+
+/// Manual changes follow the generator: jobs that require first communion
+/// only take people who have made it
+async fn ensure_first_communion(
+    pool: &PgPool,
+    person_id: &str,
+    job_id: &str,
+) -> Result<(), (StatusCode, String)> {
+    let missing: Option<(String, String)> = sqlx::query_as(
+        r#"
+        SELECT p.first_name || ' ' || p.last_name, j.name
+        FROM people p, jobs j
+        WHERE p.id = $1 AND j.id = $2
+          AND j.requires_first_communion = true
+          AND COALESCE(p.first_communion, false) = false
+        "#,
+    )
+    .bind(person_id)
+    .bind(job_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    match missing {
+        Some((person_name, job_name)) => Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "{} no ha hecho la primera comunión, requerida para {}",
+                person_name, job_name
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+// ============ Helper: Check if person is qualified for job ============
+
+async fn is_person_qualified_for_job(
+    pool: &PgPool,
+    person_id: &str,
+    job_id: &str,
+) -> Result<bool, String> {
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM person_jobs WHERE person_id = $1 AND job_id = $2)",
+    )
+    .bind(person_id)
+    .bind(job_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(exists)
+}
+
+async fn get_person_name(pool: &PgPool, person_id: &str) -> Result<String, String> {
+    let name: String =
+        sqlx::query_scalar("SELECT first_name || ' ' || last_name FROM people WHERE id = $1")
+            .bind(person_id)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    Ok(name)
+}
+
+async fn get_job_name(pool: &PgPool, job_id: &str) -> Result<String, String> {
+    let name: String = sqlx::query_scalar("SELECT name FROM jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(name)
+}
+
+// ============ Swap Assignments ============
+
+#[derive(Debug, serde::Deserialize, utoipa::ToSchema)]
+pub struct SwapAssignmentsRequest {
+    pub assignment_id_1: String,
+    pub assignment_id_2: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/assignments/swap",
+    tag = "schedules",
+    responses((status = 200, body = Vec<AssignmentWithDetails>))
+)]
+pub async fn swap_assignments(
+    State(pool): State<PgPool>,
+    mut tx: Tx,
+    claims: Claims,
+    Json(input): Json<SwapAssignmentsRequest>,
+) -> Result<Json<Vec<AssignmentWithDetails>>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "edit assignments")?;
+
+    // Get both assignments
+    let locked =
+        lock_assignments(&mut tx, &[&input.assignment_id_1, &input.assignment_id_2]).await?;
+    let assignment1 = locked
+        .iter()
+        .find(|a| a.id == input.assignment_id_1)
+        .cloned()
+        .ok_or((StatusCode::NOT_FOUND, "Assignment 1 not found".to_string()))?;
+    let assignment2 = locked
+        .iter()
+        .find(|a| a.id == input.assignment_id_2)
+        .cloned()
+        .ok_or((StatusCode::NOT_FOUND, "Assignment 2 not found".to_string()))?;
+    auth::require_job_scope(&claims, &assignment1.job_id)?;
+    auth::require_job_scope(&claims, &assignment2.job_id)?;
+
+    let results = swap_locked(&pool, &mut tx, &claims, &assignment1, &assignment2).await?;
+    edit_log::close(&mut tx, "swap", &claims.username)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(results))
+}
+
+/// Swap the people in two assignments already locked on `conn`
+async fn swap_locked(
+    pool: &PgPool,
+    conn: &mut PgConnection,
+    claims: &Claims,
+    assignment1: &Assignment,
+    assignment2: &Assignment,
+) -> Result<Vec<AssignmentWithDetails>, (StatusCode, String)> {
+    // Validate job qualifications before swapping
+    // Check if person1 is qualified for assignment2's job
+    if let Some(p1) = &assignment1.person_id {
+        if assignment1.job_id != assignment2.job_id {
+            let is_qualified = is_person_qualified_for_job(pool, p1, &assignment2.job_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            if !is_qualified {
+                let person_name = get_person_name(pool, p1)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+                let job_name = get_job_name(pool, &assignment2.job_id)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("{} no está configurado como {}", person_name, job_name),
+                ));
+            }
+            ensure_first_communion(pool, p1, &assignment2.job_id).await?;
+        }
+    }
+
+    // Check if person2 is qualified for assignment1's job
+    if let Some(p2) = &assignment2.person_id {
+        if assignment1.job_id != assignment2.job_id {
+            let is_qualified = is_person_qualified_for_job(pool, p2, &assignment1.job_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            if !is_qualified {
+                let person_name = get_person_name(pool, p2)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+                let job_name = get_job_name(pool, &assignment1.job_id)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("{} no está configurado como {}", person_name, job_name),
+                ));
+            }
+            ensure_first_communion(pool, p2, &assignment1.job_id).await?;
+        }
+    }
+
+    // Get service dates for history updates
+    let sd1 = sqlx::query_as::<_, ServiceDate>("SELECT * FROM service_dates WHERE id = $1")
+        .bind(&assignment1.service_date_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let sd2 = sqlx::query_as::<_, ServiceDate>("SELECT * FROM service_dates WHERE id = $1")
+        .bind(&assignment2.service_date_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    // Swap person_ids
+    let person1 = assignment1.person_id.clone();
+    let person2 = assignment2.person_id.clone();
+
+    // To avoid unique constraint violation, we need to use NULL as intermediate step:
+    // 1. Set assignment 1 to NULL
+    // 2. Set assignment 2 to person1
+    // 3. Set assignment 1 to person2
+
+    // Step 1: Clear assignment 1
+    sqlx::query("UPDATE assignments SET person_id = NULL, manual_override = true, suggested = false, override_reason = NULL, updated_by = $2 WHERE id = $1")
+        .bind(&assignment1.id)
+        .bind(&claims.username)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    // Step 2: Update assignment 2 with person 1
+    sqlx::query("UPDATE assignments SET person_id = $1, manual_override = true, suggested = false, override_reason = NULL, updated_by = $3 WHERE id = $2")
+        .bind(&person1)
+        .bind(&assignment2.id)
+        .bind(&claims.username)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    // Step 3: Update assignment 1 with person 2
+    sqlx::query("UPDATE assignments SET person_id = $1, manual_override = true, suggested = false, updated_by = $3 WHERE id = $2")
+        .bind(&person2)
+        .bind(&assignment1.id)
+        .bind(&claims.username)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    for (before, now) in [(assignment1, &person2), (assignment2, &person1)] {
+        assignment_events::record(
+            &mut *conn,
+            before,
+            "swapped",
+            now.as_ref(),
+            Some(&claims.username),
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+
+    // Each person gives back the position they left and takes the one they moved to
+    for from in [assignment1, assignment2] {
+        if let (Some(person_id), Some(pos)) = (&from.person_id, from.position) {
+            rotation::unmark_done(&mut *conn, person_id, &from.job_id, pos)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        }
+    }
+    for (from, to, to_date) in [
+        (assignment1, assignment2, sd2.service_date),
+        (assignment2, assignment1, sd1.service_date),
+    ] {
+        if let (Some(person_id), Some(pos)) = (&from.person_id, to.position) {
+            rotation::mark_done(&mut *conn, person_id, &to.job_id, to_date, pos)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        }
+    }
+
+    // Update assignment history for person 1
+    if let Some(p1) = &person1 {
+        // Remove old history entry for person 1 at slot 1
+        sqlx::query(
+            "DELETE FROM assignment_history WHERE person_id = $1 AND job_id = $2 AND service_date = $3"
+        )
+        .bind(p1)
+        .bind(&assignment1.job_id)
+        .bind(sd1.service_date)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        // Add new history entry for person 1 at slot 2
+        let history_id = Uuid::new_v4().to_string();
+        let year = sd2.service_date.year();
+        let week_number = sd2.service_date.iso_week().week() as i32;
+        sqlx::query(
+            r#"
+            INSERT INTO assignment_history (id, person_id, job_id, service_date, year, week_number, position)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#
+        )
+        .bind(&history_id)
+        .bind(p1)
+        .bind(&assignment2.job_id)
+        .bind(sd2.service_date)
+        .bind(year)
+        .bind(week_number)
+        .bind(assignment2.position)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+
+    // Update assignment history for person 2
+    if let Some(p2) = &person2 {
+        // Remove old history entry for person 2 at slot 2
+        sqlx::query(
+            "DELETE FROM assignment_history WHERE person_id = $1 AND job_id = $2 AND service_date = $3"
+        )
+        .bind(p2)
+        .bind(&assignment2.job_id)
+        .bind(sd2.service_date)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        // Add new history entry for person 2 at slot 1
+        let history_id = Uuid::new_v4().to_string();
+        let year = sd1.service_date.year();
+        let week_number = sd1.service_date.iso_week().week() as i32;
+        sqlx::query(
+            r#"
+            INSERT INTO assignment_history (id, person_id, job_id, service_date, year, week_number, position)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#
+        )
+        .bind(&history_id)
+        .bind(p2)
+        .bind(&assignment1.job_id)
+        .bind(sd1.service_date)
+        .bind(year)
+        .bind(week_number)
+        .bind(assignment1.position)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+
+    // Return both updated assignments
+    let mut results = Vec::new();
+
+    for id in [&assignment1.id, &assignment2.id] {
+        results.push(fetch_assignment_details(&mut *conn, id, claims.is_admin()).await?);
+    }
+
+    Ok(results)
+}
+
+// ============ Bulk Edit ============
+
+/// Apply a list of set/clear/swap edits to one schedule in a single transaction, in order.
+/// Drag-and-drop sessions land whole or not at all; the answer is the updated schedule.
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/assignments/bulk",
+    tag = "schedules",
+    responses((status = 200, body = ScheduleWithDates))
+)]
+pub async fn bulk_update_assignments(
+    State(pool): State<PgPool>,
+    mut tx: Tx,
+    claims: Claims,
+    Path(schedule_id): Path<String>,
+    Json(input): Json<BulkAssignmentsRequest>,
+) -> Result<Json<ScheduleWithDates>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "edit assignments")?;
+
+    if input.operations.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "No operations given".to_string()));
+    }
+
+    let schedule = sqlx::query_as::<_, Schedule>("SELECT * FROM schedules WHERE id = $1")
+        .bind(&schedule_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))?;
+
+    // Lock every slot up front, in id order, so the edits cannot deadlock with another request
+    let ids: Vec<&String> = input
+        .operations
+        .iter()
+        .flat_map(|op| match op {
+            BulkAssignmentOperation::Set { assignment_id, .. }
+            | BulkAssignmentOperation::Clear { assignment_id } => vec![assignment_id],
+            BulkAssignmentOperation::Swap {
+                assignment_id_1,
+                assignment_id_2,
+            } => vec![assignment_id_1, assignment_id_2],
+        })
+        .collect();
+    let locked = lock_assignments(&mut tx, &ids).await?;
+
+    let service_date_ids: Vec<String> =
+        sqlx::query_scalar("SELECT id FROM service_dates WHERE schedule_id = $1")
+            .bind(&schedule_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    for id in &ids {
+        let assignment = locked.iter().find(|a| &a.id == *id).ok_or((
+            StatusCode::NOT_FOUND,
+            format!("Assignment {} not found", id),
+        ))?;
+        if !service_date_ids.contains(&assignment.service_date_id) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Assignment {} is not in this schedule", id),
+            ));
+        }
+        auth::require_job_scope(&claims, &assignment.job_id)?;
+    }
+
+    // Each edit sees the slots as the previous ones left them
+    for op in &input.operations {
+        match op {
+            BulkAssignmentOperation::Set {
+                assignment_id,
+                person_id,
+                expected_person_id,
+                override_reason,
+            } => {
+                let expected = expected_person_id.as_ref().ok_or((
+                    StatusCode::PRECONDITION_REQUIRED,
+                    "expected_person_id is required (null for an empty slot)".to_string(),
+                ))?;
+                let current = reload_locked(&mut tx, assignment_id).await?;
+                ensure_first_communion(&pool, person_id, &current.job_id).await?;
+                assign_locked(
+                    &mut tx,
+                    &claims,
+                    &current,
+                    expected,
+                    person_id,
+                    override_reason.as_deref(),
+                )
+                .await?;
+            }
+            BulkAssignmentOperation::Clear { assignment_id } => {
+                let current = reload_locked(&mut tx, assignment_id).await?;
+                clear_locked(&mut tx, &claims, &current).await?;
+            }
+            BulkAssignmentOperation::Swap {
+                assignment_id_1,
+                assignment_id_2,
+            } => {
+                let assignment1 = reload_locked(&mut tx, assignment_id_1).await?;
+                let assignment2 = reload_locked(&mut tx, assignment_id_2).await?;
+                swap_locked(&pool, &mut tx, &claims, &assignment1, &assignment2).await?;
+            }
+        }
+    }
+    edit_log::close(&mut tx, "bulk", &claims.username)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let service_dates = sqlx::query_as::<_, ServiceDate>(
+        "SELECT * FROM service_dates WHERE schedule_id = $1 ORDER BY service_date",
+    )
+    .bind(&schedule_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut dates_with_assignments = Vec::new();
+    for sd in service_dates {
+        let assignments = sqlx::query_as::<_, AssignmentRow>(&format!(
+            "{} WHERE a.service_date_id = $1 ORDER BY j.name, a.position",
+            ASSIGNMENT_DETAILS_SELECT
+        ))
+        .bind(&sd.id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        dates_with_assignments.push(ServiceDateWithAssignments {
+            service_date: sd,
+            assignments: assignments
+                .into_iter()
+                .map(|row| row.into_details(claims.is_admin()))
+                .collect(),
+        });
+    }
+
+    let max_jobs = settings::get_i64(&pool, "max_jobs_per_person_per_month", 0)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let conflicts = max_jobs_conflicts(max_jobs, &dates_with_assignments);
+
+    Ok(Json(ScheduleWithDates {
+        schedule,
+        service_dates: dates_with_assignments,
+        conflicts,
+    }))
+}
+
+/// Current state of a slot this transaction already holds the lock on
+async fn reload_locked(
+    conn: &mut PgConnection,
+    id: &String,
+) -> Result<Assignment, (StatusCode, String)> {
+    sqlx::query_as::<_, Assignment>("SELECT * FROM assignments WHERE id = $1")
+        .bind(id)
+        .fetch_optional(conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Assignment not found".to_string()))
+}
+
+// ============ Undo / Redo ============
+
+/// Revert the latest edit of the schedule (update, clear, swap, move or bulk), with its
+/// rotation and assignment_history changes
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/undo",
+    tag = "schedules",
+    responses((status = 200, body = Vec<AssignmentWithDetails>))
+)]
+pub async fn undo(
+    mut tx: Tx,
+    claims: Claims,
+    Path(schedule_id): Path<String>,
+) -> Result<Json<Vec<AssignmentWithDetails>>, (StatusCode, String)> {
+    replay_edit(&mut tx, &claims, &schedule_id, true)
+        .await
+        .map(Json)
+}
+
+/// Apply again the edit undone last; a new edit in between discards it
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/redo",
+    tag = "schedules",
+    responses((status = 200, body = Vec<AssignmentWithDetails>))
+)]
+pub async fn redo(
+    mut tx: Tx,
+    claims: Claims,
+    Path(schedule_id): Path<String>,
+) -> Result<Json<Vec<AssignmentWithDetails>>, (StatusCode, String)> {
+    replay_edit(&mut tx, &claims, &schedule_id, false)
+        .await
+        .map(Json)
+}
+
+/// Take an edit's slots back to who they held before it (`undo`) or after it
+async fn replay_edit(
+    conn: &mut PgConnection,
+    claims: &Claims,
+    schedule_id: &str,
+    undo: bool,
+) -> Result<Vec<AssignmentWithDetails>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(claims, "edit assignments")?;
+
+    // Lock the schedule so two undos cannot pick the same edit
+    sqlx::query_scalar::<_, String>("SELECT id FROM schedules WHERE id = $1 FOR UPDATE")
+        .bind(schedule_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))?;
+
+    let edit = if undo {
+        edit_log::last_done(conn, schedule_id).await
+    } else {
+        edit_log::next_undone(conn, schedule_id).await
+    }
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+    .ok_or_else(|| {
+        let message = if undo {
+            "Nothing to undo"
+        } else {
+            "Nothing to redo"
+        };
+        (StatusCode::CONFLICT, message.to_string())
+    })?;
+
+    let changes = edit_log::changes(conn, &edit.id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let ids: Vec<&String> = changes.iter().map(|c| &c.assignment_id).collect();
+    let locked = lock_assignments(conn, &ids).await?;
+
+    // Every slot must still hold who the edit (or its undo) left there
+    let mut slots: Vec<(&Assignment, &Option<String>)> = Vec::new();
+    for change in &changes {
+        let (from, to) = if undo {
+            (&change.after, &change.before)
+        } else {
+            (&change.before, &change.after)
+        };
+        let slot = locked
+            .iter()
+            .find(|a| a.id == change.assignment_id)
+            .filter(|a| &a.person_id == from)
+            .ok_or((
+                StatusCode::CONFLICT,
+                format!(
+                    "The slots of the last {} were changed since, it cannot be {}",
+                    edit.kind,
+                    if undo { "undone" } else { "redone" }
+                ),
+            ))?;
+        auth::require_job_scope(claims, &slot.job_id)?;
+        if from != to {
+            slots.push((slot, to));
+        }
+    }
+
+    // Empty the slots first so nobody is in two of them at once, as in a swap
+    for (slot, _) in &slots {
+        vacate_slot(conn, slot, &claims.username).await?;
+    }
+    let action = if undo { "undone" } else { "redone" };
+    for (slot, to) in &slots {
+        if let Some(person_id) = to {
+            fill_slot(conn, slot, person_id, &claims.username).await?;
+        }
+        assignment_events::record(
+            &mut *conn,
+            slot,
+            action,
+            to.as_ref(),
+            Some(&claims.username),
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+
+    edit_log::set_undone(conn, &edit.id, undo)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let mut results = Vec::new();
+    for change in &changes {
+        let details = fetch_assignment_details(conn, &change.assignment_id, claims.is_admin());
+        results.push(details.await?);
+    }
+    Ok(results)
+}
+
+/// Take the person out of a locked slot, giving back their rotation position and history
+async fn vacate_slot(
+    conn: &mut PgConnection,
+    slot: &Assignment,
+    changed_by: &str,
+) -> Result<(), (StatusCode, String)> {
+    let Some(person_id) = &slot.person_id else {
+        return Ok(());
+    };
+    let sd = sqlx::query_as::<_, ServiceDate>("SELECT * FROM service_dates WHERE id = $1")
+        .bind(&slot.service_date_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    sqlx::query("UPDATE assignments SET person_id = NULL, manual_override = true, suggested = false, override_reason = NULL, updated_by = $2 WHERE id = $1")
+        .bind(&slot.id)
+        .bind(changed_by)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if let Some(pos) = slot.position {
+        rotation::unmark_done(&mut *conn, person_id, &slot.job_id, pos)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+
+    sqlx::query(
+        "DELETE FROM assignment_history WHERE person_id = $1 AND job_id = $2 AND service_date = $3",
+    )
+    .bind(person_id)
+    .bind(&slot.job_id)
+    .bind(sd.service_date)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(())
+}
+
+/// Put a person in a slot emptied by `vacate_slot`, recording rotation and history
+async fn fill_slot(
+    conn: &mut PgConnection,
+    slot: &Assignment,
+    person_id: &String,
+    changed_by: &str,
+) -> Result<(), (StatusCode, String)> {
+    let sd = sqlx::query_as::<_, ServiceDate>("SELECT * FROM service_dates WHERE id = $1")
+        .bind(&slot.service_date_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    sqlx::query("UPDATE assignments SET person_id = $1, manual_override = true, suggested = false, updated_by = $3 WHERE id = $2")
+        .bind(person_id)
+        .bind(&slot.id)
+        .bind(changed_by)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if let Some(pos) = slot.position {
+        rotation::mark_done(&mut *conn, person_id, &slot.job_id, sd.service_date, pos)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO assignment_history (id, person_id, job_id, service_date, year, week_number, position)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(person_id)
+    .bind(&slot.job_id)
+    .bind(sd.service_date)
+    .bind(sd.service_date.year())
+    .bind(sd.service_date.iso_week().week() as i32)
+    .bind(slot.position)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(())
+}
+
+// ============ Move Assignment ============
+
+#[derive(Debug, serde::Deserialize, utoipa::ToSchema)]
+pub struct MoveAssignmentRequest {
+    pub target_service_date_id: String,
+    pub target_job_id: String,
+    pub target_position: i32,
+}
+
+#[utoipa::path(
+    put,
+    path = "/assignments/{id}/move",
+    tag = "schedules",
+    responses((status = 200, body = Vec<AssignmentWithDetails>))
+)]
+pub async fn move_assignment(
+    State(pool): State<PgPool>,
+    mut tx: Tx,
+    claims: Claims,
+    Path(id): Path<String>,
+    Json(input): Json<MoveAssignmentRequest>,
+) -> Result<Json<Vec<AssignmentWithDetails>>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "edit assignments")?;
+
+    // Get source assignment, locked until the move commits
+    let source = lock_assignments(&mut tx, &[&id])
+        .await?
+        .pop()
+        .ok_or((StatusCode::NOT_FOUND, "Assignment not found".to_string()))?;
+    auth::require_job_scope(&claims, &source.job_id)?;
+    auth::require_job_scope(&claims, &input.target_job_id)?;
+
+    // Validate job qualification if moving to a different job
+    if let Some(person_id) = &source.person_id {
+        if source.job_id != input.target_job_id {
+            let is_qualified = is_person_qualified_for_job(&pool, person_id, &input.target_job_id)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            if !is_qualified {
+                let person_name = get_person_name(&pool, person_id)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+                let job_name = get_job_name(&pool, &input.target_job_id)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("{} no está configurado como {}", person_name, job_name),
+                ));
+            }
+            ensure_first_communion(&pool, person_id, &input.target_job_id).await?;
+        }
+    }
+
+    ensure_not_archived(&mut tx, &[&input.target_service_date_id]).await?;
+    let cancelled: bool =
+        sqlx::query_scalar("SELECT cancelled_at IS NOT NULL FROM service_dates WHERE id = $1")
+            .bind(&input.target_service_date_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((StatusCode::NOT_FOUND, "Service date not found".to_string()))?;
+    if cancelled {
+        return Err((
+            StatusCode::CONFLICT,
+            "The target service date is cancelled".to_string(),
+        ));
+    }
+
+    // Check if target slot exists, locking it in the same transaction
+    let target = sqlx::query_as::<_, Assignment>(
+        r#"
+        SELECT * FROM assignments
+        WHERE service_date_id = $1 AND job_id = $2 AND position = $3
+        FOR UPDATE NOWAIT
+        "#,
+    )
+    .bind(&input.target_service_date_id)
+    .bind(&input.target_job_id)
+    .bind(input.target_position)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(lock_error)?;
+
+    if let Some(target_assignment) = target {
+        // Target slot exists - if it has a person, swap; if empty, move
+        if target_assignment.person_id.is_some() {
+            // Swap
+            let results = swap_locked(&pool, &mut tx, &claims, &source, &target_assignment).await?;
+            edit_log::close(&mut tx, "move", &claims.username)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            return Ok(Json(results));
+        } else {
+            // Target is empty - move source person to target, clear source
+            let source_sd =
+                sqlx::query_as::<_, ServiceDate>("SELECT * FROM service_dates WHERE id = $1")
+                    .bind(&source.service_date_id)
+                    .fetch_one(&mut *tx)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+            let target_sd =
+                sqlx::query_as::<_, ServiceDate>("SELECT * FROM service_dates WHERE id = $1")
+                    .bind(&input.target_service_date_id)
+                    .fetch_one(&mut *tx)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+            // Move person to target
+            sqlx::query(
+                "UPDATE assignments SET person_id = $1, manual_override = true, suggested = false, override_reason = NULL, updated_by = $3 WHERE id = $2",
+            )
+            .bind(&source.person_id)
+            .bind(&target_assignment.id)
+            .bind(&claims.username)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+            // Clear source
+            sqlx::query(
+                "UPDATE assignments SET person_id = NULL, manual_override = true, suggested = false, override_reason = NULL, updated_by = $2 WHERE id = $1",
+            )
+            .bind(&id)
+            .bind(&claims.username)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+            for (before, now) in [
+                (&target_assignment, source.person_id.as_ref()),
+                (&source, None),
+            ] {
+                assignment_events::record(&mut tx, before, "moved", now, Some(&claims.username))
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            }
+
+            if let Some(person_id) = &source.person_id {
+                if let Some(pos) = source.position {
+                    rotation::unmark_done(&mut tx, person_id, &source.job_id, pos)
+                        .await
+                        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+                }
+                rotation::mark_done(
+                    &mut tx,
+                    person_id,
+                    &input.target_job_id,
+                    target_sd.service_date,
+                    input.target_position,
+                )
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            }
+
+            // Update history
+            if let Some(person_id) = &source.person_id {
+                // Remove old history
+                sqlx::query(
+                    "DELETE FROM assignment_history WHERE person_id = $1 AND job_id = $2 AND service_date = $3"
+                )
+                .bind(person_id)
+                .bind(&source.job_id)
+                .bind(source_sd.service_date)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+                // Add new history at target
+                let history_id = Uuid::new_v4().to_string();
+                let year = target_sd.service_date.year();
+                let week_number = target_sd.service_date.iso_week().week() as i32;
+                sqlx::query(
+                    r#"
+                    INSERT INTO assignment_history (id, person_id, job_id, service_date, year, week_number, position)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7)
+                    "#
+                )
+                .bind(&history_id)
+                .bind(person_id)
+                .bind(&input.target_job_id)
+                .bind(target_sd.service_date)
+                .bind(year)
+                .bind(week_number)
+                .bind(input.target_position)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            }
+
+            edit_log::close(&mut tx, "move", &claims.username)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+            // Return both updated assignments
+            let mut results = Vec::new();
+            for aid in [&id, &target_assignment.id] {
+                results.push(fetch_assignment_details(&mut tx, aid, claims.is_admin()).await?);
+            }
+
+            return Ok(Json(results));
+        }
+    }
+
+    // Target slot doesn't exist - this shouldn't happen in normal flow
+    Err((StatusCode::NOT_FOUND, "Target slot not found".to_string()))
+}
+
+// ============ Swap suggestions ============
+
+/// How many swaps to suggest for one assignment
+const MAX_SWAP_SUGGESTIONS: usize = 10;
+
+/// SQL condition: `person` may serve `job` on `date` under the generator's hard rules
+/// (active, qualified, not excluded or blacked out, available, first communion)
+fn eligibility_condition(person: &str, job: &str, date: &str) -> String {
+    format!(
+        r#"EXISTS (
+            SELECT 1
+            FROM people ep
+            JOIN person_jobs epj ON epj.person_id = ep.id AND epj.job_id = {job}
+            JOIN jobs ej ON ej.id = {job}
+            WHERE ep.id = {person}
+              AND ep.active = true
+              AND (NOT ej.requires_first_communion OR ep.first_communion = true)
+              AND NOT EXISTS (
+                  SELECT 1 FROM unavailability u
+                  WHERE u.person_id = ep.id AND unavailability_matches(u, {date})
+              )
+              AND NOT EXISTS (
+                  SELECT 1 FROM person_job_exclusions x
+                  WHERE x.person_id = ep.id AND x.job_id = {job}
+              )
+              AND NOT EXISTS (
+                  SELECT 1 FROM person_job_blackouts b
+                  WHERE b.person_id = ep.id AND b.job_id = {job}
+                    AND EXTRACT(MONTH FROM {date}::date)::int = ANY(b.months)
+              )
+        )"#
+    )
+}
+
+#[derive(FromRow)]
+struct SwapSourceRow {
+    schedule_id: String,
+    person_id: Option<String>,
+    job_id: String,
+    service_date: NaiveDate,
+}
+
+#[derive(FromRow)]
+struct SwapCandidateRow {
+    id: String,
+    person_id: String,
+    person_name: String,
+    service_date: NaiveDate,
+    job_id: String,
+    job_name: String,
+    position_name: Option<String>,
+}
+
+/// Swaps with other filled slots of the same schedule that keep both people eligible and
+/// do not double-book anyone, best fairness gain first. Swaps that would make the
+/// combined fairness worse are left out.
+#[utoipa::path(
+    get,
+    path = "/assignments/{id}/swap-suggestions",
+    tag = "schedules",
+    responses((status = 200, body = Vec<SwapSuggestion>))
+)]
+pub async fn get_swap_suggestions(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<SwapSuggestion>>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can view swap suggestions".to_string(),
+        ));
+    }
+
+    let source = sqlx::query_as::<_, SwapSourceRow>(
+        r#"
+        SELECT sd.schedule_id, a.person_id, a.job_id, sd.service_date
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        WHERE a.id = $1
+        "#,
+    )
+    .bind(&id)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or((StatusCode::NOT_FOUND, "Assignment not found".to_string()))?;
+
+    let person_id = source.person_id.ok_or((
+        StatusCode::BAD_REQUEST,
+        "Assignment is empty, there is no one to swap".to_string(),
+    ))?;
+
+    // $1 = source assignment, $2 = its person, $3 = its job, $4 = its date, $5 = schedule
+    let query = format!(
+        r#"
+        SELECT b.id, b.person_id, p.first_name || ' ' || p.last_name as person_name,
+               sd.service_date, b.job_id, j.name as job_name, b.position_name
+        FROM assignments b
+        JOIN service_dates sd ON b.service_date_id = sd.id
+        JOIN people p ON b.person_id = p.id
+        JOIN jobs j ON b.job_id = j.id
+        WHERE sd.schedule_id = $5
+          AND b.id <> $1
+          AND b.person_id <> $2
+          AND {source_fits_target}
+          AND {target_fits_source}
+          -- Neither person may already serve another slot on the date they move to
+          AND NOT EXISTS (
+              SELECT 1 FROM assignments x
+              JOIN service_dates xsd ON x.service_date_id = xsd.id
+              WHERE x.person_id = $2 AND xsd.service_date = sd.service_date
+                AND x.id <> $1 AND x.id <> b.id
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM assignments x
+              JOIN service_dates xsd ON x.service_date_id = xsd.id
+              WHERE x.person_id = b.person_id AND xsd.service_date = $4
+                AND x.id <> $1 AND x.id <> b.id
+          )
+        ORDER BY sd.service_date, j.name, b.position
+        "#,
+        source_fits_target = eligibility_condition("$2", "b.job_id", "sd.service_date"),
+        target_fits_source = eligibility_condition("b.person_id", "$3", "$4"),
+    );
+
+    let candidates = sqlx::query_as::<_, SwapCandidateRow>(&query)
+        .bind(&id)
+        .bind(&person_id)
+        .bind(&source.job_id)
+        .bind(source.service_date)
+        .bind(&source.schedule_id)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let fairness = generator::FairnessSettings::load(&mut conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let mut suggestions = Vec::new();
+    for candidate in candidates {
+        // Lower scores serve first, so a swap helps when each person lands where they score lower.
+        // Scores: [source now, candidate now, source after, candidate after]
+        let mut scores = Vec::with_capacity(4);
+        for (person, job, date) in [
+            (&person_id, &source.job_id, source.service_date),
+            (
+                &candidate.person_id,
+                &candidate.job_id,
+                candidate.service_date,
+            ),
+            (&person_id, &candidate.job_id, candidate.service_date),
+            (&candidate.person_id, &source.job_id, source.service_date),
+        ] {
+            scores.push(
+                generator::fairness_score(&mut conn, &fairness, person, job, date)
+                    .await
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?,
+            );
+        }
+        let before = scores[0] + scores[1];
+        let after = scores[2] + scores[3];
+
+        let fairness_gain = before - after;
+        if fairness_gain < 0 {
+            continue;
+        }
+        suggestions.push(SwapSuggestion {
+            assignment_id: candidate.id,
+            person_id: candidate.person_id,
+            person_name: candidate.person_name,
+            service_date: candidate.service_date,
+            job_id: candidate.job_id,
+            job_name: candidate.job_name,
+            position_name: candidate.position_name,
+            fairness_gain,
+        });
+    }
+
+    suggestions.sort_by(|a, b| {
+        b.fairness_gain
+            .cmp(&a.fairness_gain)
+            .then(a.service_date.cmp(&b.service_date))
+    });
+    suggestions.truncate(MAX_SWAP_SUGGESTIONS);
+
+    Ok(Json(suggestions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn override_reason_only_needed_with_warnings() {
+        assert_eq!(require_override_reason(&[], Some("porque sí")), Ok(None));
+
+        let warnings = vec!["the person is unavailable on this date".to_string()];
+        let (status, message) = require_override_reason(&warnings, Some("   ")).unwrap_err();
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(message.contains("unavailable"));
+        assert_eq!(
+            require_override_reason(&warnings, Some(" Cubre a su hermano ")),
+            Ok(Some("Cubre a su hermano".to_string()))
+        );
+    }
+}
@@ -0,0 +1,813 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Datelike, NaiveDate, NaiveTime, Utc};
+use image::Rgb;
+use serde::Deserialize;
+use sqlx::{FromRow, PgPool};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use utoipa::IntoParams;
+
+use crate::auth::{self, Claims};
+use crate::models::Schedule;
+use crate::routes::settings;
+use crate::services::signage::{self, SignageSchedule};
+use crate::services::{csv, ical, png};
+use crate::services::{naming, visibility};
+
+use super::{published_assignments, MyAssignment};
+
+// ============ Export ============
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// "csv", or "png" for an image to share in messaging groups; the Excel workbook is only
+    /// built by the desktop app for now
+    pub format: Option<String>,
+    /// "list" (default): one line per slot. "grid": service dates as columns and job positions
+    /// as rows, the way the parish bulletin prints the roster. CSV only.
+    pub layout: Option<String>,
+    /// Limits the image to one service date; the whole month otherwise
+    pub date: Option<NaiveDate>,
+}
+
+/// Columns of the CSV export, in Spanish like the spreadsheets coordinators keep
+const CSV_HEADER: [&str; 5] = ["Fecha", "Ministerio", "Posición", "Servidor", "Teléfono"];
+
+/// One slot of a schedule export; empty slots have no person
+#[derive(Debug, Clone, PartialEq, FromRow)]
+struct ExportRow {
+    service_date: NaiveDate,
+    job_id: String,
+    job_name: String,
+    position: Option<i32>,
+    position_name: Option<String>,
+    person_name: Option<String>,
+    phone: Option<String>,
+}
+
+/// Dates as dd/mm/yyyy (what a Spanish-locale sheet parses as a date), the position's name or
+/// its number, and empty slots left blank
+fn csv_rows(rows: &[ExportRow]) -> Vec<Vec<String>> {
+    rows.iter()
+        .map(|row| {
+            vec![
+                row.service_date.format("%d/%m/%Y").to_string(),
+                row.job_name.clone(),
+                row.position_name
+                    .clone()
+                    .or_else(|| row.position.map(|p| p.to_string()))
+                    .unwrap_or_default(),
+                row.person_name.clone().unwrap_or_default(),
+                row.phone.clone().unwrap_or_default(),
+            ]
+        })
+        .collect()
+}
+
+/// A row of the grid layout: the job position and, per service date, who serves it
+struct GridLine<'a> {
+    label: String,
+    cells: Vec<Vec<&'a str>>,
+}
+
+/// The bulletin layout: a header of service dates (dd/mm) and one row per job position, each
+/// cell holding who serves it that day
+fn grid_rows(rows: &[ExportRow]) -> (Vec<String>, Vec<Vec<String>>) {
+    let dates: BTreeSet<NaiveDate> = rows.iter().map(|row| row.service_date).collect();
+    let columns: HashMap<NaiveDate, usize> = dates
+        .iter()
+        .enumerate()
+        .map(|(i, date)| (*date, i))
+        .collect();
+
+    // Keyed by job name, job and position, so rows come out in the order the bulletin uses
+    let mut lines: BTreeMap<(&str, &str, i32), GridLine> = BTreeMap::new();
+    for row in rows {
+        let key = (
+            row.job_name.as_str(),
+            row.job_id.as_str(),
+            row.position.unwrap_or(0),
+        );
+        let line = lines.entry(key).or_insert_with(|| {
+            let label = match (&row.position_name, row.position) {
+                (Some(name), _) => format!("{} - {}", row.job_name, name),
+                (None, Some(position)) => format!("{} {}", row.job_name, position),
+                (None, None) => row.job_name.clone(),
+            };
+            GridLine {
+                label,
+                cells: vec![Vec::new(); dates.len()],
+            }
+        });
+        if let Some(person) = &row.person_name {
+            line.cells[columns[&row.service_date]].push(person);
+        }
+    }
+
+    let mut header = vec!["Ministerio".to_string()];
+    header.extend(dates.iter().map(|date| date.format("%d/%m").to_string()));
+    let grid = lines
+        .into_values()
+        .map(|line| {
+            let mut out = vec![line.label];
+            out.extend(line.cells.into_iter().map(|people| people.join(" / ")));
+            out
+        })
+        .collect();
+    (header, grid)
+}
+
+/// The schedule and its slots on dates that were not cancelled, for admins and coordinators
+/// (coordinators get their own jobs only)
+async fn load_export(
+    pool: &PgPool,
+    claims: &Claims,
+    id: &str,
+) -> Result<(Schedule, Vec<ExportRow>), (StatusCode, String)> {
+    if !claims.is_admin() && !claims.is_coordinator() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins and coordinators can export schedules".to_string(),
+        ));
+    }
+
+    let schedule = sqlx::query_as::<_, Schedule>("SELECT * FROM schedules WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))?;
+
+    let rows = sqlx::query_as::<_, ExportRow>(
+        r#"
+        SELECT sd.service_date, a.job_id, j.name as job_name, a.position, a.position_name,
+               p.first_name || ' ' || p.last_name as person_name, p.phone
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN jobs j ON a.job_id = j.id
+        LEFT JOIN people p ON a.person_id = p.id
+        WHERE sd.schedule_id = $1 AND sd.cancelled_at IS NULL
+        ORDER BY sd.service_date, j.name, a.position
+        "#,
+    )
+    .bind(id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let rows: Vec<ExportRow> = rows
+        .into_iter()
+        .filter(|row| claims.in_scope(&row.job_id))
+        .collect();
+
+    Ok((schedule, rows))
+}
+
+/// Every slot of the schedule (drafts included, for coordinators who send messages before
+/// publishing) with the person's phone. Coordinators only get their own jobs.
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/export",
+    params(ExportQuery),
+    tag = "schedules",
+    responses((status = 200, content((String = "text/csv"), ("image/png"))))
+)]
+pub async fn export_schedule(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let image = match query.format.as_deref() {
+        Some("csv") => false,
+        Some("png") => true,
+        _ => {
+            return Err((
+                StatusCode::NOT_IMPLEMENTED,
+                "Excel export is only built by the desktop app, use csv or png".to_string(),
+            ))
+        }
+    };
+    let grid = match query.layout.as_deref() {
+        None | Some("list") => false,
+        Some("grid") => true,
+        Some(other) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Unknown layout '{}', use list or grid", other),
+            ))
+        }
+    };
+    let (schedule, rows) = load_export(&pool, &claims, &id).await?;
+
+    if image {
+        let (rows, filename) = match query.date {
+            Some(date) => {
+                let rows: Vec<ExportRow> = rows
+                    .into_iter()
+                    .filter(|row| row.service_date == date)
+                    .collect();
+                if rows.is_empty() {
+                    return Err((
+                        StatusCode::NOT_FOUND,
+                        "No assignments on that date".to_string(),
+                    ));
+                }
+                (rows, format!("horario-{}.png", date))
+            }
+            None => (
+                rows,
+                format!("horario-{}-{:02}.png", schedule.year, schedule.month),
+            ),
+        };
+        let notes = date_notes(&pool, &id).await?;
+        let mass_time = mass_time_label(&pool).await?;
+        let body = schedule_image(&schedule.name, mass_time.as_deref(), &rows, &notes)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        return Ok(png::attachment(&filename, body));
+    }
+
+    let filename = format!("horario-{}-{:02}.csv", schedule.year, schedule.month);
+    if grid {
+        let (header, lines) = grid_rows(&rows);
+        let header: Vec<&str> = header.iter().map(String::as_str).collect();
+        return Ok(csv::attachment(&filename, csv::to_csv(&header, &lines)));
+    }
+    Ok(csv::attachment(
+        &filename,
+        csv::to_csv(&CSV_HEADER, &csv_rows(&rows)),
+    ))
+}
+
+// ============ Chat summary ============
+
+/// "Domingo 1 de Noviembre"
+fn date_label(date: NaiveDate) -> String {
+    format!(
+        "{} {} de {}",
+        naming::weekday_name(date.weekday()),
+        date.day(),
+        naming::month_name(date.month() as i32)
+    )
+}
+
+/// The schedule as a message for the parish WhatsApp group: a heading per date (`*bold*` in
+/// WhatsApp) with the mass time and the date's note, then each job and one bullet per position
+fn summary_text(
+    name: &str,
+    mass_time: Option<&str>,
+    rows: &[ExportRow],
+    notes: &HashMap<NaiveDate, String>,
+) -> String {
+    let mut out = format!("📋 *Horario {}*\n", name);
+    let mut current_date = None;
+    let mut current_job = None;
+    for row in rows {
+        let date = row.service_date;
+        if current_date != Some(date) {
+            current_date = Some(date);
+            current_job = None;
+            out.push_str(&format!("\n🗓️ *{}*", date_label(date)));
+            if let Some(time) = mass_time {
+                out.push_str(&format!(" · {}", time));
+            }
+            out.push('\n');
+            if let Some(note) = notes.get(&date) {
+                out.push_str(&format!("📝 {}\n", note));
+            }
+        }
+        if current_job != Some(row.job_id.as_str()) {
+            current_job = Some(row.job_id.as_str());
+            out.push_str(&format!("*{}*\n", row.job_name));
+        }
+        let person = row.person_name.as_deref().unwrap_or("_por asignar_");
+        match &row.position_name {
+            Some(position) => out.push_str(&format!("• {}: {}\n", position, person)),
+            None => out.push_str(&format!("• {}\n", person)),
+        }
+    }
+    out
+}
+
+/// The notes of the schedule's dates that have one
+async fn date_notes(
+    pool: &PgPool,
+    id: &str,
+) -> Result<HashMap<NaiveDate, String>, (StatusCode, String)> {
+    let notes: Vec<(NaiveDate, String)> = sqlx::query_as(
+        r#"
+        SELECT service_date, notes FROM service_dates
+        WHERE schedule_id = $1 AND cancelled_at IS NULL AND notes IS NOT NULL AND notes <> ''
+        "#,
+    )
+    .bind(id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(notes.into_iter().collect())
+}
+
+/// The mass_time setting as written, None when blank
+async fn mass_time_label(pool: &PgPool) -> Result<Option<String>, (StatusCode, String)> {
+    Ok(settings::get_string(pool, "mass_time")
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .filter(|time| !time.trim().is_empty()))
+}
+
+/// Plain text to paste into the group chat; same access and slots as the CSV export
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/summary.txt",
+    tag = "schedules",
+    responses((status = 200, content_type = "text/plain", body = String))
+)]
+pub async fn export_summary(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Response, (StatusCode, String)> {
+    let (schedule, rows) = load_export(&pool, &claims, &id).await?;
+
+    let notes = date_notes(&pool, &id).await?;
+    let mass_time = mass_time_label(&pool).await?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        summary_text(&schedule.name, mass_time.as_deref(), &rows, &notes),
+    )
+        .into_response())
+}
+
+// ============ Image ============
+
+// The image is drawn at twice the font's size, on a page wide enough for a phone screen
+const IMAGE_WIDTH: u32 = 720;
+const IMAGE_MARGIN: u32 = 24;
+const IMAGE_SCALE: u32 = 2;
+const TITLE_SCALE: u32 = 3;
+/// How far people are indented under their job
+const PERSON_INDENT: u32 = 24;
+
+// White background, near-black text, the app's blue, a light blue band, gray notes
+const WHITE: Rgb<u8> = Rgb([0xFF, 0xFF, 0xFF]);
+const TEXT: Rgb<u8> = Rgb([0x1F, 0x29, 0x37]);
+const BLUE: Rgb<u8> = Rgb([0x1E, 0x40, 0xAF]);
+const BAND: Rgb<u8> = Rgb([0xDB, 0xEA, 0xFE]);
+const GRAY: Rgb<u8> = Rgb([0x6B, 0x72, 0x80]);
+
+/// What the image shows, top to bottom
+enum ImageLine {
+    DateHeading(String),
+    Note(String),
+    Job(String),
+    Person(String),
+    Gap,
+}
+
+/// The schedule as a PNG: a blue title band, then per date a heading band, its note, and each
+/// job with the people serving it, as in the chat summary
+fn schedule_image(
+    name: &str,
+    mass_time: Option<&str>,
+    rows: &[ExportRow],
+    notes: &HashMap<NaiveDate, String>,
+) -> Result<Vec<u8>, String> {
+    let line_height = png::LINE_HEIGHT * IMAGE_SCALE;
+    let title_height = png::LINE_HEIGHT * TITLE_SCALE + 2 * IMAGE_MARGIN;
+    let max_chars = ((IMAGE_WIDTH - 2 * IMAGE_MARGIN - PERSON_INDENT)
+        / (png::CHAR_WIDTH * IMAGE_SCALE)) as usize;
+
+    let mut lines = Vec::new();
+    let mut current_date = None;
+    let mut current_job = None;
+    for row in rows {
+        let date = row.service_date;
+        if current_date != Some(date) {
+            if current_date.is_some() {
+                lines.push(ImageLine::Gap);
+            }
+            current_date = Some(date);
+            current_job = None;
+            lines.push(ImageLine::DateHeading(match mass_time {
+                Some(time) => format!("{} · {}", date_label(date), time),
+                None => date_label(date),
+            }));
+            if let Some(note) = notes.get(&date) {
+                lines.extend(png::wrap(note, max_chars).into_iter().map(ImageLine::Note));
+            }
+        }
+        if current_job != Some(row.job_id.as_str()) {
+            current_job = Some(row.job_id.as_str());
+            lines.push(ImageLine::Job(row.job_name.clone()));
+        }
+        let person = row.person_name.as_deref().unwrap_or("(por asignar)");
+        let text = match &row.position_name {
+            Some(position) => format!("{}: {}", position, person),
+            None => person.to_string(),
+        };
+        lines.extend(
+            png::wrap(&text, max_chars)
+                .into_iter()
+                .map(ImageLine::Person),
+        );
+    }
+
+    let height = title_height + lines.len() as u32 * line_height + IMAGE_MARGIN;
+    let mut canvas = png::Canvas::new(IMAGE_WIDTH, height, WHITE);
+    canvas.fill(0, 0, IMAGE_WIDTH, title_height, BLUE);
+    canvas.text(
+        IMAGE_MARGIN,
+        IMAGE_MARGIN,
+        TITLE_SCALE,
+        WHITE,
+        &format!("Horario {}", name),
+    );
+
+    let mut y = title_height;
+    for line in &lines {
+        match line {
+            ImageLine::DateHeading(text) => {
+                canvas.fill(0, y, IMAGE_WIDTH, line_height, BAND);
+                canvas.text(IMAGE_MARGIN, y, IMAGE_SCALE, BLUE, text);
+            }
+            ImageLine::Note(text) => canvas.text(IMAGE_MARGIN, y, IMAGE_SCALE, GRAY, text),
+            ImageLine::Job(text) => canvas.text(IMAGE_MARGIN, y, IMAGE_SCALE, BLUE, text),
+            ImageLine::Person(text) => {
+                canvas.text(IMAGE_MARGIN + PERSON_INDENT, y, IMAGE_SCALE, TEXT, text)
+            }
+            ImageLine::Gap => {}
+        }
+        y += line_height;
+    }
+
+    canvas.to_png()
+}
+
+// ============ Calendar ============
+
+/// One VEVENT per assignment; the uid is the date, job and position so a re-import or a feed
+/// refresh updates the event rather than adding a second one
+fn calendar_events(
+    assignments: Vec<MyAssignment>,
+    mass_time: Option<NaiveTime>,
+    location: Option<&str>,
+) -> Vec<ical::Event> {
+    assignments
+        .into_iter()
+        .map(|a| {
+            let description: Vec<String> = [a.notes, a.service_date_notes]
+                .into_iter()
+                .flatten()
+                .collect();
+            ical::Event {
+                uid: format!(
+                    "{}-{}-{}@people-scheduler",
+                    a.service_date,
+                    a.job_id,
+                    a.position.unwrap_or(0)
+                ),
+                date: a.service_date,
+                start: mass_time,
+                summary: match a.position_name {
+                    Some(position) => format!("{} ({})", a.job_name, position),
+                    None => a.job_name,
+                },
+                description: (!description.is_empty()).then(|| description.join("\n")),
+                location: location.map(str::to_string),
+            }
+        })
+        .collect()
+}
+
+/// A person's published assignments up to `horizon` as an iCalendar document, timed at the
+/// mass_time setting (all-day when it is blank) and placed at the service_location setting
+pub(crate) async fn person_calendar(
+    pool: &PgPool,
+    person_id: &str,
+    horizon: Option<NaiveDate>,
+) -> Result<String, (StatusCode, String)> {
+    let (first_name, last_name) = sqlx::query_as::<_, (String, String)>(
+        "SELECT first_name, last_name FROM people WHERE id = $1",
+    )
+    .bind(person_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or((StatusCode::NOT_FOUND, "Person not found".to_string()))?;
+
+    let mass_time = settings::get_string(pool, "mass_time")
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .and_then(|time| NaiveTime::parse_from_str(time.trim(), "%H:%M").ok());
+    let location = settings::get_string(pool, "service_location")
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .map(|location| location.trim().to_string())
+        .filter(|location| !location.is_empty());
+
+    let assignments = published_assignments(pool, person_id, horizon).await?;
+    Ok(ical::to_calendar(
+        &format!("Servicios de {} {}", first_name, last_name),
+        &calendar_events(assignments, mass_time, location.as_deref()),
+    ))
+}
+
+/// A person's published assignments as an .ics file to import into a phone calendar. Servidores
+/// can download their own; coordinators the people in their jobs.
+#[utoipa::path(
+    get,
+    path = "/people/{id}/calendar.ics",
+    tag = "schedules",
+    responses((status = 200, content_type = "text/calendar", body = String))
+)]
+pub async fn export_person_calendar(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+) -> Result<Response, (StatusCode, String)> {
+    auth::require_self_or_person_scope(&pool, &claims, &person_id).await?;
+
+    // Same window the servidor sees in the app
+    let horizon = if claims.is_admin() {
+        None
+    } else {
+        visibility::horizon(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+    };
+
+    let calendar = person_calendar(&pool, &person_id, horizon).await?;
+    Ok(ical::attachment("servicios.ics", calendar))
+}
+
+// ============ Signage ============
+
+/// The schedule for the narthex display, in the versioned shape of `services::signage`.
+/// Only published jobs are shown.
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/signage.json",
+    tag = "schedules",
+    responses((status = 200, body = SignageSchedule))
+)]
+pub async fn export_signage(
+    State(pool): State<PgPool>,
+    Path(id): Path<String>,
+) -> Result<Json<SignageSchedule>, (StatusCode, String)> {
+    let schedule = sqlx::query_as::<_, Schedule>("SELECT * FROM schedules WHERE id = $1")
+        .bind(&id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))?;
+
+    let mass_time = settings::get_string(&pool, "mass_time")
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let (dates, slots) = signage::load(&mut conn, &id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(signage::build(
+        &schedule.id,
+        &schedule.name,
+        schedule.year,
+        schedule.month,
+        mass_time.as_deref(),
+        dates,
+        &slots,
+    )))
+}
+
+// ============ Next Service ============
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NextServiceQuery {
+    /// How many upcoming service dates to return (1-10, default 1)
+    pub count: Option<i64>,
+}
+
+/// Clients may show a cached roster for a while, and keep showing it for a day when the
+/// server cannot be reached, rather than an empty screen on Sunday morning
+const NEXT_SERVICE_CACHE_CONTROL: &str =
+    "private, max-age=300, stale-while-revalidate=3600, stale-if-error=86400";
+
+/// Who serves at the next service date(s), across jobs and schedules, with fill counts and
+/// notes. Answers 304 when the client's ETag still matches.
+#[utoipa::path(
+    get,
+    path = "/next-service",
+    params(NextServiceQuery),
+    tag = "schedules",
+    responses(
+        (status = 200, body = signage::NextServices),
+        (status = 304, description = "The client's ETag still matches"),
+    )
+)]
+pub async fn get_next_service(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<NextServiceQuery>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, String)> {
+    let count = query.count.unwrap_or(1);
+    if !(1..=10).contains(&count) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "count must be between 1 and 10".to_string(),
+        ));
+    }
+
+    // Servidores only see dates inside the visibility window
+    let until = if claims.is_admin() {
+        None
+    } else {
+        visibility::horizon(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+    };
+    let mass_time = settings::get_string(&pool, "mass_time")
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let (dates, slots) = signage::load_upcoming(&mut conn, Utc::now().date_naive(), until, count)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let next = signage::build_next(mass_time.as_deref(), dates, &slots);
+
+    let body = serde_json::to_string(&next)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    let etag = format!("\"{:016x}\"", hasher.finish());
+
+    let cache_headers = [
+        (
+            header::CACHE_CONTROL,
+            NEXT_SERVICE_CACHE_CONTROL.to_string(),
+        ),
+        (header::ETAG, etag.clone()),
+    ];
+    let unchanged = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|tag| tag.trim() == etag));
+    if unchanged {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    Ok((
+        cache_headers,
+        [(header::CONTENT_TYPE, "application/json")],
+        body,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(day: u32, position: i32, person: Option<&str>) -> ExportRow {
+        ExportRow {
+            service_date: NaiveDate::from_ymd_opt(2026, 11, day).unwrap(),
+            job_id: "lectores".to_string(),
+            job_name: "Lectores".to_string(),
+            position: Some(position),
+            position_name: (position == 1).then(|| "Primera lectura".to_string()),
+            person_name: person.map(str::to_string),
+            phone: None,
+        }
+    }
+
+    #[test]
+    fn grid_puts_dates_in_columns_and_positions_in_rows() {
+        let (header, lines) = grid_rows(&[
+            slot(1, 1, Some("Ana")),
+            slot(1, 2, None),
+            slot(8, 1, Some("Luis")),
+            slot(8, 2, Some("Marta")),
+        ]);
+        assert_eq!(header, vec!["Ministerio", "01/11", "08/11"]);
+        assert_eq!(
+            lines,
+            vec![
+                vec!["Lectores - Primera lectura", "Ana", "Luis"],
+                vec!["Lectores 2", "", "Marta"],
+            ]
+        );
+    }
+
+    #[test]
+    fn summary_groups_slots_by_date_and_job() {
+        let mut rows = vec![
+            slot(1, 1, Some("Ana López")),
+            slot(1, 2, None),
+            slot(8, 1, Some("Luis")),
+        ];
+        rows.insert(
+            2,
+            ExportRow {
+                job_id: "monaguillos".to_string(),
+                job_name: "Monaguillos".to_string(),
+                position_name: None,
+                ..slot(1, 1, Some("Marta"))
+            },
+        );
+        let notes = HashMap::from([(
+            NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+            "Todos los Santos".to_string(),
+        )]);
+
+        assert_eq!(
+            summary_text("Noviembre 2026", Some("10:00"), &rows, &notes),
+            "📋 *Horario Noviembre 2026*\n\
+             \n🗓️ *Domingo 1 de Noviembre* · 10:00\n\
+             📝 Todos los Santos\n\
+             *Lectores*\n\
+             • Primera lectura: Ana López\n\
+             • _por asignar_\n\
+             *Monaguillos*\n\
+             • Marta\n\
+             \n🗓️ *Domingo 8 de Noviembre* · 10:00\n\
+             *Lectores*\n\
+             • Primera lectura: Luis\n"
+        );
+    }
+
+    #[test]
+    fn csv_rows_leave_empty_slots_blank() {
+        let row = ExportRow {
+            service_date: NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+            job_id: "lectores".to_string(),
+            job_name: "Lectores".to_string(),
+            position: Some(2),
+            position_name: None,
+            person_name: None,
+            phone: None,
+        };
+        let named = ExportRow {
+            position_name: Some("Salmo".to_string()),
+            person_name: Some("Ana López".to_string()),
+            phone: Some("5555-1234".to_string()),
+            ..row.clone()
+        };
+        assert_eq!(
+            csv_rows(&[row, named]),
+            vec![
+                vec!["01/11/2026", "Lectores", "2", "", ""],
+                vec!["01/11/2026", "Lectores", "Salmo", "Ana López", "5555-1234"],
+            ]
+        );
+    }
+
+    fn assignment(position: Option<i32>, position_name: Option<&str>) -> MyAssignment {
+        MyAssignment {
+            service_date: NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+            job_id: "lectores".to_string(),
+            job_name: "Lectores".to_string(),
+            job_color: "#3B82F6".to_string(),
+            position,
+            position_name: position_name.map(str::to_string),
+            notes: Some("Llegar temprano".to_string()),
+            service_date_notes: Some("Primera comunión".to_string()),
+        }
+    }
+
+    #[test]
+    fn calendar_events_name_the_position_and_keep_stable_uids() {
+        let events = calendar_events(
+            vec![
+                assignment(Some(1), Some("Primera lectura")),
+                assignment(None, None),
+            ],
+            NaiveTime::from_hms_opt(10, 0, 0),
+            Some("Parroquia San José"),
+        );
+        assert_eq!(events[0].summary, "Lectores (Primera lectura)");
+        assert_eq!(events[0].uid, "2026-11-01-lectores-1@people-scheduler");
+        assert_eq!(
+            events[0].description.as_deref(),
+            Some("Llegar temprano\nPrimera comunión")
+        );
+        assert_eq!(events[1].summary, "Lectores");
+        assert_eq!(events[1].uid, "2026-11-01-lectores-0@people-scheduler");
+        assert_eq!(events[1].location.as_deref(), Some("Parroquia San José"));
+    }
+}
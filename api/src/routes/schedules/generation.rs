@@ -0,0 +1,1555 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::Deserialize;
+use sqlx::{FromRow, PgConnection, PgPool};
+use std::collections::HashMap;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::auth::{self, Claims};
+use crate::db::tx::Tx;
+use crate::models::{
+    Assignment, BackgroundJob, BatchMonthSummary, DuplicateScheduleRequest,
+    GenerateScheduleBatchRequest, GenerateScheduleRangeRequest, GenerateScheduleRequest, Job,
+    RegenerateScheduleRequest, Schedule, ScheduleConflict, ScheduleJobStatus, ScheduleStatusEvent,
+    ScheduleWithDates, ServiceDate, ServiceDateWithAssignments, UnpublishScheduleRequest,
+    UpdateScheduleRequest, JUNIOR_JOB_ID,
+};
+use crate::routes::settings;
+use crate::services::{
+    assignment_events, background_jobs, chat_bot, generator, naming, notifications, push, rotation,
+    schedule_events, snapshots,
+};
+
+use super::{archived_error, month_taken_error, AssignmentRow, ASSIGNMENT_DETAILS_SELECT};
+
+// ============ Generate Schedule ============
+
+#[utoipa::path(
+    post,
+    path = "/schedules",
+    tag = "schedules",
+    responses((status = 200, body = ScheduleWithDates))
+)]
+pub async fn generate(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
+    Json(input): Json<GenerateScheduleRequest>,
+) -> Result<Json<ScheduleWithDates>, (StatusCode, String)> {
+    auth::require_admin(&claims, "generate schedules")?;
+
+    let year = input.year;
+    let month = input.month;
+
+    // Check if schedule already exists
+    let existing =
+        sqlx::query_scalar::<_, String>("SELECT id FROM schedules WHERE year = $1 AND month = $2 AND status NOT IN ('ARCHIVED', 'EXPIRED')")
+            .bind(year)
+            .bind(month)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if existing.is_some() {
+        return Err(month_taken_error(year, month));
+    }
+
+    let jobs = load_jobs_for_generation(&pool).await?;
+
+    // Get Sundays of the month
+    let sundays = generator::get_sundays_of_month(year, month as u32);
+    let pattern = naming::load_pattern(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let schedule_name = naming::schedule_name(&pattern, year, month);
+
+    let result =
+        generator::generate_for_dates(&mut tx, &jobs, &schedule_name, year, month, &sundays, None)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+            .ok_or_else(|| month_taken_error(year, month))?;
+
+    Ok(Json(result))
+}
+
+// ============ Generate Schedules for a Date Range ============
+
+/// Generate a quarter or a liturgical season in one request, either as one long schedule
+/// (`mode: "single"`) or as one linked schedule per calendar month (`mode: "monthly"`)
+#[utoipa::path(
+    post,
+    path = "/schedules/range",
+    tag = "schedules",
+    responses((status = 200, body = Vec<ScheduleWithDates>))
+)]
+pub async fn generate_range(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
+    Json(input): Json<GenerateScheduleRangeRequest>,
+) -> Result<Json<Vec<ScheduleWithDates>>, (StatusCode, String)> {
+    auth::require_admin(&claims, "generate schedules")?;
+
+    if input.end_date < input.start_date {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "end_date must be on or after start_date".to_string(),
+        ));
+    }
+    if (input.end_date - input.start_date).num_days() > MAX_RANGE_DAYS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("A range can span at most {} days", MAX_RANGE_DAYS),
+        ));
+    }
+
+    let monthly = match input.mode.as_deref().unwrap_or("monthly") {
+        "monthly" => true,
+        "single" => false,
+        other => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Invalid mode: {}. Allowed: single, monthly", other),
+            ))
+        }
+    };
+
+    let sundays: Vec<NaiveDate> = input
+        .start_date
+        .iter_days()
+        .take_while(|d| *d <= input.end_date)
+        .filter(|d| d.weekday() == Weekday::Sun)
+        .collect();
+    if sundays.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "There are no Sundays in the selected range".to_string(),
+        ));
+    }
+
+    // Group the Sundays into the schedules to create: (year, month, dates)
+    let mut segments: Vec<(i32, i32, Vec<NaiveDate>)> = Vec::new();
+    for sunday in sundays {
+        let key = (sunday.year(), sunday.month() as i32);
+        match segments.last_mut() {
+            Some((y, m, dates)) if !monthly || (*y, *m) == key => dates.push(sunday),
+            _ => segments.push((key.0, key.1, vec![sunday])),
+        }
+    }
+
+    // Nothing is written unless the whole range is free
+    let overlapping: Option<String> = sqlx::query_scalar(
+        r#"
+        SELECT s.name
+        FROM service_dates sd
+        JOIN schedules s ON s.id = sd.schedule_id
+        WHERE sd.service_date BETWEEN $1 AND $2
+        LIMIT 1
+        "#,
+    )
+    .bind(input.start_date)
+    .bind(input.end_date)
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if let Some(name) = overlapping {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("The range overlaps the existing schedule {}", name),
+        ));
+    }
+
+    for (year, month, _) in &segments {
+        let existing = sqlx::query_scalar::<_, String>(
+            "SELECT id FROM schedules WHERE year = $1 AND month = $2 AND status NOT IN ('ARCHIVED', 'EXPIRED')",
+        )
+        .bind(year)
+        .bind(month)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        if existing.is_some() {
+            return Err(month_taken_error(*year, *month));
+        }
+    }
+
+    let jobs = load_jobs_for_generation(&pool).await?;
+    let pattern = naming::load_pattern(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let series_id = Uuid::new_v4().to_string();
+
+    // All segments land together or not at all: they share the request transaction
+    let mut result = Vec::new();
+    for (year, month, dates) in &segments {
+        let name = match (&input.name, monthly) {
+            (Some(name), true) => format!("{} - {} {}", name, naming::month_name(*month), year),
+            (Some(name), false) => name.clone(),
+            (None, true) => naming::schedule_name(&pattern, *year, *month),
+            (None, false) => format!(
+                "{} - {}",
+                dates[0].format("%d/%m/%Y"),
+                dates[dates.len() - 1].format("%d/%m/%Y")
+            ),
+        };
+
+        result.push(
+            generator::generate_for_dates(
+                &mut tx,
+                &jobs,
+                &name,
+                *year,
+                *month,
+                dates,
+                Some(&series_id),
+            )
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+            .ok_or_else(|| month_taken_error(*year, *month))?,
+        );
+    }
+
+    Ok(Json(result))
+}
+
+// ============ Generate Several Months ============
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BatchQuery {
+    /// Queue the batch for the background worker and answer 202 with the job right away
+    pub background: Option<bool>,
+}
+
+/// Generate one schedule per month from start to end, in order, like the desktop's
+/// `generate_year_schedules`. Each month's history is written before the next one is
+/// generated, so rotation and consecutive-month rules carry forward. Months that already
+/// have a schedule are skipped; everything else lands together or not at all.
+#[utoipa::path(
+    post,
+    path = "/schedules/batch",
+    params(BatchQuery),
+    tag = "schedules",
+    responses(
+        (status = 200, body = Vec<BatchMonthSummary>),
+        (status = 202, description = "Queued as a background job", body = BackgroundJob),
+    )
+)]
+pub async fn generate_batch(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
+    Query(query): Query<BatchQuery>,
+    Json(input): Json<GenerateScheduleBatchRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    auth::require_admin(&claims, "generate schedules")?;
+
+    if query.background.unwrap_or(false) {
+        batch_months(
+            (input.start_year, input.start_month),
+            (input.end_year, input.end_month),
+        )
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        let payload = serde_json::to_value(&input)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        let job =
+            background_jobs::enqueue(&mut *tx, "generate_batch", &payload, Some(&claims.username))
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        return Ok((StatusCode::ACCEPTED, Json(job)).into_response());
+    }
+
+    let summaries = run_batch(&pool, &mut tx, &input).await?;
+    Ok(Json(summaries).into_response())
+}
+
+/// The work of `generate_batch`, also run by the background worker for queued batches
+pub async fn run_batch(
+    pool: &PgPool,
+    conn: &mut PgConnection,
+    input: &GenerateScheduleBatchRequest,
+) -> Result<Vec<BatchMonthSummary>, (StatusCode, String)> {
+    let months = batch_months(
+        (input.start_year, input.start_month),
+        (input.end_year, input.end_month),
+    )
+    .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let jobs = load_jobs_for_generation(pool).await?;
+    let pattern = naming::load_pattern(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let mut summaries = Vec::new();
+    for (year, month) in months {
+        let sundays = generator::get_sundays_of_month(year, month as u32);
+        let name = naming::schedule_name(&pattern, year, month);
+        let generated =
+            generator::generate_for_dates(&mut *conn, &jobs, &name, year, month, &sundays, None)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+        // The month already has a schedule: the insert left it alone
+        let Some(generated) = generated else {
+            let schedule_id = sqlx::query_scalar::<_, String>(
+                "SELECT id FROM schedules WHERE year = $1 AND month = $2 AND status NOT IN ('ARCHIVED', 'EXPIRED')",
+            )
+            .bind(year)
+            .bind(month)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            summaries.push(BatchMonthSummary {
+                year,
+                month,
+                status: "skipped".to_string(),
+                schedule_id,
+                service_dates: 0,
+                total_slots: 0,
+                filled_slots: 0,
+                conflicts: Vec::new(),
+            });
+            continue;
+        };
+
+        let slots = generated
+            .service_dates
+            .iter()
+            .flat_map(|sd| &sd.assignments);
+        summaries.push(BatchMonthSummary {
+            year,
+            month,
+            status: "generated".to_string(),
+            schedule_id: Some(generated.schedule.id.clone()),
+            service_dates: generated.service_dates.len(),
+            total_slots: slots.clone().count(),
+            filled_slots: slots.filter(|a| a.assignment.person_id.is_some()).count(),
+            conflicts: generated.conflicts,
+        });
+    }
+
+    Ok(summaries)
+}
+
+/// Most months a single batch may generate
+const MAX_BATCH_MONTHS: usize = 24;
+
+/// The (year, month) pairs from `start` to `end`, both included
+fn batch_months(start: (i32, i32), end: (i32, i32)) -> Result<Vec<(i32, i32)>, String> {
+    for (year, month) in [start, end] {
+        if !(1..=12).contains(&month) {
+            return Err(format!("Invalid month {}/{}", month, year));
+        }
+    }
+    if end < start {
+        return Err("The end month must not be before the start month".to_string());
+    }
+
+    let mut months = Vec::new();
+    let (mut year, mut month) = start;
+    while (year, month) <= end {
+        months.push((year, month));
+        if months.len() > MAX_BATCH_MONTHS {
+            return Err(format!(
+                "A batch can generate at most {} months",
+                MAX_BATCH_MONTHS
+            ));
+        }
+        if month == 12 {
+            year += 1;
+            month = 1;
+        } else {
+            month += 1;
+        }
+    }
+    Ok(months)
+}
+
+/// Longest range accepted by `generate_range`
+const MAX_RANGE_DAYS: i64 = 366;
+
+/// Active jobs in generation order, after checking there is someone to schedule
+async fn load_jobs_for_generation(pool: &PgPool) -> Result<Vec<Job>, (StatusCode, String)> {
+    let mut jobs = sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE active = true")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    preflight_check(pool, &jobs).await?;
+
+    // Juniors are filled last so the per-Mass cap can look at the seniors already assigned
+    jobs.sort_by_key(|j| j.id == JUNIOR_JOB_ID);
+
+    Ok(jobs)
+}
+
+/// Refuse to generate when the result could only be empty, before any rows are written
+async fn preflight_check(pool: &PgPool, jobs: &[Job]) -> Result<(), (StatusCode, String)> {
+    if jobs.is_empty() {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "No active jobs to schedule. Activate at least one job before generating".to_string(),
+        ));
+    }
+
+    let mut unstaffable = Vec::new();
+    for job in jobs.iter().filter(|j| j.people_required > 0) {
+        let qualified: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(DISTINCT p.id)
+            FROM people p
+            JOIN person_jobs pj ON p.id = pj.person_id
+            WHERE pj.job_id = $1
+              AND p.active = true
+              AND NOT EXISTS (
+                  SELECT 1 FROM person_job_exclusions x
+                  WHERE x.person_id = p.id AND x.job_id = $1
+              )
+              AND (NOT $2 OR p.first_communion = true)
+            "#,
+        )
+        .bind(&job.id)
+        .bind(job.requires_first_communion)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        if qualified == 0 {
+            unstaffable.push(job.name.clone());
+        }
+    }
+
+    if !unstaffable.is_empty() {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!(
+                "No active people qualified for {}. Assign people to these jobs or deactivate them before generating",
+                unstaffable.join(", ")
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+// ============ Regenerate Schedule ============
+
+/// Run the algorithm again over an existing schedule. With `mode: "future"` (the default)
+/// dates before today keep their assignments and history exactly as served, and the people
+/// on them still count toward this month's limits; `mode: "all"` recomputes every date.
+/// Manual edits on recomputed dates are replaced. Returns the recomputed dates only.
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/regenerate",
+    tag = "schedules",
+    responses((status = 200, body = ScheduleWithDates))
+)]
+pub async fn regenerate(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
+    Path(id): Path<String>,
+    Json(input): Json<RegenerateScheduleRequest>,
+) -> Result<Json<ScheduleWithDates>, (StatusCode, String)> {
+    auth::require_admin(&claims, "regenerate schedules")?;
+
+    let keep_past = match input.mode.as_deref().unwrap_or("future") {
+        "future" => true,
+        "all" => false,
+        other => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Invalid mode: {}. Allowed: future, all", other),
+            ))
+        }
+    };
+
+    let schedule =
+        sqlx::query_as::<_, Schedule>("SELECT * FROM schedules WHERE id = $1 FOR UPDATE")
+            .bind(&id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))?;
+    if schedule.status == "ARCHIVED" {
+        return Err(archived_error());
+    }
+
+    let service_dates = sqlx::query_as::<_, ServiceDate>(
+        r#"
+        SELECT * FROM service_dates
+        WHERE schedule_id = $1 AND cancelled_at IS NULL
+          AND (NOT $2 OR service_date >= CURRENT_DATE)
+        ORDER BY service_date
+        "#,
+    )
+    .bind(&id)
+    .bind(keep_past)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let Some(first_date) = service_dates.first().map(|sd| sd.service_date) else {
+        return Err((
+            StatusCode::CONFLICT,
+            "Every date of this schedule has already passed".to_string(),
+        ));
+    };
+    let service_date_ids: Vec<String> = service_dates.iter().map(|sd| sd.id.clone()).collect();
+
+    let jobs = load_jobs_for_generation(&pool).await?;
+
+    // Clear the dates being recomputed: rotation state of their people is derived again
+    // from what is left, then their history and assignments go
+    sqlx::query(
+        r#"
+        DELETE FROM position_rotation
+        WHERE (person_id, job_id) IN (
+            SELECT a.person_id, a.job_id
+            FROM assignments a
+            WHERE a.service_date_id = ANY($1) AND a.person_id IS NOT NULL
+        )
+        "#,
+    )
+    .bind(&service_date_ids)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    sqlx::query(
+        r#"
+        DELETE FROM assignment_history
+        WHERE service_date IN (SELECT service_date FROM service_dates WHERE id = ANY($1))
+        "#,
+    )
+    .bind(&service_date_ids)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    sqlx::query("DELETE FROM assignments WHERE service_date_id = ANY($1)")
+        .bind(&service_date_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    // Kept dates in the same month still count toward the monthly limits
+    let kept: Vec<(String, String)> = sqlx::query_as(
+        r#"
+        SELECT a.person_id, a.job_id
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        WHERE sd.schedule_id = $1
+          AND a.person_id IS NOT NULL
+          AND date_trunc('month', sd.service_date) = date_trunc('month', $2::date)
+        ORDER BY sd.service_date
+        "#,
+    )
+    .bind(&id)
+    .bind(first_date)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut assigned_this_month: HashMap<String, Vec<String>> = HashMap::new();
+    for (person_id, job_id) in kept {
+        assigned_this_month
+            .entry(person_id)
+            .or_default()
+            .push(job_id);
+    }
+
+    let (service_dates, conflicts) = generator::fill_service_dates(
+        &mut tx,
+        &schedule,
+        &jobs,
+        service_dates,
+        assigned_this_month,
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(ScheduleWithDates {
+        schedule,
+        service_dates,
+        conflicts,
+    }))
+}
+
+// ============ Duplicate Schedule ============
+
+#[derive(FromRow)]
+struct CopiedSlot {
+    job_id: String,
+    person_id: Option<String>,
+    position: Option<i32>,
+    position_name: Option<String>,
+    /// Inactive, no longer in the job, or unavailable on the new date
+    unavailable: bool,
+}
+
+/// Start a month from another schedule: the Nth service date of the source goes to the Nth
+/// Sunday of the new month. Copied people are marked as suggestions until someone edits the
+/// slot; anyone who cannot serve the new date leaves the slot empty and is listed in `conflicts`.
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/duplicate",
+    tag = "schedules",
+    responses((status = 200, body = ScheduleWithDates))
+)]
+pub async fn duplicate(
+    claims: Claims,
+    mut tx: Tx,
+    Path(id): Path<String>,
+    Json(input): Json<DuplicateScheduleRequest>,
+) -> Result<Json<ScheduleWithDates>, (StatusCode, String)> {
+    auth::require_admin(&claims, "duplicate schedules")?;
+
+    if !(1..=12).contains(&input.month) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Invalid month: {}", input.month),
+        ));
+    }
+
+    let source_dates = sqlx::query_as::<_, ServiceDate>(
+        "SELECT * FROM service_dates WHERE schedule_id = $1 ORDER BY service_date",
+    )
+    .bind(&id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if source_dates.is_empty() {
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM schedules WHERE id = $1)")
+                .bind(&id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        if !exists {
+            return Err((StatusCode::NOT_FOUND, "Schedule not found".to_string()));
+        }
+    }
+
+    let existing =
+        sqlx::query_scalar::<_, String>("SELECT id FROM schedules WHERE year = $1 AND month = $2 AND status NOT IN ('ARCHIVED', 'EXPIRED')")
+            .bind(input.year)
+            .bind(input.month)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if existing.is_some() {
+        return Err(month_taken_error(input.year, input.month));
+    }
+
+    let name = match input.name.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => {
+            let pattern = naming::load_pattern(&mut *tx)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            naming::schedule_name(&pattern, input.year, input.month)
+        }
+    };
+
+    let sundays = generator::get_sundays_of_month(input.year, input.month as u32);
+    let schedule = sqlx::query_as::<_, Schedule>(
+        r#"
+        INSERT INTO schedules (id, name, year, month, status, start_date, end_date)
+        VALUES ($1, $2, $3, $4, 'DRAFT', $5, $6)
+        ON CONFLICT (year, month) WHERE status NOT IN ('ARCHIVED', 'EXPIRED') DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&name)
+    .bind(input.year)
+    .bind(input.month)
+    .bind(sundays.first())
+    .bind(sundays.last())
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or_else(|| month_taken_error(input.year, input.month))?;
+
+    let mut service_dates = Vec::new();
+    let mut conflicts = Vec::new();
+    for (i, sunday) in sundays.iter().enumerate() {
+        let sd = sqlx::query_as::<_, ServiceDate>(
+            r#"
+            INSERT INTO service_dates (id, schedule_id, service_date)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&schedule.id)
+        .bind(sunday)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        // A fifth Sunday with no counterpart in the source starts empty
+        if let Some(source) = source_dates.get(i) {
+            let slots = sqlx::query_as::<_, CopiedSlot>(
+                r#"
+                SELECT a.job_id, a.person_id, a.position, a.position_name,
+                       a.person_id IS NOT NULL AND (
+                           NOT COALESCE(p.active, false)
+                           OR NOT EXISTS (
+                               SELECT 1 FROM person_jobs pj
+                               WHERE pj.person_id = a.person_id AND pj.job_id = a.job_id
+                           )
+                           OR EXISTS (
+                               SELECT 1 FROM unavailability u
+                               WHERE u.person_id = a.person_id AND unavailability_matches(u, $2)
+                           )
+                       ) as unavailable
+                FROM assignments a
+                LEFT JOIN people p ON a.person_id = p.id
+                WHERE a.service_date_id = $1
+                ORDER BY a.job_id, a.position
+                "#,
+            )
+            .bind(&source.id)
+            .bind(sunday)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+            for slot in slots {
+                if slot.unavailable {
+                    conflicts.push(ScheduleConflict {
+                        service_date: *sunday,
+                        job_id: slot.job_id.clone(),
+                        conflict_type: "not_copied".to_string(),
+                        message: format!(
+                            "Not copied from {}: the person is inactive, no longer in this job or unavailable",
+                            source.service_date
+                        ),
+                        affected_person_ids: slot.person_id.iter().cloned().collect(),
+                    });
+                }
+                let person_id = slot.person_id.filter(|_| !slot.unavailable);
+                copy_slot(
+                    &mut tx,
+                    &sd,
+                    &slot.job_id,
+                    person_id.as_ref(),
+                    slot.position,
+                    slot.position_name.as_ref(),
+                    &claims.username,
+                )
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            }
+        }
+
+        let assignments = sqlx::query_as::<_, AssignmentRow>(&format!(
+            "{} WHERE a.service_date_id = $1 ORDER BY j.name, a.position",
+            ASSIGNMENT_DETAILS_SELECT
+        ))
+        .bind(&sd.id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        service_dates.push(ServiceDateWithAssignments {
+            service_date: sd,
+            assignments: assignments
+                .into_iter()
+                .map(|row| row.into_details(true))
+                .collect(),
+        });
+    }
+
+    Ok(Json(ScheduleWithDates {
+        schedule,
+        service_dates,
+        conflicts,
+    }))
+}
+
+/// Insert one copied slot, keeping history and rotation in step as the generator does
+async fn copy_slot(
+    conn: &mut PgConnection,
+    sd: &ServiceDate,
+    job_id: &str,
+    person_id: Option<&String>,
+    position: Option<i32>,
+    position_name: Option<&String>,
+    changed_by: &str,
+) -> Result<(), String> {
+    let assignment = sqlx::query_as::<_, Assignment>(
+        r#"
+        INSERT INTO assignments
+            (id, service_date_id, job_id, person_id, position, position_name, suggested, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $4 IS NOT NULL, $7)
+        RETURNING *
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&sd.id)
+    .bind(job_id)
+    .bind(person_id)
+    .bind(position)
+    .bind(position_name)
+    .bind(changed_by)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let Some(person_id) = person_id else {
+        return Ok(());
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO assignment_history (id, person_id, job_id, service_date, year, week_number, position)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(person_id)
+    .bind(job_id)
+    .bind(sd.service_date)
+    .bind(sd.service_date.year())
+    .bind(sd.service_date.iso_week().week() as i32)
+    .bind(position)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if let Some(pos) = position {
+        rotation::mark_done(&mut *conn, person_id, job_id, sd.service_date, pos).await?;
+    }
+
+    let empty_slot = Assignment {
+        person_id: None,
+        ..assignment
+    };
+    assignment_events::record(
+        &mut *conn,
+        &empty_slot,
+        "copied",
+        Some(person_id),
+        Some(changed_by),
+    )
+    .await
+}
+
+// ============ Publish Schedule ============
+
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/publish",
+    tag = "schedules",
+    responses((status = 200, body = Schedule))
+)]
+pub async fn publish(
+    claims: Claims,
+    mut tx: Tx,
+    Path(id): Path<String>,
+) -> Result<Json<Schedule>, (StatusCode, String)> {
+    auth::require_admin(&claims, "publish schedules")?;
+
+    let current = lock_schedule(&mut tx, &id).await?;
+    if current.status == "ARCHIVED" {
+        return Err(archived_error());
+    }
+
+    let schedule = sqlx::query_as::<_, Schedule>(
+        r#"
+        UPDATE schedules
+        SET status = 'PUBLISHED', published_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(&id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if current.status != "PUBLISHED" {
+        schedule_events::record(
+            &mut *tx,
+            &id,
+            &current.status,
+            "PUBLISHED",
+            None,
+            &claims.username,
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+    reset_job_statuses(&mut tx, &id).await?;
+    // Republishing saves a new version too, so changes made since can be diffed
+    snapshots::take(&mut tx, &id, &claims.username)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    // Everyone assigned is told by the background worker once the publish commits
+    if current.status != "PUBLISHED" {
+        background_jobs::enqueue(
+            &mut *tx,
+            "notify_published",
+            &serde_json::json!({ "schedule_id": id }),
+            Some(&claims.username),
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+
+    Ok(Json(schedule))
+}
+
+/// The work of `notify_published`: tell everyone with dates in a newly published schedule
+/// which ones, on the `publish_channel` setting (blank = not told), and with a push to the
+/// browsers and phones they subscribed. A schedule taken back to draft before the job runs
+/// tells nobody. Returns how many people were told.
+pub async fn notify_published(conn: &mut PgConnection, schedule_id: &str) -> Result<usize, String> {
+    let schedule = sqlx::query_as::<_, Schedule>("SELECT * FROM schedules WHERE id = $1")
+        .bind(schedule_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+    let Some(schedule) = schedule.filter(|s| s.status == "PUBLISHED") else {
+        return Ok(0);
+    };
+
+    let channel = settings::get_string(&mut *conn, "publish_channel")
+        .await?
+        .map(|channel| channel.trim().to_string());
+
+    let rows = sqlx::query_as::<_, (String, NaiveDate)>(
+        r#"
+        SELECT DISTINCT a.person_id, sd.service_date
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        WHERE sd.schedule_id = $1 AND a.person_id IS NOT NULL AND sd.cancelled_at IS NULL
+        ORDER BY a.person_id, sd.service_date
+        "#,
+    )
+    .bind(&schedule.id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut dates: Vec<(String, Vec<NaiveDate>)> = Vec::new();
+    for (person_id, date) in rows {
+        match dates.last_mut() {
+            Some((last, person_dates)) if *last == person_id => person_dates.push(date),
+            _ => dates.push((person_id, vec![date])),
+        }
+    }
+
+    for (person_id, person_dates) in &dates {
+        let message = format!(
+            "Se publicó el horario \"{}\". Tus fechas: {}.",
+            schedule.name,
+            person_dates
+                .iter()
+                .map(|d| chat_bot::format_date(*d))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        if let Some(channel) = &channel {
+            notifications::send_or_warn(
+                &mut *conn,
+                channel,
+                person_id,
+                "schedule_published",
+                &message,
+            )
+            .await?;
+        }
+        if channel.as_deref() != Some("push") {
+            push::enqueue_extra(&mut *conn, person_id, "schedule_published", &message).await?;
+        }
+    }
+
+    Ok(dates.len())
+}
+
+// ============ Unpublish Schedule ============
+
+/// Take a published schedule back to draft for major changes. The reason is kept with the
+/// change; with `notify` everyone assigned in it is told the published dates may change.
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/unpublish",
+    tag = "schedules",
+    responses((status = 200, body = Schedule))
+)]
+pub async fn unpublish(
+    claims: Claims,
+    mut tx: Tx,
+    Path(id): Path<String>,
+    Json(input): Json<UnpublishScheduleRequest>,
+) -> Result<Json<Schedule>, (StatusCode, String)> {
+    auth::require_admin(&claims, "unpublish schedules")?;
+
+    let reason = input.reason.trim();
+    if reason.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "A reason is required to unpublish a schedule".to_string(),
+        ));
+    }
+
+    let current = lock_schedule(&mut tx, &id).await?;
+    if current.status != "PUBLISHED" {
+        return Err((
+            StatusCode::CONFLICT,
+            format!(
+                "Only published schedules can be unpublished, this one is {}",
+                current.status
+            ),
+        ));
+    }
+
+    let schedule = sqlx::query_as::<_, Schedule>(
+        r#"
+        UPDATE schedules
+        SET status = 'DRAFT', published_at = NULL, updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(&id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    reset_job_statuses(&mut tx, &id).await?;
+
+    let event_id = schedule_events::record(
+        &mut *tx,
+        &id,
+        "PUBLISHED",
+        "DRAFT",
+        Some(reason),
+        &claims.username,
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    if input.notify.unwrap_or(false) {
+        let person_ids: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT a.person_id
+            FROM assignments a
+            JOIN service_dates sd ON a.service_date_id = sd.id
+            WHERE sd.schedule_id = $1 AND a.person_id IS NOT NULL
+            "#,
+        )
+        .bind(&id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        sqlx::query("UPDATE schedule_status_events SET notified = $2 WHERE id = $1")
+            .bind(&event_id)
+            .bind(person_ids.len() as i32)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        let message = format!(
+            "El horario \"{}\" se retiró para hacer cambios. Tus fechas pueden cambiar; te avisaremos cuando se publique de nuevo.",
+            schedule.name
+        );
+        for person_id in &person_ids {
+            notifications::send_or_warn(
+                &mut tx,
+                "in_app",
+                person_id,
+                "schedule_retracted",
+                &message,
+            )
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        }
+    }
+
+    Ok(Json(schedule))
+}
+
+/// Status changes of a schedule, newest first
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/status-history",
+    tag = "schedules",
+    responses((status = 200, body = Vec<ScheduleStatusEvent>))
+)]
+pub async fn get_status_history(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<ScheduleStatusEvent>>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can see a schedule's history".to_string(),
+        ));
+    }
+
+    let events = sqlx::query_as::<_, ScheduleStatusEvent>(
+        r#"
+        SELECT id, from_status, to_status, reason, changed_by, notified, created_at
+        FROM schedule_status_events
+        WHERE schedule_id = $1
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(&id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(events))
+}
+
+/// Load a schedule and hold its row until the request's transaction ends
+async fn lock_schedule(
+    conn: &mut PgConnection,
+    id: &str,
+) -> Result<Schedule, (StatusCode, String)> {
+    sqlx::query_as::<_, Schedule>("SELECT * FROM schedules WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))
+}
+
+// ============ Per-job Publishing ============
+
+/// Publish one job of a draft schedule, e.g. Lectores while Monaguillos is still being
+/// worked on. Its assignments show up in my-assignments right away.
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/jobs/{job_id}/publish",
+    tag = "schedules",
+    responses((status = 200, body = ScheduleJobStatus))
+)]
+pub async fn publish_job(
+    claims: Claims,
+    tx: Tx,
+    Path((id, job_id)): Path<(String, String)>,
+) -> Result<Json<ScheduleJobStatus>, (StatusCode, String)> {
+    set_job_status(claims, tx, id, job_id, "PUBLISHED").await
+}
+
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/jobs/{job_id}/unpublish",
+    tag = "schedules",
+    responses((status = 200, body = ScheduleJobStatus))
+)]
+pub async fn unpublish_job(
+    claims: Claims,
+    tx: Tx,
+    Path((id, job_id)): Path<(String, String)>,
+) -> Result<Json<ScheduleJobStatus>, (StatusCode, String)> {
+    set_job_status(claims, tx, id, job_id, "DRAFT").await
+}
+
+async fn set_job_status(
+    claims: Claims,
+    mut tx: Tx,
+    id: String,
+    job_id: String,
+    status: &str,
+) -> Result<Json<ScheduleJobStatus>, (StatusCode, String)> {
+    if !claims.is_admin() && !claims.is_coordinator() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins and coordinators can publish jobs".to_string(),
+        ));
+    }
+    auth::require_job_scope(&claims, &job_id)?;
+
+    let schedule_status: String =
+        sqlx::query_scalar("SELECT status FROM schedules WHERE id = $1 FOR UPDATE")
+            .bind(&id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))?;
+    match schedule_status.as_str() {
+        "DRAFT" => {}
+        "ARCHIVED" => return Err(archived_error()),
+        _ => {
+            return Err((
+                StatusCode::CONFLICT,
+                "The whole schedule is published, set it back to draft to publish jobs one by one"
+                    .to_string(),
+            ))
+        }
+    }
+
+    let status = sqlx::query_as::<_, ScheduleJobStatus>(
+        r#"
+        WITH saved AS (
+            INSERT INTO schedule_job_status (schedule_id, job_id, status, published_at)
+            VALUES ($1, $2, $3, CASE WHEN $3 = 'PUBLISHED' THEN NOW() END)
+            ON CONFLICT (schedule_id, job_id) DO UPDATE SET
+                status = EXCLUDED.status,
+                published_at = CASE
+                    WHEN schedule_job_status.status = EXCLUDED.status
+                        THEN schedule_job_status.published_at
+                    ELSE EXCLUDED.published_at
+                END
+            RETURNING *
+        )
+        SELECT
+            j.id as job_id,
+            j.name as job_name,
+            j.color as job_color,
+            saved.status,
+            saved.published_at,
+            (
+                SELECT COUNT(*) FROM assignments a
+                JOIN service_dates sd ON a.service_date_id = sd.id
+                WHERE sd.schedule_id = $1 AND a.job_id = $2
+            ) as assignments
+        FROM saved
+        JOIN jobs j ON j.id = saved.job_id
+        "#,
+    )
+    .bind(&id)
+    .bind(&job_id)
+    .bind(status)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db) if db.is_foreign_key_violation() => {
+            (StatusCode::NOT_FOUND, "Job not found".to_string())
+        }
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    })?;
+
+    Ok(Json(status))
+}
+
+/// A schedule-wide status change overrides whatever was published job by job
+async fn reset_job_statuses(
+    conn: &mut PgConnection,
+    schedule_id: &str,
+) -> Result<(), (StatusCode, String)> {
+    sqlx::query("DELETE FROM schedule_job_status WHERE schedule_id = $1")
+        .bind(schedule_id)
+        .execute(conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(())
+}
+
+// ============ Archive Schedule ============
+
+/// Put a finished schedule away: it leaves the default listing and can no longer be edited.
+/// PATCH it back to DRAFT to work on it again.
+#[utoipa::path(
+    post,
+    path = "/schedules/{id}/archive",
+    tag = "schedules",
+    responses((status = 200, body = Schedule))
+)]
+pub async fn archive(
+    claims: Claims,
+    mut tx: Tx,
+    Path(id): Path<String>,
+) -> Result<Json<Schedule>, (StatusCode, String)> {
+    auth::require_admin(&claims, "archive schedules")?;
+
+    let current = lock_schedule(&mut tx, &id).await?;
+    let schedule = sqlx::query_as::<_, Schedule>(
+        r#"
+        UPDATE schedules
+        SET status = 'ARCHIVED', updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(&id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if current.status != "ARCHIVED" {
+        schedule_events::record(
+            &mut *tx,
+            &id,
+            &current.status,
+            "ARCHIVED",
+            None,
+            &claims.username,
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+
+    Ok(Json(schedule))
+}
+
+// ============ Update Schedule Metadata ============
+
+/// Status changes PATCH allows: a draft can be published or archived, a published schedule
+/// taken back to draft or archived, and an archived one reopened as a draft
+fn check_status_transition(from: &str, to: &str) -> Result<(), String> {
+    match (from, to) {
+        (from, to) if from == to => Ok(()),
+        ("DRAFT", "PUBLISHED" | "ARCHIVED")
+        | ("PUBLISHED", "DRAFT" | "ARCHIVED")
+        | ("ARCHIVED", "DRAFT") => Ok(()),
+        (from, to) if ["DRAFT", "PUBLISHED", "ARCHIVED"].contains(&to) => {
+            Err(format!("A {} schedule cannot become {}", from, to))
+        }
+        (_, to) => Err(format!(
+            "Invalid status: {}. Allowed: DRAFT, PUBLISHED, ARCHIVED",
+            to
+        )),
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/schedules/{id}",
+    tag = "schedules",
+    responses((status = 200, body = Schedule))
+)]
+pub async fn update(
+    claims: Claims,
+    mut tx: Tx,
+    Path(id): Path<String>,
+    Json(input): Json<UpdateScheduleRequest>,
+) -> Result<Json<Schedule>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can edit schedules".to_string(),
+        ));
+    }
+
+    let schedule =
+        sqlx::query_as::<_, Schedule>("SELECT * FROM schedules WHERE id = $1 FOR UPDATE")
+            .bind(&id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))?;
+
+    // An archived schedule only changes by being restored to draft
+    let restoring = input
+        .status
+        .as_deref()
+        .is_some_and(|s| s.trim().eq_ignore_ascii_case("DRAFT"));
+    if schedule.status == "ARCHIVED"
+        && !restoring
+        && (input.name.is_some() || input.notes.is_some())
+    {
+        return Err(archived_error());
+    }
+
+    let name = match input.name.as_deref().map(str::trim) {
+        Some("") => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Schedule name cannot be empty".to_string(),
+            ))
+        }
+        Some(name) if name.chars().count() > 255 => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Schedule name is limited to 255 characters".to_string(),
+            ))
+        }
+        Some(name) => name.to_string(),
+        None => schedule.name,
+    };
+    let notes = match input.notes.as_deref().map(str::trim) {
+        Some("") => None,
+        Some(notes) => Some(notes.to_string()),
+        None => schedule.notes,
+    };
+    let status = match input.status.as_deref() {
+        Some(status) => {
+            let status = status.trim().to_uppercase();
+            check_status_transition(&schedule.status, &status)
+                .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+            status
+        }
+        None => schedule.status.clone(),
+    };
+
+    // Restoring an archived schedule takes its month back, unless another one has it since
+    if schedule.status == "ARCHIVED" && status != "ARCHIVED" {
+        let taken = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM schedules
+                WHERE year = $1 AND month = $2 AND id <> $3
+                  AND status NOT IN ('ARCHIVED', 'EXPIRED')
+            )
+            "#,
+        )
+        .bind(schedule.year)
+        .bind(schedule.month)
+        .bind(&id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        if taken {
+            return Err(month_taken_error(schedule.year, schedule.month));
+        }
+    }
+
+    // published_at follows the status: set when it becomes published, cleared back in draft
+    let updated = sqlx::query_as::<_, Schedule>(
+        r#"
+        UPDATE schedules
+        SET name = $2,
+            notes = $3,
+            status = $4,
+            published_at = CASE
+                WHEN $4 = 'PUBLISHED' AND status <> 'PUBLISHED' THEN NOW()
+                WHEN $4 = 'DRAFT' THEN NULL
+                ELSE published_at
+            END,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(&id)
+    .bind(&name)
+    .bind(&notes)
+    .bind(&status)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if status != schedule.status {
+        reset_job_statuses(&mut tx, &id).await?;
+        schedule_events::record(
+            &mut *tx,
+            &id,
+            &schedule.status,
+            &status,
+            None,
+            &claims.username,
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        if status == "PUBLISHED" {
+            snapshots::take(&mut tx, &id, &claims.username)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        }
+    }
+
+    Ok(Json(updated))
+}
+
+// ============ Delete Schedule ============
+
+#[utoipa::path(
+    delete,
+    path = "/schedules/{id}",
+    tag = "schedules",
+    responses((status = 204))
+)]
+pub async fn delete(
+    claims: Claims,
+    mut tx: Tx,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    auth::require_admin(&claims, "delete schedules")?;
+
+    // Forget rotation state of everyone in this schedule; it is derived again from what is left
+    sqlx::query(
+        r#"
+        DELETE FROM position_rotation
+        WHERE (person_id, job_id) IN (
+            SELECT a.person_id, a.job_id
+            FROM assignments a
+            JOIN service_dates sd ON a.service_date_id = sd.id
+            WHERE sd.schedule_id = $1 AND a.person_id IS NOT NULL
+        )
+        "#,
+    )
+    .bind(&id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    // Delete assignment history for this schedule's dates
+    sqlx::query(
+        r#"
+        DELETE FROM assignment_history
+        WHERE service_date IN (
+            SELECT service_date FROM service_dates WHERE schedule_id = $1
+        )
+        "#,
+    )
+    .bind(&id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    // Delete schedule (cascades to service_dates and assignments)
+    let result = sqlx::query("DELETE FROM schedules WHERE id = $1")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "Schedule not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_months_cross_the_year_boundary() {
+        assert_eq!(
+            batch_months((2025, 11), (2026, 2)).unwrap(),
+            vec![(2025, 11), (2025, 12), (2026, 1), (2026, 2)]
+        );
+    }
+
+    #[test]
+    fn batch_months_reject_bad_ranges() {
+        assert!(batch_months((2026, 3), (2026, 2)).is_err());
+        assert!(batch_months((2026, 0), (2026, 2)).is_err());
+        assert!(batch_months((2026, 1), (2028, 1)).is_err());
+        assert_eq!(
+            batch_months((2026, 1), (2027, 12)).unwrap().len(),
+            MAX_BATCH_MONTHS
+        );
+    }
+
+    #[test]
+    fn status_transitions_follow_the_schedule_lifecycle() {
+        assert!(check_status_transition("DRAFT", "PUBLISHED").is_ok());
+        assert!(check_status_transition("PUBLISHED", "DRAFT").is_ok());
+        assert!(check_status_transition("PUBLISHED", "ARCHIVED").is_ok());
+        assert!(check_status_transition("ARCHIVED", "DRAFT").is_ok());
+        assert!(check_status_transition("ARCHIVED", "ARCHIVED").is_ok());
+        assert!(check_status_transition("ARCHIVED", "PUBLISHED").is_err());
+        assert!(check_status_transition("DRAFT", "DONE").is_err());
+    }
+}
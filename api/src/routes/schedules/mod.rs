@@ -0,0 +1,165 @@
+use axum::http::StatusCode;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{FromRow, PgConnection};
+
+use crate::models::{Assignment, AssignmentWithDetails};
+
+mod assignments;
+mod export;
+mod generation;
+mod queries;
+
+pub use assignments::*;
+pub use export::*;
+pub use generation::*;
+pub use queries::*;
+
+/// Assignments with their person and job, read into `AssignmentRow`. Callers append the
+/// WHERE and ORDER BY clauses.
+const ASSIGNMENT_DETAILS_SELECT: &str = r#"
+    SELECT
+        a.id, a.service_date_id, a.job_id, a.person_id, a.position, a.position_name, a.manual_override, a.suggested,
+        a.notes, a.override_reason, a.created_by, a.updated_by, a.updated_at,
+        p.first_name || ' ' || p.last_name as person_name,
+        j.name as job_name, j.color as job_color,
+        sd.service_date, sd.schedule_id, s.year as schedule_year, s.month as schedule_month,
+        p.notes as person_notes, p.scheduling_notes
+    FROM assignments a
+    LEFT JOIN people p ON a.person_id = p.id
+    JOIN jobs j ON a.job_id = j.id
+    JOIN service_dates sd ON a.service_date_id = sd.id
+    JOIN schedules s ON sd.schedule_id = s.id
+"#;
+
+/// Longest note accepted; it has to fit next to a name in exports and on a phone screen
+const MAX_NOTE_CHARS: usize = 500;
+
+/// Trim a note (on an assignment or a service date), turning a blank one into no note
+pub(crate) fn normalize_note(notes: Option<&str>) -> Result<Option<String>, (StatusCode, String)> {
+    let Some(note) = notes.map(str::trim).filter(|n| !n.is_empty()) else {
+        return Ok(None);
+    };
+    if note.chars().count() > MAX_NOTE_CHARS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Notes cannot be longer than {} characters", MAX_NOTE_CHARS),
+        ));
+    }
+    Ok(Some(note.to_string()))
+}
+
+/// Archived schedules are read-only until restored to DRAFT
+pub(crate) fn archived_error() -> (StatusCode, String) {
+    (
+        StatusCode::CONFLICT,
+        "Schedule is archived, restore it to draft before editing".to_string(),
+    )
+}
+
+/// A month holds one active schedule; also what a create that lost the race on the unique
+/// index over active (year, month) answers
+fn month_taken_error(year: i32, month: i32) -> (StatusCode, String) {
+    (
+        StatusCode::CONFLICT,
+        format!("Schedule for {}/{} already exists", month, year),
+    )
+}
+
+#[derive(FromRow)]
+struct AssignmentRow {
+    id: String,
+    service_date_id: String,
+    job_id: String,
+    person_id: Option<String>,
+    position: Option<i32>,
+    position_name: Option<String>,
+    manual_override: Option<bool>,
+    suggested: bool,
+    notes: Option<String>,
+    override_reason: Option<String>,
+    created_by: Option<String>,
+    updated_by: Option<String>,
+    updated_at: Option<DateTime<Utc>>,
+    person_name: Option<String>,
+    job_name: String,
+    job_color: Option<String>,
+    service_date: NaiveDate,
+    schedule_id: String,
+    schedule_year: i32,
+    schedule_month: i32,
+    person_notes: Option<String>,
+    scheduling_notes: Option<String>,
+}
+
+impl AssignmentRow {
+    /// Notes about the person are only shown to admins
+    fn into_details(self, is_admin: bool) -> AssignmentWithDetails {
+        AssignmentWithDetails {
+            assignment: Assignment {
+                id: self.id,
+                service_date_id: self.service_date_id,
+                job_id: self.job_id,
+                person_id: self.person_id,
+                position: self.position,
+                position_name: self.position_name,
+                manual_override: self.manual_override,
+                suggested: self.suggested,
+                notes: self.notes,
+                override_reason: self.override_reason,
+                created_by: self.created_by,
+                updated_by: self.updated_by,
+                created_at: None,
+                updated_at: self.updated_at,
+            },
+            person_name: self.person_name.unwrap_or_default(),
+            job_name: self.job_name,
+            job_color: self.job_color,
+            service_date: self.service_date,
+            schedule_id: self.schedule_id,
+            schedule_year: self.schedule_year,
+            schedule_month: self.schedule_month,
+            person_notes: self.person_notes.filter(|_| is_admin),
+            scheduling_notes: self.scheduling_notes.filter(|_| is_admin),
+        }
+    }
+}
+
+/// One assignment with its details, as the editing endpoints return it
+async fn fetch_assignment_details(
+    conn: &mut PgConnection,
+    id: &str,
+    is_admin: bool,
+) -> Result<AssignmentWithDetails, (StatusCode, String)> {
+    let row = sqlx::query_as::<_, AssignmentRow>(&format!(
+        "{} WHERE a.id = $1",
+        ASSIGNMENT_DETAILS_SELECT
+    ))
+    .bind(id)
+    .fetch_one(conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(row.into_details(is_admin))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_notes_are_removed() {
+        assert_eq!(normalize_note(None).unwrap(), None);
+        assert_eq!(normalize_note(Some("   ")).unwrap(), None);
+        assert_eq!(
+            normalize_note(Some("  Llegar 20 min antes ")).unwrap(),
+            Some("Llegar 20 min antes".to_string())
+        );
+    }
+
+    #[test]
+    fn long_notes_are_rejected() {
+        let note = "á".repeat(MAX_NOTE_CHARS);
+        assert!(normalize_note(Some(&note)).is_ok());
+        assert!(normalize_note(Some(&format!("{}a", note))).is_err());
+    }
+}
@@ -0,0 +1,1596 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use sqlx::{FromRow, PgPool};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::auth::{self, Claims};
+use crate::db::filters::Filter;
+use crate::models::{
+    AbsenceImpact, AbsenceSimulation, AssignmentWithDetails, DoubleBooking, DoubleBookingSlot,
+    EmptySlotSuggestions, Schedule, ScheduleConflict, ScheduleDiff, ScheduleJobStatus,
+    ScheduleSnapshot, ScheduleWithDates, ServiceDate, ServiceDateWithAssignments, SlotCandidate,
+    JUNIOR_JOB_ID, SENIOR_JOB_ID,
+};
+use crate::routes::settings;
+use crate::services::{generator, snapshots, visibility};
+
+use super::{AssignmentRow, ASSIGNMENT_DETAILS_SELECT};
+
+// ============ List Schedules ============
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListSchedulesQuery {
+    /// Archived schedules are left out unless this is true or `status=ARCHIVED` is asked for
+    pub include_archived: Option<bool>,
+    pub year: Option<i32>,
+    pub status: Option<String>,
+    /// e.g. `-year,-month` (default) or `name`
+    pub sort: Option<String>,
+}
+
+const SCHEDULE_SORTS: &[(&str, &str)] = &[
+    ("year", "year"),
+    ("month", "month"),
+    ("name", "name"),
+    ("created_at", "created_at"),
+];
+
+#[utoipa::path(
+    get,
+    path = "/schedules",
+    params(ListSchedulesQuery),
+    tag = "schedules",
+    responses((status = 200, body = Vec<Schedule>))
+)]
+pub async fn get_all(
+    State(pool): State<PgPool>,
+    Query(query): Query<ListSchedulesQuery>,
+) -> Result<Json<Vec<Schedule>>, (StatusCode, String)> {
+    let status = query.status.as_deref().map(str::to_uppercase);
+    let mut filter = Filter::new("SELECT * FROM schedules");
+    filter.eq("year", query.year).eq("status", status.as_ref());
+    if status.is_none() && !query.include_archived.unwrap_or(false) {
+        filter.raw("status <> 'ARCHIVED'");
+    }
+    filter
+        .order_by(
+            query.sort.as_deref(),
+            SCHEDULE_SORTS,
+            "year DESC, month DESC",
+        )
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let schedules = filter
+        .into_builder()
+        .build_query_as::<Schedule>()
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(schedules))
+}
+
+// ============ Get Schedule with Details ============
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ScheduleDetailQuery {
+    /// Only this job's assignments, for a per-job view of the schedule
+    pub job_id: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}",
+    params(ScheduleDetailQuery),
+    tag = "schedules",
+    responses((status = 200, body = ScheduleWithDates))
+)]
+pub async fn get_by_id(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+    Query(query): Query<ScheduleDetailQuery>,
+) -> Result<Json<ScheduleWithDates>, (StatusCode, String)> {
+    let schedule = sqlx::query_as::<_, Schedule>("SELECT * FROM schedules WHERE id = $1")
+        .bind(&id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))?;
+
+    // Servidores only see dates inside the visibility window; admins see everything
+    let horizon = if claims.is_admin() {
+        None
+    } else {
+        visibility::horizon(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+    };
+
+    let service_dates = sqlx::query_as::<_, ServiceDate>(
+        r#"
+        SELECT * FROM service_dates
+        WHERE schedule_id = $1 AND ($2::date IS NULL OR service_date <= $2)
+        ORDER BY service_date
+        "#,
+    )
+    .bind(&id)
+    .bind(horizon)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut dates_with_assignments = Vec::new();
+
+    for sd in service_dates {
+        let assignments = sqlx::query_as::<_, AssignmentRow>(&format!(
+            "{} WHERE a.service_date_id = $1 AND ($2::varchar IS NULL OR a.job_id = $2) \
+             ORDER BY j.name, a.position",
+            ASSIGNMENT_DETAILS_SELECT
+        ))
+        .bind(&sd.id)
+        .bind(&query.job_id)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        let assignments_with_details: Vec<AssignmentWithDetails> = assignments
+            .into_iter()
+            .map(|row| row.into_details(claims.is_admin()))
+            .collect();
+
+        dates_with_assignments.push(ServiceDateWithAssignments {
+            service_date: sd,
+            assignments: assignments_with_details,
+        });
+    }
+
+    let max_jobs = settings::get_i64(&pool, "max_jobs_per_person_per_month", 0)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let conflicts = max_jobs_conflicts(max_jobs, &dates_with_assignments);
+
+    Ok(Json(ScheduleWithDates {
+        schedule,
+        service_dates: dates_with_assignments,
+        conflicts,
+    }))
+}
+
+// ============ Published Versions ============
+
+/// Versions saved each time the schedule was published, newest first
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/snapshots",
+    tag = "schedules",
+    responses((status = 200, body = Vec<ScheduleSnapshot>))
+)]
+pub async fn get_snapshots(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<ScheduleSnapshot>>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can see schedule versions".to_string(),
+        ));
+    }
+
+    let snapshots = sqlx::query_as::<_, ScheduleSnapshot>(
+        r#"
+        SELECT s.version, s.created_by, s.created_at, COUNT(sa.person_id) as assignments
+        FROM schedule_snapshots s
+        LEFT JOIN schedule_snapshot_assignments sa ON sa.snapshot_id = s.id
+        WHERE s.schedule_id = $1
+        GROUP BY s.id, s.version, s.created_by, s.created_at
+        ORDER BY s.version DESC
+        "#,
+    )
+    .bind(&id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(snapshots))
+}
+
+/// One change to a slot of the schedule, for the audit trail
+#[derive(Debug, serde::Serialize, FromRow, utoipa::ToSchema)]
+pub struct ScheduleAuditEntry {
+    pub id: String,
+    pub service_date: NaiveDate,
+    pub assignment_id: String,
+    pub job_id: String,
+    pub job_name: String,
+    pub position: Option<i32>,
+    pub position_name: Option<String>,
+    pub action: String,
+    pub person_id: Option<String>,
+    pub person_name: Option<String>,
+    pub previous_person_id: Option<String>,
+    pub previous_person_name: Option<String>,
+    pub changed_by: Option<String>, // None = generator
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Who changed which slots of the schedule and when, newest first. Coordinators only see
+/// the jobs in their scope.
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/audit",
+    tag = "schedules",
+    responses((status = 200, body = Vec<ScheduleAuditEntry>))
+)]
+pub async fn get_audit(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<ScheduleAuditEntry>>, (StatusCode, String)> {
+    if !claims.is_admin() && !claims.is_coordinator() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins and coordinators can see the audit trail".to_string(),
+        ));
+    }
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM schedules WHERE id = $1)")
+        .bind(&id)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, "Schedule not found".to_string()));
+    }
+
+    let entries = sqlx::query_as::<_, ScheduleAuditEntry>(
+        r#"
+        SELECT
+            e.id, sd.service_date, e.assignment_id, e.job_id, j.name as job_name, e.position,
+            jp.name as position_name,
+            e.action,
+            e.person_id, p.first_name || ' ' || p.last_name as person_name,
+            e.previous_person_id, pp.first_name || ' ' || pp.last_name as previous_person_name,
+            e.changed_by, e.reason, e.created_at
+        FROM assignment_events e
+        JOIN service_dates sd ON e.service_date_id = sd.id
+        JOIN jobs j ON e.job_id = j.id
+        LEFT JOIN job_positions jp ON jp.job_id = e.job_id AND jp.position_number = e.position
+        LEFT JOIN people p ON e.person_id = p.id
+        LEFT JOIN people pp ON e.previous_person_id = pp.id
+        WHERE sd.schedule_id = $1
+        ORDER BY e.created_at DESC, e.seq DESC
+        "#,
+    )
+    .bind(&id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(
+        entries
+            .into_iter()
+            .filter(|e| claims.in_scope(&e.job_id))
+            .collect(),
+    ))
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DiffQuery {
+    /// Version to compare from; defaults to the one before `to`
+    pub from: Option<i32>,
+    /// Version to compare to, or "current" for the schedule as it is now; defaults to the
+    /// latest version
+    pub to: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/diff",
+    params(DiffQuery),
+    tag = "schedules",
+    responses((status = 200, body = ScheduleDiff))
+)]
+pub async fn get_diff(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+    Query(query): Query<DiffQuery>,
+) -> Result<Json<ScheduleDiff>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can compare schedule versions".to_string(),
+        ));
+    }
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let latest: Option<i32> =
+        sqlx::query_scalar("SELECT MAX(version) FROM schedule_snapshots WHERE schedule_id = $1")
+            .bind(&id)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let Some(latest) = latest else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            "Schedule has not been published yet".to_string(),
+        ));
+    };
+
+    let to_version = match query.to.as_deref().map(str::trim) {
+        Some("current") => None,
+        Some(v) => Some(v.parse::<i32>().map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid to: {}. Use a version number or current", v),
+            )
+        })?),
+        None => Some(latest),
+    };
+    let from_version = match (query.from, to_version) {
+        (Some(from), _) => from,
+        (None, Some(to)) => to - 1,
+        (None, None) => latest,
+    };
+
+    let missing = |v: i32| (StatusCode::NOT_FOUND, format!("Version {} not found", v));
+    let from = snapshots::load(&mut conn, &id, from_version)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or_else(|| missing(from_version))?;
+    let to = match to_version {
+        Some(v) => snapshots::load(&mut conn, &id, v)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+            .ok_or_else(|| missing(v))?,
+        None => snapshots::current(&mut conn, &id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?,
+    };
+
+    let (added, removed, changed) = snapshots::diff(&from, &to);
+    Ok(Json(ScheduleDiff {
+        from_version,
+        to_version,
+        added,
+        removed,
+        changed,
+    }))
+}
+
+// ============ Per-job Publishing State ============
+
+/// Each job that has slots in the schedule with its effective publishing status.
+/// Coordinators only get the jobs in their scope.
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/jobs",
+    tag = "schedules",
+    responses((status = 200, body = Vec<ScheduleJobStatus>))
+)]
+pub async fn get_job_statuses(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<ScheduleJobStatus>>, (StatusCode, String)> {
+    let statuses = sqlx::query_as::<_, ScheduleJobStatus>(
+        r#"
+        SELECT
+            j.id as job_id,
+            j.name as job_name,
+            j.color as job_color,
+            CASE WHEN s.status = 'DRAFT' THEN COALESCE(sj.status, 'DRAFT') ELSE s.status END
+                as status,
+            CASE WHEN s.status = 'DRAFT' THEN sj.published_at ELSE s.published_at END
+                as published_at,
+            COUNT(a.id) as assignments
+        FROM schedules s
+        JOIN service_dates sd ON sd.schedule_id = s.id
+        JOIN assignments a ON a.service_date_id = sd.id
+        JOIN jobs j ON a.job_id = j.id
+        LEFT JOIN schedule_job_status sj ON sj.schedule_id = s.id AND sj.job_id = j.id
+        WHERE s.id = $1
+        GROUP BY j.id, j.name, j.color, s.status, s.published_at, sj.status, sj.published_at
+        ORDER BY j.name
+        "#,
+    )
+    .bind(&id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(
+        statuses
+            .into_iter()
+            .filter(|s| claims.in_scope(&s.job_id))
+            .collect(),
+    ))
+}
+
+/// People serving in more different jobs within a month than max_jobs_per_person_per_month
+/// allows. The generator never does this, so these come from manual edits.
+pub(super) fn max_jobs_conflicts(
+    max_jobs: i64,
+    service_dates: &[ServiceDateWithAssignments],
+) -> Vec<ScheduleConflict> {
+    let mut conflicts = Vec::new();
+    if max_jobs <= 0 {
+        return conflicts;
+    }
+
+    // (person_id, year, month) -> distinct job_ids in order of first assignment
+    let mut jobs_by_month: HashMap<(String, i32, u32), Vec<String>> = HashMap::new();
+    for sd in service_dates {
+        let date = sd.service_date.service_date;
+        for a in &sd.assignments {
+            let Some(person_id) = &a.assignment.person_id else {
+                continue;
+            };
+            let jobs = jobs_by_month
+                .entry((person_id.clone(), date.year(), date.month()))
+                .or_default();
+            if jobs.contains(&a.assignment.job_id) {
+                continue;
+            }
+            jobs.push(a.assignment.job_id.clone());
+            if jobs.len() as i64 > max_jobs {
+                conflicts.push(ScheduleConflict {
+                    service_date: date,
+                    job_id: a.assignment.job_id.clone(),
+                    conflict_type: "max_jobs_exceeded".to_string(),
+                    message: format!(
+                        "{} serves in {} different jobs this month (limit {})",
+                        a.person_name,
+                        jobs.len(),
+                        max_jobs
+                    ),
+                    affected_person_ids: vec![person_id.clone()],
+                });
+            }
+        }
+    }
+    conflicts
+}
+
+// ============ Get My Assignments (for Servidores) ============
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct MyAssignment {
+    pub service_date: NaiveDate,
+    pub job_id: String,
+    pub job_name: String,
+    pub job_color: String,
+    pub position: Option<i32>,
+    pub position_name: Option<String>,
+    pub notes: Option<String>,
+    /// The service date's note, shared by everyone serving that day
+    pub service_date_notes: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/my-assignments/{person_id}",
+    tag = "schedules",
+    responses((status = 200, body = Vec<MyAssignment>))
+)]
+pub async fn get_my_assignments(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+) -> Result<Json<Vec<MyAssignment>>, (StatusCode, String)> {
+    // Servidores only see assignments inside the visibility window; admins see everything
+    let horizon = if claims.is_admin() {
+        None
+    } else {
+        visibility::horizon(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+    };
+
+    Ok(Json(
+        published_assignments(&pool, &person_id, horizon).await?,
+    ))
+}
+
+/// The person's published assignments up to `horizon` (if any): upcoming dates first, soonest
+/// first, then past dates, most recent first. Shared with the chat bot's "mis fechas".
+pub async fn published_assignments(
+    pool: &PgPool,
+    person_id: &str,
+    horizon: Option<NaiveDate>,
+) -> Result<Vec<MyAssignment>, (StatusCode, String)> {
+    // Get all assignments for this person from published schedules
+    // Order by: future dates first (ascending), then past dates (descending)
+    let rows = sqlx::query_as::<
+        _,
+        (
+            NaiveDate,
+            String,
+            String,
+            Option<String>,
+            Option<i32>,
+            Option<String>,
+            Option<String>,
+            Option<String>,
+        ),
+    >(
+        r#"
+        SELECT
+            sd.service_date,
+            j.id as job_id,
+            j.name as job_name,
+            j.color as job_color,
+            a.position,
+            a.position_name,
+            a.notes,
+            sd.notes as service_date_notes
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN schedules s ON sd.schedule_id = s.id
+        JOIN jobs j ON a.job_id = j.id
+        WHERE a.person_id = $1
+          AND job_is_published(s.id, a.job_id)
+          AND ($2::date IS NULL OR sd.service_date <= $2)
+        ORDER BY
+            CASE WHEN sd.service_date >= CURRENT_DATE THEN 0 ELSE 1 END,
+            CASE WHEN sd.service_date >= CURRENT_DATE THEN sd.service_date END ASC,
+            CASE WHEN sd.service_date < CURRENT_DATE THEN sd.service_date END DESC
+        "#,
+    )
+    .bind(person_id)
+    .bind(horizon)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let assignments: Vec<MyAssignment> = rows
+        .into_iter()
+        .map(
+            |(
+                service_date,
+                job_id,
+                job_name,
+                job_color,
+                position,
+                position_name,
+                notes,
+                service_date_notes,
+            )| MyAssignment {
+                service_date,
+                job_id,
+                job_name,
+                job_color: job_color.unwrap_or_else(|| "#3B82F6".to_string()),
+                position,
+                position_name,
+                notes,
+                service_date_notes,
+            },
+        )
+        .collect();
+
+    Ok(assignments)
+}
+
+// ============ Person's Assignments Across Schedules ============
+
+/// One of a person's slots in any schedule, draft or published
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, FromRow, utoipa::ToSchema)]
+pub struct PersonScheduleAssignment {
+    pub assignment_id: String,
+    pub schedule_id: String,
+    pub schedule_name: String,
+    pub schedule_status: String,
+    /// Whether the person has been told: their job is published in that schedule
+    pub job_published: bool,
+    pub service_date: NaiveDate,
+    pub job_id: String,
+    pub job_name: String,
+    pub position: Option<i32>,
+    pub position_name: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PersonAssignmentsQuery {
+    /// Past dates are left out unless this is true
+    #[serde(default)]
+    pub include_past: bool,
+}
+
+/// A person's assignments in every non-archived schedule, drafts included, so the load can
+/// be checked before publishing. Coordinators only see the jobs in their scope.
+#[utoipa::path(
+    get,
+    path = "/people/{id}/assignments",
+    params(PersonAssignmentsQuery),
+    tag = "schedules",
+    responses((status = 200, body = Vec<PersonScheduleAssignment>))
+)]
+pub async fn get_person_assignments(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+    Query(query): Query<PersonAssignmentsQuery>,
+) -> Result<Json<Vec<PersonScheduleAssignment>>, (StatusCode, String)> {
+    if !claims.is_admin() && !claims.is_coordinator() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins and coordinators can see a person's draft assignments".to_string(),
+        ));
+    }
+    auth::require_person_scope(&pool, &claims, &person_id).await?;
+
+    let rows = sqlx::query_as::<_, PersonScheduleAssignment>(
+        r#"
+        SELECT
+            a.id as assignment_id,
+            s.id as schedule_id,
+            s.name as schedule_name,
+            s.status as schedule_status,
+            job_is_published(s.id, a.job_id) as job_published,
+            sd.service_date,
+            a.job_id,
+            j.name as job_name,
+            a.position,
+            a.position_name,
+            a.notes
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN schedules s ON sd.schedule_id = s.id
+        JOIN jobs j ON a.job_id = j.id
+        WHERE a.person_id = $1
+          AND s.status <> 'ARCHIVED'
+          AND ($2 OR sd.service_date >= CURRENT_DATE)
+        ORDER BY sd.service_date, j.name, a.position
+        "#,
+    )
+    .bind(&person_id)
+    .bind(query.include_past)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(
+        rows.into_iter()
+            .filter(|row| claims.in_scope(&row.job_id))
+            .collect(),
+    ))
+}
+
+// ============ Cross-Schedule Double Bookings ============
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DoubleBookingsQuery {
+    /// Earliest date to check; defaults to today
+    pub from: Option<NaiveDate>,
+}
+
+#[derive(FromRow)]
+struct BookedSlotRow {
+    person_id: String,
+    person_name: String,
+    service_date: NaiveDate,
+    #[sqlx(flatten)]
+    slot: DoubleBookingSlot,
+}
+
+/// Group booked slots (sorted by date and person) into one entry per person and day
+fn group_double_bookings(rows: Vec<BookedSlotRow>) -> Vec<DoubleBooking> {
+    let mut bookings: Vec<DoubleBooking> = Vec::new();
+    for row in rows {
+        match bookings.last_mut() {
+            Some(last)
+                if last.person_id == row.person_id && last.service_date == row.service_date =>
+            {
+                last.slots.push(row.slot)
+            }
+            _ => bookings.push(DoubleBooking {
+                person_id: row.person_id,
+                person_name: row.person_name,
+                service_date: row.service_date,
+                slots: vec![row.slot],
+            }),
+        }
+    }
+    bookings
+}
+
+/// People assigned on two service dates that fall on the same day, across all schedules.
+/// Coordinators only get the bookings that involve one of their jobs.
+#[utoipa::path(
+    get,
+    path = "/schedules/double-bookings",
+    params(DoubleBookingsQuery),
+    tag = "schedules",
+    responses((status = 200, body = Vec<DoubleBooking>))
+)]
+pub async fn get_double_bookings(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<DoubleBookingsQuery>,
+) -> Result<Json<Vec<DoubleBooking>>, (StatusCode, String)> {
+    if !claims.is_admin() && !claims.is_coordinator() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins and coordinators can check double bookings".to_string(),
+        ));
+    }
+    let from = query.from.unwrap_or_else(|| Utc::now().date_naive());
+
+    let rows = sqlx::query_as::<_, BookedSlotRow>(
+        r#"
+        SELECT
+            a.person_id,
+            p.first_name || ' ' || p.last_name as person_name,
+            sd.service_date,
+            a.id as assignment_id,
+            s.id as schedule_id,
+            s.name as schedule_name,
+            a.job_id,
+            j.name as job_name,
+            a.position
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN schedules s ON sd.schedule_id = s.id
+        JOIN jobs j ON a.job_id = j.id
+        JOIN people p ON a.person_id = p.id
+        WHERE sd.service_date >= $1
+          AND EXISTS (
+              SELECT 1 FROM assignments other
+              JOIN service_dates osd ON other.service_date_id = osd.id
+              WHERE other.person_id = a.person_id
+                AND osd.service_date = sd.service_date
+                AND osd.id <> sd.id
+          )
+        ORDER BY sd.service_date, p.last_name, p.first_name, a.person_id, s.year, s.month, j.name
+        "#,
+    )
+    .bind(from)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(
+        group_double_bookings(rows)
+            .into_iter()
+            .filter(|b| b.slots.iter().any(|s| claims.in_scope(&s.job_id)))
+            .collect(),
+    ))
+}
+
+// ============ Get My Family's Assignments ============
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct FamilyAssignment {
+    pub person_id: String,
+    pub person_name: String,
+    pub service_date: NaiveDate,
+    pub job_id: String,
+    pub job_name: String,
+    pub job_color: String,
+    pub position: Option<i32>,
+    pub position_name: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FamilyAssignmentsQuery {
+    /// YYYY-MM, defaults to the current month
+    month: Option<String>,
+}
+
+#[derive(FromRow)]
+struct FamilyAssignmentRow {
+    person_id: String,
+    person_name: String,
+    service_date: NaiveDate,
+    job_id: String,
+    job_name: String,
+    job_color: Option<String>,
+    position: Option<i32>,
+    position_name: Option<String>,
+    notes: Option<String>,
+}
+
+/// Parse a "YYYY-MM" month into its first day
+fn parse_month(month: &str) -> Option<NaiveDate> {
+    let (year, month) = month.split_once('-')?;
+    NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, 1)
+}
+
+/// Published assignments of the signed-in servidor and their siblings (everyone sharing a
+/// sibling group with them) for one month, in date order, so a family can plan its trips.
+/// For a guardian, those of all their children and the children's siblings.
+#[utoipa::path(
+    get,
+    path = "/my-family-assignments",
+    params(FamilyAssignmentsQuery),
+    tag = "schedules",
+    responses((status = 200, body = Vec<FamilyAssignment>))
+)]
+pub async fn get_my_family_assignments(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<FamilyAssignmentsQuery>,
+) -> Result<Json<Vec<FamilyAssignment>>, (StatusCode, String)> {
+    if claims.person_id.is_none() && !claims.is_guardian() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "No tiene un servidor vinculado a su cuenta".to_string(),
+        ));
+    }
+    let guardian_id = if claims.is_guardian() {
+        Uuid::parse_str(&claims.sub).ok()
+    } else {
+        None
+    };
+
+    let month_start = match query.month.as_deref() {
+        Some(month) => parse_month(month).ok_or((
+            StatusCode::BAD_REQUEST,
+            "Mes inválido, use el formato AAAA-MM".to_string(),
+        ))?,
+        None => {
+            let today = Utc::now().date_naive();
+            NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap()
+        }
+    };
+
+    let horizon = if claims.is_admin() {
+        None
+    } else {
+        visibility::horizon(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+    };
+
+    let rows = sqlx::query_as::<_, FamilyAssignmentRow>(
+        r#"
+        WITH mine AS (
+            SELECT $1::varchar as person_id WHERE $1 IS NOT NULL
+            UNION
+            SELECT person_id FROM guardian_children WHERE user_id = $4
+        ), family AS (
+            SELECT person_id FROM mine
+            UNION
+            SELECT other.person_id
+            FROM sibling_group_members own
+            JOIN sibling_group_members other ON other.sibling_group_id = own.sibling_group_id
+            WHERE own.person_id IN (SELECT person_id FROM mine)
+        )
+        SELECT
+            p.id as person_id,
+            p.first_name || ' ' || p.last_name as person_name,
+            sd.service_date,
+            j.id as job_id,
+            j.name as job_name,
+            j.color as job_color,
+            a.position,
+            a.position_name,
+            a.notes
+        FROM family f
+        JOIN assignments a ON a.person_id = f.person_id
+        JOIN people p ON a.person_id = p.id
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN schedules s ON sd.schedule_id = s.id
+        JOIN jobs j ON a.job_id = j.id
+        WHERE job_is_published(s.id, a.job_id)
+          AND sd.service_date >= $2
+          AND sd.service_date < ($2 + INTERVAL '1 month')::date
+          AND ($3::date IS NULL OR sd.service_date <= $3)
+        ORDER BY sd.service_date, p.first_name, p.last_name, j.name, a.position
+        "#,
+    )
+    .bind(&claims.person_id)
+    .bind(month_start)
+    .bind(horizon)
+    .bind(guardian_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(
+        rows.into_iter()
+            .map(|row| FamilyAssignment {
+                person_id: row.person_id,
+                person_name: row.person_name,
+                service_date: row.service_date,
+                job_id: row.job_id,
+                job_name: row.job_name,
+                job_color: row.job_color.unwrap_or_else(|| "#3B82F6".to_string()),
+                position: row.position,
+                position_name: row.position_name,
+                notes: row.notes,
+            })
+            .collect(),
+    ))
+}
+
+// ============ Get My Calendar ============
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, FromRow, utoipa::ToSchema)]
+pub struct CalendarAssignment {
+    #[serde(skip)]
+    pub service_date: NaiveDate,
+    pub job_id: String,
+    pub job_name: String,
+    pub job_color: Option<String>,
+    pub position_name: Option<String>,
+}
+
+/// An unavailability record covering a calendar day. Only `single_day` records can be
+/// toggled off from the grid; longer or recurring ones are edited as a whole.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, FromRow, utoipa::ToSchema)]
+pub struct CalendarUnavailability {
+    #[serde(skip)]
+    pub date: NaiveDate,
+    pub id: String,
+    pub reason: Option<String>,
+    pub single_day: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, utoipa::ToSchema)]
+pub struct CalendarDay {
+    pub date: NaiveDate,
+    /// "cancelled", "assigned", "unavailable" or "available"; assigned wins over
+    /// unavailable, so a clash shows up as an assigned day that still lists its unavailability
+    pub status: &'static str,
+    pub assignments: Vec<CalendarAssignment>,
+    pub unavailability: Vec<CalendarUnavailability>,
+}
+
+#[derive(serde::Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MyCalendarQuery {
+    /// YYYY-MM, defaults to the current month
+    month: Option<String>,
+}
+
+/// One entry per date, in date order, with that date's assignments and unavailability
+fn build_calendar(
+    mut dates: Vec<NaiveDate>,
+    cancelled: &[NaiveDate],
+    assignments: Vec<CalendarAssignment>,
+    unavailability: Vec<CalendarUnavailability>,
+) -> Vec<CalendarDay> {
+    dates.sort();
+    dates.dedup();
+    dates
+        .into_iter()
+        .map(|date| {
+            let assignments: Vec<CalendarAssignment> = assignments
+                .iter()
+                .filter(|a| a.service_date == date)
+                .cloned()
+                .collect();
+            let unavailability: Vec<CalendarUnavailability> = unavailability
+                .iter()
+                .filter(|u| u.date == date)
+                .cloned()
+                .collect();
+            let status = if cancelled.contains(&date) {
+                "cancelled"
+            } else if !assignments.is_empty() {
+                "assigned"
+            } else if !unavailability.is_empty() {
+                "unavailable"
+            } else {
+                "available"
+            };
+            CalendarDay {
+                date,
+                status,
+                assignments,
+                unavailability,
+            }
+        })
+        .collect()
+}
+
+/// The signed-in servidor's month at a glance: every Sunday and scheduled service date with
+/// whether they serve (published assignments inside the visibility window), marked
+/// themselves unavailable, or are free
+#[utoipa::path(
+    get,
+    path = "/my-calendar",
+    params(MyCalendarQuery),
+    tag = "schedules",
+    responses((status = 200, body = Vec<CalendarDay>))
+)]
+pub async fn get_my_calendar(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<MyCalendarQuery>,
+) -> Result<Json<Vec<CalendarDay>>, (StatusCode, String)> {
+    let person_id = claims.person_id.clone().ok_or((
+        StatusCode::FORBIDDEN,
+        "No tiene un servidor vinculado a su cuenta".to_string(),
+    ))?;
+
+    let month_start = match query.month.as_deref() {
+        Some(month) => parse_month(month).ok_or((
+            StatusCode::BAD_REQUEST,
+            "Mes inválido, use el formato AAAA-MM".to_string(),
+        ))?,
+        None => {
+            let today = Utc::now().date_naive();
+            NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap()
+        }
+    };
+
+    let horizon = if claims.is_admin() {
+        None
+    } else {
+        visibility::horizon(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+    };
+
+    let mut dates = generator::get_sundays_of_month(month_start.year(), month_start.month());
+    // A date is cancelled when no schedule still holds a service on it
+    let scheduled: Vec<(NaiveDate, bool)> = sqlx::query_as(
+        r#"
+        SELECT service_date, bool_and(cancelled_at IS NOT NULL)
+        FROM service_dates
+        WHERE service_date >= $1 AND service_date < ($1 + INTERVAL '1 month')::date
+        GROUP BY service_date
+        "#,
+    )
+    .bind(month_start)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let cancelled: Vec<NaiveDate> = scheduled
+        .iter()
+        .filter(|(_, cancelled)| *cancelled)
+        .map(|(date, _)| *date)
+        .collect();
+    dates.extend(scheduled.into_iter().map(|(date, _)| date));
+
+    let assignments = sqlx::query_as::<_, CalendarAssignment>(
+        r#"
+        SELECT sd.service_date, j.id as job_id, j.name as job_name, j.color as job_color,
+               a.position_name
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN jobs j ON a.job_id = j.id
+        WHERE a.person_id = $1
+          AND job_is_published(sd.schedule_id, a.job_id)
+          AND sd.service_date >= $2
+          AND sd.service_date < ($2 + INTERVAL '1 month')::date
+          AND ($3::date IS NULL OR sd.service_date <= $3)
+        ORDER BY sd.service_date, j.name, a.position
+        "#,
+    )
+    .bind(&person_id)
+    .bind(month_start)
+    .bind(horizon)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let unavailability = sqlx::query_as::<_, CalendarUnavailability>(
+        r#"
+        SELECT d.date, u.id, u.reason,
+               (u.start_date = u.end_date AND u.recurrence IS NULL) as single_day
+        FROM unnest($2::date[]) AS d(date)
+        JOIN unavailability u ON u.person_id = $1 AND unavailability_matches(u, d.date)
+        ORDER BY d.date, u.start_date
+        "#,
+    )
+    .bind(&person_id)
+    .bind(&dates)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(build_calendar(
+        dates,
+        &cancelled,
+        assignments,
+        unavailability,
+    )))
+}
+
+// ============ Get Schedule Completeness ============
+
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct EmptySlot {
+    pub service_date: String,
+    pub job_name: String,
+    pub position_name: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct CompletenessResponse {
+    pub is_complete: bool,
+    pub total_slots: i64,
+    pub filled_slots: i64,
+    pub empty_slots: Vec<EmptySlot>,
+}
+
+/// A (date, job) pair of the schedule with how many people it needs and has
+struct ExpectedSlot {
+    service_date: NaiveDate,
+    job_id: String,
+    job_name: String,
+    required: i64,
+    rows: i64,
+    filled: i64,
+}
+
+/// Expected slots as the generator sees them: staffing overrides win over
+/// jobs.people_required, and Monaguillos Jr. is capped by the seniors actually
+/// serving that date. Only jobs the schedule was generated with are counted.
+async fn expected_slots(
+    pool: &PgPool,
+    schedule_id: &str,
+) -> Result<Vec<ExpectedSlot>, (StatusCode, String)> {
+    let slot_rows: Vec<(NaiveDate, String, String, i32, i64, i64)> = sqlx::query_as(
+        r#"
+        SELECT
+            sd.service_date,
+            j.id as job_id,
+            j.name as job_name,
+            COALESCE(o.people_required, j.people_required) as required,
+            COUNT(a.id) as rows,
+            COUNT(a.person_id) as filled
+        FROM service_dates sd
+        JOIN jobs j ON j.id IN (
+            SELECT DISTINCT sa.job_id
+            FROM assignments sa
+            JOIN service_dates ssd ON sa.service_date_id = ssd.id
+            WHERE ssd.schedule_id = $1
+        )
+        LEFT JOIN staffing_overrides o ON o.service_date = sd.service_date AND o.job_id = j.id
+        LEFT JOIN assignments a ON a.service_date_id = sd.id AND a.job_id = j.id
+        WHERE sd.schedule_id = $1 AND sd.cancelled_at IS NULL
+        GROUP BY sd.service_date, j.id, j.name, o.people_required, j.people_required
+        ORDER BY sd.service_date, j.name
+        "#,
+    )
+    .bind(schedule_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let juniors_max_per_senior = settings::get_i64(pool, "juniors_max_per_senior", 1)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(slot_rows
+        .iter()
+        .map(|(service_date, job_id, job_name, required, rows, filled)| {
+            let mut required = (*required).max(0) as i64;
+            if job_id == JUNIOR_JOB_ID {
+                let seniors = slot_rows
+                    .iter()
+                    .find(|(d, j, ..)| d == service_date && j == SENIOR_JOB_ID)
+                    .map(|(.., senior_filled)| *senior_filled)
+                    .unwrap_or(0);
+                required = required.min(seniors * juniors_max_per_senior.max(0));
+            }
+            ExpectedSlot {
+                service_date: *service_date,
+                job_id: job_id.clone(),
+                job_name: job_name.clone(),
+                required,
+                rows: *rows,
+                filled: *filled,
+            }
+        })
+        .collect())
+}
+
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/completeness",
+    tag = "schedules",
+    responses((status = 200, body = CompletenessResponse))
+)]
+pub async fn get_schedule_completeness(
+    State(pool): State<PgPool>,
+    Path(id): Path<String>,
+) -> Result<Json<CompletenessResponse>, (StatusCode, String)> {
+    let expected = expected_slots(&pool, &id).await?;
+
+    let total_slots: i64 = expected.iter().map(|slot| slot.required).sum();
+    let filled_slots: i64 = expected
+        .iter()
+        .map(|slot| slot.filled.min(slot.required))
+        .sum();
+
+    // Get empty slots details
+    let empty_rows: Vec<(NaiveDate, String, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT sd.service_date, j.name as job_name, a.position_name
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN jobs j ON a.job_id = j.id
+        WHERE sd.schedule_id = $1 AND a.person_id IS NULL
+        ORDER BY sd.service_date, j.name, a.position
+        "#,
+    )
+    .bind(&id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut empty_slots: Vec<EmptySlot> = empty_rows
+        .into_iter()
+        .map(|(service_date, job_name, position_name)| EmptySlot {
+            service_date: service_date.to_string(),
+            job_name,
+            position_name,
+        })
+        .collect();
+
+    // Positions that were never created (not enough people, or an override raised the count)
+    for slot in &expected {
+        for _ in slot.rows..slot.required {
+            empty_slots.push(EmptySlot {
+                service_date: slot.service_date.to_string(),
+                job_name: slot.job_name.clone(),
+                position_name: None,
+            });
+        }
+    }
+    empty_slots.sort_by(|a, b| {
+        (a.service_date.as_str(), a.job_name.as_str())
+            .cmp(&(b.service_date.as_str(), b.job_name.as_str()))
+    });
+
+    Ok(Json(CompletenessResponse {
+        is_complete: filled_slots >= total_slots,
+        total_slots,
+        filled_slots,
+        empty_slots,
+    }))
+}
+
+// ============ What-if: person absence ============
+
+/// How many replacements to suggest per slot
+const MAX_REPLACEMENTS: usize = 5;
+
+#[derive(FromRow)]
+struct AbsentSlotRow {
+    id: String,
+    service_date: NaiveDate,
+    job_id: String,
+    job_name: String,
+    position: Option<i32>,
+    position_name: Option<String>,
+}
+
+/// Simulate a person dropping out of a draft schedule: list the upcoming slots they hold and
+/// the best replacement for each. Nothing is written.
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/what-if/absence/{person_id}",
+    tag = "schedules",
+    responses((status = 200, body = AbsenceSimulation))
+)]
+pub async fn simulate_absence(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path((id, person_id)): Path<(String, String)>,
+) -> Result<Json<AbsenceSimulation>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can run schedule simulations".to_string(),
+        ));
+    }
+
+    let schedule = sqlx::query_as::<_, Schedule>("SELECT * FROM schedules WHERE id = $1")
+        .bind(&id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))?;
+    if schedule.status != "DRAFT" {
+        return Err((
+            StatusCode::CONFLICT,
+            "Only draft schedules can be simulated".to_string(),
+        ));
+    }
+
+    let person_name: String =
+        sqlx::query_scalar("SELECT first_name || ' ' || last_name FROM people WHERE id = $1")
+            .bind(&person_id)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((StatusCode::NOT_FOUND, "Person not found".to_string()))?;
+
+    let slots = sqlx::query_as::<_, AbsentSlotRow>(
+        r#"
+        SELECT a.id, sd.service_date, a.job_id, j.name as job_name, a.position, a.position_name
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN jobs j ON a.job_id = j.id
+        WHERE sd.schedule_id = $1
+          AND a.person_id = $2
+          AND sd.service_date >= CURRENT_DATE
+        ORDER BY sd.service_date, j.name, a.position
+        "#,
+    )
+    .bind(&id)
+    .bind(&person_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let fairness = generator::FairnessSettings::load(&mut conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let exclude = vec![person_id.clone()];
+
+    let mut affected = Vec::new();
+    for slot in slots {
+        let replacements = generator::rank_replacements(
+            &mut conn,
+            &fairness,
+            slot.service_date,
+            &slot.job_id,
+            &exclude,
+            MAX_REPLACEMENTS,
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+        affected.push(AbsenceImpact {
+            assignment_id: slot.id,
+            service_date: slot.service_date,
+            job_id: slot.job_id,
+            job_name: slot.job_name,
+            position: slot.position,
+            position_name: slot.position_name,
+            replacements,
+        });
+    }
+
+    let uncovered = affected
+        .iter()
+        .filter(|a| a.replacements.is_empty())
+        .count();
+
+    Ok(Json(AbsenceSimulation {
+        schedule_id: id,
+        person_id,
+        person_name,
+        affected,
+        uncovered,
+    }))
+}
+
+// ============ Empty slot suggestions ============
+
+/// How many candidates to list per empty slot
+const MAX_SLOT_CANDIDATES: usize = 8;
+
+#[derive(FromRow)]
+struct EmptyAssignmentRow {
+    id: String,
+    service_date: NaiveDate,
+    job_id: String,
+    job_name: String,
+    position_name: Option<String>,
+}
+
+/// Companion to the completeness check: every empty slot with the people who could fill
+/// it. Eligible people come first; near misses list the rules that block them so the
+/// admin can decide what to relax.
+#[utoipa::path(
+    get,
+    path = "/schedules/{id}/completeness/suggestions",
+    tag = "schedules",
+    responses((status = 200, body = Vec<EmptySlotSuggestions>))
+)]
+pub async fn get_completeness_suggestions(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<EmptySlotSuggestions>>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can view slot suggestions".to_string(),
+        ));
+    }
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM schedules WHERE id = $1)")
+        .bind(&id)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, "Schedule not found".to_string()));
+    }
+
+    let empty_rows = sqlx::query_as::<_, EmptyAssignmentRow>(
+        r#"
+        SELECT a.id, sd.service_date, a.job_id, j.name as job_name, a.position_name
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN jobs j ON a.job_id = j.id
+        WHERE sd.schedule_id = $1 AND a.person_id IS NULL
+        ORDER BY sd.service_date, j.name, a.position
+        "#,
+    )
+    .bind(&id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut slots: Vec<EmptySlotSuggestions> = empty_rows
+        .into_iter()
+        .map(|row| EmptySlotSuggestions {
+            assignment_id: Some(row.id),
+            service_date: row.service_date,
+            job_id: row.job_id,
+            job_name: row.job_name,
+            position_name: row.position_name,
+            candidates: Vec::new(),
+        })
+        .collect();
+
+    // Positions that were never created
+    for slot in expected_slots(&pool, &id).await? {
+        for _ in slot.rows..slot.required {
+            slots.push(EmptySlotSuggestions {
+                assignment_id: None,
+                service_date: slot.service_date,
+                job_id: slot.job_id.clone(),
+                job_name: slot.job_name.clone(),
+                position_name: None,
+                candidates: Vec::new(),
+            });
+        }
+    }
+    slots.sort_by(|a, b| (a.service_date, &a.job_name).cmp(&(b.service_date, &b.job_name)));
+
+    let max_jobs = settings::get_i64(&pool, "max_jobs_per_person_per_month", 0)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let mut conn = pool
+        .acquire()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let fairness = generator::FairnessSettings::load(&mut conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    // Empty positions of the same job on the same date share one ranking
+    let mut ranked: HashMap<(NaiveDate, String), Vec<SlotCandidate>> = HashMap::new();
+    for slot in &mut slots {
+        let key = (slot.service_date, slot.job_id.clone());
+        if !ranked.contains_key(&key) {
+            let candidates = generator::rank_slot_candidates(
+                &mut conn,
+                &fairness,
+                slot.service_date,
+                &slot.job_id,
+                &slot.job_name,
+                max_jobs,
+                MAX_SLOT_CANDIDATES,
+            )
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+            ranked.insert(key.clone(), candidates);
+        }
+        slot.candidates = ranked[&key].clone();
+    }
+
+    Ok(Json(slots))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_bookings_group_by_person_and_day() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 11, d).unwrap();
+        let row = |person: &str, date, schedule: &str| BookedSlotRow {
+            person_id: person.to_string(),
+            person_name: person.to_string(),
+            service_date: date,
+            slot: DoubleBookingSlot {
+                assignment_id: format!("{}-{}", person, schedule),
+                schedule_id: schedule.to_string(),
+                schedule_name: schedule.to_string(),
+                job_id: "lectores".to_string(),
+                job_name: "Lectores".to_string(),
+                position: Some(1),
+            },
+        };
+
+        let bookings = group_double_bookings(vec![
+            row("ana", day(1), "octubre"),
+            row("ana", day(1), "noviembre"),
+            row("luis", day(1), "octubre"),
+            row("luis", day(1), "noviembre"),
+            row("ana", day(2), "noviembre"),
+            row("ana", day(2), "especial"),
+        ]);
+        let summary: Vec<(&str, u32, usize)> = bookings
+            .iter()
+            .map(|b| (b.person_id.as_str(), b.service_date.day(), b.slots.len()))
+            .collect();
+        assert_eq!(summary, vec![("ana", 1, 2), ("luis", 1, 2), ("ana", 2, 2)]);
+    }
+
+    #[test]
+    fn parse_month_reads_year_and_month() {
+        assert_eq!(parse_month("2026-03"), NaiveDate::from_ymd_opt(2026, 3, 1));
+        assert_eq!(parse_month("2026-3"), NaiveDate::from_ymd_opt(2026, 3, 1));
+    }
+
+    #[test]
+    fn calendar_days_prefer_assigned_over_unavailable() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let assignment = CalendarAssignment {
+            service_date: day(8),
+            job_id: "lectores".to_string(),
+            job_name: "Lectores".to_string(),
+            job_color: None,
+            position_name: Some("Primera lectura".to_string()),
+        };
+        let away = |d| CalendarUnavailability {
+            date: day(d),
+            id: format!("u{}", d),
+            reason: None,
+            single_day: true,
+        };
+
+        let calendar = build_calendar(
+            vec![day(15), day(1), day(8), day(8), day(22)],
+            &[day(22)],
+            vec![assignment],
+            vec![away(8), away(15), away(22)],
+        );
+        let statuses: Vec<(u32, &str)> =
+            calendar.iter().map(|d| (d.date.day(), d.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (1, "available"),
+                (8, "assigned"),
+                (15, "unavailable"),
+                (22, "cancelled")
+            ]
+        );
+        assert_eq!(calendar[1].unavailability.len(), 1);
+    }
+
+    #[test]
+    fn parse_month_rejects_malformed_input() {
+        assert_eq!(parse_month("2026-13"), None);
+        assert_eq!(parse_month("2026"), None);
+        assert_eq!(parse_month("marzo-2026"), None);
+    }
+}
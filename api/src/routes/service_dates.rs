@@ -0,0 +1,274 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Datelike;
+use sqlx::PgPool;
+
+use crate::auth::{self, Claims};
+use crate::db::tx::Tx;
+use crate::models::{
+    Assignment, AssignmentEvent, CancelServiceDateRequest, ServiceDate,
+    UpdateServiceDateNotesRequest,
+};
+use crate::services::{assignment_events, naming, notifications, rotation};
+
+use super::schedules::{archived_error, normalize_note};
+
+/// Chronological changes to a service date's assignments (generated, substituted, swapped...);
+/// coordinators only see the jobs they coordinate
+#[utoipa::path(
+    get,
+    path = "/service-dates/{id}/history",
+    tag = "service-dates",
+    responses((status = 200, body = Vec<AssignmentEvent>))
+)]
+pub async fn get_history(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<AssignmentEvent>>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "see a service date's history")?;
+
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM service_dates WHERE id = $1)")
+            .bind(&id)
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if !exists {
+        return Err((StatusCode::NOT_FOUND, "Service date not found".to_string()));
+    }
+
+    let events = sqlx::query_as::<_, AssignmentEvent>(
+        r#"
+        SELECT
+            e.id, e.assignment_id, e.job_id, j.name as job_name, e.position,
+            jp.name as position_name,
+            e.action,
+            e.person_id, p.first_name || ' ' || p.last_name as person_name,
+            e.previous_person_id, pp.first_name || ' ' || pp.last_name as previous_person_name,
+            e.changed_by, e.reason, e.created_at
+        FROM assignment_events e
+        JOIN jobs j ON e.job_id = j.id
+        LEFT JOIN job_positions jp ON jp.job_id = e.job_id AND jp.position_number = e.position
+        LEFT JOIN people p ON e.person_id = p.id
+        LEFT JOIN people pp ON e.previous_person_id = pp.id
+        WHERE e.service_date_id = $1
+        ORDER BY e.created_at, j.name, e.position
+        "#,
+    )
+    .bind(&id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(
+        events
+            .into_iter()
+            .filter(|e| claims.in_scope(&e.job_id))
+            .collect(),
+    ))
+}
+
+/// Set or remove the note on a service date (e.g. "Misa de Primeras Comuniones, llegar 7:30")
+#[utoipa::path(
+    put,
+    path = "/service-dates/{id}/notes",
+    tag = "service-dates",
+    responses((status = 200, body = ServiceDate))
+)]
+pub async fn update_notes(
+    claims: Claims,
+    mut tx: Tx,
+    Path(id): Path<String>,
+    Json(input): Json<UpdateServiceDateNotesRequest>,
+) -> Result<Json<ServiceDate>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can edit service date notes".to_string(),
+        ));
+    }
+    let notes = normalize_note(input.notes.as_deref())?;
+
+    let status: String = sqlx::query_scalar(
+        r#"
+        SELECT s.status FROM service_dates sd
+        JOIN schedules s ON s.id = sd.schedule_id
+        WHERE sd.id = $1
+        FOR UPDATE OF s
+        "#,
+    )
+    .bind(&id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or((StatusCode::NOT_FOUND, "Service date not found".to_string()))?;
+    if status == "ARCHIVED" {
+        return Err(archived_error());
+    }
+
+    let service_date = sqlx::query_as::<_, ServiceDate>(
+        "UPDATE service_dates SET notes = $1 WHERE id = $2 RETURNING *",
+    )
+    .bind(&notes)
+    .bind(&id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(service_date))
+}
+
+/// Call off a service date: its assignments and their history entries are removed (so they
+/// no longer count for fairness or rotation), the date drops out of completeness, and
+/// everyone who could already see their assignment on it is notified
+#[utoipa::path(
+    post,
+    path = "/service-dates/{id}/cancel",
+    tag = "service-dates",
+    responses((status = 200, body = ServiceDate))
+)]
+pub async fn cancel(
+    claims: Claims,
+    mut tx: Tx,
+    Path(id): Path<String>,
+    Json(input): Json<CancelServiceDateRequest>,
+) -> Result<Json<ServiceDate>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can cancel service dates".to_string(),
+        ));
+    }
+
+    let service_date =
+        sqlx::query_as::<_, ServiceDate>("SELECT * FROM service_dates WHERE id = $1 FOR UPDATE")
+            .bind(&id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .ok_or((StatusCode::NOT_FOUND, "Service date not found".to_string()))?;
+    if service_date.cancelled_at.is_some() {
+        return Err((
+            StatusCode::CONFLICT,
+            "Service date is already cancelled".to_string(),
+        ));
+    }
+
+    let status: String =
+        sqlx::query_scalar("SELECT status FROM schedules WHERE id = $1 FOR UPDATE")
+            .bind(&service_date.schedule_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if status == "ARCHIVED" {
+        return Err(archived_error());
+    }
+
+    // People who were told they serve: their job was published for this schedule
+    let notify: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT DISTINCT person_id FROM assignments
+        WHERE service_date_id = $1 AND person_id IS NOT NULL
+          AND job_is_published($2, job_id)
+        "#,
+    )
+    .bind(&id)
+    .bind(&service_date.schedule_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let assignments = sqlx::query_as::<_, Assignment>(
+        "SELECT * FROM assignments WHERE service_date_id = $1 ORDER BY id FOR UPDATE",
+    )
+    .bind(&id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    for assignment in &assignments {
+        let Some(person_id) = &assignment.person_id else {
+            continue;
+        };
+        assignment_events::record(
+            &mut tx,
+            assignment,
+            "cancelled",
+            None,
+            Some(&claims.username),
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+        if let Some(pos) = assignment.position {
+            rotation::unmark_done(&mut tx, person_id, &assignment.job_id, pos)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        }
+
+        sqlx::query(
+            "DELETE FROM assignment_history WHERE person_id = $1 AND job_id = $2 AND service_date = $3",
+        )
+        .bind(person_id)
+        .bind(&assignment.job_id)
+        .bind(service_date.service_date)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+
+    sqlx::query("DELETE FROM assignments WHERE service_date_id = $1")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let reason = input
+        .reason
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty());
+    let service_date = sqlx::query_as::<_, ServiceDate>(
+        r#"
+        UPDATE service_dates
+        SET cancelled_at = NOW(), cancelled_by = $2, cancel_reason = $3
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(&id)
+    .bind(&claims.username)
+    .bind(reason)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let date = service_date.service_date;
+    let mut message = format!(
+        "Se canceló el servicio del {} de {} de {}; ya no tienes asignación ese día.",
+        date.day(),
+        naming::month_name(date.month() as i32).to_lowercase(),
+        date.year()
+    );
+    if let Some(reason) = reason {
+        message.push_str(&format!(" Motivo: {}", reason));
+    }
+    for person_id in &notify {
+        notifications::send_or_warn(
+            &mut tx,
+            "in_app",
+            person_id,
+            "service_date_cancelled",
+            &message,
+        )
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+
+    Ok(Json(service_date))
+}
@@ -0,0 +1,176 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::auth::{self, Claims};
+use crate::models::{RevokedSessions, UserSession};
+use crate::services::sessions;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RevokeAllQuery {
+    /// Stay signed in on the device making the request
+    #[serde(default)]
+    pub keep_current: bool,
+}
+
+fn user_id(claims: &Claims) -> Result<Uuid, (StatusCode, String)> {
+    Uuid::parse_str(&claims.sub).map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid user".to_string()))
+}
+
+fn parse_user_id(id: &str) -> Result<Uuid, (StatusCode, String)> {
+    Uuid::parse_str(id).map_err(|_| (StatusCode::NOT_FOUND, "User not found".to_string()))
+}
+
+/// Where the user is signed in, most recently used first
+#[utoipa::path(
+    get,
+    path = "/auth/sessions",
+    tag = "sessions",
+    responses((status = 200, body = Vec<UserSession>))
+)]
+pub async fn get_mine(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<Vec<UserSession>>, (StatusCode, String)> {
+    let sessions = sessions::list(&pool, user_id(&claims)?, claims.sid.as_deref())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(sessions))
+}
+
+/// Sign out one of the user's devices
+#[utoipa::path(
+    delete,
+    path = "/auth/sessions/{id}",
+    tag = "sessions",
+    responses((status = 204))
+)]
+pub async fn revoke_mine(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let revoked = sessions::revoke(&pool, user_id(&claims)?, &id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if !revoked {
+        return Err((StatusCode::NOT_FOUND, "Session not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Sign out everywhere, or everywhere else with `?keep_current=true`
+#[utoipa::path(
+    delete,
+    path = "/auth/sessions",
+    params(RevokeAllQuery),
+    tag = "sessions",
+    responses((status = 200, body = RevokedSessions))
+)]
+pub async fn revoke_all_mine(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<RevokeAllQuery>,
+) -> Result<Json<RevokedSessions>, (StatusCode, String)> {
+    let except = claims.sid.as_deref().filter(|_| query.keep_current);
+    let revoked = sessions::revoke_all(&pool, user_id(&claims)?, except)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(RevokedSessions { revoked }))
+}
+
+/// End the session the request was made with
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    tag = "sessions",
+    responses((status = 204))
+)]
+pub async fn logout(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<StatusCode, (StatusCode, String)> {
+    if let Some(sid) = &claims.sid {
+        sessions::revoke(&pool, user_id(&claims)?, sid)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// A user's sessions, for an admin helping someone who lost a device
+#[utoipa::path(
+    get,
+    path = "/admin/users/{id}/sessions",
+    tag = "sessions",
+    responses((status = 200, body = Vec<UserSession>))
+)]
+pub async fn get_for_user(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<UserSession>>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage other users' sessions")?;
+
+    let sessions = sessions::list(&pool, parse_user_id(&id)?, claims.sid.as_deref())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(sessions))
+}
+
+/// Sign a user out of one session
+#[utoipa::path(
+    delete,
+    path = "/admin/users/{id}/sessions/{session_id}",
+    tag = "sessions",
+    responses((status = 204))
+)]
+pub async fn revoke_for_user(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path((id, session_id)): Path<(String, String)>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage other users' sessions")?;
+
+    let revoked = sessions::revoke(&pool, parse_user_id(&id)?, &session_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if !revoked {
+        return Err((StatusCode::NOT_FOUND, "Session not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Sign a user out everywhere
+#[utoipa::path(
+    delete,
+    path = "/admin/users/{id}/sessions",
+    tag = "sessions",
+    responses((status = 200, body = RevokedSessions))
+)]
+pub async fn revoke_all_for_user(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<RevokedSessions>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage other users' sessions")?;
+
+    let revoked = sessions::revoke_all(&pool, parse_user_id(&id)?, None)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(RevokedSessions { revoked }))
+}
@@ -0,0 +1,195 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::NaiveTime;
+use sqlx::{Executor, PgPool, Postgres};
+
+use crate::auth::{self, Claims};
+use crate::models::{AppSetting, UpdateSetting};
+use crate::services::{naming, notifications};
+
+/// Settings include the token and session lifetimes, so only admins read or change them
+#[utoipa::path(
+    get,
+    path = "/settings",
+    tag = "settings",
+    responses((status = 200, body = Vec<AppSetting>))
+)]
+pub async fn get_all(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<Vec<AppSetting>>, (StatusCode, String)> {
+    auth::require_admin(&claims, "see the settings")?;
+
+    let settings = sqlx::query_as::<_, AppSetting>("SELECT * FROM app_settings ORDER BY key")
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(settings))
+}
+
+/// Numeric settings with their allowed range (inclusive). A value that does not parse would make
+/// `get_i64` fall back to the default and quietly switch the rule off, so it is rejected on write.
+const NUMERIC_SETTINGS: &[(&str, i64, i64)] = &[
+    ("juniors_max_per_senior", 0, 20),
+    ("junior_promotion_min_age", 0, 25),
+    ("junior_promotion_min_services", 0, 500),
+    ("username_alias_grace_days", 0, 365),
+    ("fairness_job_weight", 0, 100),
+    ("fairness_window_months", 0, 120),
+    ("fairness_decay_percent", 0, 100),
+    ("max_jobs_per_person_per_month", 0, 50),
+    ("co_assignment_penalty", 0, 1000),
+    ("assignment_visibility_weeks", 0, 520),
+    ("reminder_days_before", 0, 60),
+    ("jwt_lifetime_hours_admin", 1, 24 * 90),
+    ("jwt_lifetime_hours_coordinator", 1, 24 * 90),
+    ("jwt_lifetime_hours_servidor", 1, 24 * 90),
+    ("session_lifetime_days", 1, 365),
+    ("invitation_lifetime_days", 1, 90),
+];
+
+/// A numeric setting's value held to its allowed range, for values that reached the table
+/// without going through `update` (older rows, manual edits); other keys pass through
+pub fn clamp_numeric(key: &str, value: i64) -> i64 {
+    NUMERIC_SETTINGS
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map_or(value, |(_, min, max)| value.clamp(*min, *max))
+}
+
+/// Channels `notifications::send` can deliver on
+pub const NOTIFICATION_CHANNELS: &[&str] = &["email", "sms", "whatsapp", "push", "in_app"];
+
+fn validate_value(key: &str, value: &str) -> Result<(), (StatusCode, String)> {
+    if key == "schedule_name_pattern" {
+        return naming::validate_pattern(value).map_err(|e| (StatusCode::BAD_REQUEST, e));
+    }
+    if key == "mass_time" {
+        let value = value.trim();
+        if value.is_empty() || NaiveTime::parse_from_str(value, "%H:%M").is_ok() {
+            return Ok(());
+        }
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "mass_time must be a time like 10:30, or blank".to_string(),
+        ));
+    }
+    if key == "reminder_channel" || key == "publish_channel" || key == "change_channel" {
+        let value = value.trim();
+        // Publish and change notices can be switched off; reminders are switched off by their days
+        let off = key != "reminder_channel" && value.is_empty();
+        if off {
+            return Ok(());
+        }
+        if NOTIFICATION_CHANNELS.contains(&value) {
+            return match notifications::missing_sender(value) {
+                None => Ok(()),
+                Some(missing) => Err((
+                    StatusCode::BAD_REQUEST,
+                    format!("{} cannot be sent from this server: {}", value, missing),
+                )),
+            };
+        }
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "{} must be one of {}",
+                key,
+                NOTIFICATION_CHANNELS.join(", ")
+            ),
+        ));
+    }
+    let Some((_, min, max)) = NUMERIC_SETTINGS.iter().find(|(k, _, _)| *k == key) else {
+        return Ok(());
+    };
+    match value.parse::<i64>() {
+        Ok(n) if (*min..=*max).contains(&n) => Ok(()),
+        _ => Err((
+            StatusCode::BAD_REQUEST,
+            format!("{} must be a whole number from {} to {}", key, min, max),
+        )),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/settings/{key}",
+    tag = "settings",
+    responses((status = 200, body = AppSetting))
+)]
+pub async fn update(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(key): Path<String>,
+    Json(input): Json<UpdateSetting>,
+) -> Result<Json<AppSetting>, (StatusCode, String)> {
+    auth::require_admin(&claims, "change the settings")?;
+    validate_value(&key, input.value.trim())?;
+
+    let setting = sqlx::query_as::<_, AppSetting>(
+        r#"
+        UPDATE app_settings SET value = $2, updated_at = NOW()
+        WHERE key = $1
+        RETURNING *
+        "#,
+    )
+    .bind(&key)
+    .bind(input.value.trim())
+    .fetch_optional(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or((StatusCode::NOT_FOUND, "Setting not found".to_string()))?;
+
+    Ok(Json(setting))
+}
+
+// ============ Helpers ============
+
+/// Read a numeric setting, falling back to `default` if it is missing or not a number
+pub async fn get_i64<'e, E>(executor: E, key: &str, default: i64) -> Result<i64, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let value: Option<String> = sqlx::query_scalar("SELECT value FROM app_settings WHERE key = $1")
+        .bind(key)
+        .fetch_optional(executor)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(value
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(default))
+}
+
+/// Read a text setting; None if it is missing or blank
+pub async fn get_string<'e, E>(executor: E, key: &str) -> Result<Option<String>, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let value: Option<String> = sqlx::query_scalar("SELECT value FROM app_settings WHERE key = $1")
+        .bind(key)
+        .fetch_optional(executor)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_settings_are_held_to_their_range() {
+        assert_eq!(clamp_numeric("session_lifetime_days", 3650), 365);
+        assert_eq!(clamp_numeric("session_lifetime_days", 0), 1);
+        assert_eq!(clamp_numeric("session_lifetime_days", 30), 30);
+        assert_eq!(clamp_numeric("not_a_setting", 3650), 3650);
+    }
+}
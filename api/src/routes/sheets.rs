@@ -0,0 +1,164 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::{self, Claims};
+use crate::models::{CreateSheetExportRequest, SheetExport};
+use crate::services::sheets;
+
+// ============ Google Sheets exports ============
+//
+// Each export keeps one tab of a shared spreadsheet in step with the published assignments.
+// The background worker rewrites a tab when its rows change (services::sheets::sync); the
+// spreadsheet must be shared with the service account's email.
+
+const EXPORT_SELECT: &str = r#"
+    SELECT id, name, spreadsheet_id, sheet_name, job_id, created_by, created_at,
+           last_pushed_at, last_error
+    FROM sheet_exports
+"#;
+
+async fn fetch_export(pool: &PgPool, id: &str) -> Result<SheetExport, (StatusCode, String)> {
+    sqlx::query_as::<_, SheetExport>(&format!("{} WHERE id = $1", EXPORT_SELECT))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Export not found".to_string()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/sheet-exports",
+    tag = "sheets",
+    responses((status = 200, body = Vec<SheetExport>))
+)]
+pub async fn get_exports(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<Vec<SheetExport>>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage spreadsheet exports")?;
+
+    let exports = sqlx::query_as::<_, SheetExport>(&format!("{} ORDER BY name", EXPORT_SELECT))
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(exports))
+}
+
+/// Add an export and write its tab straight away; a failed first write is reported in
+/// `last_error` rather than refusing the export, so it can be fixed by sharing the sheet
+#[utoipa::path(
+    post,
+    path = "/admin/sheet-exports",
+    tag = "sheets",
+    responses((status = 200, body = SheetExport))
+)]
+pub async fn create_export(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(input): Json<CreateSheetExportRequest>,
+) -> Result<Json<SheetExport>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage spreadsheet exports")?;
+    let name = input.name.trim();
+    let sheet_name = input.sheet_name.trim();
+    if name.is_empty() || sheet_name.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Name and sheet name are required".to_string(),
+        ));
+    }
+    let spreadsheet_id = sheets::spreadsheet_id(&input.spreadsheet).ok_or((
+        StatusCode::BAD_REQUEST,
+        "Give the spreadsheet's id or its docs.google.com address".to_string(),
+    ))?;
+
+    if let Some(job_id) = &input.job_id {
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM jobs WHERE id = $1)")
+            .bind(job_id)
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        if !exists {
+            return Err((StatusCode::NOT_FOUND, "Job not found".to_string()));
+        }
+    }
+
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        r#"
+        INSERT INTO sheet_exports (id, name, spreadsheet_id, sheet_name, job_id, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(&id)
+    .bind(name)
+    .bind(&spreadsheet_id)
+    .bind(sheet_name)
+    .bind(&input.job_id)
+    .bind(&claims.username)
+    .execute(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    sheets::sync(&pool, Some(&id), true)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(fetch_export(&pool, &id).await?))
+}
+
+/// Write the tab now, even if nothing changed (after the sheet was edited by hand, or to
+/// check a fix for `last_error`)
+#[utoipa::path(
+    post,
+    path = "/admin/sheet-exports/{id}/push",
+    tag = "sheets",
+    responses((status = 200, body = SheetExport))
+)]
+pub async fn push_export(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<Json<SheetExport>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage spreadsheet exports")?;
+    fetch_export(&pool, &id).await?;
+
+    sheets::sync(&pool, Some(&id), true)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(fetch_export(&pool, &id).await?))
+}
+
+/// Stop writing to a sheet; what was written stays there
+#[utoipa::path(
+    delete,
+    path = "/admin/sheet-exports/{id}",
+    tag = "sheets",
+    responses((status = 204))
+)]
+pub async fn delete_export(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage spreadsheet exports")?;
+
+    let result = sqlx::query("DELETE FROM sheet_exports WHERE id = $1")
+        .bind(&id)
+        .execute(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "Export not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
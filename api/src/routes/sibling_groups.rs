@@ -3,11 +3,41 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use sqlx::PgPool;
+use chrono::NaiveDate;
+use sqlx::{FromRow, PgPool};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use uuid::Uuid;
 
-use crate::models::{CreateSiblingGroup, SiblingGroup, SiblingGroupWithMembers};
+use crate::auth::{self, Claims};
+use crate::db::tx::Tx;
+use crate::models::{
+    CreateSiblingGroup, MergeSiblingGroups, ScheduleConflict, SiblingGroup, SiblingGroupDuplicate,
+    SiblingGroupMergeResult, SiblingGroupWithMembers, PAIRING_SCOPE_SAME_DATE,
+    PAIRING_SCOPE_SAME_JOB,
+};
+
+fn validate_scope(scope: Option<&str>) -> Result<String, (StatusCode, String)> {
+    let scope = scope
+        .map(|s| s.trim().to_uppercase())
+        .unwrap_or_else(|| PAIRING_SCOPE_SAME_JOB.to_string());
+    if scope != PAIRING_SCOPE_SAME_JOB && scope != PAIRING_SCOPE_SAME_DATE {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Invalid pairing scope: {}. Allowed: SAME_JOB, SAME_DATE",
+                scope
+            ),
+        ));
+    }
+    Ok(scope)
+}
 
+#[utoipa::path(
+    get,
+    path = "/sibling-groups",
+    tag = "sibling-groups",
+    responses((status = 200, body = Vec<SiblingGroupWithMembers>))
+)]
 pub async fn get_all(
     State(pool): State<PgPool>,
 ) -> Result<Json<Vec<SiblingGroupWithMembers>>, (StatusCode, String)> {
@@ -32,23 +62,35 @@ pub async fn get_all(
     Ok(Json(result))
 }
 
+// Sibling groups span jobs, so only admins change them
+
+#[utoipa::path(
+    post,
+    path = "/sibling-groups",
+    tag = "sibling-groups",
+    responses((status = 200, body = SiblingGroupWithMembers))
+)]
 pub async fn create(
-    State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
     Json(input): Json<CreateSiblingGroup>,
 ) -> Result<Json<SiblingGroupWithMembers>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage sibling groups")?;
+    let pairing_scope = validate_scope(input.pairing_scope.as_deref())?;
     let id = Uuid::new_v4().to_string();
 
     let group = sqlx::query_as::<_, SiblingGroup>(
         r#"
-        INSERT INTO sibling_groups (id, name, pairing_rule)
-        VALUES ($1, $2, $3)
+        INSERT INTO sibling_groups (id, name, pairing_rule, pairing_scope)
+        VALUES ($1, $2, $3, $4)
         RETURNING *
         "#,
     )
     .bind(&id)
     .bind(&input.name)
     .bind(&input.pairing_rule)
-    .fetch_one(&pool)
+    .bind(&pairing_scope)
+    .fetch_one(&mut *tx)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
@@ -61,7 +103,7 @@ pub async fn create(
         .bind(&sgm_id)
         .bind(&id)
         .bind(member_id)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     }
@@ -72,31 +114,45 @@ pub async fn create(
     }))
 }
 
+#[utoipa::path(
+    put,
+    path = "/sibling-groups/{id}",
+    tag = "sibling-groups",
+    responses((status = 200, body = SiblingGroupWithMembers))
+)]
 pub async fn update(
-    State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
     Path(id): Path<String>,
     Json(input): Json<CreateSiblingGroup>,
 ) -> Result<Json<SiblingGroupWithMembers>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage sibling groups")?;
+    let pairing_scope = match &input.pairing_scope {
+        Some(scope) => Some(validate_scope(Some(scope))?),
+        None => None,
+    };
+
     // Update group
     let group = sqlx::query_as::<_, SiblingGroup>(
         r#"
         UPDATE sibling_groups
-        SET name = $1, pairing_rule = $2
-        WHERE id = $3
+        SET name = $1, pairing_rule = $2, pairing_scope = COALESCE($3, pairing_scope)
+        WHERE id = $4
         RETURNING *
         "#,
     )
     .bind(&input.name)
     .bind(&input.pairing_rule)
+    .bind(&pairing_scope)
     .bind(&id)
-    .fetch_one(&pool)
+    .fetch_one(&mut *tx)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     // Update members - delete existing and re-add
     sqlx::query("DELETE FROM sibling_group_members WHERE sibling_group_id = $1")
         .bind(&id)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
@@ -108,7 +164,7 @@ pub async fn update(
         .bind(&sgm_id)
         .bind(&id)
         .bind(member_id)
-        .execute(&pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     }
@@ -119,10 +175,19 @@ pub async fn update(
     }))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/sibling-groups/{id}",
+    tag = "sibling-groups",
+    responses((status = 204))
+)]
 pub async fn delete(
     State(pool): State<PgPool>,
+    claims: Claims,
     Path(id): Path<String>,
 ) -> Result<StatusCode, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage sibling groups")?;
+
     let result = sqlx::query("DELETE FROM sibling_groups WHERE id = $1")
         .bind(&id)
         .execute(&pool)
@@ -135,3 +200,361 @@ pub async fn delete(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+// ============ Duplicates and merge ============
+
+/// Indexes of groups linked by shared members (directly or through another group), for every
+/// cluster of two or more groups, in input order
+fn overlap_clusters(groups: &[SiblingGroupWithMembers]) -> Vec<Vec<usize>> {
+    // Union-find over group indexes, joined through the first group seen for each person
+    let mut parent: Vec<usize> = (0..groups.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut first_group: HashMap<&str, usize> = HashMap::new();
+    for (i, group) in groups.iter().enumerate() {
+        for member in &group.member_ids {
+            if let Some(&j) = first_group.get(member.as_str()) {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                parent[a.max(b)] = a.min(b);
+            } else {
+                first_group.insert(member, i);
+            }
+        }
+    }
+
+    let mut clusters: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for i in 0..groups.len() {
+        let r = root(&mut parent, i);
+        clusters.entry(r).or_default().push(i);
+    }
+    clusters.into_values().filter(|c| c.len() > 1).collect()
+}
+
+/// Groups that share members, with the people they share and whether their rules disagree
+#[utoipa::path(
+    get,
+    path = "/sibling-groups/duplicates",
+    tag = "sibling-groups",
+    responses((status = 200, body = Vec<SiblingGroupDuplicate>))
+)]
+pub async fn get_duplicates(
+    State(pool): State<PgPool>,
+) -> Result<Json<Vec<SiblingGroupDuplicate>>, (StatusCode, String)> {
+    let Json(groups) = get_all(State(pool)).await?;
+
+    let duplicates = overlap_clusters(&groups)
+        .into_iter()
+        .map(|cluster| {
+            let cluster: Vec<SiblingGroupWithMembers> =
+                cluster.into_iter().map(|i| groups[i].clone()).collect();
+
+            let mut seen: HashMap<&str, usize> = HashMap::new();
+            for group in &cluster {
+                for member in &group.member_ids {
+                    *seen.entry(member).or_default() += 1;
+                }
+            }
+            let mut shared_member_ids: Vec<String> = seen
+                .into_iter()
+                .filter(|(_, count)| *count > 1)
+                .map(|(member, _)| member.to_string())
+                .collect();
+            shared_member_ids.sort();
+
+            let first = &cluster[0].group;
+            let conflicting_rules = cluster.iter().any(|g| {
+                g.group.pairing_rule != first.pairing_rule
+                    || g.group.pairing_scope != first.pairing_scope
+            });
+
+            SiblingGroupDuplicate {
+                groups: cluster,
+                shared_member_ids,
+                conflicting_rules,
+            }
+        })
+        .collect();
+
+    Ok(Json(duplicates))
+}
+
+/// The explicitly chosen value, or the one every group already agrees on
+fn resolve_setting(
+    chosen: Option<String>,
+    current: &[&str],
+    what: &str,
+) -> Result<String, (StatusCode, String)> {
+    if let Some(chosen) = chosen {
+        return Ok(chosen);
+    }
+    match current.split_first() {
+        Some((first, rest)) if rest.iter().all(|v| v == first) => Ok(first.to_string()),
+        _ => Err((
+            StatusCode::CONFLICT,
+            format!(
+                "The groups have different {} values; choose one to merge them",
+                what
+            ),
+        )),
+    }
+}
+
+#[derive(FromRow)]
+struct MemberSlotRow {
+    service_date: NaiveDate,
+    job_id: String,
+    job_name: String,
+    person_id: String,
+}
+
+/// Upcoming slots where two members of a SEPARATE group serve together: in the same job
+/// (SAME_JOB) or on the same date in any job (SAME_DATE)
+fn separate_conflicts(scope: &str, slots: &[MemberSlotRow]) -> Vec<ScheduleConflict> {
+    let mut together: BTreeMap<(NaiveDate, &str), Vec<&MemberSlotRow>> = BTreeMap::new();
+    for slot in slots {
+        let job_key = if scope == PAIRING_SCOPE_SAME_DATE {
+            ""
+        } else {
+            slot.job_id.as_str()
+        };
+        together
+            .entry((slot.service_date, job_key))
+            .or_default()
+            .push(slot);
+    }
+
+    together
+        .into_values()
+        .filter_map(|serving| {
+            let mut person_ids: Vec<String> = serving.iter().map(|s| s.person_id.clone()).collect();
+            person_ids.sort();
+            person_ids.dedup();
+            if person_ids.len() < 2 {
+                return None;
+            }
+            Some(ScheduleConflict {
+                service_date: serving[0].service_date,
+                job_id: serving[0].job_id.clone(),
+                conflict_type: "sibling_violation".to_string(),
+                message: format!(
+                    "{}: siblings that should be kept apart are serving together",
+                    serving[0].job_name
+                ),
+                affected_person_ids: person_ids,
+            })
+        })
+        .collect()
+}
+
+/// Fold several sibling groups into the first one. Members are combined, the other groups are
+/// deleted, and the pairing rule and scope are resolved (or taken from the request). When the
+/// result keeps siblings apart, upcoming assignments that now break the rule are returned.
+#[utoipa::path(
+    post,
+    path = "/sibling-groups/merge",
+    tag = "sibling-groups",
+    responses((status = 200, body = SiblingGroupMergeResult))
+)]
+pub async fn merge(
+    claims: Claims,
+    mut tx: Tx,
+    Json(input): Json<MergeSiblingGroups>,
+) -> Result<Json<SiblingGroupMergeResult>, (StatusCode, String)> {
+    auth::require_admin(&claims, "manage sibling groups")?;
+    let group_ids = &input.group_ids;
+    let distinct: HashSet<&String> = group_ids.iter().collect();
+    if group_ids.len() < 2 || distinct.len() != group_ids.len() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Pick at least two different groups to merge".to_string(),
+        ));
+    }
+    if let Some(rule) = &input.pairing_rule {
+        if rule != "TOGETHER" && rule != "SEPARATE" {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Invalid pairing rule: {}. Allowed: TOGETHER, SEPARATE",
+                    rule
+                ),
+            ));
+        }
+    }
+    let chosen_scope = match &input.pairing_scope {
+        Some(scope) => Some(validate_scope(Some(scope))?),
+        None => None,
+    };
+
+    let mut groups = sqlx::query_as::<_, SiblingGroup>(
+        "SELECT * FROM sibling_groups WHERE id = ANY($1) ORDER BY id FOR UPDATE",
+    )
+    .bind(group_ids)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if groups.len() != group_ids.len() {
+        return Err((StatusCode::NOT_FOUND, "Sibling group not found".to_string()));
+    }
+    groups.sort_by_key(|g| group_ids.iter().position(|id| id == &g.id));
+
+    let rules: Vec<&str> = groups.iter().map(|g| g.pairing_rule.as_str()).collect();
+    let pairing_rule = resolve_setting(input.pairing_rule.clone(), &rules, "pairing rule")?;
+    let scopes: Vec<&str> = groups.iter().map(|g| g.pairing_scope.as_str()).collect();
+    let pairing_scope = resolve_setting(chosen_scope, &scopes, "pairing scope")?;
+
+    let target_id = &group_ids[0];
+    let member_ids: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT DISTINCT person_id FROM sibling_group_members
+        WHERE sibling_group_id = ANY($1)
+        ORDER BY person_id
+        "#,
+    )
+    .bind(group_ids)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let group = sqlx::query_as::<_, SiblingGroup>(
+        r#"
+        UPDATE sibling_groups
+        SET name = COALESCE($1, name), pairing_rule = $2, pairing_scope = $3
+        WHERE id = $4
+        RETURNING *
+        "#,
+    )
+    .bind(&input.name)
+    .bind(&pairing_rule)
+    .bind(&pairing_scope)
+    .bind(target_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    for member_id in &member_ids {
+        sqlx::query(
+            r#"
+            INSERT INTO sibling_group_members (id, sibling_group_id, person_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (sibling_group_id, person_id) DO NOTHING
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(target_id)
+        .bind(member_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+
+    // Memberships of the merged groups go with them
+    sqlx::query("DELETE FROM sibling_groups WHERE id = ANY($1)")
+        .bind(&group_ids[1..])
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let conflicts = if pairing_rule == "SEPARATE" {
+        let slots = sqlx::query_as::<_, MemberSlotRow>(
+            r#"
+            SELECT sd.service_date, a.job_id, j.name as job_name, a.person_id
+            FROM assignments a
+            JOIN service_dates sd ON a.service_date_id = sd.id
+            JOIN schedules s ON sd.schedule_id = s.id
+            JOIN jobs j ON a.job_id = j.id
+            WHERE a.person_id = ANY($1)
+              AND s.status <> 'ARCHIVED'
+              AND sd.service_date >= CURRENT_DATE
+            ORDER BY sd.service_date, j.name
+            "#,
+        )
+        .bind(&member_ids)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        separate_conflicts(&pairing_scope, &slots)
+    } else {
+        Vec::new()
+    };
+
+    Ok(Json(SiblingGroupMergeResult {
+        group: SiblingGroupWithMembers { group, member_ids },
+        conflicts,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(id: &str, members: &[&str]) -> SiblingGroupWithMembers {
+        SiblingGroupWithMembers {
+            group: SiblingGroup {
+                id: id.to_string(),
+                name: id.to_string(),
+                pairing_rule: "TOGETHER".to_string(),
+                created_at: None,
+                updated_at: None,
+                pairing_scope: PAIRING_SCOPE_SAME_JOB.to_string(),
+            },
+            member_ids: members.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn clusters_follow_shared_members_transitively() {
+        let groups = vec![
+            group("a", &["ana", "luis"]),
+            group("b", &["pedro"]),
+            group("c", &["luis", "sofia"]),
+            group("d", &["sofia", "marta"]),
+        ];
+        assert_eq!(overlap_clusters(&groups), vec![vec![0, 2, 3]]);
+    }
+
+    #[test]
+    fn rules_must_agree_unless_chosen() {
+        assert_eq!(
+            resolve_setting(None, &["SEPARATE", "SEPARATE"], "rule").unwrap(),
+            "SEPARATE"
+        );
+        assert_eq!(
+            resolve_setting(None, &["SEPARATE", "TOGETHER"], "rule")
+                .unwrap_err()
+                .0,
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            resolve_setting(
+                Some("TOGETHER".to_string()),
+                &["SEPARATE", "TOGETHER"],
+                "rule"
+            )
+            .unwrap(),
+            "TOGETHER"
+        );
+    }
+
+    #[test]
+    fn separate_conflicts_depend_on_scope() {
+        let date = NaiveDate::from_ymd_opt(2026, 11, 1).unwrap();
+        let slot = |job: &str, person: &str| MemberSlotRow {
+            service_date: date,
+            job_id: job.to_string(),
+            job_name: job.to_string(),
+            person_id: person.to_string(),
+        };
+        let slots = vec![slot("lectores", "ana"), slot("monaguillos", "luis")];
+
+        assert!(separate_conflicts(PAIRING_SCOPE_SAME_JOB, &slots).is_empty());
+        let conflicts = separate_conflicts(PAIRING_SCOPE_SAME_DATE, &slots);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].affected_person_ids, vec!["ana", "luis"]);
+    }
+}
@@ -0,0 +1,148 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use sqlx::{Executor, PgPool, Postgres};
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::auth::{self, Claims};
+use crate::models::{SetStaffingOverride, StaffingOverride};
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StaffingOverrideQuery {
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/staffing-overrides",
+    params(StaffingOverrideQuery),
+    tag = "staffing",
+    responses((status = 200, body = Vec<StaffingOverride>))
+)]
+pub async fn get_all(
+    State(pool): State<PgPool>,
+    Query(query): Query<StaffingOverrideQuery>,
+) -> Result<Json<Vec<StaffingOverride>>, (StatusCode, String)> {
+    let overrides = sqlx::query_as::<_, StaffingOverride>(
+        r#"
+        SELECT * FROM staffing_overrides
+        WHERE ($1::date IS NULL OR service_date >= $1)
+          AND ($2::date IS NULL OR service_date <= $2)
+        ORDER BY service_date, job_id
+        "#,
+    )
+    .bind(query.from)
+    .bind(query.to)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(overrides))
+}
+
+/// Create or replace the override for a date and job; coordinators only for their jobs
+#[utoipa::path(
+    put,
+    path = "/staffing-overrides",
+    tag = "staffing",
+    responses((status = 200, body = StaffingOverride))
+)]
+pub async fn set(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(input): Json<SetStaffingOverride>,
+) -> Result<Json<StaffingOverride>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "change staffing")?;
+    auth::require_job_scope(&claims, &input.job_id)?;
+    if input.people_required < 0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "people_required cannot be negative".to_string(),
+        ));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let staffing = sqlx::query_as::<_, StaffingOverride>(
+        r#"
+        INSERT INTO staffing_overrides (id, service_date, job_id, people_required, reason)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (service_date, job_id) DO UPDATE
+        SET people_required = EXCLUDED.people_required, reason = EXCLUDED.reason
+        RETURNING *
+        "#,
+    )
+    .bind(&id)
+    .bind(input.service_date)
+    .bind(&input.job_id)
+    .bind(input.people_required)
+    .bind(&input.reason)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(staffing))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/staffing-overrides/{id}",
+    tag = "staffing",
+    responses((status = 204))
+)]
+pub async fn delete(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "change staffing")?;
+
+    let job_id: String = sqlx::query_scalar("SELECT job_id FROM staffing_overrides WHERE id = $1")
+        .bind(&id)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            "Staffing override not found".to_string(),
+        ))?;
+    auth::require_job_scope(&claims, &job_id)?;
+
+    sqlx::query("DELETE FROM staffing_overrides WHERE id = $1")
+        .bind(&id)
+        .execute(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============ Helpers ============
+
+/// People required for a job on a date, honoring any override
+pub async fn people_required<'e, E>(
+    executor: E,
+    service_date: NaiveDate,
+    job_id: &str,
+    default: i32,
+) -> Result<i32, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let required: Option<i32> = sqlx::query_scalar(
+        "SELECT people_required FROM staffing_overrides WHERE service_date = $1 AND job_id = $2",
+    )
+    .bind(service_date)
+    .bind(job_id)
+    .fetch_optional(executor)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(required.unwrap_or(default))
+}
@@ -0,0 +1,101 @@
+use axum::{extract::State, http::StatusCode, Json};
+use sqlx::PgPool;
+
+use crate::auth::Claims;
+use crate::db::tx::Tx;
+use crate::models::{
+    OrphanUser, ReconcileUsersRequest, SyncDeletedPeopleRequest, SyncDeletedPeopleResponse,
+};
+
+// ============ Desktop sync (admin) ============
+
+/// Apply person deletions made in the desktop app. A deleted person takes its servidor account
+/// with it, so the cloud is not left with a login for someone who no longer exists.
+#[utoipa::path(
+    post,
+    path = "/admin/sync/deleted-people",
+    tag = "sync",
+    responses((status = 200, body = SyncDeletedPeopleResponse))
+)]
+pub async fn apply_deleted_people(
+    claims: Claims,
+    mut tx: Tx,
+    Json(input): Json<SyncDeletedPeopleRequest>,
+) -> Result<Json<SyncDeletedPeopleResponse>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can sync deletions".to_string(),
+        ));
+    }
+
+    let mut response = SyncDeletedPeopleResponse {
+        deleted_people: Vec::new(),
+        deleted_users: Vec::new(),
+        not_found: Vec::new(),
+    };
+
+    for person_id in &input.person_ids {
+        let usernames: Vec<String> =
+            sqlx::query_scalar("DELETE FROM users WHERE person_id = $1 RETURNING username")
+                .bind(person_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        response.deleted_users.extend(usernames);
+
+        let result = sqlx::query("DELETE FROM people WHERE id = $1")
+            .bind(person_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            response.not_found.push(person_id.clone());
+        } else {
+            response.deleted_people.push(person_id.clone());
+        }
+    }
+
+    Ok(Json(response))
+}
+
+/// Servidor accounts left without a person, in the cloud or in the desktop app
+#[utoipa::path(
+    post,
+    path = "/admin/sync/reconcile-users",
+    tag = "sync",
+    responses((status = 200, body = Vec<OrphanUser>))
+)]
+pub async fn reconcile_users(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Json(input): Json<ReconcileUsersRequest>,
+) -> Result<Json<Vec<OrphanUser>>, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can reconcile users".to_string(),
+        ));
+    }
+
+    let orphans = sqlx::query_as::<_, OrphanUser>(
+        r#"
+        SELECT u.id, u.username, u.person_id,
+               p.first_name || ' ' || p.last_name as person_name,
+               CASE WHEN p.id IS NULL THEN 'no_person' ELSE 'deleted_locally' END as reason,
+               u.created_at
+        FROM users u
+        LEFT JOIN people p ON p.id = u.person_id
+        WHERE u.role = 'servidor'
+          AND (p.id IS NULL OR NOT (p.id = ANY($1)))
+        ORDER BY u.username
+        "#,
+    )
+    .bind(&input.person_ids)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(orphans))
+}
@@ -0,0 +1,264 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::{Executor, PgConnection, PgPool, Postgres};
+use uuid::Uuid;
+
+use crate::auth::{self, Claims};
+use crate::db::tx::Tx;
+use crate::models::{CreateTeam, Team, TeamWithMembers};
+
+#[utoipa::path(
+    get,
+    path = "/teams",
+    tag = "teams",
+    responses((status = 200, body = Vec<TeamWithMembers>))
+)]
+pub async fn get_all(
+    State(pool): State<PgPool>,
+) -> Result<Json<Vec<TeamWithMembers>>, (StatusCode, String)> {
+    let teams = sqlx::query_as::<_, Team>("SELECT * FROM teams ORDER BY job_id, name")
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut result = Vec::new();
+    for team in teams {
+        let member_ids = get_member_ids(&pool, &team.id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+        result.push(TeamWithMembers { team, member_ids });
+    }
+
+    Ok(Json(result))
+}
+
+#[utoipa::path(
+    post,
+    path = "/teams",
+    tag = "teams",
+    responses((status = 200, body = TeamWithMembers))
+)]
+pub async fn create(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
+    Json(input): Json<CreateTeam>,
+) -> Result<Json<TeamWithMembers>, (StatusCode, String)> {
+    require_team_rights(&claims, &input.job_id)?;
+    validate_members(&pool, &input).await?;
+
+    let id = Uuid::new_v4().to_string();
+
+    let team = sqlx::query_as::<_, Team>(
+        r#"
+        INSERT INTO teams (id, name, job_id, active)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(&id)
+    .bind(&input.name)
+    .bind(&input.job_id)
+    .bind(input.active.unwrap_or(true))
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    insert_members(&mut tx, &id, &input.member_ids).await?;
+
+    Ok(Json(TeamWithMembers {
+        team,
+        member_ids: input.member_ids,
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/teams/{id}",
+    tag = "teams",
+    responses((status = 200, body = TeamWithMembers))
+)]
+pub async fn update(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    mut tx: Tx,
+    Path(id): Path<String>,
+    Json(input): Json<CreateTeam>,
+) -> Result<Json<TeamWithMembers>, (StatusCode, String)> {
+    // Both the team's current job and the one it moves to
+    require_team_rights(&claims, &team_job_id(&pool, &id).await?)?;
+    require_team_rights(&claims, &input.job_id)?;
+    validate_members(&pool, &input).await?;
+
+    let team = sqlx::query_as::<_, Team>(
+        r#"
+        UPDATE teams
+        SET name = $1, job_id = $2, active = COALESCE($3, active)
+        WHERE id = $4
+        RETURNING *
+        "#,
+    )
+    .bind(&input.name)
+    .bind(&input.job_id)
+    .bind(input.active)
+    .bind(&id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .ok_or((StatusCode::NOT_FOUND, "Team not found".to_string()))?;
+
+    // Update members - delete existing and re-add
+    sqlx::query("DELETE FROM team_members WHERE team_id = $1")
+        .bind(&id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    insert_members(&mut tx, &id, &input.member_ids).await?;
+
+    Ok(Json(TeamWithMembers {
+        team,
+        member_ids: input.member_ids,
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/teams/{id}",
+    tag = "teams",
+    responses((status = 204))
+)]
+pub async fn delete(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    require_team_rights(&claims, &team_job_id(&pool, &id).await?)?;
+
+    let result = sqlx::query("DELETE FROM teams WHERE id = $1")
+        .bind(&id)
+        .execute(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "Team not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============ Helpers ============
+
+/// Admins, or a coordinator of the team's job
+fn require_team_rights(claims: &Claims, job_id: &str) -> Result<(), (StatusCode, String)> {
+    auth::require_admin_or_coordinator(claims, "manage teams")?;
+    auth::require_job_scope(claims, job_id)
+}
+
+async fn team_job_id(pool: &PgPool, team_id: &str) -> Result<String, (StatusCode, String)> {
+    sqlx::query_scalar("SELECT job_id FROM teams WHERE id = $1")
+        .bind(team_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Team not found".to_string()))
+}
+
+pub async fn get_member_ids<'e, E>(executor: E, team_id: &str) -> Result<Vec<String>, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    sqlx::query_scalar("SELECT person_id FROM team_members WHERE team_id = $1 ORDER BY created_at")
+        .bind(team_id)
+        .fetch_all(executor)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Members must be distinct, existing people qualified for the team's job
+async fn validate_members(pool: &PgPool, input: &CreateTeam) -> Result<(), (StatusCode, String)> {
+    if input.member_ids.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "A team needs at least one member".to_string(),
+        ));
+    }
+
+    let mut seen: Vec<&String> = Vec::new();
+    for member_id in &input.member_ids {
+        if seen.contains(&member_id) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Member listed twice: {}", member_id),
+            ));
+        }
+        seen.push(member_id);
+    }
+
+    let known: Vec<String> = sqlx::query_scalar("SELECT id FROM people WHERE id = ANY($1)")
+        .bind(&input.member_ids)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let unknown: Vec<&str> = input
+        .member_ids
+        .iter()
+        .filter(|id| !known.contains(id))
+        .map(|id| id.as_str())
+        .collect();
+    if !unknown.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Unknown person: {}", unknown.join(", ")),
+        ));
+    }
+
+    let unqualified: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT p.first_name || ' ' || p.last_name
+        FROM people p
+        WHERE p.id = ANY($1)
+          AND NOT EXISTS (
+              SELECT 1 FROM person_jobs pj WHERE pj.person_id = p.id AND pj.job_id = $2
+          )
+        "#,
+    )
+    .bind(&input.member_ids)
+    .bind(&input.job_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if !unqualified.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Not qualified for this job: {}", unqualified.join(", ")),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn insert_members(
+    conn: &mut PgConnection,
+    team_id: &str,
+    member_ids: &[String],
+) -> Result<(), (StatusCode, String)> {
+    for member_id in member_ids {
+        let tm_id = Uuid::new_v4().to_string();
+        sqlx::query("INSERT INTO team_members (id, team_id, person_id) VALUES ($1, $2, $3)")
+            .bind(&tm_id)
+            .bind(team_id)
+            .bind(member_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,232 @@
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::Claims;
+use crate::models::{RecoveryCodes, TwoFactorCodeRequest, TwoFactorSetup, TwoFactorStatus};
+use crate::services::auth_events::{self, AuthEvent, RequestOrigin};
+use crate::services::totp;
+use crate::services::two_factor::{self, SecondFactor};
+
+fn user_id(claims: &Claims) -> Result<Uuid, (StatusCode, String)> {
+    Uuid::parse_str(&claims.sub).map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid user".to_string()))
+}
+
+async fn record(
+    pool: &PgPool,
+    claims: &Claims,
+    headers: &HeaderMap,
+    event: &str,
+    detail: Option<&str>,
+) -> Result<(), (StatusCode, String)> {
+    let event = AuthEvent {
+        event,
+        user_id: Uuid::parse_str(&claims.sub).ok(),
+        username: Some(&claims.username),
+        detail,
+    };
+    auth_events::record(pool, &event, &RequestOrigin::from_headers(headers))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))
+}
+
+/// Turning two-factor off or getting new recovery codes needs a current code, so a token
+/// left on an unlocked computer is not enough. Wrong codes count towards the login lockout.
+async fn require_code(
+    pool: &PgPool,
+    claims: &Claims,
+    headers: &HeaderMap,
+    code: &str,
+) -> Result<(), (StatusCode, String)> {
+    let second_factor = two_factor::check_login(pool, user_id(claims)?, Some(code))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    match second_factor {
+        SecondFactor::NotEnrolled => Err((
+            StatusCode::CONFLICT,
+            "Two-factor authentication is not on".to_string(),
+        )),
+        SecondFactor::Passed | SecondFactor::PassedWithRecoveryCode => Ok(()),
+        other => {
+            let reason = other.refusal().unwrap_or(two_factor::WRONG_CODE);
+            record(pool, claims, headers, "two_factor_failed", Some(reason)).await?;
+            Err((StatusCode::UNAUTHORIZED, reason.to_string()))
+        }
+    }
+}
+
+/// Whether the signed-in user has two-factor on, and how many recovery codes are left
+#[utoipa::path(
+    get,
+    path = "/auth/two-factor",
+    tag = "two-factor",
+    responses((status = 200, body = TwoFactorStatus))
+)]
+pub async fn get_status(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<TwoFactorStatus>, (StatusCode, String)> {
+    let status = two_factor::status(&pool, user_id(&claims)?)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(status))
+}
+
+/// A new secret to add to the authenticator app. Nothing changes at login until `enable`
+/// confirms a code from it.
+#[utoipa::path(
+    post,
+    path = "/auth/two-factor/setup",
+    tag = "two-factor",
+    responses((status = 200, body = TwoFactorSetup))
+)]
+pub async fn setup(
+    State(pool): State<PgPool>,
+    claims: Claims,
+) -> Result<Json<TwoFactorSetup>, (StatusCode, String)> {
+    if !two_factor::ROLES.contains(&claims.role.as_str()) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Two-factor authentication is for admin and coordinator accounts".to_string(),
+        ));
+    }
+
+    let secret = two_factor::start_setup(&pool, user_id(&claims)?)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((
+            StatusCode::CONFLICT,
+            "Two-factor authentication is already on".to_string(),
+        ))?;
+
+    Ok(Json(TwoFactorSetup {
+        otpauth_uri: totp::otpauth_uri(&claims.username, &secret),
+        secret,
+    }))
+}
+
+/// Confirm the app works with one of its codes; answers with the recovery codes
+#[utoipa::path(
+    post,
+    path = "/auth/two-factor/enable",
+    tag = "two-factor",
+    responses((status = 200, body = RecoveryCodes))
+)]
+pub async fn enable(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    headers: HeaderMap,
+    Json(input): Json<TwoFactorCodeRequest>,
+) -> Result<Json<RecoveryCodes>, (StatusCode, String)> {
+    let recovery_codes = two_factor::enable(&pool, user_id(&claims)?, &input.code)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((
+            StatusCode::BAD_REQUEST,
+            "The code does not match; start the setup first and check the phone's clock"
+                .to_string(),
+        ))?;
+    record(&pool, &claims, &headers, "two_factor_enabled", None).await?;
+
+    Ok(Json(RecoveryCodes { recovery_codes }))
+}
+
+/// Turn two-factor off, with a code from the app or a recovery code
+#[utoipa::path(
+    post,
+    path = "/auth/two-factor/disable",
+    tag = "two-factor",
+    responses((status = 204))
+)]
+pub async fn disable(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    headers: HeaderMap,
+    Json(input): Json<TwoFactorCodeRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    require_code(&pool, &claims, &headers, &input.code).await?;
+
+    two_factor::disable(&pool, user_id(&claims)?)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    record(&pool, &claims, &headers, "two_factor_disabled", None).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// New recovery codes in place of the old ones
+#[utoipa::path(
+    post,
+    path = "/auth/two-factor/recovery-codes",
+    tag = "two-factor",
+    responses((status = 200, body = RecoveryCodes))
+)]
+pub async fn regenerate_recovery_codes(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    headers: HeaderMap,
+    Json(input): Json<TwoFactorCodeRequest>,
+) -> Result<Json<RecoveryCodes>, (StatusCode, String)> {
+    require_code(&pool, &claims, &headers, &input.code).await?;
+
+    let recovery_codes = two_factor::replace_recovery_codes(&pool, user_id(&claims)?)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(Json(RecoveryCodes { recovery_codes }))
+}
+
+/// For a user who lost both their phone and recovery codes: they sign in with the password
+/// alone and can set two-factor up again
+#[utoipa::path(
+    delete,
+    path = "/admin/users/{id}/two-factor",
+    tag = "two-factor",
+    responses((status = 204))
+)]
+pub async fn reset_for_user(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    if !claims.is_admin() {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Only admins can reset two-factor authentication".to_string(),
+        ));
+    }
+    let target =
+        Uuid::parse_str(&id).map_err(|_| (StatusCode::NOT_FOUND, "User not found".to_string()))?;
+
+    let username: Option<String> = sqlx::query_scalar("SELECT username FROM users WHERE id = $1")
+        .bind(target)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let username = username.ok_or((StatusCode::NOT_FOUND, "User not found".to_string()))?;
+
+    two_factor::disable(&pool, target)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let detail = format!("Reset by {}", claims.username);
+    auth_events::record(
+        &pool,
+        &AuthEvent {
+            event: "two_factor_disabled",
+            user_id: Some(target),
+            username: Some(&username),
+            detail: Some(&detail),
+        },
+        &RequestOrigin::from_headers(&headers),
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
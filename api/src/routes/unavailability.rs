@@ -1,23 +1,69 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
 use chrono::{DateTime, NaiveDate, Utc};
-use serde::Deserialize;
-use sqlx::{FromRow, PgPool};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgConnection, PgPool};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use crate::auth::Claims;
-use crate::models::{CreateUnavailability, Unavailability, UnavailabilityWithPerson};
+use crate::auth::{self, Claims};
+use crate::db::filters::Filter;
+use crate::db::tx::Tx;
+use crate::models::{
+    CreatePersonJobBlackout, CreateUnavailability, PersonJobBlackout, Unavailability,
+    UnavailabilityWithPerson,
+};
+use crate::services::recurrence::Recurrence;
+
+/// How many upcoming dates of a recurring record the list endpoints spell out
+const LISTED_OCCURRENCES: usize = 8;
+
+/// Fill in the next dates a recurring record covers, from today to its end date
+fn expand_occurrences(unavailability: &mut Unavailability) {
+    let Some(recurrence) = unavailability
+        .recurrence
+        .as_deref()
+        .and_then(|rule| Recurrence::parse(rule).ok())
+    else {
+        return;
+    };
+    let from = unavailability.start_date.max(Utc::now().date_naive());
+    unavailability.occurrences =
+        recurrence.occurrences(from, unavailability.end_date, LISTED_OCCURRENCES);
+}
 
 // Input for servidor self-service unavailability
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateMyUnavailability {
     pub dates: Vec<NaiveDate>, // List of dates to mark as unavailable
     pub reason: Option<String>,
 }
 
+/// `?person_id=` on the self-service routes: a guardian acting for one of their children
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OnBehalfQuery {
+    pub person_id: Option<String>,
+}
+
+/// Body of `PUT /my-availability/{date}`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetMyAvailability {
+    pub available: bool,
+    pub reason: Option<String>,
+}
+
+/// A day's availability after a toggle; `unavailability` is the single-day record, if any
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MyAvailability {
+    pub date: NaiveDate,
+    pub available: bool,
+    pub unavailability: Option<Unavailability>,
+}
+
 #[derive(FromRow)]
 struct UnavailabilityRow {
     id: String,
@@ -26,92 +72,196 @@ struct UnavailabilityRow {
     end_date: NaiveDate,
     reason: Option<String>,
     recurring: Option<bool>,
+    recurrence: Option<String>,
     created_at: Option<DateTime<Utc>>,
     person_name: Option<String>,
 }
 
+impl UnavailabilityRow {
+    fn into_with_person(self) -> UnavailabilityWithPerson {
+        let mut unavailability = Unavailability {
+            id: self.id,
+            person_id: self.person_id,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            reason: self.reason,
+            recurring: self.recurring,
+            recurrence: self.recurrence,
+            created_at: self.created_at,
+            occurrences: Vec::new(),
+        };
+        expand_occurrences(&mut unavailability);
+        UnavailabilityWithPerson {
+            unavailability,
+            person_name: self.person_name.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UnavailabilityQuery {
+    pub person_id: Option<String>,
+    /// Only records still running on or after this date
+    pub from: Option<NaiveDate>,
+    /// Only records starting on or before this date
+    pub to: Option<NaiveDate>,
+    /// e.g. `-start_date` (default) or `person,start_date`
+    pub sort: Option<String>,
+}
+
+const UNAVAILABILITY_SORTS: &[(&str, &str)] = &[
+    ("start_date", "u.start_date"),
+    ("end_date", "u.end_date"),
+    ("person", "p.last_name"),
+    ("created_at", "u.created_at"),
+];
+
+#[utoipa::path(
+    get,
+    path = "/unavailability",
+    params(UnavailabilityQuery),
+    tag = "unavailability",
+    responses((status = 200, body = Vec<UnavailabilityWithPerson>))
+)]
 pub async fn get_all(
     State(pool): State<PgPool>,
+    claims: Claims,
+    Query(query): Query<UnavailabilityQuery>,
 ) -> Result<Json<Vec<UnavailabilityWithPerson>>, (StatusCode, String)> {
-    let rows = sqlx::query_as::<_, UnavailabilityRow>(
+    auth::require_admin_or_coordinator(&claims, "see everyone's unavailability")?;
+
+    let mut filter = Filter::new(
         r#"
         SELECT
-            u.id, u.person_id, u.start_date, u.end_date, u.reason, u.recurring, u.created_at,
-            p.first_name || ' ' || p.last_name as person_name
+            u.id, u.person_id, u.start_date, u.end_date, u.reason, u.recurring, u.recurrence,
+            u.created_at, p.first_name || ' ' || p.last_name as person_name
         FROM unavailability u
         JOIN people p ON u.person_id = p.id
-        ORDER BY u.start_date DESC
         "#,
-    )
-    .fetch_all(&pool)
-    .await
-    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    );
+    filter
+        .eq("u.person_id", query.person_id.as_ref())
+        .gte("u.end_date", query.from)
+        .lte("u.start_date", query.to)
+        .order_by(
+            query.sort.as_deref(),
+            UNAVAILABILITY_SORTS,
+            "u.start_date DESC",
+        )
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let rows = filter
+        .into_builder()
+        .build_query_as::<UnavailabilityRow>()
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     let result: Vec<UnavailabilityWithPerson> = rows
         .into_iter()
-        .map(|row| UnavailabilityWithPerson {
-            unavailability: Unavailability {
-                id: row.id,
-                person_id: row.person_id,
-                start_date: row.start_date,
-                end_date: row.end_date,
-                reason: row.reason,
-                recurring: row.recurring,
-                created_at: row.created_at,
-            },
-            person_name: row.person_name.unwrap_or_default(),
-        })
+        .map(UnavailabilityRow::into_with_person)
         .collect();
 
     Ok(Json(result))
 }
 
+#[utoipa::path(
+    post,
+    path = "/unavailability",
+    tag = "unavailability",
+    responses((status = 200, body = UnavailabilityWithPerson))
+)]
 pub async fn create(
     State(pool): State<PgPool>,
+    claims: Claims,
     Json(input): Json<CreateUnavailability>,
 ) -> Result<Json<UnavailabilityWithPerson>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "record unavailability for others")?;
+    auth::require_person_scope(&pool, &claims, &input.person_id).await?;
+
+    if input.end_date < input.start_date {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "end_date must be on or after start_date".to_string(),
+        ));
+    }
+
+    // A recurring record only covers the days of its period that match the pattern
+    let rule = input
+        .recurrence
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty());
+    let recurrence = rule
+        .map(Recurrence::parse)
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    if input.recurring == Some(true) && recurrence.is_none() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Recurring unavailability needs a recurrence pattern".to_string(),
+        ));
+    }
+
     let id = Uuid::new_v4().to_string();
 
     // Insert and fetch with person name in one query
     let row = sqlx::query_as::<_, UnavailabilityRow>(
         r#"
-        INSERT INTO unavailability (id, person_id, start_date, end_date, reason, recurring)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO unavailability (
+            id, person_id, start_date, end_date, reason, recurring,
+            recurrence, recurrence_weekday, recurrence_week
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
         RETURNING
-            id, person_id, start_date, end_date, reason, recurring, created_at,
+            id, person_id, start_date, end_date, reason, recurring, recurrence, created_at,
             (SELECT first_name || ' ' || last_name FROM people WHERE id = $2) as person_name
         "#,
     )
     .bind(&id)
     .bind(&input.person_id)
-    .bind(&input.start_date)
-    .bind(&input.end_date)
+    .bind(input.start_date)
+    .bind(input.end_date)
     .bind(&input.reason)
-    .bind(&input.recurring)
+    .bind(recurrence.is_some())
+    .bind(rule)
+    .bind(recurrence.map(|r| r.dow()))
+    .bind(recurrence.and_then(|r| r.week))
     .fetch_one(&pool)
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    let result = UnavailabilityWithPerson {
-        unavailability: Unavailability {
-            id: row.id,
-            person_id: row.person_id,
-            start_date: row.start_date,
-            end_date: row.end_date,
-            reason: row.reason,
-            recurring: row.recurring,
-            created_at: row.created_at,
-        },
-        person_name: row.person_name.unwrap_or_default(),
-    };
-
-    Ok(Json(result))
+    Ok(Json(row.into_with_person()))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/unavailability/{id}",
+    tag = "unavailability",
+    responses((status = 204))
+)]
 pub async fn delete(
     State(pool): State<PgPool>,
+    claims: Claims,
     Path(id): Path<String>,
 ) -> Result<StatusCode, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "remove unavailability for others")?;
+
+    if claims.is_coordinator() {
+        let person_id: String =
+            sqlx::query_scalar("SELECT person_id FROM unavailability WHERE id = $1")
+                .bind(&id)
+                .fetch_optional(&pool)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                .ok_or((
+                    StatusCode::NOT_FOUND,
+                    "Unavailability not found".to_string(),
+                ))?;
+        auth::require_person_scope(&pool, &claims, &person_id).await?;
+    }
+
     let result = sqlx::query("DELETE FROM unavailability WHERE id = $1")
         .bind(&id)
         .execute(&pool)
@@ -131,18 +281,23 @@ pub async fn delete(
 // ============ Self-service endpoints for servidores ============
 
 // Get my unavailability records
+#[utoipa::path(
+    get,
+    path = "/my-unavailability",
+    params(OnBehalfQuery),
+    tag = "unavailability",
+    responses((status = 200, body = Vec<Unavailability>))
+)]
 pub async fn get_my_unavailability(
     State(pool): State<PgPool>,
     claims: Claims,
+    Query(query): Query<OnBehalfQuery>,
 ) -> Result<Json<Vec<Unavailability>>, (StatusCode, String)> {
-    let person_id = claims.person_id.ok_or((
-        StatusCode::FORBIDDEN,
-        "No tiene un servidor vinculado a su cuenta".to_string(),
-    ))?;
+    let person_id = auth::acting_person(&pool, &claims, query.person_id.as_deref()).await?;
 
-    let records = sqlx::query_as::<_, Unavailability>(
+    let mut records = sqlx::query_as::<_, Unavailability>(
         r#"
-        SELECT id, person_id, start_date, end_date, reason, recurring, created_at
+        SELECT id, person_id, start_date, end_date, reason, recurring, recurrence, created_at
         FROM unavailability
         WHERE person_id = $1
         ORDER BY start_date ASC
@@ -153,19 +308,26 @@ pub async fn get_my_unavailability(
     .await
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
+    records.iter_mut().for_each(expand_occurrences);
+
     Ok(Json(records))
 }
 
 // Create my unavailability (multiple dates at once)
+#[utoipa::path(
+    post,
+    path = "/my-unavailability",
+    params(OnBehalfQuery),
+    tag = "unavailability",
+    responses((status = 200, body = Vec<Unavailability>))
+)]
 pub async fn create_my_unavailability(
-    State(pool): State<PgPool>,
     claims: Claims,
+    mut tx: Tx,
+    Query(query): Query<OnBehalfQuery>,
     Json(input): Json<CreateMyUnavailability>,
 ) -> Result<Json<Vec<Unavailability>>, (StatusCode, String)> {
-    let person_id = claims.person_id.ok_or((
-        StatusCode::FORBIDDEN,
-        "No tiene un servidor vinculado a su cuenta".to_string(),
-    ))?;
+    let person_id = auth::acting_person(&mut *tx, &claims, query.person_id.as_deref()).await?;
 
     if input.dates.is_empty() {
         return Err((
@@ -188,9 +350,9 @@ pub async fn create_my_unavailability(
         )
         .bind(&id)
         .bind(&person_id)
-        .bind(&date)
+        .bind(date)
         .bind(&input.reason)
-        .fetch_one(&pool)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
@@ -201,15 +363,20 @@ pub async fn create_my_unavailability(
 }
 
 // Delete my unavailability (only if it belongs to me)
+#[utoipa::path(
+    delete,
+    path = "/my-unavailability/{id}",
+    params(OnBehalfQuery),
+    tag = "unavailability",
+    responses((status = 204))
+)]
 pub async fn delete_my_unavailability(
     State(pool): State<PgPool>,
     claims: Claims,
     Path(id): Path<String>,
+    Query(query): Query<OnBehalfQuery>,
 ) -> Result<StatusCode, (StatusCode, String)> {
-    let person_id = claims.person_id.ok_or((
-        StatusCode::FORBIDDEN,
-        "No tiene un servidor vinculado a su cuenta".to_string(),
-    ))?;
+    let person_id = auth::acting_person(&pool, &claims, query.person_id.as_deref()).await?;
 
     // Only delete if it belongs to the authenticated user
     let result = sqlx::query("DELETE FROM unavailability WHERE id = $1 AND person_id = $2")
@@ -228,3 +395,253 @@ pub async fn delete_my_unavailability(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+// Mark one day available or unavailable. Idempotent, so the calendar can send the state it
+// wants without first looking up the id of the record to delete.
+#[utoipa::path(
+    put,
+    path = "/my-availability/{date}",
+    params(OnBehalfQuery),
+    tag = "unavailability",
+    responses((status = 200, body = MyAvailability))
+)]
+pub async fn set_my_availability(
+    claims: Claims,
+    mut tx: Tx,
+    Path(date): Path<NaiveDate>,
+    Query(query): Query<OnBehalfQuery>,
+    Json(input): Json<SetMyAvailability>,
+) -> Result<Json<MyAvailability>, (StatusCode, String)> {
+    let person_id = auth::acting_person(&mut *tx, &claims, query.person_id.as_deref()).await?;
+
+    let availability = set_availability(
+        &mut tx,
+        &person_id,
+        date,
+        input.available,
+        input.reason.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(availability))
+}
+
+/// Mark one of the person's days available or unavailable, shared by the calendar and the
+/// chat bot. Runs on the caller's transaction.
+pub async fn set_availability(
+    conn: &mut PgConnection,
+    person_id: &str,
+    date: NaiveDate,
+    available: bool,
+    reason: Option<&str>,
+) -> Result<MyAvailability, (StatusCode, String)> {
+    // Serialize toggles for the same person so two clicks cannot create two records
+    sqlx::query("SELECT id FROM people WHERE id = $1 FOR UPDATE")
+        .bind(person_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let single_day = sqlx::query_as::<_, Unavailability>(
+        r#"
+        SELECT * FROM unavailability
+        WHERE person_id = $1 AND start_date = $2 AND end_date = $2 AND recurrence IS NULL
+        ORDER BY created_at
+        "#,
+    )
+    .bind(person_id)
+    .bind(date)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if available {
+        // A longer or recurring absence cannot lose a single day from here
+        let covered_by_other: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM unavailability u
+                WHERE u.person_id = $1 AND unavailability_matches(u, $2)
+                  AND NOT (u.start_date = $2 AND u.end_date = $2 AND u.recurrence IS NULL)
+            )
+            "#,
+        )
+        .bind(person_id)
+        .bind(date)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        if covered_by_other {
+            return Err((
+                StatusCode::CONFLICT,
+                "La fecha es parte de una ausencia de varios días; modifíquela desde sus ausencias"
+                    .to_string(),
+            ));
+        }
+
+        sqlx::query(
+            r#"
+            DELETE FROM unavailability
+            WHERE person_id = $1 AND start_date = $2 AND end_date = $2 AND recurrence IS NULL
+            "#,
+        )
+        .bind(person_id)
+        .bind(date)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        return Ok(MyAvailability {
+            date,
+            available: true,
+            unavailability: None,
+        });
+    }
+
+    let reason = reason.map(str::trim).filter(|r| !r.is_empty());
+    let unavailability = match single_day.into_iter().next() {
+        // Already marked: keep the record, only the reason may change
+        Some(existing) if reason.is_none() => existing,
+        Some(existing) => sqlx::query_as::<_, Unavailability>(
+            "UPDATE unavailability SET reason = $1 WHERE id = $2 RETURNING *",
+        )
+        .bind(reason)
+        .bind(&existing.id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+        None => sqlx::query_as::<_, Unavailability>(
+            r#"
+            INSERT INTO unavailability (id, person_id, start_date, end_date, reason, recurring)
+            VALUES ($1, $2, $3, $3, $4, false)
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(person_id)
+        .bind(date)
+        .bind(reason)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+    };
+
+    Ok(MyAvailability {
+        date,
+        available: false,
+        unavailability: Some(unavailability),
+    })
+}
+
+// ============ Per-job blackout months (admin) ============
+
+#[utoipa::path(
+    get,
+    path = "/people/{id}/blackouts",
+    tag = "unavailability",
+    responses((status = 200, body = Vec<PersonJobBlackout>))
+)]
+pub async fn get_person_blackouts(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+) -> Result<Json<Vec<PersonJobBlackout>>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "see job blackouts")?;
+
+    let blackouts = sqlx::query_as::<_, PersonJobBlackout>(
+        "SELECT * FROM person_job_blackouts WHERE person_id = $1 ORDER BY job_id",
+    )
+    .bind(&person_id)
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(blackouts))
+}
+
+/// Create or replace the blackout months of a person for one job
+#[utoipa::path(
+    put,
+    path = "/people/{id}/blackouts",
+    tag = "unavailability",
+    responses((status = 200, body = PersonJobBlackout))
+)]
+pub async fn set_person_blackout(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(person_id): Path<String>,
+    Json(input): Json<CreatePersonJobBlackout>,
+) -> Result<Json<PersonJobBlackout>, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "set job blackouts")?;
+    auth::require_job_scope(&claims, &input.job_id)?;
+    auth::require_person_scope(&pool, &claims, &person_id).await?;
+
+    if input.months.is_empty() || input.months.iter().any(|m| !(1..=12).contains(m)) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "months must be a non-empty list of values between 1 and 12".to_string(),
+        ));
+    }
+
+    let mut months = input.months.clone();
+    months.sort_unstable();
+    months.dedup();
+
+    let id = Uuid::new_v4().to_string();
+    let blackout = sqlx::query_as::<_, PersonJobBlackout>(
+        r#"
+        INSERT INTO person_job_blackouts (id, person_id, job_id, months, reason)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (person_id, job_id) DO UPDATE
+        SET months = EXCLUDED.months, reason = EXCLUDED.reason
+        RETURNING *
+        "#,
+    )
+    .bind(&id)
+    .bind(&person_id)
+    .bind(&input.job_id)
+    .bind(&months)
+    .bind(&input.reason)
+    .fetch_one(&pool)
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(blackout))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/blackouts/{id}",
+    tag = "unavailability",
+    responses((status = 204))
+)]
+pub async fn delete_blackout(
+    State(pool): State<PgPool>,
+    claims: Claims,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    auth::require_admin_or_coordinator(&claims, "set job blackouts")?;
+
+    if claims.is_coordinator() {
+        let job_id: String =
+            sqlx::query_scalar("SELECT job_id FROM person_job_blackouts WHERE id = $1")
+                .bind(&id)
+                .fetch_optional(&pool)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                .ok_or((StatusCode::NOT_FOUND, "Blackout not found".to_string()))?;
+        auth::require_job_scope(&claims, &job_id)?;
+    }
+
+    let result = sqlx::query("DELETE FROM person_job_blackouts WHERE id = $1")
+        .bind(&id)
+        .execute(&pool)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if result.rows_affected() == 0 {
+        return Err((StatusCode::NOT_FOUND, "Blackout not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
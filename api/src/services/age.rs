@@ -0,0 +1,49 @@
+use chrono::{Datelike, NaiveDate};
+
+/// Age in whole years on the given date (e.g. a service date)
+pub fn age_on(birth_date: NaiveDate, date: NaiveDate) -> i32 {
+    let mut age = date.year() - birth_date.year();
+    if (date.month(), date.day()) < (birth_date.month(), birth_date.day()) {
+        age -= 1;
+    }
+    age
+}
+
+/// Birthday falling in the given year; Feb 29 birthdays fall on Feb 28 in common years
+pub fn birthday_in_year(birth_date: NaiveDate, year: i32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, birth_date.month(), birth_date.day())
+        .or_else(|| NaiveDate::from_ymd_opt(year, birth_date.month(), birth_date.day() - 1))
+        .unwrap_or(birth_date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn age_counts_the_birthday_itself() {
+        assert_eq!(age_on(date(2012, 5, 10), date(2024, 5, 10)), 12);
+    }
+
+    #[test]
+    fn age_before_birthday_is_one_less() {
+        assert_eq!(age_on(date(2012, 5, 10), date(2024, 5, 9)), 11);
+        assert_eq!(age_on(date(2012, 12, 31), date(2024, 1, 1)), 11);
+    }
+
+    #[test]
+    fn leap_day_birthday_moves_to_feb_28_in_common_years() {
+        assert_eq!(birthday_in_year(date(2012, 2, 29), 2023), date(2023, 2, 28));
+        assert_eq!(birthday_in_year(date(2012, 2, 29), 2024), date(2024, 2, 29));
+    }
+
+    #[test]
+    fn leap_day_child_turns_a_year_older_on_march_1_in_common_years() {
+        assert_eq!(age_on(date(2012, 2, 29), date(2023, 2, 28)), 10);
+        assert_eq!(age_on(date(2012, 2, 29), date(2023, 3, 1)), 11);
+    }
+}
@@ -0,0 +1,215 @@
+//! Long-lived API keys for read-only integrations: the parish website pulling the published
+//! assignments, the sacristy display pulling the next service. A key is "psk_<id>.<secret>",
+//! sent in the `X-Api-Key` header, and only reaches the endpoints of its scopes.
+//!
+//! The secret is random, so a SHA-256 digest is enough to store it and is cheap to check on
+//! every request, unlike a password hash. Keys created before that hold an Argon2 hash, which
+//! is replaced by the digest the first time the key is used.
+
+use sqlx::PgPool;
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+use crate::auth::verify_password;
+use crate::models::ApiKey;
+use crate::services::tokens;
+
+pub const HEADER: &str = "x-api-key";
+
+/// What a key may read; each scope is one `/integrations/...` endpoint
+pub const SCOPES: &[&str] = &["assignments:read", "next_service:read"];
+
+/// Marks the keys as ours, for secret scanners and for whoever finds one in a config file
+const PREFIX: &str = "psk_";
+
+const API_KEY_COLUMNS: &str = "id, name, scopes, created_by, created_at, last_used_at, revoked_at";
+
+/// Sorted, without repeats; an error names the first unknown scope
+pub fn normalize_scopes(scopes: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = scopes.iter().map(|s| s.trim().to_string()).collect();
+    normalized.sort();
+    normalized.dedup();
+    if normalized.is_empty() {
+        return Err("At least one scope is required".to_string());
+    }
+    if let Some(unknown) = normalized.iter().find(|s| !SCOPES.contains(&s.as_str())) {
+        return Err(format!(
+            "Unknown scope '{}'; expected one of: {}",
+            unknown,
+            SCOPES.join(", ")
+        ));
+    }
+    Ok(normalized)
+}
+
+/// Whether `secret` is the one `key_hash` was made from, comparing digests in constant time
+fn secret_matches(secret: &str, key_hash: &str) -> bool {
+    tokens::digest(secret)
+        .as_bytes()
+        .ct_eq(key_hash.as_bytes())
+        .into()
+}
+
+/// The key id and secret of a key
+pub fn split_key(key: &str) -> Option<(&str, &str)> {
+    let (id, secret) = key.trim().strip_prefix(PREFIX)?.split_once('.')?;
+    if id.is_empty() || secret.is_empty() {
+        return None;
+    }
+    Some((id, secret))
+}
+
+pub async fn list(pool: &PgPool) -> Result<Vec<ApiKey>, String> {
+    sqlx::query_as::<_, ApiKey>(&format!(
+        "SELECT {} FROM api_keys ORDER BY revoked_at IS NOT NULL, name",
+        API_KEY_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// A new key; returns it with the full key, which is not stored and cannot be shown again
+pub async fn create(
+    pool: &PgPool,
+    name: &str,
+    scopes: &[String],
+    created_by: &str,
+) -> Result<(ApiKey, String), String> {
+    let id = Uuid::new_v4().simple().to_string();
+    let secret = tokens::generate_token();
+    let key_hash = tokens::digest(&secret);
+
+    let api_key = sqlx::query_as::<_, ApiKey>(&format!(
+        r#"
+        INSERT INTO api_keys (id, name, key_hash, scopes, created_by)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING {}
+        "#,
+        API_KEY_COLUMNS
+    ))
+    .bind(&id)
+    .bind(name)
+    .bind(&key_hash)
+    .bind(scopes)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok((api_key, format!("{}{}.{}", PREFIX, id, secret)))
+}
+
+/// Change what a key may read; None when there is no such key still in use
+pub async fn set_scopes(
+    pool: &PgPool,
+    id: &str,
+    scopes: &[String],
+) -> Result<Option<ApiKey>, String> {
+    sqlx::query_as::<_, ApiKey>(&format!(
+        "UPDATE api_keys SET scopes = $2 WHERE id = $1 AND revoked_at IS NULL RETURNING {}",
+        API_KEY_COLUMNS
+    ))
+    .bind(id)
+    .bind(scopes)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// The key stops working at once; it stays listed as revoked. False when it was not in use.
+pub async fn revoke(pool: &PgPool, id: &str) -> Result<bool, String> {
+    let result =
+        sqlx::query("UPDATE api_keys SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL")
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// The scopes of a key in use, noting when it was last used; None for a wrong or revoked key
+pub async fn authenticate(pool: &PgPool, key: &str) -> Result<Option<Vec<String>>, String> {
+    let Some((id, secret)) = split_key(key) else {
+        return Ok(None);
+    };
+    let found: Option<(String, Vec<String>)> = sqlx::query_as(
+        "SELECT key_hash, scopes FROM api_keys WHERE id = $1 AND revoked_at IS NULL",
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some((key_hash, scopes)) = found else {
+        return Ok(None);
+    };
+    if key_hash.starts_with("$argon2") {
+        if !verify_password(secret, &key_hash) {
+            return Ok(None);
+        }
+        sqlx::query("UPDATE api_keys SET key_hash = $2 WHERE id = $1")
+            .bind(id)
+            .bind(tokens::digest(secret))
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    } else if !secret_matches(secret, &key_hash) {
+        return Ok(None);
+    }
+
+    // At most once a minute, so a display polling often does not write on every request
+    sqlx::query(
+        r#"
+        UPDATE api_keys SET last_used_at = NOW()
+        WHERE id = $1 AND (last_used_at IS NULL OR last_used_at < NOW() - INTERVAL '1 minute')
+        "#,
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(Some(scopes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn scopes_are_known_sorted_and_unique() {
+        assert_eq!(
+            normalize_scopes(&strings(&[
+                "next_service:read",
+                " assignments:read",
+                "next_service:read"
+            ])),
+            Ok(strings(&["assignments:read", "next_service:read"]))
+        );
+        assert!(normalize_scopes(&[]).is_err());
+        assert!(normalize_scopes(&strings(&["people:write"])).is_err());
+    }
+
+    #[test]
+    fn keys_split_into_id_and_secret() {
+        assert_eq!(split_key(" psk_abc.def "), Some(("abc", "def")));
+        assert_eq!(split_key("abc.def"), None);
+        assert_eq!(split_key("psk_abc"), None);
+        assert_eq!(split_key("psk_.def"), None);
+        assert_eq!(split_key("psk_abc."), None);
+    }
+
+    #[test]
+    fn only_the_right_secret_matches_its_digest() {
+        let key_hash = tokens::digest("s3cr3t");
+        assert_eq!(key_hash.len(), 64);
+        assert!(secret_matches("s3cr3t", &key_hash));
+        assert!(!secret_matches("s3cr3T", &key_hash));
+        assert!(!secret_matches("s3cr3t", &key_hash[..63]));
+    }
+}
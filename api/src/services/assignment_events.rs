@@ -0,0 +1,51 @@
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::models::Assignment;
+use crate::services::change_notices;
+
+/// Log a change to an assignment slot. `before` is the slot as it was, `person_id` who is in it now.
+/// When the slot's job is published, the people leaving and taking it are told once `conn`'s
+/// transaction commits (see `change_notices`).
+pub async fn record(
+    conn: &mut PgConnection,
+    before: &Assignment,
+    action: &str,
+    person_id: Option<&String>,
+    changed_by: Option<&str>,
+) -> Result<(), String> {
+    record_with_reason(conn, before, action, person_id, changed_by, None).await
+}
+
+/// Like `record`, keeping why the change was made despite a warning
+pub async fn record_with_reason(
+    conn: &mut PgConnection,
+    before: &Assignment,
+    action: &str,
+    person_id: Option<&String>,
+    changed_by: Option<&str>,
+    reason: Option<&str>,
+) -> Result<(), String> {
+    sqlx::query(
+        r#"
+        INSERT INTO assignment_events
+            (id, service_date_id, assignment_id, job_id, position, action, person_id, previous_person_id, changed_by, reason)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(&before.service_date_id)
+    .bind(&before.id)
+    .bind(&before.job_id)
+    .bind(before.position)
+    .bind(action)
+    .bind(person_id)
+    .bind(&before.person_id)
+    .bind(changed_by)
+    .bind(reason)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    change_notices::assignment_changed(conn, before, person_id).await
+}
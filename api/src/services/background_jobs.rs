@@ -0,0 +1,422 @@
+//! Long-running work kept out of request handlers. Jobs are rows in `background_jobs`; the
+//! local server runs a worker that polls them (`spawn_worker`). On Lambda each job is also
+//! sent to SQS (`job_queue`) and the worker Lambda runs it with `run_job`; a scheduled
+//! `POST /api/admin/background-jobs/run` picks up anything whose message went missing.
+//! A failed run is retried with a growing delay until `max_attempts`, then left as failed.
+//! The poller and the scheduled call also deliver the queued email, SMS and WhatsApp
+//! notifications, send the queued Web Push messages and bring the Google Sheets exports up
+//! to date.
+
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::{Executor, FromRow, PgPool, Postgres};
+use std::time::Duration as StdDuration;
+
+use crate::models::{BackgroundJob, GenerateScheduleBatchRequest};
+use crate::routes::{reminders, schedules};
+use crate::services::{job_queue, notifications, push, reminders as reminder_service, sheets};
+
+/// What can be queued
+pub const KINDS: &[&str] = &[
+    "generate_batch",
+    "send_reminders",
+    "send_digests",
+    "notify_published",
+];
+
+/// How often the local worker looks for due jobs
+const POLL_INTERVAL: StdDuration = StdDuration::from_secs(5);
+
+/// Most jobs one call to `run_pending` works through
+pub const RUN_LIMIT: usize = 10;
+
+/// A running job whose worker has not reported back in this long is taken to have died and
+/// is picked up again
+const STALE_MINUTES: i32 = 15;
+
+const JOB_SELECT: &str = r#"
+    SELECT id, kind, payload, status, attempts, max_attempts, run_after, result, error,
+           created_by, created_at, started_at, finished_at
+    FROM background_jobs
+"#;
+
+const JOB_RETURNING: &str = r#"
+    RETURNING id, kind, payload, status, attempts, max_attempts, run_after, result, error,
+              created_by, created_at, started_at, finished_at
+"#;
+
+#[derive(FromRow)]
+struct JobRow {
+    id: String,
+    kind: String,
+    payload: String,
+    status: String,
+    attempts: i32,
+    max_attempts: i32,
+    run_after: chrono::DateTime<Utc>,
+    result: Option<String>,
+    error: Option<String>,
+    created_by: Option<String>,
+    created_at: chrono::DateTime<Utc>,
+    started_at: Option<chrono::DateTime<Utc>>,
+    finished_at: Option<chrono::DateTime<Utc>>,
+}
+
+/// Stored JSON that no longer parses is shown as the raw text rather than hidden
+fn parse_json(text: &str) -> Value {
+    serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_string()))
+}
+
+impl From<JobRow> for BackgroundJob {
+    fn from(row: JobRow) -> Self {
+        BackgroundJob {
+            id: row.id,
+            kind: row.kind,
+            payload: parse_json(&row.payload),
+            status: row.status,
+            attempts: row.attempts,
+            max_attempts: row.max_attempts,
+            run_after: row.run_after,
+            result: row.result.as_deref().map(parse_json),
+            error: row.error,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            started_at: row.started_at,
+            finished_at: row.finished_at,
+        }
+    }
+}
+
+/// The schedule whose people `notify_published` tells
+#[derive(Deserialize)]
+struct PublishedSchedule {
+    schedule_id: String,
+}
+
+/// The kind's payload, read the same way when queueing and when running
+enum Work {
+    GenerateBatch(GenerateScheduleBatchRequest),
+    SendReminders(reminders::ReminderQuery),
+    SendDigests,
+    NotifyPublished(PublishedSchedule),
+}
+
+fn parse_work(kind: &str, payload: &Value) -> Result<Work, String> {
+    let invalid = |e: serde_json::Error| format!("Invalid payload for {}: {}", kind, e);
+    match kind {
+        "generate_batch" => Ok(Work::GenerateBatch(
+            GenerateScheduleBatchRequest::deserialize(payload).map_err(invalid)?,
+        )),
+        "send_reminders" => Ok(Work::SendReminders(
+            reminders::ReminderQuery::deserialize(payload).map_err(invalid)?,
+        )),
+        "send_digests" => Ok(Work::SendDigests),
+        "notify_published" => Ok(Work::NotifyPublished(
+            PublishedSchedule::deserialize(payload).map_err(invalid)?,
+        )),
+        other => Err(format!(
+            "Unknown job kind {}; expected one of {}",
+            other,
+            KINDS.join(", ")
+        )),
+    }
+}
+
+/// Check a payload fits its kind, so a bad request fails when it is queued instead of in the
+/// worker
+pub fn validate(kind: &str, payload: &Value) -> Result<(), String> {
+    parse_work(kind, payload).map(|_| ())
+}
+
+/// Wait before the next try after `attempts` failed runs: 1, 2, 4... minutes, at most an hour
+pub fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.clamp(1, 7) - 1;
+    Duration::minutes((1i64 << exponent).min(60))
+}
+
+/// Hand a queued job to SQS. Failing to is only logged: the job is safely in the table, and
+/// the scheduled run call still finds it.
+async fn wake(job: &BackgroundJob) {
+    if let Err(e) = job_queue::send(&job.id, job.run_after).await {
+        tracing::warn!("{}", e);
+    }
+}
+
+/// Queue a job. Inside a transaction the SQS message can arrive before the commit; the worker
+/// then does not find the job yet and SQS delivers the message again.
+pub async fn enqueue<'e, E>(
+    executor: E,
+    kind: &str,
+    payload: &Value,
+    created_by: Option<&str>,
+) -> Result<BackgroundJob, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    validate(kind, payload)?;
+    let sql = format!(
+        "INSERT INTO background_jobs (id, kind, payload, created_by) VALUES ($1, $2, $3, $4) {}",
+        JOB_RETURNING
+    );
+    let row = sqlx::query_as::<_, JobRow>(&sql)
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(kind)
+        .bind(payload.to_string())
+        .bind(created_by)
+        .fetch_one(executor)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let job = row.into();
+    wake(&job).await;
+    Ok(job)
+}
+
+pub async fn get<'e, E>(executor: E, id: &str) -> Result<Option<BackgroundJob>, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let sql = format!("{} WHERE id = $1", JOB_SELECT);
+    let row = sqlx::query_as::<_, JobRow>(&sql)
+        .bind(id)
+        .fetch_optional(executor)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(row.map(Into::into))
+}
+
+/// Newest first, optionally only those in one status
+pub async fn list(
+    pool: &PgPool,
+    status: Option<&str>,
+    limit: i64,
+) -> Result<Vec<BackgroundJob>, String> {
+    let sql = format!(
+        "{} WHERE ($1::text IS NULL OR status = $1) ORDER BY created_at DESC LIMIT $2",
+        JOB_SELECT
+    );
+    let rows = sqlx::query_as::<_, JobRow>(&sql)
+        .bind(status)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(rows.into_iter().map(Into::into).collect())
+}
+
+/// Queue a failed job again with a fresh set of attempts; None when it is not failed
+pub async fn retry(pool: &PgPool, id: &str) -> Result<Option<BackgroundJob>, String> {
+    let sql = format!(
+        r#"
+        UPDATE background_jobs
+        SET status = 'queued', attempts = 0, run_after = NOW(), error = NULL,
+            started_at = NULL, finished_at = NULL
+        WHERE id = $1 AND status = 'failed'
+        {}
+        "#,
+        JOB_RETURNING
+    );
+    let row = sqlx::query_as::<_, JobRow>(&sql)
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let job = row.map(Into::into);
+    if let Some(job) = &job {
+        wake(job).await;
+    }
+    Ok(job)
+}
+
+/// Take the next due job (or that one job, with `only`), or one whose worker died, so no other
+/// worker runs it too
+async fn claim(pool: &PgPool, only: Option<&str>) -> Result<Option<BackgroundJob>, String> {
+    let sql = format!(
+        r#"
+        UPDATE background_jobs
+        SET status = 'running', attempts = attempts + 1, started_at = NOW()
+        WHERE id = (
+            SELECT id FROM background_jobs
+            WHERE ($2::text IS NULL OR id = $2)
+              AND ((status = 'queued' AND run_after <= NOW())
+                   OR (status = 'running' AND started_at < NOW() - make_interval(mins => $1)))
+            ORDER BY run_after
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED
+        )
+        {}
+        "#,
+        JOB_RETURNING
+    );
+    let row = sqlx::query_as::<_, JobRow>(&sql)
+        .bind(STALE_MINUTES)
+        .bind(only)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(row.map(Into::into))
+}
+
+async fn execute(pool: &PgPool, job: &BackgroundJob) -> Result<Value, String> {
+    match parse_work(&job.kind, &job.payload)? {
+        Work::GenerateBatch(input) => {
+            let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+            let summaries = schedules::run_batch(pool, &mut tx, &input)
+                .await
+                .map_err(|(_, e)| e)?;
+            tx.commit().await.map_err(|e| e.to_string())?;
+            serde_json::to_value(summaries).map_err(|e| e.to_string())
+        }
+        Work::SendReminders(query) => {
+            let run = reminders::reminder_run(pool, &query)
+                .await
+                .map_err(|(_, e)| e)?;
+            let sent = reminder_service::send_reminders(pool, run).await?;
+            serde_json::to_value(sent).map_err(|e| e.to_string())
+        }
+        Work::SendDigests => {
+            let sent = notifications::send_digests(pool).await?;
+            Ok(json!({ "sent": sent }))
+        }
+        Work::NotifyPublished(published) => {
+            let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+            let notified = schedules::notify_published(&mut tx, &published.schedule_id).await?;
+            tx.commit().await.map_err(|e| e.to_string())?;
+            Ok(json!({ "notified": notified }))
+        }
+    }
+}
+
+/// Record how a run went: done, queued for another try, or failed for good
+async fn finish(
+    pool: &PgPool,
+    job: &BackgroundJob,
+    outcome: Result<Value, String>,
+) -> Result<BackgroundJob, String> {
+    let sql = format!(
+        r#"
+        UPDATE background_jobs
+        SET status = $2, result = $3, error = $4, run_after = $5,
+            finished_at = CASE WHEN $2 = 'queued' THEN NULL ELSE NOW() END
+        WHERE id = $1
+        {}
+        "#,
+        JOB_RETURNING
+    );
+    let (status, result, error, run_after) = match outcome {
+        Ok(result) => ("done", Some(result.to_string()), None, job.run_after),
+        Err(e) if job.attempts < job.max_attempts => (
+            "queued",
+            None,
+            Some(e),
+            Utc::now() + retry_delay(job.attempts),
+        ),
+        Err(e) => ("failed", None, Some(e), job.run_after),
+    };
+    let row = sqlx::query_as::<_, JobRow>(&sql)
+        .bind(&job.id)
+        .bind(status)
+        .bind(result)
+        .bind(error)
+        .bind(run_after)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let job: BackgroundJob = row.into();
+    if job.status == "queued" {
+        wake(&job).await;
+    }
+    Ok(job)
+}
+
+/// Claim, run and finish one job
+async fn run_claimed(pool: &PgPool, job: BackgroundJob) -> Result<BackgroundJob, String> {
+    let outcome = execute(pool, &job).await;
+    if let Err(e) = &outcome {
+        tracing::warn!("Background job {} ({}) failed: {}", job.id, job.kind, e);
+    }
+    finish(pool, &job, outcome).await
+}
+
+/// Run the due jobs, up to `limit`, one after another. Returns each as it was left.
+pub async fn run_pending(pool: &PgPool, limit: usize) -> Result<Vec<BackgroundJob>, String> {
+    let mut ran = Vec::new();
+    while ran.len() < limit {
+        let Some(job) = claim(pool, None).await? else {
+            break;
+        };
+        ran.push(run_claimed(pool, job).await?);
+    }
+    Ok(ran)
+}
+
+/// Run the job an SQS message is about, if it is due and nobody else has it. A job not due yet
+/// (its retry delay is longer than SQS waits) is sent again; one already done, failed or being
+/// run is left alone. Errors when the job is not there (yet), so SQS delivers it again.
+pub async fn run_job(pool: &PgPool, id: &str) -> Result<Option<BackgroundJob>, String> {
+    if let Some(job) = claim(pool, Some(id)).await? {
+        return run_claimed(pool, job).await.map(Some);
+    }
+    let job = get(pool, id)
+        .await?
+        .ok_or_else(|| format!("Background job {} not found", id))?;
+    if job.status == "queued" {
+        wake(&job).await;
+    }
+    Ok(None)
+}
+
+/// Poll for due jobs for as long as the server runs
+pub fn spawn_worker(pool: PgPool) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_pending(&pool, RUN_LIMIT).await {
+                tracing::warn!("Background worker: {}", e);
+            }
+            if let Err(e) = notifications::send_queued(&pool).await {
+                tracing::warn!("Notification sender: {}", e);
+            }
+            if let Err(e) = push::send_pending(&pool).await {
+                tracing::warn!("Push sender: {}", e);
+            }
+            if let Err(e) = sheets::sync(&pool, None, false).await {
+                tracing::warn!("Sheet exports: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_back_off_up_to_an_hour() {
+        assert_eq!(retry_delay(1), Duration::minutes(1));
+        assert_eq!(retry_delay(2), Duration::minutes(2));
+        assert_eq!(retry_delay(3), Duration::minutes(4));
+        assert_eq!(retry_delay(7), Duration::minutes(60));
+        assert_eq!(retry_delay(20), Duration::minutes(60));
+    }
+
+    #[test]
+    fn payloads_are_checked_against_their_kind() {
+        let batch =
+            json!({ "start_year": 2027, "start_month": 1, "end_year": 2027, "end_month": 6 });
+        assert!(validate("generate_batch", &batch).is_ok());
+        assert!(validate("generate_batch", &json!({})).is_err());
+        assert!(validate("send_reminders", &json!({ "date": "2026-11-15" })).is_ok());
+        assert!(validate("send_digests", &json!({})).is_ok());
+        assert!(validate("notify_published", &json!({ "schedule_id": "s1" })).is_ok());
+        assert!(validate("notify_published", &json!({})).is_err());
+        assert!(validate("export_pdf", &json!({})).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use chrono::NaiveDate;
+use sqlx::PgConnection;
+
+use crate::models::Assignment;
+use crate::routes::settings;
+use crate::services::{chat_bot, notifications, push};
+
+/// One side of a slot change: who it is and their name, to tell the other side
+pub struct Party<'a> {
+    pub person_id: &'a str,
+    pub name: Option<&'a str>,
+}
+
+fn party<'a>(person_id: &'a str, names: &'a [(String, String)]) -> Party<'a> {
+    Party {
+        person_id,
+        name: names
+            .iter()
+            .find(|(id, _)| id == person_id)
+            .map(|(_, name)| name.as_str()),
+    }
+}
+
+/// What the person who left a slot and the one who took it are told, each with who is on the
+/// other side of the change
+pub fn change_messages(
+    job_name: &str,
+    position_name: Option<&str>,
+    date: NaiveDate,
+    previous: Option<&Party>,
+    now: Option<&Party>,
+) -> Vec<(String, String)> {
+    if previous.map(|p| p.person_id) == now.map(|p| p.person_id) {
+        return Vec::new();
+    }
+    let slot = match position_name {
+        Some(position) => format!("{} ({})", job_name, position),
+        None => job_name.to_string(),
+    };
+    let date = chat_bot::format_date(date);
+
+    let mut messages = Vec::new();
+    if let Some(previous) = previous {
+        let message = match now.and_then(|p| p.name) {
+            Some(name) => format!(
+                "Ya no estás asignado a {} el {}; ahora lo cubre {}.",
+                slot, date, name
+            ),
+            None => format!("Ya no estás asignado a {} el {}.", slot, date),
+        };
+        messages.push((previous.person_id.to_string(), message));
+    }
+    if let Some(now) = now {
+        let message = match previous.and_then(|p| p.name) {
+            Some(name) => format!("Te asignaron a {} el {} (antes: {}).", slot, date, name),
+            None => format!("Te asignaron a {} el {}.", slot, date),
+        };
+        messages.push((now.person_id.to_string(), message));
+    }
+    messages
+}
+
+/// Tell the people affected by a change to a slot whose job is already published, on the
+/// `change_channel` setting and with a push; drafts are still being worked on, so nobody
+/// hears about them. The notices are only queued on `conn` (see `notifications::send`): the
+/// worker delivers them after the change commits, and a change rolled back tells nobody.
+pub async fn assignment_changed(
+    conn: &mut PgConnection,
+    before: &Assignment,
+    person_id: Option<&String>,
+) -> Result<(), String> {
+    if before.person_id.as_ref() == person_id {
+        return Ok(());
+    }
+    let slot = sqlx::query_as::<_, (NaiveDate, String)>(
+        r#"
+        SELECT sd.service_date, j.name
+        FROM service_dates sd, jobs j
+        WHERE sd.id = $1 AND j.id = $2 AND job_is_published(sd.schedule_id, j.id)
+        "#,
+    )
+    .bind(&before.service_date_id)
+    .bind(&before.job_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some((date, job_name)) = slot else {
+        return Ok(());
+    };
+
+    let ids: Vec<&String> = before.person_id.iter().chain(person_id).collect();
+    let names: Vec<(String, String)> =
+        sqlx::query_as("SELECT id, first_name || ' ' || last_name FROM people WHERE id = ANY($1)")
+            .bind(&ids)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+    let previous = before.person_id.as_deref().map(|id| party(id, &names));
+    let now = person_id.map(|id| party(id, &names));
+
+    let channel = settings::get_string(&mut *conn, "change_channel")
+        .await?
+        .map(|channel| channel.trim().to_string())
+        .filter(|channel| !channel.is_empty());
+
+    for (person_id, message) in change_messages(
+        &job_name,
+        before.position_name.as_deref(),
+        date,
+        previous.as_ref(),
+        now.as_ref(),
+    ) {
+        if let Some(channel) = &channel {
+            notifications::send(
+                &mut *conn,
+                channel,
+                &person_id,
+                "assignment_changed",
+                &message,
+            )
+            .await?;
+        }
+        if channel.as_deref() != Some("push") {
+            push::enqueue_extra(&mut *conn, &person_id, "assignment_changed", &message).await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_people_hear_about_a_swap() {
+        let date = NaiveDate::from_ymd_opt(2026, 11, 15).unwrap();
+        let ana = Party {
+            person_id: "ana",
+            name: Some("Ana López"),
+        };
+        let luis = Party {
+            person_id: "luis",
+            name: Some("Luis Pérez"),
+        };
+        assert_eq!(
+            change_messages("Lectores", Some("Salmo"), date, Some(&ana), Some(&luis)),
+            vec![
+                (
+                    "ana".to_string(),
+                    "Ya no estás asignado a Lectores (Salmo) el 15/11; ahora lo cubre Luis Pérez."
+                        .to_string()
+                ),
+                (
+                    "luis".to_string(),
+                    "Te asignaron a Lectores (Salmo) el 15/11 (antes: Ana López).".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            change_messages("Coro", None, date, None, Some(&luis)),
+            vec![(
+                "luis".to_string(),
+                "Te asignaron a Coro el 15/11.".to_string()
+            )]
+        );
+        assert_eq!(
+            change_messages("Coro", None, date, Some(&ana), None),
+            vec![(
+                "ana".to_string(),
+                "Ya no estás asignado a Coro el 15/11.".to_string()
+            )]
+        );
+        assert!(change_messages("Coro", None, date, Some(&ana), Some(&ana)).is_empty());
+    }
+}
@@ -0,0 +1,217 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::routes::schedules::MyAssignment;
+use crate::services::signage::NextService;
+
+/// Sent back when a message is not understood
+pub const HELP: &str = "Puede escribir:\n\
+    • \"quién sirve el domingo\"\n\
+    • \"mis fechas\"\n\
+    • \"no puedo el 14\" (o \"no puedo el 14/11 Ana\" si el número es de varios servidores)";
+
+/// What a message to the parish bot asks for
+#[derive(Debug, PartialEq)]
+pub enum BotCommand {
+    /// "quién sirve el domingo"
+    WhoServes,
+    /// "mis fechas"
+    MyDates,
+    /// "no puedo el 14", "no puedo el 14/11 ana"
+    CannotServe {
+        day: u32,
+        month: Option<u32>,
+        name: Option<String>,
+    },
+    Help,
+}
+
+/// Lowercase without accents or punctuation, so "¿Quién sirve?" and "quien sirve" match
+pub fn normalize(text: &str) -> String {
+    let folded: String = text
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'á' | 'à' | 'ä' | 'â' => 'a',
+            'é' | 'è' | 'ë' | 'ê' => 'e',
+            'í' | 'ì' | 'ï' | 'î' => 'i',
+            'ó' | 'ò' | 'ö' | 'ô' => 'o',
+            'ú' | 'ù' | 'ü' | 'û' => 'u',
+            c if c.is_alphanumeric() || c == '/' => c,
+            _ => ' ',
+        })
+        .collect();
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// "14" or "14/11"
+fn parse_day(word: &str) -> Option<(u32, Option<u32>)> {
+    let (day, month) = match word.split_once('/') {
+        Some((day, month)) => (day, Some(month.parse().ok()?)),
+        None => (word, None),
+    };
+    Some((day.parse().ok()?, month))
+}
+
+pub fn parse(text: &str) -> BotCommand {
+    let text = normalize(text);
+
+    if let Some(rest) = text.strip_prefix("no puedo") {
+        let words: Vec<&str> = rest
+            .split_whitespace()
+            .filter(|w| !matches!(*w, "el" | "ir" | "servir" | "dia"))
+            .collect();
+        let Some((&first, others)) = words.split_first() else {
+            return BotCommand::Help;
+        };
+        let Some((day, month)) = parse_day(first) else {
+            return BotCommand::Help;
+        };
+        return BotCommand::CannotServe {
+            day,
+            month,
+            name: (!others.is_empty()).then(|| others.join(" ")),
+        };
+    }
+    if text.contains("quien sirve") || text.contains("quienes sirven") {
+        return BotCommand::WhoServes;
+    }
+    if text.contains("mis fechas") || text.contains("cuando sirvo") {
+        return BotCommand::MyDates;
+    }
+    BotCommand::Help
+}
+
+/// The next date from `today` on with this day (and month, when given)
+pub fn resolve_date(today: NaiveDate, day: u32, month: Option<u32>) -> Option<NaiveDate> {
+    match month {
+        Some(month) => [today.year(), today.year() + 1]
+            .into_iter()
+            .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+            .find(|date| *date >= today),
+        // A day missing from a short month (31) rolls on to the next month that has it
+        None => (0..3)
+            .filter_map(|ahead| {
+                let first = today
+                    .with_day(1)?
+                    .checked_add_months(chrono::Months::new(ahead))?;
+                first.with_day(day)
+            })
+            .find(|date| *date >= today),
+    }
+}
+
+/// The last 8 digits of a phone number (a Guatemalan number without +502), so "+502 5555-1234"
+/// and "55551234" match. None when there are too few digits to identify anyone.
+pub fn phone_key(phone: &str) -> Option<String> {
+    let digits: Vec<char> = phone.chars().filter(char::is_ascii_digit).collect();
+    (digits.len() >= 8).then(|| digits[digits.len() - 8..].iter().collect())
+}
+
+pub fn format_date(date: NaiveDate) -> String {
+    date.format("%d/%m").to_string()
+}
+
+/// The roster of the next service as a chat message
+pub fn format_next_service(service: Option<&NextService>) -> String {
+    let Some(service) = service else {
+        return "Aún no hay servicios publicados.".to_string();
+    };
+    let date = &service.date;
+    if date.cancelled {
+        return format!("La misa del {} fue cancelada.", format_date(date.date));
+    }
+
+    let mut out = match &date.mass_time {
+        Some(time) => format!("Misa del {} ({}):", format_date(date.date), time),
+        None => format!("Misa del {}:", format_date(date.date)),
+    };
+    for job in &date.jobs {
+        let people: Vec<String> = job
+            .positions
+            .iter()
+            .map(|p| {
+                let person = p.person.as_deref().unwrap_or("(sin asignar)");
+                match &p.name {
+                    Some(name) => format!("{}: {}", name, person),
+                    None => person.to_string(),
+                }
+            })
+            .collect();
+        out.push_str(&format!("\n{}: {}", job.name, people.join(", ")));
+    }
+    if let Some(notes) = &date.notes {
+        out.push_str(&format!("\nNota: {}", notes));
+    }
+    out
+}
+
+/// One line per upcoming assignment, e.g. "16/11 Lectores (Primera lectura)"
+pub fn format_dates(name: &str, assignments: &[MyAssignment]) -> String {
+    if assignments.is_empty() {
+        return format!("{} no tiene fechas próximas publicadas.", name);
+    }
+    let mut out = format!("Fechas de {}:", name);
+    for a in assignments {
+        out.push_str(&format!("\n{} {}", format_date(a.service_date), a.job_name));
+        if let Some(position) = &a.position_name {
+            out.push_str(&format!(" ({})", position));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parses_the_supported_commands() {
+        assert_eq!(parse("¿Quién sirve el domingo?"), BotCommand::WhoServes);
+        assert_eq!(parse("MIS FECHAS"), BotCommand::MyDates);
+        assert_eq!(
+            parse("No puedo el 14"),
+            BotCommand::CannotServe {
+                day: 14,
+                month: None,
+                name: None
+            }
+        );
+        assert_eq!(
+            parse("no puedo el 14/11 Ana Sofía"),
+            BotCommand::CannotServe {
+                day: 14,
+                month: Some(11),
+                name: Some("ana sofia".to_string())
+            }
+        );
+        assert_eq!(parse("no puedo"), BotCommand::Help);
+        assert_eq!(parse("hola"), BotCommand::Help);
+    }
+
+    #[test]
+    fn dates_resolve_to_the_next_occurrence() {
+        let today = date(2026, 10, 16);
+        assert_eq!(resolve_date(today, 18, None), Some(date(2026, 10, 18)));
+        assert_eq!(resolve_date(today, 14, None), Some(date(2026, 11, 14)));
+        assert_eq!(
+            resolve_date(date(2026, 11, 16), 31, None),
+            Some(date(2026, 12, 31))
+        );
+        assert_eq!(resolve_date(today, 3, Some(1)), Some(date(2027, 1, 3)));
+        assert_eq!(resolve_date(today, 30, Some(2)), None);
+    }
+
+    #[test]
+    fn phones_match_on_the_local_number() {
+        assert_eq!(phone_key("+502 5555-1234").as_deref(), Some("55551234"));
+        assert_eq!(
+            phone_key("whatsapp:+50255551234").as_deref(),
+            Some("55551234")
+        );
+        assert_eq!(phone_key("1234"), None);
+    }
+}
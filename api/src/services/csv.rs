@@ -0,0 +1,72 @@
+use axum::{
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+/// Quote a field if it contains a separator, quote or line break
+pub fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Build a CSV document from a header and rows of already formatted fields
+pub fn to_csv(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut out = header.join(",");
+    out.push('\n');
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|f| escape(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Return a CSV body as a file download
+pub fn attachment(filename: &str, body: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_fields_are_left_alone() {
+        assert_eq!(escape("Monaguillos"), "Monaguillos");
+        assert_eq!(escape(""), "");
+    }
+
+    #[test]
+    fn separators_and_line_breaks_are_quoted() {
+        assert_eq!(escape("Pérez, Juan"), "\"Pérez, Juan\"");
+        assert_eq!(escape("línea 1\nlínea 2"), "\"línea 1\nlínea 2\"");
+        assert_eq!(escape("a\rb"), "\"a\rb\"");
+    }
+
+    #[test]
+    fn quotes_are_doubled() {
+        assert_eq!(escape("Juan \"Juanito\""), "\"Juan \"\"Juanito\"\"\"");
+    }
+
+    #[test]
+    fn to_csv_escapes_rows_but_not_the_header() {
+        let rows = vec![vec!["1".to_string(), "Pérez, Juan".to_string()]];
+        assert_eq!(
+            to_csv(&["id", "name"], &rows),
+            "id,name\n1,\"Pérez, Juan\"\n"
+        );
+    }
+}
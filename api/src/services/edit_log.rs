@@ -0,0 +1,209 @@
+use sqlx::{FromRow, PgConnection};
+use uuid::Uuid;
+
+/// An edit of a schedule as undo/redo sees it
+#[derive(Debug, Clone, FromRow)]
+pub struct Edit {
+    pub id: String,
+    pub kind: String,
+}
+
+/// Who a slot held before an edit and who it holds after
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotChange {
+    pub assignment_id: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Group the events `changed_by` recorded in the current transaction into one edit per schedule.
+/// Called by the editing handlers once their changes are made; a new edit also drops whatever
+/// was waiting to be redone, as in any editor.
+pub async fn close(conn: &mut PgConnection, kind: &str, changed_by: &str) -> Result<(), String> {
+    // created_at defaults to NOW(), the start of the transaction, so it tells this request's
+    // events apart from everyone else's
+    let schedule_ids: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT DISTINCT sd.schedule_id
+        FROM assignment_events e
+        JOIN service_dates sd ON sd.id = e.service_date_id
+        WHERE e.created_at = NOW() AND e.edit_id IS NULL AND e.changed_by = $1
+          AND e.action NOT IN ('undone', 'redone')
+        "#,
+    )
+    .bind(changed_by)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    for schedule_id in schedule_ids {
+        sqlx::query(
+            "DELETE FROM assignment_edits WHERE schedule_id = $1 AND undone_at IS NOT NULL",
+        )
+        .bind(&schedule_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let edit_id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO assignment_edits (id, schedule_id, kind, changed_by) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(&edit_id)
+        .bind(&schedule_id)
+        .bind(kind)
+        .bind(changed_by)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        sqlx::query(
+            r#"
+            UPDATE assignment_events e SET edit_id = $1
+            FROM service_dates sd
+            WHERE sd.id = e.service_date_id AND sd.schedule_id = $2
+              AND e.created_at = NOW() AND e.edit_id IS NULL AND e.changed_by = $3
+              AND e.action NOT IN ('undone', 'redone')
+            "#,
+        )
+        .bind(&edit_id)
+        .bind(&schedule_id)
+        .bind(changed_by)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// The latest edit still in effect, if any
+pub async fn last_done(conn: &mut PgConnection, schedule_id: &str) -> Result<Option<Edit>, String> {
+    sqlx::query_as::<_, Edit>(
+        r#"
+        SELECT id, kind FROM assignment_edits
+        WHERE schedule_id = $1 AND undone_at IS NULL
+        ORDER BY created_at DESC, id DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(schedule_id)
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// The most recently undone edit, i.e. the oldest one waiting to be redone
+pub async fn next_undone(
+    conn: &mut PgConnection,
+    schedule_id: &str,
+) -> Result<Option<Edit>, String> {
+    sqlx::query_as::<_, Edit>(
+        r#"
+        SELECT id, kind FROM assignment_edits
+        WHERE schedule_id = $1 AND undone_at IS NOT NULL
+        ORDER BY created_at, id
+        LIMIT 1
+        "#,
+    )
+    .bind(schedule_id)
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Mark an edit undone (`undone = true`) or back in effect
+pub async fn set_undone(
+    conn: &mut PgConnection,
+    edit_id: &str,
+    undone: bool,
+) -> Result<(), String> {
+    sqlx::query(
+        "UPDATE assignment_edits SET undone_at = CASE WHEN $2 THEN NOW() END WHERE id = $1",
+    )
+    .bind(edit_id)
+    .bind(undone)
+    .execute(conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Net effect of an edit on each slot it touched, in the order the slots were first touched
+pub async fn changes(conn: &mut PgConnection, edit_id: &str) -> Result<Vec<SlotChange>, String> {
+    let events: Vec<(String, Option<String>, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT assignment_id, previous_person_id, person_id
+        FROM assignment_events
+        WHERE edit_id = $1
+        ORDER BY seq
+        "#,
+    )
+    .bind(edit_id)
+    .fetch_all(conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(net_changes(events))
+}
+
+/// Fold events `(assignment_id, previous_person_id, person_id)` into one change per slot:
+/// who it held before the first event and after the last
+pub fn net_changes(events: Vec<(String, Option<String>, Option<String>)>) -> Vec<SlotChange> {
+    let mut changes: Vec<SlotChange> = Vec::new();
+    for (assignment_id, before, after) in events {
+        match changes
+            .iter_mut()
+            .find(|c| c.assignment_id == assignment_id)
+        {
+            Some(change) => change.after = after,
+            None => changes.push(SlotChange {
+                assignment_id,
+                before,
+                after,
+            }),
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(
+        slot: &str,
+        before: Option<&str>,
+        after: Option<&str>,
+    ) -> (String, Option<String>, Option<String>) {
+        (
+            slot.to_string(),
+            before.map(str::to_string),
+            after.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn keeps_first_before_and_last_after_per_slot() {
+        let changes = net_changes(vec![
+            event("a", Some("ana"), Some("luis")),
+            event("b", None, Some("ana")),
+            event("a", Some("luis"), None),
+        ]);
+        assert_eq!(
+            changes,
+            vec![
+                SlotChange {
+                    assignment_id: "a".to_string(),
+                    before: Some("ana".to_string()),
+                    after: None,
+                },
+                SlotChange {
+                    assignment_id: "b".to_string(),
+                    before: None,
+                    after: Some("ana".to_string()),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Email notifications, sent over SMTP with lettre to whatever relay the parish uses (Google
+//! Workspace, SES, their hosting provider...). Configured with SMTP_HOST, SMTP_PORT,
+//! SMTP_USERNAME, SMTP_PASSWORD and EMAIL_FROM; without SMTP_HOST nothing is sent by email.
+
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use crate::services::notifications::DeliveryError;
+
+/// From SMTP_HOST, SMTP_PORT, SMTP_USERNAME, SMTP_PASSWORD and EMAIL_FROM
+#[derive(Debug, Clone)]
+pub struct EmailConfig {
+    pub host: String,
+    /// Default 587 (STARTTLS); 465 connects over TLS from the start
+    pub port: u16,
+    pub credentials: Option<(String, String)>,
+    pub from: String,
+}
+
+fn env_value(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// None when SMTP_HOST is not set
+pub fn config() -> Option<EmailConfig> {
+    let host = env_value("SMTP_HOST")?;
+    let username = env_value("SMTP_USERNAME");
+    Some(EmailConfig {
+        port: env_value("SMTP_PORT")
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(587),
+        credentials: username
+            .clone()
+            .map(|user| (user, env_value("SMTP_PASSWORD").unwrap_or_default())),
+        from: env_value("EMAIL_FROM")
+            .or(username)
+            .unwrap_or_else(|| format!("no-reply@{}", host)),
+        host,
+    })
+}
+
+/// The subject line for each kind of notification
+pub fn subject(kind: &str) -> &'static str {
+    match kind {
+        "service_reminder" => "Recordatorio de tu servicio",
+        "schedule_published" => "Se publicó un nuevo horario",
+        "schedule_retracted" => "Se retiró un horario",
+        "assignment_changed" => "Cambió tu asignación",
+        "service_date_cancelled" => "Se canceló una fecha",
+        "username_changed" => "Cambió tu usuario",
+        "digest" => "Tus avisos del día",
+        _ => "Aviso de la parroquia",
+    }
+}
+
+/// A plain-text message; only a bad `to` is the person's (permanent) problem
+pub fn message(from: &str, to: &str, kind: &str, body: &str) -> Result<Message, DeliveryError> {
+    let from: Mailbox = from
+        .parse()
+        .map_err(|e| DeliveryError::transient(format!("Invalid EMAIL_FROM {}: {}", from, e)))?;
+    let to: Mailbox = to
+        .parse()
+        .map_err(|e| DeliveryError::permanent(format!("Invalid email address {}: {}", to, e)))?;
+    Message::builder()
+        .from(from)
+        .to(to)
+        .subject(subject(kind))
+        .header(ContentType::TEXT_PLAIN)
+        .body(body.to_string())
+        .map_err(|e| DeliveryError::transient(e.to_string()))
+}
+
+pub async fn send(config: &EmailConfig, message: Message) -> Result<(), DeliveryError> {
+    let builder = if config.port == 465 {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+    };
+    let mut builder = builder
+        .map_err(|e| DeliveryError::transient(format!("SMTP relay {}: {}", config.host, e)))?
+        .port(config.port);
+    if let Some((username, password)) = &config.credentials {
+        builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+    }
+
+    builder
+        .build()
+        .send(message)
+        .await
+        .map(|_| ())
+        .map_err(|e| DeliveryError {
+            // 5xx: the server will not take this message as it is (usually the address)
+            permanent: e.is_permanent(),
+            error: e.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_are_plain_text_with_the_kind_as_subject() {
+        let message = message(
+            "Parroquia <avisos@parroquia.org>",
+            "ana@example.com",
+            "service_reminder",
+            "Hola Ana",
+        )
+        .unwrap();
+        let text = String::from_utf8(message.formatted()).unwrap();
+        assert!(text.contains("Subject: Recordatorio de tu servicio"));
+        assert!(text.contains("To: ana@example.com"));
+        assert!(text.contains("Content-Type: text/plain"));
+
+        assert!(super::message("avisos@parroquia.org", "ana", "digest", "Hola").is_err());
+    }
+}
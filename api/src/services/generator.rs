@@ -0,0 +1,1590 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use sqlx::{FromRow, PgConnection};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::models::{
+    Assignment, AssignmentWithDetails, Job, ReplacementCandidate, Schedule, ScheduleConflict,
+    ScheduleWithDates, ServiceDate, ServiceDateWithAssignments, SlotCandidate, JUNIOR_JOB_ID,
+    PAIRING_AVOID, PAIRING_PREFER, PAIRING_SCOPE_SAME_DATE, SENIOR_JOB_ID,
+};
+use crate::routes::{settings, staffing, teams};
+use crate::services::{assignment_events, rotation};
+
+/// Create a schedule covering `dates` and fill every service date with the algorithm.
+/// The schedule is stored under `year`/`month`; `dates` may span several months.
+/// Returns None when that month already has a schedule (one was created concurrently).
+pub async fn generate_for_dates(
+    conn: &mut PgConnection,
+    jobs: &[Job],
+    schedule_name: &str,
+    year: i32,
+    month: i32,
+    dates: &[NaiveDate],
+    series_id: Option<&str>,
+) -> Result<Option<ScheduleWithDates>, String> {
+    // Create schedule; the unique index on active (year, month) settles concurrent generations
+    let schedule_id = Uuid::new_v4().to_string();
+
+    let schedule = sqlx::query_as::<_, Schedule>(
+        r#"
+        INSERT INTO schedules (id, name, year, month, status, start_date, end_date, series_id)
+        VALUES ($1, $2, $3, $4, 'DRAFT', $5, $6, $7)
+        ON CONFLICT (year, month) WHERE status NOT IN ('ARCHIVED', 'EXPIRED') DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(&schedule_id)
+    .bind(schedule_name)
+    .bind(year)
+    .bind(month)
+    .bind(dates.first())
+    .bind(dates.last())
+    .bind(series_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some(schedule) = schedule else {
+        return Ok(None);
+    };
+
+    // Create service dates
+    let mut service_dates = Vec::new();
+    for sunday in dates {
+        let sd_id = Uuid::new_v4().to_string();
+        let sd = sqlx::query_as::<_, ServiceDate>(
+            r#"
+            INSERT INTO service_dates (id, schedule_id, service_date)
+            VALUES ($1, $2, $3)
+            RETURNING *
+            "#,
+        )
+        .bind(&sd_id)
+        .bind(&schedule_id)
+        .bind(sunday)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+        service_dates.push(sd);
+    }
+
+    let (dates_with_assignments, conflicts) =
+        fill_service_dates(&mut *conn, &schedule, jobs, service_dates, HashMap::new()).await?;
+
+    Ok(Some(ScheduleWithDates {
+        schedule,
+        service_dates: dates_with_assignments,
+        conflicts,
+    }))
+}
+
+/// Run the algorithm over existing, empty service dates of `schedule`, in order.
+/// `assigned_this_month` seeds the per-month limits with assignments already made in the
+/// month of the first date (e.g. the dates kept by a regeneration).
+pub async fn fill_service_dates(
+    conn: &mut PgConnection,
+    schedule: &Schedule,
+    jobs: &[Job],
+    service_dates: Vec<ServiceDate>,
+    mut assigned_this_month: HashMap<String, Vec<String>>,
+) -> Result<(Vec<ServiceDateWithAssignments>, Vec<ScheduleConflict>), String> {
+    let juniors_max_per_senior = settings::get_i64(&mut *conn, "juniors_max_per_senior", 1).await?;
+    let rules = GenerationRules::load(&mut *conn).await?;
+
+    // Generate assignments using the algorithm
+    let mut dates_with_assignments = Vec::new();
+
+    // Track who has been assigned to each job this month (for limiting assignments per month)
+    // person_id -> list of job_ids they've been assigned
+    let mut current_month = service_dates.first().map(|sd| sd.service_date.month());
+    let mut conflicts: Vec<ScheduleConflict> = Vec::new();
+
+    for sd in service_dates {
+        // A long schedule starts counting again at each new calendar month
+        if current_month != Some(sd.service_date.month()) {
+            current_month = Some(sd.service_date.month());
+            assigned_this_month.clear();
+        }
+
+        let mut assignments: Vec<AssignmentWithDetails> = Vec::new();
+        // Track person_id -> job_name for exclusivity checking (same day)
+        let mut assigned_this_date: HashMap<String, String> = HashMap::new();
+
+        for job in jobs {
+            // Monaguillos Jr. cap: at most juniors_max_per_senior juniors per senior at this Mass
+            let max_people = if job.id == JUNIOR_JOB_ID {
+                let seniors = assignments
+                    .iter()
+                    .filter(|a| {
+                        a.assignment.job_id == SENIOR_JOB_ID && a.assignment.person_id.is_some()
+                    })
+                    .count();
+                Some(seniors * juniors_max_per_senior.max(0) as usize)
+            } else {
+                None
+            };
+
+            let job_assignments = generate_job_assignments(
+                &mut *conn,
+                schedule,
+                &sd,
+                job,
+                &rules,
+                &assigned_this_date,
+                &assigned_this_month,
+                max_people,
+                &mut conflicts,
+            )
+            .await?;
+
+            // Track who was assigned to this job (by name for exclusivity check)
+            for assignment in &job_assignments {
+                if let Some(pid) = &assignment.assignment.person_id {
+                    assigned_this_date.insert(pid.clone(), job.name.clone());
+                    // Also track for monthly limit
+                    assigned_this_month
+                        .entry(pid.clone())
+                        .or_default()
+                        .push(job.id.clone());
+                }
+            }
+
+            assignments.extend(job_assignments);
+        }
+
+        dates_with_assignments.push(ServiceDateWithAssignments {
+            service_date: sd,
+            assignments,
+        });
+    }
+
+    Ok((dates_with_assignments, conflicts))
+}
+
+// Helper: Get Sundays of a month
+pub fn get_sundays_of_month(year: i32, month: u32) -> Vec<NaiveDate> {
+    let mut sundays = Vec::new();
+    let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    let days_in_month = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap()
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1).unwrap()
+    }
+    .signed_duration_since(first_day)
+    .num_days();
+
+    for day in 1..=days_in_month as u32 {
+        if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
+            if date.weekday() == Weekday::Sun {
+                sundays.push(date);
+            }
+        }
+    }
+
+    sundays
+}
+
+// ============ Scheduling Algorithm ============
+
+/// Check if two jobs are mutually exclusive (a person can only be assigned to one per date)
+/// Note: job_name should be passed in lowercase for comparison
+fn are_jobs_exclusive(job1_name: &str, job2_name: &str) -> bool {
+    let j1 = job1_name.to_lowercase();
+    let j2 = job2_name.to_lowercase();
+    let exclusive_pairs = [
+        ("monaguillos", "monaguillos jr"),
+        ("monaguillos", "lectores"), // Can't be monaguillo and lector same day
+    ];
+    exclusive_pairs
+        .iter()
+        .any(|(a, b)| (j1 == *a && j2 == *b) || (j1 == *b && j2 == *a))
+}
+
+/// Check if a job has the consecutive month restriction (monaguillos and lectores only)
+pub fn has_consecutive_month_restriction(job_name: &str) -> bool {
+    let name = job_name.to_lowercase();
+    name == "monaguillos" || name == "lectores"
+}
+
+/// Count Sundays in a given month
+fn count_sundays_in_month(year: i32, month: u32) -> u32 {
+    get_sundays_of_month(year, month).len() as u32
+}
+
+#[derive(FromRow, Clone)]
+struct CandidatePerson {
+    id: String,
+    first_name: String,
+    last_name: String,
+    preferred_frequency: Option<String>,
+    last_served: Option<NaiveDate>,
+}
+
+/// Most a recent service can add to the fairness score, for serving the day before
+const FREQUENCY_PENALTY: f64 = 4.0;
+
+/// Days between services a person asked for (people.preferred_frequency, bimonthly by default)
+fn preferred_days(frequency: Option<&str>) -> i64 {
+    match frequency {
+        Some("weekly") => 7,
+        Some("monthly") => 30,
+        _ => 14,
+    }
+}
+
+/// Recency scoring from the desktop generator: someone whose last service is closer than their
+/// preferred frequency moves back, the more the sooner it was; past that gap nothing changes
+fn frequency_adjustment(
+    frequency: Option<&str>,
+    last_served: Option<NaiveDate>,
+    service_date: NaiveDate,
+) -> i64 {
+    let Some(last) = last_served else {
+        return 0;
+    };
+    let recency =
+        ((service_date - last).num_days() as f64 / preferred_days(frequency) as f64).min(1.0);
+    ((1.0 - recency) * FREQUENCY_PENALTY).round() as i64
+}
+
+/// SQL condition: person `p` served in each of the `max_consecutive_weeks` weeks before the
+/// week of `$2`, so another Sunday would make the run too long (0 or NULL = no limit)
+const AT_MAX_CONSECUTIVE_WEEKS: &str = r#"(COALESCE(p.max_consecutive_weeks, 0) > 0 AND (
+    SELECT COUNT(DISTINCT date_trunc('week', cw.service_date))
+    FROM assignment_history cw
+    WHERE cw.person_id = p.id
+      AND cw.service_date >= date_trunc('week', $2::date)
+                             - make_interval(weeks => p.max_consecutive_weeks)
+      AND cw.service_date < date_trunc('week', $2::date)
+) >= p.max_consecutive_weeks)"#;
+
+#[derive(FromRow)]
+struct AssignmentCountRow {
+    person_id: String,
+    count: f64,
+    job_count: f64,
+}
+
+/// Settings behind the fairness score, loaded once per generation or request
+pub struct FairnessSettings {
+    job_weight: i64,
+    window_months: i32,
+    monthly_factor: f64,
+}
+
+impl FairnessSettings {
+    pub async fn load(conn: &mut PgConnection) -> Result<Self, String> {
+        let job_weight = settings::get_i64(&mut *conn, "fairness_job_weight", 50)
+            .await?
+            .clamp(0, 100);
+        let window_months = settings::get_i64(&mut *conn, "fairness_window_months", 12)
+            .await?
+            .clamp(0, 120) as i32;
+        let decay_percent = settings::get_i64(&mut *conn, "fairness_decay_percent", 10)
+            .await?
+            .clamp(0, 100);
+        Ok(Self {
+            job_weight,
+            window_months,
+            monthly_factor: 1.0 - decay_percent as f64 / 100.0,
+        })
+    }
+}
+
+/// Fairness score (lower serves first): a blend of all assignments and assignments in this job,
+/// so serving a lot in one ministry does not hide you from another. Counts cover a trailing
+/// window (older months weigh less) so the rotation does not restart every January.
+pub async fn fairness_score(
+    conn: &mut PgConnection,
+    fairness: &FairnessSettings,
+    person_id: &str,
+    job_id: &str,
+    service_date: NaiveDate,
+) -> Result<i64, String> {
+    let scores = fairness_scores(
+        conn,
+        fairness,
+        &[person_id.to_string()],
+        job_id,
+        service_date,
+    )
+    .await?;
+    Ok(scores.get(person_id).copied().unwrap_or(0))
+}
+
+/// `fairness_score` for several people at once, in one query. People missing from the map
+/// have nothing in the window and score 0.
+pub async fn fairness_scores(
+    conn: &mut PgConnection,
+    fairness: &FairnessSettings,
+    person_ids: &[String],
+    job_id: &str,
+    service_date: NaiveDate,
+) -> Result<HashMap<String, i64>, String> {
+    let counts = sqlx::query_as::<_, AssignmentCountRow>(
+        r#"
+        SELECT
+            person_id,
+            COALESCE(SUM(weight), 0) as count,
+            COALESCE(SUM(weight) FILTER (WHERE job_id = $3), 0) as job_count
+        FROM (
+            SELECT person_id, job_id,
+                   POWER($4::float8, GREATEST(0,
+                       (EXTRACT(YEAR FROM $2::date) * 12 + EXTRACT(MONTH FROM $2::date))
+                       - (EXTRACT(YEAR FROM service_date) * 12 + EXTRACT(MONTH FROM service_date))
+                   )) as weight
+            FROM assignment_history
+            WHERE person_id = ANY($1)
+              AND CASE WHEN $5 > 0
+                       THEN service_date > $2::date - make_interval(months => $5)
+                       ELSE year = EXTRACT(YEAR FROM $2::date)::int
+                  END
+        ) weighted
+        GROUP BY person_id
+        "#,
+    )
+    .bind(person_ids)
+    .bind(service_date)
+    .bind(job_id)
+    .bind(fairness.monthly_factor)
+    .bind(fairness.window_months)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(counts
+        .into_iter()
+        .map(|count| {
+            let score = (count.count * (100 - fairness.job_weight) as f64
+                + count.job_count * fairness.job_weight as f64)
+                / 100.0;
+            (count.person_id, score.round() as i64)
+        })
+        .collect())
+}
+
+#[derive(FromRow)]
+struct PairingRow {
+    person_a_id: String,
+    person_b_id: String,
+    preference: String,
+}
+
+/// Score adjustment from pairing preferences with people already serving this date.
+/// AVOID pushes the person behind everyone else; PREFER counts as one fewer assignment.
+fn pairing_adjustment(person_id: &str, on_date: &[&String], pairings: &[PairingRow]) -> i64 {
+    pairings
+        .iter()
+        .filter_map(|p| {
+            let other = if p.person_a_id == person_id {
+                &p.person_b_id
+            } else if p.person_b_id == person_id {
+                &p.person_a_id
+            } else {
+                return None;
+            };
+            if !on_date.contains(&other) {
+                return None;
+            }
+            match p.preference.as_str() {
+                PAIRING_AVOID => Some(1000),
+                PAIRING_PREFER => Some(-1),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+#[derive(FromRow)]
+struct SiblingMemberRow {
+    sibling_group_id: String,
+    pairing_rule: String,
+    pairing_scope: String,
+    person_id: String,
+}
+
+/// Score adjustment from sibling groups. A sibling counts when serving in this job (SAME_JOB)
+/// or in any job this date (SAME_DATE), so a lector and a monaguillo can be "together".
+/// TOGETHER counts as one fewer assignment; SEPARATE pushes the person behind everyone else.
+fn sibling_adjustment(
+    person_id: &str,
+    in_job: &[&String],
+    on_date: &[&String],
+    siblings: &[SiblingMemberRow],
+) -> i64 {
+    let mut adjustment = 0;
+    for group in siblings.iter().filter(|s| s.person_id == person_id) {
+        let serving = if group.pairing_scope == PAIRING_SCOPE_SAME_DATE {
+            on_date
+        } else {
+            in_job
+        };
+        let sibling_serving = siblings.iter().any(|s| {
+            s.sibling_group_id == group.sibling_group_id
+                && s.person_id != person_id
+                && serving.contains(&&s.person_id)
+        });
+        if sibling_serving {
+            adjustment += if group.pairing_rule == "SEPARATE" {
+                10_000
+            } else {
+                -1
+            };
+        }
+    }
+    adjustment
+}
+
+/// How far back shared services count against picking the same group again
+const CO_ASSIGNMENT_WINDOW_DAYS: i32 = 35;
+
+#[derive(FromRow)]
+struct CoAssignmentRow {
+    person_a_id: String,
+    person_b_id: String,
+    times: i64,
+}
+
+/// Variety penalty: `weight` per recent service this person shared with someone already picked
+fn co_assignment_adjustment(
+    person_id: &str,
+    in_job: &[&String],
+    co_assignments: &HashMap<(String, String), i64>,
+    weight: i64,
+) -> i64 {
+    in_job
+        .iter()
+        .filter_map(|other| co_assignments.get(&(person_id.to_string(), (*other).clone())))
+        .sum::<i64>()
+        * weight
+}
+
+/// What the generator reads once per run rather than for every job and date
+struct GenerationRules {
+    fairness: FairnessSettings,
+    /// max_jobs_per_person_per_month (0 = no limit)
+    max_jobs: i64,
+    co_assignment_weight: i64,
+    pairings: Vec<PairingRow>,
+    siblings: Vec<SiblingMemberRow>,
+}
+
+impl GenerationRules {
+    async fn load(conn: &mut PgConnection) -> Result<Self, String> {
+        let fairness = FairnessSettings::load(&mut *conn).await?;
+        let max_jobs = settings::get_i64(&mut *conn, "max_jobs_per_person_per_month", 0).await?;
+        let co_assignment_weight = settings::get_i64(&mut *conn, "co_assignment_penalty", 1)
+            .await?
+            .max(0);
+        let pairings = sqlx::query_as::<_, PairingRow>(
+            "SELECT person_a_id, person_b_id, preference FROM pairing_preferences",
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+        let siblings = sqlx::query_as::<_, SiblingMemberRow>(
+            r#"
+            SELECT sg.id as sibling_group_id, sg.pairing_rule, sg.pairing_scope, sgm.person_id
+            FROM sibling_groups sg
+            JOIN sibling_group_members sgm ON sgm.sibling_group_id = sg.id
+            "#,
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            fairness,
+            max_jobs,
+            co_assignment_weight,
+            pairings,
+            siblings,
+        })
+    }
+}
+
+#[derive(FromRow)]
+struct TeamRotationRow {
+    id: String,
+    name: String,
+}
+
+/// Pick the team for this job that served least recently and whose members are all still candidates.
+/// Returns None when no team is complete, so the caller falls back to individual assignment.
+async fn pick_team(
+    conn: &mut PgConnection,
+    job: &Job,
+    candidates: &[(CandidatePerson, i64)],
+    slots: usize,
+) -> Result<Option<(String, Vec<String>)>, String> {
+    let teams = sqlx::query_as::<_, TeamRotationRow>(
+        r#"
+        SELECT t.id, t.name
+        FROM teams t
+        -- The team's own last service: the latest date all of its members served this job
+        -- together (members serving individually do not count)
+        LEFT JOIN LATERAL (
+            SELECT MAX(together.service_date) as last_served
+            FROM (
+                SELECT ah.service_date
+                FROM team_members tm
+                JOIN assignment_history ah ON ah.person_id = tm.person_id AND ah.job_id = t.job_id
+                WHERE tm.team_id = t.id
+                GROUP BY ah.service_date
+                HAVING COUNT(DISTINCT ah.person_id) =
+                    (SELECT COUNT(*) FROM team_members WHERE team_id = t.id)
+            ) together
+        ) served ON true
+        WHERE t.job_id = $1 AND t.active = true
+        ORDER BY served.last_served NULLS FIRST, t.name
+        "#,
+    )
+    .bind(&job.id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    for team in teams {
+        let member_ids = teams::get_member_ids(&mut *conn, &team.id).await?;
+        let complete = !member_ids.is_empty()
+            && member_ids.len() <= slots
+            && member_ids
+                .iter()
+                .all(|m| candidates.iter().any(|(c, _)| &c.id == m));
+
+        if complete {
+            return Ok(Some((team.name, member_ids)));
+        }
+        tracing::info!(
+            "Team {} not usable for {} (member unavailable or team larger than {} slots)",
+            team.name,
+            job.name,
+            slots
+        );
+    }
+
+    Ok(None)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn generate_job_assignments(
+    conn: &mut PgConnection,
+    schedule: &Schedule,
+    service_date: &ServiceDate,
+    job: &Job,
+    rules: &GenerationRules,
+    assigned_this_date: &HashMap<String, String>,
+    assigned_this_month: &HashMap<String, Vec<String>>, // person_id -> list of job_ids they've been assigned this month
+    max_people: Option<usize>, // Cap below people_required (e.g. juniors vs seniors per Mass)
+    conflicts: &mut Vec<ScheduleConflict>,
+) -> Result<Vec<AssignmentWithDetails>, String> {
+    let num_positions = staffing::people_required(
+        &mut *conn,
+        service_date.service_date,
+        &job.id,
+        job.people_required,
+    )
+    .await?;
+    if num_positions != job.people_required {
+        tracing::info!(
+            "Staffing override for {} on {}: {} people instead of {}",
+            job.name,
+            service_date.service_date,
+            num_positions,
+            job.people_required
+        );
+    }
+    if num_positions <= 0 {
+        return Ok(Vec::new());
+    }
+
+    // Get candidates: active people qualified for this job and available on this date
+    // Also filter out people excluded from this job or with a blackout for this month,
+    // and people without first communion when the job requires it
+    let all_candidates = sqlx::query_as::<_, CandidatePerson>(
+        r#"
+        SELECT DISTINCT p.id, p.first_name, p.last_name, p.preferred_frequency,
+               (SELECT MAX(ah.service_date) FROM assignment_history ah
+                WHERE ah.person_id = p.id AND ah.service_date < $2) as last_served
+        FROM people p
+        JOIN person_jobs pj ON p.id = pj.person_id
+        WHERE pj.job_id = $1
+          AND p.active = true
+          AND NOT EXISTS (
+              SELECT 1 FROM unavailability u
+              WHERE u.person_id = p.id
+                AND unavailability_matches(u, $2)
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM person_job_exclusions x
+              WHERE x.person_id = p.id AND x.job_id = $1
+          )
+          AND (NOT $4 OR p.first_communion = true)
+          AND NOT EXISTS (
+              SELECT 1 FROM person_job_blackouts b
+              WHERE b.person_id = p.id
+                AND b.job_id = $1
+                AND $3 = ANY(b.months)
+          )
+        "#,
+    )
+    .bind(&job.id)
+    .bind(service_date.service_date)
+    .bind(service_date.service_date.month() as i32)
+    .bind(job.requires_first_communion)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        "Candidates for {} after exclusion filter: {}",
+        job.name,
+        all_candidates.len()
+    );
+
+    // Filter out candidates already assigned to an exclusive job
+    let mut candidates: Vec<CandidatePerson> = all_candidates
+        .into_iter()
+        .filter(|candidate| {
+            // Check if this person is already assigned to an exclusive job
+            if let Some(assigned_job_name) = assigned_this_date.get(&candidate.id) {
+                // If they're assigned to an exclusive job, exclude them
+                !are_jobs_exclusive(assigned_job_name, &job.name)
+            } else {
+                // Not assigned yet, include them
+                true
+            }
+        })
+        .collect();
+
+    // Apply consecutive month restriction for monaguillos and lectores
+    // Rule: Cannot serve in SAME role two consecutive months, UNLESS current month has 5 Sundays
+    // Note: A person CAN serve as Monaguillo in April AND Lector in April (same month, different days)
+    //       But if they served as Monaguillo in March, they cannot be Monaguillo in April
+    if has_consecutive_month_restriction(&job.name) {
+        let current_month = service_date.service_date.month();
+        let current_year = service_date.service_date.year();
+        let sundays_this_month = count_sundays_in_month(current_year, current_month);
+
+        // Only apply restriction if current month has 4 or fewer Sundays
+        if sundays_this_month <= 4 {
+            // Calculate previous month
+            let (prev_year, prev_month) = if current_month == 1 {
+                (current_year - 1, 12u32)
+            } else {
+                (current_year, current_month - 1)
+            };
+
+            // Get list of people who served in THIS SAME job last month
+            let served_last_month: Vec<String> = sqlx::query_scalar(
+                r#"
+                SELECT DISTINCT person_id
+                FROM assignment_history
+                WHERE job_id = $1
+                  AND EXTRACT(YEAR FROM service_date) = $2
+                  AND EXTRACT(MONTH FROM service_date) = $3
+                "#,
+            )
+            .bind(&job.id)
+            .bind(prev_year)
+            .bind(prev_month as i32)
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+
+            let candidates_before = candidates.len();
+
+            // Simply filter out those who served in this same job last month
+            candidates.retain(|c| !served_last_month.contains(&c.id));
+
+            tracing::info!(
+                "Consecutive month filter for {}: {} total, {} served last month in same role, {} available",
+                job.name,
+                candidates_before,
+                served_last_month.len(),
+                candidates.len()
+            );
+
+            if candidates.is_empty() {
+                tracing::warn!(
+                    "No candidates available for {} after consecutive month filter!",
+                    job.name
+                );
+            }
+        } else {
+            tracing::info!(
+                "Skipping consecutive month restriction for {} - month has {} Sundays (>4)",
+                job.name,
+                sundays_this_month
+            );
+        }
+    }
+
+    // Apply per-person minimum rest gap (people.min_rest_days)
+    // Same-date assignments in other jobs are handled by the exclusivity rules instead
+    let resting: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT DISTINCT ah.person_id
+        FROM assignment_history ah
+        JOIN people p ON ah.person_id = p.id
+        WHERE p.min_rest_days IS NOT NULL
+          AND ah.service_date <> $1
+          AND ABS(ah.service_date - $1) < p.min_rest_days
+        "#,
+    )
+    .bind(service_date.service_date)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if !resting.is_empty() {
+        let candidates_before = candidates.len();
+        candidates.retain(|c| !resting.contains(&c.id));
+        tracing::info!(
+            "Minimum rest filter for {}: {} -> {} candidates",
+            job.name,
+            candidates_before,
+            candidates.len()
+        );
+    }
+
+    // Apply per-person consecutive Sunday limit (people.max_consecutive_weeks)
+    let on_a_run: Vec<String> = sqlx::query_scalar(&format!(
+        "SELECT p.id FROM people p WHERE p.id = ANY($1) AND {}",
+        AT_MAX_CONSECUTIVE_WEEKS
+    ))
+    .bind(candidates.iter().map(|c| c.id.clone()).collect::<Vec<_>>())
+    .bind(service_date.service_date)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if !on_a_run.is_empty() {
+        let candidates_before = candidates.len();
+        candidates.retain(|c| !on_a_run.contains(&c.id));
+        tracing::info!(
+            "Consecutive weeks filter for {}: {} -> {} candidates",
+            job.name,
+            candidates_before,
+            candidates.len()
+        );
+    }
+
+    // Keep people within max_jobs_per_person_per_month different jobs (0 = no limit)
+    let max_jobs = rules.max_jobs;
+    if max_jobs > 0 {
+        let candidates_before = candidates.len();
+        candidates.retain(|c| match assigned_this_month.get(&c.id) {
+            Some(jobs) if !jobs.contains(&job.id) => {
+                let mut distinct: Vec<&String> = jobs.iter().collect();
+                distinct.sort();
+                distinct.dedup();
+                (distinct.len() as i64) < max_jobs
+            }
+            _ => true,
+        });
+        tracing::info!(
+            "Max jobs per month filter for {}: {} -> {} candidates",
+            job.name,
+            candidates_before,
+            candidates.len()
+        );
+    }
+
+    // Filter out people who have already been assigned to this job this month
+    // (limit to 1 assignment per job per month, unless not enough candidates)
+    let candidates_before_monthly = candidates.len();
+
+    // Count how many times each candidate has been assigned to this job this month
+    let mut candidates_with_counts: Vec<(CandidatePerson, usize)> = candidates
+        .iter()
+        .map(|c| {
+            let count = if let Some(jobs_assigned) = assigned_this_month.get(&c.id) {
+                jobs_assigned.iter().filter(|j| *j == &job.id).count()
+            } else {
+                0
+            };
+            (c.clone(), count)
+        })
+        .collect();
+
+    // Sort by assignment count (ascending) - prefer those who haven't served yet
+    candidates_with_counts.sort_by_key(|(_, count)| *count);
+
+    // Get candidates who haven't served this job this month
+    let candidates_without_monthly: Vec<CandidatePerson> = candidates_with_counts
+        .iter()
+        .filter(|(_, count)| *count == 0)
+        .map(|(c, _)| c.clone())
+        .collect();
+
+    // If we have enough candidates who haven't served yet, use only those
+    if candidates_without_monthly.len() >= num_positions as usize {
+        tracing::info!(
+            "Monthly limit filter for {}: {} -> {} candidates (using only those who haven't served this month)",
+            job.name,
+            candidates_before_monthly,
+            candidates_without_monthly.len()
+        );
+        candidates = candidates_without_monthly;
+    } else {
+        // Not enough fresh candidates - need to reuse some
+        // Take the people with fewest assignments first
+        tracing::warn!(
+            "Not enough fresh candidates for {} ({} fresh, {} needed). Will prioritize those with fewer assignments.",
+            job.name,
+            candidates_without_monthly.len(),
+            num_positions
+        );
+
+        // Rebuild candidates list prioritized by fewest assignments this month
+        candidates = candidates_with_counts.into_iter().map(|(c, _)| c).collect();
+    }
+
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let scores = fairness_scores(
+        &mut *conn,
+        &rules.fairness,
+        &candidates.iter().map(|c| c.id.clone()).collect::<Vec<_>>(),
+        &job.id,
+        service_date.service_date,
+    )
+    .await?;
+    let mut person_scores: Vec<(CandidatePerson, i64)> = Vec::new();
+    for candidate in &candidates {
+        let score = scores.get(&candidate.id).copied().unwrap_or(0);
+        let frequency = frequency_adjustment(
+            candidate.preferred_frequency.as_deref(),
+            candidate.last_served,
+            service_date.service_date,
+        );
+        person_scores.push((candidate.clone(), score + frequency));
+    }
+
+    // Sort by fewest assignments (fairness), behind anyone asked to serve less often
+    person_scores.sort_by_key(|(_, count)| *count);
+
+    // Log all candidates with their scores
+    tracing::info!(
+        "Candidates for {} on {}: {} total",
+        job.name,
+        service_date.service_date,
+        person_scores.len()
+    );
+    for (p, count) in &person_scores {
+        tracing::debug!(
+            "  - {} {} (fairness score: {})",
+            p.first_name,
+            p.last_name,
+            count
+        );
+    }
+
+    // Select top N people
+    let mut take_count = num_positions as usize;
+    if let Some(cap) = max_people {
+        if cap < take_count {
+            tracing::info!(
+                "Capping {} on {} to {} people (juniors per senior limit)",
+                job.name,
+                service_date.service_date,
+                cap
+            );
+            take_count = cap;
+        }
+    }
+
+    // Pick one at a time so pairing preferences see who is already serving this date
+    let pairings = &rules.pairings;
+    let siblings = &rules.siblings;
+
+    // Novices: fewer than job.novice_threshold past assignments in this job
+    let novices: Vec<String> = if job.max_novices_per_date.is_some() || job.requires_mentor {
+        sqlx::query_scalar(
+            r#"
+            SELECT p.id
+            FROM people p
+            LEFT JOIN assignment_history ah ON ah.person_id = p.id AND ah.job_id = $1
+            WHERE p.id = ANY($2)
+            GROUP BY p.id
+            HAVING COUNT(ah.id) < $3
+            "#,
+        )
+        .bind(&job.id)
+        .bind(
+            person_scores
+                .iter()
+                .map(|(p, _)| p.id.clone())
+                .collect::<Vec<_>>(),
+        )
+        .bind(job.novice_threshold as i64)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?
+    } else {
+        Vec::new()
+    };
+
+    // Recent services candidates shared in this job, so the same group is not picked every week
+    let co_assignment_weight = rules.co_assignment_weight;
+    let co_assignments: HashMap<(String, String), i64> = if co_assignment_weight > 0 {
+        sqlx::query_as::<_, CoAssignmentRow>(
+            r#"
+            SELECT a.person_id as person_a_id, b.person_id as person_b_id, COUNT(*) as times
+            FROM assignment_history a
+            JOIN assignment_history b
+              ON b.service_date = a.service_date AND b.job_id = a.job_id AND b.person_id <> a.person_id
+            WHERE a.job_id = $1
+              AND a.person_id = ANY($2)
+              AND a.service_date < $3
+              AND a.service_date >= $3 - $4
+            GROUP BY a.person_id, b.person_id
+            "#,
+        )
+        .bind(&job.id)
+        .bind(person_scores.iter().map(|(p, _)| p.id.clone()).collect::<Vec<_>>())
+        .bind(service_date.service_date)
+        .bind(CO_ASSIGNMENT_WINDOW_DAYS)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|r| ((r.person_a_id, r.person_b_id), r.times))
+        .collect()
+    } else {
+        HashMap::new()
+    };
+
+    let mut remaining = person_scores;
+    let mut selected: Vec<CandidatePerson> = Vec::new();
+
+    // Team templates: seat a whole team first, individuals only fill what is left
+    if let Some((team_name, member_ids)) =
+        pick_team(&mut *conn, job, &remaining, take_count).await?
+    {
+        tracing::info!(
+            "Using team {} for {} on {}",
+            team_name,
+            job.name,
+            service_date.service_date
+        );
+        for member_id in &member_ids {
+            if let Some(i) = remaining.iter().position(|(p, _)| &p.id == member_id) {
+                selected.push(remaining.remove(i).0);
+            }
+        }
+    }
+
+    let mut forced_novices: Vec<String> = Vec::new();
+    let mut separated_siblings: Vec<String> = Vec::new();
+    while selected.len() < take_count && !remaining.is_empty() {
+        let on_date: Vec<&String> = assigned_this_date
+            .keys()
+            .chain(selected.iter().map(|p| &p.id))
+            .collect();
+        let in_job: Vec<&String> = selected.iter().map(|p| &p.id).collect();
+        let novice_limit_reached = job.max_novices_per_date.is_some_and(|max| {
+            selected.iter().filter(|p| novices.contains(&p.id)).count() >= max as usize
+        });
+        // Mentor rule: the last open slot goes to an experienced person if nobody experienced is in yet
+        let mentor_needed = job.requires_mentor
+            && selected.len() + 1 == take_count
+            && selected.iter().all(|p| novices.contains(&p.id));
+
+        let best = remaining
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (p, count))| {
+                let novice_penalty =
+                    if (novice_limit_reached || mentor_needed) && novices.contains(&p.id) {
+                        10_000
+                    } else {
+                        0
+                    };
+                *count
+                    + pairing_adjustment(&p.id, &on_date, pairings)
+                    + sibling_adjustment(&p.id, &in_job, &on_date, siblings)
+                    + co_assignment_adjustment(
+                        &p.id,
+                        &in_job,
+                        &co_assignments,
+                        co_assignment_weight,
+                    )
+                    + novice_penalty
+            })
+            .map(|(i, _)| i)
+            .unwrap();
+
+        let (person, _) = remaining.remove(best);
+        if novice_limit_reached && novices.contains(&person.id) {
+            forced_novices.push(person.id.clone());
+        }
+        if sibling_adjustment(&person.id, &in_job, &on_date, siblings) >= 10_000 {
+            separated_siblings.push(person.id.clone());
+        }
+        selected.push(person);
+    }
+
+    if !separated_siblings.is_empty() {
+        tracing::warn!(
+            "SEPARATE sibling rule broken for {} on {}: {} person(s)",
+            job.name,
+            service_date.service_date,
+            separated_siblings.len()
+        );
+        conflicts.push(ScheduleConflict {
+            service_date: service_date.service_date,
+            job_id: job.id.clone(),
+            conflict_type: "sibling_violation".to_string(),
+            message: format!(
+                "{}: siblings that should be kept apart are serving together (no one else available)",
+                job.name
+            ),
+            affected_person_ids: separated_siblings,
+        });
+    }
+
+    if !forced_novices.is_empty() {
+        let max = job.max_novices_per_date.unwrap_or(0);
+        tracing::warn!(
+            "Novice limit exceeded for {} on {}: {} extra novice(s) over the limit of {}",
+            job.name,
+            service_date.service_date,
+            forced_novices.len(),
+            max
+        );
+        conflicts.push(ScheduleConflict {
+            service_date: service_date.service_date,
+            job_id: job.id.clone(),
+            conflict_type: "novice_limit_exceeded".to_string(),
+            message: format!(
+                "{}: more than {} people with fewer than {} assignments (not enough experienced people available)",
+                job.name, max, job.novice_threshold
+            ),
+            affected_person_ids: selected
+                .iter()
+                .filter(|p| novices.contains(&p.id))
+                .map(|p| p.id.clone())
+                .collect(),
+        });
+    }
+
+    let selected_novices: Vec<String> = selected
+        .iter()
+        .filter(|p| novices.contains(&p.id))
+        .map(|p| p.id.clone())
+        .collect();
+    if job.requires_mentor
+        && !selected_novices.is_empty()
+        && selected_novices.len() == selected.len()
+    {
+        tracing::warn!(
+            "No experienced person for {} on {} to accompany {} novice(s)",
+            job.name,
+            service_date.service_date,
+            selected_novices.len()
+        );
+        conflicts.push(ScheduleConflict {
+            service_date: service_date.service_date,
+            job_id: job.id.clone(),
+            conflict_type: "mentor_missing".to_string(),
+            message: format!(
+                "{}: only people with fewer than {} assignments are serving (no experienced person available)",
+                job.name, job.novice_threshold
+            ),
+            affected_person_ids: selected_novices,
+        });
+    }
+
+    // Log selected candidates
+    tracing::info!(
+        "Selected {} of {} required for {} on {}: [{}]",
+        selected.len(),
+        num_positions,
+        job.name,
+        service_date.service_date,
+        selected
+            .iter()
+            .map(|p| format!("{} {}", p.first_name, p.last_name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    // Build position bags for rotation algorithm
+    let mut person_bags: HashMap<String, Vec<i32>> = HashMap::new();
+
+    for person in &selected {
+        // Bag = positions this person still owes in their rotation cycle for this job
+        let (_, done) = rotation::load(&mut *conn, &person.id, &job.id).await?;
+        person_bags.insert(
+            person.id.clone(),
+            rotation::pending_positions(&done, num_positions),
+        );
+    }
+
+    // Assign positions using simplified algorithm
+    // Prioritize positions in bags, but fall back to any unassigned person
+    let mut assignments: Vec<AssignmentWithDetails> = Vec::new();
+    let mut assigned_positions: Vec<i32> = Vec::new();
+    let mut assigned_people: Vec<String> = Vec::new();
+
+    for pos in 1..=num_positions {
+        // Find person with this position in their bag (rotation preference)
+        let mut candidates_for_pos: Vec<(&String, usize)> = person_bags
+            .iter()
+            .filter(|(pid, bag)| !assigned_people.contains(pid) && bag.contains(&pos))
+            .map(|(pid, bag)| (pid, bag.len()))
+            .collect();
+
+        // Sort by smallest bag (most constrained first)
+        candidates_for_pos.sort_by_key(|(_, bag_size)| *bag_size);
+
+        // If no one has this position in their bag, fall back to any unassigned person
+        let person_id = if let Some((pid, _)) = candidates_for_pos.first() {
+            (*pid).clone()
+        } else {
+            // Fallback: pick any unassigned person from selected
+            match selected.iter().find(|p| !assigned_people.contains(&p.id)) {
+                Some(p) => p.id.clone(),
+                None => break, // No more people available
+            }
+        };
+
+        if !assigned_people.contains(&person_id) {
+            let person = selected.iter().find(|p| p.id == person_id).unwrap();
+
+            // Get position name
+            let position_name = sqlx::query_scalar::<_, String>(
+                "SELECT name FROM job_positions WHERE job_id = $1 AND position_number = $2",
+            )
+            .bind(&job.id)
+            .bind(pos)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+
+            // Create assignment
+            let assignment_id = Uuid::new_v4().to_string();
+            sqlx::query(
+                r#"
+                INSERT INTO assignments (id, service_date_id, job_id, person_id, position, position_name)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#
+            )
+            .bind(&assignment_id)
+            .bind(&service_date.id)
+            .bind(&job.id)
+            .bind(&person_id)
+            .bind(pos)
+            .bind(&position_name)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+
+            // Create history entry
+            let history_id = Uuid::new_v4().to_string();
+            let week_number = service_date.service_date.iso_week().week() as i32;
+            sqlx::query(
+                r#"
+                INSERT INTO assignment_history (id, person_id, job_id, service_date, year, week_number, position)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#
+            )
+            .bind(&history_id)
+            .bind(&person_id)
+            .bind(&job.id)
+            .bind(service_date.service_date)
+            .bind(service_date.service_date.year())
+            .bind(week_number)
+            .bind(pos)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+
+            rotation::mark_done(
+                &mut *conn,
+                &person_id,
+                &job.id,
+                service_date.service_date,
+                pos,
+            )
+            .await?;
+
+            let assignment = Assignment {
+                id: assignment_id,
+                service_date_id: service_date.id.clone(),
+                job_id: job.id.clone(),
+                person_id: Some(person_id.clone()),
+                position: Some(pos),
+                position_name: position_name.clone(),
+                manual_override: Some(false),
+                suggested: false,
+                notes: None,
+                override_reason: None,
+                created_by: None,
+                updated_by: None,
+                created_at: None,
+                updated_at: None,
+            };
+            let empty_slot = Assignment {
+                person_id: None,
+                ..assignment.clone()
+            };
+            assignment_events::record(&mut *conn, &empty_slot, "generated", Some(&person_id), None)
+                .await?;
+
+            assignments.push(AssignmentWithDetails {
+                assignment,
+                person_name: format!("{} {}", person.first_name, person.last_name),
+                job_name: job.name.clone(),
+                job_color: job.color.clone(),
+                service_date: service_date.service_date,
+                schedule_id: schedule.id.clone(),
+                schedule_year: schedule.year,
+                schedule_month: schedule.month,
+                person_notes: None,
+                scheduling_notes: None,
+            });
+
+            assigned_positions.push(pos);
+            assigned_people.push(person_id);
+        }
+    }
+
+    // Log final results
+    if assignments.len() < num_positions as usize {
+        tracing::warn!(
+            "INCOMPLETE: Only {} of {} {} assignments created for {}. Selected had {} people.",
+            assignments.len(),
+            num_positions,
+            job.name,
+            service_date.service_date,
+            selected.len()
+        );
+    } else {
+        tracing::info!(
+            "Created {} {} assignments for {}",
+            assignments.len(),
+            job.name,
+            service_date.service_date
+        );
+    }
+
+    Ok(assignments)
+}
+
+// ============ Candidates for open slots ============
+
+#[derive(FromRow)]
+struct ReplacementRow {
+    id: String,
+    person_name: String,
+}
+
+/// People who could take a slot in `job_id` on `service_date`, best fairness score first.
+/// Applies the generator's hard rules (qualified, active, available, not excluded or blacked
+/// out, first communion) and skips anyone already serving that date or listed in `exclude`.
+pub async fn rank_replacements(
+    conn: &mut PgConnection,
+    fairness: &FairnessSettings,
+    service_date: NaiveDate,
+    job_id: &str,
+    exclude: &[String],
+    limit: usize,
+) -> Result<Vec<ReplacementCandidate>, String> {
+    let rows = sqlx::query_as::<_, ReplacementRow>(
+        r#"
+        SELECT p.id, p.first_name || ' ' || p.last_name as person_name
+        FROM people p
+        JOIN person_jobs pj ON pj.person_id = p.id AND pj.job_id = $1
+        JOIN jobs j ON j.id = $1
+        WHERE p.active = true
+          AND NOT (p.id = ANY($3))
+          AND (NOT j.requires_first_communion OR p.first_communion = true)
+          AND NOT EXISTS (
+              SELECT 1 FROM unavailability u
+              WHERE u.person_id = p.id AND unavailability_matches(u, $2)
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM person_job_exclusions x
+              WHERE x.person_id = p.id AND x.job_id = $1
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM person_job_blackouts b
+              WHERE b.person_id = p.id AND b.job_id = $1
+                AND EXTRACT(MONTH FROM $2::date)::int = ANY(b.months)
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM assignments a
+              JOIN service_dates sd ON a.service_date_id = sd.id
+              WHERE a.person_id = p.id AND sd.service_date = $2
+          )
+        "#,
+    )
+    .bind(job_id)
+    .bind(service_date)
+    .bind(exclude)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let scores = fairness_scores(
+        &mut *conn,
+        fairness,
+        &rows.iter().map(|r| r.id.clone()).collect::<Vec<_>>(),
+        job_id,
+        service_date,
+    )
+    .await?;
+    let mut candidates: Vec<ReplacementCandidate> = rows
+        .into_iter()
+        .map(|row| ReplacementCandidate {
+            fairness_score: scores.get(&row.id).copied().unwrap_or(0),
+            person_id: row.id,
+            person_name: row.person_name,
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        a.fairness_score
+            .cmp(&b.fairness_score)
+            .then_with(|| a.person_name.cmp(&b.person_name))
+    });
+    candidates.truncate(limit);
+
+    Ok(candidates)
+}
+
+/// A person qualified for a job with each hard rule checked against one date
+#[derive(FromRow, Default)]
+struct SlotCheckRow {
+    id: String,
+    person_name: String,
+    missing_first_communion: bool,
+    unavailable: bool,
+    excluded: bool,
+    blacked_out: bool,
+    serving_that_date: bool,
+    served_last_month: bool,
+    resting: bool,
+    at_max_consecutive_weeks: bool,
+    at_max_jobs: bool,
+}
+
+/// Reason codes for the rules that keep a person out of a slot, in the order an admin
+/// would have to deal with them. `served_last_month` only counts when the job has the
+/// consecutive month restriction and the month has 4 Sundays or fewer, as in the generator.
+fn blocking_reasons(row: &SlotCheckRow, consecutive_rule_applies: bool) -> Vec<String> {
+    [
+        (row.unavailable, "unavailable"),
+        (row.excluded, "excluded"),
+        (row.blacked_out, "blacked_out"),
+        (row.missing_first_communion, "first_communion"),
+        (row.serving_that_date, "serving_that_date"),
+        (
+            row.served_last_month && consecutive_rule_applies,
+            "served_last_month",
+        ),
+        (row.resting, "min_rest_days"),
+        (row.at_max_consecutive_weeks, "max_consecutive_weeks"),
+        (row.at_max_jobs, "max_jobs_per_month"),
+    ]
+    .into_iter()
+    .filter(|(blocked, _)| *blocked)
+    .map(|(_, reason)| reason.to_string())
+    .collect()
+}
+
+/// Everyone qualified for `job_id`, eligible people first by fairness score, then near
+/// misses by how many rules block them
+pub async fn rank_slot_candidates(
+    conn: &mut PgConnection,
+    fairness: &FairnessSettings,
+    service_date: NaiveDate,
+    job_id: &str,
+    job_name: &str,
+    max_jobs: i64,
+    limit: usize,
+) -> Result<Vec<SlotCandidate>, String> {
+    let rows = sqlx::query_as::<_, SlotCheckRow>(&format!(
+        r#"
+        SELECT
+            p.id,
+            p.first_name || ' ' || p.last_name as person_name,
+            (j.requires_first_communion AND NOT p.first_communion) as missing_first_communion,
+            EXISTS (
+                SELECT 1 FROM unavailability u
+                WHERE u.person_id = p.id AND unavailability_matches(u, $2)
+            ) as unavailable,
+            EXISTS (
+                SELECT 1 FROM person_job_exclusions x
+                WHERE x.person_id = p.id AND x.job_id = $1
+            ) as excluded,
+            EXISTS (
+                SELECT 1 FROM person_job_blackouts b
+                WHERE b.person_id = p.id AND b.job_id = $1
+                  AND EXTRACT(MONTH FROM $2::date)::int = ANY(b.months)
+            ) as blacked_out,
+            EXISTS (
+                SELECT 1 FROM assignments a
+                JOIN service_dates sd ON a.service_date_id = sd.id
+                WHERE a.person_id = p.id AND sd.service_date = $2
+            ) as serving_that_date,
+            EXISTS (
+                SELECT 1 FROM assignment_history ah
+                WHERE ah.person_id = p.id AND ah.job_id = $1
+                  AND date_trunc('month', ah.service_date)
+                      = date_trunc('month', $2::date - INTERVAL '1 month')
+            ) as served_last_month,
+            (p.min_rest_days IS NOT NULL AND EXISTS (
+                SELECT 1 FROM assignment_history ah
+                WHERE ah.person_id = p.id
+                  AND ah.service_date <> $2
+                  AND ABS(ah.service_date - $2) < p.min_rest_days
+            )) as resting,
+            {} as at_max_consecutive_weeks,
+            ($3 > 0 AND (
+                SELECT COUNT(DISTINCT a.job_id)
+                FROM assignments a
+                JOIN service_dates sd ON a.service_date_id = sd.id
+                WHERE a.person_id = p.id AND a.job_id <> $1
+                  AND date_trunc('month', sd.service_date) = date_trunc('month', $2::date)
+            ) >= $3) as at_max_jobs
+        FROM people p
+        JOIN person_jobs pj ON pj.person_id = p.id AND pj.job_id = $1
+        JOIN jobs j ON j.id = $1
+        WHERE p.active = true
+        "#,
+        AT_MAX_CONSECUTIVE_WEEKS
+    ))
+    .bind(job_id)
+    .bind(service_date)
+    .bind(max_jobs)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let consecutive_rule_applies = has_consecutive_month_restriction(job_name)
+        && count_sundays_in_month(service_date.year(), service_date.month()) <= 4;
+
+    let scores = fairness_scores(
+        &mut *conn,
+        fairness,
+        &rows.iter().map(|r| r.id.clone()).collect::<Vec<_>>(),
+        job_id,
+        service_date,
+    )
+    .await?;
+    let mut candidates: Vec<SlotCandidate> = rows
+        .into_iter()
+        .map(|row| SlotCandidate {
+            blocked_by: blocking_reasons(&row, consecutive_rule_applies),
+            fairness_score: scores.get(&row.id).copied().unwrap_or(0),
+            person_id: row.id,
+            person_name: row.person_name,
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        a.blocked_by
+            .len()
+            .cmp(&b.blocked_by.len())
+            .then(a.fairness_score.cmp(&b.fairness_score))
+            .then_with(|| a.person_name.cmp(&b.person_name))
+    });
+    candidates.truncate(limit);
+
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sundays_of_month_cover_four_and_five_sunday_months() {
+        let march = get_sundays_of_month(2026, 3);
+        assert_eq!(march.len(), 5);
+        assert_eq!(march[0], NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+        assert_eq!(get_sundays_of_month(2026, 2).len(), 4);
+        assert_eq!(
+            get_sundays_of_month(2026, 12).last(),
+            NaiveDate::from_ymd_opt(2026, 12, 27).as_ref()
+        );
+    }
+
+    #[test]
+    fn exclusive_jobs_match_in_both_directions_ignoring_case() {
+        assert!(are_jobs_exclusive("Monaguillos", "Lectores"));
+        assert!(are_jobs_exclusive("lectores", "MONAGUILLOS"));
+        assert!(are_jobs_exclusive("Monaguillos Jr", "Monaguillos"));
+        assert!(!are_jobs_exclusive("Lectores", "Monaguillos Jr"));
+        assert!(!are_jobs_exclusive("Lectores", "Lectores"));
+    }
+
+    #[test]
+    fn consecutive_month_restriction_covers_monaguillos_and_lectores() {
+        assert!(has_consecutive_month_restriction("Monaguillos"));
+        assert!(has_consecutive_month_restriction("lectores"));
+        assert!(!has_consecutive_month_restriction("Monaguillos Jr"));
+        assert!(!has_consecutive_month_restriction("Ujieres"));
+    }
+
+    #[test]
+    fn frequency_adjustment_follows_the_preferred_gap() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let sunday = date(2026, 3, 15);
+        let week_before = Some(date(2026, 3, 8));
+        assert_eq!(frequency_adjustment(Some("weekly"), week_before, sunday), 0);
+        assert_eq!(
+            frequency_adjustment(Some("bimonthly"), week_before, sunday),
+            2
+        );
+        assert_eq!(
+            frequency_adjustment(Some("monthly"), week_before, sunday),
+            3
+        );
+        assert_eq!(frequency_adjustment(None, week_before, sunday), 2);
+        assert_eq!(
+            frequency_adjustment(Some("monthly"), Some(date(2026, 2, 8)), sunday),
+            0
+        );
+        assert_eq!(frequency_adjustment(Some("monthly"), None, sunday), 0);
+    }
+
+    #[test]
+    fn blocking_reasons_are_empty_for_an_eligible_person() {
+        assert!(blocking_reasons(&SlotCheckRow::default(), true).is_empty());
+    }
+
+    #[test]
+    fn blocking_reasons_list_every_rule_in_order() {
+        let row = SlotCheckRow {
+            unavailable: true,
+            missing_first_communion: true,
+            at_max_consecutive_weeks: true,
+            at_max_jobs: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            blocking_reasons(&row, true),
+            vec![
+                "unavailable",
+                "first_communion",
+                "max_consecutive_weeks",
+                "max_jobs_per_month"
+            ]
+        );
+    }
+
+    #[test]
+    fn served_last_month_only_blocks_when_the_rule_applies() {
+        let row = SlotCheckRow {
+            served_last_month: true,
+            ..Default::default()
+        };
+        assert_eq!(blocking_reasons(&row, true), vec!["served_last_month"]);
+        assert!(blocking_reasons(&row, false).is_empty());
+    }
+}
@@ -0,0 +1,13 @@
+//! The client for the API's own outgoing requests (push services, the sign-in provider's keys)
+
+use std::time::Duration;
+
+/// How long any outside service gets to answer before the request is given up
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("HTTP client")
+}
@@ -0,0 +1,157 @@
+use axum::{
+    http::header,
+    response::{IntoResponse, Response},
+};
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+
+/// How long a timed event lasts; a Sunday Mass with the servidores arriving a bit early
+const EVENT_MINUTES: i64 = 90;
+
+/// One VEVENT of a calendar
+#[derive(Debug, Clone)]
+pub struct Event {
+    /// Stable across exports, so re-importing or refreshing a feed updates instead of duplicating
+    pub uid: String,
+    pub date: NaiveDate,
+    /// Local (floating) start time; None makes an all-day event
+    pub start: Option<NaiveTime>,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+}
+
+/// Escape a text value (RFC 5545 section 3.3.11)
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Write a content line, folding it at 75 octets without splitting a UTF-8 character
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// Build an iCalendar (RFC 5545) document named `name`
+pub fn to_calendar(name: &str, events: &[Event]) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//People Scheduler//ES");
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, "METHOD:PUBLISH");
+    push_line(&mut out, &format!("X-WR-CALNAME:{}", escape(name)));
+    for event in events {
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:{}", escape(&event.uid)));
+        push_line(&mut out, &format!("DTSTAMP:{}", stamp));
+        match event.start {
+            Some(time) => {
+                let start = event.date.and_time(time);
+                let end = start + Duration::minutes(EVENT_MINUTES);
+                push_line(
+                    &mut out,
+                    &format!("DTSTART:{}", start.format("%Y%m%dT%H%M%S")),
+                );
+                push_line(&mut out, &format!("DTEND:{}", end.format("%Y%m%dT%H%M%S")));
+            }
+            None => {
+                let end = event.date.succ_opt().unwrap_or(event.date);
+                push_line(
+                    &mut out,
+                    &format!("DTSTART;VALUE=DATE:{}", event.date.format("%Y%m%d")),
+                );
+                push_line(
+                    &mut out,
+                    &format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")),
+                );
+            }
+        }
+        push_line(&mut out, &format!("SUMMARY:{}", escape(&event.summary)));
+        if let Some(description) = &event.description {
+            push_line(&mut out, &format!("DESCRIPTION:{}", escape(description)));
+        }
+        if let Some(location) = &event.location {
+            push_line(&mut out, &format!("LOCATION:{}", escape(location)));
+        }
+        push_line(&mut out, "TRANSP:TRANSPARENT");
+        push_line(&mut out, "END:VEVENT");
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// Return a calendar as a file download
+pub fn attachment(filename: &str, body: String) -> Response {
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                "text/calendar; charset=utf-8".to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(start: Option<NaiveTime>) -> Event {
+        Event {
+            uid: "2026-11-01-lectores-1@people-scheduler".to_string(),
+            date: NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+            start,
+            summary: "Lectores, Primera lectura".to_string(),
+            description: Some("Llegar 20 min antes;\ntraer la hoja".to_string()),
+            location: None,
+        }
+    }
+
+    #[test]
+    fn timed_and_all_day_events() {
+        let timed = to_calendar("Fechas", &[event(NaiveTime::from_hms_opt(10, 0, 0))]);
+        assert!(timed.contains("DTSTART:20261101T100000\r\nDTEND:20261101T113000\r\n"));
+        assert!(timed.contains("SUMMARY:Lectores\\, Primera lectura\r\n"));
+        assert!(timed.contains("DESCRIPTION:Llegar 20 min antes\\;\\ntraer la hoja\r\n"));
+        assert!(!timed.contains("LOCATION"));
+
+        let all_day = to_calendar("Fechas", &[event(None)]);
+        assert!(all_day.contains("DTSTART;VALUE=DATE:20261101\r\nDTEND;VALUE=DATE:20261102\r\n"));
+        assert!(all_day.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(all_day.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn long_lines_fold_on_character_boundaries() {
+        let mut out = String::new();
+        push_line(&mut out, &format!("SUMMARY:{}", "á".repeat(60)));
+        let lines: Vec<&str> = out.split("\r\n").collect();
+        assert!(lines.len() > 2);
+        assert!(lines.iter().all(|l| l.len() <= 75));
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(
+            out.replace("\r\n ", ""),
+            format!("SUMMARY:{}\r\n", "á".repeat(60))
+        );
+    }
+}
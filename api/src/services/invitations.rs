@@ -0,0 +1,114 @@
+//! Invitation links (`invitations`): instead of handing out a generated password, a coordinator
+//! sends the servidor a one-time link "<invitation id>.<secret>". Opening it, the servidor picks
+//! a password and their login is created; until then there is no account to sign in to.
+
+use chrono::{Duration, Utc};
+use sqlx::{Executor, PgPool, Postgres};
+use uuid::Uuid;
+
+use crate::auth::{hash_password, verify_password};
+use crate::models::Invitation;
+use crate::services::tokens;
+
+/// Used when the `invitation_lifetime_days` setting is missing
+pub const DEFAULT_LIFETIME_DAYS: i64 = 7;
+
+const INVITATION_COLUMNS: &str = "person_id, created_by, created_at, expires_at, accepted_at";
+
+/// The invitation id and secret of a link's token
+pub fn split_token(token: &str) -> Option<(&str, &str)> {
+    let (id, secret) = token.trim().split_once('.')?;
+    if id.is_empty() || secret.is_empty() {
+        return None;
+    }
+    Some((id, secret))
+}
+
+/// A new link for the person, replacing any earlier one (which stops working); returns the
+/// invitation and the link's token, which is not stored
+pub async fn issue<'e, E>(
+    executor: E,
+    person_id: &str,
+    created_by: &str,
+    lifetime_days: i64,
+) -> Result<(Invitation, String), String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let id = Uuid::new_v4().simple().to_string();
+    let secret = tokens::generate_token();
+    let token_hash = hash_password(&secret).map_err(|e| e.to_string())?;
+
+    let invitation = sqlx::query_as::<_, Invitation>(&format!(
+        r#"
+        INSERT INTO invitations (id, person_id, token_hash, created_by, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (person_id) DO UPDATE SET
+            id = EXCLUDED.id, token_hash = EXCLUDED.token_hash,
+            created_by = EXCLUDED.created_by, created_at = NOW(),
+            expires_at = EXCLUDED.expires_at, accepted_at = NULL
+        RETURNING {}
+        "#,
+        INVITATION_COLUMNS
+    ))
+    .bind(&id)
+    .bind(person_id)
+    .bind(&token_hash)
+    .bind(created_by)
+    .bind(Utc::now() + Duration::days(lifetime_days.max(1)))
+    .fetch_one(executor)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok((invitation, format!("{}.{}", id, secret)))
+}
+
+pub async fn for_person<'e, E>(executor: E, person_id: &str) -> Result<Option<Invitation>, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as::<_, Invitation>(&format!(
+        "SELECT {} FROM invitations WHERE person_id = $1",
+        INVITATION_COLUMNS
+    ))
+    .bind(person_id)
+    .fetch_optional(executor)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// The invitation id and person of a link that can still be used; None when the token is wrong,
+/// the link was replaced, or it expired or was already used
+pub async fn find_open(pool: &PgPool, token: &str) -> Result<Option<(String, String)>, String> {
+    let Some((id, secret)) = split_token(token) else {
+        return Ok(None);
+    };
+    let found: Option<(String, String)> = sqlx::query_as(
+        r#"
+        SELECT token_hash, person_id FROM invitations
+        WHERE id = $1 AND accepted_at IS NULL AND expires_at > NOW()
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some((token_hash, person_id)) = found else {
+        return Ok(None);
+    };
+
+    Ok(verify_password(secret, &token_hash).then(|| (id.to_string(), person_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_split_into_id_and_secret() {
+        assert_eq!(split_token(" abc.def "), Some(("abc", "def")));
+        assert_eq!(split_token("abc"), None);
+        assert_eq!(split_token(".def"), None);
+        assert_eq!(split_token("abc."), None);
+    }
+}
@@ -0,0 +1,115 @@
+//! The SQS queue between the API and the worker Lambda (`src/worker.rs`). With
+//! BACKGROUND_JOBS_QUEUE_URL set, every queued job is also sent there as a message holding its
+//! id, and SQS invokes the worker with it, so a job starts within seconds of being queued
+//! even though the API Lambda stops between requests. The job itself (payload, status,
+//! attempts) stays in `background_jobs`. Without the variable nothing is sent and jobs wait
+//! for the polling worker or the scheduled run call.
+
+use aws_sdk_sqs::error::DisplayErrorContext;
+use aws_sdk_sqs::Client;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::OnceCell;
+
+/// The most SQS holds a message back; jobs due later come around again and are re-sent
+const MAX_DELAY_SECONDS: i64 = 900;
+
+static CLIENT: OnceCell<Client> = OnceCell::const_new();
+
+pub fn queue_url() -> Option<String> {
+    std::env::var("BACKGROUND_JOBS_QUEUE_URL")
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Credentials and region come from the Lambda's environment (or the usual AWS profile)
+async fn client() -> &'static Client {
+    CLIENT
+        .get_or_init(|| async { Client::new(&aws_config::load_from_env().await) })
+        .await
+}
+
+/// Seconds until `run_after`, as far as SQS can wait
+pub fn delay_seconds(run_after: DateTime<Utc>, now: DateTime<Utc>) -> i32 {
+    (run_after - now).num_seconds().clamp(0, MAX_DELAY_SECONDS) as i32
+}
+
+/// Send the worker a job, to be delivered when it is due. Does nothing without a queue.
+pub async fn send(job_id: &str, run_after: DateTime<Utc>) -> Result<(), String> {
+    let Some(queue_url) = queue_url() else {
+        return Ok(());
+    };
+    client()
+        .await
+        .send_message()
+        .queue_url(queue_url)
+        .message_body(job_id)
+        .delay_seconds(delay_seconds(run_after, Utc::now()))
+        .send()
+        .await
+        .map_err(|e| format!("Sending job {} to SQS: {}", job_id, DisplayErrorContext(e)))?;
+    Ok(())
+}
+
+/// The SQS event the worker Lambda is invoked with (only the fields it reads)
+#[derive(Debug, Deserialize)]
+pub struct SqsEvent {
+    #[serde(rename = "Records")]
+    pub records: Vec<SqsMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SqsMessage {
+    #[serde(rename = "messageId")]
+    pub message_id: String,
+    /// The job id
+    pub body: String,
+}
+
+/// The messages to deliver again (SQS's partial batch response); the rest are deleted
+#[derive(Debug, Default, Serialize)]
+pub struct BatchResponse {
+    #[serde(rename = "batchItemFailures")]
+    pub batch_item_failures: Vec<BatchItemFailure>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchItemFailure {
+    #[serde(rename = "itemIdentifier")]
+    pub item_identifier: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn delays_stop_at_what_sqs_allows() {
+        let now = Utc::now();
+        assert_eq!(delay_seconds(now - Duration::minutes(1), now), 0);
+        assert_eq!(delay_seconds(now + Duration::minutes(2), now), 120);
+        assert_eq!(delay_seconds(now + Duration::hours(1), now), 900);
+    }
+
+    #[test]
+    fn reads_the_sqs_event_and_answers_with_the_failed_messages() {
+        let event: SqsEvent = serde_json::from_str(
+            r#"{"Records": [{"messageId": "m1", "body": "job-1", "eventSource": "aws:sqs"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(event.records[0].message_id, "m1");
+        assert_eq!(event.records[0].body, "job-1");
+
+        let response = BatchResponse {
+            batch_item_failures: vec![BatchItemFailure {
+                item_identifier: "m1".to_string(),
+            }],
+        };
+        assert_eq!(
+            serde_json::to_value(response).unwrap(),
+            serde_json::json!({ "batchItemFailures": [{ "itemIdentifier": "m1" }] })
+        );
+    }
+}
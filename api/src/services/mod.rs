@@ -0,0 +1,39 @@
+pub mod age;
+pub mod api_keys;
+pub mod assignment_events;
+pub mod auth_events;
+pub mod background_jobs;
+pub mod change_notices;
+pub mod chat_bot;
+pub mod csv;
+pub mod edit_log;
+pub mod email;
+pub mod generator;
+pub mod http;
+pub mod ical;
+pub mod invitations;
+pub mod job_queue;
+pub mod naming;
+pub mod notifications;
+pub mod oidc;
+pub mod pdf;
+pub mod png;
+pub mod push;
+pub mod recurrence;
+pub mod reminders;
+pub mod rotation;
+pub mod schedule_events;
+pub mod sessions;
+pub mod sheets;
+pub mod signage;
+pub mod signing_keys;
+pub mod sms;
+pub mod snapshots;
+pub mod tokens;
+pub mod totp;
+pub mod two_factor;
+pub mod vcard;
+pub mod visibility;
+pub mod web_push;
+pub mod whatsapp;
+pub mod xlsx;
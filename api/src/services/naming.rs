@@ -0,0 +1,98 @@
+use chrono::Weekday;
+use sqlx::{Executor, Postgres};
+
+use crate::routes::settings;
+
+/// Used when the `schedule_name_pattern` setting is missing or empty
+pub const DEFAULT_SCHEDULE_NAME_PATTERN: &str = "{mes} {año}";
+
+const MONTH_NAMES: [&str; 12] = [
+    "Enero",
+    "Febrero",
+    "Marzo",
+    "Abril",
+    "Mayo",
+    "Junio",
+    "Julio",
+    "Agosto",
+    "Septiembre",
+    "Octubre",
+    "Noviembre",
+    "Diciembre",
+];
+
+pub fn month_name(month: i32) -> &'static str {
+    usize::try_from(month - 1)
+        .ok()
+        .and_then(|i| MONTH_NAMES.get(i))
+        .copied()
+        .unwrap_or("?")
+}
+
+pub fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Lunes",
+        Weekday::Tue => "Martes",
+        Weekday::Wed => "Miércoles",
+        Weekday::Thu => "Jueves",
+        Weekday::Fri => "Viernes",
+        Weekday::Sat => "Sábado",
+        Weekday::Sun => "Domingo",
+    }
+}
+
+/// Fill a naming pattern: `{mes}` is the Spanish month name, `{mm}` the two-digit month and
+/// `{año}` the year, e.g. "Monaguillos – {mes} {año}" gives "Monaguillos – Marzo 2026"
+pub fn schedule_name(pattern: &str, year: i32, month: i32) -> String {
+    pattern
+        .replace("{mes}", month_name(month))
+        .replace("{mm}", &format!("{:02}", month))
+        .replace("{año}", &year.to_string())
+}
+
+/// A pattern has to tell months apart, otherwise every schedule of a year gets the same name
+pub fn validate_pattern(pattern: &str) -> Result<(), String> {
+    if !pattern.contains("{año}") || !(pattern.contains("{mes}") || pattern.contains("{mm}")) {
+        return Err(
+            "schedule_name_pattern must contain {año} and either {mes} or {mm}".to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// The configured schedule name pattern, falling back to the default when unset or invalid
+pub async fn load_pattern<'e, E>(executor: E) -> Result<String, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let pattern = settings::get_string(executor, "schedule_name_pattern").await?;
+    Ok(pattern
+        .filter(|p| validate_pattern(p).is_ok())
+        .unwrap_or_else(|| DEFAULT_SCHEDULE_NAME_PATTERN.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_every_placeholder() {
+        assert_eq!(
+            schedule_name("Monaguillos – {mes} {año}", 2026, 3),
+            "Monaguillos – Marzo 2026"
+        );
+        assert_eq!(schedule_name("{mm}/{año}", 2026, 3), "03/2026");
+        assert_eq!(
+            schedule_name(DEFAULT_SCHEDULE_NAME_PATTERN, 2025, 12),
+            "Diciembre 2025"
+        );
+    }
+
+    #[test]
+    fn patterns_must_name_the_month_and_year() {
+        assert!(validate_pattern("{mes} {año}").is_ok());
+        assert!(validate_pattern("{mm}-{año}").is_ok());
+        assert!(validate_pattern("Horario {mes}").is_err());
+        assert!(validate_pattern("Horario {año}").is_err());
+    }
+}
@@ -0,0 +1,477 @@
+use sqlx::{Connection, PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::routes::settings;
+use crate::services::{email, http, push, sms, whatsapp};
+
+/// Store an in-app notification for a person (shown in /my-notifications)
+pub async fn notify_person(
+    conn: &mut PgConnection,
+    person_id: &str,
+    kind: &str,
+    message: &str,
+) -> Result<(), String> {
+    sqlx::query("INSERT INTO notifications (id, person_id, kind, message) VALUES ($1, $2, $3, $4)")
+        .bind(Uuid::new_v4().to_string())
+        .bind(person_id)
+        .bind(kind)
+        .bind(message)
+        .execute(conn)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// A notification that could not be delivered on an outside channel (email, SMS, ...)
+pub struct FailedDelivery<'a> {
+    pub person_id: &'a str,
+    pub channel: &'a str,
+    pub destination: Option<&'a str>,
+    pub kind: &'a str,
+    pub message: &'a str,
+    pub error: &'a str,
+    /// The address or number itself is bad; flags the person so the coordinator fixes it
+    pub permanent: bool,
+}
+
+/// Park a failed delivery in the dead-letter queue for a later retry
+pub async fn record_failure(
+    conn: &mut PgConnection,
+    failure: &FailedDelivery<'_>,
+) -> Result<(), String> {
+    sqlx::query(
+        r#"
+        INSERT INTO notification_failures
+            (id, person_id, channel, destination, kind, message, error, permanent)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(failure.person_id)
+    .bind(failure.channel)
+    .bind(failure.destination)
+    .bind(failure.kind)
+    .bind(failure.message)
+    .bind(failure.error)
+    .bind(failure.permanent)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if failure.permanent {
+        sqlx::query("UPDATE people SET contact_bounced = true WHERE id = $1")
+            .bind(failure.person_id)
+            .execute(conn)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    tracing::warn!(
+        "Notification {} to {} via {} failed: {}",
+        failure.kind,
+        failure.person_id,
+        failure.channel,
+        failure.error
+    );
+
+    Ok(())
+}
+
+/// Why a delivery did not go through
+#[derive(Debug)]
+pub struct DeliveryError {
+    pub error: String,
+    /// The address or number itself is bad, so retrying as-is will not help
+    pub permanent: bool,
+}
+
+impl DeliveryError {
+    pub fn transient(error: impl Into<String>) -> Self {
+        Self {
+            error: error.into(),
+            permanent: false,
+        }
+    }
+
+    pub fn permanent(error: impl Into<String>) -> Self {
+        Self {
+            error: error.into(),
+            permanent: true,
+        }
+    }
+}
+
+/// Where a notification on `channel` goes for this person (their current email or phone)
+async fn destination(
+    conn: &mut PgConnection,
+    channel: &str,
+    person_id: &str,
+) -> Result<Option<String>, String> {
+    let column = match channel {
+        "email" => "email",
+        "sms" | "whatsapp" => "phone",
+        _ => return Ok(None),
+    };
+
+    let value: Option<Option<String>> =
+        sqlx::query_scalar(&format!("SELECT {} FROM people WHERE id = $1", column))
+            .bind(person_id)
+            .fetch_optional(conn)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    Ok(value.flatten().filter(|v| !v.trim().is_empty()))
+}
+
+/// What is missing for this server to send on `channel`; None when it can
+pub fn missing_sender(channel: &str) -> Option<&'static str> {
+    match channel {
+        "email" if email::config().is_none() => Some("SMTP_HOST is not set"),
+        "sms" if sms::config().is_none() => {
+            Some("TWILIO_ACCOUNT_SID, TWILIO_AUTH_TOKEN and TWILIO_FROM are not set")
+        }
+        "whatsapp" if whatsapp::config().is_none() => {
+            Some("WHATSAPP_ACCESS_TOKEN and WHATSAPP_PHONE_NUMBER_ID are not set")
+        }
+        _ => None,
+    }
+}
+
+/// The person's phone in international digits, local numbers getting the
+/// `whatsapp_country_code` setting
+async fn international_phone(
+    conn: &mut PgConnection,
+    channel: &str,
+    person_id: &str,
+) -> Result<String, DeliveryError> {
+    let phone = destination(&mut *conn, channel, person_id)
+        .await
+        .map_err(DeliveryError::transient)?
+        .ok_or_else(|| DeliveryError::permanent("No phone on file"))?;
+    let country_code = settings::get_string(&mut *conn, "whatsapp_country_code")
+        .await
+        .map_err(DeliveryError::transient)?
+        .unwrap_or_else(|| "502".to_string());
+    whatsapp::international_number(&phone, country_code.trim())
+        .ok_or_else(|| DeliveryError::permanent(format!("Invalid phone number {}", phone)))
+}
+
+/// Send a notification on the given channel. Outside channels read the person's current
+/// email/phone, so a retry after the coordinator fixes the contact goes to the new one.
+/// Email, SMS and WhatsApp go out right away; pushes are queued for the push sender.
+/// Callers other than the worker and the retry endpoint should use `send`, which queues the
+/// outside channels and parks failures.
+pub async fn deliver(
+    conn: &mut PgConnection,
+    channel: &str,
+    person_id: &str,
+    kind: &str,
+    message: &str,
+) -> Result<(), DeliveryError> {
+    match channel {
+        "in_app" => notify_person(conn, person_id, kind, message)
+            .await
+            .map_err(DeliveryError::transient),
+        "email" => {
+            let address = destination(conn, channel, person_id)
+                .await
+                .map_err(DeliveryError::transient)?
+                .ok_or_else(|| DeliveryError::permanent("No email on file"))?;
+            if !address.contains('@') {
+                return Err(DeliveryError::permanent(format!(
+                    "Invalid email address {}",
+                    address
+                )));
+            }
+            let config = email::config()
+                .ok_or_else(|| DeliveryError::transient("No email sender: SMTP_HOST is not set"))?;
+            let email = email::message(&config.from, &address, kind, message)?;
+            email::send(&config, email).await
+        }
+        "sms" => {
+            let number = international_phone(conn, channel, person_id).await?;
+            let config = sms::config().ok_or_else(|| {
+                DeliveryError::transient("No SMS sender: the TWILIO_* settings are not set")
+            })?;
+            sms::send(&http::client(), &config, &number, message).await
+        }
+        // Queued for the push sender, one per subscribed browser or phone
+        "push" => match push::enqueue_for_person(conn, person_id, kind, message).await {
+            Ok(0) => Err(DeliveryError::transient(
+                "No browser or phone subscribed to notifications",
+            )),
+            Ok(_) => Ok(()),
+            Err(e) => Err(DeliveryError::transient(e)),
+        },
+        // In the approved template for its kind when there is one
+        "whatsapp" => {
+            let number = international_phone(&mut *conn, channel, person_id).await?;
+            let config = whatsapp::config().ok_or_else(|| {
+                DeliveryError::transient("No WhatsApp sender: the WHATSAPP_* settings are not set")
+            })?;
+            let template = whatsapp::template_for(conn, kind)
+                .await
+                .map_err(DeliveryError::transient)?;
+            whatsapp::send(
+                &http::client(),
+                &config,
+                &number,
+                message,
+                template.as_ref(),
+            )
+            .await
+        }
+        other => Err(DeliveryError::transient(format!(
+            "Unknown channel {}",
+            other
+        ))),
+    }
+}
+
+/// The channel the person chose (None = each notification's default) and whether they want
+/// a daily digest
+async fn preferences(
+    conn: &mut PgConnection,
+    person_id: &str,
+) -> Result<(Option<String>, bool), String> {
+    let row: Option<(Option<String>, bool)> =
+        sqlx::query_as("SELECT channel, digest FROM notification_preferences WHERE person_id = $1")
+            .bind(person_id)
+            .fetch_optional(conn)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    Ok(row.unwrap_or((None, false)))
+}
+
+/// Deliver a notification on the person's preferred channel, `channel` being the default for
+/// people without one. People who chose a daily digest have it held for `send_digests`
+/// instead (in-app notifications are always immediate). Email, SMS and WhatsApp are queued in
+/// `notification_outbox` on the caller's connection, for `send_queued` to deliver once the
+/// caller's transaction commits. Failures are parked in the dead-letter queue; this only
+/// errors if that itself fails.
+pub async fn send(
+    conn: &mut PgConnection,
+    channel: &str,
+    person_id: &str,
+    kind: &str,
+    message: &str,
+) -> Result<(), String> {
+    let (preferred, digest) = preferences(&mut *conn, person_id).await?;
+    let channel = preferred.as_deref().unwrap_or(channel);
+
+    if digest && channel != "in_app" {
+        sqlx::query(
+            r#"
+            INSERT INTO notification_digest_items (id, person_id, channel, kind, message)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(person_id)
+        .bind(channel)
+        .bind(kind)
+        .bind(message)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    match channel {
+        "email" | "sms" | "whatsapp" => {
+            sqlx::query(
+                r#"
+                INSERT INTO notification_outbox (id, person_id, channel, kind, message)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(person_id)
+            .bind(channel)
+            .bind(kind)
+            .bind(message)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+            Ok(())
+        }
+        _ => send_now(conn, channel, person_id, kind, message)
+            .await
+            .map(|_| ()),
+    }
+}
+
+/// `send` for a notice the caller's change should not wait on: it runs in a savepoint, so a
+/// failed statement is logged and undone without aborting the caller's transaction. Only
+/// errors if the savepoint itself cannot be opened or closed.
+pub async fn send_or_warn(
+    conn: &mut PgConnection,
+    channel: &str,
+    person_id: &str,
+    kind: &str,
+    message: &str,
+) -> Result<(), String> {
+    let mut savepoint = conn.begin().await.map_err(|e| e.to_string())?;
+    match send(&mut savepoint, channel, person_id, kind, message).await {
+        Ok(()) => savepoint.commit().await.map_err(|e| e.to_string()),
+        Err(e) => {
+            tracing::warn!("Could not queue {} notice for {}: {}", kind, person_id, e);
+            savepoint.rollback().await.map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// `deliver`, parking a failure in the dead-letter queue. Returns whether it went out.
+async fn send_now(
+    conn: &mut PgConnection,
+    channel: &str,
+    person_id: &str,
+    kind: &str,
+    message: &str,
+) -> Result<bool, String> {
+    let Err(failed) = deliver(&mut *conn, channel, person_id, kind, message).await else {
+        return Ok(true);
+    };
+
+    let destination = destination(&mut *conn, channel, person_id).await?;
+    record_failure(
+        conn,
+        &FailedDelivery {
+            person_id,
+            channel,
+            destination: destination.as_deref(),
+            kind,
+            message,
+            error: &failed.error,
+            permanent: failed.permanent,
+        },
+    )
+    .await
+    .map(|_| false)
+}
+
+/// Most queued notifications one call to `send_queued` delivers
+pub const SEND_LIMIT: i64 = 100;
+
+/// Deliver the queued email, SMS and WhatsApp notifications, oldest first, up to `SEND_LIMIT`.
+/// Each is taken off the queue before it is sent, so two workers never send it twice; one
+/// that fails is parked in the dead-letter queue like any other. Returns how many went out.
+pub async fn send_queued(pool: &PgPool) -> Result<u64, String> {
+    let queued = sqlx::query_as::<_, (String, String, String, String)>(
+        r#"
+        WITH taken AS (
+            DELETE FROM notification_outbox
+            WHERE id IN (
+                SELECT id FROM notification_outbox
+                ORDER BY created_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING person_id, channel, kind, message, created_at
+        )
+        SELECT person_id, channel, kind, message FROM taken ORDER BY created_at
+        "#,
+    )
+    .bind(SEND_LIMIT)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    if queued.is_empty() {
+        return Ok(0);
+    }
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let mut sent = 0;
+    for (person_id, channel, kind, message) in &queued {
+        if send_now(&mut conn, channel, person_id, kind, message).await? {
+            sent += 1;
+        }
+    }
+
+    Ok(sent)
+}
+
+/// One message out of a person's held notifications, oldest first
+pub fn digest_message(messages: &[String]) -> String {
+    match messages {
+        [only] => only.clone(),
+        _ => {
+            let mut out = format!("Tienes {} avisos:", messages.len());
+            for message in messages {
+                out.push_str(&format!("\n• {}", message));
+            }
+            out
+        }
+    }
+}
+
+/// Send everyone's held notifications, one digest per person on the channel they were held
+/// for. Meant to run once a day. Returns how many people got one.
+pub async fn send_digests(pool: &PgPool) -> Result<usize, String> {
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let items = sqlx::query_as::<_, (String, String, String, String)>(
+        r#"
+        SELECT id, person_id, channel, message FROM notification_digest_items
+        ORDER BY person_id, created_at
+        "#,
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut digests: Vec<(String, String, Vec<String>, Vec<String>)> = Vec::new();
+    for (id, person_id, channel, message) in items {
+        match digests.last_mut() {
+            Some((last, latest_channel, ids, messages)) if *last == person_id => {
+                *latest_channel = channel;
+                ids.push(id);
+                messages.push(message);
+            }
+            _ => digests.push((person_id, channel, vec![id], vec![message])),
+        }
+    }
+
+    for (person_id, channel, ids, messages) in &digests {
+        // Taken out first, so a second run at the same time does not send them again
+        let taken = sqlx::query("DELETE FROM notification_digest_items WHERE id = ANY($1)")
+            .bind(ids)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| e.to_string())?;
+        if taken.rows_affected() == 0 {
+            continue;
+        }
+        send_now(
+            &mut conn,
+            channel,
+            person_id,
+            "digest",
+            &digest_message(messages),
+        )
+        .await?;
+    }
+
+    Ok(digests.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_lists_the_held_messages() {
+        let one = vec!["Se publicó el horario \"Noviembre 2026\".".to_string()];
+        assert_eq!(digest_message(&one), one[0]);
+
+        let two = vec![
+            "Se publicó el horario \"Noviembre 2026\".".to_string(),
+            "Te asignaron a Coro el 15/11.".to_string(),
+        ];
+        assert_eq!(
+            digest_message(&two),
+            "Tienes 2 avisos:\n• Se publicó el horario \"Noviembre 2026\".\n• Te asignaron a Coro el 15/11."
+        );
+    }
+}
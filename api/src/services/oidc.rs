@@ -0,0 +1,405 @@
+//! Sign-in with an OpenID Connect provider. The browser gets an ID token from the provider
+//! (Google Identity Services for the diocese's Workspace accounts) and hands it to
+//! `POST /login/oidc`; here it is checked against the provider's signing keys and matched to
+//! an existing user by email. The provider's keys are fetched from its JWKS endpoint and kept
+//! in `oidc_signing_keys`, so every instance (and every Lambda) shares them.
+
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use sqlx::{Executor, PgConnection, PgPool, Postgres};
+
+use crate::auth::User;
+use crate::services::http;
+
+pub const DEFAULT_ISSUER: &str = "https://accounts.google.com";
+
+/// Stored keys older than this are fetched again; Google rotates its keys every few days
+const KEYS_MAX_AGE_HOURS: i32 = 6;
+
+/// A token signed with a key not in the stored set fetches the set again, at most this often,
+/// so made-up key ids cannot make the API hammer the provider
+const REFETCH_MINUTES: i32 = 1;
+
+/// From OIDC_CLIENT_ID, OIDC_ISSUER, OIDC_HOSTED_DOMAIN and OIDC_JWKS_URI
+#[derive(Debug, Clone)]
+pub struct OidcConfig {
+    pub client_id: String,
+    pub issuer: String,
+    /// Only accounts of this Workspace domain (the `hd` claim) may sign in
+    pub hosted_domain: Option<String>,
+    /// Where the signing keys are; None to look it up in the issuer's discovery document
+    pub jwks_uri: Option<String>,
+}
+
+fn env_value(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// None when OIDC_CLIENT_ID is not set: only local passwords are offered
+pub fn config() -> Option<OidcConfig> {
+    Some(OidcConfig {
+        client_id: env_value("OIDC_CLIENT_ID")?,
+        issuer: env_value("OIDC_ISSUER").unwrap_or_else(|| DEFAULT_ISSUER.to_string()),
+        hosted_domain: env_value("OIDC_HOSTED_DOMAIN").map(|d| d.to_lowercase()),
+        jwks_uri: env_value("OIDC_JWKS_URI"),
+    })
+}
+
+/// Google writes the issuer with or without the scheme, so both forms are accepted
+pub fn accepted_issuers(issuer: &str) -> Vec<String> {
+    let mut issuers = vec![issuer.to_string()];
+    if let Some(bare) = issuer.strip_prefix("https://") {
+        issuers.push(bare.to_string());
+    }
+    issuers
+}
+
+/// The ID token claims used here
+#[derive(Debug, Deserialize)]
+pub struct IdClaims {
+    pub iss: String,
+    pub sub: String,
+    pub email: Option<String>,
+    pub email_verified: Option<bool>,
+    pub hd: Option<String>,
+}
+
+/// The account's email, lowercased, once it is verified and from the allowed domain
+pub fn verified_email(claims: &IdClaims, hosted_domain: Option<&str>) -> Result<String, String> {
+    let email = claims
+        .email
+        .as_deref()
+        .map(|e| e.trim().to_lowercase())
+        .filter(|e| !e.is_empty())
+        .ok_or("The account has no email")?;
+    if claims.email_verified != Some(true) {
+        return Err("The account's email is not verified".to_string());
+    }
+    if let Some(domain) = hosted_domain {
+        if claims.hd.as_deref().map(str::to_lowercase).as_deref() != Some(domain) {
+            return Err(format!("Only {} accounts can sign in", domain));
+        }
+    }
+    Ok(email)
+}
+
+/// Check the token's signature, issuer, audience and expiry
+pub fn verify(token: &str, keys: &JwkSet, config: &OidcConfig) -> Result<IdClaims, String> {
+    let header = decode_header(token).map_err(|e| format!("Invalid ID token: {}", e))?;
+    let kid = header.kid.ok_or("The ID token has no key id")?;
+    let jwk = keys
+        .find(&kid)
+        .ok_or("The ID token was signed with an unknown key")?;
+    let key = DecodingKey::from_jwk(jwk).map_err(|e| e.to_string())?;
+
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.set_issuer(&accepted_issuers(&config.issuer));
+    validation.set_audience(&[&config.client_id]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+    let data = decode::<IdClaims>(token, &key, &validation)
+        .map_err(|e| format!("Invalid ID token: {}", e))?;
+    Ok(data.claims)
+}
+
+/// Every key needs an id and must be one jsonwebtoken can verify with
+pub fn check_keys(keys: &JwkSet) -> Result<(), String> {
+    if keys.keys.is_empty() {
+        return Err("The key set is empty".to_string());
+    }
+    for jwk in &keys.keys {
+        if jwk.common.key_id.is_none() {
+            return Err("Every key needs a kid".to_string());
+        }
+        DecodingKey::from_jwk(jwk).map_err(|e| format!("Unusable key: {}", e))?;
+    }
+    Ok(())
+}
+
+/// The stored keys and how many minutes ago the oldest was fetched; None when none are stored
+async fn load_keys<'e, E>(executor: E) -> Result<(JwkSet, Option<i32>), String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let stored: Vec<(String, i32)> = sqlx::query_as(
+        r#"
+        SELECT jwk, (EXTRACT(EPOCH FROM NOW() - updated_at) / 60)::int
+        FROM oidc_signing_keys
+        "#,
+    )
+    .fetch_all(executor)
+    .await
+    .map_err(|e| e.to_string())?;
+    let age = stored.iter().map(|(_, age)| *age).max();
+    let keys = stored
+        .iter()
+        .filter_map(|(jwk, _)| serde_json::from_str::<Jwk>(jwk).ok())
+        .collect();
+    Ok((JwkSet { keys }, age))
+}
+
+/// Swap the stored keys for the provider's current set
+async fn replace_keys(pool: &PgPool, keys: &JwkSet) -> Result<(), String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM oidc_signing_keys")
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    for jwk in &keys.keys {
+        sqlx::query("INSERT INTO oidc_signing_keys (kid, jwk) VALUES ($1, $2)")
+            .bind(&jwk.common.key_id)
+            .bind(serde_json::to_string(jwk).map_err(|e| e.to_string())?)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())
+}
+
+/// Whether the stored keys have to be fetched again before checking a token signed with `kid`
+fn needs_fetch(keys: &JwkSet, age_minutes: Option<i32>, kid: Option<&str>) -> bool {
+    let Some(age) = age_minutes else {
+        return true;
+    };
+    if age >= KEYS_MAX_AGE_HOURS * 60 {
+        return true;
+    }
+    let known = kid.is_some_and(|kid| keys.find(kid).is_some());
+    !known && age >= REFETCH_MINUTES
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<T, String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Fetching {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Fetching {}: answered {}", url, response.status()));
+    }
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Fetching {}: {}", url, e))?;
+    serde_json::from_slice(&body).map_err(|e| format!("Fetching {}: {}", url, e))
+}
+
+#[derive(Deserialize)]
+struct Discovery {
+    jwks_uri: String,
+}
+
+/// The provider's current key set, from OIDC_JWKS_URI or the issuer's discovery document
+async fn fetch_keys(config: &OidcConfig) -> Result<JwkSet, String> {
+    let client = http::client();
+    let jwks_uri = match &config.jwks_uri {
+        Some(uri) => uri.clone(),
+        None => {
+            let discovery_url = format!(
+                "{}/.well-known/openid-configuration",
+                config.issuer.trim_end_matches('/')
+            );
+            get_json::<Discovery>(&client, &discovery_url)
+                .await?
+                .jwks_uri
+        }
+    };
+    let keys: JwkSet = get_json(&client, &jwks_uri).await?;
+    check_keys(&keys)?;
+    Ok(keys)
+}
+
+/// The keys to check `id_token` with: the stored set, fetched again when it is old or does
+/// not have the token's key. When the provider cannot be reached the stored set is used.
+pub async fn current_keys(
+    pool: &PgPool,
+    config: &OidcConfig,
+    id_token: &str,
+) -> Result<JwkSet, String> {
+    let (stored, age) = load_keys(pool).await?;
+    let kid = decode_header(id_token).ok().and_then(|h| h.kid);
+    if !needs_fetch(&stored, age, kid.as_deref()) {
+        return Ok(stored);
+    }
+
+    match fetch_keys(config).await {
+        Ok(keys) => {
+            replace_keys(pool, &keys).await?;
+            Ok(keys)
+        }
+        Err(e) if !stored.keys.is_empty() => {
+            tracing::warn!("Keeping the stored OIDC keys: {}", e);
+            Ok(stored)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+const USER_SELECT: &str =
+    "SELECT u.id, u.username, u.password_hash, u.role, u.person_id FROM users u";
+
+/// The user an external account signs in as: the one it was matched to before, else the user
+/// with that email, else the user of the person with that email. The match is remembered.
+pub async fn find_user(
+    conn: &mut PgConnection,
+    claims: &IdClaims,
+    email: &str,
+) -> Result<Option<User>, String> {
+    let linked = sqlx::query_as::<_, User>(&format!(
+        "{} JOIN oidc_identities i ON i.user_id = u.id WHERE i.issuer = $1 AND i.subject = $2",
+        USER_SELECT
+    ))
+    .bind(&claims.iss)
+    .bind(&claims.sub)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let user = match linked {
+        Some(user) => Some(user),
+        None => {
+            let by_user_email =
+                sqlx::query_as::<_, User>(&format!("{} WHERE LOWER(u.email) = $1", USER_SELECT))
+                    .bind(email)
+                    .fetch_optional(&mut *conn)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            match by_user_email {
+                Some(user) => Some(user),
+                // Several people can share a family email; then it does not say who is signing in
+                None => {
+                    let mut by_person_email = sqlx::query_as::<_, User>(&format!(
+                        "{} JOIN people p ON u.person_id = p.id WHERE LOWER(p.email) = $1",
+                        USER_SELECT
+                    ))
+                    .bind(email)
+                    .fetch_all(&mut *conn)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                    if by_person_email.len() == 1 {
+                        by_person_email.pop()
+                    } else {
+                        None
+                    }
+                }
+            }
+        }
+    };
+
+    let Some(user) = user else {
+        return Ok(None);
+    };
+    sqlx::query(
+        r#"
+        INSERT INTO oidc_identities (issuer, subject, user_id, email)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (issuer, subject) DO UPDATE SET email = $4, last_login_at = NOW()
+        "#,
+    )
+    .bind(&claims.iss)
+    .bind(&claims.sub)
+    .bind(user.id)
+    .bind(email)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(Some(user))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(email: Option<&str>, verified: Option<bool>, hd: Option<&str>) -> IdClaims {
+        IdClaims {
+            iss: DEFAULT_ISSUER.to_string(),
+            sub: "1234".to_string(),
+            email: email.map(str::to_string),
+            email_verified: verified,
+            hd: hd.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn only_verified_emails_of_the_domain_sign_in() {
+        let ok = claims(
+            Some("Ana.Lopez@Parroquia.org"),
+            Some(true),
+            Some("parroquia.org"),
+        );
+        assert_eq!(
+            verified_email(&ok, Some("parroquia.org")),
+            Ok("ana.lopez@parroquia.org".to_string())
+        );
+        assert!(verified_email(&ok, None).is_ok());
+
+        let gmail = claims(Some("ana@gmail.com"), Some(true), None);
+        assert!(verified_email(&gmail, Some("parroquia.org")).is_err());
+        assert!(verified_email(&gmail, None).is_ok());
+
+        assert!(verified_email(&claims(Some("ana@gmail.com"), Some(false), None), None).is_err());
+        assert!(verified_email(&claims(None, Some(true), None), None).is_err());
+    }
+
+    #[test]
+    fn google_issuer_is_accepted_with_or_without_scheme() {
+        assert_eq!(
+            accepted_issuers(DEFAULT_ISSUER),
+            vec!["https://accounts.google.com", "accounts.google.com"]
+        );
+        assert_eq!(
+            accepted_issuers("https://login.example.org"),
+            vec!["https://login.example.org", "login.example.org"]
+        );
+    }
+
+    #[test]
+    fn tokens_signed_with_unknown_keys_are_refused() {
+        let config = OidcConfig {
+            client_id: "client".to_string(),
+            issuer: DEFAULT_ISSUER.to_string(),
+            hosted_domain: None,
+            jwks_uri: None,
+        };
+        // {"alg":"RS256","kid":"old"}.{"sub":"1"}.signature
+        let token = "eyJhbGciOiJSUzI1NiIsImtpZCI6Im9sZCJ9.eyJzdWIiOiIxIn0.c2ln";
+        let keys = JwkSet { keys: Vec::new() };
+        assert_eq!(
+            verify(token, &keys, &config).unwrap_err(),
+            "The ID token was signed with an unknown key"
+        );
+        assert!(verify("not a token", &keys, &config).is_err());
+        assert!(check_keys(&keys).is_err());
+    }
+
+    #[test]
+    fn keys_are_fetched_when_missing_old_or_without_the_token_key() {
+        let keys: JwkSet = serde_json::from_value(serde_json::json!({
+            "keys": [{ "kty": "RSA", "kid": "current", "alg": "RS256", "n": "AQAB", "e": "AQAB" }]
+        }))
+        .unwrap();
+
+        assert!(needs_fetch(
+            &JwkSet { keys: Vec::new() },
+            None,
+            Some("current")
+        ));
+        assert!(!needs_fetch(&keys, Some(30), Some("current")));
+        assert!(needs_fetch(
+            &keys,
+            Some(KEYS_MAX_AGE_HOURS * 60),
+            Some("current")
+        ));
+        assert!(needs_fetch(&keys, Some(30), Some("rotated")));
+        // Just fetched: an unknown key is not worth asking the provider about again yet
+        assert!(!needs_fetch(&keys, Some(0), Some("rotated")));
+    }
+}
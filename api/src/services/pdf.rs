@@ -0,0 +1,84 @@
+use axum::{
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+// Landscape A4, in points
+const PAGE_WIDTH: f32 = 842.0;
+const PAGE_HEIGHT: f32 = 595.0;
+
+/// A line of text centered on the page at height `y` (from the bottom)
+pub struct CenteredLine {
+    pub text: String,
+    pub size: f32,
+    pub y: f32,
+    pub bold: bool,
+}
+
+/// One landscape page with a thin border and centered lines, as used for certificates
+pub struct Page {
+    pub lines: Vec<CenteredLine>,
+}
+
+fn layout(page: &Page) -> scheduler_pdf::Page {
+    let mut out = scheduler_pdf::Page::default();
+    out.frame(28.0, 28.0, 786.0, 539.0, 0.6);
+    for line in &page.lines {
+        let x = ((PAGE_WIDTH - scheduler_pdf::text_width(&line.text, line.size)) / 2.0).max(0.0);
+        out.text(x, line.y, line.size, line.bold, &line.text);
+    }
+    out
+}
+
+/// Build a PDF document using the built-in Helvetica fonts (nothing is embedded)
+pub fn document(title: &str, pages: &[Page]) -> Result<Vec<u8>, String> {
+    let pages: Vec<scheduler_pdf::Page> = pages.iter().map(layout).collect();
+    scheduler_pdf::document(title, &pages, PAGE_WIDTH, PAGE_HEIGHT)
+}
+
+/// Return a PDF as a file download
+pub fn attachment(filename: &str, body: Vec<u8>) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_are_centered_inside_the_border() {
+        let page = layout(&Page {
+            lines: vec![CenteredLine {
+                text: "Certificado".to_string(),
+                size: 12.0,
+                y: 300.0,
+                bold: true,
+            }],
+        });
+        let width = scheduler_pdf::text_width("Certificado", 12.0);
+        assert_eq!(
+            page.marks[1],
+            scheduler_pdf::Mark::Text {
+                x: (PAGE_WIDTH - width) / 2.0,
+                y: 300.0,
+                size: 12.0,
+                bold: true,
+                text: "Certificado".to_string(),
+            }
+        );
+        assert!(matches!(page.marks[0], scheduler_pdf::Mark::Frame { .. }));
+
+        let pdf = document("Certificados", &[Page { lines: vec![] }]).unwrap();
+        assert!(pdf.starts_with(b"%PDF-"));
+    }
+}
@@ -0,0 +1,168 @@
+use std::io::Cursor;
+
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont};
+use axum::{
+    http::header,
+    response::{IntoResponse, Response},
+};
+use image::{ImageFormat, Rgb, RgbImage};
+
+// DejaVu Sans Mono (license in assets/fonts): monospaced, so a line's width and where it wraps
+// only depend on its number of characters, and it has the accents and ¿¡ of Spanish
+const FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSansMono.ttf");
+
+/// Advance and line height of the font at scale 1, spacing included
+pub const CHAR_WIDTH: u32 = 6;
+pub const LINE_HEIGHT: u32 = 13;
+
+/// An RGB image, drawn on top to bottom
+pub struct Canvas {
+    image: RgbImage,
+    font: FontRef<'static>,
+}
+
+impl Canvas {
+    /// A canvas filled with the background color
+    pub fn new(width: u32, height: u32, background: Rgb<u8>) -> Self {
+        Canvas {
+            image: RgbImage::from_pixel(width, height, background),
+            font: FontRef::try_from_slice(FONT).expect("the bundled font parses"),
+        }
+    }
+
+    /// Fill a rectangle, clipped to the canvas
+    pub fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, color: Rgb<u8>) {
+        for row in y..(y + height).min(self.image.height()) {
+            for col in x..(x + width).min(self.image.width()) {
+                self.image.put_pixel(col, row, color);
+            }
+        }
+    }
+
+    /// Draw a line of text with its top-left corner at (x, y), each character `CHAR_WIDTH *
+    /// scale` pixels wide; the line takes `LINE_HEIGHT * scale` pixels
+    pub fn text(&mut self, x: u32, y: u32, scale: u32, color: Rgb<u8>, text: &str) {
+        // Size the font so its advance is exactly CHAR_WIDTH pixels at scale 1
+        let units_per_em = self.font.units_per_em().unwrap_or(2048.0);
+        let advance = self.font.h_advance_unscaled(self.font.glyph_id('M'));
+        let size = (CHAR_WIDTH * scale) as f32 * units_per_em / advance;
+        let font = self.font.as_scaled(PxScale::from(size));
+        // Center the glyphs' ascent and descent in the line
+        let padding = ((LINE_HEIGHT * scale) as f32 - font.height()) / 2.0;
+        let baseline = y as f32 + padding + font.ascent();
+
+        for (i, c) in text.chars().enumerate() {
+            let left = (x + i as u32 * CHAR_WIDTH * scale) as f32;
+            let glyph = font
+                .glyph_id(c)
+                .with_scale_and_position(font.scale(), ab_glyph::point(left, baseline));
+            let Some(outline) = self.font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outline.px_bounds();
+            let image = &mut self.image;
+            outline.draw(|gx, gy, coverage| {
+                let px = bounds.min.x as i64 + gx as i64;
+                let py = bounds.min.y as i64 + gy as i64;
+                if px < 0 || py < 0 || px >= image.width() as i64 || py >= image.height() as i64 {
+                    return;
+                }
+                let pixel = image.get_pixel_mut(px as u32, py as u32);
+                let coverage = coverage.clamp(0.0, 1.0);
+                for (channel, ink) in pixel.0.iter_mut().zip(color.0) {
+                    *channel =
+                        (*channel as f32 * (1.0 - coverage) + ink as f32 * coverage).round() as u8;
+                }
+            });
+        }
+    }
+
+    /// The image as a PNG
+    pub fn to_png(&self) -> Result<Vec<u8>, String> {
+        let mut out = Cursor::new(Vec::new());
+        self.image
+            .write_to(&mut out, ImageFormat::Png)
+            .map_err(|e| e.to_string())?;
+        Ok(out.into_inner())
+    }
+}
+
+/// Pixel width of a line of text at the given scale
+pub fn text_width(text: &str, scale: u32) -> u32 {
+    text.chars().count() as u32 * CHAR_WIDTH * scale
+}
+
+/// Break text into lines of at most `max_chars` characters, on spaces
+pub fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let len = current.chars().count();
+        if len > 0 && len + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+pub fn attachment(filename: &str, body: Vec<u8>) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, "image/png".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: Rgb<u8> = Rgb([255, 255, 255]);
+    const BLACK: Rgb<u8> = Rgb([0, 0, 0]);
+
+    #[test]
+    fn text_stays_inside_its_cells_and_the_png_decodes() {
+        let mut canvas = Canvas::new(80, 40, WHITE);
+        canvas.fill(0, 0, 80, 10, BLACK);
+        canvas.text(2, 12, 2, BLACK, "Ñandú");
+
+        let inked: Vec<(u32, u32)> = canvas
+            .image
+            .enumerate_pixels()
+            .filter(|(_, y, p)| *y >= 10 && **p != WHITE)
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        assert!(!inked.is_empty());
+        let right = 2 + text_width("Ñandú", 2);
+        assert!(inked
+            .iter()
+            .all(|&(x, y)| x < right && (12..12 + LINE_HEIGHT * 2).contains(&y)));
+
+        let png = canvas.to_png().unwrap();
+        let decoded = image::load_from_memory_with_format(&png, ImageFormat::Png).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (80, 40));
+        assert_eq!(decoded.to_rgb8().get_pixel(5, 5), &BLACK);
+    }
+
+    #[test]
+    fn text_wraps_on_spaces() {
+        assert_eq!(
+            wrap("Primera lectura: Ana López", 16),
+            vec!["Primera lectura:", "Ana López"]
+        );
+        assert_eq!(wrap("", 16), vec![""]);
+    }
+}
@@ -0,0 +1,161 @@
+use sqlx::{Executor, FromRow, PgPool, Postgres};
+
+use crate::services::http;
+use crate::services::web_push::{self, Delivery, Vapid};
+
+/// Most pushes one call to `send_pending` sends
+pub const SEND_LIMIT: i64 = 100;
+
+/// The notification title shown on the phone for each kind of message
+pub fn title(kind: &str) -> &'static str {
+    match kind {
+        "service_reminder" => "Recordatorio de servicio",
+        "schedule_published" => "Horario publicado",
+        "schedule_retracted" => "Horario retirado",
+        "assignment_changed" => "Cambio en tu horario",
+        "service_date_cancelled" => "Servicio cancelado",
+        "digest" => "Resumen de avisos",
+        _ => "Horario de servicio",
+    }
+}
+
+/// Queue a push to every browser or phone where the person's users turned notifications on.
+/// Returns how many were queued; zero when they never subscribed.
+pub async fn enqueue_for_person<'e, E>(
+    executor: E,
+    person_id: &str,
+    kind: &str,
+    body: &str,
+) -> Result<u64, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    enqueue(executor, person_id, kind, body, false).await
+}
+
+/// A push on top of the notification sent on the person's channel, for people who have not
+/// said how they want to be told: one with a channel or digest preference only hears there
+pub async fn enqueue_extra<'e, E>(
+    executor: E,
+    person_id: &str,
+    kind: &str,
+    body: &str,
+) -> Result<u64, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    enqueue(executor, person_id, kind, body, true).await
+}
+
+async fn enqueue<'e, E>(
+    executor: E,
+    person_id: &str,
+    kind: &str,
+    body: &str,
+    unless_preferred: bool,
+) -> Result<u64, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let result = sqlx::query(
+        r#"
+        INSERT INTO push_outbox (id, subscription_id, kind, title, body)
+        SELECT gen_random_uuid()::text, s.id, $2, $3, $4
+        FROM push_subscriptions s
+        JOIN users u ON s.user_id = u.id
+        WHERE u.person_id = $1
+          AND NOT ($5 AND EXISTS (
+              SELECT 1 FROM notification_preferences np
+              WHERE np.person_id = $1 AND (np.channel IS NOT NULL OR np.digest)
+          ))
+        "#,
+    )
+    .bind(person_id)
+    .bind(kind)
+    .bind(title(kind))
+    .bind(body)
+    .bind(unless_preferred)
+    .execute(executor)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(result.rows_affected())
+}
+
+#[derive(FromRow)]
+struct Outgoing {
+    id: String,
+    subscription_id: String,
+    endpoint: String,
+    p256dh: String,
+    auth: String,
+    title: String,
+    body: String,
+}
+
+/// Send the queued pushes, oldest first, up to `SEND_LIMIT`. Each is taken off the queue
+/// before it is sent and not retried: a missed push is also in the person's in-app
+/// notifications or other channel. An expired subscription is removed. Nothing is sent, and
+/// the queue is left alone, while the VAPID keys are not configured.
+pub async fn send_pending(pool: &PgPool) -> Result<u64, String> {
+    let Some(vapid) = Vapid::from_env() else {
+        return Ok(0);
+    };
+
+    let messages = sqlx::query_as::<_, Outgoing>(
+        r#"
+        WITH taken AS (
+            DELETE FROM push_outbox
+            WHERE id IN (
+                SELECT id FROM push_outbox
+                ORDER BY created_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, subscription_id, title, body, created_at
+        )
+        SELECT t.id, t.subscription_id, s.endpoint, s.p256dh, s.auth, t.title, t.body
+        FROM taken t
+        JOIN push_subscriptions s ON t.subscription_id = s.id
+        ORDER BY t.created_at
+        "#,
+    )
+    .bind(SEND_LIMIT)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    if messages.is_empty() {
+        return Ok(0);
+    }
+
+    let client = http::client();
+    let mut sent = 0;
+    for message in messages {
+        // The service worker (public/sw.js) shows { title, body }
+        let payload = serde_json::json!({ "title": message.title, "body": message.body });
+        match web_push::send(
+            &client,
+            &vapid,
+            &message.endpoint,
+            &message.p256dh,
+            &message.auth,
+            payload.to_string().as_bytes(),
+        )
+        .await
+        {
+            Delivery::Sent => sent += 1,
+            Delivery::Gone => {
+                sqlx::query("DELETE FROM push_subscriptions WHERE id = $1")
+                    .bind(&message.subscription_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            Delivery::Failed(e) => {
+                tracing::warn!("Push {} was not delivered: {}", message.id, e);
+            }
+        }
+    }
+
+    Ok(sent)
+}
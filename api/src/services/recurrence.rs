@@ -0,0 +1,173 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// A day that repeats inside an unavailability period, written RRULE-style:
+/// `FREQ=WEEKLY;BYDAY=SA` is every Saturday, `FREQ=MONTHLY;BYDAY=1SU` every first Sunday
+/// and `FREQ=MONTHLY;BYDAY=-1SU` the last Sunday of each month
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recurrence {
+    pub weekday: Weekday,
+    /// Week of the month (1-5, -1 = last); None repeats every week
+    pub week: Option<i16>,
+}
+
+const DAY_CODES: [(&str, Weekday); 7] = [
+    ("SU", Weekday::Sun),
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+];
+
+impl Recurrence {
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let mut freq = None;
+        let mut by_day = None;
+        for part in rule.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid recurrence part: {}", part))?;
+            match key.trim().to_uppercase().as_str() {
+                "FREQ" => freq = Some(value.trim().to_uppercase()),
+                "BYDAY" => by_day = Some(value.trim().to_uppercase()),
+                other => return Err(format!("Unsupported recurrence field: {}", other)),
+            }
+        }
+
+        let by_day = by_day.ok_or("Recurrence needs BYDAY")?;
+        if by_day.len() < 2 || !by_day.is_ascii() {
+            return Err(format!("Invalid BYDAY: {}", by_day));
+        }
+        let (prefix, code) = by_day.split_at(by_day.len() - 2);
+        let weekday = DAY_CODES
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, d)| *d)
+            .ok_or_else(|| format!("Invalid day in BYDAY: {}", code))?;
+
+        match freq.as_deref() {
+            Some("WEEKLY") if prefix.is_empty() => Ok(Self {
+                weekday,
+                week: None,
+            }),
+            Some("WEEKLY") => Err("Weekly recurrences cannot pick a week of the month".to_string()),
+            Some("MONTHLY") => match prefix.parse::<i16>() {
+                Ok(week) if (1..=5).contains(&week) || week == -1 => Ok(Self {
+                    weekday,
+                    week: Some(week),
+                }),
+                _ => Err(format!(
+                    "Monthly recurrences need a week of the month (1-5 or -1): {}",
+                    by_day
+                )),
+            },
+            Some(other) => Err(format!(
+                "Unsupported FREQ: {}. Allowed: WEEKLY, MONTHLY",
+                other
+            )),
+            None => Err("Recurrence needs FREQ".to_string()),
+        }
+    }
+
+    /// Day of week as Postgres EXTRACT(DOW) numbers it (Sunday = 0)
+    pub fn dow(&self) -> i16 {
+        self.weekday.num_days_from_sunday() as i16
+    }
+
+    pub fn matches(&self, date: NaiveDate) -> bool {
+        if date.weekday() != self.weekday {
+            return false;
+        }
+        match self.week {
+            None => true,
+            Some(-1) => (date + Duration::days(7)).month() != date.month(),
+            Some(week) => ((date.day() - 1) / 7 + 1) as i16 == week,
+        }
+    }
+
+    /// Matching dates from `from` to `to` (both included), at most `limit`
+    pub fn occurrences(&self, from: NaiveDate, to: NaiveDate, limit: usize) -> Vec<NaiveDate> {
+        let offset = (7 + self.weekday.num_days_from_monday() as i64
+            - from.weekday().num_days_from_monday() as i64)
+            % 7;
+        let mut dates = Vec::new();
+        let mut date = from + Duration::days(offset);
+        while date <= to && dates.len() < limit {
+            if self.matches(date) {
+                dates.push(date);
+            }
+            date += Duration::days(7);
+        }
+        dates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parses_weekly_and_monthly_rules() {
+        assert_eq!(
+            Recurrence::parse("FREQ=WEEKLY;BYDAY=SA").unwrap(),
+            Recurrence {
+                weekday: Weekday::Sat,
+                week: None
+            }
+        );
+        assert_eq!(
+            Recurrence::parse("byday=-1su; freq=monthly").unwrap(),
+            Recurrence {
+                weekday: Weekday::Sun,
+                week: Some(-1)
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_incomplete_or_unknown_rules() {
+        assert!(Recurrence::parse("FREQ=WEEKLY").is_err());
+        assert!(Recurrence::parse("FREQ=DAILY;BYDAY=SU").is_err());
+        assert!(Recurrence::parse("FREQ=MONTHLY;BYDAY=SU").is_err());
+        assert!(Recurrence::parse("FREQ=MONTHLY;BYDAY=6SU").is_err());
+        assert!(Recurrence::parse("FREQ=WEEKLY;BYDAY=2SU").is_err());
+        assert!(Recurrence::parse("FREQ=WEEKLY;BYDAY=XX").is_err());
+    }
+
+    #[test]
+    fn first_and_last_sundays_of_the_month() {
+        let first = Recurrence::parse("FREQ=MONTHLY;BYDAY=1SU").unwrap();
+        let last = Recurrence::parse("FREQ=MONTHLY;BYDAY=-1SU").unwrap();
+        // March 2026: Sundays 1, 8, 15, 22, 29
+        assert_eq!(
+            first.occurrences(date(2026, 3, 1), date(2026, 4, 30), 10),
+            vec![date(2026, 3, 1), date(2026, 4, 5)]
+        );
+        assert_eq!(
+            last.occurrences(date(2026, 3, 1), date(2026, 4, 30), 10),
+            vec![date(2026, 3, 29), date(2026, 4, 26)]
+        );
+        assert!(!first.matches(date(2026, 3, 8)));
+    }
+
+    #[test]
+    fn weekly_occurrences_respect_range_and_limit() {
+        let saturdays = Recurrence::parse("FREQ=WEEKLY;BYDAY=SA").unwrap();
+        assert_eq!(
+            saturdays.occurrences(date(2026, 3, 2), date(2026, 3, 21), 10),
+            vec![date(2026, 3, 7), date(2026, 3, 14), date(2026, 3, 21)]
+        );
+        assert_eq!(
+            saturdays
+                .occurrences(date(2026, 3, 2), date(2026, 12, 31), 2)
+                .len(),
+            2
+        );
+        assert_eq!(saturdays.dow(), 6);
+    }
+}
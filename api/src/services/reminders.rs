@@ -0,0 +1,255 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use sqlx::PgPool;
+
+use crate::models::ServiceReminder;
+use crate::routes::settings;
+use crate::services::{naming, notifications};
+
+/// One published assignment on the date being reminded
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ReminderRow {
+    pub person_id: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub service_date_id: String,
+    pub service_date: NaiveDate,
+    pub job_name: String,
+    pub position_name: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+/// "Lectores, Monaguillos y Coro"
+fn join_spanish(items: &[String]) -> String {
+    match items.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} y {}", rest.join(", "), last),
+    }
+}
+
+/// The reminder text for one person, e.g. "Hola Ana, te recordamos que el domingo 15 de
+/// noviembre sirves en Lectores (Salmo) a las 10:30. ¡Gracias por tu servicio!"
+pub fn reminder_message(
+    first_name: &str,
+    date: NaiveDate,
+    jobs: &[String],
+    mass_time: Option<&str>,
+) -> String {
+    let mut message = format!(
+        "Hola {}, te recordamos que el {} {} de {} sirves en {}",
+        first_name,
+        naming::weekday_name(date.weekday()).to_lowercase(),
+        date.day(),
+        naming::month_name(date.month() as i32).to_lowercase(),
+        join_spanish(jobs)
+    );
+    if let Some(time) = mass_time {
+        message.push_str(&format!(" a las {}", time));
+    }
+    message.push_str(". ¡Gracias por tu servicio!");
+    message
+}
+
+/// One reminder per person and date. Rows must come ordered by person and date.
+pub fn group_reminders(rows: Vec<ReminderRow>, mass_time: Option<&str>) -> Vec<ServiceReminder> {
+    let mut reminders: Vec<(String, ServiceReminder)> = Vec::new();
+    for row in rows {
+        let job = match &row.position_name {
+            Some(position) => format!("{} ({})", row.job_name, position),
+            None => row.job_name.clone(),
+        };
+        if let Some((_, reminder)) = reminders.last_mut() {
+            if reminder.person_id == row.person_id
+                && reminder.service_date_id == row.service_date_id
+            {
+                reminder.jobs.push(job);
+                continue;
+            }
+        }
+        reminders.push((
+            row.first_name.clone(),
+            ServiceReminder {
+                person_name: format!("{} {}", row.first_name, row.last_name),
+                person_id: row.person_id,
+                service_date_id: row.service_date_id,
+                service_date: row.service_date,
+                jobs: vec![job],
+                message: String::new(),
+                sent_at: row.sent_at,
+            },
+        ));
+    }
+
+    reminders
+        .into_iter()
+        .map(|(first_name, mut reminder)| {
+            reminder.message = reminder_message(
+                &first_name,
+                reminder.service_date,
+                &reminder.jobs,
+                mass_time,
+            );
+            reminder
+        })
+        .collect()
+}
+
+/// Which reminders a run covers
+#[derive(Debug, Clone, Copy)]
+pub enum ReminderRun {
+    /// Everyone serving on this date, whatever their lead time (previewing or sending by hand)
+    Date(NaiveDate),
+    /// Everyone whose lead time ends today: their own `reminder_days_before` preference, or the
+    /// parish setting (`default_days`). A lead time of 0 means no reminders.
+    Due { today: NaiveDate, default_days: i64 },
+}
+
+/// The reminders a run covers, whether or not they went out yet. Cancelled dates and inactive
+/// people are left out.
+pub async fn reminders_for(
+    pool: &PgPool,
+    run: ReminderRun,
+) -> Result<Vec<ServiceReminder>, String> {
+    let (date, today, default_days) = match run {
+        ReminderRun::Date(date) => (Some(date), None, 0),
+        ReminderRun::Due {
+            today,
+            default_days,
+        } => (None, Some(today), default_days as i32),
+    };
+    let rows = sqlx::query_as::<_, ReminderRow>(
+        r#"
+        SELECT p.id as person_id, p.first_name, p.last_name, sd.id as service_date_id,
+               sd.service_date, j.name as job_name, a.position_name, r.sent_at
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN jobs j ON a.job_id = j.id
+        JOIN people p ON a.person_id = p.id
+        LEFT JOIN notification_preferences np ON np.person_id = p.id
+        LEFT JOIN service_reminders r
+            ON r.person_id = p.id AND r.service_date_id = sd.id
+        WHERE (sd.service_date = $1
+               OR ($1 IS NULL AND COALESCE(np.reminder_days_before, $3) > 0
+                   AND sd.service_date = $2::date + COALESCE(np.reminder_days_before, $3)))
+          AND sd.cancelled_at IS NULL
+          AND p.active = true
+          AND job_is_published(sd.schedule_id, a.job_id)
+        ORDER BY p.first_name, p.last_name, p.id, sd.id, j.name, a.position
+        "#,
+    )
+    .bind(date)
+    .bind(today)
+    .bind(default_days)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mass_time = settings::get_string(pool, "mass_time")
+        .await?
+        .map(|time| time.trim().to_string());
+    Ok(group_reminders(rows, mass_time.as_deref()))
+}
+
+/// Send the run's reminders that have not gone out yet, on the `reminder_channel` setting (or
+/// the person's own channel, see `notifications::send`).
+/// Outside channels are queued for the background worker; a delivery that fails there is
+/// parked in the dead-letter queue like any other notification and still counts as sent, so
+/// the next run does not repeat it. Returns the reminders sent now.
+pub async fn send_reminders(
+    pool: &PgPool,
+    run: ReminderRun,
+) -> Result<Vec<ServiceReminder>, String> {
+    let channel = settings::get_string(pool, "reminder_channel")
+        .await?
+        .map(|channel| channel.trim().to_string())
+        .unwrap_or_else(|| "email".to_string());
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let mut sent = Vec::new();
+    for mut reminder in reminders_for(pool, run).await? {
+        if reminder.sent_at.is_some() {
+            continue;
+        }
+        let recorded: Option<DateTime<Utc>> = sqlx::query_scalar(
+            r#"
+            INSERT INTO service_reminders (person_id, service_date_id, channel)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            RETURNING sent_at
+            "#,
+        )
+        .bind(&reminder.person_id)
+        .bind(&reminder.service_date_id)
+        .bind(&channel)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| e.to_string())?;
+        // Another run got there first
+        let Some(sent_at) = recorded else {
+            continue;
+        };
+
+        notifications::send(
+            &mut conn,
+            &channel,
+            &reminder.person_id,
+            "service_reminder",
+            &reminder.message,
+        )
+        .await?;
+        reminder.sent_at = Some(sent_at);
+        sent.push(reminder);
+    }
+
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(person: &str, job: &str, position: Option<&str>) -> ReminderRow {
+        ReminderRow {
+            person_id: person.to_lowercase(),
+            first_name: person.to_string(),
+            last_name: "López".to_string(),
+            service_date_id: "sd1".to_string(),
+            service_date: NaiveDate::from_ymd_opt(2026, 11, 15).unwrap(),
+            job_name: job.to_string(),
+            position_name: position.map(str::to_string),
+            sent_at: None,
+        }
+    }
+
+    #[test]
+    fn one_reminder_per_person_listing_their_jobs() {
+        let reminders = group_reminders(
+            vec![
+                row("Ana", "Coro", None),
+                row("Ana", "Lectores", Some("Salmo")),
+                row("Luis", "Monaguillos", Some("Monaguillo 1")),
+            ],
+            Some("10:30"),
+        );
+
+        assert_eq!(reminders.len(), 2);
+        assert_eq!(reminders[0].person_name, "Ana López");
+        assert_eq!(reminders[0].jobs, vec!["Coro", "Lectores (Salmo)"]);
+        assert_eq!(
+            reminders[0].message,
+            "Hola Ana, te recordamos que el domingo 15 de noviembre sirves en Coro y \
+             Lectores (Salmo) a las 10:30. ¡Gracias por tu servicio!"
+        );
+        assert_eq!(reminders[1].jobs, vec!["Monaguillos (Monaguillo 1)"]);
+    }
+
+    #[test]
+    fn message_leaves_out_a_blank_mass_time() {
+        let date = NaiveDate::from_ymd_opt(2026, 11, 14).unwrap();
+        assert_eq!(
+            reminder_message("Luis", date, &["Coro".to_string()], None),
+            "Hola Luis, te recordamos que el sábado 14 de noviembre sirves en Coro. \
+             ¡Gracias por tu servicio!"
+        );
+    }
+}
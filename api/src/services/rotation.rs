@@ -0,0 +1,169 @@
+use chrono::NaiveDate;
+use sqlx::PgConnection;
+
+/// Positions a person already did in their current rotation cycle for a job, with the cycle number.
+/// Untracked people get their state derived from assignment_history (latest positions back to the
+/// first repeat). Read-only: the state is stored by `mark_done` / `unmark_done` when an
+/// assignment is actually saved.
+pub async fn load(
+    conn: &mut PgConnection,
+    person_id: &str,
+    job_id: &str,
+) -> Result<(i32, Vec<i32>), String> {
+    let stored: Option<(i32, Vec<i32>)> = sqlx::query_as(
+        "SELECT cycle, done_positions FROM position_rotation WHERE person_id = $1 AND job_id = $2",
+    )
+    .bind(person_id)
+    .bind(job_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if let Some(state) = stored {
+        return Ok(state);
+    }
+
+    let history: Vec<Option<i32>> = sqlx::query_scalar(
+        r#"
+        SELECT position FROM assignment_history
+        WHERE person_id = $1 AND job_id = $2
+        ORDER BY service_date DESC
+        "#,
+    )
+    .bind(person_id)
+    .bind(job_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let mut done: Vec<i32> = Vec::new();
+    for pos in history.into_iter().flatten() {
+        if done.contains(&pos) {
+            // Found a repeat, cycle boundary
+            break;
+        }
+        done.push(pos);
+    }
+    done.reverse();
+
+    Ok((1, done))
+}
+
+/// Positions still owed in the cycle; once everything is done the bag is full again
+pub fn pending_positions(done: &[i32], num_positions: i32) -> Vec<i32> {
+    let bag: Vec<i32> = (1..=num_positions).filter(|p| !done.contains(p)).collect();
+    if bag.is_empty() {
+        (1..=num_positions).collect()
+    } else {
+        bag
+    }
+}
+
+/// Record that a person served a position on a date; repeating a position or finishing the cycle
+/// starts a new one. The cycle length is the job's positions that date (staffing overrides win).
+pub async fn mark_done(
+    conn: &mut PgConnection,
+    person_id: &str,
+    job_id: &str,
+    service_date: NaiveDate,
+    position: i32,
+) -> Result<(), String> {
+    let num_positions: i32 = sqlx::query_scalar(
+        r#"
+        SELECT COALESCE(o.people_required, j.people_required)
+        FROM jobs j
+        LEFT JOIN staffing_overrides o ON o.job_id = j.id AND o.service_date = $2
+        WHERE j.id = $1
+        "#,
+    )
+    .bind(job_id)
+    .bind(service_date)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let (mut cycle, mut done) = load(conn, person_id, job_id).await?;
+    let cycle_complete = (1..=num_positions).all(|p| done.contains(&p));
+    if done.contains(&position) || cycle_complete {
+        cycle += 1;
+        done = vec![position];
+    } else {
+        done.push(position);
+    }
+
+    save(conn, person_id, job_id, cycle, &done).await
+}
+
+/// Undo a position that is no longer served (slot cleared or given to someone else)
+pub async fn unmark_done(
+    conn: &mut PgConnection,
+    person_id: &str,
+    job_id: &str,
+    position: i32,
+) -> Result<(), String> {
+    let (cycle, mut done) = load(conn, person_id, job_id).await?;
+    if !done.contains(&position) {
+        return Ok(());
+    }
+    done.retain(|p| *p != position);
+
+    save(conn, person_id, job_id, cycle, &done).await
+}
+
+async fn save(
+    conn: &mut PgConnection,
+    person_id: &str,
+    job_id: &str,
+    cycle: i32,
+    done: &[i32],
+) -> Result<(), String> {
+    sqlx::query(
+        r#"
+        INSERT INTO position_rotation (person_id, job_id, cycle, done_positions, updated_at)
+        VALUES ($1, $2, $3, $4, NOW())
+        ON CONFLICT (person_id, job_id)
+        DO UPDATE SET cycle = $3, done_positions = $4, updated_at = NOW()
+        "#,
+    )
+    .bind(person_id)
+    .bind(job_id)
+    .bind(cycle)
+    .bind(done)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_cycle_owes_every_position() {
+        assert_eq!(pending_positions(&[], 4), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn done_positions_leave_the_bag() {
+        assert_eq!(pending_positions(&[1, 3], 4), vec![2, 4]);
+    }
+
+    #[test]
+    fn completed_cycle_refills_the_bag() {
+        assert_eq!(pending_positions(&[2, 1, 4, 3], 4), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn positions_beyond_a_smaller_count_are_ignored() {
+        // A date with fewer positions (staffing override) only owes what exists that day
+        assert_eq!(pending_positions(&[1, 5, 6], 3), vec![2, 3]);
+        assert_eq!(pending_positions(&[1, 2, 3, 5], 3), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn no_positions_means_an_empty_bag() {
+        assert!(pending_positions(&[], 0).is_empty());
+    }
+}
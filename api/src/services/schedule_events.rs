@@ -0,0 +1,35 @@
+use sqlx::{Executor, Postgres};
+use uuid::Uuid;
+
+/// Log a schedule status change made by `changed_by` (a username)
+pub async fn record<'e, E>(
+    executor: E,
+    schedule_id: &str,
+    from_status: &str,
+    to_status: &str,
+    reason: Option<&str>,
+    changed_by: &str,
+) -> Result<String, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let id = Uuid::new_v4().to_string();
+    sqlx::query(
+        r#"
+        INSERT INTO schedule_status_events
+            (id, schedule_id, from_status, to_status, reason, changed_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(&id)
+    .bind(schedule_id)
+    .bind(from_status)
+    .bind(to_status)
+    .bind(reason)
+    .bind(changed_by)
+    .execute(executor)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(id)
+}
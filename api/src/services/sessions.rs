@@ -0,0 +1,224 @@
+//! Login sessions (`user_sessions`). Each login gets a session whose id travels in the access
+//! token (`sid`) and a refresh token "<session id>.<secret>" to get new access tokens with.
+//! Revoking a session stops both: `auth_middleware` refuses access tokens of ended sessions.
+
+use chrono::{Duration, Utc};
+use sqlx::{Executor, PgPool, Postgres};
+use uuid::Uuid;
+
+use crate::auth::{hash_password, verify_password, User};
+use crate::models::UserSession;
+use crate::services::auth_events::RequestOrigin;
+use crate::services::tokens;
+
+/// Used when the `session_lifetime_days` setting is missing
+pub const DEFAULT_LIFETIME_DAYS: i64 = 30;
+
+/// The session id and secret of a refresh token
+pub fn split_refresh_token(token: &str) -> Option<(&str, &str)> {
+    let (id, secret) = token.trim().split_once('.')?;
+    if id.is_empty() || secret.is_empty() {
+        return None;
+    }
+    Some((id, secret))
+}
+
+/// Start a session; returns its id and refresh token
+pub async fn create<'e, E>(
+    executor: E,
+    user_id: Uuid,
+    origin: &RequestOrigin,
+    lifetime_days: i64,
+) -> Result<(String, String), String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let id = Uuid::new_v4().to_string();
+    let secret = tokens::generate_token();
+    let refresh_hash = hash_password(&secret).map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO user_sessions (id, user_id, refresh_hash, device, ip, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(&id)
+    .bind(user_id)
+    .bind(&refresh_hash)
+    .bind(&origin.user_agent)
+    .bind(&origin.ip)
+    .bind(Utc::now() + Duration::days(lifetime_days.max(1)))
+    .execute(executor)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let refresh_token = format!("{}.{}", id, secret);
+    Ok((id, refresh_token))
+}
+
+/// Trade a refresh token for its user, session id and a new refresh token; the old one stops
+/// working. None when the token is wrong or its session ended or expired.
+pub async fn refresh(
+    pool: &PgPool,
+    refresh_token: &str,
+    origin: &RequestOrigin,
+) -> Result<Option<(User, String, String)>, String> {
+    let Some((id, secret)) = split_refresh_token(refresh_token) else {
+        return Ok(None);
+    };
+    let session: Option<(String, Uuid)> = sqlx::query_as(
+        r#"
+        SELECT refresh_hash, user_id FROM user_sessions
+        WHERE id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some((refresh_hash, user_id)) = session else {
+        return Ok(None);
+    };
+    if !verify_password(secret, &refresh_hash) {
+        return Ok(None);
+    }
+
+    let new_secret = tokens::generate_token();
+    let new_hash = hash_password(&new_secret).map_err(|e| e.to_string())?;
+    // Compare-and-swap on the old hash, so two refreshes of the same token cannot both win
+    let rotated = sqlx::query(
+        r#"
+        UPDATE user_sessions
+        SET refresh_hash = $3, last_used_at = NOW(), ip = COALESCE($4, ip)
+        WHERE id = $1 AND refresh_hash = $2 AND revoked_at IS NULL
+        "#,
+    )
+    .bind(id)
+    .bind(&refresh_hash)
+    .bind(&new_hash)
+    .bind(&origin.ip)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    if rotated.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    let user = sqlx::query_as::<_, User>(
+        "SELECT id, username, password_hash, role, person_id FROM users WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(user.map(|user| (user, id.to_string(), format!("{}.{}", id, new_secret))))
+}
+
+/// Whether the session is still on; notes that it was used (at most once a minute)
+pub async fn is_active(pool: &PgPool, id: &str) -> Result<bool, String> {
+    sqlx::query_scalar(
+        r#"
+        WITH active AS (
+            SELECT id FROM user_sessions
+            WHERE id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+        ), touched AS (
+            UPDATE user_sessions SET last_used_at = NOW()
+            WHERE id IN (SELECT id FROM active) AND last_used_at < NOW() - INTERVAL '1 minute'
+        )
+        SELECT EXISTS(SELECT 1 FROM active)
+        "#,
+    )
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// The user's sessions that are still on, most recently used first. `current` marks the one
+/// with id `current_id`.
+pub async fn list<'e, E>(
+    executor: E,
+    user_id: Uuid,
+    current_id: Option<&str>,
+) -> Result<Vec<UserSession>, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as::<_, UserSession>(
+        r#"
+        SELECT id, device, ip, created_at, last_used_at, expires_at,
+               id = COALESCE($2, '') AS current
+        FROM user_sessions
+        WHERE user_id = $1 AND revoked_at IS NULL AND expires_at > NOW()
+        ORDER BY last_used_at DESC
+        "#,
+    )
+    .bind(user_id)
+    .bind(current_id)
+    .fetch_all(executor)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// End one of the user's sessions; false when it is not theirs or already ended
+pub async fn revoke<'e, E>(executor: E, user_id: Uuid, id: &str) -> Result<bool, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let result = sqlx::query(
+        r#"
+        UPDATE user_sessions SET revoked_at = NOW()
+        WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL
+        "#,
+    )
+    .bind(id)
+    .bind(user_id)
+    .execute(executor)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// End all of the user's sessions, but `except` when given. Returns how many ended.
+pub async fn revoke_all<'e, E>(
+    executor: E,
+    user_id: Uuid,
+    except: Option<&str>,
+) -> Result<u64, String>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    let result = sqlx::query(
+        r#"
+        UPDATE user_sessions SET revoked_at = NOW()
+        WHERE user_id = $1 AND revoked_at IS NULL AND id <> COALESCE($2, '')
+        "#,
+    )
+    .bind(user_id)
+    .bind(except)
+    .execute(executor)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_tokens_are_session_id_and_secret() {
+        assert_eq!(
+            split_refresh_token("0b6f-11.Xk3pQ"),
+            Some(("0b6f-11", "Xk3pQ"))
+        );
+        assert_eq!(split_refresh_token(" s1.abc \n"), Some(("s1", "abc")));
+        assert_eq!(split_refresh_token("no-secret"), None);
+        assert_eq!(split_refresh_token(".abc"), None);
+        assert_eq!(split_refresh_token("s1."), None);
+    }
+}
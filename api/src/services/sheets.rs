@@ -0,0 +1,441 @@
+//! Google Sheets exports: published assignments written into a tab of a shared spreadsheet
+//! through the Sheets API, signed in as a service account (GOOGLE_SERVICE_ACCOUNT_KEY, the
+//! account's JSON key). The spreadsheet has to be shared with the account's email as an
+//! editor. `sync` writes every export whose rows changed since the last write; the background
+//! worker calls it, so edits to a published schedule reach the sheet within a few seconds.
+
+use chrono::{Datelike, NaiveDate, Utc};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{PgConnection, PgPool};
+use utoipa::ToSchema;
+
+use crate::services::http;
+
+const SCOPE: &str = "https://www.googleapis.com/auth/spreadsheets";
+const SHEETS_API: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+const DEFAULT_TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+
+/// A failed export waits this long before the worker tries it again
+const RETRY_MINUTES: i32 = 5;
+
+/// Columns of an export, in Spanish like the sheet the coordinators already keep
+pub const HEADER: [&str; 6] = [
+    "Fecha",
+    "Ministerio",
+    "Posición",
+    "Servidor",
+    "Notas",
+    "Notas del día",
+];
+
+/// One published slot as it appears in the sheet
+#[derive(Debug, Clone, PartialEq, Serialize, sqlx::FromRow, ToSchema)]
+pub struct SheetRow {
+    pub service_date: NaiveDate,
+    pub job_name: String,
+    pub position: Option<i32>,
+    pub position_name: Option<String>,
+    pub person_name: Option<String>,
+    pub notes: Option<String>,
+    pub service_date_notes: Option<String>,
+}
+
+/// Published assignments from `from` onwards, optionally for a single job. Drafts (and jobs not
+/// yet published on their own) stay out, so the sheet only shows what servidores can see.
+pub async fn published_rows(
+    conn: &mut PgConnection,
+    from: NaiveDate,
+    job_id: Option<&str>,
+) -> Result<Vec<SheetRow>, String> {
+    sqlx::query_as::<_, SheetRow>(
+        r#"
+        SELECT sd.service_date, j.name as job_name, a.position, a.position_name,
+               p.first_name || ' ' || p.last_name as person_name, a.notes,
+               sd.notes as service_date_notes
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN jobs j ON a.job_id = j.id
+        LEFT JOIN people p ON a.person_id = p.id
+        WHERE sd.service_date >= $1
+          AND ($2::VARCHAR IS NULL OR a.job_id = $2)
+          AND job_is_published(sd.schedule_id, a.job_id)
+        ORDER BY sd.service_date, j.name, a.position
+        "#,
+    )
+    .bind(from)
+    .bind(job_id)
+    .fetch_all(conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Format rows for the sheet: dates as dd/mm/yyyy (what a Spanish-locale sheet parses as a
+/// date), the position's name or its number, and empty slots left blank
+pub fn to_grid(rows: &[SheetRow]) -> Vec<Vec<String>> {
+    rows.iter()
+        .map(|row| {
+            vec![
+                row.service_date.format("%d/%m/%Y").to_string(),
+                row.job_name.clone(),
+                row.position_name
+                    .clone()
+                    .or_else(|| row.position.map(|p| p.to_string()))
+                    .unwrap_or_default(),
+                row.person_name.clone().unwrap_or_default(),
+                row.notes.clone().unwrap_or_default(),
+                row.service_date_notes.clone().unwrap_or_default(),
+            ]
+        })
+        .collect()
+}
+
+/// The fields of a service account's JSON key used here
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServiceAccount {
+    pub client_email: String,
+    pub private_key: String,
+    #[serde(default = "default_token_uri")]
+    pub token_uri: String,
+}
+
+fn default_token_uri() -> String {
+    DEFAULT_TOKEN_URI.to_string()
+}
+
+/// None when GOOGLE_SERVICE_ACCOUNT_KEY is not set
+pub fn service_account() -> Result<Option<ServiceAccount>, String> {
+    let Some(key) = std::env::var("GOOGLE_SERVICE_ACCOUNT_KEY")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+    else {
+        return Ok(None);
+    };
+    serde_json::from_str(&key).map(Some).map_err(|e| {
+        format!(
+            "GOOGLE_SERVICE_ACCOUNT_KEY is not a service account key: {}",
+            e
+        )
+    })
+}
+
+#[derive(Serialize)]
+struct AssertionClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+/// Trade a signed assertion for an access token (the OAuth 2.0 JWT bearer grant)
+async fn access_token(
+    client: &reqwest::Client,
+    account: &ServiceAccount,
+) -> Result<String, String> {
+    let now = Utc::now().timestamp();
+    let claims = AssertionClaims {
+        iss: &account.client_email,
+        scope: SCOPE,
+        aud: &account.token_uri,
+        iat: now,
+        exp: now + 3600,
+    };
+    let key = EncodingKey::from_rsa_pem(account.private_key.as_bytes())
+        .map_err(|e| format!("Invalid service account private key: {}", e))?;
+    let assertion = encode(&Header::new(Algorithm::RS256), &claims, &key)
+        .map_err(|e| format!("Could not sign the token request: {}", e))?;
+
+    let response = client
+        .post(&account.token_uri)
+        .form(&[
+            ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            ("assertion", assertion.as_str()),
+        ])
+        .send()
+        .await
+        .map_err(|e| format!("Google sign-in: {}", e))?;
+    let body = checked_body(response, "Google sign-in").await?;
+    let token: TokenResponse =
+        serde_json::from_slice(&body).map_err(|e| format!("Google sign-in: {}", e))?;
+    Ok(token.access_token)
+}
+
+/// The body of a successful answer, or the status and Google's message
+async fn checked_body(response: reqwest::Response, what: &str) -> Result<Vec<u8>, String> {
+    let status = response.status();
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| format!("{}: {}", what, e))?;
+    if !status.is_success() {
+        return Err(format!(
+            "{} answered {}: {}",
+            what,
+            status,
+            String::from_utf8_lossy(&body)
+        ));
+    }
+    Ok(body.to_vec())
+}
+
+/// The whole tab in A1 notation; quotes in the name are doubled
+pub fn tab_range(sheet_name: &str) -> String {
+    format!("'{}'", sheet_name.replace('\'', "''"))
+}
+
+/// The spreadsheet id, given either the id itself or the sheet's address
+/// (`https://docs.google.com/spreadsheets/d/<id>/edit...`)
+pub fn spreadsheet_id(input: &str) -> Option<String> {
+    let input = input.trim();
+    let id = match input.split_once("/spreadsheets/d/") {
+        Some((_, rest)) => rest.split(['/', '?', '#']).next().unwrap_or_default(),
+        None => input,
+    };
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then(|| id.to_string())
+}
+
+fn values_url(spreadsheet_id: &str, range: &str, action: &str) -> Result<reqwest::Url, String> {
+    let mut url = reqwest::Url::parse(SHEETS_API).map_err(|e| e.to_string())?;
+    url.path_segments_mut()
+        .map_err(|_| "Invalid Sheets API address".to_string())?
+        .push(spreadsheet_id)
+        .push("values")
+        .push(&format!("{}{}", range, action));
+    Ok(url)
+}
+
+/// Replace the tab's contents with the header and rows: clear it, then write from A1
+pub async fn write_tab(
+    client: &reqwest::Client,
+    token: &str,
+    spreadsheet_id: &str,
+    sheet_name: &str,
+    grid: &[Vec<String>],
+) -> Result<(), String> {
+    let range = tab_range(sheet_name);
+
+    let response = client
+        .post(values_url(spreadsheet_id, &range, ":clear")?)
+        .bearer_auth(token)
+        .header("Content-Type", "application/json")
+        .body("{}")
+        .send()
+        .await
+        .map_err(|e| format!("Clearing the sheet: {}", e))?;
+    checked_body(response, "Clearing the sheet").await?;
+
+    let mut values = vec![HEADER.iter().map(|h| h.to_string()).collect::<Vec<_>>()];
+    values.extend(grid.iter().cloned());
+    let body = serde_json::json!({ "range": range, "majorDimension": "ROWS", "values": values });
+    let mut url = values_url(spreadsheet_id, &range, "")?;
+    // USER_ENTERED so the dates land as dates, as if typed in
+    url.query_pairs_mut()
+        .append_pair("valueInputOption", "USER_ENTERED");
+    let response = client
+        .put(url)
+        .bearer_auth(token)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .send()
+        .await
+        .map_err(|e| format!("Writing the sheet: {}", e))?;
+    checked_body(response, "Writing the sheet").await?;
+    Ok(())
+}
+
+/// Digest of what an export would write, to tell whether the sheet is already up to date
+pub fn grid_digest(grid: &[Vec<String>]) -> String {
+    let text = serde_json::to_string(grid).unwrap_or_default();
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Exported rows start at the first of the current month
+pub fn export_start() -> NaiveDate {
+    let today = Utc::now().date_naive();
+    today.with_day(1).unwrap_or(today)
+}
+
+#[derive(sqlx::FromRow)]
+struct ExportTarget {
+    id: String,
+    spreadsheet_id: String,
+    sheet_name: String,
+    job_id: Option<String>,
+    content_hash: Option<String>,
+    last_error: Option<String>,
+}
+
+/// Write the exports whose rows changed since their last write (all of them with `force`),
+/// or just the one with `only`. Each export records its outcome; returns how many were written.
+/// An export that failed is tried again after `RETRY_MINUTES`, unless forced.
+pub async fn sync(pool: &PgPool, only: Option<&str>, force: bool) -> Result<usize, String> {
+    let exports = sqlx::query_as::<_, ExportTarget>(
+        r#"
+        SELECT id, spreadsheet_id, sheet_name, job_id, content_hash, last_error
+        FROM sheet_exports
+        WHERE ($1::VARCHAR IS NULL OR id = $1)
+          AND ($2 OR last_error IS NULL OR last_error_at < NOW() - make_interval(mins => $3))
+        "#,
+    )
+    .bind(only)
+    .bind(force)
+    .bind(RETRY_MINUTES)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    if exports.is_empty() {
+        return Ok(0);
+    }
+
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    let from = export_start();
+    let mut changed = Vec::new();
+    for export in exports {
+        let rows = published_rows(&mut conn, from, export.job_id.as_deref()).await?;
+        let grid = to_grid(&rows);
+        let digest = grid_digest(&grid);
+        if force || export.content_hash.as_deref() != Some(digest.as_str()) {
+            changed.push((export, grid, digest));
+        }
+    }
+    if changed.is_empty() {
+        return Ok(0);
+    }
+
+    let client = http::client();
+    let token = match service_account()?
+        .ok_or_else(|| "GOOGLE_SERVICE_ACCOUNT_KEY is not set".to_string())
+    {
+        Ok(account) => access_token(&client, &account).await,
+        Err(e) => Err(e),
+    };
+    let mut written = 0;
+    for (export, grid, digest) in changed {
+        let outcome = match &token {
+            Ok(token) => {
+                write_tab(
+                    &client,
+                    token,
+                    &export.spreadsheet_id,
+                    &export.sheet_name,
+                    &grid,
+                )
+                .await
+            }
+            Err(e) => Err(e.clone()),
+        };
+        match outcome {
+            Ok(()) => {
+                written += 1;
+                sqlx::query(
+                    r#"
+                    UPDATE sheet_exports
+                    SET content_hash = $2, last_pushed_at = NOW(), last_error = NULL,
+                        last_error_at = NULL
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(&export.id)
+                .bind(&digest)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| e.to_string())?;
+            }
+            Err(e) => {
+                if export.last_error.as_deref() != Some(e.as_str()) {
+                    tracing::warn!("Sheet export {} was not written: {}", export.id, e);
+                }
+                sqlx::query(
+                    "UPDATE sheet_exports SET last_error = $2, last_error_at = NOW() WHERE id = $1",
+                )
+                .bind(&export.id)
+                .bind(&e)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(position: Option<i32>, position_name: Option<&str>, person: Option<&str>) -> SheetRow {
+        SheetRow {
+            service_date: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            job_name: "Coro".to_string(),
+            position,
+            position_name: position_name.map(str::to_string),
+            person_name: person.map(str::to_string),
+            notes: None,
+            service_date_notes: None,
+        }
+    }
+
+    #[test]
+    fn formats_dates_positions_and_empty_slots() {
+        let grid = to_grid(&[
+            row(Some(1), Some("Soprano"), Some("Ana Pérez")),
+            row(Some(2), None, None),
+            row(None, None, Some("Luis Gómez")),
+        ]);
+        assert_eq!(
+            grid[0],
+            vec!["01/03/2026", "Coro", "Soprano", "Ana Pérez", "", ""]
+        );
+        assert_eq!(grid[1], vec!["01/03/2026", "Coro", "2", "", "", ""]);
+        assert_eq!(grid[2][2], "");
+    }
+
+    #[test]
+    fn spreadsheets_are_found_by_id_or_address() {
+        let id = "1AbC-d_EfGh";
+        assert_eq!(spreadsheet_id(id).as_deref(), Some(id));
+        assert_eq!(
+            spreadsheet_id(&format!(
+                "https://docs.google.com/spreadsheets/d/{}/edit#gid=0",
+                id
+            ))
+            .as_deref(),
+            Some(id)
+        );
+        assert_eq!(spreadsheet_id("not a sheet"), None);
+        assert_eq!(spreadsheet_id(""), None);
+    }
+
+    #[test]
+    fn tabs_are_quoted_and_requests_encoded() {
+        assert_eq!(tab_range("Horario"), "'Horario'");
+        assert_eq!(tab_range("Coro's"), "'Coro''s'");
+        let url = values_url("abc", &tab_range("Mis asignaciones"), ":clear").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://sheets.googleapis.com/v4/spreadsheets/abc/values/'Mis%20asignaciones':clear"
+        );
+    }
+
+    #[test]
+    fn digests_change_with_the_rows() {
+        let grid = to_grid(&[row(Some(1), None, Some("Ana Pérez"))]);
+        assert_eq!(grid_digest(&grid), grid_digest(&grid.clone()));
+        assert_ne!(grid_digest(&grid), grid_digest(&[]));
+    }
+}
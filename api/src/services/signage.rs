@@ -0,0 +1,354 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use sqlx::{FromRow, PgConnection};
+use utoipa::ToSchema;
+
+/// Bumped only when a field is removed or changes meaning; adding fields keeps the version.
+/// The narthex display reads this, not the internal models, so those can change freely.
+pub const SIGNAGE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, PartialEq, ToSchema)]
+pub struct SignageSchedule {
+    pub version: u32,
+    pub schedule_id: String,
+    pub name: String,
+    pub year: i32,
+    pub month: i32,
+    pub dates: Vec<SignageDate>,
+}
+
+#[derive(Debug, Serialize, PartialEq, ToSchema)]
+pub struct SignageDate {
+    pub date: NaiveDate,
+    /// "HH:MM" from the mass_time setting; None when not configured
+    pub mass_time: Option<String>,
+    pub notes: Option<String>,
+    pub cancelled: bool,
+    pub jobs: Vec<SignageJob>,
+}
+
+#[derive(Debug, Serialize, PartialEq, ToSchema)]
+pub struct SignageJob {
+    pub id: String,
+    pub name: String,
+    pub color: Option<String>,
+    pub positions: Vec<SignagePosition>,
+}
+
+#[derive(Debug, Serialize, PartialEq, ToSchema)]
+pub struct SignagePosition {
+    pub number: Option<i32>,
+    pub name: Option<String>,
+    /// None for a slot nobody fills yet
+    pub person: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// A service date of the schedule as the display needs it
+#[derive(Debug, Clone, FromRow)]
+pub struct DateRow {
+    pub id: String,
+    pub service_date: NaiveDate,
+    pub notes: Option<String>,
+    pub cancelled: bool,
+}
+
+/// One slot of a published job
+#[derive(Debug, Clone, FromRow)]
+pub struct SlotRow {
+    pub service_date_id: String,
+    pub job_id: String,
+    pub job_name: String,
+    pub job_color: Option<String>,
+    pub position: Option<i32>,
+    pub position_name: Option<String>,
+    pub person_name: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// The schedule's dates and the slots of its published jobs, in display order
+pub async fn load(
+    conn: &mut PgConnection,
+    schedule_id: &str,
+) -> Result<(Vec<DateRow>, Vec<SlotRow>), String> {
+    let dates = sqlx::query_as::<_, DateRow>(
+        r#"
+        SELECT id, service_date, notes, cancelled_at IS NOT NULL as cancelled
+        FROM service_dates
+        WHERE schedule_id = $1
+        ORDER BY service_date
+        "#,
+    )
+    .bind(schedule_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let slots = sqlx::query_as::<_, SlotRow>(
+        r#"
+        SELECT a.service_date_id, j.id as job_id, j.name as job_name, j.color as job_color,
+               a.position, a.position_name,
+               p.first_name || ' ' || p.last_name as person_name, a.notes
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN jobs j ON a.job_id = j.id
+        LEFT JOIN people p ON a.person_id = p.id
+        WHERE sd.schedule_id = $1 AND job_is_published(sd.schedule_id, a.job_id)
+        ORDER BY j.name, a.position
+        "#,
+    )
+    .bind(schedule_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok((dates, slots))
+}
+
+/// A date with its slots grouped by job; `slots` come ordered by job and position
+fn build_date(date: DateRow, mass_time: Option<&str>, slots: &[SlotRow]) -> SignageDate {
+    let mut jobs: Vec<SignageJob> = Vec::new();
+    for slot in slots.iter().filter(|s| s.service_date_id == date.id) {
+        let position = SignagePosition {
+            number: slot.position,
+            name: slot.position_name.clone(),
+            person: slot.person_name.clone(),
+            notes: slot.notes.clone(),
+        };
+        match jobs.iter_mut().find(|j| j.id == slot.job_id) {
+            Some(job) => job.positions.push(position),
+            None => jobs.push(SignageJob {
+                id: slot.job_id.clone(),
+                name: slot.job_name.clone(),
+                color: slot.job_color.clone(),
+                positions: vec![position],
+            }),
+        }
+    }
+    SignageDate {
+        date: date.service_date,
+        mass_time: mass_time.map(str::to_string),
+        notes: date.notes,
+        cancelled: date.cancelled,
+        jobs,
+    }
+}
+
+/// Group slots under their date and job; `slots` come ordered by job and position
+pub fn build(
+    schedule_id: &str,
+    name: &str,
+    year: i32,
+    month: i32,
+    mass_time: Option<&str>,
+    dates: Vec<DateRow>,
+    slots: &[SlotRow],
+) -> SignageSchedule {
+    let dates = dates
+        .into_iter()
+        .map(|date| build_date(date, mass_time, slots))
+        .collect();
+
+    SignageSchedule {
+        version: SIGNAGE_VERSION,
+        schedule_id: schedule_id.to_string(),
+        name: name.to_string(),
+        year,
+        month,
+        dates,
+    }
+}
+
+// ============ Next service ============
+
+/// The next service dates across schedules, read every week by the WhatsApp bot and the
+/// kiosk. Same versioning rule as the signage schedule.
+#[derive(Debug, Serialize, PartialEq, ToSchema)]
+pub struct NextServices {
+    pub version: u32,
+    pub services: Vec<NextService>,
+}
+
+#[derive(Debug, Serialize, PartialEq, ToSchema)]
+pub struct NextService {
+    pub schedule_id: String,
+    pub schedule_name: String,
+    #[serde(flatten)]
+    pub date: SignageDate,
+    pub filled_slots: usize,
+    pub open_slots: usize,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct UpcomingDateRow {
+    pub schedule_id: String,
+    pub schedule_name: String,
+    #[sqlx(flatten)]
+    pub date: DateRow,
+}
+
+/// The first `count` service dates from `from` (up to `until` when given) that have a
+/// published job or were cancelled, with their published slots
+pub async fn load_upcoming(
+    conn: &mut PgConnection,
+    from: NaiveDate,
+    until: Option<NaiveDate>,
+    count: i64,
+) -> Result<(Vec<UpcomingDateRow>, Vec<SlotRow>), String> {
+    let dates = sqlx::query_as::<_, UpcomingDateRow>(
+        r#"
+        SELECT s.id as schedule_id, s.name as schedule_name,
+               sd.id, sd.service_date, sd.notes, sd.cancelled_at IS NOT NULL as cancelled
+        FROM service_dates sd
+        JOIN schedules s ON sd.schedule_id = s.id
+        WHERE sd.service_date >= $1
+          AND ($2::date IS NULL OR sd.service_date <= $2)
+          AND (
+              sd.cancelled_at IS NOT NULL
+              OR EXISTS (
+                  SELECT 1 FROM assignments a
+                  WHERE a.service_date_id = sd.id AND job_is_published(s.id, a.job_id)
+              )
+          )
+        ORDER BY sd.service_date, s.year, s.month
+        LIMIT $3
+        "#,
+    )
+    .bind(from)
+    .bind(until)
+    .bind(count)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let date_ids: Vec<&str> = dates.iter().map(|d| d.date.id.as_str()).collect();
+    let slots = sqlx::query_as::<_, SlotRow>(
+        r#"
+        SELECT a.service_date_id, j.id as job_id, j.name as job_name, j.color as job_color,
+               a.position, a.position_name,
+               p.first_name || ' ' || p.last_name as person_name, a.notes
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN jobs j ON a.job_id = j.id
+        LEFT JOIN people p ON a.person_id = p.id
+        WHERE sd.id = ANY($1) AND job_is_published(sd.schedule_id, a.job_id)
+        ORDER BY j.name, a.position
+        "#,
+    )
+    .bind(&date_ids)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok((dates, slots))
+}
+
+pub fn build_next(
+    mass_time: Option<&str>,
+    dates: Vec<UpcomingDateRow>,
+    slots: &[SlotRow],
+) -> NextServices {
+    let services = dates
+        .into_iter()
+        .map(|row| {
+            let date = build_date(row.date, mass_time, slots);
+            let positions: Vec<&SignagePosition> =
+                date.jobs.iter().flat_map(|j| &j.positions).collect();
+            let filled_slots = positions.iter().filter(|p| p.person.is_some()).count();
+            let open_slots = positions.len() - filled_slots;
+            NextService {
+                schedule_id: row.schedule_id,
+                schedule_name: row.schedule_name,
+                date,
+                filled_slots,
+                open_slots,
+            }
+        })
+        .collect();
+
+    NextServices {
+        version: SIGNAGE_VERSION,
+        services,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(date_id: &str, job: &str, position: i32, person: Option<&str>) -> SlotRow {
+        SlotRow {
+            service_date_id: date_id.to_string(),
+            job_id: job.to_lowercase(),
+            job_name: job.to_string(),
+            job_color: Some("#3B82F6".to_string()),
+            position: Some(position),
+            position_name: None,
+            person_name: person.map(str::to_string),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn groups_slots_by_date_and_job() {
+        let dates = vec![
+            DateRow {
+                id: "d1".to_string(),
+                service_date: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+                notes: Some("Primeras comuniones".to_string()),
+                cancelled: false,
+            },
+            DateRow {
+                id: "d2".to_string(),
+                service_date: NaiveDate::from_ymd_opt(2026, 3, 8).unwrap(),
+                notes: None,
+                cancelled: true,
+            },
+        ];
+        let slots = vec![
+            slot("d1", "Lectores", 1, Some("Ana Pérez")),
+            slot("d1", "Monaguillos", 1, Some("Luis Gómez")),
+            slot("d1", "Monaguillos", 2, None),
+        ];
+
+        let signage = build("s1", "Marzo 2026", 2026, 3, Some("10:00"), dates, &slots);
+
+        assert_eq!(signage.version, SIGNAGE_VERSION);
+        assert_eq!(signage.dates.len(), 2);
+        let first = &signage.dates[0];
+        assert_eq!(first.mass_time.as_deref(), Some("10:00"));
+        assert_eq!(first.jobs.len(), 2);
+        assert_eq!(first.jobs[1].name, "Monaguillos");
+        assert_eq!(first.jobs[1].positions.len(), 2);
+        assert_eq!(first.jobs[1].positions[1].person, None);
+        assert!(signage.dates[1].cancelled);
+        assert!(signage.dates[1].jobs.is_empty());
+    }
+
+    #[test]
+    fn next_services_count_filled_and_open_slots() {
+        let date = UpcomingDateRow {
+            schedule_id: "s1".to_string(),
+            schedule_name: "Marzo 2026".to_string(),
+            date: DateRow {
+                id: "d1".to_string(),
+                service_date: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+                notes: None,
+                cancelled: false,
+            },
+        };
+        let slots = vec![
+            slot("d1", "Lectores", 1, Some("Ana Pérez")),
+            slot("d1", "Monaguillos", 1, None),
+            slot("d1", "Monaguillos", 2, None),
+            slot("d2", "Lectores", 1, Some("Luis Gómez")),
+        ];
+
+        let next = build_next(None, vec![date], &slots);
+
+        assert_eq!(next.services.len(), 1);
+        assert_eq!(next.services[0].filled_slots, 1);
+        assert_eq!(next.services[0].open_slots, 2);
+        assert_eq!(next.services[0].date.jobs.len(), 2);
+    }
+}
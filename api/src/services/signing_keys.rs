@@ -0,0 +1,206 @@
+//! The keys JWTs are signed with. Several can be active at once, each with an id that goes in
+//! the token header (`kid`), so the secret can be rotated without logging everyone out: add
+//! the new key in front, let it sign, and drop the old one once its tokens have expired.
+//!
+//! Configured with JWT_KEYS (`kid=secret,kid=secret`) and JWT_SIGNING_KEY_ID (default the
+//! first key). A lone JWT_SECRET still works as the key `default`. Without either the API does
+//! not start, unless JWT_EPHEMERAL_KEY=true asks for a random key (local development only).
+
+use std::sync::OnceLock;
+
+use jsonwebtoken::{DecodingKey, EncodingKey};
+
+/// Id of the key a lone JWT_SECRET becomes, which tokens from before key ids map to
+pub const LEGACY_KEY_ID: &str = "default";
+
+#[derive(Debug)]
+struct SigningKey {
+    kid: String,
+    secret: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct KeyRing {
+    keys: Vec<SigningKey>,
+    /// Index of the key new tokens are signed with
+    signing: usize,
+}
+
+fn env_value(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Read the key ring from the JWT_KEYS, JWT_SIGNING_KEY_ID and JWT_SECRET values. None when
+/// no key is configured.
+pub fn parse(
+    jwt_keys: Option<&str>,
+    signing_kid: Option<&str>,
+    legacy_secret: Option<&str>,
+) -> Result<Option<KeyRing>, String> {
+    let mut keys = Vec::new();
+    match jwt_keys {
+        Some(list) => {
+            for entry in list.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+                let (kid, secret) = entry
+                    .split_once('=')
+                    .map(|(k, s)| (k.trim(), s.trim()))
+                    .filter(|(k, s)| !k.is_empty() && !s.is_empty())
+                    .ok_or_else(|| format!("JWT_KEYS entry {:?} is not kid=secret", entry))?;
+                if keys.iter().any(|k: &SigningKey| k.kid == kid) {
+                    return Err(format!("JWT_KEYS has the key id {} twice", kid));
+                }
+                keys.push(SigningKey {
+                    kid: kid.to_string(),
+                    secret: secret.as_bytes().to_vec(),
+                });
+            }
+        }
+        None => {
+            if let Some(secret) = legacy_secret {
+                keys.push(SigningKey {
+                    kid: LEGACY_KEY_ID.to_string(),
+                    secret: secret.as_bytes().to_vec(),
+                });
+            }
+        }
+    }
+    if keys.is_empty() {
+        return Ok(None);
+    }
+
+    let signing = match signing_kid {
+        Some(kid) => keys
+            .iter()
+            .position(|k| k.kid == kid)
+            .ok_or_else(|| format!("JWT_SIGNING_KEY_ID {} is not one of JWT_KEYS", kid))?,
+        None => 0,
+    };
+    Ok(Some(KeyRing { keys, signing }))
+}
+
+/// A key for this process only: every restart logs everyone out, and Lambda instances do not
+/// accept each other's tokens. Only meant for local development.
+fn ephemeral() -> KeyRing {
+    use rand::RngCore;
+    let mut secret = vec![0u8; 32];
+    rand::thread_rng().fill_bytes(&mut secret);
+    KeyRing {
+        keys: vec![SigningKey {
+            kid: "ephemeral".to_string(),
+            secret,
+        }],
+        signing: 0,
+    }
+}
+
+/// The configured keys; with none, the process-only key if it was asked for, otherwise an error
+fn configured_or_ephemeral(
+    configured: Option<KeyRing>,
+    allow_ephemeral: bool,
+) -> Result<KeyRing, String> {
+    match configured {
+        Some(ring) => Ok(ring),
+        None if allow_ephemeral => Ok(ephemeral()),
+        None => Err(
+            "set JWT_KEYS or JWT_SECRET (or JWT_EPHEMERAL_KEY=true for a random key in local \
+             development)"
+                .to_string(),
+        ),
+    }
+}
+
+impl KeyRing {
+    /// The id and key new tokens are signed with
+    pub fn signing_key(&self) -> (&str, EncodingKey) {
+        let key = &self.keys[self.signing];
+        (&key.kid, EncodingKey::from_secret(&key.secret))
+    }
+
+    /// The keys a token may have been signed with: the one its `kid` names, or, for tokens
+    /// from before key ids, every key
+    pub fn verifying_keys(&self, kid: Option<&str>) -> Vec<DecodingKey> {
+        self.keys
+            .iter()
+            .filter(|k| kid.is_none_or(|kid| k.kid == kid))
+            .map(|k| DecodingKey::from_secret(&k.secret))
+            .collect()
+    }
+
+    pub fn key_ids(&self) -> Vec<&str> {
+        self.keys.iter().map(|k| k.kid.as_str()).collect()
+    }
+}
+
+static KEYS: OnceLock<KeyRing> = OnceLock::new();
+
+/// The configured keys, read once. Panics on a bad or missing configuration, so the servers
+/// call this at startup rather than finding out at the first login.
+pub fn keys() -> &'static KeyRing {
+    KEYS.get_or_init(|| {
+        // Unit tests sign their own tokens without any configuration
+        let allow_ephemeral =
+            cfg!(test) || env_value("JWT_EPHEMERAL_KEY").as_deref() == Some("true");
+        let ring = parse(
+            env_value("JWT_KEYS").as_deref(),
+            env_value("JWT_SIGNING_KEY_ID").as_deref(),
+            env_value("JWT_SECRET").as_deref(),
+        )
+        .and_then(|parsed| configured_or_ephemeral(parsed, allow_ephemeral))
+        .unwrap_or_else(|e| panic!("Invalid JWT key configuration: {}", e));
+        if ring.key_ids() == ["ephemeral"] {
+            tracing::warn!(
+                "JWT_EPHEMERAL_KEY set; signing with a random key that lasts until restart"
+            );
+        }
+        ring
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_read_in_order_with_their_ids() {
+        let ring = parse(Some("2026-10=new, 2026-04=old"), None, Some("ignored"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(ring.key_ids(), vec!["2026-10", "2026-04"]);
+        assert_eq!(ring.signing_key().0, "2026-10");
+        assert_eq!(ring.verifying_keys(Some("2026-04")).len(), 1);
+        assert_eq!(ring.verifying_keys(Some("2025-01")).len(), 0);
+        assert_eq!(ring.verifying_keys(None).len(), 2);
+
+        let ring = parse(Some("a=1,b=2"), Some("b"), None).unwrap().unwrap();
+        assert_eq!(ring.signing_key().0, "b");
+    }
+
+    #[test]
+    fn a_lone_secret_is_the_default_key() {
+        let ring = parse(None, None, Some("secret")).unwrap().unwrap();
+        assert_eq!(ring.key_ids(), vec![LEGACY_KEY_ID]);
+        assert!(parse(None, None, None).unwrap().is_none());
+    }
+
+    #[test]
+    fn a_random_key_has_to_be_asked_for() {
+        assert!(configured_or_ephemeral(None, false).is_err());
+        let ring = configured_or_ephemeral(None, true).unwrap();
+        assert_eq!(ring.key_ids(), vec!["ephemeral"]);
+
+        let configured = parse(None, None, Some("secret")).unwrap();
+        let ring = configured_or_ephemeral(configured, true).unwrap();
+        assert_eq!(ring.key_ids(), vec![LEGACY_KEY_ID]);
+    }
+
+    #[test]
+    fn bad_configurations_are_refused() {
+        assert!(parse(Some("no-secret"), None, None).is_err());
+        assert!(parse(Some("a="), None, None).is_err());
+        assert!(parse(Some("a=1,a=2"), None, None).is_err());
+        assert!(parse(Some("a=1"), Some("b"), None).is_err());
+    }
+}
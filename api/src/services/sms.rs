@@ -0,0 +1,96 @@
+//! SMS notifications, sent through Twilio's REST API. Configured with TWILIO_ACCOUNT_SID,
+//! TWILIO_AUTH_TOKEN and TWILIO_FROM (the sending number or messaging service id); without
+//! them nothing is sent by SMS.
+
+use crate::services::notifications::DeliveryError;
+
+/// From TWILIO_ACCOUNT_SID, TWILIO_AUTH_TOKEN and TWILIO_FROM
+#[derive(Debug, Clone)]
+pub struct SmsConfig {
+    pub account_sid: String,
+    pub auth_token: String,
+    pub from: String,
+}
+
+fn env_value(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// None unless all three are set
+pub fn config() -> Option<SmsConfig> {
+    Some(SmsConfig {
+        account_sid: env_value("TWILIO_ACCOUNT_SID")?,
+        auth_token: env_value("TWILIO_AUTH_TOKEN")?,
+        from: env_value("TWILIO_FROM")?,
+    })
+}
+
+/// The form Twilio's Messages endpoint takes; a messaging service id (MG...) goes in its own
+/// field instead of From
+pub fn message_form(from: &str, number: &str, body: &str) -> Vec<(&'static str, String)> {
+    let sender = if from.starts_with("MG") {
+        "MessagingServiceSid"
+    } else {
+        "From"
+    };
+    vec![
+        ("To", format!("+{}", number)),
+        (sender, from.to_string()),
+        ("Body", body.to_string()),
+    ]
+}
+
+/// Send `body` to `number` (international digits, see `whatsapp::international_number`)
+pub async fn send(
+    client: &reqwest::Client,
+    config: &SmsConfig,
+    number: &str,
+    body: &str,
+) -> Result<(), DeliveryError> {
+    let url = format!(
+        "https://api.twilio.com/2010-04-01/Accounts/{}/Messages.json",
+        config.account_sid
+    );
+    let response = client
+        .post(url)
+        .basic_auth(&config.account_sid, Some(&config.auth_token))
+        .form(&message_form(&config.from, number, body))
+        .send()
+        .await
+        .map_err(|e| DeliveryError::transient(e.to_string()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let detail = response.text().await.unwrap_or_default();
+    Err(DeliveryError {
+        error: format!("Twilio answered {}: {}", status, detail),
+        // 400 is Twilio rejecting the message, mostly for a number it cannot send to; bad
+        // credentials (401) and outages are worth retrying once fixed
+        permanent: status == reqwest::StatusCode::BAD_REQUEST,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_go_from_a_number_or_a_messaging_service() {
+        let form = message_form("+15005550006", "50255551234", "Hola");
+        assert_eq!(
+            form,
+            vec![
+                ("To", "+50255551234".to_string()),
+                ("From", "+15005550006".to_string()),
+                ("Body", "Hola".to_string()),
+            ]
+        );
+        let form = message_form("MG123", "50255551234", "Hola");
+        assert_eq!(form[1], ("MessagingServiceSid", "MG123".to_string()));
+    }
+}
@@ -0,0 +1,215 @@
+use chrono::NaiveDate;
+use sqlx::PgConnection;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::models::{AssignmentChange, SnapshotAssignment};
+
+/// Save the schedule's current assignments as its next version and return the version number
+pub async fn take(
+    conn: &mut PgConnection,
+    schedule_id: &str,
+    created_by: &str,
+) -> Result<i32, String> {
+    let snapshot_id = Uuid::new_v4().to_string();
+    let version: i32 = sqlx::query_scalar(
+        r#"
+        INSERT INTO schedule_snapshots (id, schedule_id, version, created_by)
+        SELECT $1, $2, COALESCE(MAX(version), 0) + 1, $3
+        FROM schedule_snapshots WHERE schedule_id = $2
+        RETURNING version
+        "#,
+    )
+    .bind(&snapshot_id)
+    .bind(schedule_id)
+    .bind(created_by)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO schedule_snapshot_assignments
+            (snapshot_id, service_date, job_id, job_name, position, position_name, person_id, person_name)
+        SELECT $1, sd.service_date, a.job_id, j.name, a.position, a.position_name, a.person_id,
+               p.first_name || ' ' || p.last_name
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN jobs j ON a.job_id = j.id
+        LEFT JOIN people p ON a.person_id = p.id
+        WHERE sd.schedule_id = $2
+        "#,
+    )
+    .bind(&snapshot_id)
+    .bind(schedule_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(version)
+}
+
+/// The assignments saved in a version, None if the schedule has no such version
+pub async fn load(
+    conn: &mut PgConnection,
+    schedule_id: &str,
+    version: i32,
+) -> Result<Option<Vec<SnapshotAssignment>>, String> {
+    let snapshot_id: Option<String> = sqlx::query_scalar(
+        "SELECT id FROM schedule_snapshots WHERE schedule_id = $1 AND version = $2",
+    )
+    .bind(schedule_id)
+    .bind(version)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| e.to_string())?;
+    let Some(snapshot_id) = snapshot_id else {
+        return Ok(None);
+    };
+
+    sqlx::query_as::<_, SnapshotAssignment>(
+        r#"
+        SELECT service_date, job_id, job_name, position, position_name, person_id, person_name
+        FROM schedule_snapshot_assignments
+        WHERE snapshot_id = $1
+        "#,
+    )
+    .bind(&snapshot_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map(Some)
+    .map_err(|e| e.to_string())
+}
+
+/// The schedule's assignments as they are now, in snapshot form
+pub async fn current(
+    conn: &mut PgConnection,
+    schedule_id: &str,
+) -> Result<Vec<SnapshotAssignment>, String> {
+    sqlx::query_as::<_, SnapshotAssignment>(
+        r#"
+        SELECT sd.service_date, a.job_id, j.name as job_name, a.position, a.position_name,
+               a.person_id, p.first_name || ' ' || p.last_name as person_name
+        FROM assignments a
+        JOIN service_dates sd ON a.service_date_id = sd.id
+        JOIN jobs j ON a.job_id = j.id
+        LEFT JOIN people p ON a.person_id = p.id
+        WHERE sd.schedule_id = $1
+        "#,
+    )
+    .bind(schedule_id)
+    .fetch_all(conn)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+type SlotKey = (NaiveDate, String, Option<i32>);
+
+/// Compare two versions slot by slot (date, job, position). Within a slot, people only in
+/// `to` were added and people only in `from` removed; when both happen in the same slot they
+/// pair up as changes. Empty slots are ignored.
+pub fn diff(
+    from: &[SnapshotAssignment],
+    to: &[SnapshotAssignment],
+) -> (
+    Vec<SnapshotAssignment>,
+    Vec<SnapshotAssignment>,
+    Vec<AssignmentChange>,
+) {
+    fn by_slot(rows: &[SnapshotAssignment]) -> BTreeMap<SlotKey, Vec<&SnapshotAssignment>> {
+        let mut slots: BTreeMap<SlotKey, Vec<&SnapshotAssignment>> = BTreeMap::new();
+        for row in rows.iter().filter(|r| r.person_id.is_some()) {
+            slots
+                .entry((row.service_date, row.job_id.clone(), row.position))
+                .or_default()
+                .push(row);
+        }
+        slots
+    }
+
+    let before = by_slot(from);
+    let after = by_slot(to);
+    let mut keys: Vec<&SlotKey> = before.keys().chain(after.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let (mut added, mut removed, mut changed) = (Vec::new(), Vec::new(), Vec::new());
+    for key in keys {
+        let old = before.get(key).map(Vec::as_slice).unwrap_or_default();
+        let new = after.get(key).map(Vec::as_slice).unwrap_or_default();
+        let gone: Vec<&SnapshotAssignment> = old
+            .iter()
+            .filter(|o| !new.iter().any(|n| n.person_id == o.person_id))
+            .copied()
+            .collect();
+        let came: Vec<&SnapshotAssignment> = new
+            .iter()
+            .filter(|n| !old.iter().any(|o| o.person_id == n.person_id))
+            .copied()
+            .collect();
+
+        let paired = gone.len().min(came.len());
+        for (o, n) in gone.iter().zip(came.iter()) {
+            changed.push(AssignmentChange {
+                service_date: n.service_date,
+                job_id: n.job_id.clone(),
+                job_name: n.job_name.clone(),
+                position: n.position,
+                position_name: n.position_name.clone(),
+                from_person_id: o.person_id.clone(),
+                from_person_name: o.person_name.clone(),
+                to_person_id: n.person_id.clone(),
+                to_person_name: n.person_name.clone(),
+            });
+        }
+        removed.extend(gone[paired..].iter().map(|o| (*o).clone()));
+        added.extend(came[paired..].iter().map(|n| (*n).clone()));
+    }
+
+    (added, removed, changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(day: u32, position: i32, person: Option<&str>) -> SnapshotAssignment {
+        SnapshotAssignment {
+            service_date: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            job_id: "lectores".to_string(),
+            job_name: "Lectores".to_string(),
+            position: Some(position),
+            position_name: None,
+            person_id: person.map(str::to_string),
+            person_name: person.map(|p| p.to_uppercase()),
+        }
+    }
+
+    #[test]
+    fn unchanged_versions_have_no_diff() {
+        let rows = vec![slot(1, 1, Some("ana")), slot(1, 2, None)];
+        let (added, removed, changed) = diff(&rows, &rows);
+        assert!(added.is_empty() && removed.is_empty() && changed.is_empty());
+    }
+
+    #[test]
+    fn classifies_added_removed_and_changed_slots() {
+        let from = vec![
+            slot(1, 1, Some("ana")),
+            slot(1, 2, Some("luis")),
+            slot(8, 1, None),
+        ];
+        let to = vec![
+            slot(1, 1, Some("marta")),
+            slot(1, 2, None),
+            slot(8, 1, Some("ana")),
+        ];
+        let (added, removed, changed) = diff(&from, &to);
+
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].from_person_id.as_deref(), Some("ana"));
+        assert_eq!(changed[0].to_person_id.as_deref(), Some("marta"));
+        assert_eq!(removed, vec![slot(1, 2, Some("luis"))]);
+        assert_eq!(added, vec![slot(8, 1, Some("ana"))]);
+    }
+}
@@ -0,0 +1,43 @@
+//! Random secrets handed out in links and keys: session refresh tokens, invitations, API keys
+//! and calendar feeds
+
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// A hard-to-guess random token, without the characters that are easy to misread
+pub fn generate_token() -> String {
+    const CHARSET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZabcdefghjkmnpqrstuvwxyz23456789";
+    let mut rng = rand::thread_rng();
+    (0..32)
+        .map(|_| CHARSET[rng.gen_range(0..CHARSET.len())] as char)
+        .collect()
+}
+
+/// Hex SHA-256 of a token, kept in the database in its place
+pub fn digest(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_long_and_unambiguous() {
+        let token = generate_token();
+        assert_eq!(token.len(), 32);
+        assert!(!token.contains(['0', 'O', '1', 'l', 'I']));
+        assert_ne!(token, generate_token());
+    }
+
+    #[test]
+    fn digests_are_hex_sha256() {
+        assert_eq!(
+            digest("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Time-based one-time passwords (RFC 6238): six digits from HMAC-SHA1 of the 30-second step,
+//! as authenticator apps compute them from the secret in the `otpauth://` link.
+
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha1::Sha1;
+
+/// Name the authenticator app lists the account under
+pub const ISSUER: &str = "People Scheduler";
+
+const STEP_SECONDS: i64 = 30;
+const DIGITS: u32 = 6;
+/// Steps either side of now that are accepted, for a phone clock a little off
+const WINDOW: i64 = 1;
+
+const BASE32: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// RFC 4648 base32 without padding, the form authenticator apps expect
+pub fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+/// Ignores case, spaces and padding; None on any other character
+pub fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in text.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = BASE32
+            .iter()
+            .position(|&b| b as char == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// A new 160-bit secret, base32
+pub fn generate_secret() -> String {
+    let bytes: [u8; 20] = rand::thread_rng().gen();
+    base32_encode(&bytes)
+}
+
+/// The time step a Unix time falls in
+pub fn step_at(unix_seconds: i64) -> i64 {
+    unix_seconds.div_euclid(STEP_SECONDS)
+}
+
+pub fn code_at(secret: &[u8], step: i64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC takes keys of any length");
+    mac.update(&step.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+    // Dynamic truncation: four bytes from the offset in the last nibble
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let value = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    value % 10u32.pow(DIGITS)
+}
+
+/// The step whose code was typed, if it is within the window around `now_step` and later
+/// than the last code accepted
+pub fn matching_step(
+    secret: &[u8],
+    code: &str,
+    now_step: i64,
+    last_step: Option<i64>,
+) -> Option<i64> {
+    let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+    if code.len() != DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let code: u32 = code.parse().ok()?;
+    (now_step - WINDOW..=now_step + WINDOW)
+        .filter(|step| last_step.is_none_or(|last| *step > last))
+        .find(|step| code_at(secret, *step) == code)
+}
+
+fn uri_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// The link to show as a QR code for the authenticator app to scan
+pub fn otpauth_uri(account: &str, secret: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        uri_encode(ISSUER),
+        uri_encode(account),
+        secret,
+        uri_encode(ISSUER),
+        DIGITS,
+        STEP_SECONDS
+    )
+}
+
+/// Recovery codes are typed by hand, so they avoid look-alike characters and ignore dashes,
+/// spaces and case
+pub fn generate_recovery_code() -> String {
+    const CHARSET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+    let mut rng = rand::thread_rng();
+    let mut code: String = (0..10)
+        .map(|_| CHARSET[rng.gen_range(0..CHARSET.len())] as char)
+        .collect();
+    code.insert(5, '-');
+    code
+}
+
+pub fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6238 appendix B, SHA1 secret; the last six of its eight digits
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn codes_match_the_rfc_test_vectors() {
+        assert_eq!(code_at(RFC_SECRET, step_at(59)), 287082);
+        assert_eq!(code_at(RFC_SECRET, step_at(1111111109)), 81804);
+        assert_eq!(code_at(RFC_SECRET, step_at(1234567890)), 5924);
+        assert_eq!(code_at(RFC_SECRET, step_at(2000000000)), 279037);
+    }
+
+    #[test]
+    fn base32_round_trips() {
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(
+            base32_decode("mzxw 6ytb oi======"),
+            Some(b"foobar".to_vec())
+        );
+        assert_eq!(base32_decode("MZXW1"), None);
+        let secret = generate_secret();
+        assert_eq!(secret.len(), 32);
+        assert_eq!(base32_decode(&secret).unwrap().len(), 20);
+    }
+
+    #[test]
+    fn codes_are_accepted_once_within_the_window() {
+        let now = step_at(1111111109);
+        assert_eq!(matching_step(RFC_SECRET, "081804", now, None), Some(now));
+        assert_eq!(
+            matching_step(RFC_SECRET, "081 804", now + 1, None),
+            Some(now)
+        );
+        assert_eq!(matching_step(RFC_SECRET, "081804", now + 2, None), None);
+        // Already used
+        assert_eq!(matching_step(RFC_SECRET, "081804", now, Some(now)), None);
+        assert_eq!(matching_step(RFC_SECRET, "81804", now, None), None);
+        assert_eq!(matching_step(RFC_SECRET, "abcdef", now, None), None);
+    }
+
+    #[test]
+    fn otpauth_links_name_the_issuer_and_account() {
+        assert_eq!(
+            otpauth_uri("ana lopez", "MZXW6YTBOI"),
+            "otpauth://totp/People%20Scheduler:ana%20lopez?secret=MZXW6YTBOI\
+             &issuer=People%20Scheduler&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+
+    #[test]
+    fn recovery_codes_ignore_dashes_and_case() {
+        let code = generate_recovery_code();
+        assert_eq!(code.len(), 11);
+        assert_eq!(normalize_recovery_code("AbCde-fGh23"), "abcdefgh23");
+        assert_eq!(normalize_recovery_code(&code).len(), 10);
+    }
+}
@@ -0,0 +1,298 @@
+//! The optional second factor of admin and coordinator accounts: a TOTP authenticator app,
+//! with one-time recovery codes for when the phone is lost. Once enabled, signing in (by
+//! password or OIDC) also needs a current code or an unused recovery code.
+
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::auth::{hash_password, verify_password};
+use crate::models::TwoFactorStatus;
+use crate::services::totp;
+
+/// Roles that can enroll; servidores sign in with generated passwords on shared family devices
+pub const ROLES: &[&str] = &["admin", "coordinator"];
+
+/// What the login answers when the password was right but the code is missing; the login page
+/// then asks for the code
+pub const CODE_REQUIRED: &str = "Two-factor code required";
+pub const WRONG_CODE: &str = "Wrong two-factor code";
+const LOCKED_OUT: &str = "Too many wrong two-factor codes; try again in 15 minutes";
+
+const RECOVERY_CODES: usize = 10;
+
+/// Wrong codes tolerated within `LOCKOUT_MINUTES`, so six digits cannot be guessed
+const MAX_WRONG_CODES: i64 = 5;
+const LOCKOUT_MINUTES: i32 = 15;
+
+/// How a sign-in's second factor went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecondFactor {
+    NotEnrolled,
+    Passed,
+    PassedWithRecoveryCode,
+    Missing,
+    Wrong,
+    LockedOut,
+}
+
+impl SecondFactor {
+    /// Why the sign-in is refused, if it is
+    pub fn refusal(self) -> Option<&'static str> {
+        match self {
+            SecondFactor::Wrong => Some(WRONG_CODE),
+            SecondFactor::LockedOut => Some(LOCKED_OUT),
+            _ => None,
+        }
+    }
+
+    /// Noted on the sign-in's auth event
+    pub fn detail(self) -> Option<&'static str> {
+        match self {
+            SecondFactor::PassedWithRecoveryCode => Some("With a recovery code"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct TotpState {
+    totp_secret: Option<String>,
+    totp_enabled_at: Option<DateTime<Utc>>,
+    totp_last_step: Option<i64>,
+}
+
+async fn state(pool: &PgPool, user_id: Uuid) -> Result<Option<TotpState>, String> {
+    sqlx::query_as::<_, TotpState>(
+        "SELECT totp_secret, totp_enabled_at, totp_last_step FROM users WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+pub async fn status(pool: &PgPool, user_id: Uuid) -> Result<TwoFactorStatus, String> {
+    let state = state(pool, user_id).await?;
+    let recovery_codes_left: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM totp_recovery_codes WHERE user_id = $1 AND used_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(TwoFactorStatus {
+        enabled: state.as_ref().is_some_and(|s| s.totp_enabled_at.is_some()),
+        enabled_at: state.and_then(|s| s.totp_enabled_at),
+        recovery_codes_left,
+    })
+}
+
+/// Give the user a new secret to add to their authenticator app. It is only used once
+/// `enable` confirms a code from it. None when two-factor is already on.
+pub async fn start_setup(pool: &PgPool, user_id: Uuid) -> Result<Option<String>, String> {
+    let secret = totp::generate_secret();
+    let result =
+        sqlx::query("UPDATE users SET totp_secret = $2 WHERE id = $1 AND totp_enabled_at IS NULL")
+            .bind(user_id)
+            .bind(&secret)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    Ok((result.rows_affected() > 0).then_some(secret))
+}
+
+/// Turn two-factor on with a code from the secret of `start_setup`; returns the recovery
+/// codes, or None when the code does not match
+pub async fn enable(
+    pool: &PgPool,
+    user_id: Uuid,
+    code: &str,
+) -> Result<Option<Vec<String>>, String> {
+    let Some(TotpState {
+        totp_secret: Some(secret),
+        totp_enabled_at: None,
+        ..
+    }) = state(pool, user_id).await?
+    else {
+        return Ok(None);
+    };
+    let secret = totp::base32_decode(&secret).ok_or("Stored TOTP secret is not base32")?;
+    let now_step = totp::step_at(Utc::now().timestamp());
+    let Some(step) = totp::matching_step(&secret, code, now_step, None) else {
+        return Ok(None);
+    };
+
+    sqlx::query("UPDATE users SET totp_enabled_at = NOW(), totp_last_step = $2 WHERE id = $1")
+        .bind(user_id)
+        .bind(step)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    replace_recovery_codes(pool, user_id).await.map(Some)
+}
+
+/// Turn two-factor off and forget the secret and recovery codes. False when it was not on.
+pub async fn disable(pool: &PgPool, user_id: Uuid) -> Result<bool, String> {
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let result = sqlx::query(
+        r#"
+        UPDATE users SET totp_secret = NULL, totp_enabled_at = NULL, totp_last_step = NULL
+        WHERE id = $1 AND totp_secret IS NOT NULL
+        "#,
+    )
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM totp_recovery_codes WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// A fresh set of recovery codes; the old ones stop working. Only the hashes are kept, so
+/// this is the one time the codes are shown.
+pub async fn replace_recovery_codes(pool: &PgPool, user_id: Uuid) -> Result<Vec<String>, String> {
+    let codes: Vec<String> = (0..RECOVERY_CODES)
+        .map(|_| totp::generate_recovery_code())
+        .collect();
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    sqlx::query("DELETE FROM totp_recovery_codes WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    for code in &codes {
+        let code_hash =
+            hash_password(&totp::normalize_recovery_code(code)).map_err(|e| e.to_string())?;
+        sqlx::query("INSERT INTO totp_recovery_codes (id, user_id, code_hash) VALUES ($1, $2, $3)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(user_id)
+            .bind(&code_hash)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+
+    Ok(codes)
+}
+
+/// Check a code from the authenticator app, or a recovery code, and use it up
+pub async fn verify(pool: &PgPool, user_id: Uuid, code: &str) -> Result<SecondFactor, String> {
+    let Some(TotpState {
+        totp_secret: Some(secret),
+        totp_enabled_at: Some(_),
+        totp_last_step,
+    }) = state(pool, user_id).await?
+    else {
+        return Ok(SecondFactor::NotEnrolled);
+    };
+
+    let secret = totp::base32_decode(&secret).ok_or("Stored TOTP secret is not base32")?;
+    let now_step = totp::step_at(Utc::now().timestamp());
+    if let Some(step) = totp::matching_step(&secret, code, now_step, totp_last_step) {
+        // Only the first of two sign-ins racing with the same code gets through
+        let used = sqlx::query(
+            r#"
+            UPDATE users SET totp_last_step = $2
+            WHERE id = $1 AND (totp_last_step IS NULL OR totp_last_step < $2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        return Ok(if used.rows_affected() > 0 {
+            SecondFactor::Passed
+        } else {
+            SecondFactor::Wrong
+        });
+    }
+
+    let recovery_code = totp::normalize_recovery_code(code);
+    let unused: Vec<(String, String)> = sqlx::query_as(
+        "SELECT id, code_hash FROM totp_recovery_codes WHERE user_id = $1 AND used_at IS NULL",
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    for (id, code_hash) in unused {
+        if verify_password(&recovery_code, &code_hash) {
+            let used = sqlx::query(
+                "UPDATE totp_recovery_codes SET used_at = NOW() WHERE id = $1 AND used_at IS NULL",
+            )
+            .bind(&id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+            if used.rows_affected() > 0 {
+                return Ok(SecondFactor::PassedWithRecoveryCode);
+            }
+        }
+    }
+
+    Ok(SecondFactor::Wrong)
+}
+
+/// The second factor of a sign-in whose first one (password or OIDC) already passed
+pub async fn check_login(
+    pool: &PgPool,
+    user_id: Uuid,
+    code: Option<&str>,
+) -> Result<SecondFactor, String> {
+    let enrolled = state(pool, user_id)
+        .await?
+        .is_some_and(|s| s.totp_enabled_at.is_some());
+    if !enrolled {
+        return Ok(SecondFactor::NotEnrolled);
+    }
+    let Some(code) = code.map(str::trim).filter(|c| !c.is_empty()) else {
+        return Ok(SecondFactor::Missing);
+    };
+
+    let wrong_codes: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM auth_events
+        WHERE user_id = $1 AND detail = $2
+          AND created_at > NOW() - make_interval(mins => $3)
+        "#,
+    )
+    .bind(user_id)
+    .bind(WRONG_CODE)
+    .bind(LOCKOUT_MINUTES)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    if wrong_codes >= MAX_WRONG_CODES {
+        return Ok(SecondFactor::LockedOut);
+    }
+
+    verify(pool, user_id, code).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_failed_codes_refuse_the_sign_in() {
+        assert_eq!(SecondFactor::Wrong.refusal(), Some(WRONG_CODE));
+        assert!(SecondFactor::LockedOut.refusal().is_some());
+        assert_eq!(SecondFactor::Passed.refusal(), None);
+        assert_eq!(SecondFactor::NotEnrolled.refusal(), None);
+        assert_eq!(SecondFactor::Missing.refusal(), None);
+        assert!(SecondFactor::PassedWithRecoveryCode.detail().is_some());
+    }
+}
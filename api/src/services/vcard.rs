@@ -0,0 +1,264 @@
+use axum::{
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+/// The fields of a contact card this app reads and writes
+#[derive(Debug, Clone, Default)]
+pub struct Contact {
+    pub first_name: String,
+    pub last_name: String,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+}
+
+/// Escape a text value (RFC 6350 section 3.4)
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// Split a structured value (like N) on unescaped semicolons
+fn split_components(value: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for c in value.chars() {
+        if escaped {
+            current.push('\\');
+            current.push(c);
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == ';' {
+            parts.push(unescape(&current));
+            current.clear();
+        } else {
+            current.push(c);
+        }
+    }
+    parts.push(unescape(&current));
+    parts
+}
+
+/// Build a vCard 3.0 bundle, one card per contact
+pub fn to_vcards(contacts: &[Contact]) -> String {
+    let mut out = String::new();
+    for c in contacts {
+        out.push_str("BEGIN:VCARD\r\nVERSION:3.0\r\n");
+        out.push_str(&format!(
+            "N:{};{};;;\r\n",
+            escape(&c.last_name),
+            escape(&c.first_name)
+        ));
+        out.push_str(&format!(
+            "FN:{}\r\n",
+            escape(format!("{} {}", c.first_name, c.last_name).trim())
+        ));
+        if let Some(phone) = &c.phone {
+            out.push_str(&format!("TEL;TYPE=CELL:{}\r\n", escape(phone)));
+        }
+        if let Some(email) = &c.email {
+            out.push_str(&format!("EMAIL;TYPE=INTERNET:{}\r\n", escape(email)));
+        }
+        out.push_str("END:VCARD\r\n");
+    }
+    out
+}
+
+/// Read every card in a bundle (vCard 2.1, 3.0 or 4.0). Only the first phone and email are kept;
+/// cards without a name are dropped.
+pub fn parse(text: &str) -> Vec<Contact> {
+    // Unfold continuation lines (a line starting with a space or tab continues the previous one)
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.lines() {
+        if let Some(rest) = raw.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        lines.push(raw.to_string());
+    }
+
+    let mut contacts = Vec::new();
+    let mut current: Option<(Contact, Option<String>)> = None;
+    for line in &lines {
+        let Some((name_and_params, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Drop parameters (TEL;TYPE=CELL) and groups (item1.EMAIL)
+        let name = name_and_params
+            .split(';')
+            .next()
+            .unwrap_or("")
+            .rsplit('.')
+            .next()
+            .unwrap_or("")
+            .to_uppercase();
+        let value = value.trim();
+
+        match name.as_str() {
+            "BEGIN" if value.eq_ignore_ascii_case("VCARD") => {
+                current = Some((Contact::default(), None));
+            }
+            "END" if value.eq_ignore_ascii_case("VCARD") => {
+                if let Some((mut contact, full_name)) = current.take() {
+                    if contact.first_name.is_empty() && contact.last_name.is_empty() {
+                        // No N property: split the display name at the first space
+                        let full_name = full_name.unwrap_or_default();
+                        let mut parts = full_name.trim().splitn(2, ' ');
+                        contact.first_name = parts.next().unwrap_or("").trim().to_string();
+                        contact.last_name = parts.next().unwrap_or("").trim().to_string();
+                    }
+                    if !contact.first_name.is_empty() || !contact.last_name.is_empty() {
+                        contacts.push(contact);
+                    }
+                }
+            }
+            "N" => {
+                if let Some((contact, _)) = current.as_mut() {
+                    let parts = split_components(value);
+                    contact.last_name = parts
+                        .first()
+                        .map(|s| s.trim().to_string())
+                        .unwrap_or_default();
+                    contact.first_name = parts
+                        .get(1)
+                        .map(|s| s.trim().to_string())
+                        .unwrap_or_default();
+                }
+            }
+            "FN" => {
+                if let Some((_, full_name)) = current.as_mut() {
+                    *full_name = Some(unescape(value));
+                }
+            }
+            "TEL" => {
+                if let Some((contact, _)) = current.as_mut() {
+                    if contact.phone.is_none() && !value.is_empty() {
+                        contact.phone = Some(unescape(value.trim_start_matches("tel:")));
+                    }
+                }
+            }
+            "EMAIL" => {
+                if let Some((contact, _)) = current.as_mut() {
+                    if contact.email.is_none() && !value.is_empty() {
+                        contact.email = Some(unescape(value));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    contacts
+}
+
+/// Return a vCard bundle as a file download
+pub fn attachment(filename: &str, body: String) -> Response {
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                "text/vcard; charset=utf-8".to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_and_unescape_round_trip() {
+        for value in ["Pérez; López", "a,b", "back\\slash", "two\nlines", "plain"] {
+            assert_eq!(unescape(&escape(value)), value);
+        }
+    }
+
+    #[test]
+    fn escape_normalizes_crlf_to_newline() {
+        assert_eq!(unescape(&escape("one\r\ntwo")), "one\ntwo");
+    }
+
+    #[test]
+    fn split_components_keeps_escaped_semicolons() {
+        let value = format!("{};{};;;", escape("De la Cruz; Jr."), escape("Ana, María"));
+        assert_eq!(
+            split_components(&value),
+            vec!["De la Cruz; Jr.", "Ana, María", "", "", ""]
+        );
+    }
+
+    #[test]
+    fn export_then_parse_gives_back_the_contacts() {
+        let contacts = vec![
+            Contact {
+                first_name: "José".to_string(),
+                last_name: "Pérez; Gómez".to_string(),
+                phone: Some("+502 5555-1234".to_string()),
+                email: Some("jose@example.com".to_string()),
+            },
+            Contact {
+                first_name: "Ana".to_string(),
+                last_name: "López".to_string(),
+                phone: None,
+                email: None,
+            },
+        ];
+
+        let parsed = parse(&to_vcards(&contacts));
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].first_name, "José");
+        assert_eq!(parsed[0].last_name, "Pérez; Gómez");
+        assert_eq!(parsed[0].phone.as_deref(), Some("+502 5555-1234"));
+        assert_eq!(parsed[0].email.as_deref(), Some("jose@example.com"));
+        assert_eq!(parsed[1].last_name, "López");
+        assert!(parsed[1].phone.is_none());
+    }
+
+    #[test]
+    fn parse_unfolds_lines_and_falls_back_to_fn() {
+        let text = "BEGIN:VCARD\r\nVERSION:2.1\r\nFN:Carlos\r\n  Méndez\r\nitem1.EMAIL;TYPE=HOME:c@example.com\r\nEND:VCARD\r\n";
+        let parsed = parse(text);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].first_name, "Carlos");
+        assert_eq!(parsed[0].last_name, "Méndez");
+        assert_eq!(parsed[0].email.as_deref(), Some("c@example.com"));
+    }
+
+    #[test]
+    fn parse_drops_cards_without_a_name() {
+        let text = "BEGIN:VCARD\nVERSION:3.0\nTEL:123\nEND:VCARD\n";
+        assert!(parse(text).is_empty());
+    }
+}
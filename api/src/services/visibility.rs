@@ -0,0 +1,16 @@
+use chrono::{Duration, NaiveDate, Utc};
+use sqlx::PgPool;
+
+use crate::routes::settings;
+
+/// Last service date a servidor may see assignments for, or None when there is no limit.
+/// Set with `assignment_visibility_weeks` (0 = everything published is visible).
+/// Applied to the assignments servidores see: their own list and the schedules they open.
+pub async fn horizon(pool: &PgPool) -> Result<Option<NaiveDate>, String> {
+    let weeks = settings::get_i64(pool, "assignment_visibility_weeks", 0).await?;
+    if weeks <= 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(Utc::now().date_naive() + Duration::weeks(weeks)))
+}
@@ -0,0 +1,345 @@
+//! Sending Web Push messages: the payload is encrypted for the browser's subscription keys
+//! (aes128gcm, RFC 8291) and the request to the push service is signed with the server's
+//! VAPID key (RFC 8292), an ES256 JWT over the push service's origin.
+//!
+//! Configured with VAPID_PUBLIC_KEY (the uncompressed P-256 point browsers subscribe with),
+//! VAPID_PRIVATE_KEY (its 32-byte scalar) and VAPID_SUBJECT (a `mailto:` or `https:` contact
+//! for the push service), the first two base64url as `web-push generate-vapid-keys` prints them.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes128Gcm, Nonce};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hkdf::Hkdf;
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature, SigningKey};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::{PublicKey, SecretKey};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::Sha256;
+
+/// Contact sent to push services when VAPID_SUBJECT is unset
+const DEFAULT_SUBJECT: &str = "mailto:admin@example.com";
+
+/// How long a push service keeps a message for a phone that is offline
+const TTL_SECONDS: u32 = 24 * 60 * 60;
+
+/// How long the VAPID token is good for; push services refuse more than 24 hours
+const VAPID_VALID_SECONDS: i64 = 12 * 60 * 60;
+
+/// Record size written in the aes128gcm header; a push payload always fits in one record
+const RECORD_SIZE: u32 = 4096;
+
+pub struct Vapid {
+    key: SigningKey,
+    /// base64url uncompressed point, sent as `k=` next to the token
+    public_key: String,
+    subject: String,
+}
+
+/// What the push service made of one message
+#[derive(Debug, PartialEq)]
+pub enum Delivery {
+    Sent,
+    /// 404/410: the subscription expired or the user turned notifications off
+    Gone,
+    Failed(String),
+}
+
+fn env_value(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// base64url with or without padding, also taking the standard alphabet some clients send
+fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    let text: String = text
+        .trim()
+        .trim_end_matches('=')
+        .chars()
+        .map(|c| match c {
+            '+' => '-',
+            '/' => '_',
+            c => c,
+        })
+        .collect();
+    URL_SAFE_NO_PAD
+        .decode(text)
+        .map_err(|e| format!("Invalid base64: {}", e))
+}
+
+fn public_key_bytes(key: &PublicKey) -> Vec<u8> {
+    key.to_encoded_point(false).as_bytes().to_vec()
+}
+
+impl Vapid {
+    /// Check the private key is the one the public key browsers subscribed with belongs to
+    pub fn new(public_key: &str, private_key: &str, subject: &str) -> Result<Self, String> {
+        let key = SigningKey::from_slice(&decode_base64(private_key)?)
+            .map_err(|_| "VAPID_PRIVATE_KEY is not a P-256 private key".to_string())?;
+        let derived =
+            URL_SAFE_NO_PAD.encode(public_key_bytes(&PublicKey::from(key.verifying_key())));
+        if decode_base64(public_key)? != decode_base64(&derived)? {
+            return Err("VAPID_PUBLIC_KEY does not match VAPID_PRIVATE_KEY".to_string());
+        }
+        Ok(Vapid {
+            key,
+            public_key: derived,
+            subject: subject.to_string(),
+        })
+    }
+
+    /// The configured keys; None when push is not set up. A broken pair is logged and treated
+    /// as not set up, so browsers are not offered a key nothing can send with.
+    pub fn from_env() -> Option<Self> {
+        let public_key = env_value("VAPID_PUBLIC_KEY")?;
+        let private_key = env_value("VAPID_PRIVATE_KEY")?;
+        let subject = env_value("VAPID_SUBJECT").unwrap_or_else(|| DEFAULT_SUBJECT.to_string());
+        Vapid::new(&public_key, &private_key, &subject)
+            .inspect_err(|e| tracing::warn!("Web Push disabled: {}", e))
+            .ok()
+    }
+
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    /// The `Authorization` header for a push to `endpoint`
+    pub fn authorization(&self, endpoint: &str, now: i64) -> Result<String, String> {
+        let audience = origin(endpoint)?;
+        let header = URL_SAFE_NO_PAD.encode(r#"{"typ":"JWT","alg":"ES256"}"#);
+        let claims = serde_json::json!({
+            "aud": audience,
+            "exp": now + VAPID_VALID_SECONDS,
+            "sub": self.subject,
+        });
+        let claims = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let signing_input = format!("{}.{}", header, claims);
+        let signature: Signature = self.key.sign(signing_input.as_bytes());
+        Ok(format!(
+            "vapid t={}.{}, k={}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature.to_bytes()),
+            self.public_key
+        ))
+    }
+}
+
+/// `https://fcm.googleapis.com/fcm/send/abc` → `https://fcm.googleapis.com`
+fn origin(endpoint: &str) -> Result<String, String> {
+    let rest = endpoint
+        .strip_prefix("https://")
+        .ok_or_else(|| "Push endpoint must be an https URL".to_string())?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if host.is_empty() {
+        return Err("Push endpoint has no host".to_string());
+    }
+    Ok(format!("https://{}", host))
+}
+
+fn hkdf_expand(salt: &[u8], ikm: &[u8], info: &[u8], out: &mut [u8]) {
+    Hkdf::<Sha256>::new(Some(salt), ikm)
+        .expand(info, out)
+        .expect("HKDF output of at most 32 bytes");
+}
+
+/// Encrypt `payload` for the subscription's `p256dh` key and `auth` secret, as the body of an
+/// aes128gcm push: salt, record size, the server's one-off public key, then the record
+pub fn encrypt(p256dh: &str, auth: &str, payload: &[u8]) -> Result<Vec<u8>, String> {
+    let ua_public = PublicKey::from_sec1_bytes(&decode_base64(p256dh)?)
+        .map_err(|_| "Subscription p256dh is not a P-256 public key".to_string())?;
+    let auth_secret = decode_base64(auth)?;
+    if auth_secret.len() != 16 {
+        return Err("Subscription auth secret must be 16 bytes".to_string());
+    }
+    // Push services take 4096 bytes of body: the 86-byte header, the delimiter and the tag
+    if payload.len() + 86 + 1 + 16 > 4096 {
+        return Err("Push payload is too long".to_string());
+    }
+
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    Ok(seal(
+        &ua_public,
+        &auth_secret,
+        payload,
+        &SecretKey::random(&mut OsRng),
+        &salt,
+    ))
+}
+
+/// The encryption itself, with the server's one-off key and the salt given
+fn seal(
+    ua_public: &PublicKey,
+    auth_secret: &[u8],
+    payload: &[u8],
+    server_key: &SecretKey,
+    salt: &[u8; 16],
+) -> Vec<u8> {
+    let ua_public_bytes = public_key_bytes(ua_public);
+    let server_public_bytes = public_key_bytes(&server_key.public_key());
+    let shared = p256::ecdh::diffie_hellman(server_key.to_nonzero_scalar(), ua_public.as_affine());
+
+    let mut key_info = b"WebPush: info\0".to_vec();
+    key_info.extend_from_slice(&ua_public_bytes);
+    key_info.extend_from_slice(&server_public_bytes);
+    let mut ikm = [0u8; 32];
+    hkdf_expand(auth_secret, shared.raw_secret_bytes(), &key_info, &mut ikm);
+
+    let mut cek = [0u8; 16];
+    hkdf_expand(salt, &ikm, b"Content-Encoding: aes128gcm\0", &mut cek);
+    let mut nonce = [0u8; 12];
+    hkdf_expand(salt, &ikm, b"Content-Encoding: nonce\0", &mut nonce);
+
+    // One record, so it is the last: the delimiter is 2 and no padding follows
+    let mut plaintext = payload.to_vec();
+    plaintext.push(2);
+    let record = Aes128Gcm::new_from_slice(&cek)
+        .expect("16-byte key")
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .expect("a payload that fits one record");
+
+    let mut body = salt.to_vec();
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(server_public_bytes.len() as u8);
+    body.extend_from_slice(&server_public_bytes);
+    body.extend_from_slice(&record);
+    body
+}
+
+/// Encrypt and post one push to its subscription's push service
+pub async fn send(
+    client: &reqwest::Client,
+    vapid: &Vapid,
+    endpoint: &str,
+    p256dh: &str,
+    auth: &str,
+    payload: &[u8],
+) -> Delivery {
+    let prepared = encrypt(p256dh, auth, payload).and_then(|body| {
+        let authorization = vapid.authorization(endpoint, chrono::Utc::now().timestamp())?;
+        Ok((body, authorization))
+    });
+    let (body, authorization) = match prepared {
+        Ok(prepared) => prepared,
+        Err(e) => return Delivery::Failed(e),
+    };
+
+    let response = client
+        .post(endpoint)
+        .header("Authorization", authorization)
+        .header("Content-Encoding", "aes128gcm")
+        .header("Content-Type", "application/octet-stream")
+        .header("TTL", TTL_SECONDS.to_string())
+        .body(body)
+        .send()
+        .await;
+    match response {
+        Ok(response) if response.status().is_success() => Delivery::Sent,
+        Ok(response) if matches!(response.status().as_u16(), 404 | 410) => Delivery::Gone,
+        Ok(response) => Delivery::Failed(format!("Push service answered {}", response.status())),
+        Err(e) => Delivery::Failed(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::signature::Verifier;
+    use p256::ecdsa::VerifyingKey;
+
+    fn hex(text: &str) -> Vec<u8> {
+        (0..text.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// The worked example of RFC 8291, appendix A
+    #[test]
+    fn encryption_matches_the_rfc_example() {
+        let ua_public = PublicKey::from_sec1_bytes(&hex(
+            "042571b2becdfde360551aaf1ed0f4cd366c11cebe555f89bcb7b186a53339173168ece2ebe018597bd30479b86e3c8f8eced577ca59187e9246990db682008b0e",
+        ))
+        .unwrap();
+        let server_key = SecretKey::from_slice(&hex(
+            "c9f58f89813e9f8e872e71f42aa64e1757c9254dcc62b72ddc010bb4043ea11c",
+        ))
+        .unwrap();
+        let salt: [u8; 16] = hex("0c6bfaadad67958803092d454676f397").try_into().unwrap();
+
+        let body = seal(
+            &ua_public,
+            &hex("05305932a1c7eabe13b6cec9fda48882"),
+            b"When I grow up, I want to be a watermelon",
+            &server_key,
+            &salt,
+        );
+
+        assert_eq!(
+            body,
+            hex("0c6bfaadad67958803092d454676f397000010004104fe33f4ab0dea71914db55823f73b54948f41306d920732dbb9a59a53286482200e597a7b7bc260ba1c227998580992e93973002f3012a28ae8f06bbb78e5ec0ff297de5b429bba7153d3a4ae0caa091fd425f3b4b5414add8ab37a19c1bbb05cf5cb5b2a2e0562d558635641ec52812c6c8ff42e95ccb86be7cd")
+        );
+    }
+
+    #[test]
+    fn bad_subscription_keys_are_refused() {
+        let ua_key = SecretKey::random(&mut OsRng);
+        let p256dh = URL_SAFE_NO_PAD.encode(public_key_bytes(&ua_key.public_key()));
+        assert!(encrypt(&p256dh, "c2hvcnQ", b"hola").is_err());
+        assert!(encrypt("bm90IGEga2V5", &URL_SAFE_NO_PAD.encode([1u8; 16]), b"hola").is_err());
+    }
+
+    #[test]
+    fn the_vapid_token_is_signed_for_the_push_service_origin() {
+        let key = SecretKey::random(&mut OsRng);
+        let private_key = URL_SAFE_NO_PAD.encode(key.to_bytes());
+        let public_key = URL_SAFE_NO_PAD.encode(public_key_bytes(&key.public_key()));
+        let vapid = Vapid::new(&public_key, &private_key, "mailto:admin@parroquia.org").unwrap();
+
+        let header = vapid
+            .authorization("https://fcm.googleapis.com/fcm/send/abc123", 1_800_000_000)
+            .unwrap();
+        let token = header
+            .strip_prefix("vapid t=")
+            .and_then(|rest| rest.split_once(", k="))
+            .map(|(token, k)| {
+                assert_eq!(k, public_key);
+                token
+            })
+            .unwrap();
+        let (signing_input, signature) = token.rsplit_once('.').unwrap();
+        let signature = Signature::from_slice(&decode_base64(signature).unwrap()).unwrap();
+        VerifyingKey::from(&SigningKey::from(&key))
+            .verify(signing_input.as_bytes(), &signature)
+            .unwrap();
+
+        let claims = decode_base64(signing_input.split('.').nth(1).unwrap()).unwrap();
+        let claims: serde_json::Value = serde_json::from_slice(&claims).unwrap();
+        assert_eq!(claims["aud"], "https://fcm.googleapis.com");
+        assert_eq!(claims["exp"], 1_800_000_000 + VAPID_VALID_SECONDS);
+        assert_eq!(claims["sub"], "mailto:admin@parroquia.org");
+    }
+
+    #[test]
+    fn a_public_key_from_another_pair_is_refused() {
+        let key = SecretKey::random(&mut OsRng);
+        let other = SecretKey::random(&mut OsRng);
+        let private_key = URL_SAFE_NO_PAD.encode(key.to_bytes());
+        let public_key = URL_SAFE_NO_PAD.encode(public_key_bytes(&other.public_key()));
+        assert!(Vapid::new(&public_key, &private_key, DEFAULT_SUBJECT).is_err());
+    }
+
+    #[test]
+    fn pushes_only_go_to_https_endpoints() {
+        assert_eq!(
+            origin("https://updates.push.services.mozilla.com/wpush/v2/x").unwrap(),
+            "https://updates.push.services.mozilla.com"
+        );
+        assert!(origin("http://push.example.com/x").is_err());
+        assert!(origin("https:///x").is_err());
+    }
+}
@@ -0,0 +1,172 @@
+//! WhatsApp notifications, sent with the WhatsApp Business Cloud API. Configured with
+//! WHATSAPP_ACCESS_TOKEN and WHATSAPP_PHONE_NUMBER_ID (the sending number's id in Meta's
+//! business manager); without them nothing is sent on WhatsApp.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::PgConnection;
+
+use crate::services::notifications::DeliveryError;
+
+const GRAPH_API: &str = "https://graph.facebook.com/v21.0";
+
+/// Meta's code for a number that is not on WhatsApp (or cannot get business messages)
+const UNDELIVERABLE: i64 = 131026;
+
+/// From WHATSAPP_ACCESS_TOKEN and WHATSAPP_PHONE_NUMBER_ID
+#[derive(Debug, Clone)]
+pub struct WhatsappConfig {
+    pub access_token: String,
+    pub phone_number_id: String,
+}
+
+fn env_value(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// None unless both are set
+pub fn config() -> Option<WhatsappConfig> {
+    Some(WhatsappConfig {
+        access_token: env_value("WHATSAPP_ACCESS_TOKEN")?,
+        phone_number_id: env_value("WHATSAPP_PHONE_NUMBER_ID")?,
+    })
+}
+
+/// A phone number the way WhatsApp wants it: digits only, country code first. Numbers saved
+/// without a country code (8 digits in Guatemala) get `country_code`; None when there are too
+/// few digits to be a number.
+pub fn international_number(phone: &str, country_code: &str) -> Option<String> {
+    let digits: String = phone.chars().filter(char::is_ascii_digit).collect();
+    let digits = digits.strip_prefix("00").unwrap_or(&digits);
+    match digits.len() {
+        0..=7 => None,
+        8 => Some(format!("{}{}", country_code, digits)),
+        _ => Some(digits.to_string()),
+    }
+}
+
+/// The approved template (name and language) set up for a kind of notification
+pub async fn template_for(
+    conn: &mut PgConnection,
+    kind: &str,
+) -> Result<Option<(String, String)>, String> {
+    sqlx::query_as("SELECT name, language FROM whatsapp_templates WHERE kind = $1")
+        .bind(kind)
+        .fetch_optional(conn)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// The Cloud API request: the message in its template (as the template's one parameter), or
+/// as plain text for kinds without one, which WhatsApp only delivers within 24 hours of the
+/// person writing in
+pub fn message_body(number: &str, message: &str, template: Option<&(String, String)>) -> Value {
+    match template {
+        Some((name, language)) => json!({
+            "messaging_product": "whatsapp",
+            "to": number,
+            "type": "template",
+            "template": {
+                "name": name,
+                "language": { "code": language },
+                "components": [{
+                    "type": "body",
+                    "parameters": [{ "type": "text", "text": message }],
+                }],
+            },
+        }),
+        None => json!({
+            "messaging_product": "whatsapp",
+            "to": number,
+            "type": "text",
+            "text": { "body": message },
+        }),
+    }
+}
+
+#[derive(Deserialize)]
+struct GraphError {
+    error: GraphErrorDetail,
+}
+
+#[derive(Deserialize)]
+struct GraphErrorDetail {
+    code: i64,
+    message: String,
+}
+
+/// Send `message` to `number` (international digits)
+pub async fn send(
+    client: &reqwest::Client,
+    config: &WhatsappConfig,
+    number: &str,
+    message: &str,
+    template: Option<&(String, String)>,
+) -> Result<(), DeliveryError> {
+    let response = client
+        .post(format!("{}/{}/messages", GRAPH_API, config.phone_number_id))
+        .bearer_auth(&config.access_token)
+        .header("Content-Type", "application/json")
+        .body(message_body(number, message, template).to_string())
+        .send()
+        .await
+        .map_err(|e| DeliveryError::transient(e.to_string()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let detail = response.bytes().await.unwrap_or_default();
+    match serde_json::from_slice::<GraphError>(&detail) {
+        Ok(GraphError { error }) => Err(DeliveryError {
+            permanent: error.code == UNDELIVERABLE,
+            error: format!("WhatsApp answered {}: {}", status, error.message),
+        }),
+        Err(_) => Err(DeliveryError::transient(format!(
+            "WhatsApp answered {}",
+            status
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_numbers_get_the_country_code() {
+        assert_eq!(
+            international_number("5555-1234", "502"),
+            Some("50255551234".to_string())
+        );
+        assert_eq!(
+            international_number("+502 5555 1234", "502"),
+            Some("50255551234".to_string())
+        );
+        assert_eq!(
+            international_number("001 (212) 555-0100", "502"),
+            Some("12125550100".to_string())
+        );
+        assert_eq!(international_number("555-123", "502"), None);
+    }
+
+    #[test]
+    fn kinds_with_a_template_are_sent_in_it() {
+        let template = ("recordatorio".to_string(), "es".to_string());
+        let body = message_body("50255551234", "Hola Ana", Some(&template));
+        assert_eq!(body["type"], "template");
+        assert_eq!(body["template"]["name"], "recordatorio");
+        assert_eq!(body["template"]["language"]["code"], "es");
+        assert_eq!(
+            body["template"]["components"][0]["parameters"][0]["text"],
+            "Hola Ana"
+        );
+
+        let body = message_body("50255551234", "Hola Ana", None);
+        assert_eq!(body["type"], "text");
+        assert_eq!(body["text"]["body"], "Hola Ana");
+    }
+}
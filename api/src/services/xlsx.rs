@@ -0,0 +1,170 @@
+use axum::{
+    http::header,
+    response::{IntoResponse, Response},
+};
+use rust_xlsxwriter::{Format, Workbook};
+
+/// A worksheet cell
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Text(String),
+    Number(f64),
+    Empty,
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        Cell::Text(text.to_string())
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Cell::Text(text)
+    }
+}
+
+impl From<i64> for Cell {
+    fn from(n: i64) -> Self {
+        Cell::Number(n as f64)
+    }
+}
+
+/// One worksheet; the first row is the header and is written in bold
+pub struct Sheet {
+    pub name: String,
+    pub rows: Vec<Vec<Cell>>,
+    /// Column widths in characters, from the first column; the rest keep Excel's default
+    pub widths: Vec<f32>,
+}
+
+/// Excel refuses sheet names over 31 characters, with []:*?/\ or repeated in a workbook
+fn sheet_names(sheets: &[Sheet]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for sheet in sheets {
+        let base: String = sheet
+            .name
+            .chars()
+            .map(|c| if "[]:*?/\\".contains(c) { '-' } else { c })
+            .take(31)
+            .collect();
+        let base = if base.trim().is_empty() {
+            "Hoja".to_string()
+        } else {
+            base
+        };
+        let mut name = base.clone();
+        let mut n = 2;
+        while names.iter().any(|taken| taken.eq_ignore_ascii_case(&name)) {
+            let suffix = format!(" ({})", n);
+            let keep = 31 - suffix.chars().count();
+            name = format!("{}{}", base.chars().take(keep).collect::<String>(), suffix);
+            n += 1;
+        }
+        names.push(name);
+    }
+    names
+}
+
+/// Build an .xlsx workbook with the sheets in order; each header row stays visible while
+/// scrolling
+pub fn workbook(sheets: &[Sheet]) -> Result<Vec<u8>, String> {
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+    let plain = Format::new();
+
+    for (sheet, name) in sheets.iter().zip(sheet_names(sheets)) {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(name).map_err(|e| e.to_string())?;
+        for (c, width) in sheet.widths.iter().enumerate() {
+            worksheet
+                .set_column_width(c as u16, *width)
+                .map_err(|e| e.to_string())?;
+        }
+        if !sheet.rows.is_empty() {
+            worksheet
+                .set_freeze_panes(1, 0)
+                .map_err(|e| e.to_string())?;
+        }
+        for (r, row) in sheet.rows.iter().enumerate() {
+            let format = if r == 0 { &bold } else { &plain };
+            for (c, cell) in row.iter().enumerate() {
+                let (r, c) = (r as u32, c as u16);
+                match cell {
+                    Cell::Text(text) => worksheet.write_string_with_format(r, c, text, format),
+                    Cell::Number(n) => worksheet.write_number_with_format(r, c, *n, format),
+                    Cell::Empty => continue,
+                }
+                .map_err(|e| e.to_string())?;
+            }
+        }
+    }
+
+    workbook.save_to_buffer().map_err(|e| e.to_string())
+}
+
+/// Return a workbook as a file download
+pub fn attachment(filename: &str, body: Vec<u8>) -> Response {
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet".to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sheet(name: &str) -> Sheet {
+        Sheet {
+            name: name.to_string(),
+            rows: vec![vec!["Servidor".into(), "Total".into()]],
+            widths: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn sheet_names_are_valid_and_unique() {
+        let names = sheet_names(&[
+            sheet("Lectores"),
+            sheet("lectores"),
+            sheet("Monaguillos Jr. / Monaguillos Sr. del domingo"),
+            sheet(""),
+        ]);
+        assert_eq!(names[0], "Lectores");
+        assert_eq!(names[1], "lectores (2)");
+        assert_eq!(names[2], "Monaguillos Jr. - Monaguillos S");
+        assert_eq!(names[3], "Hoja");
+    }
+
+    #[test]
+    fn workbook_is_a_zip_with_one_part_per_sheet() {
+        let body = workbook(&[
+            Sheet {
+                name: "Resumen".to_string(),
+                rows: vec![
+                    vec!["Servidor".into(), "Total".into()],
+                    vec!["Ana & Luis <Pérez>".into(), Cell::Empty, 3i64.into()],
+                ],
+                widths: vec![30.0],
+            },
+            sheet("Lectores"),
+        ])
+        .unwrap();
+        assert!(body.starts_with(b"PK\x03\x04"));
+        let has_part = |part: &str| body.windows(part.len()).any(|w| w == part.as_bytes());
+        assert!(has_part("xl/worksheets/sheet1.xml"));
+        assert!(has_part("xl/worksheets/sheet2.xml"));
+        assert!(!has_part("xl/worksheets/sheet3.xml"));
+    }
+}
@@ -0,0 +1,51 @@
+//! AWS Lambda handler for the background jobs, invoked by SQS with the ids of queued jobs
+//!
+//! Build with: cargo lambda build --release --bin worker
+//! The queue is the API's BACKGROUND_JOBS_QUEUE_URL; turn on "report batch item failures" on
+//! the trigger so only the failed messages come back.
+
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use people_scheduler_api::{
+    db,
+    services::{
+        background_jobs,
+        job_queue::{BatchItemFailure, BatchResponse, SqsEvent},
+    },
+};
+use sqlx::PgPool;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+async fn handle(pool: &PgPool, event: LambdaEvent<SqsEvent>) -> Result<BatchResponse, Error> {
+    let mut response = BatchResponse::default();
+    for message in event.payload.records {
+        let job_id = message.body.trim();
+        match background_jobs::run_job(pool, job_id).await {
+            Ok(Some(job)) => tracing::info!("Background job {} is {}", job.id, job.status),
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!("Background job {}: {}", job_id, e);
+                response.batch_item_failures.push(BatchItemFailure {
+                    item_identifier: message.message_id,
+                });
+            }
+        }
+    }
+    Ok(response)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().without_time())
+        .with(tracing_subscriber::EnvFilter::new(
+            std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into()),
+        ))
+        .init();
+
+    // The API Lambda runs the migrations; the worker only needs the pool
+    let pool = db::create_pool()
+        .await
+        .expect("Failed to create database pool");
+
+    run(service_fn(|event| handle(&pool, event))).await
+}
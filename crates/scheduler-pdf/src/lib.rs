@@ -0,0 +1,236 @@
+//! PDF documents shared by the API (recognition certificates) and the desktop app (schedule
+//! exports), written with printpdf. Text is set in the built-in Helvetica and Helvetica-Bold,
+//! so no font is embedded.
+//!
+//! Callers lay out each `Page` themselves (text, lines and frames, in points from the bottom
+//! left) and `document` turns the pages into a file.
+
+use printpdf::path::PaintMode;
+use printpdf::{
+    BuiltinFont, CustomPdfConformance, IndirectFontRef, Line, Mm, PdfConformance, PdfDocument,
+    Point, Pt, Rect,
+};
+
+// Helvetica advance widths (1/1000 em) for ASCII 32..=126, from the standard AFM
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // ' '../
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, // 0..9
+    278, 278, 584, 584, 584, 556, 1015, // :..@
+    667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, // A..M
+    722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, // N..Z
+    278, 278, 278, 469, 556, 333, // [..`
+    556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, // a..m
+    556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, // n..z
+    334, 260, 334, 584, // {..~
+];
+
+/// Approximate width of the text in points; bold is a little wider but close enough to
+/// center or wrap. Letters outside ASCII (the accented ones) count as an average letter.
+pub fn text_width(text: &str, size: f32) -> f32 {
+    let units: u32 = text
+        .chars()
+        .map(|c| match c {
+            ' '..='~' => HELVETICA_WIDTHS[(c as u8 - 0x20) as usize] as u32,
+            _ => 556,
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+/// Break text into lines no wider than `max_width`, on spaces
+pub fn wrap(text: &str, size: f32, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+        if !current.is_empty() && text_width(&candidate, size) > max_width {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        } else {
+            current = candidate;
+        }
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Something drawn on a page; coordinates and sizes are in points from the bottom left
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mark {
+    /// A line of text, its baseline starting at (`x`, `y`)
+    Text {
+        x: f32,
+        y: f32,
+        size: f32,
+        bold: bool,
+        text: String,
+    },
+    /// A straight rule
+    Line {
+        from: (f32, f32),
+        to: (f32, f32),
+        width: f32,
+    },
+    /// The outline of a rectangle with its lower left corner at (`x`, `y`)
+    Frame {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        line_width: f32,
+    },
+}
+
+/// One page's marks, drawn in order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Page {
+    pub marks: Vec<Mark>,
+}
+
+impl Page {
+    pub fn text(&mut self, x: f32, y: f32, size: f32, bold: bool, text: &str) {
+        self.marks.push(Mark::Text {
+            x,
+            y,
+            size,
+            bold,
+            text: text.to_string(),
+        });
+    }
+
+    pub fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32) {
+        self.marks.push(Mark::Line { from, to, width });
+    }
+
+    pub fn frame(&mut self, x: f32, y: f32, width: f32, height: f32, line_width: f32) {
+        self.marks.push(Mark::Frame {
+            x,
+            y,
+            width,
+            height,
+            line_width,
+        });
+    }
+
+    /// The text marks, in the order they were drawn
+    pub fn texts(&self) -> impl Iterator<Item = &str> {
+        self.marks.iter().filter_map(|mark| match mark {
+            Mark::Text { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+    }
+}
+
+fn point(x: f32, y: f32) -> Point {
+    Point::new(Mm::from(Pt(x)), Mm::from(Pt(y)))
+}
+
+/// Build a PDF document from its pages, every page `width` by `height` points
+pub fn document(title: &str, pages: &[Page], width: f32, height: f32) -> Result<Vec<u8>, String> {
+    let (width, height) = (Mm::from(Pt(width)), Mm::from(Pt(height)));
+    let (doc, first_page, first_layer) = PdfDocument::new(title, width, height, "Contenido");
+    // Plain PDF: no ICC profile or XMP metadata, which only print-ready standards need
+    let doc = doc.with_conformance(PdfConformance::Custom(CustomPdfConformance {
+        requires_icc_profile: false,
+        requires_xmp_metadata: false,
+        ..Default::default()
+    }));
+    let regular: IndirectFontRef = doc
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| e.to_string())?;
+    let bold: IndirectFontRef = doc
+        .add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| e.to_string())?;
+
+    for (i, page) in pages.iter().enumerate() {
+        let layer = if i == 0 {
+            doc.get_page(first_page).get_layer(first_layer)
+        } else {
+            let (page, layer) = doc.add_page(width, height, "Contenido");
+            doc.get_page(page).get_layer(layer)
+        };
+        for mark in &page.marks {
+            match mark {
+                Mark::Text {
+                    x,
+                    y,
+                    size,
+                    bold: is_bold,
+                    text,
+                } => {
+                    let font = if *is_bold { &bold } else { &regular };
+                    layer.use_text(text, *size, Mm::from(Pt(*x)), Mm::from(Pt(*y)), font);
+                }
+                Mark::Line { from, to, width } => {
+                    layer.set_outline_thickness(*width);
+                    layer.add_line(Line {
+                        points: vec![(point(from.0, from.1), false), (point(to.0, to.1), false)],
+                        is_closed: false,
+                    });
+                }
+                Mark::Frame {
+                    x,
+                    y,
+                    width,
+                    height,
+                    line_width,
+                } => {
+                    layer.set_outline_thickness(*line_width);
+                    layer.add_rect(
+                        Rect::new(
+                            Mm::from(Pt(*x)),
+                            Mm::from(Pt(*y)),
+                            Mm::from(Pt(x + width)),
+                            Mm::from(Pt(y + height)),
+                        )
+                        .with_mode(PaintMode::Stroke),
+                    );
+                }
+            }
+        }
+    }
+
+    doc.save_to_bytes().map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_lists_wrap_on_spaces() {
+        let lines = wrap("Ana López, Luis Pérez, Marta Gómez", 10.0, 80.0);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| text_width(l, 10.0) <= 80.0));
+        assert_eq!(lines.join(" "), "Ana López, Luis Pérez, Marta Gómez");
+        assert_eq!(wrap("", 10.0, 80.0), vec![String::new()]);
+    }
+
+    #[test]
+    fn widths_follow_the_helvetica_metrics() {
+        assert_eq!(text_width("Il", 10.0), (278.0 + 222.0) * 10.0 / 1000.0);
+        assert_eq!(text_width("ñ", 10.0), text_width("n", 10.0));
+    }
+
+    #[test]
+    fn document_has_a_page_per_page() {
+        let mut first = Page::default();
+        first.text(10.0, 10.0, 12.0, false, "Año ¡sí! (uno)");
+        first.line((10.0, 20.0), (100.0, 20.0), 0.5);
+        let mut second = Page::default();
+        second.frame(28.0, 28.0, 786.0, 539.0, 0.6);
+        second.text(10.0, 10.0, 12.0, true, "Dos");
+        assert_eq!(second.texts().collect::<Vec<_>>(), vec!["Dos"]);
+
+        let pdf = document("Prueba", &[first, second], 842.0, 595.0).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-"));
+        assert_eq!(text.matches("/Type/Page/").count(), 2);
+        assert!(text.contains("/BaseFont/Helvetica-Bold"));
+    }
+}
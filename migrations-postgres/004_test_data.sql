@@ -96,7 +96,7 @@ VALUES
 ('sched_jan_2026', 'Enero 2026', 2026, 1, 'PUBLISHED', NOW()),
 ('sched_feb_2026', 'Febrero 2026', 2026, 2, 'PUBLISHED', NOW()),
 ('sched_apr_2026', 'Abril 2026', 2026, 4, 'PUBLISHED', NOW())
-ON CONFLICT (year, month) DO NOTHING;
+ON CONFLICT (year, month) WHERE status NOT IN ('ARCHIVED', 'EXPIRED') DO NOTHING;
 
 -- Create service dates (Sundays) for January 2026
 INSERT INTO service_dates (id, schedule_id, service_date) VALUES
@@ -0,0 +1,14 @@
+-- Key-value settings that tune the scheduler and reports without a redeploy
+CREATE TABLE IF NOT EXISTS app_settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    description TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Monaguillos Jr. program defaults
+INSERT INTO app_settings (key, value, description) VALUES
+    ('juniors_max_per_senior', '1', 'Maximum Monaguillos Jr. per Mass for each senior Monaguillo assigned that date'),
+    ('junior_promotion_min_age', '10', 'Minimum age for a Monaguillo Jr. to be promoted'),
+    ('junior_promotion_min_services', '8', 'Minimum Monaguillos Jr. services before promotion')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,16 @@
+-- Prefer-with / avoid-with pairs between any two people (beyond sibling groups)
+-- Pairs are stored with person_a_id < person_b_id so each pair exists once
+CREATE TABLE IF NOT EXISTS pairing_preferences (
+    id VARCHAR(255) PRIMARY KEY,
+    person_a_id VARCHAR(255) NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    person_b_id VARCHAR(255) NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    preference VARCHAR(50) NOT NULL CHECK (preference IN ('PREFER', 'AVOID')),
+    notes TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE(person_a_id, person_b_id),
+    CHECK (person_a_id < person_b_id)
+);
+
+DROP TRIGGER IF EXISTS update_pairing_preferences_updated_at ON pairing_preferences;
+CREATE TRIGGER update_pairing_preferences_updated_at BEFORE UPDATE ON pairing_preferences FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
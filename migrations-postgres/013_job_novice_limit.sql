@@ -0,0 +1,5 @@
+-- Per-job limit of inexperienced people per service date
+-- A novice is someone with fewer than novice_threshold past assignments in this job
+-- NULL max_novices_per_date means no limit
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS max_novices_per_date INTEGER;
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS novice_threshold INTEGER NOT NULL DEFAULT 3;
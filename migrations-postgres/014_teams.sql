@@ -0,0 +1,23 @@
+-- Fixed teams that the generator rotates as a unit for one job
+CREATE TABLE IF NOT EXISTS teams (
+    id VARCHAR(255) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    job_id VARCHAR(255) NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS team_members (
+    id VARCHAR(255) PRIMARY KEY,
+    team_id VARCHAR(255) NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    person_id VARCHAR(255) NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE(team_id, person_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_teams_job ON teams(job_id);
+CREATE INDEX IF NOT EXISTS idx_team_members_team ON team_members(team_id);
+
+DROP TRIGGER IF EXISTS update_teams_updated_at ON teams;
+CREATE TRIGGER update_teams_updated_at BEFORE UPDATE ON teams FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,13 @@
+-- Months of the year (1-12) in which a person never serves a given job
+-- e.g. a lector who only serves during the school year
+CREATE TABLE IF NOT EXISTS person_job_blackouts (
+    id VARCHAR(255) PRIMARY KEY,
+    person_id VARCHAR(255) NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    job_id VARCHAR(255) NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    months INTEGER[] NOT NULL,
+    reason TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE(person_id, job_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_person_job_blackouts_person ON person_job_blackouts(person_id);
@@ -0,0 +1,13 @@
+-- Per-date staffing overrides (e.g. 6 monaguillos on solemnities instead of 4)
+-- Keyed by calendar date so they can be entered before the month is generated
+CREATE TABLE IF NOT EXISTS staffing_overrides (
+    id VARCHAR(255) PRIMARY KEY,
+    service_date DATE NOT NULL,
+    job_id VARCHAR(255) NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    people_required INTEGER NOT NULL CHECK (people_required >= 0),
+    reason TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE(service_date, job_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_staffing_overrides_date ON staffing_overrides(service_date);
@@ -0,0 +1,25 @@
+-- Old usernames that still work for login during a grace period after a rename
+CREATE TABLE IF NOT EXISTS username_aliases (
+    username VARCHAR(50) PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_username_aliases_user ON username_aliases(user_id);
+
+-- In-app notifications shown to a servidor (account changes, etc.)
+CREATE TABLE IF NOT EXISTS notifications (
+    id VARCHAR(255) PRIMARY KEY,
+    person_id VARCHAR(255) NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    kind VARCHAR(50) NOT NULL,
+    message TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    read_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_notifications_person ON notifications(person_id);
+
+INSERT INTO app_settings (key, value, description) VALUES
+    ('username_alias_grace_days', '30', 'Days an old username keeps working for login after it is regenerated')
+ON CONFLICT (key) DO NOTHING;
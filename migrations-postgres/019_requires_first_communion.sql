@@ -0,0 +1,2 @@
+-- Jobs that may only be given to people who made their first communion
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS requires_first_communion BOOLEAN NOT NULL DEFAULT FALSE;
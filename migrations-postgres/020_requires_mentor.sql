@@ -0,0 +1,14 @@
+-- Mentor rule: a date with a novice in this job must also have an experienced person in it
+-- Novice/experienced uses the job's novice_threshold (past assignments in the job)
+-- Monaguillos start with the rule on; only done when the column is first added so
+-- an admin turning it off is not undone on the next start
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'jobs' AND column_name = 'requires_mentor'
+    ) THEN
+        ALTER TABLE jobs ADD COLUMN requires_mentor BOOLEAN NOT NULL DEFAULT FALSE;
+        UPDATE jobs SET requires_mentor = TRUE WHERE id = 'monaguillos';
+    END IF;
+END $$;
@@ -0,0 +1,4 @@
+-- How much the per-job count weighs in generator fairness (0 = only total assignments, 100 = only this job)
+INSERT INTO app_settings (key, value, description) VALUES
+    ('fairness_job_weight', '50', 'Percent of the fairness score taken from assignments in the same job (the rest from all assignments this year)')
+ON CONFLICT (key) DO NOTHING;
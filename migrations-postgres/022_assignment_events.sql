@@ -0,0 +1,15 @@
+-- Change log of assignments, one row per slot change, to rebuild what happened to a service date
+CREATE TABLE IF NOT EXISTS assignment_events (
+    id VARCHAR(255) PRIMARY KEY,
+    service_date_id VARCHAR(255) NOT NULL REFERENCES service_dates(id) ON DELETE CASCADE,
+    assignment_id VARCHAR(255) NOT NULL,
+    job_id VARCHAR(255) NOT NULL,
+    position INTEGER,
+    action VARCHAR(50) NOT NULL,      -- generated, assigned, substituted, cleared, swapped, moved
+    person_id VARCHAR(255),           -- who is in the slot after the change
+    previous_person_id VARCHAR(255),  -- who was in it before
+    changed_by VARCHAR(255),          -- username, NULL for the generator
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_assignment_events_service_date ON assignment_events(service_date_id, created_at);
@@ -0,0 +1,11 @@
+-- Position rotation state per person and job: positions already done in the current cycle
+-- Rows are created from assignment_history the first time a person is looked at, then kept up
+-- to date by the generator and manual assignment edits
+CREATE TABLE IF NOT EXISTS position_rotation (
+    person_id VARCHAR(255) NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    job_id VARCHAR(255) NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    cycle INTEGER NOT NULL DEFAULT 1,
+    done_positions INTEGER[] NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (person_id, job_id)
+);
@@ -0,0 +1,13 @@
+-- Schedules can cover an arbitrary date range (a quarter, a liturgical season)
+-- and schedules generated together from one range share a series_id
+
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS start_date DATE;
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS end_date DATE;
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS series_id VARCHAR(255);
+
+UPDATE schedules
+SET start_date = make_date(year, month, 1),
+    end_date = (make_date(year, month, 1) + INTERVAL '1 month - 1 day')::date
+WHERE start_date IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_schedules_series ON schedules(series_id);
@@ -0,0 +1,23 @@
+-- Jobs a person is never scheduled for, even if qualified (generalizes exclude_monaguillos / exclude_lectores)
+-- The legacy flags are copied over once, when the table is first created; afterwards the API keeps them in sync
+DO $$
+BEGIN
+    IF to_regclass('person_job_exclusions') IS NULL THEN
+        CREATE TABLE person_job_exclusions (
+            id VARCHAR(255) PRIMARY KEY,
+            person_id VARCHAR(255) NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+            job_id VARCHAR(255) NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+            reason TEXT,
+            created_at TIMESTAMPTZ DEFAULT NOW(),
+            UNIQUE(person_id, job_id)
+        );
+
+        INSERT INTO person_job_exclusions (id, person_id, job_id)
+        SELECT p.id || '-' || j.id, p.id, j.id
+        FROM people p
+        JOIN jobs j ON (p.exclude_monaguillos AND j.id IN ('monaguillos', 'monaguillos_jr'))
+                    OR (p.exclude_lectores AND j.id = 'lectores');
+    END IF;
+END $$;
+
+CREATE INDEX IF NOT EXISTS idx_person_job_exclusions_person ON person_job_exclusions(person_id);
@@ -0,0 +1,4 @@
+-- How far ahead servidores can see their own assignments (0 = no limit)
+INSERT INTO app_settings (key, value, description) VALUES
+    ('assignment_visibility_weeks', '0', 'Weeks ahead a servidor can see their assignments in the app, calendar feeds and notifications (0 = no limit)')
+ON CONFLICT (key) DO NOTHING;
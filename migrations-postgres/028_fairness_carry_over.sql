@@ -0,0 +1,5 @@
+-- Fairness counts look back over a trailing window instead of resetting every January
+INSERT INTO app_settings (key, value, description) VALUES
+    ('fairness_window_months', '12', 'Months of assignment history the generator counts for fairness (0 = calendar year only)'),
+    ('fairness_decay_percent', '10', 'How much less an assignment counts for each month it is older (0 = no decay)')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,4 @@
+-- Keep a person to a limited number of ministries within one month (0 = no limit)
+INSERT INTO app_settings (key, value, description) VALUES
+    ('max_jobs_per_person_per_month', '0', 'Maximum different jobs a person is scheduled in within one month (0 = no limit)')
+ON CONFLICT (key) DO NOTHING;
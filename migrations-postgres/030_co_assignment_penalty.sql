@@ -0,0 +1,4 @@
+-- Mix group composition: penalty per recent shared service with someone already picked (0 = off)
+INSERT INTO app_settings (key, value, description) VALUES
+    ('co_assignment_penalty', '1', 'Fairness points added per time a candidate served with an already picked person in the same job over the last 5 weeks (0 = off)')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,21 @@
+-- Dead-letter queue for notifications that could not be delivered
+CREATE TABLE IF NOT EXISTS notification_failures (
+    id VARCHAR(255) PRIMARY KEY,
+    person_id VARCHAR(255) NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    channel VARCHAR(50) NOT NULL,
+    destination TEXT,
+    kind VARCHAR(50) NOT NULL,
+    message TEXT NOT NULL,
+    error TEXT NOT NULL,
+    -- The address or number itself is bad (bounce), as opposed to a temporary outage
+    permanent BOOLEAN NOT NULL DEFAULT false,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    last_attempt_at TIMESTAMPTZ DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_notification_failures_open ON notification_failures(resolved_at);
+
+-- Set when a delivery bounces, cleared when the coordinator edits the email or phone
+ALTER TABLE people ADD COLUMN IF NOT EXISTS contact_bounced BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,4 @@
+-- The visibility window covers the app only; calendar feeds and notifications do not exist yet
+UPDATE app_settings
+SET description = 'Weeks ahead a servidor can see assignments in the app (0 = no limit)'
+WHERE key = 'assignment_visibility_weeks';
@@ -0,0 +1,29 @@
+-- Recurring unavailability: an RRULE-like pattern repeated inside start_date..end_date,
+-- e.g. 'FREQ=WEEKLY;BYDAY=SA' (every Saturday) or 'FREQ=MONTHLY;BYDAY=1SU' (first Sunday)
+ALTER TABLE unavailability ADD COLUMN IF NOT EXISTS recurrence TEXT;
+
+-- Parsed from recurrence by the API so queries can match dates without parsing the rule:
+-- day of week as EXTRACT(DOW) numbers it (0 = Sunday) and week of the month
+-- (1-5, -1 = last, NULL = every week)
+ALTER TABLE unavailability ADD COLUMN IF NOT EXISTS recurrence_weekday SMALLINT;
+ALTER TABLE unavailability ADD COLUMN IF NOT EXISTS recurrence_week SMALLINT;
+
+-- Whether an unavailability record covers a date: the whole period, or only the days of
+-- the period that match its pattern
+CREATE OR REPLACE FUNCTION unavailability_matches(u unavailability, d DATE)
+RETURNS BOOLEAN AS $$
+    SELECT d BETWEEN u.start_date AND u.end_date
+       AND (
+           u.recurrence_weekday IS NULL
+           OR (
+               EXTRACT(DOW FROM d)::int = u.recurrence_weekday
+               AND (
+                   u.recurrence_week IS NULL
+                   OR (u.recurrence_week > 0
+                       AND (EXTRACT(DAY FROM d)::int - 1) / 7 + 1 = u.recurrence_week)
+                   OR (u.recurrence_week = -1
+                       AND EXTRACT(MONTH FROM d + 7) <> EXTRACT(MONTH FROM d))
+               )
+           )
+       )
+$$ LANGUAGE sql STABLE;
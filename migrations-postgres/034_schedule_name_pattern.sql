@@ -0,0 +1,5 @@
+-- How generated monthly schedules are named, e.g. 'Monaguillos – {mes} {año}'.
+-- Schedules that already exist keep their name.
+INSERT INTO app_settings (key, value, description) VALUES
+    ('schedule_name_pattern', '{mes} {año}', 'Name given to generated monthly schedules and export titles: {mes} is the month name, {mm} the month number and {año} the year')
+ON CONFLICT (key) DO NOTHING;
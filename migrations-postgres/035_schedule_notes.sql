@@ -0,0 +1,2 @@
+-- Free-text notes an admin attaches to a schedule after generation
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS notes TEXT;
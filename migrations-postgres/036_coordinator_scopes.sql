@@ -0,0 +1,7 @@
+-- Jobs a "coordinator" user manages: they can only edit people, unavailability and
+-- assignments that belong to these jobs
+CREATE TABLE IF NOT EXISTS user_job_scopes (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    job_id VARCHAR NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    PRIMARY KEY (user_id, job_id)
+);
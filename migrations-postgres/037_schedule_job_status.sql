@@ -0,0 +1,25 @@
+-- Per-job publishing: while a schedule is still a draft, single jobs can be published on
+-- their own (Lectores out while Monaguillos is still being worked on). Once the whole
+-- schedule is published or archived its own status decides for every job.
+CREATE TABLE IF NOT EXISTS schedule_job_status (
+    schedule_id VARCHAR(255) NOT NULL REFERENCES schedules(id) ON DELETE CASCADE,
+    job_id VARCHAR(255) NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    status VARCHAR(50) NOT NULL DEFAULT 'DRAFT',
+    published_at TIMESTAMPTZ,
+    PRIMARY KEY (schedule_id, job_id)
+);
+
+-- Whether servidores get to see a job's assignments in a schedule
+CREATE OR REPLACE FUNCTION job_is_published(p_schedule_id VARCHAR, p_job_id VARCHAR)
+RETURNS BOOLEAN AS $$
+    SELECT CASE s.status
+        WHEN 'PUBLISHED' THEN TRUE
+        WHEN 'DRAFT' THEN EXISTS (
+            SELECT 1 FROM schedule_job_status sj
+            WHERE sj.schedule_id = s.id AND sj.job_id = p_job_id AND sj.status = 'PUBLISHED'
+        )
+        ELSE FALSE
+    END
+    FROM schedules s
+    WHERE s.id = p_schedule_id
+$$ LANGUAGE SQL STABLE;
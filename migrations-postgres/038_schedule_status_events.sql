@@ -0,0 +1,14 @@
+-- Who moved a schedule between DRAFT / PUBLISHED / ARCHIVED, and why (required when a
+-- published schedule is taken back)
+CREATE TABLE IF NOT EXISTS schedule_status_events (
+    id VARCHAR(255) PRIMARY KEY,
+    schedule_id VARCHAR(255) NOT NULL REFERENCES schedules(id) ON DELETE CASCADE,
+    from_status VARCHAR(50) NOT NULL,
+    to_status VARCHAR(50) NOT NULL,
+    reason TEXT,
+    changed_by VARCHAR(255) NOT NULL,   -- username
+    notified INTEGER NOT NULL DEFAULT 0, -- people sent a retraction notice
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_schedule_status_events_schedule ON schedule_status_events(schedule_id, created_at);
@@ -0,0 +1,24 @@
+-- Copy of a schedule's assignments each time it is published, numbered per schedule, so
+-- admins can tell people exactly what changed between two publications
+CREATE TABLE IF NOT EXISTS schedule_snapshots (
+    id VARCHAR(255) PRIMARY KEY,
+    schedule_id VARCHAR(255) NOT NULL REFERENCES schedules(id) ON DELETE CASCADE,
+    version INTEGER NOT NULL,
+    created_by VARCHAR(255) NOT NULL,   -- username
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (schedule_id, version)
+);
+
+-- Names are copied too, so old versions still read right after a person or job is renamed
+CREATE TABLE IF NOT EXISTS schedule_snapshot_assignments (
+    snapshot_id VARCHAR(255) NOT NULL REFERENCES schedule_snapshots(id) ON DELETE CASCADE,
+    service_date DATE NOT NULL,
+    job_id VARCHAR(255) NOT NULL,
+    job_name VARCHAR(255) NOT NULL,
+    position INTEGER,
+    position_name VARCHAR(255),
+    person_id VARCHAR(255),
+    person_name VARCHAR(255)
+);
+
+CREATE INDEX IF NOT EXISTS idx_schedule_snapshot_assignments_snapshot ON schedule_snapshot_assignments(snapshot_id);
@@ -0,0 +1,7 @@
+-- How long a login lasts, per role: kiosks and servidores can stay signed in for weeks
+-- while admin sessions stay short. Applies to tokens issued after the change.
+INSERT INTO app_settings (key, value, description) VALUES
+    ('jwt_lifetime_hours_admin', '24', 'Hours an admin login stays valid'),
+    ('jwt_lifetime_hours_coordinator', '24', 'Hours a coordinator login stays valid'),
+    ('jwt_lifetime_hours_servidor', '24', 'Hours a servidor login stays valid')
+ON CONFLICT (key) DO NOTHING;
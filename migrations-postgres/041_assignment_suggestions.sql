@@ -0,0 +1,2 @@
+-- Assignments copied from another month are suggestions until someone edits the slot
+ALTER TABLE assignments ADD COLUMN IF NOT EXISTS suggested BOOLEAN NOT NULL DEFAULT FALSE;
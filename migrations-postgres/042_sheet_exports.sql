@@ -0,0 +1,15 @@
+-- Google Sheets exports: the API writes published assignments into a tab of a spreadsheet
+-- shared with its service account, and again whenever they change
+CREATE TABLE IF NOT EXISTS sheet_exports (
+    id VARCHAR(255) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    spreadsheet_id VARCHAR(255) NOT NULL,
+    sheet_name VARCHAR(255) NOT NULL,   -- the tab, which must already exist
+    job_id VARCHAR(255) REFERENCES jobs(id) ON DELETE CASCADE, -- NULL = every job
+    created_by VARCHAR(255) NOT NULL,   -- username
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    content_hash VARCHAR(64),           -- digest of the rows last written
+    last_pushed_at TIMESTAMPTZ,
+    last_error TEXT,
+    last_error_at TIMESTAMPTZ
+);
@@ -0,0 +1,2 @@
+-- Free-text note on a slot ("llegar 20 min antes para el ensayo"), shown to whoever serves it
+ALTER TABLE assignments ADD COLUMN IF NOT EXISTS notes TEXT;
@@ -0,0 +1,9 @@
+-- One active schedule per month: the application checks before creating one, but two admins
+-- generating the same month at once could both pass that check. Archived (and expired)
+-- schedules step aside so the month can be generated again. Inserts use
+-- ON CONFLICT (year, month) WHERE status NOT IN ('ARCHIVED', 'EXPIRED') DO NOTHING and answer
+-- 409 when the month is taken.
+-- Fails (and is logged) while duplicates exist; archive or delete the extra schedules and restart.
+ALTER TABLE schedules DROP CONSTRAINT IF EXISTS schedules_year_month_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_schedules_active_month ON schedules(year, month)
+    WHERE status NOT IN ('ARCHIVED', 'EXPIRED');
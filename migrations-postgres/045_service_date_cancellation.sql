@@ -0,0 +1,5 @@
+-- A service date that will not take place (no children's mass that week). Its assignments
+-- are removed when it is cancelled; the date stays so the schedule shows why it is missing.
+ALTER TABLE service_dates ADD COLUMN IF NOT EXISTS cancelled_at TIMESTAMPTZ;
+ALTER TABLE service_dates ADD COLUMN IF NOT EXISTS cancelled_by VARCHAR(255); -- username
+ALTER TABLE service_dates ADD COLUMN IF NOT EXISTS cancel_reason TEXT;
@@ -0,0 +1,6 @@
+-- Why a coordinator put someone in a slot despite a warning (unavailable that day, a sibling
+-- who should be kept apart already serving). Cleared when the slot changes hands again.
+ALTER TABLE assignments ADD COLUMN IF NOT EXISTS override_reason TEXT;
+
+-- The same reason kept on the change log entry, so it outlives later edits to the slot
+ALTER TABLE assignment_events ADD COLUMN IF NOT EXISTS reason TEXT;
@@ -0,0 +1,17 @@
+-- One row per manual edit of a schedule (update, clear, swap, move, bulk), grouping the
+-- assignment_events it produced so the edit can be undone and redone as a whole.
+CREATE TABLE IF NOT EXISTS assignment_edits (
+    id VARCHAR(255) PRIMARY KEY,
+    schedule_id VARCHAR(255) NOT NULL REFERENCES schedules(id) ON DELETE CASCADE,
+    kind VARCHAR(50) NOT NULL,        -- update, clear, swap, move, bulk
+    changed_by VARCHAR(255) NOT NULL, -- username
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    undone_at TIMESTAMPTZ             -- set while the edit is undone (available to redo)
+);
+
+CREATE INDEX IF NOT EXISTS idx_assignment_edits_schedule ON assignment_edits(schedule_id, created_at);
+
+ALTER TABLE assignment_events ADD COLUMN IF NOT EXISTS edit_id VARCHAR(255)
+    REFERENCES assignment_edits(id) ON DELETE SET NULL;
+-- Order of events inside one transaction, where created_at is the same for all of them
+ALTER TABLE assignment_events ADD COLUMN IF NOT EXISTS seq BIGSERIAL;
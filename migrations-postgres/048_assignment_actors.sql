@@ -0,0 +1,4 @@
+-- Who created each slot (NULL = the generator) and who changed it last; assignment_events
+-- keeps the full list of changes for the schedule audit
+ALTER TABLE assignments ADD COLUMN IF NOT EXISTS created_by VARCHAR(255);
+ALTER TABLE assignments ADD COLUMN IF NOT EXISTS updated_by VARCHAR(255);
@@ -0,0 +1,4 @@
+-- Time of the Mass the schedules cover, shown by the signage display (HH:MM, blank = not shown)
+INSERT INTO app_settings (key, value, description) VALUES
+    ('mass_time', '', 'Time of the Mass the schedules cover, HH:MM (shown on the narthex display; blank = not shown)')
+ON CONFLICT (key) DO NOTHING;
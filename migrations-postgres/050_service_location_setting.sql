@@ -0,0 +1,4 @@
+-- Where services take place, the LOCATION of exported calendar events (blank = not shown)
+INSERT INTO app_settings (key, value, description) VALUES
+    ('service_location', '', 'Where services take place, shown as the location of calendar events (blank = not shown)')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,9 @@
+-- Calendar subscriptions: a secret per-person link serving their published assignments as
+-- iCalendar, so a phone calendar subscribed to it stays current on its own
+CREATE TABLE IF NOT EXISTS calendar_feeds (
+    person_id VARCHAR(255) PRIMARY KEY REFERENCES people(id) ON DELETE CASCADE,
+    -- Hex SHA-256 of the token in the link; the token itself is only shown when it is created
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),   -- also when it was last rotated
+    last_fetched_at TIMESTAMPTZ
+);
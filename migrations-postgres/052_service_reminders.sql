@@ -0,0 +1,26 @@
+-- Reminders sent to servidores a few days before they serve
+INSERT INTO app_settings (key, value, description) VALUES
+    ('reminder_days_before', '2', 'Days before a service date its servidores are reminded (0 = no reminders)'),
+    ('reminder_channel', 'email', 'Where reminders are sent: email, sms or in_app')
+ON CONFLICT (key) DO NOTHING;
+
+-- Who was reminded of which date, so running the reminders twice sends them once
+CREATE TABLE IF NOT EXISTS service_reminders (
+    person_id VARCHAR(255) NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    service_date_id VARCHAR(255) NOT NULL REFERENCES service_dates(id) ON DELETE CASCADE,
+    channel VARCHAR(50) NOT NULL,
+    sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (person_id, service_date_id)
+);
+
+-- Email, SMS and WhatsApp notifications waiting for the background worker. They are written
+-- in the sender's transaction, so nothing goes out for a change that was rolled back, and no
+-- request waits on SMTP or Twilio.
+CREATE TABLE IF NOT EXISTS notification_outbox (
+    id VARCHAR(255) PRIMARY KEY,
+    person_id VARCHAR(255) NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    channel VARCHAR(50) NOT NULL,
+    kind VARCHAR(50) NOT NULL,
+    message TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
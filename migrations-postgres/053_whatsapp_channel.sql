@@ -0,0 +1,17 @@
+-- WhatsApp notifications, sent through the Cloud API: which approved template each kind of
+-- notification goes out in (WhatsApp only allows free text within 24 hours of the person
+-- writing in)
+CREATE TABLE IF NOT EXISTS whatsapp_templates (
+    kind VARCHAR(50) PRIMARY KEY,        -- service_reminder, schedule_published, ...
+    name VARCHAR(255) NOT NULL,
+    language VARCHAR(20) NOT NULL DEFAULT 'es',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO app_settings (key, value, description) VALUES
+    ('whatsapp_country_code', '502', 'Country code added to phone numbers saved without one, for WhatsApp'),
+    ('publish_channel', '', 'Where servidores are told a schedule with their dates was published: email, sms, whatsapp or in_app (blank = not told)')
+ON CONFLICT (key) DO NOTHING;
+
+UPDATE app_settings SET description = 'Where reminders are sent: email, sms, whatsapp or in_app'
+WHERE key = 'reminder_channel';
@@ -0,0 +1,28 @@
+-- Web Push: the browsers and phones each user allowed to show notifications, as returned by
+-- PushManager.subscribe()
+CREATE TABLE IF NOT EXISTS push_subscriptions (
+    id VARCHAR(255) PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    endpoint TEXT NOT NULL UNIQUE,
+    p256dh TEXT NOT NULL,
+    auth TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_push_subscriptions_user ON push_subscriptions(user_id);
+
+-- Pushes waiting for the background worker, which encrypts each payload for its subscription
+-- and posts it to the push service
+CREATE TABLE IF NOT EXISTS push_outbox (
+    id VARCHAR(255) PRIMARY KEY,
+    subscription_id VARCHAR(255) NOT NULL REFERENCES push_subscriptions(id) ON DELETE CASCADE,
+    kind VARCHAR(50) NOT NULL,
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+UPDATE app_settings SET description = 'Where reminders are sent: email, sms, whatsapp, push or in_app'
+WHERE key = 'reminder_channel';
+UPDATE app_settings SET description = 'Where servidores are told a schedule with their dates was published: email, sms, whatsapp, push or in_app (blank = not told)'
+WHERE key = 'publish_channel';
@@ -0,0 +1,24 @@
+-- How each servidor wants to be told things. A missing row (or NULL column) means whatever the
+-- notification uses by default: the reminder_channel/publish_channel settings, the parish
+-- reminder_days_before.
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    person_id VARCHAR(255) PRIMARY KEY REFERENCES people(id) ON DELETE CASCADE,
+    channel VARCHAR(50),                 -- email, sms, whatsapp, push or in_app
+    reminder_days_before INTEGER,        -- 0 = no reminders
+    -- Collect notifications into one message a day instead of sending each right away
+    digest BOOLEAN NOT NULL DEFAULT false,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Notifications held for the next digest of people who chose one
+CREATE TABLE IF NOT EXISTS notification_digest_items (
+    id VARCHAR(255) PRIMARY KEY,
+    person_id VARCHAR(255) NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    channel VARCHAR(50) NOT NULL,
+    kind VARCHAR(50) NOT NULL,
+    message TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_notification_digest_items_person
+    ON notification_digest_items(person_id, created_at);
@@ -0,0 +1,7 @@
+-- Migration 056: Tell people when their published assignments change
+-- The person leaving a slot and the one taking it hear about it on this channel, on top of
+-- the push sent to the browsers and phones they subscribed.
+
+INSERT INTO app_settings (key, value, description) VALUES
+    ('change_channel', 'in_app', 'Where servidores are told a published assignment of theirs changed: email, sms, whatsapp, push or in_app (blank = push only)')
+ON CONFLICT (key) DO NOTHING;
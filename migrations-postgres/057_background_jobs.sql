@@ -0,0 +1,22 @@
+-- Long-running work queued for the background worker instead of run inside a request:
+-- batch generation, reminder and digest runs. Payload and result are JSON text.
+CREATE TABLE IF NOT EXISTS background_jobs (
+    id VARCHAR(255) PRIMARY KEY,
+    kind VARCHAR(50) NOT NULL,           -- generate_batch, send_reminders, send_digests
+    payload TEXT NOT NULL DEFAULT '{}',
+    status VARCHAR(20) NOT NULL DEFAULT 'queued', -- queued, running, done, failed
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 3,
+    run_after TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    result TEXT,
+    error TEXT,
+    created_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_background_jobs_queue
+    ON background_jobs(run_after) WHERE status = 'queued';
+CREATE INDEX IF NOT EXISTS idx_background_jobs_created
+    ON background_jobs(created_at DESC);
@@ -0,0 +1,25 @@
+-- Sign-in with an OpenID Connect provider (the diocese's Google Workspace). Local passwords
+-- keep working; OIDC is only offered when OIDC_CLIENT_ID is set.
+
+-- Sign-in email of users without a linked person (admins); servidores use their person's email
+ALTER TABLE users ADD COLUMN IF NOT EXISTS email VARCHAR(255);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_email ON users(LOWER(email));
+
+-- External accounts already matched to a user, so later sign-ins do not depend on the email
+CREATE TABLE IF NOT EXISTS oidc_identities (
+    issuer VARCHAR(255) NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_login_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (issuer, subject)
+);
+
+-- The provider's public signing keys (its JWKS), fetched by the API and kept here so all
+-- instances share them; updated_at says when they were fetched
+CREATE TABLE IF NOT EXISTS oidc_signing_keys (
+    kid VARCHAR(255) PRIMARY KEY,
+    jwk TEXT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,17 @@
+-- Audit trail of sign-ins and password changes, for spotting guessed or shared accounts
+CREATE TABLE IF NOT EXISTS auth_events (
+    id VARCHAR(255) PRIMARY KEY,
+    -- login, login_failed, oidc_login, oidc_login_failed, password_changed,
+    -- password_change_failed, password_reset
+    event VARCHAR(50) NOT NULL,
+    user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    -- As typed for failed logins, so attempts on unknown usernames show up too
+    username VARCHAR(255),
+    detail TEXT,
+    ip VARCHAR(64),
+    user_agent TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_auth_events_created ON auth_events(created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_auth_events_username ON auth_events(username, created_at DESC);
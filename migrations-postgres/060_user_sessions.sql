@@ -0,0 +1,20 @@
+-- Every login is a session with its own refresh token, so a user can see where they are
+-- signed in and end one session (the lost family tablet) or all of them
+CREATE TABLE IF NOT EXISTS user_sessions (
+    id VARCHAR(255) PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Argon2 hash of the refresh token's secret; replaced on every refresh
+    refresh_hash VARCHAR(255) NOT NULL,
+    device TEXT,                         -- User agent at login
+    ip VARCHAR(64),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_sessions_user ON user_sessions(user_id, last_used_at DESC);
+
+INSERT INTO app_settings (key, value, description) VALUES
+    ('session_lifetime_days', '30', 'Days a login can keep refreshing its token without signing in again')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,10 @@
+-- Guardian accounts: one login for a parent with several children who serve. A user with
+-- role 'guardian' sees and manages the availability of the people linked here.
+CREATE TABLE IF NOT EXISTS guardian_children (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    person_id VARCHAR(255) NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, person_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_guardian_children_person ON guardian_children(person_id);
@@ -0,0 +1,14 @@
+-- Long-lived keys for read-only integrations (the parish website, the sacristy display), sent
+-- in the X-Api-Key header as "psk_<id>.<secret>"
+CREATE TABLE IF NOT EXISTS api_keys (
+    id VARCHAR(255) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    -- Argon2 hash of the secret; the key itself is only shown when created
+    key_hash VARCHAR(255) NOT NULL,
+    scopes TEXT[] NOT NULL,             -- e.g. {assignments:read}
+    job_id VARCHAR(255) REFERENCES jobs(id) ON DELETE CASCADE, -- NULL = every job
+    created_by VARCHAR(255) NOT NULL,   -- username
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
@@ -0,0 +1,16 @@
+-- Invitation links instead of printed passwords: the servidor opens the link, picks a
+-- password, and only then gets a login. One open invitation per person; resending replaces it.
+CREATE TABLE IF NOT EXISTS invitations (
+    id VARCHAR(255) PRIMARY KEY,
+    person_id VARCHAR(255) NOT NULL UNIQUE REFERENCES people(id) ON DELETE CASCADE,
+    -- Argon2 hash of the link's secret; the link is "<id>.<secret>"
+    token_hash VARCHAR(255) NOT NULL,
+    created_by VARCHAR(255) NOT NULL,   -- username
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),   -- also when it was last resent
+    expires_at TIMESTAMPTZ NOT NULL,
+    accepted_at TIMESTAMPTZ
+);
+
+INSERT INTO app_settings (key, value, description) VALUES
+    ('invitation_lifetime_days', '7', 'Days an invitation link can be used to set a password')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,3 @@
+-- Minimum number of days a person must rest between two assignments
+-- NULL means no restriction (only max_consecutive_weeks applies)
+ALTER TABLE people ADD COLUMN IF NOT EXISTS min_rest_days INTEGER;
@@ -0,0 +1,3 @@
+-- Short notes for whoever builds the schedule (e.g. "solo misa de 11"),
+-- kept apart from the pastoral notes in people.notes
+ALTER TABLE people ADD COLUMN IF NOT EXISTS scheduling_notes TEXT;
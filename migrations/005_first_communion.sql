@@ -0,0 +1,3 @@
+-- First communion tracking and the per-job requirement that uses it
+ALTER TABLE people ADD COLUMN IF NOT EXISTS first_communion BOOLEAN DEFAULT FALSE;
+ALTER TABLE jobs ADD COLUMN IF NOT EXISTS requires_first_communion BOOLEAN DEFAULT FALSE;
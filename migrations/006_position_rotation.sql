@@ -0,0 +1,10 @@
+-- Position rotation state per person and job: positions already done in the current cycle
+-- (comma separated), kept up to date when schedules are saved and assignments edited
+CREATE TABLE IF NOT EXISTS position_rotation (
+    person_id VARCHAR NOT NULL,
+    job_id VARCHAR NOT NULL,
+    cycle INTEGER NOT NULL DEFAULT 1,
+    done_positions VARCHAR NOT NULL DEFAULT '',
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (person_id, job_id)
+);
@@ -0,0 +1,2 @@
+-- Where a sibling pairing rule applies: SAME_JOB (within one job's selection) or SAME_DATE (any job that date)
+ALTER TABLE sibling_groups ADD COLUMN IF NOT EXISTS pairing_scope VARCHAR DEFAULT 'SAME_JOB';
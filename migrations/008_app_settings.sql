@@ -0,0 +1,12 @@
+-- Key/value settings, as in the web version. schedule_name_pattern names generated monthly
+-- schedules: {mes} is the month name, {mm} the month number and {año} the year
+CREATE TABLE IF NOT EXISTS app_settings (
+    key VARCHAR PRIMARY KEY,
+    value VARCHAR NOT NULL,
+    description VARCHAR,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO app_settings (key, value, description)
+SELECT 'schedule_name_pattern', '{mes} {año}', 'Name given to generated monthly schedules and export titles'
+WHERE NOT EXISTS (SELECT 1 FROM app_settings WHERE key = 'schedule_name_pattern');
@@ -0,0 +1,7 @@
+-- People deleted locally, kept until a cloud sync has removed them (and their servidor account)
+-- there as well; otherwise the cloud keeps a login for someone who no longer exists
+CREATE TABLE IF NOT EXISTS deleted_people (
+    person_id VARCHAR PRIMARY KEY,
+    deleted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    synced_at TIMESTAMP
+);
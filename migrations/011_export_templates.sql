@@ -0,0 +1,11 @@
+-- Column layouts for exports ("Lectores: fecha, posición, servidor, teléfono"). One install serves
+-- one parish, so templates are shared by everyone using it. fields is a comma-separated list
+-- of date, weekday, job, position, person, phone and notes; order_by is date, job or person.
+CREATE TABLE IF NOT EXISTS export_templates (
+    id VARCHAR PRIMARY KEY,
+    name VARCHAR NOT NULL UNIQUE,
+    fields VARCHAR NOT NULL,
+    order_by VARCHAR NOT NULL DEFAULT 'date',
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,28 @@
+// Service worker for Web Push: shows the notifications the API queues (schedule published,
+// assignment changed, reminders) even when the app is closed. The API sends an encrypted JSON
+// payload of { title, body }.
+self.addEventListener('push', (event) => {
+  let data = {};
+  try {
+    data = event.data ? event.data.json() : {};
+  } catch {
+    data = { body: event.data ? event.data.text() : '' };
+  }
+  event.waitUntil(
+    self.registration.showNotification(data.title || 'Horario de servicio', {
+      body: data.body || '',
+      icon: '/logo.jpeg',
+      lang: 'es',
+    })
+  );
+});
+
+self.addEventListener('notificationclick', (event) => {
+  event.notification.close();
+  event.waitUntil(
+    self.clients.matchAll({ type: 'window', includeUncontrolled: true }).then((windows) => {
+      const open = windows.find((w) => 'focus' in w);
+      return open ? open.focus() : self.clients.openWindow('/');
+    })
+  );
+});
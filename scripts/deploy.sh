@@ -16,6 +16,8 @@ NC='\033[0m' # No Color
 # Configuration - set these environment variables or create scripts/.env
 AWS_PROFILE="${AWS_PROFILE:-people-scheduler}"
 LAMBDA_FUNCTION="${LAMBDA_FUNCTION:-people-scheduler-api}"
+# The background jobs worker, triggered by the SQS queue; unset = not deployed
+WORKER_FUNCTION="${WORKER_FUNCTION:-}"
 S3_BUCKET="${S3_BUCKET:-}"
 CLOUDFRONT_DISTRIBUTION="${CLOUDFRONT_DISTRIBUTION:-}"
 PROJECT_ROOT="$(cd "$(dirname "$0")/.." && pwd)"
@@ -42,7 +44,7 @@ echo "CloudFront: $CLOUDFRONT_DISTRIBUTION"
 # Step 1: Build Lambda
 echo -e "\n${YELLOW}[1/4] Building Lambda (ARM64)...${NC}"
 cd "$PROJECT_ROOT/api"
-cargo zigbuild --release --target aarch64-unknown-linux-gnu --bin lambda
+cargo zigbuild --release --target aarch64-unknown-linux-gnu --bin lambda --bin worker
 echo -e "${GREEN}✓ Lambda built${NC}"
 
 # Step 2: Package and deploy Lambda
@@ -54,6 +56,15 @@ aws --profile "$AWS_PROFILE" lambda update-function-code \
     --zip-file fileb://lambda.zip \
     --output text --query 'LastModified'
 rm bootstrap lambda.zip
+if [ -n "$WORKER_FUNCTION" ]; then
+    cp target/aarch64-unknown-linux-gnu/release/worker bootstrap
+    zip -j worker.zip bootstrap
+    aws --profile "$AWS_PROFILE" lambda update-function-code \
+        --function-name "$WORKER_FUNCTION" \
+        --zip-file fileb://worker.zip \
+        --output text --query 'LastModified'
+    rm bootstrap worker.zip
+fi
 echo -e "${GREEN}✓ Lambda deployed${NC}"
 
 # Step 3: Build and deploy frontend
@@ -0,0 +1,34 @@
+#!/bin/bash
+set -e
+
+# Generates the API's OpenAPI description and the TypeScript types of its routes from it,
+# which src/services/apiClient.ts is typed with. Run it after changing a route or a schema
+# and commit src/types/api.gen.ts with the change; with --check it only fails when the
+# committed file is out of date (CI runs that). The API serves the same description at
+# /openapi.json.
+
+PROJECT_ROOT="$(cd "$(dirname "$0")/.." && pwd)"
+OPENAPI_TYPESCRIPT="$PROJECT_ROOT/node_modules/.bin/openapi-typescript"
+
+if [ ! -x "$OPENAPI_TYPESCRIPT" ]; then
+    echo "openapi-typescript is not installed; run npm install first" >&2
+    exit 1
+fi
+
+cd "$PROJECT_ROOT/api"
+cargo run --quiet --bin openapi > openapi.json
+echo "Wrote api/openapi.json"
+
+cd "$PROJECT_ROOT"
+if [ "$1" = "--check" ]; then
+    GENERATED="$(mktemp)"
+    trap 'rm -f "$GENERATED"' EXIT
+    "$OPENAPI_TYPESCRIPT" api/openapi.json --output "$GENERATED"
+    if ! diff -u src/types/api.gen.ts "$GENERATED"; then
+        echo "src/types/api.gen.ts is out of date; run npm run sdk and commit it" >&2
+        exit 1
+    fi
+    echo "src/types/api.gen.ts is up to date"
+else
+    "$OPENAPI_TYPESCRIPT" api/openapi.json --output src/types/api.gen.ts
+fi
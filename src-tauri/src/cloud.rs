@@ -0,0 +1,105 @@
+//! Client for the cloud API's desktop sync routes. The request and response bodies follow the
+//! API's OpenAPI description (`npm run sdk` writes it to api/openapi.json):
+//! `SyncDeletedPeopleRequest`/`SyncDeletedPeopleResponse` and `ReconcileUsersRequest`/`OrphanUser`.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, thiserror::Error)]
+pub enum CloudError {
+    /// The API rejected the token; the app refreshes it and calls again on this exact message
+    #[error("unauthorized")]
+    Unauthorized,
+    #[error("No se pudo conectar con la nube: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("{0}")]
+    Api(String),
+}
+
+#[derive(Debug, Serialize)]
+struct PersonIds<'a> {
+    person_ids: &'a [String],
+}
+
+/// POST /api/admin/sync/deleted-people
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SyncDeletedPeopleResponse {
+    pub deleted_people: Vec<String>,
+    /// Usernames of the accounts removed with their person
+    pub deleted_users: Vec<String>,
+    /// Ids the cloud did not have (already deleted or never synced)
+    pub not_found: Vec<String>,
+}
+
+/// A servidor account whose person is gone, from POST /api/admin/sync/reconcile-users
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrphanUser {
+    pub id: String,
+    pub username: String,
+    pub person_id: Option<String>,
+    pub person_name: Option<String>,
+    /// "no_person" or "deleted_locally"
+    pub reason: String,
+    pub created_at: Option<String>,
+}
+
+/// The cloud API at `base_url` (without /api), called as the admin whose token this is
+pub struct CloudClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: String,
+}
+
+impl CloudClient {
+    pub fn new(base_url: &str, token: &str) -> Self {
+        CloudClient {
+            http: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+        }
+    }
+
+    /// Delete these people, and their servidor accounts, in the cloud
+    pub async fn sync_deleted_people(
+        &self,
+        person_ids: &[String],
+    ) -> Result<SyncDeletedPeopleResponse, CloudError> {
+        self.post("/api/admin/sync/deleted-people", &PersonIds { person_ids })
+            .await
+    }
+
+    /// Cloud servidor accounts whose person is not among these
+    pub async fn reconcile_users(
+        &self,
+        person_ids: &[String],
+    ) -> Result<Vec<OrphanUser>, CloudError> {
+        self.post("/api/admin/sync/reconcile-users", &PersonIds { person_ids })
+            .await
+    }
+
+    async fn post<B: Serialize, T: for<'de> Deserialize<'de>>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T, CloudError> {
+        let response = self
+            .http
+            .post(format!("{}{}", self.base_url, path))
+            .bearer_auth(&self.token)
+            .json(body)
+            .send()
+            .await?;
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(CloudError::Unauthorized);
+        }
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(CloudError::Api(if message.is_empty() {
+                format!("HTTP error {}", status.as_u16())
+            } else {
+                message
+            }));
+        }
+        Ok(response.json().await?)
+    }
+}
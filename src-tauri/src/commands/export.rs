@@ -1,8 +1,62 @@
-use crate::export::export_schedule_to_excel;
+use super::export_templates::get_export_template;
+use crate::export::{
+    export_schedule_to_csv, export_schedule_to_excel, export_schedule_to_pdf,
+    export_schedules_to_excel, ExportLayout,
+};
+use crate::models::ExportTemplate;
 use std::path::PathBuf;
 
+fn load_template(template_id: Option<String>) -> Result<Option<ExportTemplate>, String> {
+    template_id.map(get_export_template).transpose()
+}
+
+/// `layout` is "list" (the default) or "grid", dates as columns and job positions as rows.
+/// `template_id` picks an export template, whose columns replace the layout.
+#[tauri::command]
+pub fn export_schedule_to_path(
+    schedule_id: String,
+    path: String,
+    layout: Option<String>,
+    template_id: Option<String>,
+) -> Result<(), String> {
+    let layout = ExportLayout::parse(layout.as_deref())?;
+    let template = load_template(template_id)?;
+    let path_buf = PathBuf::from(path);
+    export_schedule_to_excel(&schedule_id, &path_buf, layout, template.as_ref())
+}
+
+/// One workbook for several months (e.g. all of a year's schedules): a Resumen sheet of
+/// per-person totals and a sheet per month in the list layout
+#[tauri::command]
+pub fn export_schedules_to_path(schedule_ids: Vec<String>, path: String) -> Result<(), String> {
+    let path_buf = PathBuf::from(path);
+    export_schedules_to_excel(&schedule_ids, &path_buf)
+}
+
+/// Date, job, position, person and phone per slot, for coordinators who message servidores
+/// from a spreadsheet; `layout` and `template_id` as for the Excel export
+#[tauri::command]
+pub fn export_schedule_csv_to_path(
+    schedule_id: String,
+    path: String,
+    layout: Option<String>,
+    template_id: Option<String>,
+) -> Result<(), String> {
+    let layout = ExportLayout::parse(layout.as_deref())?;
+    let template = load_template(template_id)?;
+    let path_buf = PathBuf::from(path);
+    export_schedule_to_csv(&schedule_id, &path_buf, layout, template.as_ref())
+}
+
+/// The Excel list layout as a PDF, for printing or sending to the parish office as is;
+/// `template_id` as for the Excel export
 #[tauri::command]
-pub fn export_schedule_to_path(schedule_id: String, path: String) -> Result<(), String> {
+pub fn export_schedule_pdf_to_path(
+    schedule_id: String,
+    path: String,
+    template_id: Option<String>,
+) -> Result<(), String> {
+    let template = load_template(template_id)?;
     let path_buf = PathBuf::from(path);
-    export_schedule_to_excel(&schedule_id, &path_buf)
+    export_schedule_to_pdf(&schedule_id, &path_buf, template.as_ref())
 }
@@ -0,0 +1,112 @@
+use crate::db::with_db;
+use crate::models::{
+    CreateExportTemplateRequest, ExportField, ExportOrder, ExportTemplate,
+    UpdateExportTemplateRequest,
+};
+use uuid::Uuid;
+
+/// Stored as "date,job,person"; names that are no longer fields are dropped
+fn parse_fields(fields: &str) -> Vec<ExportField> {
+    fields
+        .split(',')
+        .filter_map(ExportField::from_str)
+        .collect()
+}
+
+fn join_fields(fields: &[ExportField]) -> Result<String, String> {
+    if fields.is_empty() {
+        return Err("A template needs at least one column".to_string());
+    }
+    Ok(fields
+        .iter()
+        .map(|f| f.to_string())
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+fn template_from_row(row: &duckdb::Row) -> duckdb::Result<ExportTemplate> {
+    Ok(ExportTemplate {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        fields: parse_fields(&row.get::<_, String>(2)?),
+        order_by: ExportOrder::from_str(&row.get::<_, String>(3)?),
+        created_at: None,
+        updated_at: None,
+    })
+}
+
+#[tauri::command]
+pub fn get_all_export_templates() -> Result<Vec<ExportTemplate>, String> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, name, fields, order_by
+             FROM export_templates
+             ORDER BY name",
+        )?;
+
+        let templates = stmt
+            .query_map([], template_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(templates)
+    })
+}
+
+#[tauri::command]
+pub fn get_export_template(id: String) -> Result<ExportTemplate, String> {
+    with_db(|conn| {
+        let mut stmt =
+            conn.prepare("SELECT id, name, fields, order_by FROM export_templates WHERE id = ?")?;
+        stmt.query_row([&id], template_from_row)
+    })
+}
+
+#[tauri::command]
+pub fn create_export_template(
+    request: CreateExportTemplateRequest,
+) -> Result<ExportTemplate, String> {
+    let id = Uuid::new_v4().to_string();
+    let fields = join_fields(&request.fields)?;
+    let order_by = request.order_by.unwrap_or_default();
+
+    with_db(|conn| {
+        conn.execute(
+            "INSERT INTO export_templates (id, name, fields, order_by) VALUES (?, ?, ?, ?)",
+            duckdb::params![&id, &request.name, &fields, order_by.to_string()],
+        )?;
+        Ok(())
+    })?;
+
+    get_export_template(id)
+}
+
+#[tauri::command]
+pub fn update_export_template(
+    request: UpdateExportTemplateRequest,
+) -> Result<ExportTemplate, String> {
+    let current = get_export_template(request.id.clone())?;
+
+    let name = request.name.unwrap_or(current.name);
+    let fields = join_fields(&request.fields.unwrap_or(current.fields))?;
+    let order_by = request.order_by.unwrap_or(current.order_by);
+
+    with_db(|conn| {
+        conn.execute(
+            "UPDATE export_templates SET name = ?, fields = ?, order_by = ?, updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?",
+            duckdb::params![name, fields, order_by.to_string(), &request.id],
+        )?;
+        Ok(())
+    })?;
+
+    get_export_template(request.id)
+}
+
+#[tauri::command]
+pub fn delete_export_template(id: String) -> Result<(), String> {
+    with_db(|conn| {
+        conn.execute("DELETE FROM export_templates WHERE id = ?", [&id])?;
+        Ok(())
+    })
+}
@@ -6,9 +6,10 @@ use uuid::Uuid;
 pub fn get_all_jobs() -> Result<Vec<Job>, String> {
     with_db(|conn| {
         let mut stmt = conn.prepare(
-            "SELECT id, name, description, people_required, color, active
+            "SELECT id, name, description, people_required, color, active,
+                    requires_first_communion
              FROM jobs
-             ORDER BY name"
+             ORDER BY name",
         )?;
 
         let jobs: Vec<Job> = stmt
@@ -23,6 +24,7 @@ pub fn get_all_jobs() -> Result<Vec<Job>, String> {
                     created_at: None,
                     updated_at: None,
                     positions: Vec::new(),
+                    requires_first_communion: row.get::<_, Option<bool>>(6)?.unwrap_or(false),
                 })
             })?
             .filter_map(|r| r.ok())
@@ -36,8 +38,9 @@ pub fn get_all_jobs() -> Result<Vec<Job>, String> {
 pub fn get_job(id: String) -> Result<Job, String> {
     with_db(|conn| {
         let mut stmt = conn.prepare(
-            "SELECT id, name, description, people_required, color, active
-             FROM jobs WHERE id = ?"
+            "SELECT id, name, description, people_required, color, active,
+                    requires_first_communion
+             FROM jobs WHERE id = ?",
         )?;
 
         let job = stmt.query_row([&id], |row| {
@@ -51,6 +54,7 @@ pub fn get_job(id: String) -> Result<Job, String> {
                 created_at: None,
                 updated_at: None,
                 positions: Vec::new(),
+                requires_first_communion: row.get::<_, Option<bool>>(6)?.unwrap_or(false),
             })
         })?;
 
@@ -64,14 +68,15 @@ pub fn create_job(request: CreateJobRequest) -> Result<Job, String> {
 
     with_db(|conn| {
         conn.execute(
-            "INSERT INTO jobs (id, name, description, people_required, color)
-             VALUES (?, ?, ?, ?, ?)",
+            "INSERT INTO jobs (id, name, description, people_required, color, requires_first_communion)
+             VALUES (?, ?, ?, ?, ?, ?)",
             duckdb::params![
                 &id,
                 &request.name,
                 &request.description,
                 request.people_required.unwrap_or(4),
-                request.color.as_deref().unwrap_or("#3B82F6")
+                request.color.as_deref().unwrap_or("#3B82F6"),
+                request.requires_first_communion.unwrap_or(false)
             ],
         )?;
         Ok(())
@@ -85,8 +90,9 @@ pub fn update_job(request: UpdateJobRequest) -> Result<Job, String> {
     with_db(|conn| {
         let current = {
             let mut stmt = conn.prepare(
-                "SELECT id, name, description, people_required, color, active
-                 FROM jobs WHERE id = ?"
+                "SELECT id, name, description, people_required, color, active,
+                        requires_first_communion
+                 FROM jobs WHERE id = ?",
             )?;
             stmt.query_row([&request.id], |row| {
                 Ok((
@@ -96,6 +102,7 @@ pub fn update_job(request: UpdateJobRequest) -> Result<Job, String> {
                     row.get::<_, i32>(3)?,
                     row.get::<_, String>(4)?,
                     row.get::<_, bool>(5)?,
+                    row.get::<_, Option<bool>>(6)?.unwrap_or(false),
                 ))
             })?
         };
@@ -105,12 +112,22 @@ pub fn update_job(request: UpdateJobRequest) -> Result<Job, String> {
         let people_required = request.people_required.unwrap_or(current.3);
         let color = request.color.unwrap_or(current.4);
         let active = request.active.unwrap_or(current.5);
+        let requires_first_communion = request.requires_first_communion.unwrap_or(current.6);
 
         conn.execute(
             "UPDATE jobs SET name = ?, description = ?, people_required = ?,
-                            color = ?, active = ?, updated_at = CURRENT_TIMESTAMP
+                            color = ?, active = ?, requires_first_communion = ?,
+                            updated_at = CURRENT_TIMESTAMP
              WHERE id = ?",
-            duckdb::params![name, description, people_required, color, active, &request.id],
+            duckdb::params![
+                name,
+                description,
+                people_required,
+                color,
+                active,
+                requires_first_communion,
+                &request.id
+            ],
         )?;
 
         Ok(())
@@ -1,15 +1,22 @@
-pub mod people;
+pub mod export;
+pub mod export_templates;
 pub mod jobs;
+pub mod people;
 pub mod schedule;
 pub mod sibling;
-pub mod unavailability;
-pub mod export;
+pub mod sync;
 pub mod test_data;
+pub mod unavailability;
 
-pub use people::*;
+pub use export::{
+    export_schedule_csv_to_path, export_schedule_pdf_to_path, export_schedule_to_path,
+    export_schedules_to_path,
+};
+pub use export_templates::*;
 pub use jobs::*;
+pub use people::*;
 pub use schedule::*;
 pub use sibling::*;
-pub use unavailability::*;
-pub use export::export_schedule_to_path;
+pub use sync::*;
 pub use test_data::*;
+pub use unavailability::*;
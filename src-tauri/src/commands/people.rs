@@ -8,9 +8,10 @@ pub fn get_all_people() -> Result<Vec<Person>, String> {
         let mut stmt = conn.prepare(
             "SELECT p.id, p.first_name, p.last_name, p.email, p.phone,
                     p.preferred_frequency, p.max_consecutive_weeks, p.preference_level,
-                    p.active, p.notes
+                    p.active, p.notes, p.min_rest_days, p.scheduling_notes,
+                    p.first_communion
              FROM people p
-             ORDER BY p.last_name, p.first_name"
+             ORDER BY p.last_name, p.first_name",
         )?;
 
         let people: Vec<Person> = stmt
@@ -29,6 +30,9 @@ pub fn get_all_people() -> Result<Vec<Person>, String> {
                     created_at: None,
                     updated_at: None,
                     job_ids: Vec::new(),
+                    min_rest_days: row.get(10)?,
+                    scheduling_notes: row.get(11)?,
+                    first_communion: row.get::<_, Option<bool>>(12)?.unwrap_or(false),
                 })
             })?
             .filter_map(|r| r.ok())
@@ -37,9 +41,8 @@ pub fn get_all_people() -> Result<Vec<Person>, String> {
         // Fetch job assignments for each person
         let mut result = Vec::new();
         for mut person in people {
-            let mut job_stmt = conn.prepare(
-                "SELECT job_id FROM person_jobs WHERE person_id = ?"
-            )?;
+            let mut job_stmt =
+                conn.prepare("SELECT job_id FROM person_jobs WHERE person_id = ?")?;
             person.job_ids = job_stmt
                 .query_map([&person.id], |row| row.get(0))?
                 .filter_map(|r| r.ok())
@@ -57,8 +60,8 @@ pub fn get_person(id: String) -> Result<Person, String> {
         let mut stmt = conn.prepare(
             "SELECT id, first_name, last_name, email, phone,
                     preferred_frequency, max_consecutive_weeks, preference_level,
-                    active, notes
-             FROM people WHERE id = ?"
+                    active, notes, min_rest_days, scheduling_notes, first_communion
+             FROM people WHERE id = ?",
         )?;
 
         let mut person: Person = stmt.query_row([&id], |row| {
@@ -76,6 +79,9 @@ pub fn get_person(id: String) -> Result<Person, String> {
                 created_at: None,
                 updated_at: None,
                 job_ids: Vec::new(),
+                min_rest_days: row.get(10)?,
+                scheduling_notes: row.get(11)?,
+                first_communion: row.get::<_, Option<bool>>(12)?.unwrap_or(false),
             })
         })?;
 
@@ -98,8 +104,9 @@ pub fn create_person(request: CreatePersonRequest) -> Result<Person, String> {
     with_db(|conn| {
         conn.execute(
             "INSERT INTO people (id, first_name, last_name, email, phone,
-                                preferred_frequency, max_consecutive_weeks, preference_level, notes)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                                preferred_frequency, max_consecutive_weeks, preference_level, notes,
+                                min_rest_days, scheduling_notes, first_communion)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
             duckdb::params![
                 &id,
                 &request.first_name,
@@ -109,7 +116,10 @@ pub fn create_person(request: CreatePersonRequest) -> Result<Person, String> {
                 freq.to_string(),
                 request.max_consecutive_weeks.unwrap_or(2),
                 request.preference_level.unwrap_or(5),
-                &request.notes
+                &request.notes,
+                request.min_rest_days,
+                &request.scheduling_notes,
+                request.first_communion.unwrap_or(false)
             ],
         )?;
 
@@ -135,8 +145,8 @@ pub fn update_person(request: UpdatePersonRequest) -> Result<Person, String> {
             let mut stmt = conn.prepare(
                 "SELECT id, first_name, last_name, email, phone,
                         preferred_frequency, max_consecutive_weeks, preference_level,
-                        active, notes
-                 FROM people WHERE id = ?"
+                        active, notes, min_rest_days, scheduling_notes, first_communion
+                 FROM people WHERE id = ?",
             )?;
             stmt.query_row([&request.id], |row| {
                 Ok((
@@ -150,6 +160,9 @@ pub fn update_person(request: UpdatePersonRequest) -> Result<Person, String> {
                     row.get::<_, i32>(7)?,
                     row.get::<_, bool>(8)?,
                     row.get::<_, Option<String>>(9)?,
+                    row.get::<_, Option<i32>>(10)?,
+                    row.get::<_, Option<String>>(11)?,
+                    row.get::<_, Option<bool>>(12)?.unwrap_or(false),
                 ))
             })?
         };
@@ -166,12 +179,16 @@ pub fn update_person(request: UpdatePersonRequest) -> Result<Person, String> {
         let pref_level = request.preference_level.unwrap_or(current.7);
         let active = request.active.unwrap_or(current.8);
         let notes = request.notes.or(current.9);
+        let min_rest_days = request.min_rest_days.or(current.10);
+        let scheduling_notes = request.scheduling_notes.or(current.11);
+        let first_communion = request.first_communion.unwrap_or(current.12);
 
         conn.execute(
             "UPDATE people SET
                 first_name = ?, last_name = ?, email = ?, phone = ?,
                 preferred_frequency = ?, max_consecutive_weeks = ?,
                 preference_level = ?, active = ?, notes = ?,
+                min_rest_days = ?, scheduling_notes = ?, first_communion = ?,
                 updated_at = CURRENT_TIMESTAMP
              WHERE id = ?",
             duckdb::params![
@@ -184,16 +201,16 @@ pub fn update_person(request: UpdatePersonRequest) -> Result<Person, String> {
                 pref_level,
                 active,
                 notes,
+                min_rest_days,
+                scheduling_notes,
+                first_communion,
                 &request.id
             ],
         )?;
 
         // Update job assignments if provided
         if let Some(job_ids) = request.job_ids {
-            conn.execute(
-                "DELETE FROM person_jobs WHERE person_id = ?",
-                [&request.id],
-            )?;
+            conn.execute("DELETE FROM person_jobs WHERE person_id = ?", [&request.id])?;
             for job_id in job_ids {
                 let pj_id = Uuid::new_v4().to_string();
                 conn.execute(
@@ -212,7 +229,43 @@ pub fn update_person(request: UpdatePersonRequest) -> Result<Person, String> {
 #[tauri::command]
 pub fn delete_person(id: String) -> Result<(), String> {
     with_db(|conn| {
-        conn.execute("DELETE FROM people WHERE id = ?", [&id])?;
+        let deleted = conn.execute("DELETE FROM people WHERE id = ?", [&id])?;
+        // Remember the deletion so the next cloud sync removes the person's account too
+        if deleted > 0 {
+            conn.execute(
+                "INSERT INTO deleted_people (person_id) VALUES (?) ON CONFLICT DO NOTHING",
+                [&id],
+            )?;
+        }
+        Ok(())
+    })
+}
+
+/// Person deletions the cloud has not been told about yet
+/// (POST /api/admin/sync/deleted-people)
+#[tauri::command]
+pub fn get_pending_person_deletions() -> Result<Vec<String>, String> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT person_id FROM deleted_people WHERE synced_at IS NULL ORDER BY deleted_at",
+        )?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(ids)
+    })
+}
+
+/// Record that a sync applied these deletions in the cloud
+#[tauri::command]
+pub fn mark_person_deletions_synced(person_ids: Vec<String>) -> Result<(), String> {
+    with_db(|conn| {
+        for id in &person_ids {
+            conn.execute(
+                "UPDATE deleted_people SET synced_at = CURRENT_TIMESTAMP WHERE person_id = ?",
+                [id],
+            )?;
+        }
         Ok(())
     })
 }
@@ -223,11 +276,12 @@ pub fn get_people_for_job(job_id: String) -> Result<Vec<Person>, String> {
         let mut stmt = conn.prepare(
             "SELECT p.id, p.first_name, p.last_name, p.email, p.phone,
                     p.preferred_frequency, p.max_consecutive_weeks, p.preference_level,
-                    p.active, p.notes
+                    p.active, p.notes, p.min_rest_days, p.scheduling_notes,
+                    p.first_communion
              FROM people p
              INNER JOIN person_jobs pj ON p.id = pj.person_id
              WHERE pj.job_id = ? AND p.active = TRUE
-             ORDER BY p.last_name, p.first_name"
+             ORDER BY p.last_name, p.first_name",
         )?;
 
         let people: Vec<Person> = stmt
@@ -246,6 +300,9 @@ pub fn get_people_for_job(job_id: String) -> Result<Vec<Person>, String> {
                     created_at: None,
                     updated_at: None,
                     job_ids: vec![job_id.clone()],
+                    min_rest_days: row.get(10)?,
+                    scheduling_notes: row.get(11)?,
+                    first_communion: row.get::<_, Option<bool>>(12)?.unwrap_or(false),
                 })
             })?
             .filter_map(|r| r.ok())
@@ -1,24 +1,27 @@
 use crate::db::with_db;
 use crate::models::{
     Assignment, EligiblePerson, FairnessScore, GenerateScheduleRequest, GetEligiblePeopleRequest,
-    JobAssignmentCount, PairingRule, Person, Schedule, SchedulePreview, ScheduleStatus,
-    ServiceDate, SiblingGroup, UpdateAssignmentRequest,
+    JobAssignmentCount, PairingRule, PairingScope, Person, Schedule, SchedulePreview,
+    ScheduleStatus, ServiceDate, SiblingGroup, UpdateAssignmentNotesRequest,
+    UpdateAssignmentRequest,
 };
-use crate::scheduler::ScheduleGenerator;
+use crate::scheduler::{rotation, ScheduleGenerator};
 use chrono::{Datelike, NaiveDate};
+use duckdb::{Connection, Result as DuckResult};
 use uuid::Uuid;
 
 #[tauri::command]
-pub fn get_all_schedules() -> Result<Vec<Schedule>, String> {
+pub fn get_all_schedules(include_archived: Option<bool>) -> Result<Vec<Schedule>, String> {
     with_db(|conn| {
         let mut stmt = conn.prepare(
             "SELECT id, name, year, month, status
              FROM schedules
-             ORDER BY year DESC, month DESC"
+             WHERE ? OR status <> 'ARCHIVED'
+             ORDER BY year DESC, month DESC",
         )?;
 
         let schedules: Vec<Schedule> = stmt
-            .query_map([], |row| {
+            .query_map([include_archived.unwrap_or(false)], |row| {
                 Ok(Schedule {
                     id: row.get(0)?,
                     name: row.get(1)?,
@@ -43,7 +46,7 @@ pub fn get_schedule(id: String) -> Result<Schedule, String> {
     with_db(|conn| {
         let mut stmt = conn.prepare(
             "SELECT id, name, year, month, status
-             FROM schedules WHERE id = ?"
+             FROM schedules WHERE id = ?",
         )?;
 
         let mut schedule: Schedule = stmt.query_row([&id], |row| {
@@ -65,7 +68,7 @@ pub fn get_schedule(id: String) -> Result<Schedule, String> {
             "SELECT id, schedule_id, CAST(service_date AS VARCHAR), notes
              FROM service_dates
              WHERE schedule_id = ?
-             ORDER BY service_date"
+             ORDER BY service_date",
         )?;
 
         schedule.service_dates = sd_stmt
@@ -92,7 +95,8 @@ pub fn get_schedule(id: String) -> Result<Schedule, String> {
                         a.manual_override,
                         p.first_name || ' ' || p.last_name as person_name,
                         j.name as job_name,
-                        COALESCE(a.position_name, jp.name) as position_name
+                        COALESCE(a.position_name, jp.name) as position_name,
+                        a.notes
                  FROM assignments a
                  INNER JOIN people p ON a.person_id = p.id
                  INNER JOIN jobs j ON a.job_id = j.id
@@ -115,6 +119,7 @@ pub fn get_schedule(id: String) -> Result<Schedule, String> {
                         person_name: row.get(6).ok(),
                         job_name: row.get(7).ok(),
                         position_name: row.get(8).ok(),
+                        notes: row.get(9)?,
                     })
                 })?
                 .filter_map(|r| r.ok())
@@ -129,9 +134,8 @@ pub fn get_schedule(id: String) -> Result<Schedule, String> {
 pub fn generate_schedule(request: GenerateScheduleRequest) -> Result<SchedulePreview, String> {
     // Check if schedule for this month/year already exists
     let existing = with_db(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, name FROM schedules WHERE year = ? AND month = ?"
-        )?;
+        let mut stmt =
+            conn.prepare("SELECT id, name FROM schedules WHERE year = ? AND month = ?")?;
 
         match stmt.query_row(duckdb::params![request.year, request.month], |row| {
             Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
@@ -157,14 +161,31 @@ pub fn generate_schedule(request: GenerateScheduleRequest) -> Result<SchedulePre
 pub fn save_schedule(preview: SchedulePreview) -> Result<Schedule, String> {
     let schedule = preview.schedule;
 
+    // Saving the same preview again replaces it; a different schedule saved for the month
+    // since this preview was generated is left alone
+    let other = with_db(|conn| {
+        conn.query_row(
+            "SELECT COUNT(*) FROM schedules WHERE year = ? AND month = ? AND id <> ?",
+            duckdb::params![schedule.year, schedule.month, &schedule.id],
+            |row| row.get::<_, i64>(0),
+        )
+    })?;
+    if other > 0 {
+        return Err(format!(
+            "Ya existe otro horario para {}/{}. Recárguelo antes de guardar.",
+            schedule.month, schedule.year
+        ));
+    }
+
     let result_id = with_db(|conn| {
         // Check if schedule for this month/year already exists
-        let mut check_stmt = conn.prepare(
-            "SELECT id FROM schedules WHERE year = ? AND month = ?"
-        )?;
+        let mut check_stmt =
+            conn.prepare("SELECT id FROM schedules WHERE year = ? AND month = ?")?;
 
         let existing_id: Option<String> = check_stmt
-            .query_row(duckdb::params![schedule.year, schedule.month], |row| row.get(0))
+            .query_row(duckdb::params![schedule.year, schedule.month], |row| {
+                row.get(0)
+            })
             .ok();
 
         if let Some(ref existing) = existing_id {
@@ -173,8 +194,21 @@ pub fn save_schedule(preview: SchedulePreview) -> Result<Schedule, String> {
                 "UPDATE schedules SET name = ?, status = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
                 duckdb::params![&schedule.name, schedule.status.to_string(), &existing],
             )?;
+            // Forget rotation state of the people in the version being replaced
+            conn.execute(
+                "DELETE FROM position_rotation WHERE (person_id, job_id) IN (
+                    SELECT a.person_id, a.job_id
+                    FROM assignments a
+                    INNER JOIN service_dates sd ON a.service_date_id = sd.id
+                    WHERE sd.schedule_id = ?
+                 )",
+                [&existing],
+            )?;
             // Delete old service dates (cascade will handle assignments)
-            conn.execute("DELETE FROM service_dates WHERE schedule_id = ?", [&existing])?;
+            conn.execute(
+                "DELETE FROM service_dates WHERE schedule_id = ?",
+                [&existing],
+            )?;
         } else {
             // Insert new schedule
             conn.execute(
@@ -202,8 +236,8 @@ pub fn save_schedule(preview: SchedulePreview) -> Result<Schedule, String> {
 
             for assignment in &sd.assignments {
                 conn.execute(
-                    "INSERT INTO assignments (id, service_date_id, job_id, person_id, position, manual_override, position_name)
-                     VALUES (?, ?, ?, ?, ?, ?, ?)",
+                    "INSERT INTO assignments (id, service_date_id, job_id, person_id, position, manual_override, position_name, notes)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
                     duckdb::params![
                         &assignment.id,
                         &sd.id,
@@ -211,7 +245,8 @@ pub fn save_schedule(preview: SchedulePreview) -> Result<Schedule, String> {
                         &assignment.person_id,
                         assignment.position,
                         assignment.manual_override,
-                        &assignment.position_name
+                        &assignment.position_name,
+                        &assignment.notes
                     ],
                 )?;
 
@@ -231,6 +266,13 @@ pub fn save_schedule(preview: SchedulePreview) -> Result<Schedule, String> {
                         assignment.position
                     ],
                 )?;
+
+                rotation::mark_done(
+                    conn,
+                    &assignment.person_id,
+                    &assignment.job_id,
+                    assignment.position,
+                )?;
             }
         }
 
@@ -240,45 +282,108 @@ pub fn save_schedule(preview: SchedulePreview) -> Result<Schedule, String> {
     get_schedule(result_id)
 }
 
+/// Archived schedules are kept as they were: their assignments cannot be edited
+fn ensure_not_archived(assignment_id: &str) -> Result<(), String> {
+    let archived = with_db(|conn| {
+        conn.query_row(
+            "SELECT s.status = 'ARCHIVED'
+             FROM assignments a
+             INNER JOIN service_dates sd ON a.service_date_id = sd.id
+             INNER JOIN schedules s ON sd.schedule_id = s.id
+             WHERE a.id = ?",
+            [assignment_id],
+            |row| row.get::<_, bool>(0),
+        )
+    })?;
+    if archived {
+        return Err(
+            "El horario está archivado. Debe restaurarlo a borrador antes de editarlo.".to_string(),
+        );
+    }
+    Ok(())
+}
+
+fn fetch_assignment(conn: &Connection, assignment_id: &str) -> DuckResult<Assignment> {
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.service_date_id, a.job_id, a.person_id, a.position,
+                a.manual_override,
+                p.first_name || ' ' || p.last_name as person_name,
+                j.name as job_name,
+                COALESCE(a.position_name, jp.name) as position_name,
+                a.notes
+         FROM assignments a
+         INNER JOIN people p ON a.person_id = p.id
+         INNER JOIN jobs j ON a.job_id = j.id
+         LEFT JOIN job_positions jp ON a.job_id = jp.job_id AND a.position = jp.position_number
+         WHERE a.id = ?",
+    )?;
+
+    stmt.query_row([assignment_id], |row| {
+        Ok(Assignment {
+            id: row.get(0)?,
+            service_date_id: row.get(1)?,
+            job_id: row.get(2)?,
+            person_id: row.get(3)?,
+            position: row.get(4)?,
+            manual_override: row.get(5)?,
+            created_at: None,
+            updated_at: None,
+            person_name: row.get(6).ok(),
+            job_name: row.get(7).ok(),
+            position_name: row.get(8).ok(),
+            notes: row.get(9)?,
+        })
+    })
+}
+
 #[tauri::command]
 pub fn update_assignment(request: UpdateAssignmentRequest) -> Result<Assignment, String> {
+    ensure_not_archived(&request.assignment_id)?;
+
     with_db(|conn| {
+        // Move the slot's position from the old person's rotation to the new one's
+        let (old_person_id, job_id, position): (String, String, i32) = conn.query_row(
+            "SELECT person_id, job_id, position FROM assignments WHERE id = ?",
+            [&request.assignment_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        if old_person_id != request.new_person_id {
+            rotation::unmark_done(conn, &old_person_id, &job_id, position)?;
+            rotation::mark_done(conn, &request.new_person_id, &job_id, position)?;
+        }
+
         conn.execute(
             "UPDATE assignments SET person_id = ?, manual_override = TRUE, updated_at = CURRENT_TIMESTAMP
              WHERE id = ?",
             duckdb::params![&request.new_person_id, &request.assignment_id],
         )?;
 
-        let mut stmt = conn.prepare(
-            "SELECT a.id, a.service_date_id, a.job_id, a.person_id, a.position,
-                    a.manual_override,
-                    p.first_name || ' ' || p.last_name as person_name,
-                    j.name as job_name,
-                    COALESCE(a.position_name, jp.name) as position_name
-             FROM assignments a
-             INNER JOIN people p ON a.person_id = p.id
-             INNER JOIN jobs j ON a.job_id = j.id
-             LEFT JOIN job_positions jp ON a.job_id = jp.job_id AND a.position = jp.position_number
-             WHERE a.id = ?"
-        )?;
+        fetch_assignment(conn, &request.assignment_id)
+    })
+}
 
-        let assignment = stmt.query_row([&request.assignment_id], |row| {
-            Ok(Assignment {
-                id: row.get(0)?,
-                service_date_id: row.get(1)?,
-                job_id: row.get(2)?,
-                person_id: row.get(3)?,
-                position: row.get(4)?,
-                manual_override: row.get(5)?,
-                created_at: None,
-                updated_at: None,
-                person_name: row.get(6).ok(),
-                job_name: row.get(7).ok(),
-                position_name: row.get(8).ok(),
-            })
-        })?;
+/// Set or remove the note on a slot; a blank note removes it
+#[tauri::command]
+pub fn update_assignment_notes(
+    request: UpdateAssignmentNotesRequest,
+) -> Result<Assignment, String> {
+    let notes = request
+        .notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+    if notes.is_some_and(|n| n.chars().count() > 500) {
+        return Err("La nota no puede tener más de 500 caracteres".to_string());
+    }
+    ensure_not_archived(&request.assignment_id)?;
 
-        Ok(assignment)
+    with_db(|conn| {
+        conn.execute(
+            "UPDATE assignments SET notes = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+            duckdb::params![notes, &request.assignment_id],
+        )?;
+
+        fetch_assignment(conn, &request.assignment_id)
     })
 }
 
@@ -296,16 +401,42 @@ pub fn publish_schedule(id: String) -> Result<Schedule, String> {
     get_schedule(id)
 }
 
+#[tauri::command]
+pub fn archive_schedule(id: String) -> Result<Schedule, String> {
+    with_db(|conn| {
+        conn.execute(
+            "UPDATE schedules SET status = 'ARCHIVED', updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?",
+            [&id],
+        )?;
+        Ok(())
+    })?;
+
+    get_schedule(id)
+}
+
 #[tauri::command]
 pub fn delete_schedule(id: String) -> Result<(), String> {
     with_db(|conn| {
         // Get all service_date IDs for this schedule
-        let mut stmt = conn.prepare("SELECT id, CAST(service_date AS VARCHAR) FROM service_dates WHERE schedule_id = ?")?;
+        let mut stmt = conn.prepare(
+            "SELECT id, CAST(service_date AS VARCHAR) FROM service_dates WHERE schedule_id = ?",
+        )?;
         let service_dates: Vec<(String, String)> = stmt
             .query_map([&id], |row| Ok((row.get(0)?, row.get(1)?)))?
             .filter_map(|r| r.ok())
             .collect();
 
+        // Forget rotation state of everyone in this schedule; it is derived again from what is left
+        for (sd_id, _) in &service_dates {
+            conn.execute(
+                "DELETE FROM position_rotation WHERE (person_id, job_id) IN (
+                    SELECT person_id, job_id FROM assignments WHERE service_date_id = ?
+                 )",
+                [sd_id],
+            )?;
+        }
+
         // Delete assignments for each service date
         for (sd_id, _) in &service_dates {
             conn.execute("DELETE FROM assignments WHERE service_date_id = ?", [sd_id])?;
@@ -313,7 +444,10 @@ pub fn delete_schedule(id: String) -> Result<(), String> {
 
         // Delete from assignment_history for this schedule's dates
         for (_, date_str) in &service_dates {
-            conn.execute("DELETE FROM assignment_history WHERE service_date = ?", [date_str])?;
+            conn.execute(
+                "DELETE FROM assignment_history WHERE service_date = ?",
+                [date_str],
+            )?;
         }
 
         // Delete service dates
@@ -341,7 +475,7 @@ pub fn get_fairness_scores(year: i32) -> Result<Vec<FairnessScore>, String> {
              LEFT JOIN assignment_history ah ON p.id = ah.person_id
              WHERE p.active = TRUE
              GROUP BY p.id, p.first_name, p.last_name
-             ORDER BY year_assignments ASC, last_date ASC NULLS FIRST"
+             ORDER BY year_assignments ASC, last_date ASC NULLS FIRST",
         )?;
 
         let mut scores: Vec<FairnessScore> = stmt
@@ -349,9 +483,8 @@ pub fn get_fairness_scores(year: i32) -> Result<Vec<FairnessScore>, String> {
                 let total: i32 = row.get(2)?;
                 let year_count: i32 = row.get(3)?;
                 let last_date_str: Option<String> = row.get(4).ok();
-                let last_date = last_date_str.and_then(|s| {
-                    NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok()
-                });
+                let last_date =
+                    last_date_str.and_then(|s| NaiveDate::parse_from_str(&s, "%Y-%m-%d").ok());
 
                 // Calculate fairness score (lower assignments = higher priority)
                 let fairness = if total == 0 {
@@ -383,7 +516,7 @@ pub fn get_fairness_scores(year: i32) -> Result<Vec<FairnessScore>, String> {
              FROM assignment_history ah
              INNER JOIN jobs j ON ah.job_id = j.id
              WHERE ah.year = ?
-             GROUP BY ah.person_id, ah.job_id, j.name"
+             GROUP BY ah.person_id, ah.job_id, j.name",
         )?;
 
         let job_counts: Vec<(String, String, String, i32)> = job_stmt
@@ -418,9 +551,7 @@ pub fn get_fairness_scores(year: i32) -> Result<Vec<FairnessScore>, String> {
 #[tauri::command]
 pub fn get_schedule_by_month(year: i32, month: i32) -> Result<Option<Schedule>, String> {
     let id_result = with_db(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id FROM schedules WHERE year = ? AND month = ?"
-        )?;
+        let mut stmt = conn.prepare("SELECT id FROM schedules WHERE year = ? AND month = ?")?;
 
         match stmt.query_row(duckdb::params![year, month], |row| row.get::<_, String>(0)) {
             Ok(id) => Ok(Some(id)),
@@ -489,10 +620,10 @@ pub fn get_eligible_people_for_assignment(
         .unwrap_or_else(|_| NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
 
     with_db(|conn| {
-
         // Get all active people
         let mut people_stmt = conn.prepare(
-            "SELECT id, first_name, last_name, preferred_frequency, max_consecutive_weeks, preference_level
+            "SELECT id, first_name, last_name, preferred_frequency, max_consecutive_weeks, preference_level,
+                    min_rest_days, notes, scheduling_notes, first_communion
              FROM people
              WHERE active = TRUE"
         )?;
@@ -511,19 +642,28 @@ pub fn get_eligible_people_for_assignment(
                     max_consecutive_weeks: row.get(4)?,
                     preference_level: row.get(5)?,
                     active: true,
-                    notes: None,
+                    notes: row.get(7)?,
                     created_at: None,
                     updated_at: None,
                     job_ids: Vec::new(),
+                    min_rest_days: row.get(6)?,
+                    scheduling_notes: row.get(8)?,
+                    first_communion: row.get::<_, Option<bool>>(9)?.unwrap_or(false),
                 })
             })?
             .filter_map(|r| r.ok())
             .collect();
 
+        let requires_first_communion: bool = conn
+            .query_row(
+                "SELECT COALESCE(requires_first_communion, FALSE) FROM jobs WHERE id = ?",
+                [&job_id],
+                |row| row.get(0),
+            )
+            .unwrap_or(false);
+
         // Get job assignments for each person
-        let mut job_assign_stmt = conn.prepare(
-            "SELECT person_id, job_id FROM person_jobs"
-        )?;
+        let mut job_assign_stmt = conn.prepare("SELECT person_id, job_id FROM person_jobs")?;
 
         let job_assignments: Vec<(String, String)> = job_assign_stmt
             .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
@@ -533,7 +673,7 @@ pub fn get_eligible_people_for_assignment(
         // Get unavailability records
         let mut unavail_stmt = conn.prepare(
             "SELECT person_id, CAST(start_date AS VARCHAR), CAST(end_date AS VARCHAR)
-             FROM unavailability"
+             FROM unavailability",
         )?;
 
         let unavailability: Vec<(String, NaiveDate, NaiveDate)> = unavail_stmt
@@ -554,28 +694,49 @@ pub fn get_eligible_people_for_assignment(
             "SELECT DISTINCT a.person_id
              FROM assignments a
              INNER JOIN service_dates sd ON a.service_date_id = sd.id
-             WHERE sd.service_date = ?"
+             WHERE sd.service_date = ?",
         )?;
 
         let already_assigned: Vec<String> = assigned_stmt
-            .query_map(duckdb::params![&service_date_str], |row| {
+            .query_map(duckdb::params![&service_date_str], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        // Same date, this job only (for SAME_JOB sibling groups)
+        let mut job_assigned_stmt = conn.prepare(
+            "SELECT DISTINCT a.person_id
+             FROM assignments a
+             INNER JOIN service_dates sd ON a.service_date_id = sd.id
+             WHERE sd.service_date = ? AND a.job_id = ?",
+        )?;
+
+        let assigned_in_job: Vec<String> = job_assigned_stmt
+            .query_map(duckdb::params![&service_date_str, &job_id], |row| {
                 row.get(0)
             })?
             .filter_map(|r| r.ok())
             .collect();
 
-        // Get recent assignments for consecutive weeks check
+        // Get nearby assignments for consecutive weeks and minimum rest checks
         let mut recent_stmt = conn.prepare(
             "SELECT person_id, CAST(service_date AS VARCHAR)
              FROM assignment_history
-             WHERE service_date >= ? AND service_date < ?"
+             WHERE service_date >= ? AND service_date <= ? AND service_date <> ?",
         )?;
 
-        let four_weeks_ago = service_date - chrono::Duration::days(28);
+        let window_days = people
+            .iter()
+            .filter_map(|p| p.min_rest_days)
+            .max()
+            .unwrap_or(0)
+            .max(28) as i64;
+        let window_start = service_date - chrono::Duration::days(window_days);
+        let window_end = service_date + chrono::Duration::days(window_days);
         let recent_assignments: Vec<(String, NaiveDate)> = recent_stmt
             .query_map(
                 duckdb::params![
-                    four_weeks_ago.format("%Y-%m-%d").to_string(),
+                    window_start.format("%Y-%m-%d").to_string(),
+                    window_end.format("%Y-%m-%d").to_string(),
                     service_date_str
                 ],
                 |row| {
@@ -595,17 +756,19 @@ pub fn get_eligible_people_for_assignment(
             "SELECT person_id, COUNT(*) as count
              FROM assignment_history
              WHERE year = ?
-             GROUP BY person_id"
+             GROUP BY person_id",
         )?;
 
         let year_counts: std::collections::HashMap<String, i32> = year_stmt
-            .query_map([year], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i32>(1)?)))?
+            .query_map([year], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i32>(1)?))
+            })?
             .filter_map(|r| r.ok())
             .collect();
 
         // Get sibling groups
         let mut sibling_stmt = conn.prepare(
-            "SELECT id, name, pairing_rule FROM sibling_groups"
+            "SELECT id, name, pairing_rule, COALESCE(pairing_scope, 'SAME_JOB') FROM sibling_groups"
         )?;
 
         let mut sibling_groups: Vec<SiblingGroup> = sibling_stmt
@@ -614,6 +777,7 @@ pub fn get_eligible_people_for_assignment(
                     id: row.get(0)?,
                     name: row.get(1)?,
                     pairing_rule: PairingRule::from_str(&row.get::<_, String>(2)?),
+                    pairing_scope: PairingScope::from_str(&row.get::<_, String>(3)?),
                     created_at: None,
                     updated_at: None,
                     member_ids: Vec::new(),
@@ -623,9 +787,8 @@ pub fn get_eligible_people_for_assignment(
             .collect();
 
         // Get sibling group members
-        let mut member_stmt = conn.prepare(
-            "SELECT sibling_group_id, person_id FROM sibling_group_members"
-        )?;
+        let mut member_stmt =
+            conn.prepare("SELECT sibling_group_id, person_id FROM sibling_group_members")?;
 
         let members: Vec<(String, String)> = member_stmt
             .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
@@ -659,21 +822,28 @@ pub fn get_eligible_people_for_assignment(
 
             let is_already_assigned = already_assigned.contains(&person.id);
 
+            let passes_first_communion_check = !requires_first_communion || person.first_communion;
+
             // Check consecutive weeks
-            let passes_consecutive_check =
-                crate::scheduler::constraints::check_consecutive_weeks(
-                    &person,
-                    service_date,
-                    &recent_assignments,
-                );
+            let passes_consecutive_check = crate::scheduler::constraints::check_consecutive_weeks(
+                &person,
+                service_date,
+                &recent_assignments,
+            );
+
+            let passes_rest_check = crate::scheduler::constraints::check_min_rest_days(
+                &person,
+                service_date,
+                &recent_assignments,
+            );
 
             // Check sibling constraints
-            let sibling_status =
-                crate::scheduler::constraints::check_sibling_constraint(
-                    &person.id,
-                    &already_assigned,
-                    &sibling_groups,
-                );
+            let sibling_status = crate::scheduler::constraints::check_sibling_constraint(
+                &person.id,
+                &assigned_in_job,
+                &already_assigned,
+                &sibling_groups,
+            );
 
             let sibling_status_str = match sibling_status {
                 crate::scheduler::constraints::SiblingConstraintResult::Preferred => "preferred",
@@ -686,12 +856,16 @@ pub fn get_eligible_people_for_assignment(
             // Determine reason if ineligible
             let reason = if !is_qualified {
                 Some("No está asignado a este trabajo".to_string())
+            } else if !passes_first_communion_check {
+                Some("Este trabajo requiere la primera comunión".to_string())
             } else if !is_available {
                 Some("No disponible en esta fecha".to_string())
             } else if is_already_assigned && person.id != current_person_id {
                 Some("Ya asignado en esta fecha".to_string())
             } else if !passes_consecutive_check {
                 Some("Excede semanas consecutivas".to_string())
+            } else if !passes_rest_check {
+                Some("No cumple el descanso mínimo entre asignaciones".to_string())
             } else if sibling_status_str == "forbidden" {
                 Some("Conflicto con regla de hermanos".to_string())
             } else {
@@ -714,10 +888,14 @@ pub fn get_eligible_people_for_assignment(
                 passes_consecutive_check,
                 sibling_status: sibling_status_str.to_string(),
                 assignments_this_year: year_assignments,
+                notes: person.notes,
+                scheduling_notes: person.scheduling_notes,
                 reason_if_ineligible: if !is_qualified
+                    || !passes_first_communion_check
                     || !is_available
                     || effective_already_assigned
                     || !passes_consecutive_check
+                    || !passes_rest_check
                     || sibling_status_str == "forbidden"
                 {
                     reason
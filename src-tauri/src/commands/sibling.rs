@@ -1,6 +1,6 @@
 use crate::db::with_db;
 use crate::models::{
-    CreateSiblingGroupRequest, PairingRule, SiblingGroup, UpdateSiblingGroupRequest,
+    CreateSiblingGroupRequest, PairingRule, PairingScope, SiblingGroup, UpdateSiblingGroupRequest,
 };
 use uuid::Uuid;
 
@@ -8,9 +8,9 @@ use uuid::Uuid;
 pub fn get_all_sibling_groups() -> Result<Vec<SiblingGroup>, String> {
     with_db(|conn| {
         let mut stmt = conn.prepare(
-            "SELECT id, name, pairing_rule
+            "SELECT id, name, pairing_rule, COALESCE(pairing_scope, 'SAME_JOB')
              FROM sibling_groups
-             ORDER BY name"
+             ORDER BY name",
         )?;
 
         let groups: Vec<SiblingGroup> = stmt
@@ -19,6 +19,7 @@ pub fn get_all_sibling_groups() -> Result<Vec<SiblingGroup>, String> {
                     id: row.get(0)?,
                     name: row.get(1)?,
                     pairing_rule: PairingRule::from_str(&row.get::<_, String>(2)?),
+                    pairing_scope: PairingScope::from_str(&row.get::<_, String>(3)?),
                     created_at: None,
                     updated_at: None,
                     member_ids: Vec::new(),
@@ -31,7 +32,7 @@ pub fn get_all_sibling_groups() -> Result<Vec<SiblingGroup>, String> {
         let mut result = Vec::new();
         for mut group in groups {
             let mut member_stmt = conn.prepare(
-                "SELECT person_id FROM sibling_group_members WHERE sibling_group_id = ?"
+                "SELECT person_id FROM sibling_group_members WHERE sibling_group_id = ?",
             )?;
             group.member_ids = member_stmt
                 .query_map([&group.id], |row| row.get(0))?
@@ -48,8 +49,8 @@ pub fn get_all_sibling_groups() -> Result<Vec<SiblingGroup>, String> {
 pub fn get_sibling_group(id: String) -> Result<SiblingGroup, String> {
     with_db(|conn| {
         let mut stmt = conn.prepare(
-            "SELECT id, name, pairing_rule
-             FROM sibling_groups WHERE id = ?"
+            "SELECT id, name, pairing_rule, COALESCE(pairing_scope, 'SAME_JOB')
+             FROM sibling_groups WHERE id = ?",
         )?;
 
         let mut group: SiblingGroup = stmt.query_row([&id], |row| {
@@ -57,15 +58,15 @@ pub fn get_sibling_group(id: String) -> Result<SiblingGroup, String> {
                 id: row.get(0)?,
                 name: row.get(1)?,
                 pairing_rule: PairingRule::from_str(&row.get::<_, String>(2)?),
+                pairing_scope: PairingScope::from_str(&row.get::<_, String>(3)?),
                 created_at: None,
                 updated_at: None,
                 member_ids: Vec::new(),
             })
         })?;
 
-        let mut member_stmt = conn.prepare(
-            "SELECT person_id FROM sibling_group_members WHERE sibling_group_id = ?"
-        )?;
+        let mut member_stmt =
+            conn.prepare("SELECT person_id FROM sibling_group_members WHERE sibling_group_id = ?")?;
         group.member_ids = member_stmt
             .query_map([&id], |row| row.get(0))?
             .filter_map(|r| r.ok())
@@ -81,8 +82,13 @@ pub fn create_sibling_group(request: CreateSiblingGroupRequest) -> Result<Siblin
 
     with_db(|conn| {
         conn.execute(
-            "INSERT INTO sibling_groups (id, name, pairing_rule) VALUES (?, ?, ?)",
-            duckdb::params![&id, &request.name, request.pairing_rule.to_string()],
+            "INSERT INTO sibling_groups (id, name, pairing_rule, pairing_scope) VALUES (?, ?, ?, ?)",
+            duckdb::params![
+                &id,
+                &request.name,
+                request.pairing_rule.to_string(),
+                request.pairing_scope.to_string()
+            ],
         )?;
 
         // Add members
@@ -105,10 +111,14 @@ pub fn update_sibling_group(request: UpdateSiblingGroupRequest) -> Result<Siblin
     with_db(|conn| {
         let current = {
             let mut stmt = conn.prepare(
-                "SELECT name, pairing_rule FROM sibling_groups WHERE id = ?"
+                "SELECT name, pairing_rule, COALESCE(pairing_scope, 'SAME_JOB') FROM sibling_groups WHERE id = ?"
             )?;
             stmt.query_row([&request.id], |row| {
-                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
             })?
         };
 
@@ -117,11 +127,15 @@ pub fn update_sibling_group(request: UpdateSiblingGroupRequest) -> Result<Siblin
             .pairing_rule
             .map(|r| r.to_string())
             .unwrap_or(current.1);
+        let pairing_scope = request
+            .pairing_scope
+            .map(|s| s.to_string())
+            .unwrap_or(current.2);
 
         conn.execute(
-            "UPDATE sibling_groups SET name = ?, pairing_rule = ?, updated_at = CURRENT_TIMESTAMP
+            "UPDATE sibling_groups SET name = ?, pairing_rule = ?, pairing_scope = ?, updated_at = CURRENT_TIMESTAMP
              WHERE id = ?",
-            duckdb::params![name, pairing_rule, &request.id],
+            duckdb::params![name, pairing_rule, pairing_scope, &request.id],
         )?;
 
         // Update members if provided
@@ -157,11 +171,11 @@ pub fn delete_sibling_group(id: String) -> Result<(), String> {
 pub fn get_person_sibling_groups(person_id: String) -> Result<Vec<SiblingGroup>, String> {
     with_db(|conn| {
         let mut stmt = conn.prepare(
-            "SELECT sg.id, sg.name, sg.pairing_rule
+            "SELECT sg.id, sg.name, sg.pairing_rule, COALESCE(sg.pairing_scope, 'SAME_JOB')
              FROM sibling_groups sg
              INNER JOIN sibling_group_members sgm ON sg.id = sgm.sibling_group_id
              WHERE sgm.person_id = ?
-             ORDER BY sg.name"
+             ORDER BY sg.name",
         )?;
 
         let groups: Vec<SiblingGroup> = stmt
@@ -170,6 +184,7 @@ pub fn get_person_sibling_groups(person_id: String) -> Result<Vec<SiblingGroup>,
                     id: row.get(0)?,
                     name: row.get(1)?,
                     pairing_rule: PairingRule::from_str(&row.get::<_, String>(2)?),
+                    pairing_scope: PairingScope::from_str(&row.get::<_, String>(3)?),
                     created_at: None,
                     updated_at: None,
                     member_ids: Vec::new(),
@@ -182,7 +197,7 @@ pub fn get_person_sibling_groups(person_id: String) -> Result<Vec<SiblingGroup>,
         let mut result = Vec::new();
         for mut group in groups {
             let mut member_stmt = conn.prepare(
-                "SELECT person_id FROM sibling_group_members WHERE sibling_group_id = ?"
+                "SELECT person_id FROM sibling_group_members WHERE sibling_group_id = ?",
             )?;
             group.member_ids = member_stmt
                 .query_map([&group.id], |row| row.get(0))?
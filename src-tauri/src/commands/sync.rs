@@ -0,0 +1,39 @@
+use super::people::{get_all_people, get_pending_person_deletions, mark_person_deletions_synced};
+use crate::cloud::{CloudClient, OrphanUser, SyncDeletedPeopleResponse};
+
+/// Send the queued person deletions to the cloud at `api_url` and mark those it is done with
+/// (deleted there, or already gone) as synced. Nothing is marked when the request fails, so
+/// the next sync sends them again.
+#[tauri::command]
+pub async fn sync_person_deletions(
+    api_url: String,
+    token: String,
+) -> Result<SyncDeletedPeopleResponse, String> {
+    let person_ids = get_pending_person_deletions()?;
+    if person_ids.is_empty() {
+        return Ok(SyncDeletedPeopleResponse::default());
+    }
+    let result = CloudClient::new(&api_url, &token)
+        .sync_deleted_people(&person_ids)
+        .await
+        .map_err(|e| e.to_string())?;
+    mark_person_deletions_synced(
+        result
+            .deleted_people
+            .iter()
+            .chain(&result.not_found)
+            .cloned()
+            .collect(),
+    )?;
+    Ok(result)
+}
+
+/// Cloud servidor accounts whose person is not in the local database
+#[tauri::command]
+pub async fn find_orphan_users(api_url: String, token: String) -> Result<Vec<OrphanUser>, String> {
+    let person_ids: Vec<String> = get_all_people()?.into_iter().map(|p| p.id).collect();
+    CloudClient::new(&api_url, &token)
+        .reconcile_users(&person_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,15 +1,15 @@
-use uuid::Uuid;
-use std::collections::HashMap;
-use chrono::Datelike;
 use crate::db::with_db;
 use crate::models::GenerateScheduleRequest;
 use crate::scheduler::ScheduleGenerator;
+use chrono::Datelike;
+use std::collections::HashMap;
+use uuid::Uuid;
 
 #[tauri::command]
 pub fn import_test_data(csv_path: String) -> Result<String, String> {
     // Read CSV file
-    let csv_content = std::fs::read_to_string(&csv_path)
-        .map_err(|e| format!("Failed to read CSV: {}", e))?;
+    let csv_content =
+        std::fs::read_to_string(&csv_path).map_err(|e| format!("Failed to read CSV: {}", e))?;
 
     // First, ensure jobs exist
     let job_ids = ensure_jobs_exist()?;
@@ -79,9 +79,8 @@ pub fn generate_year_schedules(year: i32) -> Result<String, String> {
     for month in 1..=12 {
         // Check if schedule already exists
         let exists = with_db(|conn| {
-            let mut stmt = conn.prepare(
-                "SELECT COUNT(*) FROM schedules WHERE year = ? AND month = ?"
-            )?;
+            let mut stmt =
+                conn.prepare("SELECT COUNT(*) FROM schedules WHERE year = ? AND month = ?")?;
             let count: i64 = stmt.query_row(duckdb::params![year, month], |row| row.get(0))?;
             Ok(count > 0)
         })?;
@@ -112,7 +111,11 @@ pub fn generate_year_schedules(year: i32) -> Result<String, String> {
             for service_date in &schedule.service_dates {
                 conn.execute(
                     "INSERT INTO service_dates (id, schedule_id, service_date) VALUES (?, ?, ?)",
-                    duckdb::params![service_date.id, schedule.id, service_date.service_date.to_string()],
+                    duckdb::params![
+                        service_date.id,
+                        schedule.id,
+                        service_date.service_date.to_string()
+                    ],
                 )?;
 
                 for assignment in &service_date.assignments {
@@ -154,7 +157,10 @@ pub fn generate_year_schedules(year: i32) -> Result<String, String> {
         generated_count += 1;
     }
 
-    Ok(format!("Generated {} schedules for {}", generated_count, year))
+    Ok(format!(
+        "Generated {} schedules for {}",
+        generated_count, year
+    ))
 }
 
 fn ensure_jobs_exist() -> Result<HashMap<String, String>, String> {
@@ -11,7 +11,7 @@ pub fn get_all_unavailability() -> Result<Vec<Unavailability>, String> {
                     p.first_name || ' ' || p.last_name as person_name
              FROM unavailability u
              INNER JOIN people p ON u.person_id = p.id
-             ORDER BY u.start_date DESC"
+             ORDER BY u.start_date DESC",
         )?;
 
         let records: Vec<Unavailability> = stmt
@@ -42,7 +42,7 @@ pub fn get_person_unavailability(person_id: String) -> Result<Vec<Unavailability
                     reason, recurring
              FROM unavailability
              WHERE person_id = ?
-             ORDER BY start_date DESC"
+             ORDER BY start_date DESC",
         )?;
 
         let records: Vec<Unavailability> = stmt
@@ -66,7 +66,9 @@ pub fn get_person_unavailability(person_id: String) -> Result<Vec<Unavailability
 }
 
 #[tauri::command]
-pub fn create_unavailability(request: CreateUnavailabilityRequest) -> Result<Unavailability, String> {
+pub fn create_unavailability(
+    request: CreateUnavailabilityRequest,
+) -> Result<Unavailability, String> {
     let id = Uuid::new_v4().to_string();
 
     with_db(|conn| {
@@ -97,7 +99,7 @@ pub fn get_unavailability(id: String) -> Result<Unavailability, String> {
                     p.first_name || ' ' || p.last_name as person_name
              FROM unavailability u
              INNER JOIN people p ON u.person_id = p.id
-             WHERE u.id = ?"
+             WHERE u.id = ?",
         )?;
 
         let record = stmt.query_row([&id], |row| {
@@ -118,12 +120,14 @@ pub fn get_unavailability(id: String) -> Result<Unavailability, String> {
 }
 
 #[tauri::command]
-pub fn update_unavailability(request: UpdateUnavailabilityRequest) -> Result<Unavailability, String> {
+pub fn update_unavailability(
+    request: UpdateUnavailabilityRequest,
+) -> Result<Unavailability, String> {
     with_db(|conn| {
         let current = {
             let mut stmt = conn.prepare(
                 "SELECT CAST(start_date AS VARCHAR), CAST(end_date AS VARCHAR), reason, recurring
-                 FROM unavailability WHERE id = ?"
+                 FROM unavailability WHERE id = ?",
             )?;
             stmt.query_row([&request.id], |row| {
                 Ok((
@@ -165,7 +169,7 @@ pub fn check_availability(person_id: String, date: String) -> Result<bool, Strin
     with_db(|conn| {
         let mut stmt = conn.prepare(
             "SELECT COUNT(*) FROM unavailability
-             WHERE person_id = ? AND ? BETWEEN start_date AND end_date"
+             WHERE person_id = ? AND ? BETWEEN start_date AND end_date",
         )?;
 
         let count: i64 = stmt.query_row(duckdb::params![&person_id, &date], |row| row.get(0))?;
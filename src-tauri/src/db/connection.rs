@@ -41,13 +41,55 @@ fn run_migrations(conn: &Connection) -> DuckResult<()> {
         "CREATE TABLE IF NOT EXISTS _migrations (
             name VARCHAR PRIMARY KEY,
             applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        );"
+        );",
     )?;
 
     // Define migrations
     let migrations = [
-        ("001_initial_schema", include_str!("../../../migrations/001_initial_schema.sql")),
-        ("002_job_positions", include_str!("../../../migrations/002_job_positions.sql")),
+        (
+            "001_initial_schema",
+            include_str!("../../../migrations/001_initial_schema.sql"),
+        ),
+        (
+            "002_job_positions",
+            include_str!("../../../migrations/002_job_positions.sql"),
+        ),
+        (
+            "003_min_rest_days",
+            include_str!("../../../migrations/003_min_rest_days.sql"),
+        ),
+        (
+            "004_scheduling_notes",
+            include_str!("../../../migrations/004_scheduling_notes.sql"),
+        ),
+        (
+            "005_first_communion",
+            include_str!("../../../migrations/005_first_communion.sql"),
+        ),
+        (
+            "006_position_rotation",
+            include_str!("../../../migrations/006_position_rotation.sql"),
+        ),
+        (
+            "007_sibling_pairing_scope",
+            include_str!("../../../migrations/007_sibling_pairing_scope.sql"),
+        ),
+        (
+            "008_app_settings",
+            include_str!("../../../migrations/008_app_settings.sql"),
+        ),
+        (
+            "009_deleted_people",
+            include_str!("../../../migrations/009_deleted_people.sql"),
+        ),
+        (
+            "010_assignment_notes",
+            include_str!("../../../migrations/010_assignment_notes.sql"),
+        ),
+        (
+            "011_export_templates",
+            include_str!("../../../migrations/011_export_templates.sql"),
+        ),
     ];
 
     for (name, sql) in migrations {
@@ -57,10 +99,7 @@ fn run_migrations(conn: &Connection) -> DuckResult<()> {
 
         if count == 0 {
             conn.execute_batch(sql)?;
-            conn.execute(
-                "INSERT INTO _migrations (name) VALUES (?)",
-                [name],
-            )?;
+            conn.execute("INSERT INTO _migrations (name) VALUES (?)", [name])?;
         }
     }
 
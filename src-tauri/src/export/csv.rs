@@ -0,0 +1,174 @@
+use super::grid::{schedule_grid, ExportLayout};
+use super::template::{template_header, template_rows};
+use crate::commands::get_schedule;
+use crate::db::with_db;
+use crate::models::{ExportTemplate, Schedule};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Same columns as the web export (`GET /api/schedules/{id}/export?format=csv`)
+const HEADER: [&str; 5] = ["Fecha", "Ministerio", "Posición", "Servidor", "Teléfono"];
+
+/// Quote a field if it contains a separator, quote or line break
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// One line per assignment: dates as dd/mm/yyyy (what a Spanish-locale sheet parses as a date)
+/// and the position's name or its number
+pub fn schedule_to_csv(schedule: &Schedule, phones: &HashMap<String, String>) -> String {
+    let mut out = HEADER.join(",");
+    out.push('\n');
+    for service_date in &schedule.service_dates {
+        for assignment in &service_date.assignments {
+            let fields = [
+                service_date.service_date.format("%d/%m/%Y").to_string(),
+                assignment
+                    .job_name
+                    .clone()
+                    .unwrap_or_else(|| assignment.job_id.clone()),
+                assignment
+                    .position_name
+                    .clone()
+                    .unwrap_or_else(|| assignment.position.to_string()),
+                assignment.person_name.clone().unwrap_or_default(),
+                phones
+                    .get(&assignment.person_id)
+                    .cloned()
+                    .unwrap_or_default(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|f| escape(f)).collect();
+            out.push_str(&fields.join(","));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// The grid layout: a header of dates, then one line per job position
+pub fn schedule_grid_to_csv(schedule: &Schedule) -> String {
+    let (dates, rows) = schedule_grid(schedule);
+    let mut out = std::iter::once("Ministerio")
+        .chain(dates.iter().map(String::as_str))
+        .map(escape)
+        .collect::<Vec<_>>()
+        .join(",");
+    out.push('\n');
+    for row in rows {
+        let fields: Vec<String> = std::iter::once(&row.label)
+            .chain(&row.cells)
+            .map(|f| escape(f))
+            .collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// A template export: the template's column headers, then its rows
+pub fn table_to_csv(header: &[String], rows: &[Vec<String>]) -> String {
+    let mut out = String::new();
+    for fields in std::iter::once(header).chain(rows.iter().map(Vec::as_slice)) {
+        let fields: Vec<String> = fields.iter().map(|f| escape(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Person id to phone, for the columns that show it
+pub(crate) fn load_phones() -> Result<HashMap<String, String>, String> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT id, phone FROM people WHERE phone IS NOT NULL")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    })
+}
+
+/// With a template, its columns and order replace the layout
+pub fn export_schedule_to_csv(
+    schedule_id: &str,
+    output_path: &PathBuf,
+    layout: ExportLayout,
+    template: Option<&ExportTemplate>,
+) -> Result<(), String> {
+    let schedule = get_schedule(schedule_id.to_string())?;
+    if let Some(template) = template {
+        let rows = template_rows(&schedule, template, &load_phones()?);
+        return std::fs::write(output_path, table_to_csv(&template_header(template), &rows))
+            .map_err(|e| e.to_string());
+    }
+    if layout == ExportLayout::Grid {
+        return std::fs::write(output_path, schedule_grid_to_csv(&schedule))
+            .map_err(|e| e.to_string());
+    }
+
+    let phones = load_phones()?;
+    std::fs::write(output_path, schedule_to_csv(&schedule, &phones)).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Assignment, ScheduleStatus, ServiceDate};
+    use chrono::NaiveDate;
+
+    fn assignment(person_id: &str, person_name: &str, position_name: Option<&str>) -> Assignment {
+        Assignment {
+            id: person_id.to_string(),
+            service_date_id: "sd1".to_string(),
+            job_id: "lectores".to_string(),
+            person_id: person_id.to_string(),
+            position: 2,
+            manual_override: false,
+            created_at: None,
+            updated_at: None,
+            person_name: Some(person_name.to_string()),
+            job_name: Some("Lectores".to_string()),
+            position_name: position_name.map(str::to_string),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn rows_carry_the_position_and_phone() {
+        let schedule = Schedule {
+            id: "s1".to_string(),
+            name: "Noviembre 2026".to_string(),
+            year: 2026,
+            month: 11,
+            status: ScheduleStatus::Draft,
+            created_at: None,
+            updated_at: None,
+            published_at: None,
+            service_dates: vec![ServiceDate {
+                id: "sd1".to_string(),
+                schedule_id: "s1".to_string(),
+                service_date: NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+                notes: None,
+                created_at: None,
+                assignments: vec![
+                    assignment("p1", "Ana López", Some("Salmo")),
+                    assignment("p2", "Luis \"Lucho\" Pérez", None),
+                ],
+            }],
+        };
+        let phones = HashMap::from([("p1".to_string(), "5555-1234".to_string())]);
+
+        assert_eq!(
+            schedule_to_csv(&schedule, &phones),
+            "Fecha,Ministerio,Posición,Servidor,Teléfono\n\
+             01/11/2026,Lectores,Salmo,Ana López,5555-1234\n\
+             01/11/2026,Lectores,2,\"Luis \"\"Lucho\"\" Pérez\",\n"
+        );
+    }
+}
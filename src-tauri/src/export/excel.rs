@@ -1,52 +1,247 @@
+use super::csv::load_phones;
+use super::grid::{schedule_grid, ExportLayout};
+use super::list::{job_fill, job_lines};
+use super::template::{template_header, template_rows};
+use super::totals::{month_sheet_names, person_totals};
 use crate::commands::get_schedule;
-use xlsxwriter::Workbook;
+use crate::db::with_db;
+use crate::models::{ExportTemplate, Schedule};
+use crate::scheduler::naming;
+use std::collections::HashMap;
 use std::path::PathBuf;
+use xlsxwriter::format::{Format, FormatColor};
+use xlsxwriter::{Workbook, Worksheet};
 
-pub fn export_schedule_to_excel(schedule_id: &str, output_path: &PathBuf) -> Result<(), String> {
+/// With a template, the sheet is a table of its columns instead of the layout
+pub fn export_schedule_to_excel(
+    schedule_id: &str,
+    output_path: &PathBuf,
+    layout: ExportLayout,
+    template: Option<&ExportTemplate>,
+) -> Result<(), String> {
     let schedule = get_schedule(schedule_id.to_string())?;
 
-    let workbook = Workbook::new(output_path.to_str().ok_or("Invalid path")?)
+    let workbook =
+        Workbook::new(output_path.to_str().ok_or("Invalid path")?).map_err(|e| e.to_string())?;
+
+    let mut sheet = workbook
+        .add_worksheet(Some("Horario"))
         .map_err(|e| e.to_string())?;
+    match template {
+        Some(template) => write_template(&mut sheet, &schedule, template)?,
+        None => {
+            let job_colors = load_job_colors()?;
+            write_schedule(&mut sheet, &schedule, layout, &job_colors)?;
+        }
+    }
+
+    workbook.close().map_err(|e| e.to_string())?;
 
-    let mut sheet = workbook.add_worksheet(Some("Schedule"))
+    Ok(())
+}
+
+/// Several schedules (a whole year, say) in one workbook: a Resumen sheet with how many times
+/// each person served per month, then one sheet per schedule in the list layout
+pub fn export_schedules_to_excel(
+    schedule_ids: &[String],
+    output_path: &PathBuf,
+) -> Result<(), String> {
+    if schedule_ids.is_empty() {
+        return Err("No schedules to export".to_string());
+    }
+    let mut schedules = schedule_ids
+        .iter()
+        .map(|id| get_schedule(id.clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+    schedules.sort_by_key(|s| (s.year, s.month));
+    let names = month_sheet_names(&schedules);
+    let job_colors = load_job_colors()?;
+
+    let workbook =
+        Workbook::new(output_path.to_str().ok_or("Invalid path")?).map_err(|e| e.to_string())?;
+
+    let mut summary = workbook
+        .add_worksheet(Some("Resumen"))
+        .map_err(|e| e.to_string())?;
+    summary
+        .set_column(0, 0, 28.0, None)
+        .map_err(|e| e.to_string())?;
+    summary
+        .set_column(1, names.len() as u16 + 1, 14.0, None)
+        .map_err(|e| e.to_string())?;
+    summary
+        .write_string(0, 0, "Servidor", None)
+        .map_err(|e| e.to_string())?;
+    for (i, name) in names.iter().enumerate() {
+        summary
+            .write_string(0, (i + 1) as u16, name, None)
+            .map_err(|e| e.to_string())?;
+    }
+    summary
+        .write_string(0, names.len() as u16 + 1, "Total", None)
         .map_err(|e| e.to_string())?;
+    for (r, totals) in person_totals(&schedules).iter().enumerate() {
+        let row = 1 + r as u32;
+        summary
+            .write_string(row, 0, &totals.name, None)
+            .map_err(|e| e.to_string())?;
+        for (i, count) in totals.counts.iter().enumerate() {
+            summary
+                .write_number(row, (i + 1) as u16, *count as f64, None)
+                .map_err(|e| e.to_string())?;
+        }
+        summary
+            .write_number(row, names.len() as u16 + 1, totals.total as f64, None)
+            .map_err(|e| e.to_string())?;
+    }
+
+    for (schedule, name) in schedules.iter().zip(&names) {
+        let mut sheet = workbook
+            .add_worksheet(Some(name))
+            .map_err(|e| e.to_string())?;
+        write_schedule(&mut sheet, schedule, ExportLayout::List, &job_colors)?;
+    }
+
+    workbook.close().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Job id to its "#RRGGBB" color, for the job name fills
+fn load_job_colors() -> Result<HashMap<String, String>, String> {
+    with_db(|conn| {
+        let mut stmt = conn.prepare("SELECT id, color FROM jobs")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    })
+}
+
+fn write_template(
+    sheet: &mut Worksheet,
+    schedule: &Schedule,
+    template: &ExportTemplate,
+) -> Result<(), String> {
+    let rows = template_rows(schedule, template, &load_phones()?);
 
-    // Set column widths
-    sheet.set_column(0, 0, 15.0, None).map_err(|e| e.to_string())?;
-    sheet.set_column(1, 10, 20.0, None).map_err(|e| e.to_string())?;
+    let mut title_format = Format::new();
+    title_format.set_bold().set_font_size(14.0);
+    let mut header_format = Format::new();
+    header_format.set_bold();
+
+    sheet
+        .set_column(0, template.fields.len() as u16, 20.0, None)
+        .map_err(|e| e.to_string())?;
+    let title = format!("Horario: {}", schedule.name);
+    sheet
+        .write_string(0, 0, &title, Some(&title_format))
+        .map_err(|e| e.to_string())?;
+    for (i, header) in template_header(template).iter().enumerate() {
+        sheet
+            .write_string(2, i as u16, header, Some(&header_format))
+            .map_err(|e| e.to_string())?;
+    }
+    for (r, fields) in rows.iter().enumerate() {
+        for (i, value) in fields.iter().enumerate() {
+            sheet
+                .write_string(3 + r as u32, i as u16, value, None)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+fn write_schedule(
+    sheet: &mut Worksheet,
+    schedule: &Schedule,
+    layout: ExportLayout,
+    job_colors: &HashMap<String, String>,
+) -> Result<(), String> {
+    // Set column widths; the first fits the long date headers
+    sheet
+        .set_column(0, 0, 32.0, None)
+        .map_err(|e| e.to_string())?;
+    sheet
+        .set_column(1, 10, 20.0, None)
+        .map_err(|e| e.to_string())?;
 
     // Write title
-    let title = format!("{} - Schedule", schedule.name);
-    sheet.write_string(0, 0, &title, None)
+    let mut title_format = Format::new();
+    title_format.set_bold().set_font_size(14.0);
+    let title = format!("Horario: {}", schedule.name);
+    sheet
+        .write_string(0, 0, &title, Some(&title_format))
         .map_err(|e| e.to_string())?;
 
+    if layout == ExportLayout::Grid {
+        // Bulletin layout: a header row of dates, then one row per job position
+        let (dates, rows) = schedule_grid(schedule);
+        sheet
+            .set_column(0, 0, 28.0, None)
+            .map_err(|e| e.to_string())?;
+        let mut header_format = Format::new();
+        header_format.set_bold();
+        sheet
+            .write_string(2, 0, "Ministerio", Some(&header_format))
+            .map_err(|e| e.to_string())?;
+        for (i, date) in dates.iter().enumerate() {
+            sheet
+                .write_string(2, (i + 1) as u16, date, Some(&header_format))
+                .map_err(|e| e.to_string())?;
+        }
+        for (r, grid_row) in rows.iter().enumerate() {
+            let row = 3 + r as u32;
+            sheet
+                .write_string(row, 0, &grid_row.label, None)
+                .map_err(|e| e.to_string())?;
+            for (i, cell) in grid_row.cells.iter().enumerate() {
+                sheet
+                    .write_string(row, (i + 1) as u16, cell, None)
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        return Ok(());
+    }
+
+    let mut date_format = Format::new();
+    date_format.set_bold();
+
     let mut row = 2u32;
 
-    // Group assignments by job for each date
     for service_date in &schedule.service_dates {
         // Write date header
-        let date_str = service_date.service_date.format("%B %d, %Y (%A)").to_string();
-        sheet.write_string(row, 0, &date_str, None)
+        let date_str = naming::long_date(service_date.service_date);
+        sheet
+            .write_string(row, 0, &date_str, Some(&date_format))
             .map_err(|e| e.to_string())?;
-        row += 1;
-
-        // Group by job
-        let mut jobs_map: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
-
-        for assignment in &service_date.assignments {
-            let job_name = assignment.job_name.clone().unwrap_or_else(|| assignment.job_id.clone());
-            let person_name = assignment.person_name.clone().unwrap_or_else(|| assignment.person_id.clone());
-
-            jobs_map.entry(job_name).or_default().push(person_name);
+        if let Some(notes) = &service_date.notes {
+            sheet
+                .write_string(row, 1, notes, None)
+                .map_err(|e| e.to_string())?;
         }
+        row += 1;
 
-        // Write each job's assignments
-        for (job_name, people) in &jobs_map {
-            sheet.write_string(row, 0, job_name, None)
+        // One row per job, its name filled with the job's color
+        for line in job_lines(service_date) {
+            let fill = job_colors
+                .get(&line.job_id)
+                .and_then(|c| job_fill(c))
+                .map(|rgb| {
+                    let mut format = Format::new();
+                    format.set_bg_color(FormatColor::Custom(rgb));
+                    format
+                });
+            sheet
+                .write_string(row, 0, &line.job_name, fill.as_ref())
                 .map_err(|e| e.to_string())?;
 
-            for (i, person) in people.iter().enumerate() {
-                sheet.write_string(row, (i + 1) as u16, person, None)
+            for (i, person) in line.people.iter().enumerate() {
+                sheet
+                    .write_string(row, (i + 1) as u16, person, None)
                     .map_err(|e| e.to_string())?;
             }
             row += 1;
@@ -55,7 +250,5 @@ pub fn export_schedule_to_excel(schedule_id: &str, output_path: &PathBuf) -> Res
         row += 1; // Empty row between dates
     }
 
-    workbook.close().map_err(|e| e.to_string())?;
-
     Ok(())
 }
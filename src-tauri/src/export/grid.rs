@@ -0,0 +1,140 @@
+use crate::models::Schedule;
+use std::collections::BTreeMap;
+
+/// How an export lays out a schedule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportLayout {
+    /// One block (Excel) or line (CSV) per service date, the default
+    List,
+    /// Service dates as columns and job positions as rows, as the parish bulletin prints it
+    Grid,
+}
+
+impl ExportLayout {
+    /// "list" or "grid"; None is the list layout
+    pub fn parse(layout: Option<&str>) -> Result<Self, String> {
+        match layout {
+            None | Some("list") => Ok(Self::List),
+            Some("grid") => Ok(Self::Grid),
+            Some(other) => Err(format!("Unknown layout '{}', use list or grid", other)),
+        }
+    }
+}
+
+/// A row of the grid: the job position and, per service date, who serves it
+#[derive(Debug, PartialEq)]
+pub struct GridRow {
+    pub label: String,
+    pub cells: Vec<String>,
+}
+
+/// The dates of the header (dd/mm) and one row per job position. Positions are told apart by
+/// number; the label uses the position's name when it has one.
+pub fn schedule_grid(schedule: &Schedule) -> (Vec<String>, Vec<GridRow>) {
+    let dates: Vec<String> = schedule
+        .service_dates
+        .iter()
+        .map(|sd| sd.service_date.format("%d/%m").to_string())
+        .collect();
+
+    let mut rows: BTreeMap<(String, String, i32), GridRow> = BTreeMap::new();
+    for (column, service_date) in schedule.service_dates.iter().enumerate() {
+        for assignment in &service_date.assignments {
+            let job_name = assignment
+                .job_name
+                .clone()
+                .unwrap_or_else(|| assignment.job_id.clone());
+            let key = (
+                job_name.clone(),
+                assignment.job_id.clone(),
+                assignment.position,
+            );
+            let row = rows.entry(key).or_insert_with(|| GridRow {
+                label: match &assignment.position_name {
+                    Some(name) => format!("{} - {}", job_name, name),
+                    None => format!("{} {}", job_name, assignment.position),
+                },
+                cells: vec![String::new(); dates.len()],
+            });
+            let person = assignment.person_name.clone().unwrap_or_default();
+            let cell = &mut row.cells[column];
+            if !cell.is_empty() {
+                cell.push_str(" / ");
+            }
+            cell.push_str(&person);
+        }
+    }
+
+    (dates, rows.into_values().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Assignment, ScheduleStatus, ServiceDate};
+    use chrono::NaiveDate;
+
+    fn assignment(position: i32, person: &str) -> Assignment {
+        Assignment {
+            id: format!("{}-{}", position, person),
+            service_date_id: String::new(),
+            job_id: "lectores".to_string(),
+            person_id: person.to_string(),
+            position,
+            manual_override: false,
+            created_at: None,
+            updated_at: None,
+            person_name: Some(person.to_string()),
+            job_name: Some("Lectores".to_string()),
+            position_name: (position == 1).then(|| "Primera lectura".to_string()),
+            notes: None,
+        }
+    }
+
+    fn service_date(day: u32, assignments: Vec<Assignment>) -> ServiceDate {
+        ServiceDate {
+            id: day.to_string(),
+            schedule_id: "s1".to_string(),
+            service_date: NaiveDate::from_ymd_opt(2026, 11, day).unwrap(),
+            notes: None,
+            created_at: None,
+            assignments,
+        }
+    }
+
+    #[test]
+    fn dates_become_columns_and_positions_rows() {
+        let schedule = Schedule {
+            id: "s1".to_string(),
+            name: "Noviembre 2026".to_string(),
+            year: 2026,
+            month: 11,
+            status: ScheduleStatus::Draft,
+            created_at: None,
+            updated_at: None,
+            published_at: None,
+            service_dates: vec![
+                service_date(1, vec![assignment(1, "Ana")]),
+                service_date(8, vec![assignment(2, "Marta"), assignment(1, "Luis")]),
+            ],
+        };
+
+        let (dates, rows) = schedule_grid(&schedule);
+        assert_eq!(dates, vec!["01/11", "08/11"]);
+        assert_eq!(
+            rows,
+            vec![
+                GridRow {
+                    label: "Lectores - Primera lectura".to_string(),
+                    cells: vec!["Ana".to_string(), "Luis".to_string()],
+                },
+                GridRow {
+                    label: "Lectores 2".to_string(),
+                    cells: vec![String::new(), "Marta".to_string()],
+                },
+            ]
+        );
+        assert_eq!(ExportLayout::parse(None), Ok(ExportLayout::List));
+        assert!(ExportLayout::parse(Some("poster")).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use crate::models::ServiceDate;
+use std::collections::BTreeMap;
+
+/// A job's line under a service date in the list layout
+#[derive(Debug, PartialEq)]
+pub struct JobLine {
+    pub job_id: String,
+    pub job_name: String,
+    /// "Salmo: Ana López (notes)", in position order
+    pub people: Vec<String>,
+}
+
+/// The service date's assignments grouped by job, in job name order (as the jobs screen lists
+/// them) so every date reads the same way down the sheet
+pub fn job_lines(service_date: &ServiceDate) -> Vec<JobLine> {
+    let mut jobs: BTreeMap<(String, String), Vec<(i32, String)>> = BTreeMap::new();
+    for assignment in &service_date.assignments {
+        let job_name = assignment
+            .job_name
+            .clone()
+            .unwrap_or_else(|| assignment.job_id.clone());
+        let person_name = assignment
+            .person_name
+            .clone()
+            .unwrap_or_else(|| assignment.person_id.clone());
+        let mut cell = match &assignment.position_name {
+            Some(position) => format!("{}: {}", position, person_name),
+            None => person_name,
+        };
+        if let Some(notes) = &assignment.notes {
+            cell.push_str(&format!(" ({})", notes));
+        }
+        jobs.entry((job_name, assignment.job_id.clone()))
+            .or_default()
+            .push((assignment.position, cell));
+    }
+
+    jobs.into_iter()
+        .map(|((job_name, job_id), mut people)| {
+            people.sort_by_key(|(position, _)| *position);
+            JobLine {
+                job_id,
+                job_name,
+                people: people.into_iter().map(|(_, cell)| cell).collect(),
+            }
+        })
+        .collect()
+}
+
+/// A job's "#RRGGBB" color lightened three quarters of the way to white, so black text stays
+/// readable on it as a cell fill
+pub fn job_fill(color: &str) -> Option<u32> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    let tint = |shift: u32| {
+        let channel = (rgb >> shift) & 0xFF;
+        (channel + (0xFF - channel) * 3 / 4) << shift
+    };
+    Some(tint(16) | tint(8) | tint(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Assignment;
+    use chrono::NaiveDate;
+
+    fn assignment(
+        job: &str,
+        position: i32,
+        position_name: Option<&str>,
+        person: &str,
+    ) -> Assignment {
+        Assignment {
+            id: format!("{}-{}", job, position),
+            service_date_id: "sd1".to_string(),
+            job_id: job.to_lowercase(),
+            person_id: person.to_string(),
+            position,
+            manual_override: false,
+            created_at: None,
+            updated_at: None,
+            person_name: Some(person.to_string()),
+            job_name: Some(job.to_string()),
+            position_name: position_name.map(str::to_string),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn jobs_sort_by_name_and_people_by_position() {
+        let mut late = assignment("Lectores", 1, Some("Primera lectura"), "Luis");
+        late.notes = Some("llega tarde".to_string());
+        let service_date = ServiceDate {
+            id: "sd1".to_string(),
+            schedule_id: "s1".to_string(),
+            service_date: NaiveDate::from_ymd_opt(2026, 11, 1).unwrap(),
+            notes: None,
+            created_at: None,
+            assignments: vec![
+                assignment("Monaguillos", 1, None, "Marta"),
+                assignment("Lectores", 2, Some("Salmo"), "Ana"),
+                late,
+            ],
+        };
+
+        assert_eq!(
+            job_lines(&service_date),
+            vec![
+                JobLine {
+                    job_id: "lectores".to_string(),
+                    job_name: "Lectores".to_string(),
+                    people: vec![
+                        "Primera lectura: Luis (llega tarde)".to_string(),
+                        "Salmo: Ana".to_string(),
+                    ],
+                },
+                JobLine {
+                    job_id: "monaguillos".to_string(),
+                    job_name: "Monaguillos".to_string(),
+                    people: vec!["Marta".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn job_colors_become_light_fills() {
+        assert_eq!(job_fill("#000000"), Some(0xBFBFBF));
+        assert_eq!(job_fill("#3B82F6"), Some(0xCEDFFC));
+        assert_eq!(job_fill("blue"), None);
+    }
+}
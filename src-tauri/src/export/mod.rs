@@ -1,3 +1,15 @@
+pub mod csv;
 pub mod excel;
+pub mod grid;
+pub mod list;
+pub mod pdf;
+pub mod template;
+pub mod totals;
 
+pub use csv::*;
 pub use excel::*;
+pub use grid::*;
+pub use list::*;
+pub use pdf::*;
+pub use template::*;
+pub use totals::*;
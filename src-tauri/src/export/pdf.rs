@@ -0,0 +1,232 @@
+use super::csv::load_phones;
+use super::list::job_lines;
+use super::template::{template_header, template_rows};
+use crate::commands::get_schedule;
+use crate::models::{ExportTemplate, Schedule};
+use crate::scheduler::naming;
+use scheduler_pdf::{wrap, Page};
+use std::path::PathBuf;
+
+// Portrait A4, in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+// Where the people column starts, after the job name
+const PEOPLE_X: f32 = 200.0;
+
+/// Lays text out top to bottom, starting a new page when the current one is full
+struct Writer {
+    pages: Vec<Page>,
+    y: f32,
+}
+
+impl Writer {
+    fn new() -> Self {
+        Writer {
+            pages: vec![Page::default()],
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    /// Make room for `height` points, moving to a new page if needed (then returns true)
+    fn reserve(&mut self, height: f32) -> bool {
+        if self.y - height < MARGIN {
+            self.pages.push(Page::default());
+            self.y = PAGE_HEIGHT - MARGIN;
+            return true;
+        }
+        false
+    }
+
+    fn text(&mut self, x: f32, size: f32, bold: bool, text: &str) {
+        let page = self.pages.last_mut().expect("writer always has a page");
+        page.text(x, self.y, size, bold, text);
+    }
+
+    fn rule(&mut self) {
+        let page = self.pages.last_mut().expect("writer always has a page");
+        page.line((MARGIN, self.y), (PAGE_WIDTH - MARGIN, self.y), 0.5);
+    }
+}
+
+/// The Excel export's layout on paper: the title, then per service date its heading and note
+/// and one line per job with the people serving it
+fn schedule_pages(schedule: &Schedule) -> Vec<Page> {
+    let mut writer = Writer::new();
+    writer.text(MARGIN, 18.0, true, &format!("Horario: {}", schedule.name));
+    writer.y -= 30.0;
+
+    for service_date in &schedule.service_dates {
+        // Keep a date's heading with at least its first job
+        writer.reserve(48.0);
+        writer.text(
+            MARGIN,
+            12.0,
+            true,
+            &naming::long_date(service_date.service_date),
+        );
+        writer.y -= 6.0;
+        writer.rule();
+        writer.y -= 14.0;
+        if let Some(notes) = &service_date.notes {
+            for line in wrap(notes, 9.0, PAGE_WIDTH - 2.0 * MARGIN) {
+                writer.reserve(12.0);
+                writer.text(MARGIN, 9.0, false, &line);
+                writer.y -= 12.0;
+            }
+        }
+
+        for line in job_lines(service_date) {
+            let lines = wrap(
+                &line.people.join(", "),
+                10.0,
+                PAGE_WIDTH - MARGIN - PEOPLE_X,
+            );
+            writer.reserve(14.0 * lines.len() as f32);
+            writer.text(MARGIN, 10.0, true, &line.job_name);
+            for text in lines {
+                writer.text(PEOPLE_X, 10.0, false, &text);
+                writer.y -= 14.0;
+            }
+        }
+        writer.y -= 12.0;
+    }
+
+    writer.pages
+}
+
+/// A template export on paper: the title, then a table of the template's columns with the
+/// header repeated on every page
+fn table_pages(title: &str, header: &[String], rows: &[Vec<String>]) -> Vec<Page> {
+    let mut writer = Writer::new();
+    writer.text(MARGIN, 18.0, true, title);
+    writer.y -= 30.0;
+
+    let column_width = (PAGE_WIDTH - 2.0 * MARGIN) / header.len().max(1) as f32;
+    let column_x = |i: usize| MARGIN + column_width * i as f32;
+    let write_header = |writer: &mut Writer| {
+        for (i, text) in header.iter().enumerate() {
+            writer.text(column_x(i), 10.0, true, text);
+        }
+        writer.y -= 6.0;
+        writer.rule();
+        writer.y -= 14.0;
+    };
+
+    write_header(&mut writer);
+    for row in rows {
+        let cells: Vec<Vec<String>> = row
+            .iter()
+            .map(|cell| wrap(cell, 9.0, column_width - 6.0))
+            .collect();
+        let height = cells.iter().map(Vec::len).max().unwrap_or(1) as f32 * 12.0;
+        if writer.reserve(height) {
+            write_header(&mut writer);
+        }
+        let top = writer.y;
+        for (i, lines) in cells.iter().enumerate() {
+            writer.y = top;
+            for line in lines {
+                writer.text(column_x(i), 9.0, false, line);
+                writer.y -= 12.0;
+            }
+        }
+        writer.y = top - height;
+    }
+
+    writer.pages
+}
+
+/// With a template, the PDF is a table of its columns instead of the list layout
+pub fn export_schedule_to_pdf(
+    schedule_id: &str,
+    output_path: &PathBuf,
+    template: Option<&ExportTemplate>,
+) -> Result<(), String> {
+    let schedule = get_schedule(schedule_id.to_string())?;
+    let title = format!("Horario: {}", schedule.name);
+    let pages = match template {
+        Some(template) => table_pages(
+            &title,
+            &template_header(template),
+            &template_rows(&schedule, template, &load_phones()?),
+        ),
+        None => schedule_pages(&schedule),
+    };
+    let pdf = scheduler_pdf::document(&title, &pages, PAGE_WIDTH, PAGE_HEIGHT)?;
+    std::fs::write(output_path, pdf).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Assignment, ScheduleStatus, ServiceDate};
+    use chrono::NaiveDate;
+
+    fn assignment(person: &str) -> Assignment {
+        Assignment {
+            id: person.to_string(),
+            service_date_id: String::new(),
+            job_id: "lectores".to_string(),
+            person_id: person.to_string(),
+            position: 1,
+            manual_override: false,
+            created_at: None,
+            updated_at: None,
+            person_name: Some(person.to_string()),
+            job_name: Some("Lectores".to_string()),
+            position_name: None,
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn tables_repeat_their_header_on_each_page() {
+        let header = vec!["Fecha".to_string(), "Servidor".to_string()];
+        let rows: Vec<Vec<String>> = (0..80)
+            .map(|i| vec![format!("{:02}/11/2026", i % 30 + 1), "Ana".to_string()])
+            .collect();
+
+        let pages = table_pages("Horario: Noviembre 2026", &header, &rows);
+        assert_eq!(pages.len(), 2);
+        for page in &pages {
+            assert_eq!(page.texts().filter(|t| *t == "Servidor").count(), 1);
+        }
+    }
+
+    #[test]
+    fn dates_flow_onto_new_pages() {
+        let service_dates = (1..=28)
+            .map(|day| ServiceDate {
+                id: day.to_string(),
+                schedule_id: "s1".to_string(),
+                service_date: NaiveDate::from_ymd_opt(2026, 2, day).unwrap(),
+                notes: None,
+                created_at: None,
+                assignments: vec![assignment("Ana (Sr.)"), assignment("Luis")],
+            })
+            .collect();
+        let schedule = Schedule {
+            id: "s1".to_string(),
+            name: "Febrero 2026".to_string(),
+            year: 2026,
+            month: 2,
+            status: ScheduleStatus::Draft,
+            created_at: None,
+            updated_at: None,
+            published_at: None,
+            service_dates,
+        };
+
+        let pages = schedule_pages(&schedule);
+        assert!(pages.len() > 1);
+        assert!(pages[0].texts().any(|t| t == "Horario: Febrero 2026"));
+        assert!(pages
+            .iter()
+            .flat_map(Page::texts)
+            .any(|t| t == "Ana (Sr.), Luis"));
+
+        let pdf = scheduler_pdf::document("Febrero 2026", &pages, PAGE_WIDTH, PAGE_HEIGHT).unwrap();
+        assert!(pdf.starts_with(b"%PDF-"));
+    }
+}
@@ -0,0 +1,167 @@
+use crate::models::{Assignment, ExportField, ExportOrder, ExportTemplate, Schedule};
+use crate::scheduler::naming;
+use chrono::{Datelike, NaiveDate};
+use std::collections::HashMap;
+
+/// The template's column headers
+pub fn template_header(template: &ExportTemplate) -> Vec<String> {
+    template
+        .fields
+        .iter()
+        .map(|f| f.header().to_string())
+        .collect()
+}
+
+fn field_value(
+    field: ExportField,
+    date: NaiveDate,
+    assignment: &Assignment,
+    phones: &HashMap<String, String>,
+) -> String {
+    match field {
+        ExportField::Date => date.format("%d/%m/%Y").to_string(),
+        ExportField::Weekday => naming::weekday_name(date.weekday()).to_string(),
+        ExportField::Job => assignment
+            .job_name
+            .clone()
+            .unwrap_or_else(|| assignment.job_id.clone()),
+        ExportField::Position => assignment
+            .position_name
+            .clone()
+            .unwrap_or_else(|| assignment.position.to_string()),
+        ExportField::Person => assignment.person_name.clone().unwrap_or_default(),
+        ExportField::Phone => phones
+            .get(&assignment.person_id)
+            .cloned()
+            .unwrap_or_default(),
+        ExportField::Notes => assignment.notes.clone().unwrap_or_default(),
+    }
+}
+
+/// One row per assignment with the template's columns, sorted by its order
+pub fn template_rows(
+    schedule: &Schedule,
+    template: &ExportTemplate,
+    phones: &HashMap<String, String>,
+) -> Vec<Vec<String>> {
+    let mut slots: Vec<(NaiveDate, &Assignment)> = schedule
+        .service_dates
+        .iter()
+        .flat_map(|sd| sd.assignments.iter().map(move |a| (sd.service_date, a)))
+        .collect();
+
+    let job = |a: &Assignment| a.job_name.clone().unwrap_or_default();
+    let person = |a: &Assignment| a.person_name.clone().unwrap_or_default();
+    match template.order_by {
+        ExportOrder::Date => slots.sort_by_key(|(date, a)| (*date, job(a), a.position)),
+        ExportOrder::Job => slots.sort_by_key(|(date, a)| (job(a), a.position, *date)),
+        ExportOrder::Person => slots.sort_by_key(|(date, a)| (person(a), *date)),
+    }
+
+    slots
+        .into_iter()
+        .map(|(date, assignment)| {
+            template
+                .fields
+                .iter()
+                .map(|&field| field_value(field, date, assignment, phones))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ScheduleStatus, ServiceDate};
+
+    fn assignment(job: &str, position: i32, person: &str) -> Assignment {
+        Assignment {
+            id: format!("{}-{}-{}", job, position, person),
+            service_date_id: String::new(),
+            job_id: job.to_lowercase(),
+            person_id: person.to_lowercase(),
+            position,
+            manual_override: false,
+            created_at: None,
+            updated_at: None,
+            person_name: Some(person.to_string()),
+            job_name: Some(job.to_string()),
+            position_name: None,
+            notes: None,
+        }
+    }
+
+    fn service_date(day: u32, assignments: Vec<Assignment>) -> ServiceDate {
+        ServiceDate {
+            id: day.to_string(),
+            schedule_id: "s1".to_string(),
+            service_date: NaiveDate::from_ymd_opt(2026, 11, day).unwrap(),
+            notes: None,
+            created_at: None,
+            assignments,
+        }
+    }
+
+    #[test]
+    fn rows_follow_the_template_columns_and_order() {
+        let schedule = Schedule {
+            id: "s1".to_string(),
+            name: "Noviembre 2026".to_string(),
+            year: 2026,
+            month: 11,
+            status: ScheduleStatus::Draft,
+            created_at: None,
+            updated_at: None,
+            published_at: None,
+            service_dates: vec![
+                service_date(
+                    1,
+                    vec![
+                        assignment("Monaguillos", 1, "Marta"),
+                        assignment("Lectores", 1, "Luis"),
+                    ],
+                ),
+                service_date(8, vec![assignment("Lectores", 1, "Ana")]),
+            ],
+        };
+        let mut template = ExportTemplate {
+            id: "t1".to_string(),
+            name: "Lectores".to_string(),
+            fields: vec![
+                ExportField::Person,
+                ExportField::Weekday,
+                ExportField::Job,
+                ExportField::Phone,
+            ],
+            order_by: ExportOrder::Person,
+            created_at: None,
+            updated_at: None,
+        };
+        let phones = HashMap::from([("ana".to_string(), "5555-1234".to_string())]);
+
+        assert_eq!(
+            template_header(&template),
+            vec!["Servidor", "Día", "Ministerio", "Teléfono"]
+        );
+        assert_eq!(
+            template_rows(&schedule, &template, &phones),
+            vec![
+                vec!["Ana", "Domingo", "Lectores", "5555-1234"],
+                vec!["Luis", "Domingo", "Lectores", ""],
+                vec!["Marta", "Domingo", "Monaguillos", ""],
+            ]
+        );
+
+        template.fields = vec![ExportField::Date, ExportField::Job];
+        template.order_by = ExportOrder::Job;
+        assert_eq!(
+            template_rows(&schedule, &template, &phones),
+            vec![
+                vec!["01/11/2026", "Lectores"],
+                vec!["08/11/2026", "Lectores"],
+                vec!["01/11/2026", "Monaguillos"],
+            ]
+        );
+    }
+}
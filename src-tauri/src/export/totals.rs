@@ -0,0 +1,130 @@
+use crate::models::Schedule;
+use crate::scheduler::naming;
+use std::collections::{HashMap, HashSet};
+
+/// A row of the multi-month summary: how many times a person served in each schedule
+#[derive(Debug, PartialEq)]
+pub struct PersonTotals {
+    pub name: String,
+    pub counts: Vec<u32>,
+    pub total: u32,
+}
+
+/// One row per person with an assignment in any of the schedules, busiest first and then by
+/// name. `counts` follows the order of `schedules`.
+pub fn person_totals(schedules: &[Schedule]) -> Vec<PersonTotals> {
+    let mut by_person: HashMap<&str, PersonTotals> = HashMap::new();
+    for (column, schedule) in schedules.iter().enumerate() {
+        for assignment in schedule.service_dates.iter().flat_map(|sd| &sd.assignments) {
+            let totals = by_person
+                .entry(assignment.person_id.as_str())
+                .or_insert_with(|| PersonTotals {
+                    name: assignment
+                        .person_name
+                        .clone()
+                        .unwrap_or_else(|| assignment.person_id.clone()),
+                    counts: vec![0; schedules.len()],
+                    total: 0,
+                });
+            totals.counts[column] += 1;
+            totals.total += 1;
+        }
+    }
+
+    let mut rows: Vec<PersonTotals> = by_person.into_values().collect();
+    rows.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.name.cmp(&b.name)));
+    rows
+}
+
+/// A sheet name per schedule, "Enero 2026" and so on. Excel wants them unique, so a second
+/// schedule for the same month gets a number.
+pub fn month_sheet_names(schedules: &[Schedule]) -> Vec<String> {
+    let mut used = HashSet::new();
+    schedules
+        .iter()
+        .map(|schedule| {
+            let base = format!("{} {}", naming::month_name(schedule.month), schedule.year);
+            let mut name = base.clone();
+            let mut n = 2;
+            while !used.insert(name.clone()) {
+                name = format!("{} ({})", base, n);
+                n += 1;
+            }
+            name
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Assignment, ScheduleStatus, ServiceDate};
+    use chrono::NaiveDate;
+
+    fn assignment(person: &str) -> Assignment {
+        Assignment {
+            id: person.to_string(),
+            service_date_id: String::new(),
+            job_id: "lectores".to_string(),
+            person_id: person.to_lowercase(),
+            position: 1,
+            manual_override: false,
+            created_at: None,
+            updated_at: None,
+            person_name: Some(person.to_string()),
+            job_name: Some("Lectores".to_string()),
+            position_name: None,
+            notes: None,
+        }
+    }
+
+    fn schedule(month: i32, people: &[&str]) -> Schedule {
+        Schedule {
+            id: month.to_string(),
+            name: String::new(),
+            year: 2026,
+            month,
+            status: ScheduleStatus::Published,
+            created_at: None,
+            updated_at: None,
+            published_at: None,
+            service_dates: vec![ServiceDate {
+                id: month.to_string(),
+                schedule_id: month.to_string(),
+                service_date: NaiveDate::from_ymd_opt(2026, month as u32, 1).unwrap(),
+                notes: None,
+                created_at: None,
+                assignments: people.iter().map(|p| assignment(p)).collect(),
+            }],
+        }
+    }
+
+    #[test]
+    fn totals_count_each_month_and_sort_busiest_first() {
+        let schedules = vec![schedule(1, &["Luis", "Ana"]), schedule(2, &["Ana", "Ana"])];
+        assert_eq!(
+            person_totals(&schedules),
+            vec![
+                PersonTotals {
+                    name: "Ana".to_string(),
+                    counts: vec![1, 2],
+                    total: 3,
+                },
+                PersonTotals {
+                    name: "Luis".to_string(),
+                    counts: vec![1, 0],
+                    total: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn repeated_months_get_distinct_sheet_names() {
+        let schedules = vec![schedule(1, &[]), schedule(2, &[]), schedule(1, &[])];
+        assert_eq!(
+            month_sheet_names(&schedules),
+            vec!["Enero 2026", "Febrero 2026", "Enero 2026 (2)"]
+        );
+    }
+}
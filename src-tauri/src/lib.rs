@@ -1,3 +1,4 @@
+mod cloud;
 mod commands;
 mod db;
 mod export;
@@ -23,6 +24,11 @@ pub fn run() {
             update_person,
             delete_person,
             get_people_for_job,
+            get_pending_person_deletions,
+            mark_person_deletions_synced,
+            // Cloud sync commands
+            sync_person_deletions,
+            find_orphan_users,
             // Jobs commands
             get_all_jobs,
             get_job,
@@ -35,7 +41,9 @@ pub fn run() {
             generate_schedule,
             save_schedule,
             update_assignment,
+            update_assignment_notes,
             publish_schedule,
+            archive_schedule,
             delete_schedule,
             get_fairness_scores,
             get_schedule_by_month,
@@ -58,6 +66,15 @@ pub fn run() {
             check_availability,
             // Export commands
             export_schedule_to_path,
+            export_schedules_to_path,
+            export_schedule_csv_to_path,
+            export_schedule_pdf_to_path,
+            // Export template commands
+            get_all_export_templates,
+            get_export_template,
+            create_export_template,
+            update_export_template,
+            delete_export_template,
             // Test data commands
             import_test_data,
             generate_year_schedules,
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A column an export template can include
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportField {
+    Date,
+    Weekday,
+    Job,
+    Position,
+    Person,
+    Phone,
+    Notes,
+}
+
+impl ExportField {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "date" => Some(Self::Date),
+            "weekday" => Some(Self::Weekday),
+            "job" => Some(Self::Job),
+            "position" => Some(Self::Position),
+            "person" => Some(Self::Person),
+            "phone" => Some(Self::Phone),
+            "notes" => Some(Self::Notes),
+            _ => None,
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::Date => "date".to_string(),
+            Self::Weekday => "weekday".to_string(),
+            Self::Job => "job".to_string(),
+            Self::Position => "position".to_string(),
+            Self::Person => "person".to_string(),
+            Self::Phone => "phone".to_string(),
+            Self::Notes => "notes".to_string(),
+        }
+    }
+
+    /// Column header in the exported file
+    pub fn header(&self) -> &'static str {
+        match self {
+            Self::Date => "Fecha",
+            Self::Weekday => "Día",
+            Self::Job => "Ministerio",
+            Self::Position => "Posición",
+            Self::Person => "Servidor",
+            Self::Phone => "Teléfono",
+            Self::Notes => "Notas",
+        }
+    }
+}
+
+/// How the rows of a template export are sorted
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportOrder {
+    /// Date, then job and position
+    #[default]
+    Date,
+    /// Job and position, then date: one ministry's dates together
+    Job,
+    /// Person, then date: each servidor's dates together
+    Person,
+}
+
+impl ExportOrder {
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "job" => Self::Job,
+            "person" => Self::Person,
+            _ => Self::Date,
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::Date => "date".to_string(),
+            Self::Job => "job".to_string(),
+            Self::Person => "person".to_string(),
+        }
+    }
+}
+
+/// Which columns an export has, in which order, and how its rows are sorted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportTemplate {
+    pub id: String,
+    pub name: String,
+    pub fields: Vec<ExportField>,
+    #[serde(default)]
+    pub order_by: ExportOrder,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateExportTemplateRequest {
+    pub name: String,
+    pub fields: Vec<ExportField>,
+    pub order_by: Option<ExportOrder>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateExportTemplateRequest {
+    pub id: String,
+    pub name: Option<String>,
+    pub fields: Option<Vec<ExportField>>,
+    pub order_by: Option<ExportOrder>,
+}
@@ -13,6 +13,8 @@ pub struct Job {
     pub updated_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub positions: Vec<JobPosition>,
+    #[serde(default)]
+    pub requires_first_communion: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +31,7 @@ pub struct CreateJobRequest {
     pub description: Option<String>,
     pub people_required: Option<i32>,
     pub color: Option<String>,
+    pub requires_first_communion: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,4 +42,5 @@ pub struct UpdateJobRequest {
     pub people_required: Option<i32>,
     pub color: Option<String>,
     pub active: Option<bool>,
+    pub requires_first_communion: Option<bool>,
 }
@@ -1,11 +1,13 @@
-pub mod person;
+pub mod export_template;
 pub mod job;
+pub mod person;
 pub mod schedule;
 pub mod sibling;
 pub mod unavailability;
 
-pub use person::*;
+pub use export_template::*;
 pub use job::*;
+pub use person::*;
 pub use schedule::*;
 pub use sibling::*;
 pub use unavailability::*;
@@ -17,6 +17,12 @@ pub struct Person {
     pub updated_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub job_ids: Vec<String>,
+    #[serde(default)]
+    pub min_rest_days: Option<i32>,
+    #[serde(default)]
+    pub scheduling_notes: Option<String>,
+    #[serde(default)]
+    pub first_communion: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -66,6 +72,9 @@ pub struct CreatePersonRequest {
     pub preference_level: Option<i32>,
     pub notes: Option<String>,
     pub job_ids: Vec<String>,
+    pub min_rest_days: Option<i32>,
+    pub scheduling_notes: Option<String>,
+    pub first_communion: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,6 +90,9 @@ pub struct UpdatePersonRequest {
     pub active: Option<bool>,
     pub notes: Option<String>,
     pub job_ids: Option<Vec<String>>,
+    pub min_rest_days: Option<i32>,
+    pub scheduling_notes: Option<String>,
+    pub first_communion: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +69,8 @@ pub struct Assignment {
     pub job_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub position_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -95,6 +97,12 @@ pub struct UpdateAssignmentRequest {
     pub new_person_id: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateAssignmentNotesRequest {
+    pub assignment_id: String,
+    pub notes: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchedulePreview {
     pub schedule: Schedule,
@@ -148,6 +156,8 @@ pub struct EligiblePerson {
     pub passes_consecutive_check: bool,
     pub sibling_status: String, // "preferred", "neutral", "forbidden"
     pub assignments_this_year: i32,
+    pub notes: Option<String>,
+    pub scheduling_notes: Option<String>, // e.g. "solo misa de 11", shown while picking a replacement
     pub reason_if_ineligible: Option<String>,
 }
 
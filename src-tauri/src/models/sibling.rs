@@ -25,11 +25,38 @@ impl PairingRule {
     }
 }
 
+/// Where a pairing rule applies: within one job's selection or across the whole service date
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PairingScope {
+    #[default]
+    SameJob,
+    SameDate,
+}
+
+impl PairingScope {
+    pub fn from_str(s: &str) -> Self {
+        match s.to_uppercase().as_str() {
+            "SAME_DATE" => Self::SameDate,
+            _ => Self::SameJob,
+        }
+    }
+
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::SameJob => "SAME_JOB".to_string(),
+            Self::SameDate => "SAME_DATE".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SiblingGroup {
     pub id: String,
     pub name: String,
     pub pairing_rule: PairingRule,
+    #[serde(default)]
+    pub pairing_scope: PairingScope,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
     #[serde(default)]
@@ -48,6 +75,8 @@ pub struct SiblingGroupMember {
 pub struct CreateSiblingGroupRequest {
     pub name: String,
     pub pairing_rule: PairingRule,
+    #[serde(default)]
+    pub pairing_scope: PairingScope,
     pub member_ids: Vec<String>,
 }
 
@@ -56,5 +85,6 @@ pub struct UpdateSiblingGroupRequest {
     pub id: String,
     pub name: Option<String>,
     pub pairing_rule: Option<PairingRule>,
+    pub pairing_scope: Option<PairingScope>,
     pub member_ids: Option<Vec<String>>,
 }
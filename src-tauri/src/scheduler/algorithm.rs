@@ -4,14 +4,15 @@ use uuid::Uuid;
 
 use crate::db::with_db;
 use crate::models::{
-    Assignment, ConflictType, GenerateScheduleRequest, Job, JobPosition, Person, PreferredFrequency,
-    Schedule, ScheduleConflict, SchedulePreview, ScheduleStatus, ServiceDate, SiblingGroup,
-    FairnessScore, PairingRule,
+    Assignment, ConflictType, FairnessScore, GenerateScheduleRequest, Job, JobPosition,
+    PairingRule, PairingScope, Person, PreferredFrequency, Schedule, ScheduleConflict,
+    SchedulePreview, ScheduleStatus, ServiceDate, SiblingGroup,
 };
 use crate::scheduler::constraints::{
-    calculate_fairness_score, check_consecutive_weeks, check_sibling_constraint, is_available,
-    SiblingConstraintResult,
+    calculate_fairness_score, check_consecutive_weeks, check_min_rest_days,
+    check_sibling_constraint, is_available, SiblingConstraintResult,
 };
+use crate::scheduler::{naming, rotation};
 
 pub struct ScheduleGenerator;
 
@@ -24,6 +25,7 @@ impl ScheduleGenerator {
         // Get all required data
         let jobs = self.get_active_jobs()?;
         let people = self.get_active_people()?;
+        Self::preflight_check(&jobs, &people)?;
         let sibling_groups = self.get_sibling_groups()?;
         let unavailable = self.get_unavailability(request.year, request.month)?;
         let assignment_history = self.get_assignment_history(request.year)?;
@@ -35,9 +37,10 @@ impl ScheduleGenerator {
 
         // Create schedule
         let schedule_id = Uuid::new_v4().to_string();
-        let schedule_name = request.name.unwrap_or_else(|| {
-            format!("{} {}", month_name(request.month), request.year)
-        });
+        let schedule_name = match request.name {
+            Some(name) => name,
+            None => naming::schedule_name(&naming::load_pattern()?, request.year, request.month),
+        };
 
         let mut service_dates = Vec::new();
         let mut conflicts = Vec::new();
@@ -48,6 +51,8 @@ impl ScheduleGenerator {
         for sunday in &sundays {
             let service_date_id = Uuid::new_v4().to_string();
             let mut assignments = Vec::new();
+            // Everyone serving this date so far, for SAME_DATE sibling groups
+            let mut assigned_on_date: Vec<String> = Vec::new();
 
             for job in &jobs {
                 let positions_for_job: Vec<&JobPosition> = job_positions
@@ -60,6 +65,7 @@ impl ScheduleGenerator {
                     *sunday,
                     &people,
                     &sibling_groups,
+                    &assigned_on_date,
                     &unavailable,
                     &all_assignments,
                     &mut conflicts,
@@ -72,6 +78,7 @@ impl ScheduleGenerator {
                 // Track new assignments for subsequent dates
                 for a in &job_assignments {
                     all_assignments.push((a.person_id.clone(), *sunday));
+                    assigned_on_date.push(a.person_id.clone());
                 }
 
                 assignments.extend(job_assignments);
@@ -100,7 +107,8 @@ impl ScheduleGenerator {
         };
 
         // Calculate fairness scores
-        let fairness_scores = self.calculate_all_fairness_scores(&people, &all_assignments, request.year)?;
+        let fairness_scores =
+            self.calculate_all_fairness_scores(&people, &all_assignments, request.year)?;
 
         Ok(SchedulePreview {
             schedule,
@@ -115,18 +123,20 @@ impl ScheduleGenerator {
         date: NaiveDate,
         people: &[Person],
         sibling_groups: &[SiblingGroup],
+        assigned_on_date: &[String], // People already serving in other jobs this date
         unavailable: &[(String, NaiveDate, NaiveDate)],
         recent_assignments: &[(String, NaiveDate)],
         conflicts: &mut Vec<ScheduleConflict>,
         service_date_id: &str,
         job_positions: &[&JobPosition],
-        position_history: &HashMap<(String, String), Vec<i32>>, // (person_id, job_id) -> list of positions served
+        position_history: &HashMap<(String, String), Vec<i32>>, // (person_id, job_id) -> positions done in the current cycle
         schedule_positions: &mut HashMap<(String, String), Vec<i32>>, // Track positions in current schedule generation
     ) -> Vec<Assignment> {
-        // Filter people qualified for this job
+        // Filter people qualified for this job (and with first communion if the job requires it)
         let qualified: Vec<&Person> = people
             .iter()
             .filter(|p| p.job_ids.contains(&job.id))
+            .filter(|p| !job.requires_first_communion || p.first_communion)
             .collect();
 
         // Score each candidate
@@ -143,6 +153,11 @@ impl ScheduleGenerator {
                 continue;
             }
 
+            // Check minimum rest gap between assignments
+            if !check_min_rest_days(person, date, recent_assignments) {
+                continue;
+            }
+
             // Calculate base score
             let year_assignments = recent_assignments
                 .iter()
@@ -174,14 +189,22 @@ impl ScheduleGenerator {
         // Sort by score (highest first)
         candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
 
+        // Siblings of people already serving this date (SAME_DATE groups) go first, keeping score order
+        candidates.sort_by_key(|(person, _)| {
+            check_sibling_constraint(&person.id, &[], assigned_on_date, sibling_groups)
+                != SiblingConstraintResult::Preferred
+        });
+
         // Select people considering sibling constraints
         let mut selected: Vec<Assignment> = Vec::new();
         let mut selected_ids: Vec<String> = Vec::new();
 
-        // First pass: find TOGETHER siblings that should be grouped
+        // First pass: find TOGETHER siblings that should be grouped in this job
         let together_groups: Vec<&SiblingGroup> = sibling_groups
             .iter()
-            .filter(|g| g.pairing_rule == PairingRule::Together)
+            .filter(|g| {
+                g.pairing_rule == PairingRule::Together && g.pairing_scope == PairingScope::SameJob
+            })
             .collect();
 
         // Helper to get next position for a person based on positions they've actually served
@@ -235,7 +258,13 @@ impl ScheduleGenerator {
                     break;
                 }
 
-                let constraint = check_sibling_constraint(&person.id, &selected_ids, sibling_groups);
+                let on_date: Vec<String> = assigned_on_date
+                    .iter()
+                    .chain(&selected_ids)
+                    .cloned()
+                    .collect();
+                let constraint =
+                    check_sibling_constraint(&person.id, &selected_ids, &on_date, sibling_groups);
                 match constraint {
                     SiblingConstraintResult::Forbidden => continue,
                     SiblingConstraintResult::Preferred | SiblingConstraintResult::Neutral => {
@@ -252,6 +281,7 @@ impl ScheduleGenerator {
                             person_name: Some(person_name),
                             job_name: Some(job.name.clone()),
                             position_name: None,
+                            notes: None,
                         });
                         selected_ids.push(person.id.clone());
                         position += 1;
@@ -267,7 +297,13 @@ impl ScheduleGenerator {
                     break;
                 }
 
-                let constraint = check_sibling_constraint(&person.id, &selected_ids, sibling_groups);
+                let on_date: Vec<String> = assigned_on_date
+                    .iter()
+                    .chain(&selected_ids)
+                    .cloned()
+                    .collect();
+                let constraint =
+                    check_sibling_constraint(&person.id, &selected_ids, &on_date, sibling_groups);
                 match constraint {
                     SiblingConstraintResult::Forbidden => continue,
                     SiblingConstraintResult::Preferred | SiblingConstraintResult::Neutral => {
@@ -279,17 +315,26 @@ impl ScheduleGenerator {
                         for group in &together_groups {
                             if group.member_ids.contains(&person.id) {
                                 for sibling_id in &group.member_ids {
-                                    if selected_with_positions.len() >= job.people_required as usize {
+                                    if selected_with_positions.len() >= job.people_required as usize
+                                    {
                                         break;
                                     }
-                                    if selected_ids.contains(sibling_id) || sibling_id == &person.id {
+                                    if selected_ids.contains(sibling_id) || sibling_id == &person.id
+                                    {
                                         continue;
                                     }
 
-                                    if let Some(sibling) = people.iter().find(|p| p.id == *sibling_id) {
+                                    if let Some(sibling) =
+                                        people.iter().find(|p| p.id == *sibling_id)
+                                    {
                                         if is_available(&sibling.id, date, unavailable) {
-                                            let sibling_next_pos = get_next_position(&sibling.id, &job.id, num_positions);
-                                            selected_with_positions.push((sibling, sibling_next_pos));
+                                            let sibling_next_pos = get_next_position(
+                                                &sibling.id,
+                                                &job.id,
+                                                num_positions,
+                                            );
+                                            selected_with_positions
+                                                .push((sibling, sibling_next_pos));
                                             selected_ids.push(sibling.id.clone());
                                         }
                                     }
@@ -319,11 +364,12 @@ impl ScheduleGenerator {
                 let total = all_positions_for_person.len() as i32;
                 let current_cycle = total / num_positions;
                 let cycle_start = (current_cycle * num_positions) as usize;
-                let positions_in_current_cycle: Vec<i32> = if cycle_start < all_positions_for_person.len() {
-                    all_positions_for_person[cycle_start..].to_vec()
-                } else {
-                    Vec::new()
-                };
+                let positions_in_current_cycle: Vec<i32> =
+                    if cycle_start < all_positions_for_person.len() {
+                        all_positions_for_person[cycle_start..].to_vec()
+                    } else {
+                        Vec::new()
+                    };
 
                 // Bag contains positions NOT yet done in current cycle
                 let bag: Vec<i32> = (1..=num_positions)
@@ -345,9 +391,9 @@ impl ScheduleGenerator {
             let mut filled_positions: Vec<i32> = Vec::new();
 
             // Keep assigning until all positions are filled or all people assigned
-            while filled_positions.len() < num_positions as usize &&
-                  assigned_people.len() < selected_with_positions.len() {
-
+            while filled_positions.len() < num_positions as usize
+                && assigned_people.len() < selected_with_positions.len()
+            {
                 // Find the scarcest position (fewest people have it in their bag)
                 let mut position_counts: Vec<(i32, usize)> = Vec::new();
                 for pos in 1..=num_positions {
@@ -357,8 +403,10 @@ impl ScheduleGenerator {
                     let count = selected_with_positions
                         .iter()
                         .filter(|(person, _)| {
-                            !assigned_people.contains(&person.id) &&
-                            person_bags.get(&person.id).map_or(false, |bag| bag.contains(&pos))
+                            !assigned_people.contains(&person.id)
+                                && person_bags
+                                    .get(&person.id)
+                                    .map_or(false, |bag| bag.contains(&pos))
                         })
                         .count();
                     position_counts.push((pos, count));
@@ -405,8 +453,10 @@ impl ScheduleGenerator {
                     let mut candidates: Vec<(&Person, usize)> = selected_with_positions
                         .iter()
                         .filter(|(person, _)| {
-                            !assigned_people.contains(&person.id) &&
-                            person_bags.get(&person.id).map_or(false, |bag| bag.contains(&scarce_pos))
+                            !assigned_people.contains(&person.id)
+                                && person_bags
+                                    .get(&person.id)
+                                    .map_or(false, |bag| bag.contains(&scarce_pos))
                         })
                         .map(|(person, _)| {
                             let bag_size = person_bags.get(&person.id).map_or(0, |b| b.len());
@@ -435,7 +485,10 @@ impl ScheduleGenerator {
 
                 // Track the actual position assigned for this person/job in the current schedule
                 let key = (person.id.clone(), job.id.clone());
-                schedule_positions.entry(key).or_insert_with(Vec::new).push(assigned_pos);
+                schedule_positions
+                    .entry(key)
+                    .or_insert_with(Vec::new)
+                    .push(assigned_pos);
 
                 // Find position name
                 let position_name = job_positions
@@ -456,6 +509,7 @@ impl ScheduleGenerator {
                     person_name: Some(person_name),
                     job_name: Some(job.name.clone()),
                     position_name,
+                    notes: None,
                 });
             }
         }
@@ -480,11 +534,43 @@ impl ScheduleGenerator {
         selected
     }
 
+    /// Refuse to generate when the result could only be empty
+    fn preflight_check(jobs: &[Job], people: &[Person]) -> Result<(), String> {
+        if jobs.is_empty() {
+            return Err(
+                "No hay trabajos activos para programar. Active al menos un trabajo antes de generar."
+                    .to_string(),
+            );
+        }
+
+        let unstaffable: Vec<&str> = jobs
+            .iter()
+            .filter(|job| job.people_required > 0)
+            .filter(|job| {
+                !people.iter().any(|p| {
+                    p.job_ids.contains(&job.id)
+                        && (!job.requires_first_communion || p.first_communion)
+                })
+            })
+            .map(|job| job.name.as_str())
+            .collect();
+
+        if !unstaffable.is_empty() {
+            return Err(format!(
+                "No hay personas activas calificadas para {}. Asigne personas a estos trabajos o desactívelos antes de generar.",
+                unstaffable.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+
     fn get_active_jobs(&self) -> Result<Vec<Job>, String> {
         with_db(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT id, name, description, people_required, color, active
-                 FROM jobs WHERE active = TRUE ORDER BY name"
+                "SELECT id, name, description, people_required, color, active,
+                        requires_first_communion
+                 FROM jobs WHERE active = TRUE ORDER BY name",
             )?;
 
             let jobs: Vec<Job> = stmt
@@ -499,6 +585,7 @@ impl ScheduleGenerator {
                         created_at: None,
                         updated_at: None,
                         positions: Vec::new(),
+                        requires_first_communion: row.get::<_, Option<bool>>(6)?.unwrap_or(false),
                     })
                 })?
                 .filter_map(|r| r.ok())
@@ -513,9 +600,9 @@ impl ScheduleGenerator {
             let mut stmt = conn.prepare(
                 "SELECT id, first_name, last_name, email, phone,
                         preferred_frequency, max_consecutive_weeks, preference_level,
-                        active, notes
+                        active, notes, min_rest_days, scheduling_notes, first_communion
                  FROM people WHERE active = TRUE
-                 ORDER BY last_name, first_name"
+                 ORDER BY last_name, first_name",
             )?;
 
             let people: Vec<Person> = stmt
@@ -526,7 +613,9 @@ impl ScheduleGenerator {
                         last_name: row.get(2)?,
                         email: row.get(3)?,
                         phone: row.get(4)?,
-                        preferred_frequency: PreferredFrequency::from_str(&row.get::<_, String>(5)?),
+                        preferred_frequency: PreferredFrequency::from_str(
+                            &row.get::<_, String>(5)?,
+                        ),
                         max_consecutive_weeks: row.get(6)?,
                         preference_level: row.get(7)?,
                         active: row.get(8)?,
@@ -534,6 +623,9 @@ impl ScheduleGenerator {
                         created_at: None,
                         updated_at: None,
                         job_ids: Vec::new(),
+                        min_rest_days: row.get(10)?,
+                        scheduling_notes: row.get(11)?,
+                        first_communion: row.get::<_, Option<bool>>(12)?.unwrap_or(false),
                     })
                 })?
                 .filter_map(|r| r.ok())
@@ -542,9 +634,8 @@ impl ScheduleGenerator {
             // Fetch job IDs for each person
             let mut result = Vec::new();
             for mut person in people {
-                let mut job_stmt = conn.prepare(
-                    "SELECT job_id FROM person_jobs WHERE person_id = ?"
-                )?;
+                let mut job_stmt =
+                    conn.prepare("SELECT job_id FROM person_jobs WHERE person_id = ?")?;
                 person.job_ids = job_stmt
                     .query_map([&person.id], |row| row.get(0))?
                     .filter_map(|r| r.ok())
@@ -559,7 +650,7 @@ impl ScheduleGenerator {
     fn get_sibling_groups(&self) -> Result<Vec<SiblingGroup>, String> {
         with_db(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT id, name, pairing_rule FROM sibling_groups ORDER BY name"
+                "SELECT id, name, pairing_rule, COALESCE(pairing_scope, 'SAME_JOB') FROM sibling_groups ORDER BY name"
             )?;
 
             let groups: Vec<SiblingGroup> = stmt
@@ -568,6 +659,7 @@ impl ScheduleGenerator {
                         id: row.get(0)?,
                         name: row.get(1)?,
                         pairing_rule: PairingRule::from_str(&row.get::<_, String>(2)?),
+                        pairing_scope: PairingScope::from_str(&row.get::<_, String>(3)?),
                         created_at: None,
                         updated_at: None,
                         member_ids: Vec::new(),
@@ -579,7 +671,7 @@ impl ScheduleGenerator {
             let mut result = Vec::new();
             for mut group in groups {
                 let mut member_stmt = conn.prepare(
-                    "SELECT person_id FROM sibling_group_members WHERE sibling_group_id = ?"
+                    "SELECT person_id FROM sibling_group_members WHERE sibling_group_id = ?",
                 )?;
                 group.member_ids = member_stmt
                     .query_map([&group.id], |row| row.get(0))?
@@ -592,9 +684,12 @@ impl ScheduleGenerator {
         })
     }
 
-    fn get_unavailability(&self, year: i32, month: i32) -> Result<Vec<(String, NaiveDate, NaiveDate)>, String> {
-        let first_day = NaiveDate::from_ymd_opt(year, month as u32, 1)
-            .ok_or("Invalid date")?;
+    fn get_unavailability(
+        &self,
+        year: i32,
+        month: i32,
+    ) -> Result<Vec<(String, NaiveDate, NaiveDate)>, String> {
+        let first_day = NaiveDate::from_ymd_opt(year, month as u32, 1).ok_or("Invalid date")?;
         let last_day = if month == 12 {
             NaiveDate::from_ymd_opt(year + 1, 1, 1)
         } else {
@@ -611,16 +706,19 @@ impl ScheduleGenerator {
             )?;
 
             let unavailable: Vec<(String, NaiveDate, NaiveDate)> = stmt
-                .query_map(duckdb::params![last_day.to_string(), first_day.to_string()], |row| {
-                    let person_id: String = row.get(0)?;
-                    let start_str: String = row.get(1)?;
-                    let end_str: String = row.get(2)?;
-                    let start = NaiveDate::parse_from_str(&start_str, "%Y-%m-%d")
-                        .unwrap_or(first_day);
-                    let end = NaiveDate::parse_from_str(&end_str, "%Y-%m-%d")
-                        .unwrap_or(last_day);
-                    Ok((person_id, start, end))
-                })?
+                .query_map(
+                    duckdb::params![last_day.to_string(), first_day.to_string()],
+                    |row| {
+                        let person_id: String = row.get(0)?;
+                        let start_str: String = row.get(1)?;
+                        let end_str: String = row.get(2)?;
+                        let start =
+                            NaiveDate::parse_from_str(&start_str, "%Y-%m-%d").unwrap_or(first_day);
+                        let end =
+                            NaiveDate::parse_from_str(&end_str, "%Y-%m-%d").unwrap_or(last_day);
+                        Ok((person_id, start, end))
+                    },
+                )?
                 .filter_map(|r| r.ok())
                 .collect();
 
@@ -632,7 +730,7 @@ impl ScheduleGenerator {
         with_db(|conn| {
             let mut stmt = conn.prepare(
                 "SELECT person_id, CAST(service_date AS VARCHAR) FROM assignment_history
-                 WHERE year >= ? - 1 ORDER BY service_date"
+                 WHERE year >= ? - 1 ORDER BY service_date",
             )?;
 
             let history: Vec<(String, NaiveDate)> = stmt
@@ -674,27 +772,19 @@ impl ScheduleGenerator {
 
     fn get_position_history_per_job(&self) -> Result<HashMap<(String, String), Vec<i32>>, String> {
         with_db(|conn| {
-            // Get all positions served by each person per job, ordered by date
-            // This allows us to track which positions they've actually done
-            let mut stmt = conn.prepare(
-                "SELECT person_id, job_id, COALESCE(position, 1) as position
-                 FROM assignment_history
-                 ORDER BY person_id, job_id, service_date"
-            )?;
+            // Positions each person already did in their current rotation cycle per job
+            // (persisted in position_rotation, derived from history the first time)
+            let mut stmt = conn.prepare("SELECT person_id, job_id FROM person_jobs")?;
+            let pairs: Vec<(String, String)> = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .filter_map(|r| r.ok())
+                .collect();
 
             let mut result: HashMap<(String, String), Vec<i32>> = HashMap::new();
-
-            let rows = stmt.query_map([], |row| {
-                let person_id: String = row.get(0)?;
-                let job_id: String = row.get(1)?;
-                let position: i32 = row.get(2)?;
-                Ok((person_id, job_id, position))
-            })?;
-
-            for row in rows {
-                if let Ok((person_id, job_id, position)) = row {
-                    let key = (person_id, job_id);
-                    result.entry(key).or_insert_with(Vec::new).push(position);
+            for (person_id, job_id) in pairs {
+                let (_, done) = rotation::load(conn, &person_id, &job_id)?;
+                if !done.is_empty() {
+                    result.insert((person_id, job_id), done);
                 }
             }
 
@@ -759,26 +849,12 @@ impl ScheduleGenerator {
         }
 
         // Sort by fairness score descending
-        scores.sort_by(|a, b| b.fairness_score.partial_cmp(&a.fairness_score).unwrap_or(std::cmp::Ordering::Equal));
+        scores.sort_by(|a, b| {
+            b.fairness_score
+                .partial_cmp(&a.fairness_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
 
         Ok(scores)
     }
 }
-
-fn month_name(month: i32) -> &'static str {
-    match month {
-        1 => "January",
-        2 => "February",
-        3 => "March",
-        4 => "April",
-        5 => "May",
-        6 => "June",
-        7 => "July",
-        8 => "August",
-        9 => "September",
-        10 => "October",
-        11 => "November",
-        12 => "December",
-        _ => "Unknown",
-    }
-}
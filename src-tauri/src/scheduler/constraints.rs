@@ -1,18 +1,25 @@
 use chrono::{Datelike, NaiveDate};
 
-use crate::models::{PairingRule, Person, SiblingGroup};
+use crate::models::{PairingRule, PairingScope, Person, SiblingGroup};
 
 /// Checks if a person is available on a given date
-pub fn is_available(person_id: &str, date: NaiveDate, unavailable_dates: &[(String, NaiveDate, NaiveDate)]) -> bool {
-    !unavailable_dates.iter().any(|(pid, start, end)| {
-        pid == person_id && date >= *start && date <= *end
-    })
+pub fn is_available(
+    person_id: &str,
+    date: NaiveDate,
+    unavailable_dates: &[(String, NaiveDate, NaiveDate)],
+) -> bool {
+    !unavailable_dates
+        .iter()
+        .any(|(pid, start, end)| pid == person_id && date >= *start && date <= *end)
 }
 
-/// Checks if assigning a person would violate sibling pairing rules
+/// Checks if assigning a person would violate sibling pairing rules.
+/// `assigned_in_job` is who is already in this job on the date, `assigned_on_date` who is
+/// serving in any job that date; each group looks at the one its scope asks for.
 pub fn check_sibling_constraint(
     person_id: &str,
-    already_assigned: &[String],
+    assigned_in_job: &[String],
+    assigned_on_date: &[String],
     sibling_groups: &[SiblingGroup],
 ) -> SiblingConstraintResult {
     for group in sibling_groups {
@@ -20,6 +27,11 @@ pub fn check_sibling_constraint(
             continue;
         }
 
+        let already_assigned = match group.pairing_scope {
+            PairingScope::SameJob => assigned_in_job,
+            PairingScope::SameDate => assigned_on_date,
+        };
+
         let siblings_assigned: Vec<&String> = group
             .member_ids
             .iter()
@@ -29,7 +41,9 @@ pub fn check_sibling_constraint(
         match group.pairing_rule {
             PairingRule::Together => {
                 // If any sibling is already assigned, prefer adding more siblings
-                if !siblings_assigned.is_empty() && !siblings_assigned.contains(&&person_id.to_string()) {
+                if !siblings_assigned.is_empty()
+                    && !siblings_assigned.contains(&&person_id.to_string())
+                {
                     return SiblingConstraintResult::Preferred;
                 }
             }
@@ -47,9 +61,9 @@ pub fn check_sibling_constraint(
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum SiblingConstraintResult {
-    Preferred,  // Should prioritize this person
-    Neutral,    // No preference
-    Forbidden,  // Should not assign this person
+    Preferred, // Should prioritize this person
+    Neutral,   // No preference
+    Forbidden, // Should not assign this person
 }
 
 /// Checks if assigning would exceed max consecutive weeks
@@ -67,7 +81,9 @@ pub fn check_consecutive_weeks(
         let check_year = if week > i { year } else { year - 1 };
 
         let was_assigned = recent_assignments.iter().any(|(pid, d)| {
-            pid == &person.id && d.iso_week().week() == check_week && d.iso_week().year() == check_year
+            pid == &person.id
+                && d.iso_week().week() == check_week
+                && d.iso_week().year() == check_year
         });
 
         if was_assigned {
@@ -80,6 +96,22 @@ pub fn check_consecutive_weeks(
     consecutive < person.max_consecutive_weeks as u32
 }
 
+/// Checks if the person has rested at least `min_rest_days` between this date and any other assignment
+pub fn check_min_rest_days(
+    person: &Person,
+    date: NaiveDate,
+    assignments: &[(String, NaiveDate)],
+) -> bool {
+    let min_rest = match person.min_rest_days {
+        Some(days) if days > 0 => days as i64,
+        _ => return true,
+    };
+
+    !assignments
+        .iter()
+        .any(|(pid, d)| pid == &person.id && *d != date && (date - *d).num_days().abs() < min_rest)
+}
+
 /// Calculate fairness score for a person (higher = more priority)
 pub fn calculate_fairness_score(
     person: &Person,
@@ -111,3 +143,158 @@ pub fn calculate_fairness_score(
     // Weighted combination: fairness * 0.7 + recency * 0.2 + preference * 0.1
     (assignment_score * 0.7) + (recency_score * 0.2) + (preference_score * 0.1)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PreferredFrequency;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn person(id: &str, min_rest_days: Option<i32>) -> Person {
+        Person {
+            id: id.to_string(),
+            first_name: "Test".to_string(),
+            last_name: id.to_string(),
+            email: None,
+            phone: None,
+            preferred_frequency: PreferredFrequency::Bimonthly,
+            max_consecutive_weeks: 2,
+            preference_level: 5,
+            active: true,
+            notes: None,
+            created_at: None,
+            updated_at: None,
+            job_ids: Vec::new(),
+            min_rest_days,
+            scheduling_notes: None,
+            first_communion: true,
+        }
+    }
+
+    fn group(rule: PairingRule, scope: PairingScope, members: &[&str]) -> SiblingGroup {
+        SiblingGroup {
+            id: "g1".to_string(),
+            name: "Familia".to_string(),
+            pairing_rule: rule,
+            pairing_scope: scope,
+            created_at: None,
+            updated_at: None,
+            member_ids: members.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    fn ids(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn min_rest_days_unset_or_zero_never_blocks() {
+        let assignments = vec![("p1".to_string(), date(2024, 3, 3))];
+        assert!(check_min_rest_days(
+            &person("p1", None),
+            date(2024, 3, 10),
+            &assignments
+        ));
+        assert!(check_min_rest_days(
+            &person("p1", Some(0)),
+            date(2024, 3, 10),
+            &assignments
+        ));
+    }
+
+    #[test]
+    fn min_rest_days_blocks_assignments_too_close_on_either_side() {
+        let p = person("p1", Some(14));
+        let before = vec![("p1".to_string(), date(2024, 3, 3))];
+        let after = vec![("p1".to_string(), date(2024, 3, 17))];
+        assert!(!check_min_rest_days(&p, date(2024, 3, 10), &before));
+        assert!(!check_min_rest_days(&p, date(2024, 3, 10), &after));
+    }
+
+    #[test]
+    fn min_rest_days_allows_exactly_the_minimum_gap() {
+        let p = person("p1", Some(14));
+        let assignments = vec![("p1".to_string(), date(2024, 3, 3))];
+        assert!(check_min_rest_days(&p, date(2024, 3, 17), &assignments));
+    }
+
+    #[test]
+    fn min_rest_days_ignores_other_people_and_the_same_date() {
+        let p = person("p1", Some(14));
+        let assignments = vec![
+            ("p2".to_string(), date(2024, 3, 3)),
+            ("p1".to_string(), date(2024, 3, 10)),
+        ];
+        assert!(check_min_rest_days(&p, date(2024, 3, 10), &assignments));
+    }
+
+    #[test]
+    fn same_job_separate_only_looks_at_the_job() {
+        let groups = vec![group(
+            PairingRule::Separate,
+            PairingScope::SameJob,
+            &["a", "b"],
+        )];
+        // Sibling serves another job that date: allowed
+        assert_eq!(
+            check_sibling_constraint("a", &[], &ids(&["b"]), &groups),
+            SiblingConstraintResult::Neutral
+        );
+        // Sibling already in this job: forbidden
+        assert_eq!(
+            check_sibling_constraint("a", &ids(&["b"]), &ids(&["b"]), &groups),
+            SiblingConstraintResult::Forbidden
+        );
+    }
+
+    #[test]
+    fn same_date_separate_looks_at_every_job_that_date() {
+        let groups = vec![group(
+            PairingRule::Separate,
+            PairingScope::SameDate,
+            &["a", "b"],
+        )];
+        assert_eq!(
+            check_sibling_constraint("a", &[], &ids(&["b"]), &groups),
+            SiblingConstraintResult::Forbidden
+        );
+    }
+
+    #[test]
+    fn together_prefers_joining_a_sibling_within_scope() {
+        let same_job = vec![group(
+            PairingRule::Together,
+            PairingScope::SameJob,
+            &["a", "b"],
+        )];
+        let same_date = vec![group(
+            PairingRule::Together,
+            PairingScope::SameDate,
+            &["a", "b"],
+        )];
+        assert_eq!(
+            check_sibling_constraint("a", &[], &ids(&["b"]), &same_job),
+            SiblingConstraintResult::Neutral
+        );
+        assert_eq!(
+            check_sibling_constraint("a", &[], &ids(&["b"]), &same_date),
+            SiblingConstraintResult::Preferred
+        );
+    }
+
+    #[test]
+    fn groups_without_the_person_are_ignored() {
+        let groups = vec![group(
+            PairingRule::Separate,
+            PairingScope::SameDate,
+            &["b", "c"],
+        )];
+        assert_eq!(
+            check_sibling_constraint("a", &ids(&["b"]), &ids(&["b", "c"]), &groups),
+            SiblingConstraintResult::Neutral
+        );
+    }
+}
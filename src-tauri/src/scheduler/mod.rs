@@ -1,4 +1,6 @@
 pub mod algorithm;
 pub mod constraints;
+pub mod naming;
+pub mod rotation;
 
 pub use algorithm::ScheduleGenerator;
@@ -0,0 +1,79 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::db::with_db;
+
+/// Used when the `schedule_name_pattern` setting is missing or empty
+pub const DEFAULT_SCHEDULE_NAME_PATTERN: &str = "{mes} {año}";
+
+const MONTH_NAMES: [&str; 12] = [
+    "Enero",
+    "Febrero",
+    "Marzo",
+    "Abril",
+    "Mayo",
+    "Junio",
+    "Julio",
+    "Agosto",
+    "Septiembre",
+    "Octubre",
+    "Noviembre",
+    "Diciembre",
+];
+
+pub fn month_name(month: i32) -> &'static str {
+    usize::try_from(month - 1)
+        .ok()
+        .and_then(|i| MONTH_NAMES.get(i))
+        .copied()
+        .unwrap_or("?")
+}
+
+pub fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Lunes",
+        Weekday::Tue => "Martes",
+        Weekday::Wed => "Miércoles",
+        Weekday::Thu => "Jueves",
+        Weekday::Fri => "Viernes",
+        Weekday::Sat => "Sábado",
+        Weekday::Sun => "Domingo",
+    }
+}
+
+/// Fill a naming pattern: `{mes}` is the Spanish month name, `{mm}` the two-digit month and
+/// `{año}` the year (same placeholders as the web version)
+pub fn schedule_name(pattern: &str, year: i32, month: i32) -> String {
+    pattern
+        .replace("{mes}", month_name(month))
+        .replace("{mm}", &format!("{:02}", month))
+        .replace("{año}", &year.to_string())
+}
+
+/// "Domingo 1 de Marzo de 2026"
+pub fn long_date(date: NaiveDate) -> String {
+    format!(
+        "{} {} de {} de {}",
+        weekday_name(date.weekday()),
+        date.day(),
+        month_name(date.month() as i32),
+        date.year()
+    )
+}
+
+/// The configured pattern; falls back to the default if it cannot tell months apart
+pub fn load_pattern() -> Result<String, String> {
+    let pattern: Option<String> = with_db(|conn| {
+        let mut stmt =
+            conn.prepare("SELECT value FROM app_settings WHERE key = 'schedule_name_pattern'")?;
+        let mut rows = stmt.query([])?;
+        match rows.next()? {
+            Some(row) => row.get(0),
+            None => Ok(None),
+        }
+    })?;
+
+    Ok(pattern
+        .map(|p| p.trim().to_string())
+        .filter(|p| p.contains("{año}") && (p.contains("{mes}") || p.contains("{mm}")))
+        .unwrap_or_else(|| DEFAULT_SCHEDULE_NAME_PATTERN.to_string()))
+}
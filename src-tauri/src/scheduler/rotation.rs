@@ -0,0 +1,119 @@
+use duckdb::{Connection, Result as DuckResult};
+
+/// Positions a person already did in their current rotation cycle for a job, with the cycle number.
+/// Untracked people get their state derived from assignment_history (latest positions back to the
+/// first repeat). Read-only, so previews write nothing; `mark_done` / `unmark_done` store the
+/// state when the schedule is saved. Same rules as the web API.
+pub fn load(conn: &Connection, person_id: &str, job_id: &str) -> DuckResult<(i32, Vec<i32>)> {
+    let stored = conn.query_row(
+        "SELECT cycle, done_positions FROM position_rotation WHERE person_id = ? AND job_id = ?",
+        [person_id, job_id],
+        |row| Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?)),
+    );
+
+    match stored {
+        Ok((cycle, done)) => return Ok((cycle, parse_positions(&done))),
+        Err(duckdb::Error::QueryReturnedNoRows) => {}
+        Err(e) => return Err(e),
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT position FROM assignment_history
+         WHERE person_id = ? AND job_id = ? AND position IS NOT NULL
+         ORDER BY service_date DESC",
+    )?;
+    let history: Vec<i32> = stmt
+        .query_map([person_id, job_id], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let mut done: Vec<i32> = Vec::new();
+    for pos in history {
+        if done.contains(&pos) {
+            // Found a repeat, cycle boundary
+            break;
+        }
+        done.push(pos);
+    }
+    done.reverse();
+
+    Ok((1, done))
+}
+
+/// Positions still owed in the cycle; once everything is done the bag is full again
+pub fn pending_positions(done: &[i32], num_positions: i32) -> Vec<i32> {
+    let bag: Vec<i32> = (1..=num_positions).filter(|p| !done.contains(p)).collect();
+    if bag.is_empty() {
+        (1..=num_positions).collect()
+    } else {
+        bag
+    }
+}
+
+/// Record that a person served a position; repeating a position or finishing the cycle starts a new one.
+/// The desktop app has no per-date staffing overrides, so the cycle is the job's people_required.
+pub fn mark_done(
+    conn: &Connection,
+    person_id: &str,
+    job_id: &str,
+    position: i32,
+) -> DuckResult<()> {
+    let num_positions: i32 = conn.query_row(
+        "SELECT people_required FROM jobs WHERE id = ?",
+        [job_id],
+        |row| row.get(0),
+    )?;
+
+    let (mut cycle, mut done) = load(conn, person_id, job_id)?;
+    let cycle_complete = (1..=num_positions).all(|p| done.contains(&p));
+    if done.contains(&position) || cycle_complete {
+        cycle += 1;
+        done = vec![position];
+    } else {
+        done.push(position);
+    }
+
+    save(conn, person_id, job_id, cycle, &done)
+}
+
+/// Undo a position that is no longer served (slot given to someone else)
+pub fn unmark_done(
+    conn: &Connection,
+    person_id: &str,
+    job_id: &str,
+    position: i32,
+) -> DuckResult<()> {
+    let (cycle, mut done) = load(conn, person_id, job_id)?;
+    if !done.contains(&position) {
+        return Ok(());
+    }
+    done.retain(|p| *p != position);
+
+    save(conn, person_id, job_id, cycle, &done)
+}
+
+fn save(
+    conn: &Connection,
+    person_id: &str,
+    job_id: &str,
+    cycle: i32,
+    done: &[i32],
+) -> DuckResult<()> {
+    let done_str = done
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    conn.execute(
+        "INSERT OR REPLACE INTO position_rotation (person_id, job_id, cycle, done_positions, updated_at)
+         VALUES (?, ?, ?, ?, CURRENT_TIMESTAMP)",
+        duckdb::params![person_id, job_id, cycle, done_str],
+    )?;
+
+    Ok(())
+}
+
+fn parse_positions(s: &str) -> Vec<i32> {
+    s.split(',').filter_map(|p| p.trim().parse().ok()).collect()
+}
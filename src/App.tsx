@@ -10,7 +10,9 @@ import {
   Settings,
 } from "./pages";
 import { ServidorDashboard } from "./pages/ServidorDashboard";
+import { GuardianDashboard } from "./pages/GuardianDashboard";
 import { Login } from "./pages/Login";
+import { AcceptInvitation } from "./pages/AcceptInvitation";
 import { useAuthStore } from "./stores/authStore";
 
 type Page = 'dashboard' | 'people' | 'schedule' | 'unavailability' | 'siblings' | 'reports' | 'settings';
@@ -18,6 +20,18 @@ type Page = 'dashboard' | 'people' | 'schedule' | 'unavailability' | 'siblings'
 function App() {
   const [currentPage, setCurrentPage] = useState<Page>('dashboard');
   const { isAuthenticated, user } = useAuthStore();
+  // An invitation link opens the app with ?invite=<token>
+  const [inviteToken, setInviteToken] = useState(
+    () => new URLSearchParams(window.location.search).get('invite')
+  );
+
+  if (inviteToken) {
+    const leaveInvitation = () => {
+      window.history.replaceState(null, '', window.location.pathname);
+      setInviteToken(null);
+    };
+    return <AcceptInvitation token={inviteToken} onDone={leaveInvitation} />;
+  }
 
   // If not authenticated, show login page
   if (!isAuthenticated) {
@@ -29,6 +43,11 @@ function App() {
     return <ServidorDashboard />;
   }
 
+  // A parent's login covers several servidores
+  if (user?.role === 'guardian') {
+    return <GuardianDashboard />;
+  }
+
   const renderPage = () => {
     switch (currentPage) {
       case 'dashboard':
@@ -1,5 +1,5 @@
 import React, { useState, useEffect } from 'react';
-import { Key, UserPlus } from 'lucide-react';
+import { Key, Send, UserPlus } from 'lucide-react';
 import { Button, Input, Select, Textarea, PhotoUpload } from '../common';
 import type { Person, CreatePersonRequest, UpdatePersonRequest, Job } from '../../types';
 
@@ -10,12 +10,14 @@ interface PersonFormProps {
   onCancel: () => void;
   onResetPassword?: (person: Person) => void;
   onCreateUser?: (person: Person) => void;
+  // Send (or resend) an invitation link so the servidor sets their own password
+  onInvite?: (person: Person) => void;
   onUploadPhoto?: (personId: string, photoData: string) => Promise<void>;
   onDeletePhoto?: (personId: string) => Promise<void>;
   isLoading?: boolean;
 }
 
-export function PersonForm({ person, jobs, onSubmit, onCancel, onResetPassword, onCreateUser, onUploadPhoto, onDeletePhoto, isLoading }: PersonFormProps) {
+export function PersonForm({ person, jobs, onSubmit, onCancel, onResetPassword, onCreateUser, onInvite, onUploadPhoto, onDeletePhoto, isLoading }: PersonFormProps) {
   const [formData, setFormData] = useState<{
     first_name: string;
     last_name: string;
@@ -47,6 +49,8 @@ export function PersonForm({ person, jobs, onSubmit, onCancel, onResetPassword,
     address: '',
     photo_consent: false,
   });
+  // New servidores only: send an invitation link instead of a generated password
+  const [invite, setInvite] = useState(false);
 
   useEffect(() => {
     if (person) {
@@ -98,7 +102,7 @@ export function PersonForm({ person, jobs, onSubmit, onCancel, onResetPassword,
 
     const data = person
       ? { id: person.id, ...cleanedData }
-      : cleanedData;
+      : { ...cleanedData, invite };
 
     await onSubmit(data);
   };
@@ -195,6 +199,20 @@ export function PersonForm({ person, jobs, onSubmit, onCancel, onResetPassword,
         </div>
       </div>
 
+      {!person && (
+        <label className="flex items-center cursor-pointer">
+          <input
+            type="checkbox"
+            checked={invite}
+            onChange={(e) => setInvite(e.target.checked)}
+            className="h-4 w-4 text-primary-600 focus:ring-primary-500 border-gray-300 rounded"
+          />
+          <span className="ml-2 text-sm text-gray-700">
+            Enviar enlace de invitación (el servidor elige su contraseña)
+          </span>
+        </label>
+      )}
+
       {/* Photo consent section */}
       <div className="border-t border-gray-200 pt-4 mt-4">
         <h3 className="text-sm font-medium text-gray-700 mb-3">Consentimiento de Fotografía</h3>
@@ -321,17 +339,30 @@ export function PersonForm({ person, jobs, onSubmit, onCancel, onResetPassword,
               <span className="text-sm text-yellow-800">
                 Este servidor no tiene cuenta de usuario
               </span>
-              {onCreateUser && (
-                <Button
-                  type="button"
-                  variant="primary"
-                  size="sm"
-                  onClick={() => onCreateUser(person)}
-                >
-                  <UserPlus className="w-4 h-4 mr-1" />
-                  Crear Cuenta
-                </Button>
-              )}
+              <div className="flex space-x-2">
+                {onInvite && (
+                  <Button
+                    type="button"
+                    variant="secondary"
+                    size="sm"
+                    onClick={() => onInvite(person)}
+                  >
+                    <Send className="w-4 h-4 mr-1" />
+                    Invitar
+                  </Button>
+                )}
+                {onCreateUser && (
+                  <Button
+                    type="button"
+                    variant="primary"
+                    size="sm"
+                    onClick={() => onCreateUser(person)}
+                  >
+                    <UserPlus className="w-4 h-4 mr-1" />
+                    Crear Cuenta
+                  </Button>
+                )}
+              </div>
             </div>
           )}
         </div>
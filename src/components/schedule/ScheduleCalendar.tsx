@@ -11,7 +11,9 @@ import {
   useSensors,
   closestCenter,
 } from '@dnd-kit/core';
+import { History } from 'lucide-react';
 import { DroppableSlot } from './DroppableSlot';
+import { ServiceDateHistory } from './ServiceDateHistory';
 import type { Schedule, Job, Assignment } from '../../types';
 
 interface ScheduleCalendarProps {
@@ -35,6 +37,7 @@ export function ScheduleCalendar({
 }: ScheduleCalendarProps) {
   const [activeId, setActiveId] = React.useState<string | null>(null);
   const [activeAssignment, setActiveAssignment] = React.useState<Assignment | null>(null);
+  const [historyOpenFor, setHistoryOpenFor] = React.useState<string | null>(null);
 
   const sensors = useSensors(
     useSensor(PointerSensor, {
@@ -184,12 +187,30 @@ export function ScheduleCalendar({
 
           return (
             <div key={serviceDate.id} className="bg-white rounded-lg shadow overflow-hidden">
-              <div className="bg-gray-50 px-4 py-3 border-b border-gray-200">
+              <div className="bg-gray-50 px-4 py-3 border-b border-gray-200 flex items-center justify-between">
                 <h3 className="text-lg font-medium text-gray-900">
                   {format(date, "EEEE d 'de' MMMM, yyyy", { locale: es })}
                 </h3>
+                {editable && (
+                  <button
+                    type="button"
+                    className="flex items-center text-sm text-gray-500 hover:text-gray-700"
+                    onClick={() =>
+                      setHistoryOpenFor(historyOpenFor === serviceDate.id ? null : serviceDate.id)
+                    }
+                  >
+                    <History className="w-4 h-4 mr-1" />
+                    Historial
+                  </button>
+                )}
               </div>
 
+              {historyOpenFor === serviceDate.id && (
+                <div className="px-4 py-3 border-b border-gray-200 bg-gray-50">
+                  <ServiceDateHistory serviceDateId={serviceDate.id} />
+                </div>
+              )}
+
               <div className="p-4">
                 <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
                   {Object.entries(assignmentsByJob).map(([jobId, assignments]) => {
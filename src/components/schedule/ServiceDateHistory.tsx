@@ -0,0 +1,86 @@
+import React from 'react';
+import { format, parseISO } from 'date-fns';
+import { es } from 'date-fns/locale';
+import { scheduleApi } from '../../services/api';
+import type { AssignmentEvent } from '../../types';
+
+interface ServiceDateHistoryProps {
+  serviceDateId: string;
+}
+
+const ACTION_LABELS: Record<string, string> = {
+  generated: 'Generado',
+  assigned: 'Asignado',
+  substituted: 'Sustituido',
+  cleared: 'Vaciado',
+  swapped: 'Intercambiado',
+  moved: 'Movido',
+  undone: 'Deshecho',
+  redone: 'Rehecho',
+};
+
+export function ServiceDateHistory({ serviceDateId }: ServiceDateHistoryProps) {
+  const [events, setEvents] = React.useState<AssignmentEvent[]>([]);
+  const [loading, setLoading] = React.useState(true);
+  const [error, setError] = React.useState<string | null>(null);
+
+  React.useEffect(() => {
+    setLoading(true);
+    scheduleApi
+      .getServiceDateHistory(serviceDateId)
+      .then((data) => {
+        setEvents(data);
+        setError(null);
+      })
+      .catch((e) => setError(e instanceof Error ? e.message : String(e)))
+      .finally(() => setLoading(false));
+  }, [serviceDateId]);
+
+  if (loading) {
+    return <div className="text-sm text-gray-500">Cargando historial...</div>;
+  }
+
+  if (error) {
+    return <div className="text-sm text-red-600">{error}</div>;
+  }
+
+  if (events.length === 0) {
+    return <div className="text-sm text-gray-500">Sin cambios registrados</div>;
+  }
+
+  const describe = (event: AssignmentEvent) => {
+    const slot = event.position_name || `${event.job_name} ${event.position ?? ''}`.trim();
+    switch (event.action) {
+      case 'cleared':
+        return `${slot}: se quitó a ${event.previous_person_name || 'nadie'}`;
+      case 'substituted':
+      case 'swapped':
+      case 'moved':
+      case 'undone':
+      case 'redone':
+        return `${slot}: ${event.previous_person_name || 'vacío'} → ${event.person_name || 'vacío'}`;
+      default:
+        return `${slot}: ${event.person_name || 'vacío'}`;
+    }
+  };
+
+  return (
+    <ol className="relative border-l border-gray-200 ml-2 space-y-3">
+      {events.map((event) => (
+        <li key={event.id} className="ml-4">
+          <div className="absolute w-2 h-2 bg-gray-300 rounded-full -left-1 mt-1.5" />
+          <div className="text-xs text-gray-500">
+            {format(parseISO(event.created_at), "d MMM yyyy HH:mm", { locale: es })}
+            {' · '}
+            {event.changed_by || 'Generador'}
+          </div>
+          <div className="text-sm text-gray-800">
+            <span className="font-medium">{ACTION_LABELS[event.action] || event.action}</span>
+            {' – '}
+            {describe(event)}
+          </div>
+        </li>
+      ))}
+    </ol>
+  );
+}
@@ -4,3 +4,4 @@ export { ConflictList } from './ConflictList';
 export { EditAssignmentModal } from './EditAssignmentModal';
 export { DraggableAssignment } from './DraggableAssignment';
 export { DroppableSlot } from './DroppableSlot';
+export { ServiceDateHistory } from './ServiceDateHistory';
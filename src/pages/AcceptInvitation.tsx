@@ -0,0 +1,112 @@
+import React, { useEffect, useState } from 'react';
+import { format, parseISO } from 'date-fns';
+import { es } from 'date-fns/locale';
+import { Lock } from 'lucide-react';
+import { useAuthStore } from '../stores/authStore';
+import { invitationApi } from '../services/api';
+import { Button } from '../components/common';
+import type { InvitationDetails } from '../types';
+
+// The page an invitation link opens: the servidor picks a password and is signed in
+export function AcceptInvitation({ token, onDone }: { token: string; onDone: () => void }) {
+  const [details, setDetails] = useState<InvitationDetails | null>(null);
+  const [invalid, setInvalid] = useState(false);
+  const [password, setPassword] = useState('');
+  const [confirm, setConfirm] = useState('');
+  const [formError, setFormError] = useState('');
+  const { acceptInvitation, isLoading, error, clearError } = useAuthStore();
+
+  useEffect(() => {
+    invitationApi
+      .get(token)
+      .then(setDetails)
+      .catch(() => setInvalid(true));
+  }, [token]);
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    clearError();
+    setFormError('');
+    if (password.length < 6) {
+      setFormError('La contraseña debe tener al menos 6 caracteres');
+      return;
+    }
+    if (password !== confirm) {
+      setFormError('Las contraseñas no coinciden');
+      return;
+    }
+    if (await acceptInvitation(token, password)) {
+      const username = useAuthStore.getState().user?.username;
+      alert(`¡Listo! Su usuario es "${username}". Úselo con su contraseña la próxima vez.`);
+      onDone();
+    }
+  };
+
+  return (
+    <div className="min-h-screen flex items-center justify-center bg-gray-100">
+      <div className="max-w-md w-full space-y-6 p-8 bg-white rounded-lg shadow-lg">
+        <div className="text-center">
+          <img
+            src="/logo.jpeg"
+            alt="Parroquia San Martín de Porres"
+            className="mx-auto h-32 w-auto"
+          />
+          {details && (
+            <>
+              <h2 className="mt-4 text-xl font-bold text-gray-900">
+                ¡Bienvenido, {details.first_name}!
+              </h2>
+              <p className="mt-2 text-sm text-gray-600">
+                Elija una contraseña para activar su cuenta. El enlace vence el{' '}
+                {format(parseISO(details.expires_at), "d 'de' MMMM", { locale: es })}.
+              </p>
+            </>
+          )}
+        </div>
+
+        {invalid ? (
+          <div className="space-y-4">
+            <div className="bg-red-50 border border-red-200 text-red-700 px-4 py-3 rounded-lg text-sm">
+              Este enlace de invitación no es válido o ya venció. Pida a su coordinador que se lo
+              envíe de nuevo.
+            </div>
+            <Button className="w-full" variant="secondary" onClick={onDone}>
+              Ir al inicio de sesión
+            </Button>
+          </div>
+        ) : (
+          details && (
+            <form className="space-y-4" onSubmit={handleSubmit}>
+              {(formError || error) && (
+                <div className="bg-red-50 border border-red-200 text-red-700 px-4 py-3 rounded-lg text-sm">
+                  {formError || error}
+                </div>
+              )}
+              {[
+                { value: password, set: setPassword, placeholder: 'Contraseña' },
+                { value: confirm, set: setConfirm, placeholder: 'Confirmar contraseña' },
+              ].map((field) => (
+                <div key={field.placeholder} className="relative">
+                  <div className="absolute inset-y-0 left-0 pl-3 flex items-center pointer-events-none">
+                    <Lock className="h-5 w-5 text-gray-400" />
+                  </div>
+                  <input
+                    type="password"
+                    value={field.value}
+                    onChange={(e) => field.set(e.target.value)}
+                    placeholder={field.placeholder}
+                    required
+                    className="block w-full pl-10 pr-3 py-2 border border-gray-300 rounded-lg focus:ring-primary-500 focus:border-primary-500"
+                  />
+                </div>
+              ))}
+              <Button type="submit" className="w-full" isLoading={isLoading}>
+                Activar cuenta
+              </Button>
+            </form>
+          )
+        )}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,215 @@
+import { useEffect, useState } from 'react';
+import { format, parseISO, addMonths, subMonths } from 'date-fns';
+import { es } from 'date-fns/locale';
+import { ChevronLeft, ChevronRight, LogOut, CalendarX, Trash2 } from 'lucide-react';
+import { scheduleApi, myUnavailabilityApi, guardianApi, FamilyAssignment } from '../services/api';
+import { useAuthStore } from '../stores/authStore';
+import { Button, Input, Avatar } from '../components/common';
+import type { GuardianChild, Unavailability } from '../types';
+
+// One login for a parent: the month's assignments of all their children (and the children's
+// siblings), and each child's absences
+export function GuardianDashboard() {
+  const { user, logout } = useAuthStore();
+  const [children, setChildren] = useState<GuardianChild[]>([]);
+  const [selectedChild, setSelectedChild] = useState<string>('');
+  const [currentMonth, setCurrentMonth] = useState(new Date());
+  const [assignments, setAssignments] = useState<FamilyAssignment[]>([]);
+  const [unavailabilities, setUnavailabilities] = useState<Unavailability[]>([]);
+  const [absenceForm, setAbsenceForm] = useState({ date: '', reason: '' });
+  const [isSaving, setIsSaving] = useState(false);
+
+  useEffect(() => {
+    guardianApi
+      .getMyChildren()
+      .then((data) => {
+        setChildren(data);
+        if (data.length > 0) setSelectedChild(data[0].person_id);
+      })
+      .catch((error) => console.error('Error fetching children:', error));
+  }, []);
+
+  useEffect(() => {
+    scheduleApi
+      .getMyFamilyAssignments(format(currentMonth, 'yyyy-MM'))
+      .then(setAssignments)
+      .catch((error) => console.error('Error fetching assignments:', error));
+  }, [currentMonth]);
+
+  useEffect(() => {
+    if (selectedChild) fetchUnavailabilities(selectedChild);
+  }, [selectedChild]);
+
+  const fetchUnavailabilities = async (personId: string) => {
+    try {
+      setUnavailabilities(await myUnavailabilityApi.getAll(personId));
+    } catch (error) {
+      console.error('Error fetching unavailabilities:', error);
+    }
+  };
+
+  const handleAddAbsence = async () => {
+    if (!selectedChild || !absenceForm.date) return;
+    setIsSaving(true);
+    try {
+      await myUnavailabilityApi.setAvailability(
+        absenceForm.date,
+        false,
+        absenceForm.reason || undefined,
+        selectedChild
+      );
+      setAbsenceForm({ date: '', reason: '' });
+      await fetchUnavailabilities(selectedChild);
+    } catch (error) {
+      alert('Error al guardar ausencia: ' + String(error));
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleDeleteAbsence = async (id: string) => {
+    try {
+      await myUnavailabilityApi.delete(id, selectedChild);
+      await fetchUnavailabilities(selectedChild);
+    } catch (error) {
+      alert('Error al eliminar ausencia: ' + String(error));
+    }
+  };
+
+  return (
+    <div className="min-h-screen bg-gradient-to-br from-blue-50 via-purple-50 to-pink-50">
+      <header className="bg-white shadow-sm">
+        <div className="max-w-4xl mx-auto px-4 py-4 flex items-center justify-between">
+          <div>
+            <h1 className="text-xl font-bold text-gray-900">¡Hola, {user?.username}!</h1>
+            <p className="text-sm text-gray-500">Horario de servicio de su familia</p>
+          </div>
+          <button
+            onClick={() => logout()}
+            className="p-2 text-gray-500 hover:text-red-600 hover:bg-red-50 rounded-lg transition-colors"
+            title="Cerrar sesión"
+          >
+            <LogOut className="w-5 h-5" />
+          </button>
+        </div>
+      </header>
+
+      <main className="max-w-4xl mx-auto px-4 py-8 space-y-8">
+        <section className="bg-white rounded-2xl shadow-lg p-6">
+          <div className="flex items-center justify-between mb-4">
+            <button
+              onClick={() => setCurrentMonth(subMonths(currentMonth, 1))}
+              className="p-2 hover:bg-gray-100 rounded-lg"
+            >
+              <ChevronLeft className="w-5 h-5" />
+            </button>
+            <h2 className="text-lg font-semibold text-gray-900 capitalize">
+              {format(currentMonth, 'MMMM yyyy', { locale: es })}
+            </h2>
+            <button
+              onClick={() => setCurrentMonth(addMonths(currentMonth, 1))}
+              className="p-2 hover:bg-gray-100 rounded-lg"
+            >
+              <ChevronRight className="w-5 h-5" />
+            </button>
+          </div>
+          {assignments.length === 0 ? (
+            <p className="text-center text-gray-500 py-6">No hay asignaciones publicadas este mes</p>
+          ) : (
+            <ul className="divide-y divide-gray-100">
+              {assignments.map((a) => (
+                <li
+                  key={`${a.service_date}-${a.person_id}-${a.job_id}-${a.position ?? ''}`}
+                  className="py-3 flex items-center justify-between"
+                >
+                  <div>
+                    <p className="font-medium text-gray-900">{a.person_name}</p>
+                    <p className="text-sm text-gray-500">
+                      {a.job_name}
+                      {a.position_name ? ` · ${a.position_name}` : ''}
+                    </p>
+                  </div>
+                  <span className="text-sm text-gray-700 capitalize">
+                    {format(parseISO(a.service_date), "EEEE d 'de' MMMM", { locale: es })}
+                  </span>
+                </li>
+              ))}
+            </ul>
+          )}
+        </section>
+
+        <section className="bg-white rounded-2xl shadow-lg p-6 space-y-4">
+          <h2 className="text-lg font-semibold text-gray-900 flex items-center">
+            <CalendarX className="w-5 h-5 mr-2 text-red-500" />
+            Ausencias
+          </h2>
+          <div className="flex flex-wrap gap-2">
+            {children.map((child) => (
+              <button
+                key={child.person_id}
+                onClick={() => setSelectedChild(child.person_id)}
+                className={`flex items-center space-x-2 px-3 py-2 rounded-lg border ${
+                  selectedChild === child.person_id
+                    ? 'border-primary-500 bg-primary-50'
+                    : 'border-gray-200 hover:bg-gray-50'
+                }`}
+              >
+                <Avatar
+                  photoUrl={child.photo_url}
+                  firstName={child.first_name}
+                  lastName={child.last_name}
+                  size="sm"
+                />
+                <span>{child.first_name}</span>
+              </button>
+            ))}
+          </div>
+
+          {selectedChild && (
+            <>
+              <div className="flex flex-wrap items-end gap-2">
+                <Input
+                  type="date"
+                  label="Fecha"
+                  value={absenceForm.date}
+                  onChange={(e) => setAbsenceForm({ ...absenceForm, date: e.target.value })}
+                />
+                <Input
+                  label="Motivo (opcional)"
+                  value={absenceForm.reason}
+                  onChange={(e) => setAbsenceForm({ ...absenceForm, reason: e.target.value })}
+                />
+                <Button onClick={handleAddAbsence} isLoading={isSaving} disabled={!absenceForm.date}>
+                  Agregar ausencia
+                </Button>
+              </div>
+              {unavailabilities.length === 0 ? (
+                <p className="text-gray-500">Sin ausencias registradas</p>
+              ) : (
+                <ul className="divide-y divide-gray-100">
+                  {unavailabilities.map((u) => (
+                    <li key={u.id} className="py-2 flex items-center justify-between">
+                      <span>
+                        {format(parseISO(u.start_date), "d 'de' MMMM", { locale: es })}
+                        {u.end_date !== u.start_date &&
+                          ` – ${format(parseISO(u.end_date), "d 'de' MMMM", { locale: es })}`}
+                        {u.reason && <span className="text-gray-500"> · {u.reason}</span>}
+                      </span>
+                      <button
+                        onClick={() => handleDeleteAbsence(u.id)}
+                        className="p-1 text-gray-400 hover:text-red-600"
+                        title="Eliminar ausencia"
+                      >
+                        <Trash2 className="w-4 h-4" />
+                      </button>
+                    </li>
+                  ))}
+                </ul>
+              )}
+            </>
+          )}
+        </section>
+      </main>
+    </div>
+  );
+}
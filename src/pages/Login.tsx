@@ -1,12 +1,40 @@
-import React, { useState } from 'react';
+import React, { useEffect, useRef, useState } from 'react';
 import { Lock, User } from 'lucide-react';
 import { useAuthStore } from '../stores/authStore';
-import { Button, Input } from '../components/common';
+import { Button, Input, Modal } from '../components/common';
+import { getOidcSettings, renderGoogleButton } from '../services/oidc';
 
 export function Login() {
   const [username, setUsername] = useState('');
   const [password, setPassword] = useState('');
-  const { login, isLoading, error, clearError } = useAuthStore();
+  const [code, setCode] = useState('');
+  const [ssoEnabled, setSsoEnabled] = useState(false);
+  const googleButton = useRef<HTMLDivElement>(null);
+  const {
+    login,
+    loginWithIdToken,
+    submitTwoFactorCode,
+    cancelTwoFactor,
+    twoFactorRequired,
+    isLoading,
+    error,
+    clearError,
+  } = useAuthStore();
+
+  // Offer Google sign-in when the API has it configured; passwords always work
+  useEffect(() => {
+    getOidcSettings()
+      .then(async (settings) => {
+        setSsoEnabled(settings.enabled);
+        if (settings.enabled && googleButton.current) {
+          await renderGoogleButton(googleButton.current, settings, (idToken) => {
+            clearError();
+            loginWithIdToken(idToken);
+          });
+        }
+      })
+      .catch(() => setSsoEnabled(false));
+  }, [clearError, loginWithIdToken]);
 
   const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
@@ -14,6 +42,13 @@ export function Login() {
     await login(username, password);
   };
 
+  const handleCodeSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    clearError();
+    await submitTwoFactorCode(code);
+    setCode('');
+  };
+
   return (
     <div className="min-h-screen flex items-center justify-center bg-gray-100">
       <div className="max-w-md w-full space-y-8 p-8 bg-white rounded-lg shadow-lg">
@@ -76,6 +111,51 @@ export function Login() {
             Iniciar Sesión
           </Button>
         </form>
+
+        <div className={ssoEnabled ? 'space-y-4' : 'hidden'}>
+          <div className="flex items-center gap-3 text-sm text-gray-500">
+            <div className="flex-1 border-t border-gray-200" />
+            o
+            <div className="flex-1 border-t border-gray-200" />
+          </div>
+          <div ref={googleButton} className="flex justify-center" />
+        </div>
+
+        {/* Accounts with two-factor on: the password or Google account was right */}
+        <Modal isOpen={twoFactorRequired} onClose={cancelTwoFactor} title="Verificación en dos pasos">
+          <form className="space-y-4" onSubmit={handleCodeSubmit}>
+            <p className="text-sm text-gray-600">
+              Ingrese el código de su aplicación de autenticación o uno de sus códigos de recuperación.
+            </p>
+
+            {error && (
+              <div className="bg-red-50 border border-red-200 text-red-700 px-4 py-3 rounded-lg text-sm">
+                {error}
+              </div>
+            )}
+
+            <Input
+              label="Código"
+              type="text"
+              inputMode="numeric"
+              autoComplete="one-time-code"
+              autoFocus
+              value={code}
+              onChange={(e) => setCode(e.target.value)}
+              placeholder="123456"
+              required
+            />
+
+            <div className="flex justify-end space-x-3 pt-2">
+              <Button type="button" variant="secondary" onClick={cancelTwoFactor}>
+                Cancelar
+              </Button>
+              <Button type="submit" isLoading={isLoading}>
+                Verificar
+              </Button>
+            </div>
+          </form>
+        </Modal>
       </div>
     </div>
   );
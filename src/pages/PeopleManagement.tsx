@@ -1,5 +1,5 @@
 import React, { useEffect, useState, useRef } from 'react';
-import { Plus, Search, Upload, AlertCircle, CheckCircle, X, Key, Copy, Check } from 'lucide-react';
+import { Plus, Search, Upload, AlertCircle, CheckCircle, X, Key, Copy, Check, ChevronLeft, ChevronRight } from 'lucide-react';
 import { Button, Modal, Input } from '../components/common';
 import { PersonList, PersonForm, PersonDetailModal } from '../components/people';
 import { usePeopleStore } from '../stores/peopleStore';
@@ -25,10 +25,15 @@ interface CreatedCredentials {
   personName: string;
   username: string;
   password: string;
+  // Invited instead: the link to send, in place of a username and password
+  inviteLink?: string;
 }
 
+// Servidores per page of the list
+const PAGE_SIZE = 50;
+
 export function PeopleManagement() {
-  const { people, fetchPeople, createPerson, updatePerson, deletePerson, resetPassword, createUserAccount, isLoading } = usePeopleStore();
+  const { people, total, fetchPeople, createPerson, updatePerson, deletePerson, resetPassword, createUserAccount, isLoading } = usePeopleStore();
   const { jobs, fetchJobs } = useJobsStore();
 
   const [isModalOpen, setIsModalOpen] = useState(false);
@@ -37,22 +42,46 @@ export function PeopleManagement() {
   const [isDetailModalOpen, setIsDetailModalOpen] = useState(false);
   const [viewingPerson, setViewingPerson] = useState<Person | null>(null);
   const [createdCredentials, setCreatedCredentials] = useState<CreatedCredentials | null>(null);
-  const [copiedField, setCopiedField] = useState<'username' | 'password' | null>(null);
+  const [copiedField, setCopiedField] = useState<'username' | 'password' | 'link' | null>(null);
   const [editingPerson, setEditingPerson] = useState<Person | null>(null);
   const [searchQuery, setSearchQuery] = useState('');
   const [filterJob, setFilterJob] = useState<string>('');
   const [showInactive, setShowInactive] = useState(false);
+  const [debouncedQuery, setDebouncedQuery] = useState('');
+  const [page, setPage] = useState(1);
 
   // CSV Import state
   const [importResult, setImportResult] = useState<ImportResult | null>(null);
   const [isImporting, setIsImporting] = useState(false);
   const fileInputRef = useRef<HTMLInputElement>(null);
 
+  // The list is searched, filtered and paged by the server
+  const loadPeople = () =>
+    fetchPeople({
+      q: debouncedQuery,
+      job_id: filterJob || undefined,
+      active: showInactive ? undefined : true,
+      page,
+      limit: PAGE_SIZE,
+    });
+
   useEffect(() => {
-    fetchPeople();
     fetchJobs();
   }, []);
 
+  useEffect(() => {
+    loadPeople();
+  }, [debouncedQuery, filterJob, showInactive, page]);
+
+  // Search once typing pauses, from the first page
+  useEffect(() => {
+    const timer = setTimeout(() => {
+      setDebouncedQuery(searchQuery);
+      setPage(1);
+    }, 300);
+    return () => clearTimeout(timer);
+  }, [searchQuery]);
+
   const handleCreateOrUpdate = async (data: CreatePersonRequest | UpdatePersonRequest) => {
     if ('id' in data) {
       await updatePerson(data);
@@ -62,11 +91,12 @@ export function PeopleManagement() {
       const result = await createPerson(data);
       setIsModalOpen(false);
       setEditingPerson(null);
-      // Show credentials modal for new servidor
+      // Show credentials (or the invitation link) for new servidor
       setCreatedCredentials({
         personName: `${result.first_name} ${result.last_name}`,
-        username: result.username,
-        password: result.generated_password,
+        username: result.username || '',
+        password: result.generated_password || '',
+        inviteLink: result.invitation && peopleApi.invitationUrl(result.invitation.token),
       });
       setIsCredentialsModalOpen(true);
     }
@@ -110,7 +140,31 @@ export function PeopleManagement() {
     }
   };
 
-  const copyToClipboard = async (text: string, field: 'username' | 'password') => {
+  const handleInvite = async (person: Person) => {
+    try {
+      // A pending invitation gets a new link; otherwise this is the first one
+      const pending = await peopleApi
+        .getInvitation(person.id)
+        .then((invitation) => !invitation.accepted_at)
+        .catch(() => false);
+      const invitation = pending
+        ? await peopleApi.resendInvitation(person.id)
+        : await peopleApi.invite(person.id);
+      setIsModalOpen(false);
+      setEditingPerson(null);
+      setCreatedCredentials({
+        personName: `${person.first_name} ${person.last_name}`,
+        username: '',
+        password: '',
+        inviteLink: peopleApi.invitationUrl(invitation.token),
+      });
+      setIsCredentialsModalOpen(true);
+    } catch (error) {
+      alert(`Error al enviar invitación: ${error}`);
+    }
+  };
+
+  const copyToClipboard = async (text: string, field: 'username' | 'password' | 'link') => {
     await navigator.clipboard.writeText(text);
     setCopiedField(field);
     setTimeout(() => setCopiedField(null), 2000);
@@ -139,7 +193,7 @@ export function PeopleManagement() {
   const handleUploadPhoto = async (personId: string, photoData: string) => {
     await peopleApi.uploadPhoto(personId, photoData);
     // Refresh the person data
-    await fetchPeople();
+    await loadPeople();
     // Update editingPerson if we're editing this person
     if (editingPerson?.id === personId) {
       const updated = people.find(p => p.id === personId);
@@ -152,7 +206,7 @@ export function PeopleManagement() {
   const handleDeletePhoto = async (personId: string) => {
     await peopleApi.deletePhoto(personId);
     // Refresh the person data
-    await fetchPeople();
+    await loadPeople();
     // Update editingPerson if we're editing this person
     if (editingPerson?.id === personId) {
       const updated = people.find(p => p.id === personId);
@@ -206,13 +260,13 @@ export function PeopleManagement() {
     return result;
   };
 
-  const checkDuplicates = (csvPeople: CsvPerson[]): ImportResult => {
+  const checkDuplicates = (csvPeople: CsvPerson[], existing: Person[]): ImportResult => {
     const success: CsvPerson[] = [];
     const duplicates: CsvPerson[] = [];
     const errors: { row: number; error: string }[] = [];
 
     csvPeople.forEach((csvPerson, index) => {
-      const isDuplicate = people.some(
+      const isDuplicate = existing.some(
         p => p.first_name.toLowerCase() === csvPerson.first_name.toLowerCase() &&
              p.last_name.toLowerCase() === csvPerson.last_name.toLowerCase()
       );
@@ -240,7 +294,8 @@ export function PeopleManagement() {
         return;
       }
 
-      const result = checkDuplicates(csvPeople);
+      // Against everyone, not just the page on screen
+      const result = checkDuplicates(csvPeople, await peopleApi.getAll());
       setImportResult(result);
       setIsImportModalOpen(true);
     } catch (error) {
@@ -268,7 +323,7 @@ export function PeopleManagement() {
         });
       }
 
-      await fetchPeople();
+      await loadPeople();
       setIsImportModalOpen(false);
       setImportResult(null);
       alert(`Se importaron ${importResult.success.length} servidores exitosamente`);
@@ -279,17 +334,8 @@ export function PeopleManagement() {
     }
   };
 
-  const filteredPeople = people.filter((person) => {
-    const matchesSearch =
-      person.first_name.toLowerCase().includes(searchQuery.toLowerCase()) ||
-      person.last_name.toLowerCase().includes(searchQuery.toLowerCase()) ||
-      person.email?.toLowerCase().includes(searchQuery.toLowerCase());
-
-    const matchesJob = !filterJob || person.job_ids.includes(filterJob);
-    const matchesActive = showInactive || person.active;
-
-    return matchesSearch && matchesJob && matchesActive;
-  });
+  const pageCount = Math.max(1, Math.ceil(total / PAGE_SIZE));
+  const firstShown = (page - 1) * PAGE_SIZE;
 
   return (
     <div className="space-y-6">
@@ -325,13 +371,13 @@ export function PeopleManagement() {
               <Input
                 value={searchQuery}
                 onChange={(e) => setSearchQuery(e.target.value)}
-                placeholder="Buscar por nombre o correo..."
+                placeholder="Buscar por nombre, correo o teléfono..."
                 className="pl-10"
               />
             </div>
             <select
               value={filterJob}
-              onChange={(e) => setFilterJob(e.target.value)}
+              onChange={(e) => { setFilterJob(e.target.value); setPage(1); }}
               className="px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-primary-500 focus:border-primary-500"
             >
               <option value="">Todos los Servicios</option>
@@ -343,7 +389,7 @@ export function PeopleManagement() {
               <input
                 type="checkbox"
                 checked={showInactive}
-                onChange={(e) => setShowInactive(e.target.checked)}
+                onChange={(e) => { setShowInactive(e.target.checked); setPage(1); }}
                 className="h-4 w-4 text-primary-600 focus:ring-primary-500 border-gray-300 rounded"
               />
               <span className="ml-2 text-sm text-gray-700">Mostrar Inactivos</span>
@@ -352,7 +398,7 @@ export function PeopleManagement() {
         </div>
 
         <PersonList
-          people={filteredPeople}
+          people={people}
           jobs={jobs}
           onEdit={handleEdit}
           onDelete={handleDelete}
@@ -362,8 +408,23 @@ export function PeopleManagement() {
           onViewDetail={handleViewDetail}
         />
 
-        <div className="px-6 py-4 border-t border-gray-200 text-sm text-gray-500">
-          Mostrando {filteredPeople.length} de {people.length} servidores
+        <div className="px-6 py-4 border-t border-gray-200 flex items-center justify-between text-sm text-gray-500">
+          <span>
+            Mostrando {people.length > 0 ? `${firstShown + 1}-${firstShown + people.length}` : 0} de {total} servidores
+          </span>
+          {pageCount > 1 && (
+            <div className="flex items-center space-x-2">
+              <Button variant="secondary" size="sm" onClick={() => setPage(page - 1)} disabled={page <= 1}>
+                <ChevronLeft className="w-4 h-4" />
+              </Button>
+              <span>
+                Página {page} de {pageCount}
+              </span>
+              <Button variant="secondary" size="sm" onClick={() => setPage(page + 1)} disabled={page >= pageCount}>
+                <ChevronRight className="w-4 h-4" />
+              </Button>
+            </div>
+          )}
         </div>
       </div>
 
@@ -380,6 +441,7 @@ export function PeopleManagement() {
           onCancel={() => { setIsModalOpen(false); setEditingPerson(null); }}
           onResetPassword={handleResetPassword}
           onCreateUser={handleCreateUser}
+          onInvite={handleInvite}
           onUploadPhoto={handleUploadPhoto}
           onDeletePhoto={handleDeletePhoto}
           isLoading={isLoading}
@@ -402,22 +464,24 @@ export function PeopleManagement() {
                 </h3>
               </div>
               <p className="text-sm text-green-700 mb-4">
-                Guarde esta información. La contraseña solo se muestra una vez.
+                {createdCredentials.inviteLink
+                  ? `Envíe este enlace a ${createdCredentials.personName}; con él elige su contraseña. Solo se muestra una vez.`
+                  : 'Guarde esta información. La contraseña solo se muestra una vez.'}
               </p>
 
-              <div className="space-y-3">
+              {createdCredentials.inviteLink ? (
                 <div className="bg-white rounded-lg p-3 border border-green-200">
-                  <label className="text-xs text-gray-500 uppercase tracking-wide">Usuario</label>
+                  <label className="text-xs text-gray-500 uppercase tracking-wide">Enlace de invitación</label>
                   <div className="flex items-center justify-between mt-1">
-                    <code className="text-lg font-mono font-bold text-gray-900">
-                      {createdCredentials.username}
+                    <code className="text-sm font-mono text-gray-900 break-all">
+                      {createdCredentials.inviteLink}
                     </code>
                     <button
-                      onClick={() => copyToClipboard(createdCredentials.username, 'username')}
+                      onClick={() => copyToClipboard(createdCredentials.inviteLink!, 'link')}
                       className="p-2 text-gray-400 hover:text-gray-600 rounded"
                       title="Copiar"
                     >
-                      {copiedField === 'username' ? (
+                      {copiedField === 'link' ? (
                         <Check className="w-4 h-4 text-green-600" />
                       ) : (
                         <Copy className="w-4 h-4" />
@@ -425,27 +489,49 @@ export function PeopleManagement() {
                     </button>
                   </div>
                 </div>
+              ) : (
+                <div className="space-y-3">
+                  <div className="bg-white rounded-lg p-3 border border-green-200">
+                    <label className="text-xs text-gray-500 uppercase tracking-wide">Usuario</label>
+                    <div className="flex items-center justify-between mt-1">
+                      <code className="text-lg font-mono font-bold text-gray-900">
+                        {createdCredentials.username}
+                      </code>
+                      <button
+                        onClick={() => copyToClipboard(createdCredentials.username, 'username')}
+                        className="p-2 text-gray-400 hover:text-gray-600 rounded"
+                        title="Copiar"
+                      >
+                        {copiedField === 'username' ? (
+                          <Check className="w-4 h-4 text-green-600" />
+                        ) : (
+                          <Copy className="w-4 h-4" />
+                        )}
+                      </button>
+                    </div>
+                  </div>
 
-                <div className="bg-white rounded-lg p-3 border border-green-200">
-                  <label className="text-xs text-gray-500 uppercase tracking-wide">Contraseña</label>
-                  <div className="flex items-center justify-between mt-1">
-                    <code className="text-lg font-mono font-bold text-gray-900">
-                      {createdCredentials.password}
-                    </code>
-                    <button
-                      onClick={() => copyToClipboard(createdCredentials.password, 'password')}
-                      className="p-2 text-gray-400 hover:text-gray-600 rounded"
-                      title="Copiar"
-                    >
-                      {copiedField === 'password' ? (
-                        <Check className="w-4 h-4 text-green-600" />
-                      ) : (
-                        <Copy className="w-4 h-4" />
-                      )}
-                    </button>
+                  <div className="bg-white rounded-lg p-3 border border-green-200">
+                    <label className="text-xs text-gray-500 uppercase tracking-wide">Contraseña</label>
+                    <div className="flex items-center justify-between mt-1">
+                      <code className="text-lg font-mono font-bold text-gray-900">
+                        {createdCredentials.password}
+                      </code>
+                      <button
+                        onClick={() => copyToClipboard(createdCredentials.password, 'password')}
+                        className="p-2 text-gray-400 hover:text-gray-600 rounded"
+                        title="Copiar"
+                      >
+                        {copiedField === 'password' ? (
+                          <Check className="w-4 h-4 text-green-600" />
+                        ) : (
+                          <Copy className="w-4 h-4" />
+                        )}
+                      </button>
+                    </div>
                   </div>
                 </div>
-              </div>
+              )}
             </div>
 
             <div className="bg-yellow-50 border border-yellow-200 rounded-lg p-3">
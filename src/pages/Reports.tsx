@@ -3,6 +3,7 @@ import { Download, RefreshCw } from 'lucide-react';
 import { Button, Select } from '../components/common';
 import { FairnessReport } from '../components/reports';
 import { useScheduleStore } from '../stores/scheduleStore';
+import { scheduleApi } from '../services/api';
 
 export function Reports() {
   const { fairnessScores, fetchFairnessScores, isLoading } = useScheduleStore();
@@ -18,6 +19,20 @@ export function Reports() {
     fetchFairnessScores(selectedYear);
   };
 
+  const handleDownloadWorkbook = async () => {
+    try {
+      const blob = await scheduleApi.downloadFairnessWorkbook(selectedYear);
+      const url = URL.createObjectURL(blob);
+      const link = document.createElement('a');
+      link.href = url;
+      link.download = `equidad-${selectedYear}.xlsx`;
+      link.click();
+      URL.revokeObjectURL(url);
+    } catch (error) {
+      alert('Error al exportar: ' + String(error));
+    }
+  };
+
   return (
     <div className="space-y-6">
       <div className="flex items-center justify-between">
@@ -35,6 +50,10 @@ export function Reports() {
             <RefreshCw className="w-4 h-4 mr-2" />
             Actualizar
           </Button>
+          <Button variant="secondary" onClick={handleDownloadWorkbook}>
+            <Download className="w-4 h-4 mr-2" />
+            Excel
+          </Button>
         </div>
       </div>
 
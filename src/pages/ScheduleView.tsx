@@ -1,10 +1,10 @@
 import React, { useEffect, useState } from 'react';
-import { Plus, Download, Send, Trash2, AlertCircle, XCircle, CheckCircle } from 'lucide-react';
+import { Plus, Download, Send, Trash2, AlertCircle, XCircle, CheckCircle, MessageCircle, Image as ImageIcon } from 'lucide-react';
 import { Button, Modal } from '../components/common';
 import { ScheduleCalendar, ScheduleGenerator, ConflictList, EditAssignmentModal } from '../components/schedule';
 import { useScheduleStore } from '../stores/scheduleStore';
 import { useJobsStore } from '../stores/jobsStore';
-import { scheduleApi } from '../services/api';
+import { scheduleApi, exportApi } from '../services/api';
 import type { GenerateScheduleRequest, Assignment, EmptySlot } from '../types';
 
 // Toast notification component
@@ -181,6 +181,49 @@ export function ScheduleView() {
     }
   };
 
+  const handleCopySummary = async () => {
+    if (!currentSchedule) return;
+    try {
+      const summary = await exportApi.getSummary(currentSchedule.id);
+      await navigator.clipboard.writeText(summary);
+      showMessage('success', 'Resumen copiado, listo para pegar en WhatsApp');
+    } catch (error) {
+      showMessage('error', `Error al copiar el resumen: ${error}`);
+    }
+  };
+
+  const handleExportImage = async () => {
+    if (!currentSchedule) return;
+    try {
+      const blob = await exportApi.downloadImage(currentSchedule.id);
+      const url = URL.createObjectURL(blob);
+      const link = document.createElement('a');
+      link.href = url;
+      const month = String(currentSchedule.month).padStart(2, '0');
+      link.download = `horario-${currentSchedule.year}-${month}.png`;
+      link.click();
+      URL.revokeObjectURL(url);
+    } catch (error) {
+      showMessage('error', `Error al exportar: ${error}`);
+    }
+  };
+
+  const handleExportCsv = async (layout: 'list' | 'grid') => {
+    if (!currentSchedule) return;
+    try {
+      const blob = await exportApi.downloadCsv(currentSchedule.id, layout);
+      const url = URL.createObjectURL(blob);
+      const link = document.createElement('a');
+      link.href = url;
+      const month = String(currentSchedule.month).padStart(2, '0');
+      link.download = `horario-${currentSchedule.year}-${month}${layout === 'grid' ? '-cuadricula' : ''}.csv`;
+      link.click();
+      URL.revokeObjectURL(url);
+    } catch (error) {
+      showMessage('error', `Error al exportar: ${error}`);
+    }
+  };
+
   const handleEditAssignment = (assignment: Assignment, serviceDate: string) => {
     setEditingAssignment(assignment);
     setEditingServiceDate(serviceDate);
@@ -194,6 +237,7 @@ export function ScheduleView() {
       await scheduleApi.updateAssignment({
         assignment_id: assignmentId,
         new_person_id: newPersonId,
+        expected_person_id: editingAssignment?.person_id ?? null,
       });
 
       if (preview) {
@@ -423,6 +467,37 @@ export function ScheduleView() {
                         <Download className="w-4 h-4 mr-1" />
                         Exportar
                       </Button>
+                      <Button variant="secondary" size="sm" onClick={() => handleExportCsv('list')}>
+                        <Download className="w-4 h-4 mr-1" />
+                        CSV
+                      </Button>
+                      <Button
+                        variant="secondary"
+                        size="sm"
+                        onClick={() => handleExportCsv('grid')}
+                        title="Fechas en columnas y posiciones en filas, como en el boletín"
+                      >
+                        <Download className="w-4 h-4 mr-1" />
+                        Cuadrícula
+                      </Button>
+                      <Button
+                        variant="secondary"
+                        size="sm"
+                        onClick={handleExportImage}
+                        title="Imagen del horario para compartir en grupos de mensajería"
+                      >
+                        <ImageIcon className="w-4 h-4 mr-1" />
+                        Imagen
+                      </Button>
+                      <Button
+                        variant="secondary"
+                        size="sm"
+                        onClick={handleCopySummary}
+                        title="Copiar el horario como texto para el grupo de WhatsApp"
+                      >
+                        <MessageCircle className="w-4 h-4 mr-1" />
+                        WhatsApp
+                      </Button>
                       {currentSchedule.status === 'DRAFT' && (
                         <Button size="sm" onClick={handlePublish} isLoading={isPublishing}>
                           <Send className="w-4 h-4 mr-1" />
@@ -435,6 +510,14 @@ export function ScheduleView() {
                     </div>
                   )}
                 </div>
+                {!preview && currentSchedule?.conflicts && currentSchedule.conflicts.length > 0 && (
+                  <div className="px-6 py-4 border-b border-gray-200">
+                    <h4 className="text-sm font-medium text-yellow-800 mb-2">
+                      Conflictos ({currentSchedule.conflicts.length})
+                    </h4>
+                    <ConflictList conflicts={currentSchedule.conflicts} />
+                  </div>
+                )}
               </div>
 
               <ScheduleCalendar
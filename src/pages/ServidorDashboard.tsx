@@ -1,11 +1,12 @@
 import React, { useEffect, useState } from 'react';
 import { format, parseISO, isSameMonth, startOfMonth, addMonths, subMonths, eachDayOfInterval, startOfWeek, endOfWeek, endOfMonth, isToday, isSameDay, isSunday } from 'date-fns';
 import { es } from 'date-fns/locale';
-import { Calendar, ChevronLeft, ChevronRight, Star, LogOut, Key, XCircle, CalendarX, Trash2, Camera } from 'lucide-react';
-import { scheduleApi, myUnavailabilityApi, myPhotoApi, peopleApi, MyAssignment } from '../services/api';
+import { Calendar, ChevronLeft, ChevronRight, Star, LogOut, Key, XCircle, CalendarX, Trash2, Camera, CalendarPlus, Link2, Bell, BellOff, SlidersHorizontal } from 'lucide-react';
+import { scheduleApi, myUnavailabilityApi, myPhotoApi, myNotificationsApi, peopleApi, MyAssignment } from '../services/api';
 import { useAuthStore } from '../stores/authStore';
+import { pushSupported, isPushEnabled, enablePush, disablePush } from '../services/push';
 import { Button, Modal, Input, Avatar, PhotoUpload } from '../components/common';
-import type { Unavailability, Person } from '../types';
+import type { Unavailability, Person, NotificationChannel } from '../types';
 
 export function ServidorDashboard() {
   const { user, logout, changePassword } = useAuthStore();
@@ -28,6 +29,18 @@ export function ServidorDashboard() {
   const [personData, setPersonData] = useState<Person | null>(null);
   const [isPhotoModalOpen, setIsPhotoModalOpen] = useState(false);
 
+  // Web Push on this browser
+  const [pushEnabled, setPushEnabled] = useState(false);
+
+  // Notification preferences ('' = parish default)
+  const [isPreferencesModalOpen, setIsPreferencesModalOpen] = useState(false);
+  const [preferencesForm, setPreferencesForm] = useState({ channel: '', reminderDays: '', digest: false });
+  const [isSavingPreferences, setIsSavingPreferences] = useState(false);
+
+  useEffect(() => {
+    isPushEnabled().then(setPushEnabled).catch(() => setPushEnabled(false));
+  }, []);
+
   useEffect(() => {
     if (user?.person_id) {
       fetchAssignments();
@@ -79,7 +92,7 @@ export function ServidorDashboard() {
     if (existing) {
       // Ask to delete
       if (window.confirm(`¿Deseas eliminar tu ausencia del ${format(date, "d 'de' MMMM", { locale: es })}?`)) {
-        handleDeleteUnavailability(existing.id);
+        handleMarkAvailable(date);
       }
     } else {
       // Open modal to add
@@ -95,7 +108,7 @@ export function ServidorDashboard() {
     setIsSavingUnavailability(true);
     try {
       const dateStr = format(selectedDate, 'yyyy-MM-dd');
-      await myUnavailabilityApi.create([dateStr], unavailabilityReason || undefined);
+      await myUnavailabilityApi.setAvailability(dateStr, false, unavailabilityReason || undefined);
       await fetchUnavailabilities();
       setIsUnavailabilityModalOpen(false);
       setSelectedDate(null);
@@ -108,6 +121,16 @@ export function ServidorDashboard() {
     }
   };
 
+  const handleMarkAvailable = async (date: Date) => {
+    try {
+      await myUnavailabilityApi.setAvailability(format(date, 'yyyy-MM-dd'), true);
+      await fetchUnavailabilities();
+    } catch (error) {
+      console.error('Error deleting unavailability:', error);
+      alert('Error al eliminar ausencia: ' + String(error));
+    }
+  };
+
   const handleDeleteUnavailability = async (id: string) => {
     try {
       await myUnavailabilityApi.delete(id);
@@ -141,6 +164,96 @@ export function ServidorDashboard() {
     }
   };
 
+  const handleDownloadCalendar = async () => {
+    if (!user?.person_id) return;
+    try {
+      const blob = await peopleApi.downloadCalendar(user.person_id);
+      const url = URL.createObjectURL(blob);
+      const link = document.createElement('a');
+      link.href = url;
+      link.download = 'servicios.ics';
+      link.click();
+      URL.revokeObjectURL(url);
+    } catch (error) {
+      console.error('Error downloading calendar:', error);
+      alert('Error al descargar el calendario: ' + String(error));
+    }
+  };
+
+  // A subscribed phone calendar keeps itself up to date, unlike the one-off download. The
+  // link is only shown when it is created, so getting it again replaces the old one.
+  const handleSubscribeCalendar = async () => {
+    if (!user?.person_id) return;
+    try {
+      const existing = await peopleApi.getCalendarFeed(user.person_id);
+      if (
+        existing &&
+        !window.confirm(
+          'Ya tiene un enlace de calendario. ¿Crear uno nuevo? El anterior dejará de actualizarse.'
+        )
+      ) {
+        return;
+      }
+      const feed = await peopleApi.rotateCalendarFeed(user.person_id);
+      const url = peopleApi.calendarFeedUrl(feed.token);
+      await navigator.clipboard?.writeText(url).catch(() => undefined);
+      window.prompt('Enlace copiado. Agréguelo en su calendario como "suscripción por URL":', url);
+    } catch (error) {
+      console.error('Error getting calendar feed:', error);
+      alert('Error al obtener el enlace del calendario: ' + String(error));
+    }
+  };
+
+  const handleOpenPreferences = async () => {
+    try {
+      const preferences = await myNotificationsApi.getPreferences();
+      setPreferencesForm({
+        channel: preferences.channel ?? '',
+        reminderDays: preferences.reminder_days_before?.toString() ?? '',
+        digest: preferences.digest,
+      });
+      setIsPreferencesModalOpen(true);
+    } catch (error) {
+      console.error('Error loading notification preferences:', error);
+      alert('Error al cargar las preferencias: ' + String(error));
+    }
+  };
+
+  const handleSavePreferences = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setIsSavingPreferences(true);
+    try {
+      await myNotificationsApi.updatePreferences({
+        channel: (preferencesForm.channel || null) as NotificationChannel | null,
+        reminder_days_before: preferencesForm.reminderDays === '' ? null : parseInt(preferencesForm.reminderDays),
+        digest: preferencesForm.digest,
+      });
+      setIsPreferencesModalOpen(false);
+    } catch (error) {
+      alert('Error al guardar las preferencias: ' + String(error));
+    } finally {
+      setIsSavingPreferences(false);
+    }
+  };
+
+  const handleTogglePush = async () => {
+    try {
+      if (pushEnabled) {
+        await disablePush();
+        setPushEnabled(false);
+        return;
+      }
+      const enabled = await enablePush();
+      setPushEnabled(enabled);
+      if (!enabled) {
+        alert('No se pudieron activar las notificaciones en este navegador.');
+      }
+    } catch (error) {
+      console.error('Error toggling push notifications:', error);
+      alert('Error con las notificaciones: ' + String(error));
+    }
+  };
+
   // Separate upcoming and past assignments
   const today = new Date();
   today.setHours(0, 0, 0, 0);
@@ -221,6 +334,36 @@ export function ServidorDashboard() {
             </div>
           </div>
           <div className="flex items-center space-x-2">
+            <button
+              onClick={handleDownloadCalendar}
+              className="p-2 text-gray-500 hover:text-gray-700 hover:bg-gray-100 rounded-lg transition-colors"
+              title="Agregar mis fechas al calendario del teléfono"
+            >
+              <CalendarPlus className="w-5 h-5" />
+            </button>
+            <button
+              onClick={handleSubscribeCalendar}
+              className="p-2 text-gray-500 hover:text-gray-700 hover:bg-gray-100 rounded-lg transition-colors"
+              title="Suscribir mi calendario (se actualiza solo)"
+            >
+              <Link2 className="w-5 h-5" />
+            </button>
+            {pushSupported() && (
+              <button
+                onClick={handleTogglePush}
+                className="p-2 text-gray-500 hover:text-gray-700 hover:bg-gray-100 rounded-lg transition-colors"
+                title={pushEnabled ? 'Desactivar notificaciones en este dispositivo' : 'Recibir notificaciones en este dispositivo'}
+              >
+                {pushEnabled ? <Bell className="w-5 h-5" /> : <BellOff className="w-5 h-5" />}
+              </button>
+            )}
+            <button
+              onClick={handleOpenPreferences}
+              className="p-2 text-gray-500 hover:text-gray-700 hover:bg-gray-100 rounded-lg transition-colors"
+              title="Cómo recibir mis avisos"
+            >
+              <SlidersHorizontal className="w-5 h-5" />
+            </button>
             <button
               onClick={() => setIsPasswordModalOpen(true)}
               className="p-2 text-gray-500 hover:text-gray-700 hover:bg-gray-100 rounded-lg transition-colors"
@@ -561,6 +704,56 @@ export function ServidorDashboard() {
         )}
       </main>
 
+      {/* Notification Preferences Modal */}
+      <Modal
+        isOpen={isPreferencesModalOpen}
+        onClose={() => setIsPreferencesModalOpen(false)}
+        title="Mis Avisos"
+      >
+        <form onSubmit={handleSavePreferences} className="space-y-4">
+          <div>
+            <label className="block text-sm font-medium text-gray-700 mb-1">Recibir avisos por</label>
+            <select
+              value={preferencesForm.channel}
+              onChange={(e) => setPreferencesForm({ ...preferencesForm, channel: e.target.value })}
+              className="w-full px-3 py-2 border border-gray-300 rounded-lg text-sm focus:ring-2 focus:ring-primary-500 focus:border-transparent"
+            >
+              <option value="">Lo que decida la parroquia</option>
+              <option value="whatsapp">WhatsApp</option>
+              <option value="email">Correo electrónico</option>
+              <option value="sms">SMS</option>
+              <option value="push">Notificaciones en este teléfono</option>
+              <option value="in_app">Solo en la aplicación</option>
+            </select>
+          </div>
+          <Input
+            type="number"
+            min={0}
+            max={60}
+            label="Recordarme días antes (vacío = lo de la parroquia, 0 = no recordar)"
+            value={preferencesForm.reminderDays}
+            onChange={(e) => setPreferencesForm({ ...preferencesForm, reminderDays: e.target.value })}
+          />
+          <label className="flex items-center space-x-2 text-sm text-gray-700">
+            <input
+              type="checkbox"
+              checked={preferencesForm.digest}
+              onChange={(e) => setPreferencesForm({ ...preferencesForm, digest: e.target.checked })}
+            />
+            <span>Juntar mis avisos en un solo mensaje al día</span>
+          </label>
+
+          <div className="flex justify-end space-x-3 pt-4">
+            <Button type="button" variant="secondary" onClick={() => setIsPreferencesModalOpen(false)}>
+              Cancelar
+            </Button>
+            <Button type="submit" isLoading={isSavingPreferences}>
+              Guardar
+            </Button>
+          </div>
+        </form>
+      </Modal>
+
       {/* Password Change Modal */}
       <Modal
         isOpen={isPasswordModalOpen}
@@ -1,13 +1,21 @@
 import React, { useEffect, useState } from 'react';
-import { Key, Save } from 'lucide-react';
+import { Key, RefreshCw, Save, ShieldCheck } from 'lucide-react';
 import { Button, Input, Table, Modal } from '../components/common';
 import { useJobsStore } from '../stores/jobsStore';
 import { useAuthStore } from '../stores/authStore';
-import type { Job } from '../types';
+import { twoFactorApi } from '../services/api';
+import { desktopSyncAvailable, findOrphanUsers, syncPersonDeletions } from '../services/desktopSync';
+import type {
+  Job,
+  OrphanUser,
+  SyncDeletedPeopleResponse,
+  TwoFactorSetup,
+  TwoFactorStatus,
+} from '../types';
 
 export function Settings() {
   const { jobs, fetchJobs } = useJobsStore();
-  const { changePassword, isLoading, error, clearError } = useAuthStore();
+  const { user, changePassword, isLoading, error, clearError } = useAuthStore();
 
   const [isPasswordModalOpen, setIsPasswordModalOpen] = useState(false);
   const [passwordForm, setPasswordForm] = useState({
@@ -116,6 +124,7 @@ export function Settings() {
           <Key className="w-4 h-4 mr-2" />
           Cambiar Contraseña
         </Button>
+        <TwoFactorSection />
       </div>
 
       {/* Jobs Section */}
@@ -136,6 +145,8 @@ export function Settings() {
         />
       </div>
 
+      {user?.role === 'admin' && desktopSyncAvailable() && <DesktopSyncSection />}
+
       {/* App Info */}
       <div className="bg-white rounded-lg shadow p-6">
         <h2 className="text-lg font-medium text-gray-900 mb-4">Acerca de</h2>
@@ -206,3 +217,220 @@ export function Settings() {
     </div>
   );
 }
+
+// Authenticator app as a second factor at login (admins and coordinators)
+function TwoFactorSection() {
+  const [status, setStatus] = useState<TwoFactorStatus | null>(null);
+  const [setup, setSetup] = useState<TwoFactorSetup | null>(null);
+  // What the code typed in the modal is for
+  const [action, setAction] = useState<'enable' | 'disable' | 'recovery' | null>(null);
+  const [code, setCode] = useState('');
+  const [recoveryCodes, setRecoveryCodes] = useState<string[] | null>(null);
+  const [error, setError] = useState('');
+
+  const loadStatus = () => {
+    twoFactorApi.getStatus().then(setStatus).catch(() => setStatus(null));
+  };
+
+  useEffect(() => {
+    loadStatus();
+  }, []);
+
+  const startSetup = async () => {
+    setError('');
+    try {
+      setSetup(await twoFactorApi.setup());
+      setAction('enable');
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const close = () => {
+    setAction(null);
+    setSetup(null);
+    setCode('');
+    setRecoveryCodes(null);
+    setError('');
+    loadStatus();
+  };
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setError('');
+    try {
+      if (action === 'enable') {
+        setRecoveryCodes((await twoFactorApi.enable(code)).recovery_codes);
+      } else if (action === 'recovery') {
+        setRecoveryCodes((await twoFactorApi.regenerateRecoveryCodes(code)).recovery_codes);
+      } else if (action === 'disable') {
+        await twoFactorApi.disable(code);
+        close();
+      }
+      setCode('');
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  if (!status) return null;
+
+  return (
+    <div className="mt-6 pt-6 border-t border-gray-200">
+      <h3 className="text-sm font-medium text-gray-900">Verificación en dos pasos</h3>
+      <p className="text-sm text-gray-500 mt-1">
+        {status.enabled
+          ? `Activa. Quedan ${status.recovery_codes_left} códigos de recuperación.`
+          : 'Pide un código de una aplicación de autenticación además de la contraseña.'}
+      </p>
+      {error && !action && <p className="text-sm text-red-600 mt-2">{error}</p>}
+      <div className="flex gap-3 mt-3">
+        {status.enabled ? (
+          <>
+            <Button variant="secondary" onClick={() => setAction('recovery')}>
+              Nuevos códigos de recuperación
+            </Button>
+            <Button variant="danger" onClick={() => setAction('disable')}>
+              Desactivar
+            </Button>
+          </>
+        ) : (
+          <Button onClick={startSetup}>
+            <ShieldCheck className="w-4 h-4 mr-2" />
+            Activar
+          </Button>
+        )}
+      </div>
+
+      <Modal isOpen={action !== null} onClose={close} title="Verificación en dos pasos">
+        {recoveryCodes ? (
+          <div className="space-y-4">
+            <p className="text-sm text-gray-600">
+              Guarde estos códigos de recuperación en un lugar seguro. Cada uno sirve una vez si
+              pierde el teléfono, y no se volverán a mostrar.
+            </p>
+            <div className="grid grid-cols-2 gap-2 font-mono text-sm bg-gray-50 p-4 rounded-lg">
+              {recoveryCodes.map((c) => (
+                <span key={c}>{c}</span>
+              ))}
+            </div>
+            <div className="flex justify-end">
+              <Button onClick={close}>Listo</Button>
+            </div>
+          </div>
+        ) : (
+          <form onSubmit={handleSubmit} className="space-y-4">
+            {setup && (
+              <div className="space-y-2 text-sm text-gray-600">
+                <p>
+                  Agregue la cuenta en su aplicación de autenticación (Google Authenticator, Authy...)
+                  abriendo el enlace en el teléfono o escribiendo la clave:
+                </p>
+                <a href={setup.otpauth_uri} className="text-primary-600 break-all">
+                  {setup.otpauth_uri}
+                </a>
+                <p className="font-mono bg-gray-50 p-2 rounded break-all">{setup.secret}</p>
+              </div>
+            )}
+
+            {error && (
+              <div className="bg-red-50 border border-red-200 text-red-700 px-4 py-3 rounded-lg text-sm">
+                {error}
+              </div>
+            )}
+
+            <Input
+              label={
+                action === 'enable'
+                  ? 'Código de la aplicación'
+                  : 'Código de la aplicación o de recuperación'
+              }
+              type="text"
+              autoComplete="one-time-code"
+              value={code}
+              onChange={(e) => setCode(e.target.value)}
+              required
+            />
+
+            <div className="flex justify-end space-x-3 pt-2">
+              <Button type="button" variant="secondary" onClick={close}>
+                Cancelar
+              </Button>
+              <Button type="submit" variant={action === 'disable' ? 'danger' : 'primary'}>
+                {action === 'disable' ? 'Desactivar' : 'Confirmar'}
+              </Button>
+            </div>
+          </form>
+        )}
+      </Modal>
+    </div>
+  );
+}
+
+// Desktop app only: send local person deletions to the cloud and list the cloud accounts
+// left without a person
+function DesktopSyncSection() {
+  const [result, setResult] = useState<SyncDeletedPeopleResponse | null>(null);
+  const [orphans, setOrphans] = useState<OrphanUser[] | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState('');
+
+  const run = async () => {
+    setBusy(true);
+    setError('');
+    try {
+      setResult(await syncPersonDeletions());
+      setOrphans(await findOrphanUsers());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="bg-white rounded-lg shadow p-6">
+      <h2 className="text-lg font-medium text-gray-900 mb-1">Sincronización con la nube</h2>
+      <p className="text-sm text-gray-500 mb-4">
+        Elimina en la nube a las personas borradas en esta computadora, junto con sus cuentas, y
+        revisa las cuentas de servidores que ya no tienen persona.
+      </p>
+      <Button onClick={run} isLoading={busy}>
+        <RefreshCw className="w-4 h-4 mr-2" />
+        Sincronizar
+      </Button>
+
+      {error && <p className="text-sm text-red-600 mt-3">{error}</p>}
+
+      {result && (
+        <p className="text-sm text-gray-600 mt-4">
+          {result.deleted_people.length} personas y {result.deleted_users.length} cuentas
+          eliminadas en la nube
+          {result.not_found.length > 0 && ` (${result.not_found.length} ya no existían)`}.
+        </p>
+      )}
+
+      {orphans && (
+        <div className="mt-4">
+          {orphans.length === 0 ? (
+            <p className="text-sm text-gray-600">No hay cuentas sin persona.</p>
+          ) : (
+            <>
+              <h3 className="text-sm font-medium text-gray-900">Cuentas sin persona</h3>
+              <ul className="mt-2 space-y-1 text-sm text-gray-600">
+                {orphans.map((o) => (
+                  <li key={o.id}>
+                    <span className="font-medium">{o.username}</span>
+                    {o.reason === 'no_person'
+                      ? ' — su persona fue eliminada en la nube'
+                      : ` — ${o.person_name ?? 'su persona'} no está en esta computadora`}
+                  </li>
+                ))}
+              </ul>
+            </>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
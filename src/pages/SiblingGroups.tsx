@@ -3,13 +3,18 @@ import { Plus, Trash2, Edit, Users, Search } from 'lucide-react';
 import { Button, Modal, Input, Select, Table } from '../components/common';
 import { siblingApi } from '../services/api';
 import { usePeopleStore } from '../stores/peopleStore';
-import type { SiblingGroup, CreateSiblingGroupRequest, PairingRule } from '../types';
+import type { SiblingGroup, CreateSiblingGroupRequest, PairingRule, PairingScope } from '../types';
 
 const pairingRuleLabels: Record<string, string> = {
   TOGETHER: 'JUNTOS',
   SEPARATE: 'SEPARADOS',
 };
 
+const pairingScopeLabels: Record<string, string> = {
+  SAME_JOB: 'mismo ministerio',
+  SAME_DATE: 'misma fecha',
+};
+
 export function SiblingGroups() {
   const { people, fetchPeople } = usePeopleStore();
   const [groups, setGroups] = useState<SiblingGroup[]>([]);
@@ -20,6 +25,7 @@ export function SiblingGroups() {
   const [formData, setFormData] = useState({
     name: '',
     pairing_rule: 'TOGETHER' as PairingRule,
+    pairing_scope: 'SAME_JOB' as PairingScope,
     member_ids: [] as string[],
   });
   const [memberSearchQuery, setMemberSearchQuery] = useState('');
@@ -63,6 +69,7 @@ export function SiblingGroups() {
     setFormData({
       name: group.name,
       pairing_rule: group.pairing_rule,
+      pairing_scope: group.pairing_scope || 'SAME_JOB',
       member_ids: group.member_ids,
     });
     setIsModalOpen(true);
@@ -86,6 +93,7 @@ export function SiblingGroups() {
     setFormData({
       name: '',
       pairing_rule: 'TOGETHER',
+      pairing_scope: 'SAME_JOB',
       member_ids: [],
     });
     setMemberSearchQuery('');
@@ -144,6 +152,8 @@ export function SiblingGroups() {
           }`}
         >
           {pairingRuleLabels[group.pairing_rule] || group.pairing_rule}
+          {' · '}
+          {pairingScopeLabels[group.pairing_scope] || pairingScopeLabels.SAME_JOB}
         </span>
       ),
     },
@@ -202,6 +212,7 @@ export function SiblingGroups() {
         <ul className="mt-2 text-sm text-blue-700 space-y-1">
           <li><strong>JUNTOS:</strong> Los hermanos serán programados en las mismas fechas cuando sea posible</li>
           <li><strong>SEPARADOS:</strong> Los hermanos no serán programados en las mismas fechas</li>
+          <li><strong>Alcance:</strong> "mismo ministerio" aplica la regla solo dentro de un ministerio; "misma fecha" la aplica entre todos los ministerios del domingo</li>
         </ul>
       </div>
 
@@ -230,6 +241,16 @@ export function SiblingGroups() {
             ]}
           />
 
+          <Select
+            label="Alcance de la Regla"
+            value={formData.pairing_scope}
+            onChange={(e) => setFormData({ ...formData, pairing_scope: e.target.value as PairingScope })}
+            options={[
+              { value: 'SAME_JOB', label: 'Mismo ministerio - Solo entre quienes sirven en el mismo ministerio' },
+              { value: 'SAME_DATE', label: 'Misma fecha - Entre todos los ministerios del mismo domingo' },
+            ]}
+          />
+
           <div>
             <label className="block text-sm font-medium text-gray-700 mb-2">
               Miembros ({formData.member_ids.length} seleccionados)
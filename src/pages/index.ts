@@ -6,3 +6,5 @@ export { SiblingGroups } from './SiblingGroups';
 export { Reports } from './Reports';
 export { Settings } from './Settings';
 export { ServidorDashboard } from './ServidorDashboard';
+export { GuardianDashboard } from './GuardianDashboard';
+export { AcceptInvitation } from './AcceptInvitation';
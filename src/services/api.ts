@@ -1,16 +1,43 @@
 import type {
   Person,
+  PeopleListParams,
+  PeoplePage,
   PersonWithCredentials,
+  Invitation,
+  IssuedInvitation,
+  InvitationDetails,
   CreatePersonRequest,
   UpdatePersonRequest,
   Job,
   Schedule,
+  ServiceDate,
+  BulkAssignmentOperation,
+  ScheduleAuditEntry,
+  DoubleBooking,
   GenerateScheduleRequest,
+  GenerateScheduleRangeRequest,
+  GenerateScheduleBatchRequest,
+  BatchMonthSummary,
+  BackgroundJob,
+  BackgroundJobKind,
+  AuthEventEntry,
+  UserSession,
+  RegenerateScheduleRequest,
+  UpdateScheduleRequest,
+  DuplicateScheduleRequest,
+  ScheduleJobStatus,
+  UnpublishScheduleRequest,
+  ScheduleStatusEvent,
+  ScheduleSnapshot,
+  ScheduleDiff,
   SchedulePreview,
   UpdateAssignmentRequest,
   Assignment,
   SiblingGroup,
   CreateSiblingGroupRequest,
+  SiblingGroupDuplicate,
+  MergeSiblingGroupsRequest,
+  SiblingGroupMergeResult,
   Unavailability,
   CreateUnavailabilityRequest,
   FairnessScore,
@@ -20,11 +47,52 @@ import type {
   SwapAssignmentsRequest,
   MoveAssignmentRequest,
   CompletenessResponse,
+  EmptySlotSuggestions,
+  JuniorRosterEntry,
+  RecognitionEntry,
+  AppSetting,
+  BootstrapRequest,
+  BootstrapResponse,
+  CoordinatorScope,
+  ApiKey,
+  ApiKeyScope,
+  CreatedApiKey,
+  GuardianAccount,
+  GuardianWithCredentials,
+  GuardianChild,
+  SheetExport,
+  CalendarFeed,
+  CreatedCalendarFeed,
+  BirthdayEntry,
+  Notification,
+  NotificationFailure,
+  ServiceReminder,
+  NotificationPreferences,
+  WhatsappTemplate,
+  RegenerateUsernameResponse,
+  PairingPreference,
+  CreatePairingPreferenceRequest,
+  PairingPreferenceType,
+  UpdateJobNoviceLimitRequest,
+  UpdateJobRequirementsRequest,
+  Team,
+  CreateTeamRequest,
+  PersonJobBlackout,
+  SetPersonJobBlackoutRequest,
+  PersonJobExclusion,
+  SetPersonJobExclusionRequest,
+  ImportVCardRequest,
+  VCardImportResult,
+  StaffingOverride,
+  SetStaffingOverrideRequest,
+  RecruitingNeed,
+  AssignmentEvent,
+  RotationStatus,
+  AbsenceSimulation,
+  SwapSuggestion,
 } from '../types';
 import { useAuthStore } from '../stores/authStore';
-
-// API Base URL - change for production
-const API_BASE_URL = import.meta.env.VITE_API_URL || 'http://localhost:3000';
+import { API_BASE_URL, apiClient } from './apiClient';
 
 // Get auth token from store
 const getAuthHeaders = (): HeadersInit => {
@@ -35,7 +103,8 @@ const getAuthHeaders = (): HeadersInit => {
 // Generic fetch wrapper with error handling
 async function fetchApi<T>(
   endpoint: string,
-  options?: RequestInit
+  options?: RequestInit,
+  retried = false
 ): Promise<T> {
   const url = `${API_BASE_URL}/api${endpoint}`;
 
@@ -49,7 +118,10 @@ async function fetchApi<T>(
   });
 
   if (response.status === 401) {
-    // Token expired or invalid - logout
+    // Token expired - try once with a refreshed one, else the session is over
+    if (!retried && (await useAuthStore.getState().refresh())) {
+      return fetchApi<T>(endpoint, options, true);
+    }
     useAuthStore.getState().logout();
     throw new Error('Sesión expirada. Por favor inicie sesión nuevamente.');
   }
@@ -88,26 +160,106 @@ function put<T>(endpoint: string, body?: unknown): Promise<T> {
   });
 }
 
+// Helper for PATCH requests
+function patch<T>(endpoint: string, body?: unknown): Promise<T> {
+  return fetchApi<T>(endpoint, {
+    method: 'PATCH',
+    body: body ? JSON.stringify(body) : undefined,
+  });
+}
+
 // Helper for DELETE requests
 function del<T>(endpoint: string): Promise<T> {
   return fetchApi<T>(endpoint, { method: 'DELETE' });
 }
 
+// Helper for file downloads (CSV exports)
+async function getBlob(endpoint: string, retried = false): Promise<Blob> {
+  const response = await fetch(`${API_BASE_URL}/api${endpoint}`, {
+    headers: getAuthHeaders(),
+  });
+
+  if (response.status === 401) {
+    if (!retried && (await useAuthStore.getState().refresh())) {
+      return getBlob(endpoint, true);
+    }
+    useAuthStore.getState().logout();
+    throw new Error('Sesión expirada. Por favor inicie sesión nuevamente.');
+  }
+
+  if (!response.ok) {
+    const error = await response.text();
+    throw new Error(error || `HTTP error ${response.status}`);
+  }
+
+  return response.blob();
+}
+
 // People API
+const listPeople = (params: PeopleListParams = {}) => {
+  const query = new URLSearchParams();
+  if (params.q?.trim()) query.set('q', params.q.trim());
+  if (params.job_id) query.set('job_id', params.job_id);
+  if (params.active !== undefined) query.set('active', String(params.active));
+  if (params.page) query.set('page', String(params.page));
+  if (params.limit) query.set('limit', String(params.limit));
+  if (params.include_stats) query.set('include', 'stats');
+  const qs = query.toString();
+  return get<PeoplePage>(`/people${qs ? `?${qs}` : ''}`);
+};
+
 export const peopleApi = {
-  getAll: () => get<Person[]>('/people'),
+  list: listPeople,
+  getAll: async (includeStats = false) => (await listPeople({ include_stats: includeStats })).people,
   get: (id: string) => get<Person>(`/people/${id}`),
   create: (request: CreatePersonRequest) => post<PersonWithCredentials>('/people', request),
   update: (request: UpdatePersonRequest) => put<Person>(`/people/${request.id}`, request),
   delete: (id: string) => del<void>(`/people/${id}`),
-  getForJob: async (jobId: string) => {
-    const people = await get<Person[]>('/people');
-    return people.filter(p => p.job_ids?.includes(jobId));
-  },
+  getForJob: async (jobId: string) => (await listPeople({ job_id: jobId })).people,
   resetPassword: (personId: string) => post<{ message: string; new_password: string }>(`/people/${personId}/reset-password`),
   createUserAccount: (personId: string) => post<{ username: string; password: string }>(`/people/${personId}/create-user`),
+  regenerateUsername: (personId: string) =>
+    post<RegenerateUsernameResponse>(`/people/${personId}/regenerate-username`),
   uploadPhoto: (personId: string, photoData: string) => post<{ message: string }>(`/people/${personId}/photo`, { photo_data: photoData }),
   deletePhoto: (personId: string) => del<{ message: string }>(`/people/${personId}/photo`),
+  getBlackouts: (personId: string) => get<PersonJobBlackout[]>(`/people/${personId}/blackouts`),
+  setBlackout: (personId: string, request: SetPersonJobBlackoutRequest) =>
+    put<PersonJobBlackout>(`/people/${personId}/blackouts`, request),
+  deleteBlackout: (blackoutId: string) => del<void>(`/blackouts/${blackoutId}`),
+  getExclusions: (personId: string) => get<PersonJobExclusion[]>(`/people/${personId}/exclusions`),
+  setExclusion: (personId: string, request: SetPersonJobExclusionRequest) =>
+    put<PersonJobExclusion>(`/people/${personId}/exclusions`, request),
+  deleteExclusion: (exclusionId: string) => del<void>(`/exclusions/${exclusionId}`),
+  // Upcoming assignments across draft and published schedules
+  getAssignments: (personId: string, includePast = false) =>
+    get<PersonScheduleAssignment[]>(
+      `/people/${personId}/assignments${includePast ? '?include_past=true' : ''}`
+    ),
+  downloadCalendar: (personId: string) => getBlob(`/people/${personId}/calendar.ics`),
+  getCalendarFeed: (personId: string) =>
+    get<CalendarFeed | null>(`/people/${personId}/calendar-feed`),
+  rotateCalendarFeed: (personId: string) =>
+    post<CreatedCalendarFeed>(`/people/${personId}/calendar-feed`),
+  deleteCalendarFeed: (personId: string) => del<void>(`/people/${personId}/calendar-feed`),
+  calendarFeedUrl: (token: string) => `${API_BASE_URL}/calendar/${token}.ics`,
+  // Invitation links for people without a login; resending replaces the link and its expiry
+  getInvitation: (personId: string) => get<Invitation>(`/people/${personId}/invitation`),
+  invite: (personId: string) => post<IssuedInvitation>(`/people/${personId}/invitation`),
+  resendInvitation: (personId: string) =>
+    post<IssuedInvitation>(`/people/${personId}/invitation/resend`),
+  cancelInvitation: (personId: string) => del<void>(`/people/${personId}/invitation`),
+  invitationUrl: (token: string) => `${window.location.origin}/?invite=${encodeURIComponent(token)}`,
+  exportVCard: (includeInactive = false) =>
+    getBlob(`/people/vcard${includeInactive ? '?include_inactive=true' : ''}`),
+  importVCard: (request: ImportVCardRequest) => post<VCardImportResult>('/people/vcard', request),
+  // Roster as CSV or Excel; consentedOnly leaves out people without photo/data consent
+  exportDirectory: (
+    format: 'csv' | 'xlsx' = 'csv',
+    options: { includeInactive?: boolean; consentedOnly?: boolean } = {}
+  ) =>
+    getBlob(
+      `/people/directory?format=${format}&include_inactive=${!!options.includeInactive}&consented_only=${!!options.consentedOnly}`
+    ),
 };
 
 // My Photo API (for servidores self-service)
@@ -121,6 +273,11 @@ export const jobsApi = {
   getAll: () => get<Job[]>('/jobs'),
   get: (id: string) => get<Job>(`/jobs/${id}`),
   getPositions: (jobId: string) => get<{ id: string; job_id: string; position_number: number; name: string }[]>(`/jobs/${jobId}/positions`),
+  updateNoviceLimit: (jobId: string, request: UpdateJobNoviceLimitRequest) =>
+    put<Job>(`/jobs/${jobId}/novice-limit`, request),
+  updateRequirements: (jobId: string, request: UpdateJobRequirementsRequest) =>
+    put<Job>(`/jobs/${jobId}/requirements`, request),
+  getRotation: (jobId: string) => get<RotationStatus[]>(`/jobs/${jobId}/rotation`),
   // Jobs are predefined, these are no-ops for now
   create: async () => { throw new Error('Jobs are predefined'); },
   update: async () => { throw new Error('Jobs are predefined'); },
@@ -135,44 +292,178 @@ export interface MyAssignment {
   job_color: string;
   position?: number;
   position_name?: string;
+  notes?: string; // Instructions for the slot, e.g. "llegar 20 min antes"
+  service_date_notes?: string; // Note for the whole day, shared by everyone serving
+}
+
+// One of a person's slots in any schedule, drafts included (admin/coordinator view)
+export interface PersonScheduleAssignment {
+  assignment_id: string;
+  schedule_id: string;
+  schedule_name: string;
+  schedule_status: string;
+  job_published: boolean; // The person has been told about it
+  service_date: string;
+  job_id: string;
+  job_name: string;
+  position?: number;
+  position_name?: string;
+  notes?: string;
+}
+
+// One child's assignment in the family month view
+export interface FamilyAssignment extends MyAssignment {
+  person_id: string;
+  person_name: string;
+}
+
+// One day of the servidor's month grid
+export interface MyAvailability {
+  date: string;
+  available: boolean;
+  unavailability?: Unavailability; // The single-day record when unavailable
+}
+
+export interface CalendarDay {
+  date: string; // YYYY-MM-DD
+  status: 'assigned' | 'unavailable' | 'available' | 'cancelled'; // Assigned wins when both apply
+  assignments: {
+    job_id: string;
+    job_name: string;
+    job_color?: string;
+    position_name?: string;
+  }[];
+  // Only single_day records can be toggled off with PUT /my-availability/{date}
+  unavailability: { id: string; reason?: string; single_day: boolean }[];
 }
 
 // Schedule API
 export const scheduleApi = {
-  getAll: () => get<Schedule[]>('/schedules'),
-  get: (id: string) => get<Schedule>(`/schedules/${id}`),
+  getAll: (includeArchived = false) =>
+    get<Schedule[]>(`/schedules${includeArchived ? '?include_archived=true' : ''}`),
+  get: (id: string, jobId?: string) =>
+    get<Schedule>(`/schedules/${id}${jobId ? `?job_id=${encodeURIComponent(jobId)}` : ''}`),
   getByMonth: async (year: number, month: number) => {
-    const schedules = await get<Schedule[]>('/schedules');
+    const schedules = await get<Schedule[]>('/schedules?include_archived=true');
     return schedules.find(s => s.year === year && s.month === month) || null;
   },
   generate: (request: GenerateScheduleRequest) => post<SchedulePreview>('/schedules', request),
+  generateRange: (request: GenerateScheduleRangeRequest) =>
+    post<SchedulePreview[]>('/schedules/range', request),
+  generateBatch: (request: GenerateScheduleBatchRequest) =>
+    post<BatchMonthSummary[]>('/schedules/batch', request),
+  // Queued instead: follow it with backgroundJobApi.get until it is done
+  generateBatchInBackground: (request: GenerateScheduleBatchRequest) =>
+    post<BackgroundJob>('/schedules/batch?background=true', request),
+  regenerate: (id: string, request: RegenerateScheduleRequest = {}) =>
+    post<SchedulePreview>(`/schedules/${id}/regenerate`, request),
+  update: (id: string, request: UpdateScheduleRequest) =>
+    patch<Schedule>(`/schedules/${id}`, request),
   save: async (preview: SchedulePreview) => {
     // In the web version, generate already saves the schedule
     return preview.schedule;
   },
   updateAssignment: (request: UpdateAssignmentRequest) =>
-    put<Assignment>(`/assignments/${request.assignment_id}`, { person_id: request.new_person_id }),
+    put<Assignment>(`/assignments/${request.assignment_id}`, {
+      person_id: request.new_person_id,
+      expected_person_id: request.expected_person_id,
+      override_reason: request.override_reason,
+    }),
   clearAssignment: (assignmentId: string) =>
     put<Assignment>(`/assignments/${assignmentId}/clear`),
+  // null or blank removes the note
+  updateAssignmentNotes: (assignmentId: string, notes: string | null) =>
+    put<Assignment>(`/assignments/${assignmentId}/notes`, { notes }),
   swapAssignments: (request: SwapAssignmentsRequest) =>
     post<Assignment[]>('/assignments/swap', request),
+  bulkUpdateAssignments: (scheduleId: string, operations: BulkAssignmentOperation[]) =>
+    post<Schedule>(`/schedules/${scheduleId}/assignments/bulk`, { operations }),
+  // Both answer 409 when there is nothing to undo/redo or the slots changed since
+  undo: (scheduleId: string) => post<Assignment[]>(`/schedules/${scheduleId}/undo`),
+  redo: (scheduleId: string) => post<Assignment[]>(`/schedules/${scheduleId}/redo`),
+  getSwapSuggestions: (assignmentId: string) =>
+    get<SwapSuggestion[]>(`/assignments/${assignmentId}/swap-suggestions`),
   moveAssignment: (assignmentId: string, request: MoveAssignmentRequest) =>
     put<Assignment[]>(`/assignments/${assignmentId}/move`, request),
+  getAudit: (scheduleId: string) => get<ScheduleAuditEntry[]>(`/schedules/${scheduleId}/audit`),
+  // Same-day double bookings across all schedules, from today unless `from` (YYYY-MM-DD) is given
+  getDoubleBookings: (from?: string) =>
+    get<DoubleBooking[]>(`/schedules/double-bookings${from ? `?from=${from}` : ''}`),
+  getServiceDateHistory: (serviceDateId: string) =>
+    get<AssignmentEvent[]>(`/service-dates/${serviceDateId}/history`),
+  // null or blank removes the note
+  updateServiceDateNotes: (serviceDateId: string, notes: string | null) =>
+    put<Omit<ServiceDate, 'assignments'>>(`/service-dates/${serviceDateId}/notes`, { notes }),
+  cancelServiceDate: (serviceDateId: string, reason?: string) =>
+    post<Omit<ServiceDate, 'assignments'>>(`/service-dates/${serviceDateId}/cancel`, { reason }),
   getCompleteness: (scheduleId: string) =>
     get<CompletenessResponse>(`/schedules/${scheduleId}/completeness`),
+  getCompletenessSuggestions: (scheduleId: string) =>
+    get<EmptySlotSuggestions[]>(`/schedules/${scheduleId}/completeness/suggestions`),
+  simulateAbsence: (scheduleId: string, personId: string) =>
+    get<AbsenceSimulation>(`/schedules/${scheduleId}/what-if/absence/${personId}`),
   publish: (id: string) => post<Schedule>(`/schedules/${id}/publish`),
+  unpublish: (id: string, request: UnpublishScheduleRequest) =>
+    post<Schedule>(`/schedules/${id}/unpublish`, request),
+  getStatusHistory: (id: string) =>
+    get<ScheduleStatusEvent[]>(`/schedules/${id}/status-history`),
+  archive: (id: string) => post<Schedule>(`/schedules/${id}/archive`),
+  duplicate: (id: string, request: DuplicateScheduleRequest) =>
+    post<SchedulePreview>(`/schedules/${id}/duplicate`, request),
+  getSnapshots: (id: string) => get<ScheduleSnapshot[]>(`/schedules/${id}/snapshots`),
+  getDiff: (id: string, from?: number, to?: number | 'current') => {
+    const params = new URLSearchParams();
+    if (from !== undefined) params.set('from', String(from));
+    if (to !== undefined) params.set('to', String(to));
+    const qs = params.toString();
+    return get<ScheduleDiff>(`/schedules/${id}/diff${qs ? `?${qs}` : ''}`);
+  },
+  getJobStatuses: (id: string) => get<ScheduleJobStatus[]>(`/schedules/${id}/jobs`),
+  publishJob: (id: string, jobId: string) =>
+    post<ScheduleJobStatus>(`/schedules/${id}/jobs/${jobId}/publish`),
+  unpublishJob: (id: string, jobId: string) =>
+    post<ScheduleJobStatus>(`/schedules/${id}/jobs/${jobId}/unpublish`),
   delete: (id: string) => del<void>(`/schedules/${id}`),
   getFairnessScores: (year: number) => get<FairnessScore[]>(`/reports/fairness?year=${year}`),
+  downloadFairnessWorkbook: (year: number) => getBlob(`/reports/fairness/export?year=${year}`),
   getMyAssignments: (personId: string) => get<MyAssignment[]>(`/my-assignments/${personId}`),
+  getMyFamilyAssignments: (month?: string) =>
+    get<FamilyAssignment[]>(`/my-family-assignments${month ? `?month=${month}` : ''}`),
+  // month is YYYY-MM, defaults to the current month
+  getMyCalendar: (month?: string) =>
+    get<CalendarDay[]>(`/my-calendar${month ? `?month=${month}` : ''}`),
+  getJuniorRoster: (readyOnly = false) =>
+    get<JuniorRosterEntry[]>(`/reports/juniors${readyOnly ? '?ready_only=true' : ''}`),
+  getBirthdays: (month: number, year?: number) =>
+    get<BirthdayEntry[]>(`/reports/birthdays?month=${month}${year ? `&year=${year}` : ''}`),
+  getRecruitingNeeds: (year: number, month: number) =>
+    get<RecruitingNeed[]>(`/reports/recruiting-needs?year=${year}&month=${month}`),
+  downloadRecruitingNeedsCsv: (year: number, month: number) =>
+    getBlob(`/reports/recruiting-needs?year=${year}&month=${month}&format=csv`),
+  getRecognition: (year: number) => get<RecognitionEntry[]>(`/reports/recognition/${year}`),
+  // One certificate page per servidor, or just the given person's
+  downloadRecognitionCertificates: (year: number, personId?: string) =>
+    getBlob(
+      `/reports/recognition/${year}/certificates${personId ? `?person_id=${personId}` : ''}`
+    ),
   getPersonAssignmentHistory: async (personId: string, _startDate: string, _endDate: string) => {
     const history = await get<PersonAssignmentDetail[]>(`/reports/person/${personId}/history`);
     return history;
   },
   getEligiblePeopleForAssignment: async (request: GetEligiblePeopleRequest) => {
     // Get all people qualified for the job and filter by availability
-    const people = await get<Person[]>('/people');
+    const [people, jobs] = await Promise.all([
+      listPeople({ job_id: request.job_id, active: true }).then(page => page.people),
+      get<Job[]>('/jobs'),
+    ]);
+    const job = jobs.find(j => j.id === request.job_id);
     const eligible: EligiblePerson[] = people
-      .filter(p => p.active && p.job_ids?.includes(request.job_id))
+      .filter(
+        p =>
+          p.active &&
+          p.job_ids?.includes(request.job_id) &&
+          !p.excluded_job_ids?.includes(request.job_id)
+      )
       .map(p => ({
         id: p.id,
         first_name: p.first_name,
@@ -182,6 +473,12 @@ export const scheduleApi = {
         passes_consecutive_check: true,
         sibling_status: 'neutral' as const,
         assignments_this_year: 0,
+        notes: p.notes,
+        scheduling_notes: p.scheduling_notes,
+        reason_if_ineligible:
+          job?.requires_first_communion && !p.first_communion
+            ? 'Requiere primera comunión'
+            : undefined,
       }));
     return eligible;
   },
@@ -195,22 +492,54 @@ export const siblingApi = {
   update: (request: CreateSiblingGroupRequest & { id: string }) =>
     put<SiblingGroup>(`/sibling-groups/${request.id}`, request),
   delete: (id: string) => del<void>(`/sibling-groups/${id}`),
+  getDuplicates: () => get<SiblingGroupDuplicate[]>('/sibling-groups/duplicates'),
+  merge: (request: MergeSiblingGroupsRequest) =>
+    post<SiblingGroupMergeResult>('/sibling-groups/merge', request),
   getForPerson: async (personId: string) => {
     const groups = await get<SiblingGroup[]>('/sibling-groups');
     return groups.filter(g => g.member_ids?.includes(personId));
   },
 };
 
+// Staffing Overrides API
+export const staffingApi = {
+  getAll: (from?: string, to?: string) => {
+    const params = new URLSearchParams();
+    if (from) params.set('from', from);
+    if (to) params.set('to', to);
+    const query = params.toString();
+    return get<StaffingOverride[]>(`/staffing-overrides${query ? `?${query}` : ''}`);
+  },
+  set: (request: SetStaffingOverrideRequest) => put<StaffingOverride>('/staffing-overrides', request),
+  delete: (id: string) => del<void>(`/staffing-overrides/${id}`),
+};
+
+// Teams API
+export const teamsApi = {
+  getAll: () => get<Team[]>('/teams'),
+  create: (request: CreateTeamRequest) => post<Team>('/teams', request),
+  update: (id: string, request: CreateTeamRequest) => put<Team>(`/teams/${id}`, request),
+  delete: (id: string) => del<void>(`/teams/${id}`),
+};
+
+// Pairing Preferences API
+export const pairingApi = {
+  getAll: () => get<PairingPreference[]>('/pairing-preferences'),
+  create: (request: CreatePairingPreferenceRequest) =>
+    post<PairingPreference>('/pairing-preferences', request),
+  update: (id: string, request: { preference?: PairingPreferenceType; notes?: string }) =>
+    put<PairingPreference>(`/pairing-preferences/${id}`, request),
+  delete: (id: string) => del<void>(`/pairing-preferences/${id}`),
+};
+
 // Unavailability API
 export const unavailabilityApi = {
   getAll: () => get<Unavailability[]>('/unavailability'),
-  getForPerson: async (personId: string) => {
-    const all = await get<Unavailability[]>('/unavailability');
-    return all.filter(u => u.person_id === personId);
-  },
+  getForPerson: (personId: string) =>
+    get<Unavailability[]>(`/unavailability?person_id=${encodeURIComponent(personId)}`),
   get: (id: string) => get<Unavailability>(`/unavailability/${id}`),
   create: (request: CreateUnavailabilityRequest) => post<Unavailability>('/unavailability', request),
-  update: async (request: { id: string; person_id?: string; start_date?: string; end_date?: string; reason?: string; recurring?: boolean }) => {
+  update: async (request: { id: string; person_id?: string; start_date?: string; end_date?: string; reason?: string; recurring?: boolean; recurrence?: string }) => {
     // Get existing record to fill in missing fields
     const existing = await get<Unavailability>(`/unavailability/${request.id}`);
     // Delete and recreate since we don't have an update endpoint
@@ -221,6 +550,7 @@ export const unavailabilityApi = {
       end_date: request.end_date || existing.end_date,
       reason: request.reason !== undefined ? request.reason : existing.reason,
       recurring: request.recurring !== undefined ? request.recurring : existing.recurring,
+      recurrence: request.recurrence !== undefined ? request.recurrence : existing.recurrence,
     });
   },
   delete: (id: string) => del<void>(`/unavailability/${id}`),
@@ -230,21 +560,204 @@ export const unavailabilityApi = {
     return !unavailability.some(u =>
       u.person_id === personId &&
       new Date(u.start_date) <= dateObj &&
-      new Date(u.end_date) >= dateObj
+      new Date(u.end_date) >= dateObj &&
+      (!u.recurrence || matchesRecurrence(u.recurrence, date))
     );
   },
 };
 
+const RECURRENCE_DAYS = ['SU', 'MO', 'TU', 'WE', 'TH', 'FR', 'SA'];
+
+// Mirrors services::recurrence on the API: BYDAY=SA every week, BYDAY=1SU / -1SU per month
+function matchesRecurrence(rule: string, date: string): boolean {
+  const byDay = /BYDAY=(-?\d)?([A-Z]{2})/i.exec(rule);
+  if (!byDay) return false;
+  const [year, month, day] = date.slice(0, 10).split('-').map(Number);
+  const d = new Date(Date.UTC(year, month - 1, day));
+  if (RECURRENCE_DAYS[d.getUTCDay()] !== byDay[2].toUpperCase()) return false;
+  if (!byDay[1]) return true;
+  const week = Number(byDay[1]);
+  if (week === -1) {
+    return new Date(Date.UTC(year, month - 1, day + 7)).getUTCMonth() !== month - 1;
+  }
+  return Math.floor((day - 1) / 7) + 1 === week;
+}
+
 // My Unavailability API (for servidores self-service)
+// personId: a guardian acting for one of their children; the user's own person otherwise
+const onBehalfOf = (personId?: string) =>
+  personId ? `?person_id=${encodeURIComponent(personId)}` : '';
+
 export const myUnavailabilityApi = {
-  getAll: () => get<Unavailability[]>('/my-unavailability'),
-  create: (dates: string[], reason?: string) =>
-    post<Unavailability[]>('/my-unavailability', { dates, reason }),
-  delete: (id: string) => del<void>(`/my-unavailability/${id}`),
+  getAll: (personId?: string) =>
+    get<Unavailability[]>(`/my-unavailability${onBehalfOf(personId)}`),
+  create: (dates: string[], reason?: string, personId?: string) =>
+    post<Unavailability[]>(`/my-unavailability${onBehalfOf(personId)}`, { dates, reason }),
+  delete: (id: string, personId?: string) =>
+    del<void>(`/my-unavailability/${id}${onBehalfOf(personId)}`),
+  // Idempotent per date; 409 when the date is part of a longer absence
+  setAvailability: (date: string, available: boolean, reason?: string, personId?: string) =>
+    put<MyAvailability>(`/my-availability/${date}${onBehalfOf(personId)}`, { available, reason }),
+};
+
+// Guardians: one parent login for several servidores
+export const guardianApi = {
+  getMyChildren: () => get<GuardianChild[]>('/my-children'),
+  getAll: () => get<GuardianAccount[]>('/admin/guardians'),
+  // The generated password is only returned here
+  create: (username: string, personIds: string[]) =>
+    post<GuardianWithCredentials>('/admin/guardians', { username, person_ids: personIds }),
+  setChildren: (userId: string, personIds: string[]) =>
+    put<GuardianAccount>(`/admin/guardians/${userId}/children`, { person_ids: personIds }),
+  delete: (userId: string) => del<void>(`/admin/guardians/${userId}`),
+};
+
+// Invitation page (public; the token in the link is the credential)
+export const invitationApi = {
+  get: async (token: string): Promise<InvitationDetails> => {
+    const response = await fetch(`${API_BASE_URL}/invitations/${encodeURIComponent(token)}`);
+    if (!response.ok) {
+      throw new Error(await response.text());
+    }
+    return response.json();
+  },
+};
+
+// Settings API
+export const settingsApi = {
+  getAll: () => get<AppSetting[]>('/settings'),
+  update: (key: string, value: string) => put<AppSetting>(`/settings/${key}`, { value }),
+};
+
+// Background jobs
+export const backgroundJobApi = {
+  // Admins see every job; whoever queued one can follow it
+  get: (id: string) => get<BackgroundJob>(`/background-jobs/${id}`),
+  list: (status?: BackgroundJob['status']) =>
+    get<BackgroundJob[]>(`/admin/background-jobs${status ? `?status=${status}` : ''}`),
+  create: (kind: BackgroundJobKind, payload?: unknown) =>
+    post<BackgroundJob>('/admin/background-jobs', { kind, payload }),
+  retry: (id: string) => post<BackgroundJob>(`/admin/background-jobs/${id}/retry`),
+  // Work through the due jobs now (what the worker does every few seconds)
+  runPending: () => post<BackgroundJob[]>('/admin/background-jobs/run'),
+};
+
+// Admin API
+// Where the signed-in user is logged in
+export const sessionApi = {
+  getAll: () => apiClient.get('/api/auth/sessions'),
+  revoke: (id: string) => apiClient.delete('/api/auth/sessions/{id}', { path: { id } }),
+  // Sign out everywhere, or everywhere else when keepCurrent
+  revokeAll: (keepCurrent = false) =>
+    apiClient.delete('/api/auth/sessions', { query: { keep_current: keepCurrent } }),
+};
+
+// TOTP second factor of the signed-in admin or coordinator
+export const twoFactorApi = {
+  getStatus: () => apiClient.get('/api/auth/two-factor'),
+  setup: () => apiClient.post('/api/auth/two-factor/setup'),
+  // Confirms a code from the app; answers with the recovery codes, shown only this once
+  enable: (code: string) => apiClient.post('/api/auth/two-factor/enable', { body: { code } }),
+  disable: (code: string) => apiClient.post('/api/auth/two-factor/disable', { body: { code } }),
+  regenerateRecoveryCodes: (code: string) =>
+    apiClient.post('/api/auth/two-factor/recovery-codes', { body: { code } }),
+};
+
+export const adminApi = {
+  bootstrap: (request: BootstrapRequest) => post<BootstrapResponse>('/admin/bootstrap', request),
+  getNotificationFailures: (includeResolved = false) =>
+    get<NotificationFailure[]>(
+      `/admin/notification-failures${includeResolved ? '?include_resolved=true' : ''}`
+    ),
+  retryNotificationFailure: (id: string) =>
+    post<NotificationFailure>(`/admin/notification-failures/${id}/retry`),
+  // Held notifications of people who chose a daily digest
+  sendDigests: () => post<{ sent: number }>('/admin/notifications/send-digests'),
+  getWhatsappTemplates: () => get<WhatsappTemplate[]>('/admin/whatsapp-templates'),
+  setWhatsappTemplate: (kind: string, name: string, language?: string) =>
+    put<WhatsappTemplate>(`/admin/whatsapp-templates/${kind}`, { name, language }),
+  deleteWhatsappTemplate: (kind: string) => del<void>(`/admin/whatsapp-templates/${kind}`),
+  // Without a date: the one `reminder_days_before` days from today
+  previewReminders: (date?: string) =>
+    get<ServiceReminder[]>(`/admin/reminders${date ? `?date=${date}` : ''}`),
+  sendReminders: (date?: string) =>
+    post<ServiceReminder[]>(`/admin/reminders/send${date ? `?date=${date}` : ''}`),
+  getCoordinators: () => get<CoordinatorScope[]>('/admin/coordinators'),
+  // An empty list turns the coordinator back into a servidor
+  setJobScope: (userId: string, jobIds: string[]) =>
+    put<CoordinatorScope>(`/admin/users/${userId}/job-scope`, { job_ids: jobIds }),
+  getAuthEvents: (filters: { event?: string; username?: string; ip?: string; since?: string } = {}) => {
+    const params = new URLSearchParams();
+    for (const [key, value] of Object.entries(filters)) {
+      if (value) params.set(key, value);
+    }
+    const qs = params.toString();
+    return get<AuthEventEntry[]>(`/admin/auth-events${qs ? `?${qs}` : ''}`);
+  },
+  // A user's sessions, to sign out a lost device for them
+  getUserSessions: (userId: string) => get<UserSession[]>(`/admin/users/${userId}/sessions`),
+  revokeUserSession: (userId: string, sessionId: string) =>
+    del<void>(`/admin/users/${userId}/sessions/${sessionId}`),
+  revokeUserSessions: (userId: string) =>
+    del<{ revoked: number }>(`/admin/users/${userId}/sessions`),
+  // For a user who lost their authenticator and recovery codes
+  resetUserTwoFactor: (userId: string) => del<void>(`/admin/users/${userId}/two-factor`),
+  // Google sign-in email of a user without a linked person; null clears it
+  setUserEmail: (userId: string, email: string | null) =>
+    put<void>(`/admin/users/${userId}/email`, { email }),
+  getSheetExports: () => get<SheetExport[]>('/admin/sheet-exports'),
+  // `spreadsheet` is the sheet's id or its address
+  createSheetExport: (name: string, spreadsheet: string, sheetName: string, jobId?: string) =>
+    post<SheetExport>('/admin/sheet-exports', {
+      name,
+      spreadsheet,
+      sheet_name: sheetName,
+      job_id: jobId,
+    }),
+  pushSheetExport: (id: string) => post<SheetExport>(`/admin/sheet-exports/${id}/push`),
+  deleteSheetExport: (id: string) => del<void>(`/admin/sheet-exports/${id}`),
+  // Keys for the parish website and the sacristy display; the key is only in the create answer
+  getApiKeys: () => get<ApiKey[]>('/admin/api-keys'),
+  createApiKey: (name: string, scopes: ApiKeyScope[]) =>
+    post<CreatedApiKey>('/admin/api-keys', { name, scopes }),
+  setApiKeyScopes: (id: string, scopes: ApiKeyScope[]) =>
+    put<ApiKey>(`/admin/api-keys/${id}`, { scopes }),
+  revokeApiKey: (id: string) => del<void>(`/admin/api-keys/${id}`),
+};
+
+// My Notifications API (for servidores self-service)
+export const myNotificationsApi = {
+  getAll: () => get<Notification[]>('/my-notifications'),
+  markRead: (id: string) => put<void>(`/my-notifications/${id}/read`),
+  getPreferences: () => get<NotificationPreferences>('/my-notification-preferences'),
+  updatePreferences: (preferences: Omit<NotificationPreferences, 'person_id' | 'updated_at'>) =>
+    put<NotificationPreferences>('/my-notification-preferences', preferences),
+};
+
+// Web Push: this browser's subscription for the logged-in user
+export const pushApi = {
+  // null when the server has no VAPID key, so push is not offered
+  getPublicKey: () => get<{ public_key: string | null }>('/push/public-key'),
+  subscribe: (subscription: PushSubscriptionJSON) =>
+    post<void>('/my-push-subscriptions', subscription),
+  unsubscribe: (endpoint: string) =>
+    fetchApi<void>('/my-push-subscriptions', {
+      method: 'DELETE',
+      body: JSON.stringify({ endpoint }),
+    }),
 };
 
-// Export API - not available in web version
+// Export API - the Excel workbook is not available in web version, CSV is
 export const exportApi = {
+  // 'grid' puts service dates in columns and job positions in rows, like the parish bulletin
+  downloadCsv: (scheduleId: string, layout: 'list' | 'grid' = 'list') =>
+    getBlob(`/schedules/${scheduleId}/export?format=csv&layout=${layout}`),
+  // PNG of the whole month, or of one service date (YYYY-MM-DD), to share in messaging groups
+  downloadImage: (scheduleId: string, date?: string) =>
+    getBlob(`/schedules/${scheduleId}/export?format=png${date ? `&date=${date}` : ''}`),
+  // Plain text for the parish WhatsApp group
+  getSummary: async (scheduleId: string) =>
+    (await getBlob(`/schedules/${scheduleId}/summary.txt`)).text(),
   exportSchedule: async (_scheduleId: string) => {
     throw new Error('Excel export not available in web version');
   },
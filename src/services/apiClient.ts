@@ -0,0 +1,130 @@
+import type { paths } from '../types/api.gen';
+import { useAuthStore } from '../stores/authStore';
+
+// Client typed by the API's OpenAPI description: paths, path and query parameters, bodies and
+// answers all come from src/types/api.gen.ts (`npm run sdk`), so a route that changes in the
+// API fails the build here instead of at runtime.
+
+export const API_BASE_URL = import.meta.env.VITE_API_URL || 'http://localhost:3000';
+
+const SESSION_EXPIRED = 'Sesión expirada. Por favor inicie sesión nuevamente.';
+
+// Thrown by a call whose token the API rejected
+export class Unauthorized extends Error {}
+
+// Runs `send` with the session's token; when that is rejected, once more with a refreshed
+// one, else the session is over
+export async function withSession<T>(send: (token: string | null) => Promise<T>): Promise<T> {
+  for (let attempt = 0; attempt < 2; attempt++) {
+    if (attempt > 0 && !(await useAuthStore.getState().refresh())) {
+      break;
+    }
+    try {
+      return await send(useAuthStore.getState().token);
+    } catch (error) {
+      if (!(error instanceof Unauthorized)) throw error;
+    }
+  }
+  useAuthStore.getState().logout();
+  throw new Error(SESSION_EXPIRED);
+}
+
+type Method = 'get' | 'post' | 'put' | 'patch' | 'delete';
+
+// Paths that have the method
+type PathsFor<M extends Method> = {
+  [P in keyof paths]: paths[P] extends Record<M, object> ? P : never;
+}[keyof paths];
+
+type Operation<P extends keyof paths, M extends Method> = paths[P] extends Record<M, infer O>
+  ? O
+  : never;
+
+type PathParams<O> = O extends { parameters: { path: infer T } } ? T : never;
+type QueryParams<O> = O extends { parameters: { query?: infer T } } ? T : never;
+type Body<O> = O extends { requestBody?: never }
+  ? never
+  : O extends { requestBody: { content: { 'application/json': infer T } } }
+    ? T
+    : O extends { requestBody?: { content: { 'application/json': infer T } } }
+      ? T | undefined
+      : never;
+
+// The 2xx answer's JSON, undefined for 204 No Content
+type Answer<O> = O extends { responses: infer R }
+  ? {
+      [S in keyof R]: S extends 200 | 201 | 202
+        ? R[S] extends { content: { 'application/json': infer T } }
+          ? T
+          : never
+        : S extends 204
+          ? undefined
+          : never;
+    }[keyof R]
+  : never;
+
+type Options<O> = ([PathParams<O>] extends [never] ? { path?: never } : { path: PathParams<O> }) &
+  ([QueryParams<O>] extends [never] ? { query?: never } : { query?: QueryParams<O> }) &
+  ([Body<O>] extends [never] ? { body?: never } : { body: Body<O> });
+
+async function request<O>(
+  method: Method,
+  template: string,
+  options: Options<O>
+): Promise<Answer<O>> {
+  const { path, query, body } = options as {
+    path?: Record<string, string | number>;
+    query?: Record<string, string | number | boolean | undefined | null>;
+    body?: unknown;
+  };
+  let url = template.replace(/\{(\w+)\}/g, (_, name: string) =>
+    encodeURIComponent(String(path?.[name]))
+  );
+  const params = new URLSearchParams();
+  for (const [key, value] of Object.entries(query ?? {})) {
+    if (value !== undefined && value !== null) params.set(key, String(value));
+  }
+  const qs = params.toString();
+  if (qs) url += `?${qs}`;
+
+  return withSession(async (token) => {
+    const response = await fetch(`${API_BASE_URL}${url}`, {
+      method: method.toUpperCase(),
+      headers: {
+        'Content-Type': 'application/json',
+        ...(token ? { Authorization: `Bearer ${token}` } : {}),
+      },
+      body: body === undefined ? undefined : JSON.stringify(body),
+    });
+    if (response.status === 401) {
+      throw new Unauthorized();
+    }
+    if (!response.ok) {
+      const error = await response.text();
+      throw new Error(error || `HTTP error ${response.status}`);
+    }
+    if (response.status === 204) {
+      return undefined as Answer<O>;
+    }
+    return response.json() as Promise<Answer<O>>;
+  });
+}
+
+function method<M extends Method>(name: M) {
+  return <P extends PathsFor<M>>(
+    path: P,
+    ...[options]: Options<Operation<P, M>> extends { path?: never; body?: never }
+      ? [Options<Operation<P, M>>?]
+      : [Options<Operation<P, M>>]
+  ) =>
+    request<Operation<P, M>>(name, path as string, options ?? ({} as Options<Operation<P, M>>));
+}
+
+// e.g. apiClient.delete('/api/auth/sessions/{id}', { path: { id } })
+export const apiClient = {
+  get: method('get'),
+  post: method('post'),
+  put: method('put'),
+  patch: method('patch'),
+  delete: method('delete'),
+};
@@ -0,0 +1,34 @@
+import { invoke, isTauri } from '@tauri-apps/api/core';
+import { API_BASE_URL, Unauthorized, withSession } from './apiClient';
+import type { OrphanUser, SyncDeletedPeopleResponse } from '../types';
+
+// Desktop side of the cloud sync: people deleted in the desktop app's local database are
+// queued (deleted_people) until the cloud has deleted them and their servidor accounts too.
+// The desktop app calls the cloud itself (src-tauri/src/cloud.rs) with the session's token.
+
+export function desktopSyncAvailable(): boolean {
+  return isTauri();
+}
+
+// A sync command, refreshing the token when the cloud rejects it
+function syncCommand<T>(command: string): Promise<T> {
+  return withSession(async (token) => {
+    try {
+      return await invoke<T>(command, { apiUrl: API_BASE_URL, token: token ?? '' });
+    } catch (error) {
+      if (error === 'unauthorized') throw new Unauthorized();
+      throw new Error(String(error));
+    }
+  });
+}
+
+// Sends the queued deletions to the cloud and marks those it is done with (deleted there,
+// or already gone) as synced. Nothing is marked when the request fails, so it is retried.
+export function syncPersonDeletions(): Promise<SyncDeletedPeopleResponse> {
+  return syncCommand('sync_person_deletions');
+}
+
+// Cloud servidor accounts whose person is not in the desktop app's people
+export function findOrphanUsers(): Promise<OrphanUser[]> {
+  return syncCommand('find_orphan_users');
+}
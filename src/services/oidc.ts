@@ -0,0 +1,67 @@
+import type { OidcSettings } from '../types';
+
+// Browser side of single sign-on: Google Identity Services draws the "Sign in with Google"
+// button and hands back an ID token, which the API checks at /login/oidc.
+
+const API_BASE_URL = import.meta.env.VITE_API_URL || 'http://localhost:3000';
+const GIS_SCRIPT = 'https://accounts.google.com/gsi/client';
+
+interface GoogleIdentity {
+  accounts: {
+    id: {
+      initialize: (options: {
+        client_id: string;
+        callback: (response: { credential: string }) => void;
+        hd?: string;
+      }) => void;
+      renderButton: (parent: HTMLElement, options: Record<string, unknown>) => void;
+    };
+  };
+}
+
+declare global {
+  interface Window {
+    google?: GoogleIdentity;
+  }
+}
+
+// Public: the login page asks before anyone is signed in
+export async function getOidcSettings(): Promise<OidcSettings> {
+  const response = await fetch(`${API_BASE_URL}/login/oidc`);
+  if (!response.ok) return { enabled: false };
+  return response.json();
+}
+
+function loadScript(): Promise<GoogleIdentity> {
+  if (window.google) return Promise.resolve(window.google);
+  return new Promise((resolve, reject) => {
+    const script = document.createElement('script');
+    script.src = GIS_SCRIPT;
+    script.async = true;
+    script.onload = () => (window.google ? resolve(window.google) : reject(new Error('Google no cargó')));
+    script.onerror = () => reject(new Error('No se pudo cargar el inicio de sesión de Google'));
+    document.head.appendChild(script);
+  });
+}
+
+// Draws the button into `parent`; `onIdToken` gets the token once the person picks an account
+export async function renderGoogleButton(
+  parent: HTMLElement,
+  settings: OidcSettings,
+  onIdToken: (idToken: string) => void
+): Promise<void> {
+  if (!settings.enabled || !settings.client_id) return;
+  const google = await loadScript();
+  google.accounts.id.initialize({
+    client_id: settings.client_id,
+    callback: (response) => onIdToken(response.credential),
+    hd: settings.hosted_domain,
+  });
+  google.accounts.id.renderButton(parent, {
+    theme: 'outline',
+    size: 'large',
+    text: 'signin_with',
+    locale: 'es',
+    width: parent.clientWidth || 320,
+  });
+}
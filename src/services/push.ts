@@ -0,0 +1,54 @@
+import { pushApi } from './api';
+
+// Browser side of Web Push: public/sw.js shows the notifications, this file subscribes the
+// browser with the server's VAPID key and registers the subscription with the API.
+
+export function pushSupported(): boolean {
+  return 'serviceWorker' in navigator && 'PushManager' in window && 'Notification' in window;
+}
+
+// The VAPID key comes base64url-encoded; PushManager wants the raw bytes
+function keyBytes(base64url: string): Uint8Array {
+  const base64 = (base64url + '='.repeat((4 - (base64url.length % 4)) % 4))
+    .replace(/-/g, '+')
+    .replace(/_/g, '/');
+  return Uint8Array.from(atob(base64), (c) => c.charCodeAt(0));
+}
+
+async function registration(): Promise<ServiceWorkerRegistration> {
+  return (await navigator.serviceWorker.getRegistration('/')) ?? navigator.serviceWorker.register('/sw.js');
+}
+
+export async function isPushEnabled(): Promise<boolean> {
+  if (!pushSupported() || Notification.permission !== 'granted') return false;
+  const existing = await navigator.serviceWorker.getRegistration('/');
+  return !!(await existing?.pushManager.getSubscription());
+}
+
+// Asks for permission, subscribes and saves the subscription. Returns false when the
+// browser or the server cannot do push, or the person said no.
+export async function enablePush(): Promise<boolean> {
+  if (!pushSupported()) return false;
+  const { public_key } = await pushApi.getPublicKey();
+  if (!public_key) return false;
+  if ((await Notification.requestPermission()) !== 'granted') return false;
+
+  const reg = await registration();
+  await navigator.serviceWorker.ready;
+  const subscription =
+    (await reg.pushManager.getSubscription()) ??
+    (await reg.pushManager.subscribe({
+      userVisibleOnly: true,
+      applicationServerKey: keyBytes(public_key),
+    }));
+  await pushApi.subscribe(subscription.toJSON());
+  return true;
+}
+
+export async function disablePush(): Promise<void> {
+  const reg = await navigator.serviceWorker.getRegistration('/');
+  const subscription = await reg?.pushManager.getSubscription();
+  if (!subscription) return;
+  await pushApi.unsubscribe(subscription.endpoint);
+  await subscription.unsubscribe();
+}
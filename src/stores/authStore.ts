@@ -9,13 +9,26 @@ interface User {
 
 interface AuthState {
   token: string | null;
+  // Gets a new token at /login/refresh while the session is on
+  refreshToken: string | null;
   user: User | null;
   isAuthenticated: boolean;
   isLoading: boolean;
   error: string | null;
+  // The password (or Google account) was right; the login page asks for the 2FA code
+  twoFactorRequired: boolean;
 
   // Actions
   login: (username: string, password: string) => Promise<boolean>;
+  // Single sign-on: the ID token Google Identity Services gave the page
+  loginWithIdToken: (idToken: string) => Promise<boolean>;
+  // Set the password of an invitation link; signs in with the new login
+  acceptInvitation: (token: string, password: string) => Promise<boolean>;
+  // Repeat the sign-in that asked for it with the authenticator or recovery code
+  submitTwoFactorCode: (code: string) => Promise<boolean>;
+  cancelTwoFactor: () => void;
+  // A new token for the session; false when the session has ended
+  refresh: () => Promise<boolean>;
   logout: () => void;
   changePassword: (currentPassword: string, newPassword: string) => Promise<void>;
   clearError: () => void;
@@ -23,46 +36,118 @@ interface AuthState {
 
 const API_BASE_URL = import.meta.env.VITE_API_URL || 'http://localhost:3000';
 
+type SetAuth = (state: Partial<AuthState>) => void;
+
+// What the API answers when an account with 2FA signs in without a code
+const TWO_FACTOR_REQUIRED = 'Two-factor code required';
+
+// The sign-in waiting for its 2FA code; kept in memory only, never persisted
+let pendingSignIn: { path: string; body: object } | null = null;
+
+// Both sign-ins answer with the same token and user details
+async function signIn(set: SetAuth, path: string, body: object): Promise<boolean> {
+  set({ isLoading: true, error: null });
+  try {
+    const response = await fetch(`${API_BASE_URL}${path}`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(body),
+    });
+
+    if (!response.ok) {
+      const error = await response.text();
+      if (response.status === 401 && error === TWO_FACTOR_REQUIRED) {
+        pendingSignIn = { path, body };
+        set({ twoFactorRequired: true, isLoading: false });
+        return false;
+      }
+      throw new Error(error || 'Error de autenticación');
+    }
+    pendingSignIn = null;
+
+    const data = await response.json();
+    set({
+      token: data.token,
+      refreshToken: data.refresh_token,
+      user: { username: data.username, role: data.role, person_id: data.person_id },
+      isAuthenticated: true,
+      isLoading: false,
+      twoFactorRequired: false,
+    });
+    return true;
+  } catch (error) {
+    set({ error: String(error), isLoading: false });
+    return false;
+  }
+}
+
+// Each refresh token works once, so concurrent 401s share one refresh
+let refreshing: Promise<boolean> | null = null;
+
 export const useAuthStore = create<AuthState>()(
   persist(
     (set, get) => ({
       token: null,
+      refreshToken: null,
       user: null,
       isAuthenticated: false,
       isLoading: false,
       error: null,
+      twoFactorRequired: false,
 
-      login: async (username: string, password: string) => {
-        set({ isLoading: true, error: null });
-        try {
-          const response = await fetch(`${API_BASE_URL}/login`, {
-            method: 'POST',
-            headers: { 'Content-Type': 'application/json' },
-            body: JSON.stringify({ username, password }),
-          });
+      login: (username: string, password: string) =>
+        signIn(set, '/login', { username, password }),
 
-          if (!response.ok) {
-            const error = await response.text();
-            throw new Error(error || 'Error de autenticación');
-          }
+      loginWithIdToken: (idToken: string) =>
+        signIn(set, '/login/oidc', { id_token: idToken }),
+
+      acceptInvitation: (token: string, password: string) =>
+        signIn(set, `/invitations/${encodeURIComponent(token)}/accept`, { password }),
+
+      submitTwoFactorCode: (code: string) => {
+        if (!pendingSignIn) return Promise.resolve(false);
+        const { path, body } = pendingSignIn;
+        return signIn(set, path, { ...body, totp_code: code });
+      },
+
+      cancelTwoFactor: () => {
+        pendingSignIn = null;
+        set({ twoFactorRequired: false, error: null });
+      },
 
-          const data = await response.json();
-          set({
-            token: data.token,
-            user: { username: data.username, role: data.role, person_id: data.person_id },
-            isAuthenticated: true,
-            isLoading: false,
+      refresh: () => {
+        const { refreshToken } = get();
+        if (!refreshToken) return Promise.resolve(false);
+        refreshing ??= fetch(`${API_BASE_URL}/login/refresh`, {
+          method: 'POST',
+          headers: { 'Content-Type': 'application/json' },
+          body: JSON.stringify({ refresh_token: refreshToken }),
+        })
+          .then(async (response) => {
+            if (!response.ok) return false;
+            const data = await response.json();
+            set({ token: data.token, refreshToken: data.refresh_token });
+            return true;
+          })
+          .catch(() => false)
+          .finally(() => {
+            refreshing = null;
           });
-          return true;
-        } catch (error) {
-          set({ error: String(error), isLoading: false });
-          return false;
-        }
+        return refreshing;
       },
 
       logout: () => {
+        // End the session on the server too, so its refresh token stops working
+        const { token } = get();
+        if (token) {
+          fetch(`${API_BASE_URL}/api/auth/logout`, {
+            method: 'POST',
+            headers: { 'Authorization': `Bearer ${token}` },
+          }).catch(() => undefined);
+        }
         set({
           token: null,
+          refreshToken: null,
           user: null,
           isAuthenticated: false,
           error: null,
@@ -105,6 +190,7 @@ export const useAuthStore = create<AuthState>()(
       name: 'auth-storage',
       partialize: (state) => ({
         token: state.token,
+        refreshToken: state.refreshToken,
         user: state.user,
         isAuthenticated: state.isAuthenticated,
       }),
@@ -1,15 +1,16 @@
 import { create } from 'zustand';
 import { peopleApi } from '../services/api';
-import type { Person, PersonWithCredentials, CreatePersonRequest, UpdatePersonRequest } from '../types';
+import type { Person, PeopleListParams, PersonWithCredentials, CreatePersonRequest, UpdatePersonRequest } from '../types';
 
 interface PeopleState {
   people: Person[];
+  total: number; // Matching the last fetch's filters, across pages
   selectedPerson: Person | null;
   isLoading: boolean;
   error: string | null;
 
   // Actions
-  fetchPeople: () => Promise<void>;
+  fetchPeople: (params?: PeopleListParams) => Promise<void>;
   fetchPerson: (id: string) => Promise<void>;
   createPerson: (request: CreatePersonRequest) => Promise<PersonWithCredentials>;
   updatePerson: (request: UpdatePersonRequest) => Promise<Person>;
@@ -22,15 +23,16 @@ interface PeopleState {
 
 export const usePeopleStore = create<PeopleState>((set, get) => ({
   people: [],
+  total: 0,
   selectedPerson: null,
   isLoading: false,
   error: null,
 
-  fetchPeople: async () => {
+  fetchPeople: async (params?: PeopleListParams) => {
     set({ isLoading: true, error: null });
     try {
-      const people = await peopleApi.getAll();
-      set({ people, isLoading: false });
+      const { people, total } = await peopleApi.list(params);
+      set({ people, total, isLoading: false });
     } catch (error) {
       set({ error: String(error), isLoading: false });
     }
@@ -52,6 +54,7 @@ export const usePeopleStore = create<PeopleState>((set, get) => ({
       const person = await peopleApi.create(request);
       set((state) => ({
         people: [...state.people, person],
+        total: state.total + 1,
         isLoading: false,
       }));
       return person;
@@ -83,6 +86,7 @@ export const usePeopleStore = create<PeopleState>((set, get) => ({
       await peopleApi.delete(id);
       set((state) => ({
         people: state.people.filter((p) => p.id !== id),
+        total: Math.max(state.total - 1, 0),
         selectedPerson: state.selectedPerson?.id === id ? null : state.selectedPerson,
         isLoading: false,
       }));
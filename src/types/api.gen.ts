@@ -0,0 +1,7877 @@
+/**
+ * This file was auto-generated by openapi-typescript.
+ * Do not make direct changes to the file.
+ */
+
+export interface paths {
+    "/api/admin/api-keys": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["api_keys_get_all"];
+        put?: never;
+        /** The answer carries the key itself; only its hash is kept */
+        post: operations["api_keys_create"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/api-keys/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put: operations["api_keys_set_scopes"];
+        post?: never;
+        /** Revoke a key; integrations using it get 401 from then on */
+        delete: operations["api_keys_revoke"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/auth-events": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Recent sign-ins, failed attempts, password changes and resets, newest first */
+        get: operations["auth_events_get_all"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/background-jobs": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Recent jobs, newest first */
+        get: operations["background_jobs_list"];
+        put?: never;
+        /** Queue a job; the answer is 202 with the job, whose status can then be polled */
+        post: operations["background_jobs_create"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/background-jobs/run": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Work through the due jobs now, then deliver the queued notifications and pushes and update
+         *     the Google Sheets exports. The local server's worker does this on its own; on Lambda a scheduler calls it
+         *     every few minutes.
+         */
+        post: operations["background_jobs_run"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/background-jobs/{id}/retry": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /** Queue a failed job again */
+        post: operations["background_jobs_retry"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/bootstrap": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Set up a new parish: enable the chosen standard ministries with their positions and seed the
+         *     scheduler settings. Safe to call again: existing jobs, positions and settings are left as they are.
+         */
+        post: operations["admin_bootstrap"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/coordinators": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["admin_get_coordinators"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/guardians": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["guardians_get_all"];
+        put?: never;
+        /** One login for a parent of several servidores; the generated password is shown only once */
+        post: operations["guardians_create"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/guardians/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        delete: operations["guardians_delete"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/guardians/{id}/children": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        /** Replace the children a guardian answers for */
+        put: operations["guardians_set_children"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/notification-failures": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["notifications_get_failures"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/notification-failures/{id}/retry": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Try a failed notification again. On success it is marked resolved; otherwise the attempt
+         *     count and error are updated and the failure stays in the queue.
+         */
+        post: operations["notifications_retry_failure"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/notifications/send-digests": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Send the daily digests of people who chose one. Meant to be called once a day by a
+         *     scheduler, like the reminders.
+         */
+        post: operations["notifications_send_digests"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/reminders": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Who would be reminded, with the message each gets and whether it already went out */
+        get: operations["reminders_preview"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/reminders/send": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Send the due reminders that have not gone out yet. Meant to be called once a day by a
+         *     scheduler as well as by hand; a second call the same day sends nothing new.
+         */
+        post: operations["reminders_send"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/sheet-exports": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["sheets_get_exports"];
+        put?: never;
+        /**
+         * Add an export and write its tab straight away; a failed first write is reported in
+         *     `last_error` rather than refusing the export, so it can be fixed by sharing the sheet
+         */
+        post: operations["sheets_create_export"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/sheet-exports/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        /** Stop writing to a sheet; what was written stays there */
+        delete: operations["sheets_delete_export"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/sheet-exports/{id}/push": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Write the tab now, even if nothing changed (after the sheet was edited by hand, or to
+         *     check a fix for `last_error`)
+         */
+        post: operations["sheets_push_export"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/sync/deleted-people": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Apply person deletions made in the desktop app. A deleted person takes its servidor account
+         *     with it, so the cloud is not left with a login for someone who no longer exists.
+         */
+        post: operations["sync_apply_deleted_people"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/sync/reconcile-users": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /** Servidor accounts left without a person, in the cloud or in the desktop app */
+        post: operations["sync_reconcile_users"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/users/{id}/email": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        /** Set the email a user without a linked person (an admin, say) signs in with */
+        put: operations["oidc_set_user_email"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/users/{id}/job-scope": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        /**
+         * Make a servidor account the coordinator of some jobs, or hand a coordinator's jobs back
+         *     with an empty list. Takes effect the next time they log in.
+         */
+        put: operations["admin_set_job_scope"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/users/{id}/sessions": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** A user's sessions, for an admin helping someone who lost a device */
+        get: operations["sessions_get_for_user"];
+        put?: never;
+        post?: never;
+        /** Sign a user out everywhere */
+        delete: operations["sessions_revoke_all_for_user"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/users/{id}/sessions/{session_id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        /** Sign a user out of one session */
+        delete: operations["sessions_revoke_for_user"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/users/{id}/two-factor": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        /**
+         * For a user who lost both their phone and recovery codes: they sign in with the password
+         *     alone and can set two-factor up again
+         */
+        delete: operations["two_factor_reset_for_user"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/whatsapp-templates": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Which approved template each kind of notification is sent in. Kinds without one go out as
+         *     plain text, which WhatsApp only delivers within 24 hours of the person writing in.
+         */
+        get: operations["notifications_get_whatsapp_templates"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/admin/whatsapp-templates/{kind}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        /**
+         * Send a kind of notification (service_reminder, schedule_published, ...) in the named
+         *     template. The template takes the message text as its one parameter.
+         */
+        put: operations["notifications_set_whatsapp_template"];
+        post?: never;
+        delete: operations["notifications_delete_whatsapp_template"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/assignments/swap": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["schedules_swap_assignments"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/assignments/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put: operations["schedules_update_assignment"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/assignments/{id}/clear": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put: operations["schedules_clear_assignment"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/assignments/{id}/move": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put: operations["schedules_move_assignment"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/assignments/{id}/notes": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        /**
+         * Set or remove the note on a slot. The note belongs to the slot, so it stays when the
+         *     person serving it changes.
+         */
+        put: operations["schedules_update_assignment_notes"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/assignments/{id}/swap-suggestions": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Swaps with other filled slots of the same schedule that keep both people eligible and
+         *     do not double-book anyone, best fairness gain first. Swaps that would make the
+         *     combined fairness worse are left out.
+         */
+        get: operations["schedules_get_swap_suggestions"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/auth/change-password": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["auth_change_password"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/auth/logout": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /** End the session the request was made with */
+        post: operations["sessions_logout"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/auth/me": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["auth_me"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/auth/sessions": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Where the user is signed in, most recently used first */
+        get: operations["sessions_get_mine"];
+        put?: never;
+        post?: never;
+        /** Sign out everywhere, or everywhere else with `?keep_current=true` */
+        delete: operations["sessions_revoke_all_mine"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/auth/sessions/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        /** Sign out one of the user's devices */
+        delete: operations["sessions_revoke_mine"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/auth/two-factor": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Whether the signed-in user has two-factor on, and how many recovery codes are left */
+        get: operations["two_factor_get_status"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/auth/two-factor/disable": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /** Turn two-factor off, with a code from the app or a recovery code */
+        post: operations["two_factor_disable"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/auth/two-factor/enable": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /** Confirm the app works with one of its codes; answers with the recovery codes */
+        post: operations["two_factor_enable"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/auth/two-factor/recovery-codes": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /** New recovery codes in place of the old ones */
+        post: operations["two_factor_regenerate_recovery_codes"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/auth/two-factor/setup": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * A new secret to add to the authenticator app. Nothing changes at login until `enable`
+         *     confirms a code from it.
+         */
+        post: operations["two_factor_setup"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/background-jobs/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** One job's status and result. Whoever queued it can follow it, not only admins. */
+        get: operations["background_jobs_get_one"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/blackouts/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        delete: operations["unavailability_delete_blackout"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/exclusions/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        delete: operations["people_delete_exclusion"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/jobs": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["jobs_get_all"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/jobs/{id}/novice-limit": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        /** Admins, or a coordinator of the job */
+        put: operations["jobs_update_novice_limit"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/jobs/{id}/positions": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["jobs_get_positions"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/jobs/{id}/requirements": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        /** Admins, or a coordinator of the job */
+        put: operations["jobs_update_requirements"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/jobs/{id}/rotation": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Where each qualified person stands in the position rotation of a job.
+         *     People who still owe position 1 come first, closest to finishing their cycle first.
+         */
+        get: operations["jobs_get_rotation"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/my-assignments/{person_id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["schedules_get_my_assignments"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/my-availability/{date}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put: operations["unavailability_set_my_availability"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/my-calendar": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * The signed-in servidor's month at a glance: every Sunday and scheduled service date with
+         *     whether they serve (published assignments inside the visibility window), marked
+         *     themselves unavailable, or are free
+         */
+        get: operations["schedules_get_my_calendar"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/my-children": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** The children of the signed-in guardian, for picking whose absences to manage */
+        get: operations["guardians_get_my_children"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/my-family-assignments": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Published assignments of the signed-in servidor and their siblings (everyone sharing a
+         *     sibling group with them) for one month, in date order, so a family can plan its trips.
+         *     For a guardian, those of all their children and the children's siblings.
+         */
+        get: operations["schedules_get_my_family_assignments"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/my-notification-preferences": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** The servidor's notification preferences; all defaults until they save some */
+        get: operations["notifications_get_my_preferences"];
+        /**
+         * Replace the servidor's preferences. A blank or missing channel and lead time go back to the
+         *     parish defaults.
+         */
+        put: operations["notifications_update_my_preferences"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/my-notifications": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["notifications_get_my_notifications"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/my-notifications/{id}/read": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put: operations["notifications_mark_read"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/my-photo": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["people_upload_my_photo"];
+        delete: operations["people_delete_my_photo"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/my-push-subscriptions": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Turn on notifications for this browser. Subscribing again (or from another user on the same
+         *     browser) moves the subscription to the current user.
+         */
+        post: operations["push_subscribe"];
+        delete: operations["push_unsubscribe"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/my-unavailability": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["unavailability_get_my_unavailability"];
+        put?: never;
+        post: operations["unavailability_create_my_unavailability"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/my-unavailability/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        delete: operations["unavailability_delete_my_unavailability"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/next-service": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Who serves at the next service date(s), across jobs and schedules, with fill counts and
+         *     notes. Answers 304 when the client's ETag still matches.
+         */
+        get: operations["schedules_get_next_service"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/pairing-preferences": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["pairing_preferences_get_all"];
+        put?: never;
+        post: operations["pairing_preferences_create"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/pairing-preferences/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put: operations["pairing_preferences_update"];
+        post?: never;
+        delete: operations["pairing_preferences_delete"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["people_get_all"];
+        put?: never;
+        post: operations["people_create"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/directory": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Download the people roster as CSV or Excel. Coordinators get the people in their jobs. */
+        get: operations["people_export_directory"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/vcard": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Download people as a vCard bundle (names, phone, email) for phone directories */
+        get: operations["people_export_vcard"];
+        put?: never;
+        /**
+         * Create people from a vCard bundle. Contacts matching an existing person (email, phone or name)
+         *     are skipped, or have their phone and email filled in with `on_duplicate: "update"`.
+         *     Imported people get no login; use create-user afterwards for those who need one.
+         */
+        post: operations["people_import_vcard"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["people_get_by_id"];
+        put: operations["people_update"];
+        post?: never;
+        delete: operations["people_delete"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/{id}/assignments": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * A person's assignments in every non-archived schedule, drafts included, so the load can
+         *     be checked before publishing. Coordinators only see the jobs in their scope.
+         */
+        get: operations["schedules_get_person_assignments"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/{id}/blackouts": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["unavailability_get_person_blackouts"];
+        /** Create or replace the blackout months of a person for one job */
+        put: operations["unavailability_set_person_blackout"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/{id}/calendar-feed": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["calendar_feeds_get_feed"];
+        put?: never;
+        /**
+         * Create the person's feed, or give it a new token when it exists (revoking the old link).
+         *     The answer is the only time the token is shown.
+         */
+        post: operations["calendar_feeds_rotate_feed"];
+        /** Revoke the person's feed; subscribed calendars stop updating */
+        delete: operations["calendar_feeds_delete_feed"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/{id}/calendar.ics": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * A person's published assignments as an .ics file to import into a phone calendar. Servidores
+         *     can download their own; coordinators the people in their jobs.
+         */
+        get: operations["schedules_export_person_calendar"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/{id}/create-user": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["people_create_user_account"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/{id}/exclusions": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["people_get_exclusions"];
+        /** Exclude a person from one job, or update the reason if already excluded */
+        put: operations["people_set_exclusion"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/{id}/invitation": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** The person's invitation: when it was sent, until when it works, whether it was used */
+        get: operations["invitations_get_for_person"];
+        put?: never;
+        /**
+         * Invite a person who has no login; 409 while an earlier invitation still works (resend it
+         *     instead)
+         */
+        post: operations["invitations_create"];
+        /** Withdraw a pending invitation; its link stops working */
+        delete: operations["invitations_cancel"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/{id}/invitation/resend": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /** A new link with a new expiry, for a lost or expired one; the previous link stops working */
+        post: operations["invitations_resend"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/{id}/photo": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["people_upload_photo"];
+        delete: operations["people_delete_photo"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/{id}/regenerate-username": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["people_regenerate_username"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/people/{id}/reset-password": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["people_reset_password"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/push/public-key": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** No key, so no push offered, unless the server has the key pair to send with */
+        get: operations["push_get_public_key"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/reports/birthdays": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["reports_get_birthdays"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/reports/fairness": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["reports_get_fairness_scores"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/reports/fairness/export": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * The fairness report as an Excel workbook for reviewing balance with the pastor: a summary
+         *     sheet, then one sheet per job with each person's count and last date served in it
+         */
+        get: operations["reports_export_fairness_workbook"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/reports/juniors": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["reports_get_junior_roster"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/reports/person/{id}/history": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["reports_get_person_history"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/reports/recognition/{year}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["reports_get_recognition"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/reports/recognition/{year}/certificates": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Recognition certificates as a PDF, one page per servidor (or just the one asked for) */
+        get: operations["reports_get_recognition_certificates"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/reports/recruiting-needs": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Per job, compare the slots a month needs with what the current pool can sustainably cover.
+         *     Everyone serves a job at most once a month; people who prefer to serve monthly are split
+         *     across the jobs they do, and jobs with the consecutive-month rule only get each person
+         *     every other month (unless the month has 5 Sundays).
+         */
+        get: operations["reports_get_recruiting_needs"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["schedules_get_all"];
+        put?: never;
+        post: operations["schedules_generate"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/batch": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Generate one schedule per month from start to end, in order, like the desktop's
+         *     `generate_year_schedules`. Each month's history is written before the next one is
+         *     generated, so rotation and consecutive-month rules carry forward. Months that already
+         *     have a schedule are skipped; everything else lands together or not at all.
+         */
+        post: operations["schedules_generate_batch"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/double-bookings": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * People assigned on two service dates that fall on the same day, across all schedules.
+         *     Coordinators only get the bookings that involve one of their jobs.
+         */
+        get: operations["schedules_get_double_bookings"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/range": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Generate a quarter or a liturgical season in one request, either as one long schedule
+         *     (`mode: "single"`) or as one linked schedule per calendar month (`mode: "monthly"`)
+         */
+        post: operations["schedules_generate_range"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["schedules_get_by_id"];
+        put?: never;
+        post?: never;
+        delete: operations["schedules_delete"];
+        options?: never;
+        head?: never;
+        patch: operations["schedules_update"];
+        trace?: never;
+    };
+    "/api/schedules/{id}/archive": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Put a finished schedule away: it leaves the default listing and can no longer be edited.
+         *     PATCH it back to DRAFT to work on it again.
+         */
+        post: operations["schedules_archive"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/assignments/bulk": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Apply a list of set/clear/swap edits to one schedule in a single transaction, in order.
+         *     Drag-and-drop sessions land whole or not at all; the answer is the updated schedule.
+         */
+        post: operations["schedules_bulk_update_assignments"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/audit": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Who changed which slots of the schedule and when, newest first. Coordinators only see
+         *     the jobs in their scope.
+         */
+        get: operations["schedules_get_audit"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/completeness": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["schedules_get_schedule_completeness"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/completeness/suggestions": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Companion to the completeness check: every empty slot with the people who could fill
+         *     it. Eligible people come first; near misses list the rules that block them so the
+         *     admin can decide what to relax.
+         */
+        get: operations["schedules_get_completeness_suggestions"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/diff": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["schedules_get_diff"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/duplicate": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Start a month from another schedule: the Nth service date of the source goes to the Nth
+         *     Sunday of the new month. Copied people are marked as suggestions until someone edits the
+         *     slot; anyone who cannot serve the new date leaves the slot empty and is listed in `conflicts`.
+         */
+        post: operations["schedules_duplicate"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/export": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Every slot of the schedule (drafts included, for coordinators who send messages before
+         *     publishing) with the person's phone. Coordinators only get their own jobs.
+         */
+        get: operations["schedules_export_schedule"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/jobs": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Each job that has slots in the schedule with its effective publishing status.
+         *     Coordinators only get the jobs in their scope.
+         */
+        get: operations["schedules_get_job_statuses"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/jobs/{job_id}/publish": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Publish one job of a draft schedule, e.g. Lectores while Monaguillos is still being
+         *     worked on. Its assignments show up in my-assignments right away.
+         */
+        post: operations["schedules_publish_job"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/jobs/{job_id}/unpublish": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["schedules_unpublish_job"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/publish": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["schedules_publish"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/redo": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /** Apply again the edit undone last; a new edit in between discards it */
+        post: operations["schedules_redo"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/regenerate": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Run the algorithm again over an existing schedule. With `mode: "future"` (the default)
+         *     dates before today keep their assignments and history exactly as served, and the people
+         *     on them still count toward this month's limits; `mode: "all"` recomputes every date.
+         *     Manual edits on recomputed dates are replaced. Returns the recomputed dates only.
+         */
+        post: operations["schedules_regenerate"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/signage.json": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * The schedule for the narthex display, in the versioned shape of `services::signage`.
+         *     Only published jobs are shown.
+         */
+        get: operations["schedules_export_signage"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/snapshots": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Versions saved each time the schedule was published, newest first */
+        get: operations["schedules_get_snapshots"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/status-history": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Status changes of a schedule, newest first */
+        get: operations["schedules_get_status_history"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/summary.txt": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Plain text to paste into the group chat; same access and slots as the CSV export */
+        get: operations["schedules_export_summary"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/undo": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Revert the latest edit of the schedule (update, clear, swap, move or bulk), with its
+         *     rotation and assignment_history changes
+         */
+        post: operations["schedules_undo"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/unpublish": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Take a published schedule back to draft for major changes. The reason is kept with the
+         *     change; with `notify` everyone assigned in it is told the published dates may change.
+         */
+        post: operations["schedules_unpublish"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/schedules/{id}/what-if/absence/{person_id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Simulate a person dropping out of a draft schedule: list the upcoming slots they hold and
+         *     the best replacement for each. Nothing is written.
+         */
+        get: operations["schedules_simulate_absence"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/service-dates/{id}/cancel": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Call off a service date: its assignments and their history entries are removed (so they
+         *     no longer count for fairness or rotation), the date drops out of completeness, and
+         *     everyone who could already see their assignment on it is notified
+         */
+        post: operations["service_dates_cancel"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/service-dates/{id}/history": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Chronological changes to a service date's assignments (generated, substituted, swapped...);
+         *     coordinators only see the jobs they coordinate
+         */
+        get: operations["service_dates_get_history"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/service-dates/{id}/notes": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        /** Set or remove the note on a service date (e.g. "Misa de Primeras Comuniones, llegar 7:30") */
+        put: operations["service_dates_update_notes"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/settings": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Settings include the token and session lifetimes, so only admins read or change them */
+        get: operations["settings_get_all"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/settings/{key}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put: operations["settings_update"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/sibling-groups": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["sibling_groups_get_all"];
+        put?: never;
+        post: operations["sibling_groups_create"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/sibling-groups/duplicates": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Groups that share members, with the people they share and whether their rules disagree */
+        get: operations["sibling_groups_get_duplicates"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/sibling-groups/merge": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Fold several sibling groups into the first one. Members are combined, the other groups are
+         *     deleted, and the pairing rule and scope are resolved (or taken from the request). When the
+         *     result keeps siblings apart, upcoming assignments that now break the rule are returned.
+         */
+        post: operations["sibling_groups_merge"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/sibling-groups/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put: operations["sibling_groups_update"];
+        post?: never;
+        delete: operations["sibling_groups_delete"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/staffing-overrides": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["staffing_get_all"];
+        /** Create or replace the override for a date and job; coordinators only for their jobs */
+        put: operations["staffing_set"];
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/staffing-overrides/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        delete: operations["staffing_delete"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/teams": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["teams_get_all"];
+        put?: never;
+        post: operations["teams_create"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/teams/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put: operations["teams_update"];
+        post?: never;
+        delete: operations["teams_delete"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/unavailability": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["unavailability_get_all"];
+        put?: never;
+        post: operations["unavailability_create"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/unavailability/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post?: never;
+        delete: operations["unavailability_delete"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/bot/{token}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * Webhook for the parish chat bot. The relay authenticates with the token in the path. The sender's
+         *     phone is matched to active people, so one family number can speak for several servidores.
+         *     Answers always come back as 200 with a reply so the relay just forwards them.
+         */
+        post: operations["bot_webhook"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/calendar/{token}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Public: the token is the only credential, so an unknown token is a plain 404. The path
+         *     segment carries the `.ics` extension some calendar apps insist on.
+         */
+        get: operations["calendar_feeds_get_feed_ics"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/health": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["health_health_check"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/integrations/assignments": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Published assignments, the same rows as the spreadsheet feeds. Scope `assignments:read`. */
+        get: operations["api_keys_get_assignments"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/integrations/next-service": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /**
+         * Who serves at the next service date(s), for the sacristy display; dates past the
+         *     servidores' visibility window stay out. Scope `next_service:read`.
+         */
+        get: operations["api_keys_get_next_service"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/invitations/{token}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Who the link is for, so the page can greet them before they pick a password */
+        get: operations["invitations_get_by_token"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/invitations/{token}/accept": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /** Set the password, create the login and sign in with it. The link works once. */
+        post: operations["invitations_accept"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/login": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["auth_login"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/login/oidc": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Whether the login page should offer the provider, and with which client id */
+        get: operations["oidc_get_settings"];
+        put?: never;
+        /**
+         * Sign in with the provider's ID token instead of a password. Only existing users can: the
+         *     account is matched by email (see `oidc::find_user`), never created.
+         */
+        post: operations["oidc_login"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/login/refresh": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        /**
+         * A new token for a session that is still on. The refresh token is replaced too, so each
+         *     one works once.
+         */
+        post: operations["auth_refresh"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+}
+export type webhooks = Record<string, never>;
+export interface components {
+    schemas: {
+        /** @description A slot the absent person holds and who could take it over */
+        AbsenceImpact: {
+            assignment_id: string;
+            job_id: string;
+            job_name: string;
+            /** Format: int32 */
+            position?: number | null;
+            position_name?: string | null;
+            replacements: components["schemas"]["ReplacementCandidate"][];
+            /** Format: date */
+            service_date: string;
+        };
+        /** @description Result of simulating a person's absence from a draft schedule (nothing is saved) */
+        AbsenceSimulation: {
+            affected: components["schemas"]["AbsenceImpact"][];
+            person_id: string;
+            person_name: string;
+            schedule_id: string;
+            /** @description Slots that would stay empty because nobody else is eligible */
+            uncovered: number;
+        };
+        AcceptInvitationRequest: {
+            password: string;
+        };
+        /** @description A key for a read-only integration; the secret itself is never returned after creation */
+        ApiKey: {
+            /** Format: date-time */
+            created_at: string;
+            created_by: string;
+            id: string;
+            /** Format: date-time */
+            last_used_at?: string | null;
+            name: string;
+            /** Format: date-time */
+            revoked_at?: string | null;
+            scopes: string[];
+        };
+        AppSetting: {
+            description?: string | null;
+            key: string;
+            /** Format: date-time */
+            updated_at: string;
+            value: string;
+        };
+        Assignment: {
+            /** Format: date-time */
+            created_at?: string | null;
+            /** @description Username that created the slot (None = the generator) and that last changed it */
+            created_by?: string | null;
+            id: string;
+            job_id: string;
+            manual_override?: boolean | null;
+            /** @description Instructions for whoever serves the slot, e.g. "arrive 20 min early for rehearsal" */
+            notes?: string | null;
+            /** @description Why the person was assigned despite a warning (unavailable, sibling conflict) */
+            override_reason?: string | null;
+            person_id?: string | null;
+            /** Format: int32 */
+            position?: number | null;
+            position_name?: string | null;
+            service_date_id: string;
+            /** @description Copied from another month and not looked at yet */
+            suggested?: boolean;
+            /** Format: date-time */
+            updated_at?: string | null;
+            updated_by?: string | null;
+        };
+        /** @description A slot that has someone else in it */
+        AssignmentChange: {
+            from_person_id?: string | null;
+            from_person_name?: string | null;
+            job_id: string;
+            job_name: string;
+            /** Format: int32 */
+            position?: number | null;
+            position_name?: string | null;
+            /** Format: date */
+            service_date: string;
+            to_person_id?: string | null;
+            to_person_name?: string | null;
+        };
+        AssignmentEvent: {
+            action: string;
+            assignment_id: string;
+            changed_by?: string | null;
+            /** Format: date-time */
+            created_at: string;
+            id: string;
+            job_id: string;
+            job_name: string;
+            person_id?: string | null;
+            person_name?: string | null;
+            /** Format: int32 */
+            position?: number | null;
+            position_name?: string | null;
+            previous_person_id?: string | null;
+            previous_person_name?: string | null;
+            reason?: string | null;
+        };
+        AssignmentWithDetails: components["schemas"]["Assignment"] & {
+            job_color?: string | null;
+            job_name: string;
+            person_name: string;
+            person_notes?: string | null;
+            schedule_id: string;
+            /** Format: int32 */
+            schedule_month: number;
+            /** Format: int32 */
+            schedule_year: number;
+            scheduling_notes?: string | null;
+            /** Format: date */
+            service_date: string;
+        };
+        /** @description A sign-in or password change, from the `auth_events` audit trail */
+        AuthEventEntry: {
+            /** Format: date-time */
+            created_at: string;
+            detail?: string | null;
+            event: string;
+            id: string;
+            ip?: string | null;
+            user_agent?: string | null;
+            /** Format: uuid */
+            user_id?: string | null;
+            username?: string | null;
+        };
+        /**
+         * @description Work queued for the background worker. `payload` and `result` are the kind's JSON: the
+         *     request it was queued with and what the run returned.
+         */
+        BackgroundJob: {
+            /** Format: int32 */
+            attempts: number;
+            /** Format: date-time */
+            created_at: string;
+            created_by?: string | null;
+            error?: string | null;
+            /** Format: date-time */
+            finished_at?: string | null;
+            id: string;
+            /** @description generate_batch, send_reminders, send_digests or notify_published */
+            kind: string;
+            /** Format: int32 */
+            max_attempts: number;
+            payload: unknown;
+            result?: unknown;
+            /** Format: date-time */
+            run_after: string;
+            /** Format: date-time */
+            started_at?: string | null;
+            /** @description queued, running, done or failed */
+            status: string;
+        };
+        /** @description Outcome of one month of a batch generation */
+        BatchMonthSummary: {
+            conflicts: components["schemas"]["ScheduleConflict"][];
+            filled_slots: number;
+            /** Format: int32 */
+            month: number;
+            schedule_id?: string | null;
+            service_dates: number;
+            /** @description "generated", or "skipped" when the month already had a schedule */
+            status: string;
+            total_slots: number;
+            /** Format: int32 */
+            year: number;
+        };
+        BirthdayEntry: {
+            /** Format: date */
+            birth_date: string;
+            /** Format: date */
+            birthday: string;
+            person_id: string;
+            person_name: string;
+            photo_url?: string | null;
+            /** Format: int32 */
+            turning_age: number;
+        };
+        BootstrapRequest: {
+            /** @description Standard ministries to enable: "monaguillos", "lectores", "monaguillos_jr" */
+            ministries: string[];
+            parish_name: string;
+        };
+        BootstrapResponse: {
+            jobs: components["schemas"]["Job"][];
+            jobs_created: string[];
+            parish_name: string;
+            /** Format: int64 */
+            positions_created: number;
+            settings_created: string[];
+        };
+        /** @description A message relayed from the WhatsApp/Telegram bot */
+        BotMessage: {
+            /** @description The sender's phone number in any format, e.g. "whatsapp:+50255551234" */
+            from: string;
+            text: string;
+        };
+        /** @description The text the relay sends back to the chat */
+        BotReply: {
+            reply: string;
+        };
+        /** @description One edit in a bulk request, e.g. `{"op": "swap", "assignment_id_1": ..., "assignment_id_2": ...}` */
+        BulkAssignmentOperation: {
+            assignment_id: string;
+            expected_person_id?: string | null;
+            /** @enum {string} */
+            op: "set";
+            override_reason?: string | null;
+            person_id: string;
+        } | {
+            assignment_id: string;
+            /** @enum {string} */
+            op: "clear";
+        } | {
+            assignment_id_1: string;
+            assignment_id_2: string;
+            /** @enum {string} */
+            op: "swap";
+        };
+        /** @description Applied in order, all or nothing */
+        BulkAssignmentsRequest: {
+            operations: components["schemas"]["BulkAssignmentOperation"][];
+        };
+        CalendarAssignment: {
+            job_color?: string | null;
+            job_id: string;
+            job_name: string;
+            position_name?: string | null;
+        };
+        CalendarDay: {
+            assignments: components["schemas"]["CalendarAssignment"][];
+            /** Format: date */
+            date: string;
+            /**
+             * @description "cancelled", "assigned", "unavailable" or "available"; assigned wins over
+             *     unavailable, so a clash shows up as an assigned day that still lists its unavailability
+             */
+            status: string;
+            unavailability: components["schemas"]["CalendarUnavailability"][];
+        };
+        /** @description A person's calendar subscription link; rotating replaces the token, deleting revokes it */
+        CalendarFeed: {
+            /**
+             * Format: date-time
+             * @description Also when it was last rotated
+             */
+            created_at: string;
+            /** Format: date-time */
+            last_fetched_at?: string | null;
+            person_id: string;
+        };
+        /**
+         * @description An unavailability record covering a calendar day. Only `single_day` records can be
+         *     toggled off from the grid; longer or recurring ones are edited as a whole.
+         */
+        CalendarUnavailability: {
+            id: string;
+            reason?: string | null;
+            single_day: boolean;
+        };
+        CancelServiceDateRequest: {
+            reason?: string | null;
+        };
+        ChangePasswordRequest: {
+            current_password: string;
+            new_password: string;
+        };
+        CompletenessResponse: {
+            empty_slots: components["schemas"]["EmptySlot"][];
+            /** Format: int64 */
+            filled_slots: number;
+            is_complete: boolean;
+            /** Format: int64 */
+            total_slots: number;
+        };
+        CoordinatorScope: {
+            job_ids: string[];
+            person_id?: string | null;
+            /** Format: uuid */
+            user_id: string;
+            username: string;
+        };
+        CreateApiKeyRequest: {
+            name: string;
+            scopes: string[];
+        };
+        CreateBackgroundJobRequest: {
+            kind: string;
+            payload?: unknown;
+        };
+        CreateGuardianRequest: {
+            person_ids: string[];
+            username: string;
+        };
+        CreateMyUnavailability: {
+            dates: string[];
+            reason?: string | null;
+        };
+        CreatePairingPreference: {
+            notes?: string | null;
+            person_a_id: string;
+            person_b_id: string;
+            preference: string;
+        };
+        CreatePerson: {
+            address?: string | null;
+            /** Format: date */
+            birth_date?: string | null;
+            email?: string | null;
+            first_communion?: boolean | null;
+            first_name: string;
+            invite?: boolean;
+            job_ids: string[];
+            last_name: string;
+            /** Format: int32 */
+            max_consecutive_weeks?: number | null;
+            /** Format: int32 */
+            min_rest_days?: number | null;
+            notes?: string | null;
+            parent_name?: string | null;
+            phone?: string | null;
+            photo_consent?: boolean | null;
+            /** Format: int32 */
+            preference_level?: number | null;
+            preferred_frequency?: string | null;
+            scheduling_notes?: string | null;
+        };
+        CreatePersonJobBlackout: {
+            job_id: string;
+            months: number[];
+            reason?: string | null;
+        };
+        CreatePersonJobExclusion: {
+            job_id: string;
+            reason?: string | null;
+        };
+        CreateSheetExportRequest: {
+            job_id?: string | null;
+            name: string;
+            sheet_name: string;
+            /** @description The spreadsheet's id or its address */
+            spreadsheet: string;
+        };
+        CreateSiblingGroup: {
+            member_ids: string[];
+            name: string;
+            pairing_rule: string;
+            /** @description Defaults to SAME_JOB */
+            pairing_scope?: string | null;
+        };
+        CreateTeam: {
+            active?: boolean | null;
+            job_id: string;
+            member_ids: string[];
+            name: string;
+        };
+        CreateUnavailability: {
+            /** Format: date */
+            end_date: string;
+            person_id: string;
+            reason?: string | null;
+            /** @description e.g. "FREQ=WEEKLY;BYDAY=SA" or "FREQ=MONTHLY;BYDAY=1SU"; makes the record recurring */
+            recurrence?: string | null;
+            recurring?: boolean | null;
+            /** Format: date */
+            start_date: string;
+        };
+        CreatedApiKey: components["schemas"]["ApiKey"] & {
+            /** @description "psk_<id>.<secret>", for the X-Api-Key header; shown only this once */
+            key: string;
+        };
+        CreatedCalendarFeed: components["schemas"]["CalendarFeed"] & {
+            /**
+             * @description For the link "<api host>/calendar/<token>.ics"; only its digest is kept, so it is
+             *     shown only this once
+             */
+            token: string;
+        };
+        /** @description `GET /auth/me`: who the token belongs to */
+        CurrentUser: {
+            id: string;
+            job_ids: string[];
+            role: string;
+            username: string;
+        };
+        DeletePushSubscriptionRequest: {
+            endpoint: string;
+        };
+        DigestRun: {
+            /** @description People who were sent a digest */
+            sent: number;
+        };
+        /**
+         * @description A person holding slots on more than one service date that falls on the same calendar day,
+         *     usually from two schedules that both cover a special date
+         */
+        DoubleBooking: {
+            person_id: string;
+            person_name: string;
+            /** Format: date */
+            service_date: string;
+            slots: components["schemas"]["DoubleBookingSlot"][];
+        };
+        DoubleBookingSlot: {
+            assignment_id: string;
+            job_id: string;
+            job_name: string;
+            /** Format: int32 */
+            position?: number | null;
+            schedule_id: string;
+            schedule_name: string;
+        };
+        /** @description Copy a schedule's assignments onto another month */
+        DuplicateScheduleRequest: {
+            /** Format: int32 */
+            month: number;
+            /** @description Defaults to the name pattern for the new month */
+            name?: string | null;
+            /** Format: int32 */
+            year: number;
+        };
+        EmptySlot: {
+            job_name: string;
+            position_name?: string | null;
+            service_date: string;
+        };
+        /**
+         * @description An empty slot of a schedule and the best people to fill it.
+         *     `assignment_id` is None for positions that were never created.
+         */
+        EmptySlotSuggestions: {
+            assignment_id?: string | null;
+            candidates: components["schemas"]["SlotCandidate"][];
+            job_id: string;
+            job_name: string;
+            position_name?: string | null;
+            /** Format: date */
+            service_date: string;
+        };
+        FairnessScore: {
+            assignments_by_job: components["schemas"]["JobAssignmentCount"][];
+            /** Format: int64 */
+            assignments_this_year: number;
+            /** Format: date */
+            last_assignment_date?: string | null;
+            person_id: string;
+            person_name: string;
+        };
+        FamilyAssignment: {
+            job_color: string;
+            job_id: string;
+            job_name: string;
+            notes?: string | null;
+            person_id: string;
+            person_name: string;
+            /** Format: int32 */
+            position?: number | null;
+            position_name?: string | null;
+            /** Format: date */
+            service_date: string;
+        };
+        /** @description Generate every month from start to end (inclusive), one schedule each */
+        GenerateScheduleBatchRequest: {
+            /** Format: int32 */
+            end_month: number;
+            /** Format: int32 */
+            end_year: number;
+            /** Format: int32 */
+            start_month: number;
+            /** Format: int32 */
+            start_year: number;
+        };
+        GenerateScheduleRangeRequest: {
+            /** Format: date */
+            end_date: string;
+            /** @description "monthly" (default): one linked schedule per month; "single": one long schedule */
+            mode?: string | null;
+            name?: string | null;
+            /** Format: date */
+            start_date: string;
+        };
+        GenerateScheduleRequest: {
+            /** Format: int32 */
+            month: number;
+            /** Format: int32 */
+            year: number;
+        };
+        /** @description A parent's login, answering for several servidores */
+        GuardianAccount: {
+            person_ids: string[];
+            /** Format: uuid */
+            user_id: string;
+            username: string;
+        };
+        GuardianChild: {
+            first_name: string;
+            last_name: string;
+            person_id: string;
+            photo_url?: string | null;
+        };
+        GuardianWithCredentials: components["schemas"]["GuardianAccount"] & {
+            password: string;
+        };
+        ImportVCardRequest: {
+            /** @description Contents of a .vcf file (one or more cards) */
+            data: string;
+            /** @description "skip" (default) leaves existing people untouched; "update" fills in their phone and email */
+            on_duplicate?: string | null;
+        };
+        /** @description A servidor's invitation to set their own password; the link's token is never stored */
+        Invitation: {
+            /** Format: date-time */
+            accepted_at?: string | null;
+            /**
+             * Format: date-time
+             * @description When the current link was sent
+             */
+            created_at: string;
+            created_by: string;
+            /** Format: date-time */
+            expires_at: string;
+            person_id: string;
+        };
+        /** @description What the invitation page shows before the servidor picks a password */
+        InvitationDetails: {
+            /** Format: date-time */
+            expires_at: string;
+            first_name: string;
+            last_name: string;
+        };
+        IssuedInvitation: components["schemas"]["Invitation"] & {
+            /** @description Goes in the link the servidor opens; shown only this once */
+            token: string;
+        };
+        Job: {
+            active: boolean;
+            color?: string | null;
+            /** Format: date-time */
+            created_at?: string | null;
+            description?: string | null;
+            id: string;
+            /** Format: int32 */
+            max_novices_per_date?: number | null;
+            name: string;
+            /** Format: int32 */
+            novice_threshold: number;
+            /** Format: int32 */
+            people_required: number;
+            requires_first_communion: boolean;
+            requires_mentor: boolean;
+            /** Format: date-time */
+            updated_at?: string | null;
+        };
+        JobAssignmentCount: {
+            /** Format: int64 */
+            count: number;
+            job_name: string;
+        };
+        JobPosition: {
+            /** Format: date-time */
+            created_at?: string | null;
+            id: string;
+            job_id: string;
+            name: string;
+            /** Format: int32 */
+            position_number: number;
+        };
+        JuniorRosterEntry: {
+            /** Format: int32 */
+            age?: number | null;
+            /** Format: date */
+            birth_date?: string | null;
+            first_communion: boolean;
+            /** Format: int64 */
+            junior_services: number;
+            /** Format: date */
+            last_junior_service?: string | null;
+            missing_requirements: string[];
+            person_id: string;
+            person_name: string;
+            qualified_as_senior: boolean;
+            ready_for_promotion: boolean;
+            /** Format: int64 */
+            senior_services: number;
+        };
+        LoginRequest: {
+            password: string;
+            /** @description For accounts with two-factor on: a code from the authenticator app or a recovery code */
+            totp_code?: string | null;
+            username: string;
+        };
+        LoginResponse: {
+            person_id?: string | null;
+            /** @description Trade at `POST /login/refresh` for a new token when this one expires */
+            refresh_token: string;
+            role: string;
+            token: string;
+            username: string;
+        };
+        MergeSiblingGroups: {
+            /** @description The first group is kept; the others are folded into it and deleted */
+            group_ids: string[];
+            name?: string | null;
+            /** @description Required when the groups disagree, otherwise their common value is kept */
+            pairing_rule?: string | null;
+            pairing_scope?: string | null;
+        };
+        MoveAssignmentRequest: {
+            target_job_id: string;
+            /** Format: int32 */
+            target_position: number;
+            target_service_date_id: string;
+        };
+        MyAssignment: {
+            job_color: string;
+            job_id: string;
+            job_name: string;
+            notes?: string | null;
+            /** Format: int32 */
+            position?: number | null;
+            position_name?: string | null;
+            /** Format: date */
+            service_date: string;
+            /** @description The service date's note, shared by everyone serving that day */
+            service_date_notes?: string | null;
+        };
+        /** @description A day's availability after a toggle; `unavailability` is the single-day record, if any */
+        MyAvailability: {
+            available: boolean;
+            /** Format: date */
+            date: string;
+            unavailability?: null | components["schemas"]["Unavailability"];
+        };
+        NextService: components["schemas"]["SignageDate"] & {
+            filled_slots: number;
+            open_slots: number;
+            schedule_id: string;
+            schedule_name: string;
+        };
+        /**
+         * @description The next service dates across schedules, read every week by the WhatsApp bot and the
+         *     kiosk. Same versioning rule as the signage schedule.
+         */
+        NextServices: {
+            services: components["schemas"]["NextService"][];
+            /** Format: int32 */
+            version: number;
+        };
+        Notification: {
+            /** Format: date-time */
+            created_at?: string | null;
+            id: string;
+            kind: string;
+            message: string;
+            person_id: string;
+            /** Format: date-time */
+            read_at?: string | null;
+        };
+        /** @description Notification that could not be delivered, kept until a retry succeeds */
+        NotificationFailure: {
+            /** Format: int32 */
+            attempts: number;
+            channel: string;
+            /** Format: date-time */
+            created_at?: string | null;
+            destination?: string | null;
+            error: string;
+            id: string;
+            kind: string;
+            /** Format: date-time */
+            last_attempt_at?: string | null;
+            message: string;
+            permanent: boolean;
+            person_id: string;
+            person_name: string;
+            /** Format: date-time */
+            resolved_at?: string | null;
+        };
+        /** @description How a servidor wants to be told things; None = each notification's default */
+        NotificationPreferences: {
+            /** @description email, sms, whatsapp, push or in_app */
+            channel?: string | null;
+            /** @description One message a day with everything instead of each notification right away */
+            digest: boolean;
+            person_id: string;
+            /**
+             * Format: int32
+             * @description Days before a service date to be reminded (0 = no reminders)
+             */
+            reminder_days_before?: number | null;
+            /** Format: date-time */
+            updated_at?: string | null;
+        };
+        OidcLoginRequest: {
+            /** @description The ID token the provider gave the browser */
+            id_token: string;
+            /** @description For accounts with two-factor on */
+            totp_code?: string | null;
+        };
+        /**
+         * @description What the login page needs to offer "Sign in with Google"; `enabled` is false without
+         *     OIDC_CLIENT_ID
+         */
+        OidcSettings: {
+            client_id?: string | null;
+            enabled: boolean;
+            hosted_domain?: string | null;
+            issuer?: string | null;
+        };
+        /**
+         * @description A servidor account whose person is gone: "no_person" when the cloud has no person for it,
+         *     "deleted_locally" when the person is missing from the desktop app
+         */
+        OrphanUser: {
+            /** Format: date-time */
+            created_at?: string | null;
+            /** Format: uuid */
+            id: string;
+            person_id?: string | null;
+            person_name?: string | null;
+            reason: string;
+            username: string;
+        };
+        PairingPreference: {
+            /** Format: date-time */
+            created_at?: string | null;
+            id: string;
+            notes?: string | null;
+            person_a_id: string;
+            person_b_id: string;
+            preference: string;
+            /** Format: date-time */
+            updated_at?: string | null;
+        };
+        /** @description `GET /people`: one page of the people matching the filters */
+        PeoplePage: {
+            /**
+             * Format: int64
+             * @description None when everyone matching was returned at once
+             */
+            limit?: number | null;
+            /** Format: int64 */
+            page: number;
+            people: components["schemas"]["PersonWithJobs"][];
+            /**
+             * Format: int64
+             * @description All the people matching the filters, across pages
+             */
+            total: number;
+        };
+        Person: {
+            active: boolean;
+            address?: string | null;
+            /** Format: date */
+            birth_date?: string | null;
+            contact_bounced: boolean;
+            /** Format: date-time */
+            created_at?: string | null;
+            email?: string | null;
+            exclude_lectores: boolean;
+            exclude_monaguillos: boolean;
+            first_communion: boolean;
+            first_name: string;
+            id: string;
+            last_name: string;
+            /** Format: int32 */
+            max_consecutive_weeks?: number | null;
+            /** Format: int32 */
+            min_rest_days?: number | null;
+            notes?: string | null;
+            parent_name?: string | null;
+            phone?: string | null;
+            photo_consent: boolean;
+            photo_url?: string | null;
+            /** Format: int32 */
+            preference_level?: number | null;
+            preferred_frequency?: string | null;
+            scheduling_notes?: string | null;
+            /** Format: date-time */
+            updated_at?: string | null;
+        };
+        PersonHistoryEntry: {
+            job_id: string;
+            job_name: string;
+            /** Format: int32 */
+            position?: number | null;
+            position_name?: string | null;
+            /** Format: date */
+            service_date: string;
+        };
+        PersonJobBlackout: {
+            /** Format: date-time */
+            created_at?: string | null;
+            id: string;
+            job_id: string;
+            months: number[];
+            person_id: string;
+            reason?: string | null;
+        };
+        PersonJobExclusion: {
+            /** Format: date-time */
+            created_at?: string | null;
+            id: string;
+            job_id: string;
+            person_id: string;
+            reason?: string | null;
+        };
+        /** @description One of a person's slots in any schedule, draft or published */
+        PersonScheduleAssignment: {
+            assignment_id: string;
+            job_id: string;
+            job_name: string;
+            /** @description Whether the person has been told: their job is published in that schedule */
+            job_published: boolean;
+            notes?: string | null;
+            /** Format: int32 */
+            position?: number | null;
+            position_name?: string | null;
+            schedule_id: string;
+            schedule_name: string;
+            schedule_status: string;
+            /** Format: date */
+            service_date: string;
+        };
+        /** @description Activity summary shown in the people table */
+        PersonStats: {
+            /** Format: int64 */
+            assignments_this_year: number;
+            /** Format: date */
+            last_served_date?: string | null;
+            /** Format: int64 */
+            upcoming_assignments: number;
+        };
+        PersonWithCredentials: components["schemas"]["Person"] & {
+            generated_password?: string | null;
+            invitation?: null | components["schemas"]["IssuedInvitation"];
+            job_ids: string[];
+            username?: string | null;
+        };
+        PersonWithJobs: components["schemas"]["Person"] & {
+            /** @description Jobs the person is qualified for but must not be scheduled in */
+            excluded_job_ids: string[];
+            job_ids: string[];
+            stats?: null | components["schemas"]["PersonStats"];
+            username?: string | null;
+        };
+        /** @description The VAPID public key browsers subscribe with; None when push is not set up */
+        PushPublicKey: {
+            public_key?: string | null;
+        };
+        PushSubscriptionKeys: {
+            auth: string;
+            p256dh: string;
+        };
+        /** @description A browser's push subscription, as `PushSubscription.toJSON()` gives it */
+        PushSubscriptionRequest: {
+            endpoint: string;
+            keys: components["schemas"]["PushSubscriptionKeys"];
+        };
+        /** @description A servidor's place in the year-end recognition ranking */
+        RecognitionEntry: {
+            /** Format: int64 */
+            lifetime_services: number;
+            milestones_reached: number[];
+            /** Format: int64 */
+            next_milestone?: number | null;
+            person_id: string;
+            person_name: string;
+            /** Format: int64 */
+            rank: number;
+            /** Format: int64 */
+            services_in_year: number;
+        };
+        /** @description Every person the desktop app still has, to compare against cloud accounts */
+        ReconcileUsersRequest: {
+            person_ids: string[];
+        };
+        /** @description Shown once; only their hashes are kept */
+        RecoveryCodes: {
+            recovery_codes: string[];
+        };
+        RecruitingNeed: {
+            /** Format: int64 */
+            available_people: number;
+            /** Format: int64 */
+            gap: number;
+            job_id: string;
+            job_name: string;
+            /** Format: int32 */
+            month: number;
+            /** Format: int64 */
+            qualified_people: number;
+            /** Format: int64 */
+            recruits_needed: number;
+            /** Format: int64 */
+            required_slots: number;
+            /** Format: double */
+            sustainable_slots: number;
+            /** Format: int32 */
+            year: number;
+        };
+        RefreshRequest: {
+            refresh_token: string;
+        };
+        RegenerateScheduleRequest: {
+            /** @description "future" (default): keep dates before today as served; "all": recompute every date */
+            mode?: string | null;
+        };
+        /** @description Someone who could take an open slot, best first */
+        ReplacementCandidate: {
+            /** Format: int64 */
+            fairness_score: number;
+            person_id: string;
+            person_name: string;
+        };
+        /** @description How many sessions a sign-out everywhere ended */
+        RevokedSessions: {
+            /** Format: int64 */
+            revoked: number;
+        };
+        RotationStatus: {
+            /** Format: int32 */
+            cycle: number;
+            done_positions: number[];
+            pending_positions: number[];
+            person_id: string;
+            person_name: string;
+        };
+        Schedule: {
+            /** Format: date-time */
+            created_at?: string | null;
+            /** Format: date */
+            end_date?: string | null;
+            id: string;
+            /** Format: int32 */
+            month: number;
+            name: string;
+            notes?: string | null;
+            /** Format: date-time */
+            published_at?: string | null;
+            /** @description Shared by the schedules generated together from one date range */
+            series_id?: string | null;
+            /** Format: date */
+            start_date?: string | null;
+            status: string;
+            /** Format: date-time */
+            updated_at?: string | null;
+            /** Format: int32 */
+            year: number;
+        };
+        /** @description One change to a slot of the schedule, for the audit trail */
+        ScheduleAuditEntry: {
+            action: string;
+            assignment_id: string;
+            changed_by?: string | null;
+            /** Format: date-time */
+            created_at: string;
+            id: string;
+            job_id: string;
+            job_name: string;
+            person_id?: string | null;
+            person_name?: string | null;
+            /** Format: int32 */
+            position?: number | null;
+            position_name?: string | null;
+            previous_person_id?: string | null;
+            previous_person_name?: string | null;
+            reason?: string | null;
+            /** Format: date */
+            service_date: string;
+        };
+        ScheduleConflict: {
+            affected_person_ids: string[];
+            conflict_type: string;
+            job_id: string;
+            message: string;
+            /** Format: date */
+            service_date: string;
+        };
+        /**
+         * @description What changed between two versions of a schedule. Slots that were filled and are now empty
+         *     are `removed`, slots filled since are `added`.
+         */
+        ScheduleDiff: {
+            added: components["schemas"]["SnapshotAssignment"][];
+            changed: components["schemas"]["AssignmentChange"][];
+            /** Format: int32 */
+            from_version: number;
+            removed: components["schemas"]["SnapshotAssignment"][];
+            /**
+             * Format: int32
+             * @description None when compared against the schedule as it is now
+             */
+            to_version?: number | null;
+        };
+        /**
+         * @description Publishing state of one job within a schedule. While the schedule is a draft each job is
+         *     published on its own; afterwards the schedule's status applies to all of them.
+         */
+        ScheduleJobStatus: {
+            /** Format: int64 */
+            assignments: number;
+            job_color?: string | null;
+            job_id: string;
+            job_name: string;
+            /** Format: date-time */
+            published_at?: string | null;
+            /** @description DRAFT, PUBLISHED or ARCHIVED */
+            status: string;
+        };
+        /** @description A published version of a schedule */
+        ScheduleSnapshot: {
+            /** Format: int64 */
+            assignments: number;
+            /** Format: date-time */
+            created_at: string;
+            created_by: string;
+            /** Format: int32 */
+            version: number;
+        };
+        /** @description A schedule status change: who made it and why */
+        ScheduleStatusEvent: {
+            changed_by: string;
+            /** Format: date-time */
+            created_at: string;
+            from_status: string;
+            id: string;
+            /**
+             * Format: int32
+             * @description People sent a retraction notice
+             */
+            notified: number;
+            reason?: string | null;
+            to_status: string;
+        };
+        ScheduleWithDates: components["schemas"]["Schedule"] & {
+            conflicts?: components["schemas"]["ScheduleConflict"][];
+            service_dates: components["schemas"]["ServiceDateWithAssignments"][];
+        };
+        ServiceDate: {
+            cancel_reason?: string | null;
+            /**
+             * Format: date-time
+             * @description Set when the date was called off; it keeps no assignments
+             */
+            cancelled_at?: string | null;
+            cancelled_by?: string | null;
+            /** Format: date-time */
+            created_at?: string | null;
+            id: string;
+            notes?: string | null;
+            schedule_id: string;
+            /** Format: date */
+            service_date: string;
+        };
+        ServiceDateWithAssignments: components["schemas"]["ServiceDate"] & {
+            assignments: components["schemas"]["AssignmentWithDetails"][];
+        };
+        /** @description A servidor's reminder of a service date they are assigned to */
+        ServiceReminder: {
+            /** @description "Lectores (Salmo)", one per published assignment that day */
+            jobs: string[];
+            message: string;
+            person_id: string;
+            person_name: string;
+            /** Format: date-time */
+            sent_at?: string | null;
+            /** Format: date */
+            service_date: string;
+            service_date_id: string;
+        };
+        SetApiKeyScopesRequest: {
+            scopes: string[];
+        };
+        SetGuardianChildrenRequest: {
+            person_ids: string[];
+        };
+        /** @description Jobs a user coordinates; an empty list turns a coordinator back into a servidor */
+        SetJobScopeRequest: {
+            job_ids: string[];
+        };
+        /** @description Body of `PUT /my-availability/{date}` */
+        SetMyAvailability: {
+            available: boolean;
+            reason?: string | null;
+        };
+        SetStaffingOverride: {
+            job_id: string;
+            /** Format: int32 */
+            people_required: number;
+            reason?: string | null;
+            /** Format: date */
+            service_date: string;
+        };
+        /** @description Sign-in email of a user; null clears it */
+        SetUserEmailRequest: {
+            email?: string | null;
+        };
+        SetWhatsappTemplateRequest: {
+            language?: string | null;
+            name: string;
+        };
+        /** @description A tab of a shared Google Sheet that published assignments are written into */
+        SheetExport: {
+            /** Format: date-time */
+            created_at: string;
+            created_by: string;
+            id: string;
+            /** @description None = every job */
+            job_id?: string | null;
+            /** @description Why the last write failed; cleared by the next one that succeeds */
+            last_error?: string | null;
+            /** Format: date-time */
+            last_pushed_at?: string | null;
+            name: string;
+            sheet_name: string;
+            spreadsheet_id: string;
+        };
+        /** @description One published slot as it appears in the sheet */
+        SheetRow: {
+            job_name: string;
+            notes?: string | null;
+            person_name?: string | null;
+            /** Format: int32 */
+            position?: number | null;
+            position_name?: string | null;
+            /** Format: date */
+            service_date: string;
+            service_date_notes?: string | null;
+        };
+        SiblingGroup: {
+            /** Format: date-time */
+            created_at?: string | null;
+            id: string;
+            name: string;
+            pairing_rule: string;
+            pairing_scope: string;
+            /** Format: date-time */
+            updated_at?: string | null;
+        };
+        /** @description Sibling groups sharing at least one member, usually one family imported more than once */
+        SiblingGroupDuplicate: {
+            conflicting_rules: boolean;
+            groups: components["schemas"]["SiblingGroupWithMembers"][];
+            shared_member_ids: string[];
+        };
+        SiblingGroupMergeResult: components["schemas"]["SiblingGroupWithMembers"] & {
+            /** @description Upcoming assignments that break the merged group's SEPARATE rule */
+            conflicts: components["schemas"]["ScheduleConflict"][];
+        };
+        SiblingGroupWithMembers: components["schemas"]["SiblingGroup"] & {
+            member_ids: string[];
+        };
+        SignageDate: {
+            cancelled: boolean;
+            /** Format: date */
+            date: string;
+            jobs: components["schemas"]["SignageJob"][];
+            /** @description "HH:MM" from the mass_time setting; None when not configured */
+            mass_time?: string | null;
+            notes?: string | null;
+        };
+        SignageJob: {
+            color?: string | null;
+            id: string;
+            name: string;
+            positions: components["schemas"]["SignagePosition"][];
+        };
+        SignagePosition: {
+            name?: string | null;
+            notes?: string | null;
+            /** Format: int32 */
+            number?: number | null;
+            /** @description None for a slot nobody fills yet */
+            person?: string | null;
+        };
+        SignageSchedule: {
+            dates: components["schemas"]["SignageDate"][];
+            /** Format: int32 */
+            month: number;
+            name: string;
+            schedule_id: string;
+            /** Format: int32 */
+            version: number;
+            /** Format: int32 */
+            year: number;
+        };
+        /**
+         * @description Someone qualified for an empty slot, with the rules that keep them out of it
+         *     (`blocked_by` is empty when they can take it as is)
+         */
+        SlotCandidate: {
+            blocked_by: string[];
+            /** Format: int64 */
+            fairness_score: number;
+            person_id: string;
+            person_name: string;
+        };
+        /** @description One slot as it was in a snapshot (or is now, for diffs against the live schedule) */
+        SnapshotAssignment: {
+            job_id: string;
+            job_name: string;
+            person_id?: string | null;
+            person_name?: string | null;
+            /** Format: int32 */
+            position?: number | null;
+            position_name?: string | null;
+            /** Format: date */
+            service_date: string;
+        };
+        StaffingOverride: {
+            /** Format: date-time */
+            created_at?: string | null;
+            id: string;
+            job_id: string;
+            /** Format: int32 */
+            people_required: number;
+            reason?: string | null;
+            /** Format: date */
+            service_date: string;
+        };
+        SwapAssignmentsRequest: {
+            assignment_id_1: string;
+            assignment_id_2: string;
+        };
+        /** @description Another filled slot in the same schedule whose person could trade places with the selected one */
+        SwapSuggestion: {
+            assignment_id: string;
+            /**
+             * Format: int64
+             * @description How much the two people's combined fairness score drops with the swap (0 = no change)
+             */
+            fairness_gain: number;
+            job_id: string;
+            job_name: string;
+            person_id: string;
+            person_name: string;
+            position_name?: string | null;
+            /** Format: date */
+            service_date: string;
+        };
+        /** @description People deleted in the desktop app since the last sync */
+        SyncDeletedPeopleRequest: {
+            person_ids: string[];
+        };
+        SyncDeletedPeopleResponse: {
+            deleted_people: string[];
+            /** @description Usernames of the accounts removed with their person */
+            deleted_users: string[];
+            /** @description Ids the cloud did not have (already deleted or never synced) */
+            not_found: string[];
+        };
+        Team: {
+            active: boolean;
+            /** Format: date-time */
+            created_at?: string | null;
+            id: string;
+            job_id: string;
+            name: string;
+            /** Format: date-time */
+            updated_at?: string | null;
+        };
+        TeamWithMembers: components["schemas"]["Team"] & {
+            member_ids: string[];
+        };
+        /** @description A code from the authenticator app; where noted, a recovery code works too */
+        TwoFactorCodeRequest: {
+            code: string;
+        };
+        /** @description A new secret for the authenticator app, to scan as a QR code or type in */
+        TwoFactorSetup: {
+            otpauth_uri: string;
+            secret: string;
+        };
+        TwoFactorStatus: {
+            enabled: boolean;
+            /** Format: date-time */
+            enabled_at?: string | null;
+            /** Format: int64 */
+            recovery_codes_left: number;
+        };
+        Unavailability: {
+            /** Format: date-time */
+            created_at?: string | null;
+            /** Format: date */
+            end_date: string;
+            id: string;
+            /** @description Upcoming dates a recurring record covers, filled in by the list endpoints */
+            occurrences?: string[];
+            person_id: string;
+            reason?: string | null;
+            /** @description RRULE-like pattern repeated inside start_date..end_date (see services::recurrence) */
+            recurrence?: string | null;
+            recurring?: boolean | null;
+            /** Format: date */
+            start_date: string;
+        };
+        UnavailabilityWithPerson: components["schemas"]["Unavailability"] & {
+            person_name: string;
+        };
+        UnpublishScheduleRequest: {
+            /** @description Tell everyone assigned in the schedule that its dates may change */
+            notify?: boolean | null;
+            reason: string;
+        };
+        /** @description `notes: null` (or blank) removes the note */
+        UpdateAssignmentNotesRequest: {
+            notes?: string | null;
+        };
+        UpdateAssignmentRequest: {
+            expected_person_id?: string | null;
+            override_reason?: string | null;
+            person_id: string;
+        };
+        UpdateJobNoviceLimit: {
+            /** Format: int32 */
+            max_novices_per_date?: number | null;
+            /** Format: int32 */
+            novice_threshold?: number | null;
+        };
+        UpdateJobRequirements: {
+            requires_first_communion?: boolean | null;
+            requires_mentor?: boolean | null;
+        };
+        UpdateNotificationPreferences: {
+            channel?: string | null;
+            digest?: boolean;
+            /** Format: int32 */
+            reminder_days_before?: number | null;
+        };
+        UpdatePairingPreference: {
+            notes?: string | null;
+            preference?: string | null;
+        };
+        UpdatePerson: {
+            active?: boolean | null;
+            address?: string | null;
+            /** Format: date */
+            birth_date?: string | null;
+            email?: string | null;
+            exclude_lectores?: boolean | null;
+            exclude_monaguillos?: boolean | null;
+            first_communion?: boolean | null;
+            first_name?: string | null;
+            job_ids?: string[] | null;
+            last_name?: string | null;
+            /** Format: int32 */
+            max_consecutive_weeks?: number | null;
+            /** Format: int32 */
+            min_rest_days?: number | null;
+            notes?: string | null;
+            parent_name?: string | null;
+            phone?: string | null;
+            photo_consent?: boolean | null;
+            /** Format: int32 */
+            preference_level?: number | null;
+            preferred_frequency?: string | null;
+            scheduling_notes?: string | null;
+        };
+        /** @description Fields left out are not changed; an empty `notes` clears them */
+        UpdateScheduleRequest: {
+            name?: string | null;
+            notes?: string | null;
+            /** @description DRAFT, PUBLISHED or ARCHIVED */
+            status?: string | null;
+        };
+        /** @description `notes: null` (or blank) removes the note */
+        UpdateServiceDateNotesRequest: {
+            notes?: string | null;
+        };
+        UpdateSetting: {
+            value: string;
+        };
+        UploadPhotoRequest: {
+            photo_data: string;
+        };
+        /** @description A device a user is signed in on */
+        UserSession: {
+            /** Format: date-time */
+            created_at: string;
+            /** @description The session this request was made with */
+            current: boolean;
+            /** @description User agent at login */
+            device?: string | null;
+            /** Format: date-time */
+            expires_at: string;
+            id: string;
+            ip?: string | null;
+            /** Format: date-time */
+            last_used_at: string;
+        };
+        VCardImportResult: {
+            created: components["schemas"]["Person"][];
+            skipped: components["schemas"]["VCardImportSkipped"][];
+            updated: components["schemas"]["Person"][];
+        };
+        VCardImportSkipped: {
+            name: string;
+            reason: string;
+        };
+        /** @description The approved WhatsApp template a kind of notification is sent in */
+        WhatsappTemplate: {
+            kind: string;
+            language: string;
+            name: string;
+            /** Format: date-time */
+            updated_at: string;
+        };
+    };
+    responses: never;
+    parameters: never;
+    requestBodies: never;
+    headers: never;
+    pathItems: never;
+}
+export type $defs = Record<string, never>;
+export interface operations {
+    api_keys_get_all: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiKey"][];
+                };
+            };
+        };
+    };
+    api_keys_create: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateApiKeyRequest"];
+            };
+        };
+        responses: {
+            201: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["CreatedApiKey"];
+                };
+            };
+        };
+    };
+    api_keys_set_scopes: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["SetApiKeyScopesRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ApiKey"];
+                };
+            };
+        };
+    };
+    api_keys_revoke: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    auth_events_get_all: {
+        parameters: {
+            query?: {
+                /** @description e.g. login_failed */
+                event?: string;
+                /** @description As typed at the login, or the account's */
+                username?: string;
+                ip?: string;
+                /** @description Only events at or after this time */
+                since?: string;
+                limit?: number;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AuthEventEntry"][];
+                };
+            };
+        };
+    };
+    background_jobs_list: {
+        parameters: {
+            query?: {
+                /** @description queued, running, done or failed */
+                status?: string;
+                limit?: number;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["BackgroundJob"][];
+                };
+            };
+        };
+    };
+    background_jobs_create: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateBackgroundJobRequest"];
+            };
+        };
+        responses: {
+            202: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["BackgroundJob"];
+                };
+            };
+        };
+    };
+    background_jobs_run: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["BackgroundJob"][];
+                };
+            };
+        };
+    };
+    background_jobs_retry: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["BackgroundJob"];
+                };
+            };
+        };
+    };
+    admin_bootstrap: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["BootstrapRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["BootstrapResponse"];
+                };
+            };
+        };
+    };
+    admin_get_coordinators: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["CoordinatorScope"][];
+                };
+            };
+        };
+    };
+    guardians_get_all: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["GuardianAccount"][];
+                };
+            };
+        };
+    };
+    guardians_create: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateGuardianRequest"];
+            };
+        };
+        responses: {
+            201: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["GuardianWithCredentials"];
+                };
+            };
+        };
+    };
+    guardians_delete: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    guardians_set_children: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["SetGuardianChildrenRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["GuardianAccount"];
+                };
+            };
+        };
+    };
+    notifications_get_failures: {
+        parameters: {
+            query?: {
+                include_resolved?: boolean;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["NotificationFailure"][];
+                };
+            };
+        };
+    };
+    notifications_retry_failure: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["NotificationFailure"];
+                };
+            };
+        };
+    };
+    notifications_send_digests: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["DigestRun"];
+                };
+            };
+        };
+    };
+    reminders_preview: {
+        parameters: {
+            query?: {
+                /** @description Service date to remind; without it, the reminders due today (each person's lead time) */
+                date?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ServiceReminder"][];
+                };
+            };
+        };
+    };
+    reminders_send: {
+        parameters: {
+            query?: {
+                /** @description Service date to remind; without it, the reminders due today (each person's lead time) */
+                date?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ServiceReminder"][];
+                };
+            };
+        };
+    };
+    sheets_get_exports: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SheetExport"][];
+                };
+            };
+        };
+    };
+    sheets_create_export: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateSheetExportRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SheetExport"];
+                };
+            };
+        };
+    };
+    sheets_delete_export: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    sheets_push_export: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SheetExport"];
+                };
+            };
+        };
+    };
+    sync_apply_deleted_people: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["SyncDeletedPeopleRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SyncDeletedPeopleResponse"];
+                };
+            };
+        };
+    };
+    sync_reconcile_users: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["ReconcileUsersRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["OrphanUser"][];
+                };
+            };
+        };
+    };
+    oidc_set_user_email: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["SetUserEmailRequest"];
+            };
+        };
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    admin_set_job_scope: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["SetJobScopeRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["CoordinatorScope"];
+                };
+            };
+        };
+    };
+    sessions_get_for_user: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["UserSession"][];
+                };
+            };
+        };
+    };
+    sessions_revoke_all_for_user: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["RevokedSessions"];
+                };
+            };
+        };
+    };
+    sessions_revoke_for_user: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+                session_id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    two_factor_reset_for_user: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    notifications_get_whatsapp_templates: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["WhatsappTemplate"][];
+                };
+            };
+        };
+    };
+    notifications_set_whatsapp_template: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                kind: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["SetWhatsappTemplateRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["WhatsappTemplate"];
+                };
+            };
+        };
+    };
+    notifications_delete_whatsapp_template: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                kind: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    schedules_swap_assignments: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["SwapAssignmentsRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AssignmentWithDetails"][];
+                };
+            };
+        };
+    };
+    schedules_update_assignment: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateAssignmentRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AssignmentWithDetails"];
+                };
+            };
+        };
+    };
+    schedules_clear_assignment: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AssignmentWithDetails"];
+                };
+            };
+        };
+    };
+    schedules_move_assignment: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["MoveAssignmentRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AssignmentWithDetails"][];
+                };
+            };
+        };
+    };
+    schedules_update_assignment_notes: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateAssignmentNotesRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AssignmentWithDetails"];
+                };
+            };
+        };
+    };
+    schedules_get_swap_suggestions: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SwapSuggestion"][];
+                };
+            };
+        };
+    };
+    auth_change_password: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["ChangePasswordRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": unknown;
+                };
+            };
+        };
+    };
+    sessions_logout: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    auth_me: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["CurrentUser"];
+                };
+            };
+        };
+    };
+    sessions_get_mine: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["UserSession"][];
+                };
+            };
+        };
+    };
+    sessions_revoke_all_mine: {
+        parameters: {
+            query?: {
+                /** @description Stay signed in on the device making the request */
+                keep_current?: boolean;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["RevokedSessions"];
+                };
+            };
+        };
+    };
+    sessions_revoke_mine: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    two_factor_get_status: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["TwoFactorStatus"];
+                };
+            };
+        };
+    };
+    two_factor_disable: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["TwoFactorCodeRequest"];
+            };
+        };
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    two_factor_enable: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["TwoFactorCodeRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["RecoveryCodes"];
+                };
+            };
+        };
+    };
+    two_factor_regenerate_recovery_codes: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["TwoFactorCodeRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["RecoveryCodes"];
+                };
+            };
+        };
+    };
+    two_factor_setup: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["TwoFactorSetup"];
+                };
+            };
+        };
+    };
+    background_jobs_get_one: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["BackgroundJob"];
+                };
+            };
+        };
+    };
+    unavailability_delete_blackout: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    people_delete_exclusion: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    jobs_get_all: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Job"][];
+                };
+            };
+        };
+    };
+    jobs_update_novice_limit: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateJobNoviceLimit"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Job"];
+                };
+            };
+        };
+    };
+    jobs_get_positions: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["JobPosition"][];
+                };
+            };
+        };
+    };
+    jobs_update_requirements: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateJobRequirements"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Job"];
+                };
+            };
+        };
+    };
+    jobs_get_rotation: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["RotationStatus"][];
+                };
+            };
+        };
+    };
+    schedules_get_my_assignments: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                person_id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["MyAssignment"][];
+                };
+            };
+        };
+    };
+    unavailability_set_my_availability: {
+        parameters: {
+            query?: {
+                person_id?: string;
+            };
+            header?: never;
+            path: {
+                date: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["SetMyAvailability"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["MyAvailability"];
+                };
+            };
+        };
+    };
+    schedules_get_my_calendar: {
+        parameters: {
+            query?: {
+                /** @description YYYY-MM, defaults to the current month */
+                month?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["CalendarDay"][];
+                };
+            };
+        };
+    };
+    guardians_get_my_children: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["GuardianChild"][];
+                };
+            };
+        };
+    };
+    schedules_get_my_family_assignments: {
+        parameters: {
+            query?: {
+                /** @description YYYY-MM, defaults to the current month */
+                month?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["FamilyAssignment"][];
+                };
+            };
+        };
+    };
+    notifications_get_my_preferences: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["NotificationPreferences"];
+                };
+            };
+        };
+    };
+    notifications_update_my_preferences: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateNotificationPreferences"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["NotificationPreferences"];
+                };
+            };
+        };
+    };
+    notifications_get_my_notifications: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Notification"][];
+                };
+            };
+        };
+    };
+    notifications_mark_read: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    people_upload_my_photo: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UploadPhotoRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": unknown;
+                };
+            };
+        };
+    };
+    people_delete_my_photo: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": unknown;
+                };
+            };
+        };
+    };
+    push_subscribe: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["PushSubscriptionRequest"];
+            };
+        };
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    push_unsubscribe: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["DeletePushSubscriptionRequest"];
+            };
+        };
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    unavailability_get_my_unavailability: {
+        parameters: {
+            query?: {
+                person_id?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Unavailability"][];
+                };
+            };
+        };
+    };
+    unavailability_create_my_unavailability: {
+        parameters: {
+            query?: {
+                person_id?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateMyUnavailability"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Unavailability"][];
+                };
+            };
+        };
+    };
+    unavailability_delete_my_unavailability: {
+        parameters: {
+            query?: {
+                person_id?: string;
+            };
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    schedules_get_next_service: {
+        parameters: {
+            query?: {
+                /** @description How many upcoming service dates to return (1-10, default 1) */
+                count?: number;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["NextServices"];
+                };
+            };
+            /** @description The client's ETag still matches */
+            304: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    pairing_preferences_get_all: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PairingPreference"][];
+                };
+            };
+        };
+    };
+    pairing_preferences_create: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreatePairingPreference"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PairingPreference"];
+                };
+            };
+        };
+    };
+    pairing_preferences_update: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdatePairingPreference"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PairingPreference"];
+                };
+            };
+        };
+    };
+    pairing_preferences_delete: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    people_get_all: {
+        parameters: {
+            query?: {
+                /** @description Comma-separated extras; currently only "stats" */
+                include?: string;
+                /** @description Matches the name, email or phone */
+                q?: string;
+                /** @description Only people qualified for this job */
+                job_id?: string;
+                active?: boolean;
+                /** @description 1-based; only used with `limit` */
+                page?: number;
+                /** @description Page size (at most 500); without it everyone matching is returned */
+                limit?: number;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PeoplePage"];
+                };
+            };
+        };
+    };
+    people_create: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreatePerson"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PersonWithCredentials"];
+                };
+            };
+        };
+    };
+    people_export_directory: {
+        parameters: {
+            query?: {
+                /** @description "csv" (default) or "xlsx" */
+                format?: string;
+                include_inactive?: boolean;
+                /**
+                 * @description Leave out people whose family has not given photo/data consent, for lists that are
+                 *     shared beyond the coordinators
+                 */
+                consented_only?: boolean;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "text/csv": string;
+                    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet": unknown;
+                };
+            };
+        };
+    };
+    people_export_vcard: {
+        parameters: {
+            query?: {
+                include_inactive?: boolean;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "text/vcard": string;
+                };
+            };
+        };
+    };
+    people_import_vcard: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["ImportVCardRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["VCardImportResult"];
+                };
+            };
+        };
+    };
+    people_get_by_id: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PersonWithJobs"];
+                };
+            };
+        };
+    };
+    people_update: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdatePerson"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PersonWithJobs"];
+                };
+            };
+        };
+    };
+    people_delete: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    schedules_get_person_assignments: {
+        parameters: {
+            query?: {
+                /** @description Past dates are left out unless this is true */
+                include_past?: boolean;
+            };
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PersonScheduleAssignment"][];
+                };
+            };
+        };
+    };
+    unavailability_get_person_blackouts: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PersonJobBlackout"][];
+                };
+            };
+        };
+    };
+    unavailability_set_person_blackout: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreatePersonJobBlackout"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PersonJobBlackout"];
+                };
+            };
+        };
+    };
+    calendar_feeds_get_feed: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": null | components["schemas"]["CalendarFeed"];
+                };
+            };
+        };
+    };
+    calendar_feeds_rotate_feed: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["CreatedCalendarFeed"];
+                };
+            };
+        };
+    };
+    calendar_feeds_delete_feed: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    schedules_export_person_calendar: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "text/calendar": string;
+                };
+            };
+        };
+    };
+    people_create_user_account: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": unknown;
+                };
+            };
+        };
+    };
+    people_get_exclusions: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PersonJobExclusion"][];
+                };
+            };
+        };
+    };
+    people_set_exclusion: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreatePersonJobExclusion"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PersonJobExclusion"];
+                };
+            };
+        };
+    };
+    invitations_get_for_person: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Invitation"];
+                };
+            };
+        };
+    };
+    invitations_create: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            201: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["IssuedInvitation"];
+                };
+            };
+        };
+    };
+    invitations_cancel: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    invitations_resend: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["IssuedInvitation"];
+                };
+            };
+        };
+    };
+    people_upload_photo: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UploadPhotoRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": unknown;
+                };
+            };
+        };
+    };
+    people_delete_photo: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": unknown;
+                };
+            };
+        };
+    };
+    people_regenerate_username: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": unknown;
+                };
+            };
+        };
+    };
+    people_reset_password: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": unknown;
+                };
+            };
+        };
+    };
+    push_get_public_key: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PushPublicKey"];
+                };
+            };
+        };
+    };
+    reports_get_birthdays: {
+        parameters: {
+            query: {
+                month: number;
+                year?: number;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["BirthdayEntry"][];
+                };
+            };
+        };
+    };
+    reports_get_fairness_scores: {
+        parameters: {
+            query: {
+                year: number;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["FairnessScore"][];
+                };
+            };
+        };
+    };
+    reports_export_fairness_workbook: {
+        parameters: {
+            query: {
+                year: number;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet": unknown;
+                };
+            };
+        };
+    };
+    reports_get_junior_roster: {
+        parameters: {
+            query?: {
+                ready_only?: boolean;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["JuniorRosterEntry"][];
+                };
+            };
+        };
+    };
+    reports_get_person_history: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["PersonHistoryEntry"][];
+                };
+            };
+        };
+    };
+    reports_get_recognition: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                year: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["RecognitionEntry"][];
+                };
+            };
+        };
+    };
+    reports_get_recognition_certificates: {
+        parameters: {
+            query?: {
+                person_id?: string;
+            };
+            header?: never;
+            path: {
+                year: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/pdf": unknown;
+                };
+            };
+        };
+    };
+    reports_get_recruiting_needs: {
+        parameters: {
+            query?: {
+                year?: number;
+                month?: number;
+                format?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["RecruitingNeed"][];
+                    "text/csv": string;
+                };
+            };
+        };
+    };
+    schedules_get_all: {
+        parameters: {
+            query?: {
+                /** @description Archived schedules are left out unless this is true or `status=ARCHIVED` is asked for */
+                include_archived?: boolean;
+                year?: number;
+                status?: string;
+                /** @description e.g. `-year,-month` (default) or `name` */
+                sort?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Schedule"][];
+                };
+            };
+        };
+    };
+    schedules_generate: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["GenerateScheduleRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleWithDates"];
+                };
+            };
+        };
+    };
+    schedules_generate_batch: {
+        parameters: {
+            query?: {
+                /** @description Queue the batch for the background worker and answer 202 with the job right away */
+                background?: boolean;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["GenerateScheduleBatchRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["BatchMonthSummary"][];
+                };
+            };
+            /** @description Queued as a background job */
+            202: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["BackgroundJob"];
+                };
+            };
+        };
+    };
+    schedules_get_double_bookings: {
+        parameters: {
+            query?: {
+                /** @description Earliest date to check; defaults to today */
+                from?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["DoubleBooking"][];
+                };
+            };
+        };
+    };
+    schedules_generate_range: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["GenerateScheduleRangeRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleWithDates"][];
+                };
+            };
+        };
+    };
+    schedules_get_by_id: {
+        parameters: {
+            query?: {
+                /** @description Only this job's assignments, for a per-job view of the schedule */
+                job_id?: string;
+            };
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleWithDates"];
+                };
+            };
+        };
+    };
+    schedules_delete: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    schedules_update: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateScheduleRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Schedule"];
+                };
+            };
+        };
+    };
+    schedules_archive: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Schedule"];
+                };
+            };
+        };
+    };
+    schedules_bulk_update_assignments: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["BulkAssignmentsRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleWithDates"];
+                };
+            };
+        };
+    };
+    schedules_get_audit: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleAuditEntry"][];
+                };
+            };
+        };
+    };
+    schedules_get_schedule_completeness: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["CompletenessResponse"];
+                };
+            };
+        };
+    };
+    schedules_get_completeness_suggestions: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["EmptySlotSuggestions"][];
+                };
+            };
+        };
+    };
+    schedules_get_diff: {
+        parameters: {
+            query?: {
+                /** @description Version to compare from; defaults to the one before `to` */
+                from?: number;
+                /**
+                 * @description Version to compare to, or "current" for the schedule as it is now; defaults to the
+                 *     latest version
+                 */
+                to?: string;
+            };
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleDiff"];
+                };
+            };
+        };
+    };
+    schedules_duplicate: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["DuplicateScheduleRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleWithDates"];
+                };
+            };
+        };
+    };
+    schedules_export_schedule: {
+        parameters: {
+            query?: {
+                /**
+                 * @description "csv", or "png" for an image to share in messaging groups; the Excel workbook is only
+                 *     built by the desktop app for now
+                 */
+                format?: string;
+                /**
+                 * @description "list" (default): one line per slot. "grid": service dates as columns and job positions
+                 *     as rows, the way the parish bulletin prints the roster. CSV only.
+                 */
+                layout?: string;
+                /** @description Limits the image to one service date; the whole month otherwise */
+                date?: string;
+            };
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "text/csv": string;
+                    "image/png": unknown;
+                };
+            };
+        };
+    };
+    schedules_get_job_statuses: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleJobStatus"][];
+                };
+            };
+        };
+    };
+    schedules_publish_job: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+                job_id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleJobStatus"];
+                };
+            };
+        };
+    };
+    schedules_unpublish_job: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+                job_id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleJobStatus"];
+                };
+            };
+        };
+    };
+    schedules_publish: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Schedule"];
+                };
+            };
+        };
+    };
+    schedules_redo: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AssignmentWithDetails"][];
+                };
+            };
+        };
+    };
+    schedules_regenerate: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["RegenerateScheduleRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleWithDates"];
+                };
+            };
+        };
+    };
+    schedules_export_signage: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SignageSchedule"];
+                };
+            };
+        };
+    };
+    schedules_get_snapshots: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleSnapshot"][];
+                };
+            };
+        };
+    };
+    schedules_get_status_history: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ScheduleStatusEvent"][];
+                };
+            };
+        };
+    };
+    schedules_export_summary: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "text/plain": string;
+                };
+            };
+        };
+    };
+    schedules_undo: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AssignmentWithDetails"][];
+                };
+            };
+        };
+    };
+    schedules_unpublish: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UnpublishScheduleRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Schedule"];
+                };
+            };
+        };
+    };
+    schedules_simulate_absence: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+                person_id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AbsenceSimulation"];
+                };
+            };
+        };
+    };
+    service_dates_cancel: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CancelServiceDateRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ServiceDate"];
+                };
+            };
+        };
+    };
+    service_dates_get_history: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AssignmentEvent"][];
+                };
+            };
+        };
+    };
+    service_dates_update_notes: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateServiceDateNotesRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ServiceDate"];
+                };
+            };
+        };
+    };
+    settings_get_all: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AppSetting"][];
+                };
+            };
+        };
+    };
+    settings_update: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                key: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["UpdateSetting"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AppSetting"];
+                };
+            };
+        };
+    };
+    sibling_groups_get_all: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SiblingGroupWithMembers"][];
+                };
+            };
+        };
+    };
+    sibling_groups_create: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateSiblingGroup"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SiblingGroupWithMembers"];
+                };
+            };
+        };
+    };
+    sibling_groups_get_duplicates: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SiblingGroupDuplicate"][];
+                };
+            };
+        };
+    };
+    sibling_groups_merge: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["MergeSiblingGroups"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SiblingGroupMergeResult"];
+                };
+            };
+        };
+    };
+    sibling_groups_update: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateSiblingGroup"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SiblingGroupWithMembers"];
+                };
+            };
+        };
+    };
+    sibling_groups_delete: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    staffing_get_all: {
+        parameters: {
+            query?: {
+                from?: string;
+                to?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["StaffingOverride"][];
+                };
+            };
+        };
+    };
+    staffing_set: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["SetStaffingOverride"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["StaffingOverride"];
+                };
+            };
+        };
+    };
+    staffing_delete: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    teams_get_all: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["TeamWithMembers"][];
+                };
+            };
+        };
+    };
+    teams_create: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateTeam"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["TeamWithMembers"];
+                };
+            };
+        };
+    };
+    teams_update: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateTeam"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["TeamWithMembers"];
+                };
+            };
+        };
+    };
+    teams_delete: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    unavailability_get_all: {
+        parameters: {
+            query?: {
+                person_id?: string;
+                /** @description Only records still running on or after this date */
+                from?: string;
+                /** @description Only records starting on or before this date */
+                to?: string;
+                /** @description e.g. `-start_date` (default) or `person,start_date` */
+                sort?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["UnavailabilityWithPerson"][];
+                };
+            };
+        };
+    };
+    unavailability_create: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["CreateUnavailability"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["UnavailabilityWithPerson"];
+                };
+            };
+        };
+    };
+    unavailability_delete: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            204: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    bot_webhook: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                token: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["BotMessage"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["BotReply"];
+                };
+            };
+        };
+    };
+    calendar_feeds_get_feed_ics: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                token: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "text/calendar": string;
+                };
+            };
+        };
+    };
+    health_health_check: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "text/plain": string;
+                };
+            };
+        };
+    };
+    api_keys_get_assignments: {
+        parameters: {
+            query?: {
+                /** @description First service date included; defaults to the start of the current month */
+                from?: string;
+                job_id?: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["SheetRow"][];
+                };
+            };
+        };
+    };
+    api_keys_get_next_service: {
+        parameters: {
+            query?: {
+                /** @description How many upcoming service dates to return (1-10, default 1) */
+                count?: number;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["NextServices"];
+                };
+            };
+        };
+    };
+    invitations_get_by_token: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                token: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["InvitationDetails"];
+                };
+            };
+        };
+    };
+    invitations_accept: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                token: string;
+            };
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["AcceptInvitationRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["LoginResponse"];
+                };
+            };
+        };
+    };
+    auth_login: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["LoginRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["LoginResponse"];
+                };
+            };
+        };
+    };
+    oidc_get_settings: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["OidcSettings"];
+                };
+            };
+        };
+    };
+    oidc_login: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["OidcLoginRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["LoginResponse"];
+                };
+            };
+        };
+    };
+    auth_refresh: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["RefreshRequest"];
+            };
+        };
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["LoginResponse"];
+                };
+            };
+        };
+    };
+}
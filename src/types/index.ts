@@ -1,3 +1,8 @@
+import type { components } from './api.gen';
+
+// Schemas of the API's OpenAPI description, generated by `npm run sdk`
+type Schemas = components['schemas'];
+
 // Person types
 export interface Person {
   id: string;
@@ -7,9 +12,12 @@ export interface Person {
   phone?: string;
   preferred_frequency: PreferredFrequency;
   max_consecutive_weeks: number;
+  min_rest_days?: number; // Minimum days between two assignments
   preference_level: number;
   active: boolean;
-  notes?: string;
+  notes?: string; // Pastoral notes (admins only)
+  scheduling_notes?: string; // e.g. "solo misa de 11" (admins only)
+  contact_bounced?: boolean; // A notification to their email or phone bounced
   exclude_monaguillos: boolean;
   exclude_lectores: boolean;
   photo_url?: string;
@@ -22,13 +30,59 @@ export interface Person {
   created_at?: string;
   updated_at?: string;
   job_ids: string[];
+  excluded_job_ids?: string[]; // Qualified but never scheduled in these jobs
   username?: string;
+  stats?: PersonStats; // Only when requested with include=stats
+}
+
+export interface PersonStats {
+  assignments_this_year: number;
+  last_served_date?: string;
+  upcoming_assignments: number;
+}
+
+// GET /people filters; without `limit` everyone matching comes back in one page
+export interface PeopleListParams {
+  q?: string; // Name, email or phone
+  job_id?: string;
+  active?: boolean;
+  page?: number;
+  limit?: number;
+  include_stats?: boolean;
+}
+
+export interface PeoplePage {
+  people: Person[];
+  total: number; // Matching people across all pages
+  page: number;
+  limit?: number;
 }
 
-// Returned when creating a new person (includes password shown once)
+// Returned when creating a new person: the login with its password (shown once), or with
+// `invite` the invitation link instead
 export interface PersonWithCredentials extends Person {
-  username: string; // Always present when creating a new servidor
-  generated_password: string;
+  username?: string;
+  generated_password?: string;
+  invitation?: IssuedInvitation;
+}
+
+export interface Invitation {
+  person_id: string;
+  created_by: string;
+  created_at: string; // When the current link was sent
+  expires_at: string;
+  accepted_at?: string;
+}
+
+export interface IssuedInvitation extends Invitation {
+  token: string; // Goes in the link; shown only once
+}
+
+// What the invitation page shows before the servidor picks a password
+export interface InvitationDetails {
+  first_name: string;
+  last_name: string;
+  expires_at: string;
 }
 
 export type PreferredFrequency = 'weekly' | 'bimonthly' | 'monthly';
@@ -40,8 +94,10 @@ export interface CreatePersonRequest {
   phone?: string;
   preferred_frequency?: PreferredFrequency;
   max_consecutive_weeks?: number;
+  min_rest_days?: number;
   preference_level?: number;
   notes?: string;
+  scheduling_notes?: string;
   job_ids: string[];
   // Additional servidor fields
   birth_date?: string;
@@ -49,6 +105,7 @@ export interface CreatePersonRequest {
   parent_name?: string;
   address?: string;
   photo_consent?: boolean;
+  invite?: boolean; // Invitation link instead of a generated password
 }
 
 export interface UpdatePersonRequest {
@@ -59,9 +116,11 @@ export interface UpdatePersonRequest {
   phone?: string;
   preferred_frequency?: PreferredFrequency;
   max_consecutive_weeks?: number;
+  min_rest_days?: number;
   preference_level?: number;
   active?: boolean;
   notes?: string;
+  scheduling_notes?: string;
   job_ids?: string[];
   exclude_monaguillos?: boolean;
   exclude_lectores?: boolean;
@@ -83,6 +142,20 @@ export interface Job {
   active: boolean;
   created_at?: string;
   updated_at?: string;
+  max_novices_per_date?: number; // At most N novices per date (no limit if unset)
+  novice_threshold?: number; // Novice = fewer than this many past assignments in the job
+  requires_first_communion?: boolean; // Only people with first communion can be scheduled
+  requires_mentor?: boolean; // A novice always serves with at least one experienced person
+}
+
+export interface UpdateJobNoviceLimitRequest {
+  max_novices_per_date?: number | null;
+  novice_threshold?: number;
+}
+
+export interface UpdateJobRequirementsRequest {
+  requires_first_communion?: boolean;
+  requires_mentor?: boolean;
 }
 
 export interface CreateJobRequest {
@@ -113,7 +186,12 @@ export interface Schedule {
   created_at?: string;
   updated_at?: string;
   published_at?: string;
+  start_date?: string; // First service date covered
+  end_date?: string; // Last service date covered
+  series_id?: string; // Shared by schedules generated together from one range
+  notes?: string;
   service_dates: ServiceDate[];
+  conflicts?: ScheduleConflict[]; // Soft rules broken by the web generator or by manual edits
 }
 
 export interface ServiceDate {
@@ -121,6 +199,9 @@ export interface ServiceDate {
   schedule_id: string;
   service_date: string;
   notes?: string;
+  cancelled_at?: string;
+  cancelled_by?: string;
+  cancel_reason?: string;
   created_at?: string;
   assignments: Assignment[];
 }
@@ -132,11 +213,24 @@ export interface Assignment {
   person_id: string | null;
   position: number;
   manual_override: boolean;
+  suggested?: boolean; // Copied from another month and not edited yet
+  notes?: string; // Instructions for whoever serves the slot
+  override_reason?: string; // Why the person was assigned despite a warning
+  created_by?: string; // Username; empty when the generator created the slot
+  updated_by?: string; // Username of the last change
   created_at?: string;
   updated_at?: string;
   person_name?: string;
   job_name?: string;
+  job_color?: string;
   position_name?: string;
+  // Schedule context, sent with assignment details
+  service_date?: string; // YYYY-MM-DD
+  schedule_id?: string;
+  schedule_year?: number;
+  schedule_month?: number;
+  person_notes?: string; // Only sent to admins
+  scheduling_notes?: string; // Only sent to admins
 }
 
 // Drag and Drop types
@@ -145,6 +239,18 @@ export interface SwapAssignmentsRequest {
   assignment_id_2: string;
 }
 
+// Applied in order in one transaction; if any fails, none is kept
+export type BulkAssignmentOperation =
+  | {
+      op: 'set';
+      assignment_id: string;
+      person_id: string;
+      expected_person_id: string | null;
+      override_reason?: string;
+    }
+  | { op: 'clear'; assignment_id: string }
+  | { op: 'swap'; assignment_id_1: string; assignment_id_2: string };
+
 export interface MoveAssignmentRequest {
   target_service_date_id: string;
   target_job_id: string;
@@ -164,15 +270,215 @@ export interface CompletenessResponse {
   empty_slots: EmptySlot[];
 }
 
+export type SlotBlockReason =
+  | 'unavailable'
+  | 'excluded'
+  | 'blacked_out'
+  | 'first_communion'
+  | 'serving_that_date'
+  | 'served_last_month'
+  | 'min_rest_days'
+  | 'max_consecutive_weeks'
+  | 'max_jobs_per_month';
+
+export interface SlotCandidate {
+  person_id: string;
+  person_name: string;
+  fairness_score: number;
+  blocked_by: SlotBlockReason[]; // Empty when the person can take the slot as is
+}
+
+export interface EmptySlotSuggestions {
+  assignment_id?: string; // Missing for positions that were never created
+  service_date: string;
+  job_id: string;
+  job_name: string;
+  position_name?: string;
+  candidates: SlotCandidate[];
+}
+
 export interface GenerateScheduleRequest {
   year: number;
   month: number;
   name?: string;
 }
 
+export interface GenerateScheduleRangeRequest {
+  start_date: string; // YYYY-MM-DD
+  end_date: string; // YYYY-MM-DD
+  mode?: 'single' | 'monthly'; // Defaults to 'monthly': one linked schedule per month
+  name?: string;
+}
+
+export interface GenerateScheduleBatchRequest {
+  start_year: number;
+  start_month: number;
+  end_year: number;
+  end_month: number; // Inclusive
+}
+
+// Fields left out are not changed; an empty notes string clears them
+// Copy a schedule's assignments onto another month (Nth Sunday to Nth Sunday)
+export interface DuplicateScheduleRequest {
+  year: number;
+  month: number;
+  name?: string; // Defaults to the schedule name pattern
+}
+
+export interface UpdateScheduleRequest {
+  name?: string;
+  notes?: string;
+  status?: ScheduleStatus;
+}
+
+export interface UnpublishScheduleRequest {
+  reason: string;
+  notify?: boolean; // Tell everyone assigned in the schedule its dates may change
+}
+
+// A schedule status change: who made it and why
+export interface ScheduleStatusEvent {
+  id: string;
+  from_status: ScheduleStatus;
+  to_status: ScheduleStatus;
+  reason?: string;
+  changed_by: string;
+  notified: number; // People sent a retraction notice
+  created_at: string;
+}
+
+// A published version of a schedule
+export interface ScheduleSnapshot {
+  version: number;
+  created_by: string;
+  created_at: string;
+  assignments: number;
+}
+
+export interface SnapshotAssignment {
+  service_date: string;
+  job_id: string;
+  job_name: string;
+  position?: number;
+  position_name?: string;
+  person_id?: string;
+  person_name?: string;
+}
+
+export interface AssignmentChange {
+  service_date: string;
+  job_id: string;
+  job_name: string;
+  position?: number;
+  position_name?: string;
+  from_person_id?: string;
+  from_person_name?: string;
+  to_person_id?: string;
+  to_person_name?: string;
+}
+
+// What changed between two published versions (to_version null = the schedule as it is now)
+export interface ScheduleDiff {
+  from_version: number;
+  to_version: number | null;
+  added: SnapshotAssignment[];
+  removed: SnapshotAssignment[];
+  changed: AssignmentChange[];
+}
+
+// Publishing state of one job in a schedule; jobs of a draft can be published one by one
+export interface ScheduleJobStatus {
+  job_id: string;
+  job_name: string;
+  job_color?: string;
+  status: ScheduleStatus;
+  published_at?: string;
+  assignments: number;
+}
+
+export interface RegenerateScheduleRequest {
+  mode?: 'future' | 'all'; // Defaults to 'future': dates before today stay as served
+}
+
+export interface BatchMonthSummary {
+  year: number;
+  month: number;
+  status: 'generated' | 'skipped'; // Skipped when the month already had a schedule
+  schedule_id?: string;
+  service_dates: number;
+  total_slots: number;
+  filled_slots: number;
+  conflicts: ScheduleConflict[];
+}
+
+export type BackgroundJobKind =
+  | 'generate_batch'
+  | 'send_reminders'
+  | 'send_digests'
+  | 'notify_published';
+
+// Long-running work queued for the server's background worker
+export interface BackgroundJob {
+  id: string;
+  kind: BackgroundJobKind;
+  payload: unknown; // The request it was queued with
+  status: 'queued' | 'running' | 'done' | 'failed';
+  attempts: number;
+  max_attempts: number;
+  run_after: string;
+  result?: unknown; // e.g. BatchMonthSummary[] for generate_batch
+  error?: string; // Last failure, kept while it is retried
+  created_by?: string;
+  created_at: string;
+  started_at?: string;
+  finished_at?: string;
+}
+
+export interface RotationStatus {
+  person_id: string;
+  person_name: string;
+  cycle: number;
+  done_positions: number[]; // Done in the current cycle
+  pending_positions: number[]; // Still owed in the current cycle
+}
+
+export type AssignmentEventAction =
+  | 'generated'
+  | 'assigned'
+  | 'substituted'
+  | 'cleared'
+  | 'swapped'
+  | 'moved'
+  | 'undone'
+  | 'redone';
+
+// One row of GET /schedules/{id}/audit, newest first
+export interface ScheduleAuditEntry extends AssignmentEvent {
+  service_date: string;
+}
+
+export interface AssignmentEvent {
+  id: string;
+  assignment_id: string;
+  job_id: string;
+  job_name: string;
+  position?: number;
+  position_name?: string;
+  action: AssignmentEventAction;
+  person_id?: string;
+  person_name?: string;
+  previous_person_id?: string;
+  previous_person_name?: string;
+  changed_by?: string; // Username; empty for the generator
+  reason?: string; // Override reason, when assigned despite a warning
+  created_at: string;
+}
+
 export interface UpdateAssignmentRequest {
   assignment_id: string;
   new_person_id: string;
+  expected_person_id: string | null; // Who was in the slot when loaded (null = empty); server answers 409 if it changed
+  override_reason?: string; // Required (422 otherwise) when the person is unavailable or a kept-apart sibling already serves
 }
 
 export interface SchedulePreview {
@@ -181,7 +487,7 @@ export interface SchedulePreview {
   fairness_scores: FairnessScore[];
 }
 
-export type ConflictType = 'insufficient_people' | 'sibling_violation' | 'consecutive_weeks_exceeded' | 'unavailable_person';
+export type ConflictType = 'insufficient_people' | 'sibling_violation' | 'consecutive_weeks_exceeded' | 'unavailable_person' | 'novice_limit_exceeded' | 'mentor_missing' | 'max_jobs_exceeded';
 
 export interface ScheduleConflict {
   service_date: string;
@@ -191,6 +497,59 @@ export interface ScheduleConflict {
   affected_person_ids: string[];
 }
 
+// A person holding slots on two service dates of the same day (usually two schedules)
+export interface DoubleBooking {
+  person_id: string;
+  person_name: string;
+  service_date: string;
+  slots: DoubleBookingSlot[];
+}
+
+export interface DoubleBookingSlot {
+  assignment_id: string;
+  schedule_id: string;
+  schedule_name: string;
+  job_id: string;
+  job_name: string;
+  position?: number;
+}
+
+// What-if: a person drops out of a draft schedule (nothing is saved)
+export interface ReplacementCandidate {
+  person_id: string;
+  person_name: string;
+  fairness_score: number; // Lower serves first
+}
+
+export interface AbsenceImpact {
+  assignment_id: string;
+  service_date: string;
+  job_id: string;
+  job_name: string;
+  position?: number;
+  position_name?: string;
+  replacements: ReplacementCandidate[];
+}
+
+export interface AbsenceSimulation {
+  schedule_id: string;
+  person_id: string;
+  person_name: string;
+  affected: AbsenceImpact[];
+  uncovered: number; // Slots nobody else can take
+}
+
+export interface SwapSuggestion {
+  assignment_id: string;
+  person_id: string;
+  person_name: string;
+  service_date: string;
+  job_id: string;
+  job_name: string;
+  position_name?: string;
+  fairness_gain: number; // How much the swap improves combined fairness (0 = no change)
+}
+
 export interface JobAssignmentCount {
   job_id: string;
   job_name: string;
@@ -207,6 +566,53 @@ export interface FairnessScore {
   fairness_score: number;
 }
 
+// Monaguillos Jr. roster with promotion readiness
+export interface JuniorRosterEntry {
+  person_id: string;
+  person_name: string;
+  birth_date?: string;
+  age?: number;
+  first_communion: boolean;
+  junior_services: number;
+  senior_services: number;
+  last_junior_service?: string;
+  qualified_as_senior: boolean;
+  ready_for_promotion: boolean;
+  missing_requirements: string[];
+}
+
+export interface BirthdayEntry {
+  person_id: string;
+  person_name: string;
+  birth_date: string;
+  birthday: string; // Birthday in the requested year
+  turning_age: number;
+  photo_url?: string;
+}
+
+export interface RecruitingNeed {
+  job_id: string;
+  job_name: string;
+  year: number;
+  month: number;
+  required_slots: number; // Sum of people_required over the month's Sundays
+  qualified_people: number;
+  available_people: number; // Qualified and not blacked out that month
+  sustainable_slots: number; // What the pool can cover without breaking rotation rules
+  gap: number;
+  recruits_needed: number;
+}
+
+export interface RecognitionEntry {
+  rank: number; // Ties share a rank
+  person_id: string;
+  person_name: string;
+  services_in_year: number;
+  lifetime_services: number; // Up to the end of the year
+  milestones_reached: number[]; // Lifetime milestones (25/50/100) crossed during the year
+  next_milestone?: number;
+}
+
 export interface PersonAssignmentDetail {
   service_date: string;
   job_name: string;
@@ -217,10 +623,14 @@ export interface PersonAssignmentDetail {
 // Sibling group types
 export type PairingRule = 'TOGETHER' | 'SEPARATE';
 
+// SAME_JOB: the rule applies within one job; SAME_DATE: across every job on the date
+export type PairingScope = 'SAME_JOB' | 'SAME_DATE';
+
 export interface SiblingGroup {
   id: string;
   name: string;
   pairing_rule: PairingRule;
+  pairing_scope: PairingScope;
   created_at?: string;
   updated_at?: string;
   member_ids: string[];
@@ -229,16 +639,133 @@ export interface SiblingGroup {
 export interface CreateSiblingGroupRequest {
   name: string;
   pairing_rule: PairingRule;
+  pairing_scope?: PairingScope;
   member_ids: string[];
 }
 
+// Groups sharing at least one member, usually one family imported more than once
+export interface SiblingGroupDuplicate {
+  groups: SiblingGroup[];
+  shared_member_ids: string[];
+  conflicting_rules: boolean; // The groups disagree on pairing rule or scope
+}
+
+export interface MergeSiblingGroupsRequest {
+  group_ids: string[]; // The first group is kept
+  name?: string;
+  pairing_rule?: PairingRule; // Required when the groups disagree
+  pairing_scope?: PairingScope;
+}
+
+export interface SiblingGroupMergeResult extends SiblingGroup {
+  conflicts: ScheduleConflict[]; // Upcoming assignments that break a SEPARATE rule
+}
+
 export interface UpdateSiblingGroupRequest {
   id: string;
   name?: string;
   pairing_rule?: PairingRule;
+  pairing_scope?: PairingScope;
   member_ids?: string[];
 }
 
+// Per-date staffing override (e.g. 6 monaguillos on a solemnity)
+export interface StaffingOverride {
+  id: string;
+  service_date: string;
+  job_id: string;
+  people_required: number;
+  reason?: string;
+  created_at?: string;
+}
+
+export interface SetStaffingOverrideRequest {
+  service_date: string;
+  job_id: string;
+  people_required: number;
+  reason?: string;
+}
+
+// Months (1-12) in which a person never serves a job
+export interface PersonJobBlackout {
+  id: string;
+  person_id: string;
+  job_id: string;
+  months: number[];
+  reason?: string;
+  created_at?: string;
+}
+
+export interface SetPersonJobBlackoutRequest {
+  job_id: string;
+  months: number[];
+  reason?: string;
+}
+
+// Job a person is never scheduled in (generalizes exclude_monaguillos / exclude_lectores)
+export interface PersonJobExclusion {
+  id: string;
+  person_id: string;
+  job_id: string;
+  reason?: string;
+  created_at?: string;
+}
+
+export interface SetPersonJobExclusionRequest {
+  job_id: string;
+  reason?: string;
+}
+
+// vCard import: duplicates (same email, phone or name) are skipped or updated
+export interface ImportVCardRequest {
+  data: string;
+  on_duplicate?: 'skip' | 'update';
+}
+
+export interface VCardImportResult {
+  created: Person[];
+  updated: Person[];
+  skipped: { name: string; reason: string }[];
+}
+
+// Fixed team rotated as a unit for one job
+export interface Team {
+  id: string;
+  name: string;
+  job_id: string;
+  active: boolean;
+  member_ids: string[];
+  created_at?: string;
+  updated_at?: string;
+}
+
+export interface CreateTeamRequest {
+  name: string;
+  job_id: string;
+  active?: boolean;
+  member_ids: string[];
+}
+
+// Prefer-with / avoid-with between any two people
+export type PairingPreferenceType = 'PREFER' | 'AVOID';
+
+export interface PairingPreference {
+  id: string;
+  person_a_id: string;
+  person_b_id: string;
+  preference: PairingPreferenceType;
+  notes?: string;
+  created_at?: string;
+  updated_at?: string;
+}
+
+export interface CreatePairingPreferenceRequest {
+  person_a_id: string;
+  person_b_id: string;
+  preference: PairingPreferenceType;
+  notes?: string;
+}
+
 // Unavailability types
 export interface Unavailability {
   id: string;
@@ -247,6 +774,10 @@ export interface Unavailability {
   end_date: string;
   reason?: string;
   recurring: boolean;
+  // RRULE-like pattern, e.g. "FREQ=WEEKLY;BYDAY=SA" or "FREQ=MONTHLY;BYDAY=1SU"
+  recurrence?: string;
+  // Next dates a recurring record covers (only sent for recurring records)
+  occurrences?: string[];
   created_at?: string;
   person_name?: string;
 }
@@ -257,6 +788,7 @@ export interface CreateUnavailabilityRequest {
   end_date: string;
   reason?: string;
   recurring?: boolean;
+  recurrence?: string;
 }
 
 export interface UpdateUnavailabilityRequest {
@@ -265,6 +797,7 @@ export interface UpdateUnavailabilityRequest {
   end_date?: string;
   reason?: string;
   recurring?: boolean;
+  recurrence?: string;
 }
 
 // Eligible person for assignment editing
@@ -277,6 +810,8 @@ export interface EligiblePerson {
   passes_consecutive_check: boolean;
   sibling_status: 'preferred' | 'neutral' | 'forbidden';
   assignments_this_year: number;
+  notes?: string;
+  scheduling_notes?: string;
   reason_if_ineligible?: string;
 }
 
@@ -285,3 +820,212 @@ export interface GetEligiblePeopleRequest {
   service_date: string;
   current_person_id?: string;
 }
+
+// Key-value app settings (scheduler tuning)
+export interface AppSetting {
+  key: string;
+  value: string;
+  description?: string;
+  updated_at: string;
+}
+
+export type StandardMinistry = 'monaguillos' | 'lectores' | 'monaguillos_jr';
+
+export interface BootstrapRequest {
+  parish_name: string;
+  ministries: StandardMinistry[];
+}
+
+export interface BootstrapResponse {
+  parish_name: string;
+  jobs: Job[];
+  jobs_created: string[]; // Empty when the parish was already set up
+  positions_created: number;
+  settings_created: string[];
+}
+
+// A coordinator account and the jobs it manages
+export interface CoordinatorScope {
+  user_id: string;
+  username: string;
+  person_id?: string;
+  job_ids: string[];
+}
+
+// A parent's login, answering for several servidores
+export interface GuardianAccount {
+  user_id: string;
+  username: string;
+  person_ids: string[];
+}
+
+export interface GuardianWithCredentials extends GuardianAccount {
+  password: string;
+}
+
+export interface GuardianChild {
+  person_id: string;
+  first_name: string;
+  last_name: string;
+  photo_url?: string;
+}
+
+// Desktop sync: people deleted locally and cloud accounts left without a person
+export interface SyncDeletedPeopleResponse {
+  deleted_people: string[];
+  deleted_users: string[]; // Usernames removed with their person
+  not_found: string[];
+}
+
+export interface OrphanUser {
+  id: string;
+  username: string;
+  person_id?: string;
+  person_name?: string;
+  reason: 'no_person' | 'deleted_locally';
+  created_at?: string;
+}
+
+// In-app notification for the logged-in servidor
+export interface Notification {
+  id: string;
+  person_id: string;
+  kind: string;
+  message: string;
+  created_at?: string;
+  read_at?: string;
+}
+
+// Notification that could not be delivered (dead-letter queue)
+export interface NotificationFailure {
+  id: string;
+  person_id: string;
+  person_name: string;
+  channel: string;
+  destination?: string;
+  kind: string;
+  message: string;
+  error: string;
+  permanent: boolean; // Bad address or number rather than a temporary outage
+  attempts: number;
+  created_at?: string;
+  last_attempt_at?: string;
+  resolved_at?: string;
+}
+
+// How a servidor wants to be told things; null = the parish default
+export type NotificationChannel = 'email' | 'sms' | 'whatsapp' | 'push' | 'in_app';
+
+export interface NotificationPreferences {
+  person_id: string;
+  channel: NotificationChannel | null;
+  reminder_days_before: number | null; // 0 = no reminders
+  digest: boolean; // one message a day instead of each right away
+  updated_at?: string;
+}
+
+// Approved WhatsApp template a kind of notification is sent in
+export interface WhatsappTemplate {
+  kind: string; // service_reminder, schedule_published, ...
+  name: string;
+  language: string;
+  updated_at: string;
+}
+
+// A sign-in or password change, from GET /admin/auth-events
+export interface AuthEventEntry {
+  id: string;
+  event:
+    | 'login'
+    | 'login_failed'
+    | 'oidc_login'
+    | 'oidc_login_failed'
+    | 'password_changed'
+    | 'password_change_failed'
+    | 'password_reset'
+    | 'two_factor_enabled'
+    | 'two_factor_disabled'
+    | 'two_factor_failed';
+  user_id?: string;
+  username?: string; // As typed for failed logins
+  detail?: string; // e.g. "Wrong password", or who reset it
+  ip?: string;
+  user_agent?: string;
+  created_at: string;
+}
+
+// A device the user is signed in on, from /auth/sessions
+export type UserSession = Schemas['UserSession'];
+
+// Two-factor state of the signed-in account, from /auth/two-factor
+export type TwoFactorStatus = Schemas['TwoFactorStatus'];
+
+// New secret for the authenticator app; otpauth_uri is what its QR code encodes
+export type TwoFactorSetup = Schemas['TwoFactorSetup'];
+
+// Whether the login page offers Google sign-in, from GET /login/oidc
+export interface OidcSettings {
+  enabled: boolean;
+  client_id?: string;
+  issuer?: string;
+  hosted_domain?: string; // Only accounts of this Workspace domain
+}
+
+// A servidor's reminder of a service date, from /admin/reminders
+export interface ServiceReminder {
+  person_id: string;
+  person_name: string;
+  service_date_id: string;
+  service_date: string;
+  jobs: string[]; // "Lectores (Salmo)"
+  message: string;
+  sent_at?: string;
+}
+
+// A tab of a shared Google Sheet the API keeps filled with the published assignments
+export interface SheetExport {
+  id: string;
+  name: string;
+  spreadsheet_id: string;
+  sheet_name: string;
+  job_id?: string; // Unset = every job
+  created_by: string;
+  created_at: string;
+  last_pushed_at?: string;
+  last_error?: string; // Why the last write failed, e.g. the sheet is not shared
+}
+
+// Read-only integration key, sent as the X-Api-Key header to /integrations/...
+export type ApiKeyScope = 'assignments:read' | 'next_service:read';
+
+export interface ApiKey {
+  id: string;
+  name: string;
+  scopes: ApiKeyScope[];
+  created_by: string;
+  created_at: string;
+  last_used_at?: string;
+  revoked_at?: string;
+}
+
+export interface CreatedApiKey extends ApiKey {
+  key: string; // "psk_<id>.<secret>", shown only once
+}
+
+// Secret calendar subscription link for one person: "<api host>/calendar/<token>.ics"
+export interface CalendarFeed {
+  person_id: string;
+  created_at: string; // Also when it was last rotated
+  last_fetched_at?: string;
+}
+
+export interface CreatedCalendarFeed extends CalendarFeed {
+  token: string; // Shown only once; only its digest is kept
+}
+
+export interface RegenerateUsernameResponse {
+  message: string;
+  username: string;
+  old_username?: string;
+  old_username_valid_until?: string;
+}